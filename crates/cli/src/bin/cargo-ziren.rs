@@ -1,7 +1,10 @@
 use anyhow::Result;
 use clap::{Parser, Subcommand};
 use zkm_cli::{
-    commands::{build::BuildCmd, new::NewCmd, vkey::VkeyCmd},
+    commands::{
+        build::BuildCmd, contracts::ContractsCmd, execute::ExecuteCmd, new::NewCmd,
+        proof::ProofCmd, prove::ProveCmd, vkey::VkeyCmd,
+    },
     ZKM_VERSION_MESSAGE,
 };
 
@@ -23,6 +26,10 @@ pub enum ProveCliCommands {
     New(NewCmd),
     Build(BuildCmd),
     Vkey(VkeyCmd),
+    Proof(ProofCmd),
+    Execute(ExecuteCmd),
+    Prove(ProveCmd),
+    Contracts(ContractsCmd),
 }
 
 fn main() -> Result<()> {
@@ -32,5 +39,9 @@ fn main() -> Result<()> {
         ProveCliCommands::New(cmd) => cmd.run(),
         ProveCliCommands::Build(cmd) => cmd.run(),
         ProveCliCommands::Vkey(cmd) => cmd.run(),
+        ProveCliCommands::Proof(cmd) => cmd.run(),
+        ProveCliCommands::Execute(cmd) => cmd.run(),
+        ProveCliCommands::Prove(cmd) => cmd.run(),
+        ProveCliCommands::Contracts(cmd) => cmd.run(),
     }
 }
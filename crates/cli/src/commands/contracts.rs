@@ -0,0 +1,80 @@
+use std::{fs::File, io::Read, path::PathBuf};
+
+use anyhow::{bail, Result};
+use clap::{Args, Parser};
+use zkm_build::{generate_elf_paths, BuildArgs};
+use zkm_sdk::{contracts::export_solidity_contracts, HashableKey, ProverClient};
+
+#[derive(Parser)]
+#[command(
+    name = "contracts",
+    about = "Export the Solidity verifier contracts and a gateway contract for a guest."
+)]
+pub struct ContractsCmd {
+    /// The guest the gateway contract verifies the proofs of.
+    #[command(flatten)]
+    guest: Guest,
+
+    /// The directory to write the contracts to.
+    #[arg(long, default_value = "contracts")]
+    output_dir: PathBuf,
+}
+
+#[derive(Debug, Clone, Args)]
+#[group(required = true, multiple = false)]
+pub struct Guest {
+    /// The path to the ELF file
+    #[arg(long = "elf")]
+    path: Option<String>,
+    /// The crate used to generate the ELF file
+    #[arg(long)]
+    program: Option<String>,
+    /// The verification key hash of the guest, as printed by `cargo ziren vkey`
+    #[arg(long)]
+    vkey: Option<String>,
+}
+
+impl ContractsCmd {
+    pub fn run(&self) -> Result<()> {
+        let vkey = match &self.guest.vkey {
+            Some(vkey) => vkey.clone(),
+            None => self.elf_vkey()?,
+        };
+
+        export_solidity_contracts(&vkey, &self.output_dir)?;
+        println!("Exported the verifier contracts for '{vkey}' to {}", self.output_dir.display());
+        Ok(())
+    }
+
+    /// Gets the verification key hash of the guest ELF.
+    fn elf_vkey(&self) -> Result<String> {
+        let elf_path = if let Some(path) = &self.guest.path {
+            path.clone()
+        } else if let Some(program) = &self.guest.program {
+            let metadata_cmd = cargo_metadata::MetadataCommand::new();
+            let metadata = metadata_cmd.exec()?;
+            let build_args = BuildArgs { packages: vec![program.clone()], ..Default::default() };
+
+            let elf_paths = generate_elf_paths(&metadata, Some(&build_args))?;
+            let [(_, path)] = elf_paths.as_slice() else {
+                bail!(
+                    "'{program}' has {} ELF targets, pass one of them with --elf",
+                    elf_paths.len()
+                );
+            };
+            path.to_string()
+        } else {
+            unreachable!()
+        };
+
+        // Read the elf file contents
+        let mut file = File::open(elf_path)?;
+        let mut elf = Vec::new();
+        file.read_to_end(&mut elf)?;
+
+        // Get the verification key
+        let prover = ProverClient::new();
+        let (_, vk) = prover.setup(&elf);
+        Ok(vk.vk.bytes32())
+    }
+}
@@ -0,0 +1,92 @@
+use std::{
+    fs::{self, File},
+    io::{BufWriter, Write},
+    path::PathBuf,
+};
+
+use anyhow::{Context, Result};
+use clap::Parser;
+use zkm_sdk::{ExecutionReport, ProverClient, ZKMPublicValues};
+
+use crate::guest::{FileArgs, GuestArgs, StdinArgs};
+
+#[derive(Parser)]
+#[command(name = "execute", about = "Execute a guest without proving it.")]
+pub struct ExecuteCmd {
+    #[command(flatten)]
+    guest: GuestArgs,
+    #[command(flatten)]
+    stdin: StdinArgs,
+    #[command(flatten)]
+    files: FileArgs,
+    /// The maximum number of cycles to execute
+    #[arg(long)]
+    max_cycles: Option<u64>,
+    /// The path to write the public values to
+    #[arg(long)]
+    public_values: Option<PathBuf>,
+    /// The path to write the JSON encoded execution report to
+    #[arg(long)]
+    report: Option<PathBuf>,
+    /// The path to write the per-instruction trace to, as text if it ends with `.txt` and in the
+    /// binary form otherwise
+    #[arg(long)]
+    trace: Option<PathBuf>,
+}
+
+impl ExecuteCmd {
+    pub fn run(&self) -> Result<()> {
+        let elf = self.files.attach(self.guest.load()?)?;
+        let stdin = self.stdin.load()?;
+
+        let client = ProverClient::new();
+        let mut execute = client.execute(&elf, &stdin);
+        if let Some(max_cycles) = self.max_cycles {
+            execute = execute.max_cycles(max_cycles);
+        }
+        if self.trace.is_some() {
+            execute = execute.trace();
+        }
+        let (public_values, mut report) = execute.run()?;
+
+        if let (Some(trace), Some(path)) = (report.trace.take(), &self.trace) {
+            let mut writer = BufWriter::new(
+                File::create(path)
+                    .with_context(|| format!("failed to write {}", path.display()))?,
+            );
+            if path.extension().is_some_and(|extension| extension == "txt") {
+                write!(writer, "{trace}")?;
+                writer.flush()?;
+            } else {
+                trace.write(writer)?;
+            }
+        }
+
+        println!("Executed {} cycles", report.total_instruction_count());
+        println!("{report}");
+        write_outputs(
+            &public_values,
+            Some(&report),
+            self.public_values.as_ref(),
+            self.report.as_ref(),
+        )
+    }
+}
+
+/// Write the public values and the execution report to the given paths.
+pub(crate) fn write_outputs(
+    public_values: &ZKMPublicValues,
+    report: Option<&ExecutionReport>,
+    public_values_path: Option<&PathBuf>,
+    report_path: Option<&PathBuf>,
+) -> Result<()> {
+    if let Some(path) = public_values_path {
+        fs::write(path, public_values.as_slice())
+            .with_context(|| format!("failed to write {}", path.display()))?;
+    }
+    if let (Some(report), Some(path)) = (report, report_path) {
+        fs::write(path, serde_json::to_vec_pretty(report)?)
+            .with_context(|| format!("failed to write {}", path.display()))?;
+    }
+    Ok(())
+}
@@ -1,3 +1,7 @@
 pub mod build;
+pub mod contracts;
+pub mod execute;
 pub mod new;
+pub mod proof;
+pub mod prove;
 pub mod vkey;
@@ -0,0 +1,204 @@
+use std::{
+    fs::{self, File},
+    io::Read,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{bail, Context, Result};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use zkm_sdk::{
+    HashableKey, ProverClient, ZKMProof, ZKMProofKind, ZKMProofWithPublicValues, ZKMVerifyingKey,
+};
+
+#[derive(Parser)]
+#[command(name = "proof", about = "Inspect, verify and convert saved proofs.")]
+pub struct ProofCmd {
+    #[command(subcommand)]
+    command: ProofCommands,
+}
+
+#[derive(Subcommand)]
+enum ProofCommands {
+    /// Print the kind, the public values, the verification key hash and the size of a proof.
+    Inspect(InspectCmd),
+    /// Verify a proof against a guest.
+    Verify(VerifyCmd),
+    /// Convert a proof between encodings.
+    Convert(ConvertCmd),
+}
+
+/// The encoding of a proof file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ProofFormat {
+    /// The bincode encoding written by `ZKMProofWithPublicValues::save`.
+    Bincode,
+    /// The JSON encoding of `ZKMProofWithPublicValues`.
+    Json,
+    /// The hex encoded bytes passed to the on-chain verifier. Output only.
+    Hex,
+}
+
+impl ProofFormat {
+    /// The format of `path` according to its extension, defaulting to bincode.
+    fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("json") => Self::Json,
+            Some("hex") => Self::Hex,
+            _ => Self::Bincode,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct InspectCmd {
+    /// The path to the proof.
+    proof: PathBuf,
+    /// The encoding of the proof, inferred from the extension by default.
+    #[arg(long)]
+    format: Option<ProofFormat>,
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct VerifyCmd {
+    /// The path to the proof.
+    proof: PathBuf,
+    /// The encoding of the proof, inferred from the extension by default.
+    #[arg(long)]
+    format: Option<ProofFormat>,
+    /// The key to verify the proof against.
+    #[command(flatten)]
+    key: Key,
+}
+
+#[derive(Debug, Clone, Args)]
+#[group(required = true, multiple = false)]
+pub struct Key {
+    /// The path to the ELF file of the guest
+    #[arg(long)]
+    elf: Option<PathBuf>,
+    /// The path to the bincode encoded verifying key of the guest
+    #[arg(long)]
+    vkey: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct ConvertCmd {
+    /// The path to the proof.
+    input: PathBuf,
+    /// The path to write the converted proof to.
+    output: PathBuf,
+    /// The encoding of the input, inferred from the extension by default.
+    #[arg(long)]
+    from: Option<ProofFormat>,
+    /// The encoding of the output, inferred from the extension by default.
+    #[arg(long)]
+    to: Option<ProofFormat>,
+}
+
+impl ProofCmd {
+    pub fn run(&self) -> Result<()> {
+        match &self.command {
+            ProofCommands::Inspect(cmd) => cmd.run(),
+            ProofCommands::Verify(cmd) => cmd.run(),
+            ProofCommands::Convert(cmd) => cmd.run(),
+        }
+    }
+}
+
+impl InspectCmd {
+    pub fn run(&self) -> Result<()> {
+        let proof = load_proof(&self.proof, self.format)?;
+
+        println!("Kind: {:?}", ZKMProofKind::from(&proof.proof));
+        println!("Version: {}", proof.zkm_version);
+        println!("Verification Key Hash: {}", proof.vkey_hash().as_deref().unwrap_or("n/a"));
+        println!(
+            "Public Values ({} bytes):\n{}",
+            proof.public_values.as_slice().len(),
+            proof.public_values.raw()
+        );
+
+        match &proof.proof {
+            ZKMProof::Core(shards) => {
+                println!("Shards: {}", shards.len());
+                for (index, shard) in shards.iter().enumerate() {
+                    println!("  Shard {index}: {} bytes", bincode::serialized_size(shard)?);
+                }
+            }
+            ZKMProof::Compressed(proof) => {
+                println!("Shards: 1");
+                println!("  Shard 0: {} bytes", bincode::serialized_size(&proof.proof)?);
+            }
+            ZKMProof::Plonk(proof) => {
+                println!("Proof: {} bytes", hex::decode(&proof.encoded_proof)?.len());
+            }
+            ZKMProof::Groth16(proof) => {
+                println!("Proof: {} bytes", hex::decode(&proof.encoded_proof)?.len());
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+}
+
+impl VerifyCmd {
+    pub fn run(&self) -> Result<()> {
+        let proof = load_proof(&self.proof, self.format)?;
+        let client = ProverClient::cpu();
+
+        let vk = if let Some(path) = &self.key.elf {
+            let mut elf = Vec::new();
+            File::open(path)?.read_to_end(&mut elf)?;
+            client.setup(&elf).1
+        } else if let Some(path) = &self.key.vkey {
+            let bytes = fs::read(path)?;
+            ZKMVerifyingKey::from_bytes(&bytes)
+                .with_context(|| format!("failed to read the verifying key {}", path.display()))?
+        } else {
+            unreachable!()
+        };
+
+        client.verify(&proof, &vk)?;
+        println!("Verified the proof against the verification key hash {}", vk.bytes32());
+
+        Ok(())
+    }
+}
+
+impl ConvertCmd {
+    pub fn run(&self) -> Result<()> {
+        let proof = load_proof(&self.input, self.from)?;
+        save_proof(&proof, &self.output, self.to)
+    }
+}
+
+/// Write a proof to `path`, encoded in `format` or according to its extension.
+pub(crate) fn save_proof(
+    proof: &ZKMProofWithPublicValues,
+    path: &Path,
+    format: Option<ProofFormat>,
+) -> Result<()> {
+    let bytes = match format.unwrap_or_else(|| ProofFormat::from_path(path)) {
+        ProofFormat::Bincode => bincode::serialize(proof)?,
+        ProofFormat::Json => serde_json::to_vec_pretty(proof)?,
+        ProofFormat::Hex => match &proof.proof {
+            ZKMProof::Compressed(_) | ZKMProof::Plonk(_) | ZKMProof::Groth16(_) => {
+                format!("0x{}", hex::encode(proof.bytes())).into_bytes()
+            }
+            _ => bail!("only compressed, plonk and groth16 proofs have a hex encoding"),
+        },
+    };
+    fs::write(path, bytes).with_context(|| format!("failed to write {}", path.display()))
+}
+
+/// Read a proof from `path`, encoded in `format` or according to its extension.
+fn load_proof(path: &Path, format: Option<ProofFormat>) -> Result<ZKMProofWithPublicValues> {
+    let bytes = fs::read(path).with_context(|| format!("failed to read {}", path.display()))?;
+    let proof = match format.unwrap_or_else(|| ProofFormat::from_path(path)) {
+        ProofFormat::Bincode => bincode::deserialize(&bytes)?,
+        ProofFormat::Json => serde_json::from_slice(&bytes)?,
+        ProofFormat::Hex => bail!("hex encoded proofs lack the public values and cannot be read"),
+    };
+    Ok(proof)
+}
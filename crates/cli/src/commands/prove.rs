@@ -0,0 +1,109 @@
+use std::{fs::File, path::PathBuf};
+
+use anyhow::{Context, Result};
+use clap::{Parser, ValueEnum};
+use zkm_sdk::{HashableKey, ProverClient};
+
+use crate::{
+    commands::{
+        execute::write_outputs,
+        proof::{save_proof, ProofFormat},
+    },
+    guest::{FileArgs, GuestArgs, StdinArgs},
+};
+
+/// The mode to prove a guest in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum ProveMode {
+    /// A proof for every shard, growing with the number of cycles.
+    #[default]
+    Core,
+    /// A single STARK proof of constant size.
+    Compressed,
+    /// A Groth16 proof, verifiable on-chain.
+    Groth16,
+    /// A Plonk proof, verifiable on-chain.
+    Plonk,
+}
+
+#[derive(Parser)]
+#[command(name = "prove", about = "Prove the execution of a guest.")]
+pub struct ProveCmd {
+    #[command(flatten)]
+    guest: GuestArgs,
+    #[command(flatten)]
+    stdin: StdinArgs,
+    #[command(flatten)]
+    files: FileArgs,
+    /// The mode to prove in
+    #[arg(long, value_enum, default_value_t)]
+    mode: ProveMode,
+    /// The maximum number of cycles to prove
+    #[arg(long)]
+    max_cycles: Option<u64>,
+    /// The path to write the proof to
+    #[arg(long, default_value = "proof.bin")]
+    output: PathBuf,
+    /// The encoding of the proof, inferred from the extension by default
+    #[arg(long)]
+    format: Option<ProofFormat>,
+    /// The path to write the bincode encoded verifying key to
+    #[arg(long)]
+    vkey: Option<PathBuf>,
+    /// The path to write the public values to
+    #[arg(long)]
+    public_values: Option<PathBuf>,
+    /// The path to write the JSON encoded execution report to, which executes the guest once more
+    #[arg(long)]
+    report: Option<PathBuf>,
+}
+
+impl ProveCmd {
+    pub fn run(&self) -> Result<()> {
+        let elf = self.files.attach(self.guest.load()?)?;
+        let stdin = self.stdin.load()?;
+
+        let client = ProverClient::new();
+        let (pk, vk) = client.setup(&elf);
+        println!("Verification Key Hash:\n{}", vk.bytes32());
+
+        // The provers do not return the report, so it is computed by a separate execution.
+        let report = if self.report.is_some() {
+            let mut execute = client.execute(&elf, &stdin);
+            if let Some(max_cycles) = self.max_cycles {
+                execute = execute.max_cycles(max_cycles);
+            }
+            Some(execute.run()?.1)
+        } else {
+            None
+        };
+
+        let mut prove = client.prove(&pk, stdin);
+        prove = match self.mode {
+            ProveMode::Core => prove.core(),
+            ProveMode::Compressed => prove.compressed(),
+            ProveMode::Groth16 => prove.groth16(),
+            ProveMode::Plonk => prove.plonk(),
+        };
+        if let Some(max_cycles) = self.max_cycles {
+            prove = prove.cycle_limit(max_cycles);
+        }
+        let proof = prove.run()?;
+
+        save_proof(&proof, &self.output, self.format)?;
+        println!("Wrote the proof to {}", self.output.display());
+        if let Some(path) = &self.vkey {
+            let file = File::create(path)
+                .with_context(|| format!("failed to write {}", path.display()))?;
+            bincode::serialize_into(file, &vk)?;
+        }
+        write_outputs(
+            &proof.public_values,
+            report.as_ref(),
+            self.public_values.as_ref(),
+            self.report.as_ref(),
+        )?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,127 @@
+use std::{collections::BTreeMap, fs, path::PathBuf};
+
+use anyhow::{bail, Context, Result};
+use clap::{Args, ValueEnum};
+use zkm_build::{execute_build_program, BuildArgs};
+use zkm_sdk::{attach_files, ZKMStdin};
+
+/// The guest to run, given as an ELF file or as a crate to build it from.
+#[derive(Debug, Clone, Args)]
+#[group(required = true, multiple = false)]
+pub struct GuestArgs {
+    /// The path to the ELF file
+    #[arg(long)]
+    elf: Option<PathBuf>,
+    /// The crate to build the ELF file from
+    #[arg(long)]
+    program: Option<String>,
+}
+
+impl GuestArgs {
+    /// Read the ELF, building the program first if needed.
+    pub fn load(&self) -> Result<Vec<u8>> {
+        let path = if let Some(path) = &self.elf {
+            path.clone()
+        } else if let Some(program) = &self.program {
+            let build_args = BuildArgs { packages: vec![program.clone()], ..Default::default() };
+            let mut elf_paths = execute_build_program(&build_args, None)?;
+            if elf_paths.len() != 1 {
+                bail!(
+                    "the program '{program}' has {} binaries, pass the one to run with --elf",
+                    elf_paths.len()
+                );
+            }
+            elf_paths.remove(0).1.into()
+        } else {
+            unreachable!()
+        };
+
+        fs::read(&path).with_context(|| format!("failed to read the ELF {}", path.display()))
+    }
+}
+
+/// The virtual files of the guest, read from the host.
+#[derive(Debug, Clone, Default, Args)]
+pub struct FileArgs {
+    /// A file the guest can open, as `<GUEST_PATH>=<HOST_PATH>`. Repeat to attach several files
+    #[arg(long = "file", value_name = "GUEST_PATH=HOST_PATH")]
+    files: Vec<String>,
+}
+
+impl FileArgs {
+    /// Attach the files to `elf`, which commits them in its verifying key.
+    pub fn attach(&self, elf: Vec<u8>) -> Result<Vec<u8>> {
+        if self.files.is_empty() {
+            return Ok(elf);
+        }
+        let mut files = BTreeMap::new();
+        for file in &self.files {
+            let Some((guest_path, host_path)) = file.split_once('=') else {
+                bail!("invalid file '{file}', expected <GUEST_PATH>=<HOST_PATH>");
+            };
+            let bytes = fs::read(host_path)
+                .with_context(|| format!("failed to read the file {host_path}"))?;
+            files.insert(guest_path.to_string(), bytes);
+        }
+        Ok(attach_files(&elf, &files))
+    }
+}
+
+/// The encoding of a stdin file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum StdinFormat {
+    /// The contents of the file, as a single entry.
+    Raw,
+    /// A bincode encoded `ZKMStdin`, as written by the host.
+    Bincode,
+    /// A JSON array of hex encoded entries.
+    Json,
+}
+
+/// The stdin of the guest, read from files.
+#[derive(Debug, Clone, Default, Args)]
+pub struct StdinArgs {
+    /// A file to write to the stdin of the guest. Repeat to write several files, in order.
+    #[arg(long = "stdin")]
+    files: Vec<PathBuf>,
+    /// The encoding of the stdin files, inferred from the extension by default: `.bin` for
+    /// bincode, `.json` for JSON and raw otherwise
+    #[arg(long)]
+    stdin_format: Option<StdinFormat>,
+}
+
+impl StdinArgs {
+    /// Read the files into a single [`ZKMStdin`], appending their entries in order.
+    pub fn load(&self) -> Result<ZKMStdin> {
+        let mut stdin = ZKMStdin::new();
+        for path in &self.files {
+            let bytes = fs::read(path)
+                .with_context(|| format!("failed to read the stdin {}", path.display()))?;
+            let format = self.stdin_format.unwrap_or_else(|| {
+                match path.extension().and_then(|extension| extension.to_str()) {
+                    Some("bin") => StdinFormat::Bincode,
+                    Some("json") => StdinFormat::Json,
+                    _ => StdinFormat::Raw,
+                }
+            });
+
+            match format {
+                StdinFormat::Raw => stdin.write_vec(bytes),
+                StdinFormat::Bincode => {
+                    let ZKMStdin { buffer, proofs, .. } = bincode::deserialize(&bytes)
+                        .with_context(|| format!("invalid bincode stdin {}", path.display()))?;
+                    buffer.into_iter().for_each(|entry| stdin.write_vec(entry));
+                    stdin.proofs.extend(proofs);
+                }
+                StdinFormat::Json => {
+                    let entries: Vec<String> = serde_json::from_slice(&bytes)
+                        .with_context(|| format!("invalid JSON stdin {}", path.display()))?;
+                    for entry in entries {
+                        stdin.write_vec(hex::decode(entry.trim_start_matches("0x"))?);
+                    }
+                }
+            }
+        }
+        Ok(stdin)
+    }
+}
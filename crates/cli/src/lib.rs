@@ -1,4 +1,5 @@
 pub mod commands;
+pub mod guest;
 
 pub const ZKM_VERSION_MESSAGE: &str =
     concat!("ZKM", " (", env!("VERGEN_GIT_SHA"), " ", env!("VERGEN_BUILD_TIMESTAMP"), ")");
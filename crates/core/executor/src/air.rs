@@ -5,6 +5,7 @@ use std::{
 
 use enum_map::Enum;
 use enum_map::EnumMap;
+use hashbrown::HashMap;
 use serde::{Deserialize, Serialize};
 use strum::{EnumIter, IntoEnumIterator};
 use zkm_stark::shape::Shape;
@@ -117,6 +118,28 @@ pub enum MipsAirId {
     MovCond = 49,
     // The BooleanCircuitGarble chip.
     BooleanCircuitGarble = 50,
+    /// The SHA-512 extend chip.
+    Sha512Extend = 51,
+    /// The SHA-512 compress chip.
+    Sha512Compress = 52,
+    /// The BLAKE3 compress inner chip.
+    Blake3CompressInner = 53,
+    /// The BLAKE2b compress inner chip.
+    Blake2bCompressInner = 54,
+    /// The bn254 fp6 mul assign chip.
+    Bn254Fp6MulAssign = 55,
+    /// The bls12-381 fp6 mul assign chip.
+    Bls12381Fp6MulAssign = 56,
+    /// The secp256k1 scalar mul assign chip.
+    Secp256k1ScalarMulAssign = 57,
+    /// The secp256r1 scalar mul assign chip.
+    Secp256r1ScalarMulAssign = 58,
+    /// The bn254 scalar mul assign chip.
+    Bn254ScalarMulAssign = 59,
+    /// The bls12-381 scalar mul assign chip.
+    Bls12381ScalarMulAssign = 60,
+    /// The SysFile chip.
+    SysFile = 61,
 }
 
 impl MipsAirId {
@@ -144,6 +167,25 @@ impl MipsAirId {
         ]
     }
 
+    /// Returns the number of trace rows a single event of the AIR takes.
+    #[must_use]
+    pub fn rows_per_event(&self) -> u64 {
+        match self {
+            Self::ShaCompress => 80,
+            Self::ShaExtend => 48,
+            Self::Sha512Compress => 96,
+            Self::Sha512Extend => 64,
+            Self::Blake3CompressInner => 56,
+            Self::Blake2bCompressInner => 96,
+            Self::KeccakSponge => 24,
+            Self::Secp256k1ScalarMulAssign
+            | Self::Secp256r1ScalarMulAssign
+            | Self::Bn254ScalarMulAssign
+            | Self::Bls12381ScalarMulAssign => 256,
+            _ => 1,
+        }
+    }
+
     /// Returns the string representation of the AIR.
     #[must_use]
     pub fn as_str(&self) -> &str {
@@ -198,6 +240,17 @@ impl MipsAirId {
             Self::SysLinux => "SysLinux",
             Self::MovCond => "MovCond",
             Self::BooleanCircuitGarble => "BooleanCircuitGarble",
+            Self::Sha512Extend => "Sha512Extend",
+            Self::Sha512Compress => "Sha512Compress",
+            Self::Blake3CompressInner => "Blake3CompressInner",
+            Self::Blake2bCompressInner => "Blake2bCompressInner",
+            Self::Bn254Fp6MulAssign => "Bn254Fp6MulAssign",
+            Self::Bls12381Fp6MulAssign => "Bls12381Fp6MulAssign",
+            Self::Secp256k1ScalarMulAssign => "Secp256k1ScalarMulAssign",
+            Self::Secp256r1ScalarMulAssign => "Secp256r1ScalarMulAssign",
+            Self::Bn254ScalarMulAssign => "Bn254ScalarMulAssign",
+            Self::Bls12381ScalarMulAssign => "Bls12381ScalarMulAssign",
+            Self::SysFile => "SysFile",
         }
     }
 }
@@ -245,4 +298,22 @@ impl MaximalShapes {
     pub fn iter(&self) -> impl Iterator<Item = &EnumMap<MipsAirId, u32>> {
         self.inner.iter()
     }
+
+    /// Returns the shape with the smallest trace area that fits the given core event counts.
+    #[must_use]
+    pub fn smallest_fit(
+        &self,
+        event_counts: &EnumMap<MipsAirId, u64>,
+        costs_per_air: &HashMap<MipsAirId, u64>,
+    ) -> Option<&EnumMap<MipsAirId, u32>> {
+        let core = MipsAirId::core();
+        self.inner
+            .iter()
+            .filter(|shape| core.iter().all(|air| event_counts[*air] <= 1 << shape[*air]))
+            .min_by_key(|shape| {
+                core.iter()
+                    .map(|air| (1 << shape[*air]) * costs_per_air.get(air).copied().unwrap_or(1))
+                    .sum::<u64>()
+            })
+    }
 }
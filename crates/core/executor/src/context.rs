@@ -1,11 +1,14 @@
 use core::mem::take;
+use std::sync::Arc;
 
 use hashbrown::HashMap;
 
 use crate::{
+    control::{CancellationToken, ProgressCallback, ProveProgress},
     hook::{hookify, BoxedHook, HookEnv, HookRegistry},
+    output::{boxed_sink, BoxedOutputSink, OutputSink},
     subproof::SubproofVerifier,
-    ExecutionError,
+    ExecutionError, ProfilerConfig,
 };
 
 /// Context to run a program inside Ziren.
@@ -24,6 +27,31 @@ pub struct ZKMContext<'a> {
 
     /// Skip deferred proof verification.
     pub skip_deferred_proof_verification: bool,
+
+    /// The configuration of the call-stack profiler, if profiling is enabled.
+    pub profiler: Option<ProfilerConfig>,
+
+    /// Estimate the proving cost of the execution.
+    pub estimate_cost: bool,
+
+    /// Record the per-instruction trace of the execution.
+    pub trace: bool,
+
+    /// The sink of the guest stdout.
+    ///
+    /// Note: `None` denotes printing to the host stdout.
+    pub stdout: Option<BoxedOutputSink<'a>>,
+
+    /// The sink of the guest stderr.
+    ///
+    /// Note: `None` denotes printing to the host stdout.
+    pub stderr: Option<BoxedOutputSink<'a>>,
+
+    /// The token to cancel the execution, and the proof of it, with.
+    pub cancellation_token: Option<CancellationToken>,
+
+    /// The callback invoked with the progress of the proof.
+    pub progress: Option<ProgressCallback<'a>>,
 }
 
 /// A builder for [`ZKMContext`].
@@ -34,6 +62,13 @@ pub struct ZKMContextBuilder<'a> {
     subproof_verifier: Option<&'a dyn SubproofVerifier>,
     max_cycles: Option<u64>,
     skip_deferred_proof_verification: bool,
+    profiler: Option<ProfilerConfig>,
+    estimate_cost: bool,
+    trace: bool,
+    stdout: Option<BoxedOutputSink<'a>>,
+    stderr: Option<BoxedOutputSink<'a>>,
+    cancellation_token: Option<CancellationToken>,
+    progress: Option<ProgressCallback<'a>>,
 }
 
 impl<'a> ZKMContext<'a> {
@@ -42,6 +77,33 @@ impl<'a> ZKMContext<'a> {
     pub fn builder() -> ZKMContextBuilder<'a> {
         ZKMContextBuilder::new()
     }
+
+    /// Shorten the lifetime of the context.
+    ///
+    /// The hooks and the output sinks are behind locks, which makes the context invariant over
+    /// `'a`: this is needed to run, for instance, a `'static` context on a borrowed prover.
+    #[must_use]
+    pub fn shorten<'b>(self) -> ZKMContext<'b>
+    where
+        'a: 'b,
+    {
+        let hook_registry = self.hook_registry.map(|registry| HookRegistry {
+            table: registry.table.into_iter().map(|(fd, hook)| (fd, hook as _)).collect(),
+        });
+        ZKMContext {
+            hook_registry,
+            subproof_verifier: self.subproof_verifier,
+            max_cycles: self.max_cycles,
+            skip_deferred_proof_verification: self.skip_deferred_proof_verification,
+            profiler: self.profiler,
+            estimate_cost: self.estimate_cost,
+            trace: self.trace,
+            stdout: self.stdout.map(|sink| sink as _),
+            stderr: self.stderr.map(|sink| sink as _),
+            cancellation_token: self.cancellation_token,
+            progress: self.progress.map(|progress| progress as _),
+        }
+    }
 }
 
 impl<'a> ZKMContextBuilder<'a> {
@@ -73,11 +135,25 @@ impl<'a> ZKMContextBuilder<'a> {
         let subproof_verifier = take(&mut self.subproof_verifier);
         let cycle_limit = take(&mut self.max_cycles);
         let skip_deferred_proof_verification = take(&mut self.skip_deferred_proof_verification);
+        let profiler = take(&mut self.profiler);
+        let estimate_cost = take(&mut self.estimate_cost);
+        let trace = take(&mut self.trace);
+        let stdout = take(&mut self.stdout);
+        let stderr = take(&mut self.stderr);
+        let cancellation_token = take(&mut self.cancellation_token);
+        let progress = take(&mut self.progress);
         ZKMContext {
             hook_registry,
             subproof_verifier,
             max_cycles: cycle_limit,
             skip_deferred_proof_verification,
+            profiler,
+            estimate_cost,
+            trace,
+            stdout,
+            stderr,
+            cancellation_token,
+            progress,
         }
     }
 
@@ -123,11 +199,70 @@ impl<'a> ZKMContextBuilder<'a> {
         self.skip_deferred_proof_verification = skip;
         self
     }
+
+    /// Profile the execution, attributing cycles and precompile costs to the guest call stack.
+    ///
+    /// The resulting [`Profile`](crate::Profile) is stored in the execution report.
+    pub fn profiler(&mut self, config: ProfilerConfig) -> &mut Self {
+        self.profiler = Some(config);
+        self
+    }
+
+    /// Estimate the proving cost of the execution.
+    ///
+    /// The resulting [`CostEstimate`](crate::CostEstimate) is stored in the execution report.
+    pub fn estimate_cost(&mut self, estimate: bool) -> &mut Self {
+        self.estimate_cost = estimate;
+        self
+    }
+
+    /// Record the per-instruction trace of the execution.
+    ///
+    /// The resulting [`ExecutionTrace`](crate::ExecutionTrace) is stored in the execution report.
+    pub fn trace(&mut self, trace: bool) -> &mut Self {
+        self.trace = trace;
+        self
+    }
+
+    /// Send the guest stdout to `sink` instead of printing it.
+    ///
+    /// Use an [`OutputBuffer`](crate::OutputBuffer) to capture the output in memory, a
+    /// [`WriterSink`](crate::WriterSink) to write it to a file, a
+    /// [`TracingSink`](crate::TracingSink) to forward it to `tracing`, or a closure taking the
+    /// written bytes.
+    pub fn stdout(&mut self, sink: impl OutputSink + Send + 'a) -> &mut Self {
+        self.stdout = Some(boxed_sink(sink));
+        self
+    }
+
+    /// Send the guest stderr to `sink` instead of printing it.
+    ///
+    /// See [`Self::stdout`] for the available sinks.
+    pub fn stderr(&mut self, sink: impl OutputSink + Send + 'a) -> &mut Self {
+        self.stderr = Some(boxed_sink(sink));
+        self
+    }
+
+    /// Stop the execution with [`ExecutionError::Cancelled`] once `token` is cancelled.
+    ///
+    /// The token is checked before every batch of shards.
+    pub fn cancellation_token(&mut self, token: CancellationToken) -> &mut Self {
+        self.cancellation_token = Some(token);
+        self
+    }
+
+    /// Invoke `f` with the progress of the proof.
+    pub fn progress(&mut self, f: impl Fn(ProveProgress) + Send + Sync + 'a) -> &mut Self {
+        self.progress = Some(Arc::new(f));
+        self
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{subproof::NoOpSubproofVerifier, ZKMContext};
+    use crate::{
+        subproof::NoOpSubproofVerifier, CancellationToken, OutputBuffer, OutputSink, ZKMContext,
+    };
 
     #[test]
     fn defaults() {
@@ -166,4 +301,24 @@ mod tests {
             ZKMContext::builder().subproof_verifier(&verifier).build();
         assert!(subproof_verifier.is_some());
     }
+
+    #[test]
+    fn cancellation_token() {
+        let token = CancellationToken::new();
+        let ZKMContext { cancellation_token, progress, .. } =
+            ZKMContext::builder().cancellation_token(token.clone()).build();
+        assert!(progress.is_none());
+        token.cancel();
+        assert!(cancellation_token.unwrap().is_cancelled());
+    }
+
+    #[test]
+    fn output_sinks() {
+        let buffer = OutputBuffer::new();
+        let ZKMContext { stdout, stderr, .. } =
+            ZKMContext::builder().stdout(buffer.clone()).build();
+        stdout.unwrap().lock().unwrap().write(b"hello");
+        assert!(stderr.is_none());
+        assert_eq!(buffer.take(), b"hello");
+    }
 }
@@ -0,0 +1,62 @@
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
+/// A token to cancel an execution or a proof from another thread.
+///
+/// The token is shared between its clones: cancelling one cancels all of them.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    /// Create a token that is not cancelled.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Request the cancellation. The work stops at the next point it checks the token.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    /// Whether the cancellation was requested.
+    #[must_use]
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// The progress of a proof, reported to a [`ProgressCallback`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProveProgress {
+    /// The execution records of this many shards have been generated so far.
+    ShardsExecuted(usize),
+    /// This many core shards have been proven so far.
+    ShardsProven(usize),
+    /// A layer of the compress tree has been proven.
+    CompressLayerDone {
+        /// The index of the layer, starting from the layer proving the core shards.
+        layer: usize,
+        /// The number of layers of the tree.
+        layers: usize,
+    },
+}
+
+/// A callback invoked with the progress of a proof, from the proving threads.
+pub type ProgressCallback<'a> = Arc<dyn Fn(ProveProgress) + Send + Sync + 'a>;
+
+#[cfg(test)]
+mod tests {
+    use super::CancellationToken;
+
+    #[test]
+    fn cancellation_is_shared() {
+        let token = CancellationToken::new();
+        let clone = token.clone();
+        assert!(!token.is_cancelled());
+        clone.cancel();
+        assert!(token.is_cancelled());
+    }
+}
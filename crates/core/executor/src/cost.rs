@@ -1,8 +1,14 @@
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
 use enum_map::EnumMap;
 use hashbrown::HashMap;
 use p3_koala_bear::KoalaBear;
+use serde::{Deserialize, Serialize};
+use zkm_stark::SplitOpts;
 
-use crate::{events::NUM_LOCAL_MEMORY_ENTRIES_PER_ROW_EXEC, MipsAirId, Opcode};
+use crate::{
+    events::NUM_LOCAL_MEMORY_ENTRIES_PER_ROW_EXEC, syscalls::SyscallCode, MipsAirId, Opcode,
+};
 
 const BYTE_NUM_ROWS: u64 = 1 << 16;
 const MAX_PROGRAM_SIZE: u64 = 1 << 22;
@@ -223,3 +229,222 @@ pub fn pad_mips_event_counts(
     });
     event_counts
 }
+
+/// The estimated cost of proving a single core shard.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ShardCostEstimate {
+    /// The number of cpu cycles in the shard.
+    pub cycles: u64,
+    /// The padded trace height of each core AIR.
+    pub padded_heights: EnumMap<MipsAirId, u64>,
+    /// The estimated LDE area of the shard in bytes.
+    pub lde_size: u64,
+    /// The log heights of the smallest maximal shape the shard fits in, if maximal shapes were
+    /// given.
+    pub shape: Option<EnumMap<MipsAirId, u32>>,
+}
+
+impl ShardCostEstimate {
+    /// Estimate the cost of a shard from its event counts.
+    #[must_use]
+    pub fn new(
+        cycles: u64,
+        event_counts: EnumMap<MipsAirId, u64>,
+        shape: Option<EnumMap<MipsAirId, u32>>,
+        costs_per_air: &HashMap<MipsAirId, u64>,
+    ) -> Self {
+        let mut padded_heights = EnumMap::default();
+        for air in MipsAirId::core() {
+            padded_heights[air] = match &shape {
+                Some(shape) => 1 << shape[air],
+                None => event_counts[air].next_power_of_two(),
+            };
+        }
+        let lde_size = estimate_mips_lde_size(event_counts, costs_per_air);
+        Self { cycles, padded_heights, lde_size, shape }
+    }
+}
+
+/// The estimated cost of proving an execution, collected by the executor when
+/// [`ZKMContextBuilder::estimate_cost`](crate::ZKMContextBuilder::estimate_cost) is set.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CostEstimate {
+    /// The estimates of the core shards, in execution order.
+    pub core_shards: Vec<ShardCostEstimate>,
+    /// The estimated number of precompile shards.
+    pub precompile_shards: u64,
+    /// The total trace height of each precompile AIR, over all precompile shards.
+    pub precompile_heights: EnumMap<MipsAirId, u64>,
+    /// The estimated LDE area of all precompile shards in bytes.
+    pub precompile_lde_size: u64,
+}
+
+impl CostEstimate {
+    /// Estimate the precompile shards from the number of calls to each syscall.
+    pub fn add_precompiles(
+        &mut self,
+        syscall_counts: impl IntoIterator<Item = (SyscallCode, u64)>,
+        split_opts: SplitOpts,
+        costs_per_air: &HashMap<MipsAirId, u64>,
+    ) {
+        for (syscall, count) in syscall_counts {
+            let Some(air) = syscall.as_air_id().filter(|_| count > 0) else {
+                continue;
+            };
+            let threshold = match syscall {
+                SyscallCode::KECCAK_SPONGE => split_opts.keccak,
+                SyscallCode::SHA_EXTEND => split_opts.sha_extend,
+                SyscallCode::SHA_COMPRESS => split_opts.sha_compress,
+                SyscallCode::SHA512_EXTEND => split_opts.sha512_extend,
+                SyscallCode::SHA512_COMPRESS => split_opts.sha512_compress,
+                SyscallCode::BLAKE3_COMPRESS_INNER => split_opts.blake3_compress_inner,
+                SyscallCode::BLAKE2B_COMPRESS_INNER => split_opts.blake2b_compress_inner,
+                SyscallCode::SECP256K1_SCALAR_MUL
+                | SyscallCode::SECP256R1_SCALAR_MUL
+                | SyscallCode::BN254_SCALAR_MUL
+                | SyscallCode::BLS12381_SCALAR_MUL => split_opts.weierstrass_scalar_mul,
+                SyscallCode::BOOLEAN_CIRCUIT_GARBLE => split_opts.boolean_circuit_garble,
+                SyscallCode::SYS_OPEN
+                | SyscallCode::SYS_OPENAT
+                | SyscallCode::SYS_CLOSE
+                | SyscallCode::SYS_FSTAT64 => split_opts.sys_file,
+                _ => split_opts.deferred,
+            }
+            .max(1) as u64;
+            let shards = count.div_ceil(threshold);
+            let events_per_shard = count.div_ceil(shards);
+            self.precompile_shards += shards;
+            self.precompile_heights[air] += count * air.rows_per_event();
+            self.precompile_lde_size += shards
+                * events_per_shard.next_power_of_two()
+                * costs_per_air.get(&air).copied().unwrap_or_default()
+                * ((core::mem::size_of::<KoalaBear>() << 1) as u64);
+        }
+    }
+
+    /// The total number of shards.
+    #[must_use]
+    pub fn num_shards(&self) -> u64 {
+        self.core_shards.len() as u64 + self.precompile_shards
+    }
+
+    /// The total number of cpu cycles.
+    #[must_use]
+    pub fn cycles(&self) -> u64 {
+        self.core_shards.iter().map(|shard| shard.cycles).sum()
+    }
+
+    /// The estimated LDE area of all shards in bytes.
+    #[must_use]
+    pub fn lde_size(&self) -> u64 {
+        self.core_shards.iter().map(|shard| shard.lde_size).sum::<u64>() + self.precompile_lde_size
+    }
+
+    /// The estimated LDE area of the largest core shard in bytes.
+    #[must_use]
+    pub fn max_shard_lde_size(&self) -> u64 {
+        self.core_shards.iter().map(|shard| shard.lde_size).max().unwrap_or_default()
+    }
+
+    /// The largest padded trace height of each core AIR over all core shards.
+    #[must_use]
+    pub fn max_padded_heights(&self) -> EnumMap<MipsAirId, u64> {
+        let mut heights = EnumMap::default();
+        for shard in &self.core_shards {
+            for (air, height) in shard.padded_heights {
+                heights[air] = u64::max(heights[air], height);
+            }
+        }
+        heights
+    }
+
+    /// The shape predicted for the core shards: the largest shape any core shard fits in.
+    ///
+    /// Returns `None` if no maximal shapes were given or some shard did not fit any of them.
+    #[must_use]
+    pub fn predicted_shape(&self) -> Option<EnumMap<MipsAirId, u32>> {
+        let mut shapes = self.core_shards.iter().map(|shard| shard.shape);
+        let first = shapes.next()??;
+        shapes.try_fold(first, |mut acc, shape| {
+            for (air, log_height) in shape? {
+                acc[air] = acc[air].max(log_height);
+            }
+            Some(acc)
+        })
+    }
+
+    /// Forecast the proving time and memory of the execution on a prover with the given
+    /// throughput.
+    #[must_use]
+    pub fn forecast(&self, throughput: &ProverThroughput) -> ProvingForecast {
+        let core_secs = self.lde_size() as f64 / throughput.core_lde_bytes_per_sec;
+        let compress_secs = self.num_shards() as f64 * throughput.compress_secs_per_shard;
+        let max_shard_lde_size = self
+            .max_shard_lde_size()
+            .max(self.precompile_lde_size.checked_div(self.precompile_shards).unwrap_or_default());
+        let peak_memory = max_shard_lde_size as f64
+            * throughput.memory_per_lde_byte
+            * throughput.concurrent_shards.max(1) as f64;
+        ProvingForecast { core_secs, compress_secs, peak_memory: peak_memory as u64 }
+    }
+}
+
+impl Display for CostEstimate {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        writeln!(
+            f,
+            "shards: {} ({} core, {} precompile)",
+            self.num_shards(),
+            self.core_shards.len(),
+            self.precompile_shards
+        )?;
+        writeln!(f, "estimated lde size: {:.2} GiB", self.lde_size() as f64 / (1 << 30) as f64)?;
+        writeln!(f, "max padded core trace heights:")?;
+        for (air, height) in self.max_padded_heights().into_iter().filter(|(_, h)| *h > 0) {
+            writeln!(f, "  {air}: 2^{}", height.ilog2())?;
+        }
+        writeln!(f, "precompile trace heights:")?;
+        for (air, height) in self.precompile_heights.into_iter().filter(|(_, h)| *h > 0) {
+            writeln!(f, "  {air}: {height}")?;
+        }
+        Ok(())
+    }
+}
+
+/// The throughput of a prover, used to turn a [`CostEstimate`] into a [`ProvingForecast`].
+///
+/// The defaults are rough figures for a CPU prover on a recent 64-core machine; calibrate them
+/// against real proofs on your own hardware.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProverThroughput {
+    /// The LDE area the core prover commits to per second, in bytes.
+    pub core_lde_bytes_per_sec: f64,
+    /// The time to compress the proof of one shard, in seconds.
+    pub compress_secs_per_shard: f64,
+    /// The peak memory per byte of LDE area of a shard.
+    pub memory_per_lde_byte: f64,
+    /// The number of shards proven at the same time.
+    pub concurrent_shards: usize,
+}
+
+impl Default for ProverThroughput {
+    fn default() -> Self {
+        Self {
+            core_lde_bytes_per_sec: 1e9,
+            compress_secs_per_shard: 2.5,
+            memory_per_lde_byte: 1.5,
+            concurrent_shards: 1,
+        }
+    }
+}
+
+/// A forecast of the resources needed to prove an execution.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProvingForecast {
+    /// The time to generate the core proofs, in seconds.
+    pub core_secs: f64,
+    /// The time to compress the core proofs into a single proof, in seconds.
+    pub compress_secs: f64,
+    /// The peak memory usage, in bytes.
+    pub peak_memory: u64,
+}
@@ -0,0 +1,305 @@
+//! A GDB remote serial protocol server for the [`Debugger`].
+//!
+//! Only the subset of the protocol needed by `gdb-multiarch` is implemented: register and memory
+//! reads, software breakpoints, stepping and continuing in both directions, and a few `monitor`
+//! commands. The register layout is the default one GDB uses for 32-bit MIPS targets.
+
+use std::{
+    io::{self, Read, Write},
+    net::{TcpListener, TcpStream, ToSocketAddrs},
+};
+
+use super::{Debugger, StopReason};
+use crate::{ExecutionError, Register};
+
+/// The number of registers sent in a `g` packet: the 32 GPRs, `sr`, `lo`, `hi`, `bad`, `cause`,
+/// and `pc`.
+const NUM_GDB_REGISTERS: usize = 38;
+
+/// The interrupt byte sent by GDB when the user presses Ctrl-C.
+const INTERRUPT: u8 = 0x03;
+
+/// A GDB remote serial protocol server wrapping a [`Debugger`].
+///
+/// ```text
+/// $ gdb-multiarch path/to/guest.elf -ex "set arch mips:isa32r2" -ex "target remote :9000"
+/// ```
+pub struct GdbServer<'a> {
+    debugger: Debugger<'a>,
+    last_stop: String,
+}
+
+impl<'a> GdbServer<'a> {
+    /// Create a new [`GdbServer`].
+    #[must_use]
+    pub fn new(debugger: Debugger<'a>) -> Self {
+        Self { debugger, last_stop: "S05".to_string() }
+    }
+
+    /// Wait for a single GDB connection on `addr` and serve it until GDB detaches or kills the
+    /// target. Returns the debugger in its final state.
+    pub fn listen(mut self, addr: impl ToSocketAddrs) -> io::Result<Debugger<'a>> {
+        let listener = TcpListener::bind(addr)?;
+        tracing::info!("waiting for gdb on {}", listener.local_addr()?);
+        let (stream, peer) = listener.accept()?;
+        tracing::info!("gdb connected from {peer}");
+        self.serve(stream)?;
+        Ok(self.debugger)
+    }
+
+    /// Serve GDB over an established connection.
+    pub fn serve(&mut self, mut stream: TcpStream) -> io::Result<()> {
+        stream.set_nodelay(true)?;
+        while let Some(packet) = read_packet(&mut stream)? {
+            match packet.as_str() {
+                "k" => return Ok(()),
+                "D" | "D;1" => {
+                    write_packet(&mut stream, "OK")?;
+                    return Ok(());
+                }
+                _ => {
+                    let reply = self.handle(&packet, &mut stream);
+                    write_packet(&mut stream, &reply)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Handle a single packet and return the reply.
+    fn handle(&mut self, packet: &str, stream: &mut TcpStream) -> String {
+        match packet {
+            "?" => self.last_stop.clone(),
+            "g" => self.read_registers(),
+            "c" | "vCont;c" | "vCont;c:1" => {
+                let stop = self.debugger.cont_until(|| poll_interrupt(stream));
+                self.stop_reply(stop)
+            }
+            "s" | "vCont;s" | "vCont;s:1" => {
+                let stop = self.debugger.step();
+                self.stop_reply(stop)
+            }
+            "bc" => {
+                let stop = self.debugger.reverse_cont();
+                self.stop_reply(stop)
+            }
+            "bs" => {
+                let stop = self.debugger.reverse_step();
+                self.stop_reply(stop)
+            }
+            "vCont?" => "vCont;c;s".to_string(),
+            "qAttached" => "1".to_string(),
+            "qC" => "QC1".to_string(),
+            "qfThreadInfo" => "m1".to_string(),
+            "qsThreadInfo" => "l".to_string(),
+            "qSymbol::" => "OK".to_string(),
+            _ if packet.starts_with("qSupported") => {
+                "PacketSize=4000;swbreak+;hwbreak+;ReverseStep+;ReverseContinue+".to_string()
+            }
+            _ if packet.starts_with('H') || packet.starts_with('T') => "OK".to_string(),
+            _ if packet.starts_with('p') => self.read_register(&packet[1..]),
+            _ if packet.starts_with('m') => self.read_memory(&packet[1..]),
+            _ if packet.starts_with("Z0,") || packet.starts_with("Z1,") => {
+                match parse_breakpoint(packet) {
+                    Some(addr) => {
+                        self.debugger.add_breakpoint(addr);
+                        "OK".to_string()
+                    }
+                    None => "E01".to_string(),
+                }
+            }
+            _ if packet.starts_with("z0,") || packet.starts_with("z1,") => {
+                match parse_breakpoint(packet) {
+                    Some(addr) => {
+                        self.debugger.remove_breakpoint(addr);
+                        "OK".to_string()
+                    }
+                    None => "E01".to_string(),
+                }
+            }
+            _ if packet.starts_with("qRcmd,") => {
+                let command = hex::decode(&packet["qRcmd,".len()..])
+                    .ok()
+                    .and_then(|bytes| String::from_utf8(bytes).ok());
+                match command {
+                    Some(command) => hex::encode(self.monitor(&command)),
+                    None => "E01".to_string(),
+                }
+            }
+            // An empty reply tells GDB the packet is not supported.
+            _ => String::new(),
+        }
+    }
+
+    /// Handle a `monitor` command, returning its console output.
+    fn monitor(&mut self, command: &str) -> String {
+        let mut words = command.split_whitespace();
+        match (words.next(), words.next()) {
+            (Some("break"), Some(name)) => match self.debugger.add_symbol_breakpoint(name) {
+                Some(addr) => format!("breakpoint at {addr:#010x} ({name})\n"),
+                None => format!("symbol {name} not found\n"),
+            },
+            (Some("where"), None) => {
+                let pc = self.debugger.pc();
+                format!(
+                    "{} at step {}\n",
+                    self.debugger.symbols().symbolize(pc),
+                    self.debugger.steps()
+                )
+            }
+            (Some("symbol"), Some(addr)) => {
+                match u32::from_str_radix(addr.trim_start_matches("0x"), 16) {
+                    Ok(addr) => format!("{}\n", self.debugger.symbols().symbolize(addr)),
+                    Err(_) => format!("invalid address {addr}\n"),
+                }
+            }
+            _ => "commands: break <symbol>, where, symbol <hex address>\n".to_string(),
+        }
+    }
+
+    /// Convert a [`StopReason`] into a stop reply packet and remember it for `?`.
+    fn stop_reply(&mut self, stop: StopReason) -> String {
+        let reply = match stop {
+            StopReason::Step => "S05".to_string(),
+            StopReason::Breakpoint(_) => "T05swbreak:;".to_string(),
+            StopReason::Interrupted => "S02".to_string(),
+            StopReason::StartOfHistory => "T05replaylog:begin;".to_string(),
+            StopReason::Exited(exit_code) => format!("W{:02x}", exit_code & 0xff),
+            StopReason::Error(err) => {
+                tracing::error!(
+                    "guest failed at {}: {err}",
+                    self.debugger.symbols().symbolize(self.debugger.pc())
+                );
+                format!("S{:02x}", signal(&err))
+            }
+        };
+        self.last_stop.clone_from(&reply);
+        reply
+    }
+
+    /// The value of the register at the given GDB index.
+    fn register_value(&mut self, idx: usize) -> Option<u32> {
+        match idx {
+            0..32 => Some(self.debugger.register((idx as u8).into())),
+            33 => Some(self.debugger.register(Register::LO)),
+            34 => Some(self.debugger.register(Register::HI)),
+            37 => Some(self.debugger.pc()),
+            32 | 35 | 36 => Some(0),
+            _ => None,
+        }
+    }
+
+    fn read_registers(&mut self) -> String {
+        (0..NUM_GDB_REGISTERS)
+            .map(|idx| hex::encode(self.register_value(idx).unwrap_or_default().to_le_bytes()))
+            .collect()
+    }
+
+    fn read_register(&mut self, args: &str) -> String {
+        let Ok(idx) = usize::from_str_radix(args, 16) else {
+            return "E01".to_string();
+        };
+        match self.register_value(idx) {
+            Some(value) => hex::encode(value.to_le_bytes()),
+            None => "xxxxxxxx".to_string(),
+        }
+    }
+
+    fn read_memory(&mut self, args: &str) -> String {
+        let Some((addr, len)) = args.split_once(',') else {
+            return "E01".to_string();
+        };
+        match (u32::from_str_radix(addr, 16), usize::from_str_radix(len, 16)) {
+            (Ok(addr), Ok(len)) => hex::encode(self.debugger.read_memory(addr, len)),
+            _ => "E01".to_string(),
+        }
+    }
+}
+
+/// The POSIX signal GDB should report for an execution error.
+fn signal(err: &ExecutionError) -> u8 {
+    match err {
+        ExecutionError::InvalidMemoryAccess(..)
+        | ExecutionError::MemoryOutOfBoundsAccess(_)
+        | ExecutionError::VirtualFilesOverlap(_)
+        | ExecutionError::NullPointerReference() => 11,
+        ExecutionError::UnsupportedInstruction(_) | ExecutionError::Unimplemented() => 4,
+        ExecutionError::Breakpoint() | ExecutionError::ExceptionOrTrap() => 5,
+        _ => 6,
+    }
+}
+
+/// Parse the address out of a `Z`/`z` packet of the form `Z0,addr,kind`.
+fn parse_breakpoint(packet: &str) -> Option<u32> {
+    let addr = packet.split(',').nth(1)?;
+    u32::from_str_radix(addr, 16).ok()
+}
+
+/// Check whether GDB sent an interrupt, without blocking.
+fn poll_interrupt(stream: &mut TcpStream) -> bool {
+    if stream.set_nonblocking(true).is_err() {
+        return false;
+    }
+    let mut byte = [0u8; 1];
+    let interrupted = matches!(stream.peek(&mut byte), Ok(1) if byte[0] == INTERRUPT);
+    if interrupted {
+        let _ = stream.read_exact(&mut byte);
+    }
+    let _ = stream.set_nonblocking(false);
+    interrupted
+}
+
+/// Read the next packet, acknowledging it. Returns `None` once the connection is closed.
+fn read_packet(stream: &mut TcpStream) -> io::Result<Option<String>> {
+    let mut byte = [0u8; 1];
+    loop {
+        // Skip acknowledgements and stray interrupts until the start of a packet.
+        loop {
+            if stream.read(&mut byte)? == 0 {
+                return Ok(None);
+            }
+            if byte[0] == b'$' {
+                break;
+            }
+        }
+
+        let mut data = Vec::new();
+        loop {
+            if stream.read(&mut byte)? == 0 {
+                return Ok(None);
+            }
+            if byte[0] == b'#' {
+                break;
+            }
+            data.push(byte[0]);
+        }
+        let mut checksum = [0u8; 2];
+        stream.read_exact(&mut checksum)?;
+
+        let expected =
+            std::str::from_utf8(&checksum).ok().and_then(|s| u8::from_str_radix(s, 16).ok());
+        if expected == Some(data.iter().fold(0u8, |acc, b| acc.wrapping_add(*b))) {
+            stream.write_all(b"+")?;
+            return Ok(Some(String::from_utf8_lossy(&data).into_owned()));
+        }
+        stream.write_all(b"-")?;
+    }
+}
+
+/// Write a packet with its checksum.
+fn write_packet(stream: &mut TcpStream, data: &str) -> io::Result<()> {
+    let checksum = data.bytes().fold(0u8, |acc, b| acc.wrapping_add(b));
+    write!(stream, "${data}#{checksum:02x}")?;
+    stream.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_breakpoint;
+
+    #[test]
+    fn test_packet_helpers() {
+        assert_eq!(parse_breakpoint("Z0,400120,4"), Some(0x0040_0120));
+        assert_eq!(parse_breakpoint("z1,zz,4"), None);
+    }
+}
@@ -0,0 +1,364 @@
+//! An interactive, time-travelling debugger for guest programs.
+//!
+//! The [`Debugger`] drives an [`Executor`] one instruction at a time in [`ExecutorMode::Simple`],
+//! and periodically snapshots the [`ExecutionState`]. Stepping backwards restores the closest
+//! snapshot and replays forward to the previous instruction, which is deterministic since the
+//! whole input of the guest lives in the execution state.
+//!
+//! The [`GdbServer`] exposes a debugger over the GDB remote serial protocol so `gdb-multiarch`
+//! can attach to it.
+
+mod gdb;
+
+pub use gdb::*;
+
+use std::collections::BTreeSet;
+
+use crate::{
+    state::{ExecutionState, ForkState},
+    ExecutionError, Executor, ExecutorMode, LocalCounts, Register, Symbol, SymbolTable,
+    NUM_REGISTERS,
+};
+
+/// The default number of instructions between two execution state snapshots.
+pub const DEFAULT_CHECKPOINT_INTERVAL: u64 = 1 << 20;
+
+/// Why the debugger handed control back to the user.
+#[derive(Debug)]
+pub enum StopReason {
+    /// A single step completed.
+    Step,
+    /// A breakpoint was hit at the given program counter.
+    Breakpoint(u32),
+    /// The user interrupted a continue.
+    Interrupted,
+    /// Reverse execution reached the start of the program.
+    StartOfHistory,
+    /// The program halted with the given exit code.
+    Exited(u32),
+    /// The program failed. The state is left at the faulting instruction.
+    Error(ExecutionError),
+}
+
+/// A snapshot of the executor taken while stepping forward.
+struct Checkpoint {
+    /// The number of instructions executed before the snapshot.
+    step: u64,
+    /// The state of the executor.
+    state: ExecutionState,
+}
+
+/// A debugger for guest programs, with breakpoints and reverse execution.
+pub struct Debugger<'a> {
+    /// The executor running the guest.
+    runtime: Executor<'a>,
+    /// The function symbols of the guest ELF.
+    symbols: SymbolTable,
+    /// The program counters to stop at.
+    breakpoints: BTreeSet<u32>,
+    /// The number of instructions executed so far.
+    ///
+    /// Unlike `global_clk`, this keeps counting through unconstrained blocks.
+    step: u64,
+    /// The exit code of the program, if it has finished.
+    exit_code: Option<u32>,
+    /// The number of instructions between two snapshots.
+    checkpoint_interval: u64,
+    /// The snapshots taken so far, sorted by step.
+    checkpoints: Vec<Checkpoint>,
+}
+
+impl<'a> Debugger<'a> {
+    /// Create a new [`Debugger`] for the given executor and ELF symbols.
+    ///
+    /// The executor must not have started executing yet.
+    #[must_use]
+    pub fn new(mut runtime: Executor<'a>, symbols: SymbolTable) -> Self {
+        assert_eq!(runtime.state.global_clk, 0, "the executor has already started");
+        runtime.executor_mode = ExecutorMode::Simple;
+        runtime.print_report = false;
+        runtime.initialize();
+
+        let checkpoints = vec![Checkpoint { step: 0, state: runtime.state.clone() }];
+        Self {
+            runtime,
+            symbols,
+            breakpoints: BTreeSet::new(),
+            step: 0,
+            exit_code: None,
+            checkpoint_interval: DEFAULT_CHECKPOINT_INTERVAL,
+            checkpoints,
+        }
+    }
+
+    /// Set the number of instructions between two snapshots.
+    ///
+    /// Smaller intervals make reverse execution faster at the cost of memory.
+    pub fn set_checkpoint_interval(&mut self, interval: u64) {
+        self.checkpoint_interval = interval.max(1);
+    }
+
+    /// The executor running the guest.
+    #[must_use]
+    pub fn runtime(&self) -> &Executor<'a> {
+        &self.runtime
+    }
+
+    /// The function symbols of the guest ELF.
+    #[must_use]
+    pub fn symbols(&self) -> &SymbolTable {
+        &self.symbols
+    }
+
+    /// The current program counter.
+    #[must_use]
+    pub fn pc(&self) -> u32 {
+        self.runtime.state.pc
+    }
+
+    /// The number of instructions executed so far.
+    #[must_use]
+    pub fn steps(&self) -> u64 {
+        self.step
+    }
+
+    /// The function containing the current program counter.
+    #[must_use]
+    pub fn current_symbol(&self) -> Option<&Symbol> {
+        self.symbols.lookup(self.pc())
+    }
+
+    /// The current values of the registers, indexed by [`Register`].
+    #[must_use]
+    pub fn registers(&mut self) -> [u32; NUM_REGISTERS] {
+        self.runtime.registers()
+    }
+
+    /// The current value of a register.
+    #[must_use]
+    pub fn register(&mut self, register: Register) -> u32 {
+        self.runtime.register(register)
+    }
+
+    /// Read `len` bytes of guest memory starting at `addr`.
+    #[must_use]
+    pub fn read_memory(&mut self, addr: u32, len: usize) -> Vec<u8> {
+        (0..len as u32).map(|i| self.runtime.byte(addr.wrapping_add(i))).collect()
+    }
+
+    /// Add a breakpoint at the given program counter.
+    pub fn add_breakpoint(&mut self, pc: u32) {
+        self.breakpoints.insert(pc);
+    }
+
+    /// Remove the breakpoint at the given program counter, returning whether it existed.
+    pub fn remove_breakpoint(&mut self, pc: u32) -> bool {
+        self.breakpoints.remove(&pc)
+    }
+
+    /// Add a breakpoint at the start of the function with the given raw or demangled name.
+    ///
+    /// Returns the address of the breakpoint, or `None` if the symbol was not found.
+    pub fn add_symbol_breakpoint(&mut self, name: &str) -> Option<u32> {
+        let address = self.symbols.find(name)?.address;
+        self.add_breakpoint(address);
+        Some(address)
+    }
+
+    /// The current breakpoints.
+    pub fn breakpoints(&self) -> impl Iterator<Item = u32> + '_ {
+        self.breakpoints.iter().copied()
+    }
+
+    /// Execute a single instruction.
+    pub fn step(&mut self) -> StopReason {
+        match self.execute_one() {
+            Ok(None) => StopReason::Step,
+            Ok(Some(exit_code)) => StopReason::Exited(exit_code),
+            Err(err) => StopReason::Error(err),
+        }
+    }
+
+    /// Execute until a breakpoint is hit or the program ends.
+    pub fn cont(&mut self) -> StopReason {
+        self.cont_until(|| false)
+    }
+
+    /// Execute until a breakpoint is hit, the program ends, or `interrupt` returns true.
+    ///
+    /// `interrupt` is polled every few thousand instructions.
+    pub fn cont_until(&mut self, mut interrupt: impl FnMut() -> bool) -> StopReason {
+        loop {
+            match self.execute_one() {
+                Ok(None) => {}
+                Ok(Some(exit_code)) => return StopReason::Exited(exit_code),
+                Err(err) => return StopReason::Error(err),
+            }
+            if self.breakpoints.contains(&self.pc()) {
+                return StopReason::Breakpoint(self.pc());
+            }
+            if self.step.is_multiple_of(1 << 14) && interrupt() {
+                return StopReason::Interrupted;
+            }
+        }
+    }
+
+    /// Undo the last executed instruction.
+    pub fn reverse_step(&mut self) -> StopReason {
+        if self.step == 0 {
+            return StopReason::StartOfHistory;
+        }
+        match self.replay_to(self.step - 1) {
+            Ok(()) => StopReason::Step,
+            Err(err) => StopReason::Error(err),
+        }
+    }
+
+    /// Execute backwards until a breakpoint is hit or the start of the program is reached.
+    pub fn reverse_cont(&mut self) -> StopReason {
+        let target = self.step;
+        let mut end = target;
+        // Search the snapshot intervals backwards for the last breakpoint hit before `target`.
+        for idx in (0..self.checkpoints.len()).rev() {
+            let start = self.checkpoints[idx].step;
+            if start >= end {
+                continue;
+            }
+            self.restore(idx);
+            let mut last_hit = None;
+            while self.step < end {
+                if self.breakpoints.contains(&self.pc()) {
+                    last_hit = Some(self.step);
+                }
+                if let Err(err) = self.execute_one() {
+                    return StopReason::Error(err);
+                }
+            }
+            if let Some(hit) = last_hit {
+                return match self.replay_to(hit) {
+                    Ok(()) => StopReason::Breakpoint(self.pc()),
+                    Err(err) => StopReason::Error(err),
+                };
+            }
+            end = start;
+        }
+        match self.replay_to(0) {
+            Ok(()) => StopReason::StartOfHistory,
+            Err(err) => StopReason::Error(err),
+        }
+    }
+
+    /// Restore the executor to the state right before the instruction with index `target`.
+    fn replay_to(&mut self, target: u64) -> Result<(), ExecutionError> {
+        let idx = self.checkpoints.partition_point(|checkpoint| checkpoint.step <= target) - 1;
+        self.restore(idx);
+        while self.step < target {
+            self.execute_one()?;
+        }
+        Ok(())
+    }
+
+    /// Restore the executor to the snapshot with the given index.
+    fn restore(&mut self, idx: usize) {
+        let checkpoint = &self.checkpoints[idx];
+        self.runtime.state = checkpoint.state.clone();
+        self.runtime.unconstrained = false;
+        self.runtime.unconstrained_state = ForkState::default();
+        self.runtime.local_counts = LocalCounts::default();
+        self.runtime.panic_report = None;
+        self.step = checkpoint.step;
+        self.exit_code = None;
+    }
+
+    /// Execute one instruction, returning the exit code if the program ended.
+    fn execute_one(&mut self) -> Result<Option<u32>, ExecutionError> {
+        if self.exit_code.is_some() {
+            return Ok(self.exit_code);
+        }
+
+        let done = match self.runtime.execute_cycle() {
+            Ok(done) => done,
+            Err(ExecutionError::HaltWithNonZeroExitCode(exit_code)) => {
+                self.exit_code = Some(exit_code);
+                return Ok(self.exit_code);
+            }
+            Err(ExecutionError::GuestPanic(panic)) => {
+                self.exit_code = Some(panic.exit_code);
+                return Ok(self.exit_code);
+            }
+            Err(err) => return Err(err),
+        };
+        self.step += 1;
+
+        // Roll over to a new shard like `Executor::execute` does, so that the clock never
+        // overflows on long executions.
+        if !self.runtime.unconstrained
+            && !self.runtime.state.next_is_delayslot
+            && self.runtime.inc_shard_if_need()
+        {
+            self.runtime.local_counts = LocalCounts::default();
+        }
+
+        if !self.runtime.unconstrained
+            && self.step.is_multiple_of(self.checkpoint_interval)
+            && self.checkpoints.last().is_some_and(|checkpoint| checkpoint.step < self.step)
+        {
+            self.checkpoints
+                .push(Checkpoint { step: self.step, state: self.runtime.state.clone() });
+        }
+
+        if done {
+            self.exit_code = Some(0);
+            return Ok(self.exit_code);
+        }
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zkm_stark::ZKMCoreOpts;
+
+    use super::{Debugger, StopReason};
+    use crate::{
+        programs::tests::{fibonacci_program, simple_program},
+        Executor, Register, SymbolTable,
+    };
+
+    #[test]
+    fn test_step_and_reverse_step() {
+        let runtime = Executor::new(simple_program(), ZKMCoreOpts::default());
+        let mut debugger = Debugger::new(runtime, SymbolTable::default());
+
+        assert!(matches!(debugger.step(), StopReason::Step));
+        assert!(matches!(debugger.step(), StopReason::Step));
+        assert_eq!(debugger.register(Register::FP), 37);
+        assert!(matches!(debugger.step(), StopReason::Exited(0)));
+        assert_eq!(debugger.register(Register::RA), 42);
+
+        assert!(matches!(debugger.reverse_step(), StopReason::Step));
+        assert_eq!(debugger.steps(), 2);
+        assert_eq!(debugger.register(Register::RA), 0);
+        assert_eq!(debugger.pc(), 8);
+
+        assert!(matches!(debugger.reverse_cont(), StopReason::StartOfHistory));
+        assert_eq!(debugger.pc(), 0);
+        assert_eq!(debugger.register(Register::FP), 0);
+    }
+
+    #[test]
+    fn test_breakpoints_with_replay() {
+        let runtime = Executor::new(fibonacci_program(), ZKMCoreOpts::default());
+        let symbols = SymbolTable::from_elf(test_artifacts::FIBONACCI_ELF).unwrap();
+        let mut debugger = Debugger::new(runtime, symbols);
+        debugger.set_checkpoint_interval(64);
+
+        let main = debugger.add_symbol_breakpoint("_main").unwrap();
+        assert!(matches!(debugger.cont(), StopReason::Breakpoint(pc) if pc == main));
+        let hit = debugger.steps();
+
+        assert!(matches!(debugger.cont(), StopReason::Exited(0)));
+        assert!(matches!(debugger.reverse_cont(), StopReason::Breakpoint(pc) if pc == main));
+        assert_eq!(debugger.steps(), hit);
+    }
+}
@@ -0,0 +1,31 @@
+use serde::{Deserialize, Serialize};
+
+use crate::events::{
+    memory::{MemoryReadRecord, MemoryWriteRecord},
+    MemoryLocalEvent,
+};
+
+/// BLAKE2b Compress Inner Event.
+///
+/// This event is emitted when the twelve rounds of the BLAKE2b compression function are applied to
+/// a working state. Each round is made of eight applications of the `G` function, and the memory
+/// records are stored per `G` application, in execution order. Each 64-bit word is stored in
+/// memory as two little-endian 32-bit words, so the memory records hold two entries per 64-bit
+/// word, low word first.
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct Blake2bCompressInnerEvent {
+    /// The shard number.
+    pub shard: u32,
+    /// The clock cycle.
+    pub clk: u32,
+    /// The pointer to the working state.
+    pub state_ptr: u32,
+    /// The pointer to the message block.
+    pub message_ptr: u32,
+    /// The memory records for the reads of the two message words used by each `G` application.
+    pub message_reads: Vec<[MemoryReadRecord; 4]>,
+    /// The memory records for the writes of the four state words updated by each `G` application.
+    pub state_writes: Vec<[MemoryWriteRecord; 8]>,
+    /// The local memory accesses.
+    pub local_mem_access: Vec<MemoryLocalEvent>,
+}
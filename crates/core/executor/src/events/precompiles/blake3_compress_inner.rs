@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+
+use crate::events::{
+    memory::{MemoryReadRecord, MemoryWriteRecord},
+    MemoryLocalEvent,
+};
+
+/// BLAKE3 Compress Inner Event.
+///
+/// This event is emitted when the seven rounds of the BLAKE3 compression function are applied to a
+/// working state. Each round is made of eight applications of the `G` function, and the memory
+/// records are stored per `G` application, in execution order.
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct Blake3CompressInnerEvent {
+    /// The shard number.
+    pub shard: u32,
+    /// The clock cycle.
+    pub clk: u32,
+    /// The pointer to the working state.
+    pub state_ptr: u32,
+    /// The pointer to the message block.
+    pub message_ptr: u32,
+    /// The memory records for the reads of the two message words used by each `G` application.
+    pub message_reads: Vec<[MemoryReadRecord; 2]>,
+    /// The memory records for the writes of the four state words updated by each `G` application.
+    pub state_writes: Vec<[MemoryWriteRecord; 4]>,
+    /// The local memory accesses.
+    pub local_mem_access: Vec<MemoryLocalEvent>,
+}
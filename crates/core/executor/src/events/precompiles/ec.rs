@@ -1,3 +1,4 @@
+use num::{BigUint, Zero};
 use serde::{Deserialize, Serialize};
 
 use typenum::Unsigned;
@@ -5,7 +6,7 @@ use zkm_curves::{
     params::{NumLimbs, NumWords},
     weierstrass::{
         bls12_381::bls12381_decompress, secp256k1::secp256k1_decompress,
-        secp256r1::secp256r1_decompress,
+        secp256r1::secp256r1_decompress, WeierstrassParameters,
     },
     AffinePoint, CurveError, CurveType, EllipticCurve,
 };
@@ -63,6 +64,34 @@ pub struct EllipticCurveDoubleEvent {
     pub local_mem_access: Vec<MemoryLocalEvent>,
 }
 
+/// The number of words in the scalar of an elliptic curve scalar multiplication.
+pub const NUM_WORDS_EC_SCALAR: usize = 8;
+
+/// Elliptic Curve Scalar Mul Event.
+///
+/// This event is emitted when an elliptic curve scalar multiplication is performed.
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct EllipticCurveScalarMulEvent {
+    /// The shard number.
+    pub shard: u32,
+    /// The clock cycle.
+    pub clk: u32,
+    /// The pointer to the point.
+    pub p_ptr: u32,
+    /// The point as a list of words.
+    pub p: Vec<u32>,
+    /// The pointer to the scalar.
+    pub k_ptr: u32,
+    /// The scalar as a list of little-endian words.
+    pub k: Vec<u32>,
+    /// The memory records for the point.
+    pub p_memory_records: Vec<MemoryWriteRecord>,
+    /// The memory records for the scalar.
+    pub k_memory_records: Vec<MemoryReadRecord>,
+    /// The local memory access records.
+    pub local_mem_access: Vec<MemoryLocalEvent>,
+}
+
 /// Elliptic Curve Point Decompress Event.
 ///
 /// This event is emitted when an elliptic curve point decompression operation is performed.
@@ -175,6 +204,62 @@ pub fn create_ec_double_event<E: EllipticCurve>(
     }
 }
 
+/// Create an elliptic curve scalar mul event.
+///
+/// It takes a pointer to a point and a pointer to a 256-bit little-endian scalar `k`, computes
+/// `[k]P` and writes the result back to the memory location of the point. The scalar must be in
+/// `[1, n)`, where `n` is the order of the prime-order subgroup `P` belongs to, so that neither the
+/// result nor any intermediate sum of the double-and-add loop is the point at infinity.
+pub fn create_ec_scalar_mul_event<E: EllipticCurve + WeierstrassParameters>(
+    rt: &mut SyscallContext,
+    arg1: u32,
+    arg2: u32,
+) -> EllipticCurveScalarMulEvent {
+    let start_clk = rt.clk;
+    let p_ptr = arg1;
+    if !p_ptr.is_multiple_of(4) {
+        panic!();
+    }
+    let k_ptr = arg2;
+    if !k_ptr.is_multiple_of(4) {
+        panic!();
+    }
+
+    let num_words = <E::BaseField as NumWords>::WordsCurvePoint::USIZE;
+
+    let p = rt.slice_unsafe(p_ptr, num_words);
+
+    let (k_memory_records, k) = rt.mr_slice(k_ptr, NUM_WORDS_EC_SCALAR);
+
+    // When we write to p, we want the clk to be incremented because p and k could overlap.
+    rt.clk += 1;
+
+    let scalar = BigUint::from_slice(&k);
+    assert!(
+        !scalar.is_zero() && scalar < E::prime_group_order(),
+        "scalar must be in [1, n) where n is the order of the prime-order subgroup"
+    );
+
+    let p_affine = AffinePoint::<E>::from_words_le(&p);
+    let result_affine = p_affine.scalar_mul(&scalar);
+
+    let result_words = result_affine.to_words_le();
+
+    let p_memory_records = rt.mw_slice(p_ptr, &result_words);
+
+    EllipticCurveScalarMulEvent {
+        shard: rt.current_shard(),
+        clk: start_clk,
+        p_ptr,
+        p,
+        k_ptr,
+        k,
+        p_memory_records,
+        k_memory_records,
+        local_mem_access: rt.postprocess(),
+    }
+}
+
 /// Create an elliptic curve decompress event.
 ///
 /// It takes a pointer to a memory location, reads the point from memory, decompresses it, and
@@ -1,3 +1,5 @@
+mod blake2b_compress_inner;
+mod blake3_compress_inner;
 mod boolean_circuit_garble;
 mod ec;
 mod edwards;
@@ -7,10 +9,15 @@ mod linux;
 mod poseidon2_permute;
 mod sha256_compress;
 mod sha256_extend;
+mod sha512_compress;
+mod sha512_extend;
+mod sys_file;
 mod u256x2048_mul;
 mod uint256;
 use super::{MemoryLocalEvent, SyscallEvent};
 use crate::syscalls::SyscallCode;
+pub use blake2b_compress_inner::*;
+pub use blake3_compress_inner::*;
 pub use boolean_circuit_garble::*;
 pub use ec::*;
 pub use edwards::*;
@@ -22,7 +29,10 @@ pub use poseidon2_permute::*;
 use serde::{Deserialize, Serialize};
 pub use sha256_compress::*;
 pub use sha256_extend::*;
+pub use sha512_compress::*;
+pub use sha512_extend::*;
 use strum::{EnumIter, IntoEnumIterator};
+pub use sys_file::*;
 pub use u256x2048_mul::*;
 pub use uint256::*;
 
@@ -33,6 +43,14 @@ pub enum PrecompileEvent {
     ShaExtend(ShaExtendEvent),
     /// Sha256 compress precompile event.
     ShaCompress(ShaCompressEvent),
+    /// Sha512 extend precompile event.
+    Sha512Extend(Sha512ExtendEvent),
+    /// Sha512 compress precompile event.
+    Sha512Compress(Sha512CompressEvent),
+    /// Blake3 compress inner precompile event.
+    Blake3CompressInner(Blake3CompressInnerEvent),
+    /// Blake2b compress inner precompile event.
+    Blake2bCompressInner(Blake2bCompressInnerEvent),
     /// Keccak sponge precompile event.
     KeccakSponge(KeccakSpongeEvent),
     /// Edwards curve add precompile event.
@@ -45,36 +63,48 @@ pub enum PrecompileEvent {
     Secp256k1Double(EllipticCurveDoubleEvent),
     /// Secp256k1 curve decompress precompile event.
     Secp256k1Decompress(EllipticCurveDecompressEvent),
+    /// Secp256k1 curve scalar mul precompile event.
+    Secp256k1ScalarMul(EllipticCurveScalarMulEvent),
     /// Secp256r1 curve add precompile event.
     Secp256r1Add(EllipticCurveAddEvent),
     /// Secp256r1 curve double precompile event.
     Secp256r1Double(EllipticCurveDoubleEvent),
     /// Secp256r1 curve decompress precompile event.
     Secp256r1Decompress(EllipticCurveDecompressEvent),
+    /// Secp256r1 curve scalar mul precompile event.
+    Secp256r1ScalarMul(EllipticCurveScalarMulEvent),
     /// K256 curve decompress precompile event.
     K256Decompress(EllipticCurveDecompressEvent),
     /// Bn254 curve add precompile event.
     Bn254Add(EllipticCurveAddEvent),
     /// Bn254 curve double precompile event.
     Bn254Double(EllipticCurveDoubleEvent),
+    /// Bn254 curve scalar mul precompile event.
+    Bn254ScalarMul(EllipticCurveScalarMulEvent),
     /// Bn254 base field operation precompile event.
     Bn254Fp(FpOpEvent),
     /// Bn254 quadratic field add/sub precompile event.
     Bn254Fp2AddSub(Fp2AddSubEvent),
     /// Bn254 quadratic field mul precompile event.
     Bn254Fp2Mul(Fp2MulEvent),
+    /// Bn254 sextic field mul precompile event.
+    Bn254Fp6Mul(Fp6MulEvent),
     /// Bls12-381 curve add precompile event.
     Bls12381Add(EllipticCurveAddEvent),
     /// Bls12-381 curve double precompile event.
     Bls12381Double(EllipticCurveDoubleEvent),
     /// Bls12-381 curve decompress precompile event.
     Bls12381Decompress(EllipticCurveDecompressEvent),
+    /// Bls12-381 curve scalar mul precompile event.
+    Bls12381ScalarMul(EllipticCurveScalarMulEvent),
     /// Bls12-381 base field operation precompile event.
     Bls12381Fp(FpOpEvent),
     /// Bls12-381 quadratic field add/sub precompile event.
     Bls12381Fp2AddSub(Fp2AddSubEvent),
     /// Bls12-381 quadratic field mul precompile event.
     Bls12381Fp2Mul(Fp2MulEvent),
+    /// Bls12-381 sextic field mul precompile event.
+    Bls12381Fp6Mul(Fp6MulEvent),
     /// Uint256 mul precompile event.
     Uint256Mul(Uint256MulEvent),
     /// U256XU2048 mul precompile event.
@@ -84,6 +114,8 @@ pub enum PrecompileEvent {
     /// linux precompile event.
     Linux(LinuxEvent),
     BooleanCircuitGarble(BooleanCircuitGarbleEvent),
+    /// Virtual file precompile event.
+    SysFile(SysFileEvent),
 }
 
 /// Trait to retrieve all the local memory events from a vec of precompile events.
@@ -104,6 +136,18 @@ impl PrecompileLocalMemory for Vec<(SyscallEvent, PrecompileEvent)> {
                 PrecompileEvent::ShaCompress(e) => {
                     iterators.push(e.local_mem_access.iter());
                 }
+                PrecompileEvent::Sha512Extend(e) => {
+                    iterators.push(e.local_mem_access.iter());
+                }
+                PrecompileEvent::Sha512Compress(e) => {
+                    iterators.push(e.local_mem_access.iter());
+                }
+                PrecompileEvent::Blake3CompressInner(e) => {
+                    iterators.push(e.local_mem_access.iter());
+                }
+                PrecompileEvent::Blake2bCompressInner(e) => {
+                    iterators.push(e.local_mem_access.iter());
+                }
                 PrecompileEvent::KeccakSponge(e) => {
                     iterators.push(e.local_mem_access.iter());
                 }
@@ -123,6 +167,12 @@ impl PrecompileLocalMemory for Vec<(SyscallEvent, PrecompileEvent)> {
                 | PrecompileEvent::Bls12381Double(e) => {
                     iterators.push(e.local_mem_access.iter());
                 }
+                PrecompileEvent::Secp256k1ScalarMul(e)
+                | PrecompileEvent::Secp256r1ScalarMul(e)
+                | PrecompileEvent::Bn254ScalarMul(e)
+                | PrecompileEvent::Bls12381ScalarMul(e) => {
+                    iterators.push(e.local_mem_access.iter());
+                }
                 PrecompileEvent::Secp256k1Decompress(e)
                 | PrecompileEvent::Secp256r1Decompress(e)
                 | PrecompileEvent::K256Decompress(e)
@@ -144,6 +194,9 @@ impl PrecompileLocalMemory for Vec<(SyscallEvent, PrecompileEvent)> {
                 PrecompileEvent::Bls12381Fp2Mul(e) | PrecompileEvent::Bn254Fp2Mul(e) => {
                     iterators.push(e.local_mem_access.iter());
                 }
+                PrecompileEvent::Bls12381Fp6Mul(e) | PrecompileEvent::Bn254Fp6Mul(e) => {
+                    iterators.push(e.local_mem_access.iter());
+                }
                 PrecompileEvent::Poseidon2Permute(e) => {
                     iterators.push(e.local_mem_access.iter());
                 }
@@ -153,6 +206,9 @@ impl PrecompileLocalMemory for Vec<(SyscallEvent, PrecompileEvent)> {
                 PrecompileEvent::BooleanCircuitGarble(e) => {
                     iterators.push(e.local_mem_access.iter());
                 }
+                PrecompileEvent::SysFile(e) => {
+                    iterators.push(e.local_mem_access.iter());
+                }
             }
         }
 
@@ -0,0 +1,101 @@
+use serde::{Deserialize, Serialize};
+
+use crate::events::{
+    memory::{MemoryReadRecord, MemoryRecordEnum, MemoryWriteRecord},
+    MemoryLocalEvent,
+};
+
+/// Virtual File Event.
+///
+/// This event is emitted when `open`, `openat`, `close` or `fstat64` is performed, or when `read`
+/// is performed on a file descriptor other than stdin.
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct SysFileEvent {
+    /// The shard number.
+    pub shard: u32,
+    /// The clock cycle.
+    pub clk: u32,
+    /// The Linux syscall code.
+    pub syscall_code: u32,
+    /// The first argument of the syscall.
+    pub a0: u32,
+    /// The second argument of the syscall.
+    pub a1: u32,
+    /// The syscall return value.
+    pub v0: u32,
+    /// The memory record for the errno written to A3.
+    pub errno_record: MemoryWriteRecord,
+    /// The memory record for A2: the count of `read` or the flags of `openat`.
+    pub a2_record: Option<MemoryReadRecord>,
+    /// The memory record for the file descriptor of `close`, `fstat64` and `read`.
+    pub fd_record: Option<MemoryRecordEnum>,
+    /// The memory record for the offset of the file read.
+    pub offset_record: Option<MemoryWriteRecord>,
+    /// The memory record for the address of the `stat64` buffer of the file.
+    pub stat_record: Option<MemoryReadRecord>,
+    /// The memory record for the address of the data of the file read.
+    pub data_record: Option<MemoryReadRecord>,
+    /// The memory record for the size of the file read.
+    pub size_record: Option<MemoryReadRecord>,
+    /// The lookup of the path of `open` and `openat`.
+    pub lookup: Option<PathLookupEvent>,
+    /// The bytes copied by `read` and `fstat64`.
+    pub copy: Option<ByteCopyEvent>,
+    /// The local memory access records.
+    pub local_mem_access: Vec<MemoryLocalEvent>,
+}
+
+impl SysFileEvent {
+    /// The number of rows of the event, one for the syscall followed by one per byte of the path
+    /// compared, per file descriptor scanned and per byte copied.
+    #[must_use]
+    pub fn num_rows(&self) -> usize {
+        1 + self.lookup.as_ref().map_or(0, |lookup| lookup.path.len() + lookup.slot_records.len())
+            + self.copy.as_ref().map_or(0, |copy| copy.bytes.len())
+    }
+}
+
+/// The lookup of a path among the sorted paths of the files.
+///
+/// The path is compared byte by byte to the paths of the files before and after it, which proves
+/// that the file it names is the one at `index`, or that there is none.
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct PathLookupEvent {
+    /// The index of the first file whose path isn't less than the path looked up.
+    pub index: u32,
+    /// Whether the file at `index` has the path looked up.
+    pub found: bool,
+    /// The memory record for the number of files.
+    pub count_record: MemoryReadRecord,
+    /// The memory record for the address of the path of the file before `index`.
+    pub lo_record: Option<MemoryReadRecord>,
+    /// The memory record for the address of the path of the file at `index`.
+    pub hi_record: Option<MemoryReadRecord>,
+    /// The bytes of the path compared, one per row.
+    pub path: Vec<u8>,
+    /// The memory records for the path looked up.
+    pub path_records: Vec<MemoryReadRecord>,
+    /// The memory records for the path of the file before `index`.
+    pub lo_records: Vec<MemoryReadRecord>,
+    /// The memory records for the path of the file at `index`.
+    pub hi_records: Vec<MemoryReadRecord>,
+    /// The memory records for the file descriptors scanned, the last one allocated.
+    pub slot_records: Vec<MemoryRecordEnum>,
+    /// The memory record for the offset of the file descriptor allocated.
+    pub offset_record: Option<MemoryWriteRecord>,
+}
+
+/// A copy of bytes between two possibly unaligned buffers.
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct ByteCopyEvent {
+    /// The address of the bytes read.
+    pub src: u32,
+    /// The address of the bytes written.
+    pub dst: u32,
+    /// The bytes copied.
+    pub bytes: Vec<u8>,
+    /// The memory records for the words read.
+    pub src_records: Vec<MemoryReadRecord>,
+    /// The memory records for the words written.
+    pub dst_records: Vec<MemoryWriteRecord>,
+}
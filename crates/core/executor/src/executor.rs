@@ -10,10 +10,12 @@ use hashbrown::HashMap;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use zkm_curves::CurveError;
+use zkm_primitives::types::PanicReport;
 use zkm_stark::ZKMCoreOpts;
 
 use crate::{
     context::ZKMContext,
+    control::CancellationToken,
     dependencies::{
         emit_branch_dependencies, emit_cloclz_dependencies, emit_divrem_dependencies,
         emit_jump_dependencies, emit_memory_dependencies, emit_misc_dependencies,
@@ -26,14 +28,16 @@ use crate::{
     },
     hook::{HookEnv, HookRegistry},
     memory::{Entry, Memory},
+    output::{boxed_sink, BoxedOutputSink, ConsoleSink, OutputSink},
     pad_mips_event_counts,
+    profiler::Profiler,
     record::{ExecutionRecord, MemoryAccessRecord},
     sign_extend,
     state::{ExecutionState, ForkState},
     subproof::SubproofVerifier,
     syscalls::{default_syscall_map, Syscall, SyscallCode, SyscallContext},
-    ExecutionReport, Instruction, MaximalShapes, MipsAirId, Opcode, Program, Register,
-    NUM_REGISTERS,
+    CostEstimate, ExecutionReport, ExecutionTrace, GuestPanic, Instruction, MaximalShapes,
+    MipsAirId, Opcode, Program, Register, ShardCostEstimate, NUM_REGISTERS, VFS_BASE, VFS_END,
 };
 
 /// The maximum number of instructions in a program.
@@ -109,6 +113,9 @@ pub struct Executor<'a> {
     /// The maximum number of cpu cycles to use for execution.
     pub max_cycles: Option<u64>,
 
+    /// The token to cancel the execution with.
+    pub cancellation_token: Option<CancellationToken>,
+
     /// Skip deferred proof verification. This check is informational only, not related to circuit
     /// correctness.
     pub deferred_proof_verification: DeferredProofVerification,
@@ -128,12 +135,27 @@ pub struct Executor<'a> {
     /// A counter for the number of cycles that have been executed in certain functions.
     pub cycle_tracker: HashMap<String, (u64, u32)>,
 
-    /// A buffer for stdout and stderr IO.
-    pub io_buf: HashMap<u32, String>,
+    /// The sink of the guest stdout.
+    pub stdout: BoxedOutputSink<'a>,
+
+    /// The sink of the guest stderr.
+    pub stderr: BoxedOutputSink<'a>,
 
     /// A buffer for writing trace events to a file.
     pub trace_buf: Option<BufWriter<File>>,
 
+    /// The call-stack profiler, if profiling is enabled.
+    pub profiler: Option<Profiler>,
+
+    /// The estimated proving cost collected so far, if cost estimation is enabled.
+    pub cost_estimate: Option<CostEstimate>,
+
+    /// The per-instruction trace collected so far, if tracing is enabled.
+    pub execution_trace: Option<ExecutionTrace>,
+
+    /// The panic the guest reported before halting, if any.
+    pub panic_report: Option<PanicReport>,
+
     /// The state of the runtime when in unconstrained mode.
     pub unconstrained_state: ForkState,
 
@@ -195,6 +217,10 @@ pub enum ExecutionError {
     #[error("execution failed with exit code {0}")]
     HaltWithNonZeroExitCode(u32),
 
+    /// The guest panicked, reporting the panic before halting with a non-zero exit code.
+    #[error("{0}")]
+    GuestPanic(Box<GuestPanic>),
+
     /// The execution failed with an invalid memory access.
     #[error("invalid memory access for opcode {0} and address {1}")]
     InvalidMemoryAccess(Opcode, u32),
@@ -215,6 +241,10 @@ pub enum ExecutionError {
     #[error("exceeded cycle limit of {0}")]
     ExceededCycleLimit(u64),
 
+    /// The execution was cancelled through its cancellation token.
+    #[error("execution cancelled")]
+    Cancelled(),
+
     /// The execution failed because the syscall was called in unconstrained mode.
     #[error("syscall called in unconstrained mode")]
     InvalidSyscallUsage(u64),
@@ -227,6 +257,10 @@ pub enum ExecutionError {
     #[error("exceeded memory access bound of {0}")]
     MemoryOutOfBoundsAccess(u64),
 
+    /// The execution failed because the heap or the stack reached the virtual files.
+    #[error("the heap or the stack overlaps the virtual files at {0:#x}")]
+    VirtualFilesOverlap(u32),
+
     /// The execution failed with invalid syscall args.
     #[error("invalid syscall args encountered")]
     InvalidSyscallArgs(),
@@ -314,6 +348,8 @@ impl<'a> Executor<'a> {
 
         let hook_registry = context.hook_registry.unwrap_or_default();
 
+        let profiler = context.profiler.map(|config| Profiler::new(config, program.pc_start));
+
         let costs = crate::mips_costs();
 
         Self {
@@ -325,8 +361,13 @@ impl<'a> Executor<'a> {
             shard_size: (opts.shard_size as u32) * 4,
             shard_batch_size: opts.shard_batch_size as u32,
             cycle_tracker: HashMap::new(),
-            io_buf: HashMap::new(),
+            stdout: context.stdout.unwrap_or_else(|| boxed_sink(ConsoleSink::new("stdout"))),
+            stderr: context.stderr.unwrap_or_else(|| boxed_sink(ConsoleSink::new("stderr"))),
             trace_buf,
+            profiler,
+            cost_estimate: context.estimate_cost.then(CostEstimate::default),
+            execution_trace: context.trace.then(ExecutionTrace::default),
+            panic_report: None,
             unconstrained: false,
             unconstrained_state: ForkState::default(),
             syscall_map,
@@ -340,6 +381,7 @@ impl<'a> Executor<'a> {
             hook_registry,
             opts,
             max_cycles: context.max_cycles,
+            cancellation_token: context.cancellation_token,
             deferred_proof_verification: if context.skip_deferred_proof_verification {
                 DeferredProofVerification::Disabled
             } else {
@@ -381,6 +423,9 @@ impl<'a> Executor<'a> {
     pub fn recover(program: Program, state: ExecutionState, opts: ZKMCoreOpts) -> Self {
         let mut runtime = Self::new(program, opts);
         runtime.state = state;
+        // The checkpoint creator already emitted the guest output, so don't emit it twice.
+        runtime.stdout = boxed_sink(|_: &[u8]| {});
+        runtime.stderr = boxed_sink(|_: &[u8]| {});
         // Disable deferred proof verification since we're recovering from a checkpoint, and the
         // checkpoint creator already had a chance to check the proofs.
         runtime.deferred_proof_verification = DeferredProofVerification::Disabled;
@@ -708,6 +753,12 @@ impl<'a> Executor<'a> {
         timestamp: u32,
         local_memory_access: Option<&mut HashMap<u32, MemoryLocalEvent>>,
     ) -> MemoryWriteRecord {
+        if let Some(trace) = &mut self.execution_trace {
+            if !self.unconstrained {
+                trace.memory_write(addr, value);
+            }
+        }
+
         // Get the memory record entry.
         let entry = self.state.memory.page_table.entry(addr);
         if self.executor_mode == ExecutorMode::Checkpoint || self.unconstrained {
@@ -801,6 +852,12 @@ impl<'a> Executor<'a> {
         timestamp: u32,
         local_memory_access: Option<&mut HashMap<u32, MemoryLocalEvent>>,
     ) -> MemoryWriteRecord {
+        if let Some(trace) = &mut self.execution_trace {
+            if !self.unconstrained {
+                trace.register_write(register, value);
+            }
+        }
+
         let addr = register as u32;
         // Get the memory record entry.
         let entry = self.state.memory.registers.entry(addr);
@@ -1079,6 +1136,12 @@ impl<'a> Executor<'a> {
         // P.18 of the RISC-V spec. We always write 0 to %x0.
         let value = if register == Register::ZERO { 0 } else { value };
 
+        if let Some(trace) = &mut self.execution_trace {
+            if !self.unconstrained {
+                trace.register_write(register, value);
+            }
+        }
+
         // Read the address from memory and create a memory read record.
         if self.executor_mode == ExecutorMode::Trace {
             let record =
@@ -1605,11 +1668,32 @@ impl<'a> Executor<'a> {
                         a = syscall_id;
                     }
 
-                    // If the syscall is `HALT` and the exit code is non-zero, return an error.
+                    if let Some(air) = syscall.as_air_id() {
+                        let cost = precompile_rt.rt.costs.get(&air).copied().unwrap_or_default();
+                        if let Some(profiler) = &mut precompile_rt.rt.profiler {
+                            profiler.record_precompile(air, cost);
+                        }
+                    }
+
+                    // If the syscall is `HALT` and the exit code is non-zero, return an error. The
+                    // panic report is cleared on every exit, so that it never outlives the
+                    // execution of the panic.
+                    let panic_report =
+                        if matches!(syscall, SyscallCode::HALT | SyscallCode::SYS_EXT_GROUP) {
+                            precompile_rt.rt.panic_report.take()
+                        } else {
+                            None
+                        };
                     if syscall == SyscallCode::HALT && precompile_rt.exit_code != 0 {
-                        return Err(ExecutionError::HaltWithNonZeroExitCode(
-                            precompile_rt.exit_code,
-                        ));
+                        let exit_code = precompile_rt.exit_code;
+                        return Err(match panic_report {
+                            Some(report) => ExecutionError::GuestPanic(Box::new(GuestPanic::new(
+                                report,
+                                exit_code,
+                                &precompile_rt.rt.program,
+                            ))),
+                            None => ExecutionError::HaltWithNonZeroExitCode(exit_code),
+                        });
                     }
 
                     (
@@ -2075,6 +2159,9 @@ impl<'a> Executor<'a> {
         if aligned_addr + 3 > MAX_MEMORY as u32 {
             return Err(ExecutionError::MemoryOutOfBoundsAccess(addr as u64));
         }
+        if (VFS_BASE..VFS_END).contains(&aligned_addr) && self.program.has_files() {
+            return Err(ExecutionError::VirtualFilesOverlap(aligned_addr));
+        }
 
         self.mw_cpu(
             aligned_addr, // align addr
@@ -2149,17 +2236,31 @@ impl<'a> Executor<'a> {
     /// Executes one cycle of the program, returning whether the program has finished.
     #[inline]
     #[allow(clippy::too_many_lines)]
-    fn execute_cycle(&mut self) -> Result<bool, ExecutionError> {
+    pub(crate) fn execute_cycle(&mut self) -> Result<bool, ExecutionError> {
         // Fetch the instruction at the current program counter.
+        let pc = self.state.pc;
         let instruction = self.fetch();
 
         // Log the current state of the runtime.
         #[cfg(debug_assertions)]
         self.log(&instruction);
 
+        // Start the trace entry of the instruction.
+        if let Some(trace) = &mut self.execution_trace {
+            if !self.unconstrained {
+                let word = self.program.image.get(&pc).copied().or(instruction.raw);
+                trace.begin(pc, word.unwrap_or_default());
+            }
+        }
+
         // Execute the instruction.
         self.execute_operation(&instruction)?;
 
+        // Attribute the cycle to the current call stack.
+        if let Some(profiler) = &mut self.profiler {
+            profiler.record(pc, &instruction, self.state.pc, self.unconstrained);
+        }
+
         // Increment the clock.
         self.state.global_clk += 1;
 
@@ -2284,7 +2385,7 @@ impl<'a> Executor<'a> {
         Ok((checkpoint, done))
     }
 
-    fn initialize(&mut self) {
+    pub(crate) fn initialize(&mut self) {
         self.state.clk = 0;
         self.state.records_clk_index = 0;
 
@@ -2334,6 +2435,11 @@ impl<'a> Executor<'a> {
         // Get the current shard.
         let start_shard = self.state.current_shard;
 
+        // Stop if the execution was cancelled.
+        if self.cancellation_token.as_ref().is_some_and(CancellationToken::is_cancelled) {
+            return Err(ExecutionError::Cancelled());
+        }
+
         // If it's the first cycle, initialize the program.
         if self.state.global_clk == 0 {
             self.initialize();
@@ -2367,6 +2473,7 @@ impl<'a> Executor<'a> {
         let public_values = self.record.public_values;
 
         if done {
+            self.estimate_shard_cost();
             self.postprocess();
 
             // Push the remaining execution record with memory initialize & finalize events.
@@ -2405,7 +2512,7 @@ impl<'a> Executor<'a> {
     }
 
     #[inline]
-    fn inc_shard_if_need(&mut self) -> bool {
+    pub(crate) fn inc_shard_if_need(&mut self) -> bool {
         if self.executor_mode == ExecutorMode::Trace && !self.state.records_clk.is_empty() {
             let records_clk_index = self.state.records_clk_index as usize;
             if records_clk_index < self.state.records_clk.len()
@@ -2509,6 +2616,7 @@ impl<'a> Executor<'a> {
         }
 
         if cpu_exit || !shape_match_found {
+            self.estimate_shard_cost();
             if self.executor_mode == ExecutorMode::Checkpoint {
                 self.state.records_clk.push(self.state.clk);
             }
@@ -2519,27 +2627,65 @@ impl<'a> Executor<'a> {
         false
     }
 
+    /// Record the estimated cost of the current shard, if cost estimation is enabled.
+    fn estimate_shard_cost(&mut self) {
+        let Some(cost_estimate) = &mut self.cost_estimate else {
+            return;
+        };
+        if self.state.clk == 0 {
+            return;
+        }
+
+        let cycles = (self.state.clk / 5) as u64;
+        let event_counts = estimate_mips_event_counts(
+            cycles,
+            self.local_counts.local_mem as u64,
+            self.local_counts.syscalls_sent as u64,
+            *self.local_counts.event_counts,
+        );
+        let shape = self
+            .maximal_shapes
+            .as_ref()
+            .and_then(|shapes| shapes.smallest_fit(&event_counts, &self.costs))
+            .copied();
+        cost_estimate.core_shards.push(ShardCostEstimate::new(
+            cycles,
+            event_counts,
+            shape,
+            &self.costs,
+        ));
+    }
+
     fn postprocess(&mut self) {
         // Flush remaining stdout/stderr
-        for (fd, buf) in &self.io_buf {
-            if !buf.is_empty() {
-                match fd {
-                    1 => {
-                        println!("stdout: {buf}");
-                    }
-                    2 => {
-                        println!("stderr: {buf}");
-                    }
-                    _ => {}
-                }
-            }
-        }
+        self.stdout.lock().unwrap().flush();
+        self.stderr.lock().unwrap().flush();
 
         // Flush trace buf
         if let Some(ref mut buf) = self.trace_buf {
             buf.flush().unwrap();
         }
 
+        // Move the cost estimate into the report.
+        if let Some(mut cost_estimate) = self.cost_estimate.take() {
+            cost_estimate.add_precompiles(
+                self.state.syscall_counts.iter().map(|(syscall, count)| (*syscall, *count)),
+                self.opts.split_opts,
+                &self.costs,
+            );
+            self.report.cost_estimate = Some(Box::new(cost_estimate));
+        }
+
+        // Move the call tree into the report.
+        if let Some(profiler) = self.profiler.take() {
+            self.report.profile = Some(Box::new(profiler.finish()));
+        }
+
+        // Move the instruction trace into the report.
+        if let Some(trace) = self.execution_trace.take() {
+            self.report.trace = Some(Box::new(trace));
+        }
+
         // Ensure that all proofs and input bytes were read, otherwise warn the user.
         if self.state.proof_stream_ptr != self.state.proof_stream.len() {
             tracing::warn!(
@@ -2660,9 +2806,15 @@ mod tests {
         secp256r1_double_program, simple_memory_program, simple_program, ssz_withdrawals_program,
         u256xu2048_mul_program,
     };
+    use test_artifacts::PANIC_ELF;
+    use zkm_primitives::types::PanicReport;
     use zkm_stark::ZKMCoreOpts;
 
-    use crate::{Instruction, Opcode, Register};
+    use crate::{
+        syscalls::SyscallCode, CancellationToken, ExecutionError, ExecutionReport, ExecutionTrace,
+        Instruction, MipsAirId, Opcode, OutputBuffer, Register, TraceEntry, ZKMContext, FD_STDERR,
+        FD_STDOUT,
+    };
 
     use super::{Executor, Program};
 
@@ -2702,6 +2854,154 @@ mod tests {
         let mut runtime = Executor::new(program, ZKMCoreOpts::default());
         runtime.run().unwrap();
     }
+    #[test]
+    fn test_secp256r1_add_program_estimate_cost() {
+        let program = secp256r1_add_program();
+        let context = ZKMContext::builder().estimate_cost(true).build();
+        let mut runtime = Executor::with_context(program, ZKMCoreOpts::default(), context);
+        runtime.run_fast().unwrap();
+
+        let estimate = runtime.report.cost_estimate.as_deref().unwrap();
+        assert_eq!(estimate.core_shards.len(), 1);
+        assert!(estimate.precompile_shards > 0);
+        assert!(estimate.precompile_heights[MipsAirId::Secp256r1AddAssign] > 0);
+        assert!(estimate.lde_size() > estimate.precompile_lde_size);
+
+        // The estimate survives the serialization of the report, e.g. in a checkpoint.
+        let bytes = bincode::serialize(&runtime.report).unwrap();
+        let report: ExecutionReport = bincode::deserialize(&bytes).unwrap();
+        assert_eq!(report.cost_estimate, runtime.report.cost_estimate);
+    }
+
+    #[test]
+    #[allow(clippy::unreadable_literal)]
+    fn test_write_to_output_sinks() {
+        let write = |fd: u32, len: u32| {
+            vec![
+                Instruction::new(Opcode::ADD, 2, 0, SyscallCode::WRITE as u32, false, true),
+                Instruction::new(Opcode::ADD, 4, 0, fd, false, true),
+                Instruction::new(Opcode::ADD, 5, 0, 0x27654320, false, true),
+                Instruction::new(Opcode::ADD, 6, 0, len, false, true),
+                Instruction::new(Opcode::SYSCALL, 2, 4, 5, false, false),
+            ]
+        };
+        // Store "hi\n", write it to stdout, then write "h" to stderr.
+        let mut instructions = vec![
+            Instruction::new(Opcode::ADD, 29, 0, 0x000a6968, false, true),
+            Instruction::new(Opcode::SW, 29, 0, 0x27654320, false, true),
+        ];
+        instructions.extend(write(FD_STDOUT, 3));
+        instructions.extend(write(FD_STDERR, 1));
+        let program = Program::new(instructions, 0, 0);
+
+        let (stdout, stderr) = (OutputBuffer::new(), OutputBuffer::new());
+        let context = ZKMContext::builder().stdout(stdout.clone()).stderr(stderr.clone()).build();
+        let mut runtime = Executor::with_context(program, ZKMCoreOpts::default(), context);
+        runtime.run().unwrap();
+
+        assert_eq!(stdout.take(), b"hi\n");
+        assert_eq!(stderr.take(), b"h");
+    }
+
+    #[test]
+    fn test_cancelled_execution() {
+        let program = simple_program();
+        let token = CancellationToken::new();
+        token.cancel();
+        let context = ZKMContext::builder().cancellation_token(token).build();
+        let mut runtime = Executor::with_context(program, ZKMCoreOpts::default(), context);
+        assert!(matches!(runtime.run(), Err(ExecutionError::Cancelled())));
+    }
+
+    #[test]
+    fn test_guest_panic() {
+        // Halt with exit code 1. The linking jump after the halt is never executed, but its
+        // return address is on the reported stack.
+        let instructions = vec![
+            Instruction::new(Opcode::ADD, 2, 0, SyscallCode::HALT as u32, false, true),
+            Instruction::new(Opcode::ADD, 4, 0, 1, false, true),
+            Instruction::new(Opcode::SYSCALL, 2, 4, 5, false, false),
+            Instruction::new(Opcode::Jumpi, 31, 0, 0, true, true),
+        ];
+        let program = Program::new(instructions, 0, 0);
+        let mut runtime = Executor::new(program, ZKMCoreOpts::default());
+        runtime.panic_report = Some(PanicReport {
+            message: "boom".to_string(),
+            file: "src/main.rs".to_string(),
+            line: 7,
+            column: 5,
+            stack: vec![0x1234, 4, 20, 3],
+        });
+
+        let Err(ExecutionError::GuestPanic(panic)) = runtime.run() else {
+            panic!("expected a guest panic");
+        };
+        assert_eq!(panic.exit_code, 1);
+        assert_eq!(panic.message, "boom");
+        assert_eq!(panic.backtrace.iter().map(|frame| frame.address).collect::<Vec<_>>(), [12]);
+        assert!(panic.to_string().starts_with("guest panicked at src/main.rs:7:5: boom"));
+    }
+
+    #[test]
+    fn test_guest_panic_backtrace() {
+        // The panic hook of the guest reports the panic, which is symbolized with its ELF.
+        let mut runtime = Executor::new(panic_program(), ZKMCoreOpts::default());
+        let err = runtime.run().unwrap_err().symbolize(PANIC_ELF);
+        let ExecutionError::GuestPanic(panic) = err else {
+            panic!("expected a guest panic, got {err}");
+        };
+        assert_ne!(panic.exit_code, 0);
+        assert!(panic.message.contains("left == right"));
+        assert!(panic.file.ends_with("main.rs"));
+        assert_eq!(panic.line, 6);
+        assert!(panic.backtrace.iter().any(|frame| frame
+            .function
+            .as_deref()
+            .is_some_and(|function| function.contains("core::panicking"))));
+
+        // The report doesn't outlive the execution that panicked.
+        assert!(runtime.panic_report.is_none());
+    }
+
+    #[test]
+    fn test_execution_trace() {
+        let program = simple_program();
+        let context = ZKMContext::builder().trace(true).build();
+        let mut runtime = Executor::with_context(program, ZKMCoreOpts::default(), context);
+        runtime.run().unwrap();
+
+        let trace = runtime.report.trace.take().unwrap();
+        let writes = trace.entries.iter().map(|entry| entry.register_writes.clone());
+        assert_eq!(writes.collect::<Vec<_>>(), [vec![(29, 5)], vec![(30, 37)], vec![(31, 42)]]);
+        assert_eq!(
+            trace.entries[2],
+            TraceEntry {
+                pc: 8,
+                instruction: 0,
+                register_writes: vec![(31, 42)],
+                memory_writes: vec![],
+            }
+        );
+
+        let mut bytes = Vec::new();
+        trace.write(&mut bytes).unwrap();
+        assert_eq!(ExecutionTrace::read(bytes.as_slice()).unwrap(), *trace);
+        assert_eq!(trace.to_string().lines().last(), Some("00000008 00000000 r31=0000002a"));
+    }
+
+    #[test]
+    fn test_execution_trace_is_deterministic() {
+        let run = || {
+            let context = ZKMContext::builder().trace(true).build();
+            let mut runtime =
+                Executor::with_context(fibonacci_program(), ZKMCoreOpts::default(), context);
+            runtime.run().unwrap();
+            runtime.report.trace.take().unwrap()
+        };
+        let trace = run();
+        assert!(trace.entries.iter().any(|entry| !entry.memory_writes.is_empty()));
+        assert_eq!(trace, run());
+    }
     //
     #[test]
     fn test_secp256r1_double_program_run() {
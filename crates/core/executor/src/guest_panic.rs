@@ -0,0 +1,145 @@
+//! Panics of the guest program, reported through
+//! [`FD_PANIC`](zkm_primitives::consts::fd::FD_PANIC).
+
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+use addr2line::gimli::{self, EndianSlice, LittleEndian as GimliLittleEndian};
+use anyhow::{anyhow, Result};
+use elf::{endian::LittleEndian, ElfBytes};
+use serde::{Deserialize, Serialize};
+use zkm_primitives::types::PanicReport;
+
+use crate::{ExecutionError, Program, SymbolTable};
+
+/// The maximum number of frames of a guest backtrace.
+const MAX_BACKTRACE_FRAMES: usize = 64;
+
+/// A frame of the backtrace of a guest panic.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BacktraceFrame {
+    /// The address of the call instruction.
+    pub address: u32,
+    /// The demangled name of the function making the call, once symbolized.
+    pub function: Option<String>,
+    /// The source file of the call, once symbolized with DWARF line info.
+    pub file: Option<String>,
+    /// The source line of the call, once symbolized with DWARF line info.
+    pub line: Option<u32>,
+}
+
+/// A panic of the guest program.
+///
+/// The backtrace only holds addresses until [`GuestPanic::symbolize`] resolves them with the
+/// symbols and line info of the guest ELF.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GuestPanic {
+    /// The panic message.
+    pub message: String,
+    /// The source file of the panic.
+    pub file: String,
+    /// The line of the panic.
+    pub line: u32,
+    /// The column of the panic.
+    pub column: u32,
+    /// The exit code the guest halted with.
+    pub exit_code: u32,
+    /// The calls leading to the panic, innermost first.
+    pub backtrace: Vec<BacktraceFrame>,
+}
+
+impl GuestPanic {
+    /// Build the panic from the report of the guest, picking the return addresses of `program`
+    /// out of the reported stack words.
+    #[must_use]
+    pub fn new(report: PanicReport, exit_code: u32, program: &Program) -> Self {
+        let backtrace = report
+            .stack
+            .iter()
+            .filter_map(|&word| call_address(program, word))
+            .take(MAX_BACKTRACE_FRAMES)
+            .map(|address| BacktraceFrame { address, function: None, file: None, line: None })
+            .collect();
+        Self {
+            message: report.message,
+            file: report.file,
+            line: report.line,
+            column: report.column,
+            exit_code,
+            backtrace,
+        }
+    }
+
+    /// Resolve the backtrace with the symbol table and the DWARF line info of `elf`.
+    ///
+    /// Frames are left unresolved if the ELF is stripped or has no debug info.
+    pub fn symbolize(&mut self, elf: &[u8]) -> Result<()> {
+        let symbols = SymbolTable::from_elf(elf)?;
+        let elf = ElfBytes::<LittleEndian>::minimal_parse(elf)
+            .map_err(|err| anyhow!("Elf parse error: {err}"))?;
+        let dwarf = gimli::Dwarf::load(|id| -> Result<_, gimli::Error> {
+            let data = elf
+                .section_header_by_name(id.name())
+                .ok()
+                .flatten()
+                .and_then(|header| elf.section_data(&header).ok())
+                .filter(|(_, compression)| compression.is_none())
+                .map_or(&[][..], |(data, _)| data);
+            Ok(EndianSlice::new(data, GimliLittleEndian))
+        })?;
+        let context = addr2line::Context::from_dwarf(dwarf)?;
+
+        for frame in self.backtrace.iter_mut() {
+            frame.function = symbols.lookup(frame.address).map(|symbol| symbol.demangled.clone());
+            if let Some(location) = context.find_location(frame.address as u64)? {
+                frame.file = location.file.map(str::to_string);
+                frame.line = location.line;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl ExecutionError {
+    /// Symbolize the backtrace of a [`ExecutionError::GuestPanic`] with `elf`, the ELF of the
+    /// guest that panicked.
+    #[must_use]
+    pub fn symbolize(mut self, elf: &[u8]) -> Self {
+        if let Self::GuestPanic(panic) = &mut self {
+            if let Err(err) = panic.symbolize(elf) {
+                tracing::warn!("failed to symbolize the guest backtrace: {err}");
+            }
+        }
+        self
+    }
+}
+
+impl Display for GuestPanic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(
+            f,
+            "guest panicked at {}:{}:{}: {}",
+            self.file, self.line, self.column, self.message
+        )?;
+        for (i, frame) in self.backtrace.iter().enumerate() {
+            write!(f, "\n  {i:>2}: {:#010x}", frame.address)?;
+            if let Some(function) = &frame.function {
+                write!(f, " in {function}")?;
+            }
+            if let (Some(file), Some(line)) = (&frame.file, frame.line) {
+                write!(f, " at {file}:{line}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The address of the call returning to `return_addr`, if `return_addr` follows the delay slot
+/// of a linking jump of `program`.
+fn call_address(program: &Program, return_addr: u32) -> Option<u32> {
+    let address = return_addr.checked_sub(8)?;
+    if !address.is_multiple_of(4) || address < program.pc_base {
+        return None;
+    }
+    let instruction = program.instructions.get(((address - program.pc_base) / 4) as usize)?;
+    (instruction.is_jump_instruction() && instruction.op_a != 0).then_some(address)
+}
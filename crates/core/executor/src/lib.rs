@@ -1,14 +1,19 @@
 mod air;
 mod context;
+mod control;
 mod cost;
+pub mod debugger;
 mod dependencies;
 pub mod events;
 mod executor;
+mod guest_panic;
 pub mod hook;
 mod instruction;
 mod io;
 pub mod memory;
 mod opcode;
+mod output;
+mod profiler;
 mod program;
 #[cfg(test)]
 pub mod programs;
@@ -18,16 +23,23 @@ mod register;
 pub mod report;
 mod state;
 pub mod subproof;
+mod symbols;
 pub mod syscalls;
+mod trace;
 mod utils;
+mod vfs;
 
 pub use air::*;
 pub use context::*;
+pub use control::*;
 pub use cost::*;
 pub use executor::*;
+pub use guest_panic::*;
 pub use hook::*;
 pub use instruction::*;
 pub use opcode::*;
+pub use output::*;
+pub use profiler::*;
 pub use program::*;
 pub use record::*;
 pub use reduce::*;
@@ -35,7 +47,10 @@ pub use register::*;
 pub use report::*;
 pub use state::*;
 pub use subproof::*;
+pub use symbols::*;
+pub use trace::*;
 pub use utils::*;
+pub use vfs::*;
 
 #[derive(Debug, Copy, Clone)]
 #[repr(u8)]
@@ -0,0 +1,204 @@
+use std::{
+    io::Write,
+    sync::{Arc, Mutex},
+};
+
+/// The tracing target of the guest output forwarded by a [`TracingSink`].
+pub const GUEST_OUTPUT_TARGET: &str = "zkm_guest";
+
+/// A guest output sink, wrapped in a smart pointer.
+pub type BoxedOutputSink<'a> = Arc<Mutex<dyn OutputSink + Send + 'a>>;
+
+/// A destination for the bytes a guest writes to stdout or stderr.
+pub trait OutputSink {
+    /// Write the bytes of a single `write` from the guest.
+    fn write(&mut self, buf: &[u8]);
+
+    /// Flush any buffered output. Called once the execution finishes.
+    fn flush(&mut self) {}
+}
+
+impl<F: FnMut(&[u8])> OutputSink for F {
+    /// Invokes the function `self` with the written bytes.
+    fn write(&mut self, buf: &[u8]) {
+        self(buf);
+    }
+}
+
+/// Wrap a sink in a smart pointer so it may be placed in a [`ZKMContext`](crate::ZKMContext).
+pub fn boxed_sink<'a>(sink: impl OutputSink + Send + 'a) -> BoxedOutputSink<'a> {
+    Arc::new(Mutex::new(sink))
+}
+
+/// Splits the output into lines, keeping the trailing partial line until it is completed or
+/// flushed.
+#[derive(Debug, Default)]
+struct LineBuffer {
+    buf: String,
+}
+
+impl LineBuffer {
+    /// Append `buf` and return the completed lines, or `None` if it is not valid UTF-8.
+    fn push(&mut self, buf: &[u8]) -> Option<Vec<String>> {
+        let s = core::str::from_utf8(buf).ok()?;
+        self.buf.push_str(s);
+        if !self.buf.contains('\n') {
+            return Some(vec![]);
+        }
+        let buf = std::mem::take(&mut self.buf);
+        let mut lines = buf.split('\n').map(ToString::to_string).collect::<Vec<_>>();
+        self.buf = lines.pop().unwrap_or_default();
+        Some(lines)
+    }
+
+    /// Take the trailing partial line, if any.
+    fn take(&mut self) -> Option<String> {
+        (!self.buf.is_empty()).then(|| std::mem::take(&mut self.buf))
+    }
+}
+
+/// Prints every line to the stdout of the host, prefixed with the name of the guest stream.
+///
+/// This is the default sink of both stdout and stderr.
+#[derive(Debug)]
+pub struct ConsoleSink {
+    prefix: &'static str,
+    lines: LineBuffer,
+}
+
+impl ConsoleSink {
+    /// Create a sink printing lines as `{prefix}: {line}`.
+    #[must_use]
+    pub fn new(prefix: &'static str) -> Self {
+        Self { prefix, lines: LineBuffer::default() }
+    }
+}
+
+impl OutputSink for ConsoleSink {
+    fn write(&mut self, buf: &[u8]) {
+        match self.lines.push(buf) {
+            Some(lines) => lines.into_iter().for_each(|line| println!("{}: {line}", self.prefix)),
+            None => {
+                eprintln!("Warning: {} Received invalid UTF-8 data in slice: {buf:?}", self.prefix)
+            }
+        }
+    }
+
+    fn flush(&mut self) {
+        if let Some(line) = self.lines.take() {
+            println!("{}: {line}", self.prefix);
+        }
+    }
+}
+
+/// Forwards every line as a `tracing` event with the [`GUEST_OUTPUT_TARGET`] target.
+#[derive(Debug)]
+pub struct TracingSink {
+    stream: &'static str,
+    lines: LineBuffer,
+}
+
+impl TracingSink {
+    /// Create a sink emitting lines with the given `stream` field, e.g. `"stdout"`.
+    #[must_use]
+    pub fn new(stream: &'static str) -> Self {
+        Self { stream, lines: LineBuffer::default() }
+    }
+}
+
+impl OutputSink for TracingSink {
+    fn write(&mut self, buf: &[u8]) {
+        match self.lines.push(buf) {
+            Some(lines) => lines.into_iter().for_each(|line| {
+                tracing::info!(target: GUEST_OUTPUT_TARGET, stream = self.stream, "{line}");
+            }),
+            None => tracing::warn!(
+                target: GUEST_OUTPUT_TARGET,
+                stream = self.stream,
+                "received invalid UTF-8 data: {buf:?}"
+            ),
+        }
+    }
+
+    fn flush(&mut self) {
+        if let Some(line) = self.lines.take() {
+            tracing::info!(target: GUEST_OUTPUT_TARGET, stream = self.stream, "{line}");
+        }
+    }
+}
+
+/// Writes the raw output to an [`std::io::Write`], such as a file.
+#[derive(Debug)]
+pub struct WriterSink<W: Write>(pub W);
+
+impl<W: Write> OutputSink for WriterSink<W> {
+    fn write(&mut self, buf: &[u8]) {
+        if let Err(err) = self.0.write_all(buf) {
+            tracing::warn!("failed to write guest output: {err}");
+        }
+    }
+
+    fn flush(&mut self) {
+        if let Err(err) = self.0.flush() {
+            tracing::warn!("failed to flush guest output: {err}");
+        }
+    }
+}
+
+/// Captures the raw output in memory.
+///
+/// The buffer is shared between its clones, so a clone can be given to the context and the output
+/// read back from the original after the execution.
+#[derive(Debug, Clone, Default)]
+pub struct OutputBuffer(Arc<Mutex<Vec<u8>>>);
+
+impl OutputBuffer {
+    /// Create an empty buffer.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Take the captured output, leaving the buffer empty.
+    #[must_use]
+    pub fn take(&self) -> Vec<u8> {
+        std::mem::take(&mut *self.0.lock().unwrap())
+    }
+
+    /// A copy of the captured output.
+    #[must_use]
+    pub fn contents(&self) -> Vec<u8> {
+        self.0.lock().unwrap().clone()
+    }
+}
+
+impl OutputSink for OutputBuffer {
+    fn write(&mut self, buf: &[u8]) {
+        self.0.lock().unwrap().extend_from_slice(buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LineBuffer, OutputBuffer, OutputSink};
+
+    #[test]
+    fn line_buffer_keeps_partial_lines() {
+        let mut lines = LineBuffer::default();
+        assert_eq!(lines.push(b"hello").unwrap(), Vec::<String>::new());
+        assert_eq!(lines.push(b" world\nfoo\nba").unwrap(), vec!["hello world", "foo"]);
+        assert_eq!(lines.take().as_deref(), Some("ba"));
+        assert!(lines.take().is_none());
+        assert!(lines.push(&[0xff]).is_none());
+    }
+
+    #[test]
+    fn output_buffer_is_shared() {
+        let buffer = OutputBuffer::new();
+        let mut sink = buffer.clone();
+        sink.write(b"hello ");
+        sink.write(&[0xff]);
+        assert_eq!(buffer.take(), b"hello \xff");
+        assert!(buffer.contents().is_empty());
+    }
+}
@@ -0,0 +1,402 @@
+//! A call-stack profiler for guest programs.
+//!
+//! The [`Profiler`] reconstructs the guest call stack from the jump instructions it executes and
+//! the function symbols of the ELF: a jump that writes a link register is a call, a jump to the
+//! return address of a frame on the stack is a return, and any other jump to the start of a
+//! function is a tail call. Every cycle, and the cost of every precompile, is attributed to the
+//! current stack. The resulting [`Profile`] can be written as folded stacks (for `inferno` or
+//! `flamegraph.pl`) or as a speedscope profile.
+
+use std::io::{self, Write};
+
+use hashbrown::HashMap;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::{Instruction, MipsAirId, SymbolTable};
+
+/// The name of the frame used for code outside of any known function.
+const UNKNOWN_FRAME: &str = "[unknown]";
+
+/// The configuration of the [`Profiler`].
+#[derive(Debug, Clone, Default)]
+pub struct ProfilerConfig {
+    /// The function symbols of the guest ELF.
+    pub symbols: SymbolTable,
+    /// The number of cycles between two samples of the call stack. `1` profiles every cycle.
+    pub sample_interval: u64,
+}
+
+impl ProfilerConfig {
+    /// Create a new [`ProfilerConfig`] that attributes every cycle.
+    #[must_use]
+    pub fn new(symbols: SymbolTable) -> Self {
+        Self { symbols, sample_interval: 1 }
+    }
+
+    /// Sample the call stack every `interval` cycles instead of on every cycle.
+    #[must_use]
+    pub fn sample_interval(mut self, interval: u64) -> Self {
+        self.sample_interval = interval.max(1);
+        self
+    }
+}
+
+/// A node of the call tree.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProfileNode {
+    /// The index of the frame in [`Profile::frames`].
+    pub frame: usize,
+    /// The index of the parent node, or `None` for a root.
+    pub parent: Option<usize>,
+    /// The cycles spent in the frame itself, excluding callees.
+    pub cycles: u64,
+    /// The estimated trace area of the precompiles called directly from the frame.
+    pub precompile_costs: HashMap<MipsAirId, u64>,
+}
+
+/// The call tree of a profiled execution.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Profile {
+    /// The names of the functions appearing in the tree.
+    pub frames: Vec<String>,
+    /// The nodes of the tree. Parents always come before their children.
+    pub nodes: Vec<ProfileNode>,
+}
+
+impl Profile {
+    /// The total number of cycles attributed to the tree.
+    #[must_use]
+    pub fn total_cycles(&self) -> u64 {
+        self.nodes.iter().map(|node| node.cycles).sum()
+    }
+
+    /// The total estimated trace area of all precompile calls.
+    #[must_use]
+    pub fn total_precompile_cost(&self) -> u64 {
+        self.nodes.iter().flat_map(|node| node.precompile_costs.values()).sum()
+    }
+
+    /// The cycles spent in each function, excluding callees, sorted from hottest to coldest.
+    #[must_use]
+    pub fn self_cycles(&self) -> Vec<(&str, u64)> {
+        let mut cycles = vec![0; self.frames.len()];
+        for node in &self.nodes {
+            cycles[node.frame] += node.cycles;
+        }
+        let mut cycles = self
+            .frames
+            .iter()
+            .map(String::as_str)
+            .zip(cycles)
+            .filter(|(_, cycles)| *cycles > 0)
+            .collect::<Vec<_>>();
+        cycles.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+        cycles
+    }
+
+    /// The stack of frame indices from the root to the given node.
+    fn stack(&self, mut idx: usize) -> Vec<usize> {
+        let mut stack = vec![self.nodes[idx].frame];
+        while let Some(parent) = self.nodes[idx].parent {
+            stack.push(self.nodes[parent].frame);
+            idx = parent;
+        }
+        stack.reverse();
+        stack
+    }
+
+    fn folded_stack(&self, idx: usize) -> String {
+        self.stack(idx)
+            .into_iter()
+            .map(|frame| self.frames[frame].as_str())
+            .collect::<Vec<_>>()
+            .join(";")
+    }
+
+    /// Write the cycles of each stack in the folded format, one `a;b;c <cycles>` line per stack.
+    pub fn write_folded(&self, mut writer: impl Write) -> io::Result<()> {
+        for (idx, node) in self.nodes.iter().enumerate().filter(|(_, node)| node.cycles > 0) {
+            writeln!(writer, "{} {}", self.folded_stack(idx), node.cycles)?;
+        }
+        Ok(())
+    }
+
+    /// Write the precompile costs of each stack in the folded format, with the AIR of the
+    /// precompile as the leaf frame.
+    pub fn write_precompiles_folded(&self, mut writer: impl Write) -> io::Result<()> {
+        for (idx, node) in self.nodes.iter().enumerate() {
+            let mut costs = node.precompile_costs.iter().collect::<Vec<_>>();
+            costs.sort();
+            for (air, cost) in costs {
+                writeln!(writer, "{};[{}] {cost}", self.folded_stack(idx), air.as_str())?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Write the profile in the speedscope file format, with one profile for cycles and one for
+    /// precompile costs.
+    ///
+    /// See <https://www.speedscope.app/file-format-schema.json>.
+    pub fn write_speedscope(&self, writer: impl Write, name: &str) -> io::Result<()> {
+        let mut frames = self.frames.clone();
+        let mut air_frames = HashMap::new();
+        let (mut cycle_samples, mut cycle_weights) = (Vec::new(), Vec::new());
+        let (mut cost_samples, mut cost_weights) = (Vec::new(), Vec::new());
+        for (idx, node) in self.nodes.iter().enumerate() {
+            let stack = self.stack(idx);
+            if node.cycles > 0 {
+                cycle_samples.push(stack.clone());
+                cycle_weights.push(node.cycles);
+            }
+            let mut costs = node.precompile_costs.iter().collect::<Vec<_>>();
+            costs.sort();
+            for (air, cost) in costs {
+                let frame = *air_frames.entry(*air).or_insert_with(|| {
+                    frames.push(format!("[{}]", air.as_str()));
+                    frames.len() - 1
+                });
+                let mut stack = stack.clone();
+                stack.push(frame);
+                cost_samples.push(stack);
+                cost_weights.push(*cost);
+            }
+        }
+
+        let profile = |name: &str, samples: Vec<Vec<usize>>, weights: Vec<u64>| {
+            json!({
+                "type": "sampled",
+                "name": name,
+                "unit": "none",
+                "startValue": 0,
+                "endValue": weights.iter().sum::<u64>(),
+                "samples": samples,
+                "weights": weights,
+            })
+        };
+        let value = json!({
+            "$schema": "https://www.speedscope.app/file-format-schema.json",
+            "name": name,
+            "exporter": "ziren",
+            "shared": {
+                "frames": frames.iter().map(|name| json!({ "name": name })).collect::<Vec<_>>(),
+            },
+            "profiles": [
+                profile("cycles", cycle_samples, cycle_weights),
+                profile("precompile cost", cost_samples, cost_weights),
+            ],
+        });
+        serde_json::to_writer(writer, &value).map_err(io::Error::from)
+    }
+}
+
+/// A call frame on the reconstructed stack.
+#[derive(Debug, Clone, Copy)]
+struct Frame {
+    /// The node of the call tree for this frame.
+    node: usize,
+    /// The address the frame returns to.
+    return_addr: u32,
+}
+
+/// A jump whose delay slot has not been executed yet.
+#[derive(Debug, Clone, Copy)]
+struct PendingJump {
+    /// Whether the jump writes a link register.
+    link: bool,
+    /// The address after the delay slot.
+    return_addr: u32,
+}
+
+/// Attributes cycles and precompile costs to the guest call stack.
+#[derive(Debug, Clone)]
+pub struct Profiler {
+    symbols: SymbolTable,
+    sample_interval: u64,
+    /// The frames indexed by the start address of their symbol, or `None` for unknown code.
+    frame_ids: HashMap<Option<u32>, usize>,
+    profile: Profile,
+    /// The children of each node, indexed by frame.
+    children: Vec<HashMap<usize, usize>>,
+    /// The children of the virtual root, indexed by frame.
+    roots: HashMap<usize, usize>,
+    stack: Vec<Frame>,
+    pending: Option<PendingJump>,
+    /// The stack saved when entering an unconstrained block.
+    saved: Option<(Vec<Frame>, Option<PendingJump>)>,
+    /// The cycles executed since the last sample.
+    unsampled_cycles: u64,
+}
+
+impl Profiler {
+    /// Create a new [`Profiler`] for a program starting at `pc_start`.
+    #[must_use]
+    pub fn new(config: ProfilerConfig, pc_start: u32) -> Self {
+        let mut profiler = Self {
+            symbols: config.symbols,
+            sample_interval: config.sample_interval.max(1),
+            frame_ids: HashMap::new(),
+            profile: Profile::default(),
+            children: Vec::new(),
+            roots: HashMap::new(),
+            stack: Vec::new(),
+            pending: None,
+            saved: None,
+            unsampled_cycles: 0,
+        };
+        profiler.push(pc_start, 0);
+        profiler
+    }
+
+    /// Record the execution of `instruction` at `pc`, after which execution continues at
+    /// `next_pc`.
+    ///
+    /// Cycles executed in unconstrained mode are not attributed, and the call stack is restored
+    /// when leaving unconstrained mode.
+    pub fn record(
+        &mut self,
+        pc: u32,
+        instruction: &Instruction,
+        next_pc: u32,
+        unconstrained: bool,
+    ) {
+        // Only instructions that started in constrained mode count, like in the execution report.
+        if self.saved.is_none() {
+            self.unsampled_cycles += 1;
+            if self.unsampled_cycles == self.sample_interval {
+                let node = self.current();
+                self.profile.nodes[node].cycles += self.unsampled_cycles;
+                self.unsampled_cycles = 0;
+            }
+        }
+
+        match (unconstrained, self.saved.is_some()) {
+            (true, false) => self.saved = Some((self.stack.clone(), self.pending)),
+            (false, true) => {
+                (self.stack, self.pending) = self.saved.take().unwrap();
+                return;
+            }
+            _ => {}
+        }
+
+        // The stack changes once the delay slot of a jump has executed.
+        if let Some(jump) = self.pending.take() {
+            self.jump(jump, next_pc);
+        }
+        if instruction.is_jump_instruction() {
+            self.pending =
+                Some(PendingJump { link: instruction.op_a != 0, return_addr: pc.wrapping_add(8) });
+        }
+    }
+
+    /// Attribute the cost of a precompile call to the current stack.
+    pub fn record_precompile(&mut self, air: MipsAirId, cost: u64) {
+        let node = self.current();
+        *self.profile.nodes[node].precompile_costs.entry(air).or_default() += cost;
+    }
+
+    /// Finish profiling and return the call tree.
+    #[must_use]
+    pub fn finish(mut self) -> Profile {
+        if self.unsampled_cycles > 0 {
+            let node = self.current();
+            self.profile.nodes[node].cycles += self.unsampled_cycles;
+        }
+        self.profile
+    }
+
+    /// Update the stack for a jump to `target`.
+    fn jump(&mut self, jump: PendingJump, target: u32) {
+        if jump.link {
+            self.push(target, jump.return_addr);
+        } else if let Some(idx) =
+            self.stack.iter().rposition(|frame| frame.return_addr == target && target != 0)
+        {
+            self.stack.truncate(idx);
+            if self.stack.is_empty() {
+                self.push(target, 0);
+            }
+        } else if let Some(symbol) = self.symbols.lookup(target) {
+            let frame = self.frame_id(Some(symbol.address));
+            let top = self.stack.pop().unwrap();
+            if symbol.address == target && self.profile.nodes[top.node].frame != frame {
+                // A tail call replaces the current frame.
+                self.push(target, top.return_addr);
+            } else {
+                self.stack.push(top);
+            }
+        }
+    }
+
+    /// Push a frame for the function containing `target`.
+    fn push(&mut self, target: u32, return_addr: u32) {
+        let frame = self.frame_id(self.symbols.lookup(target).map(|symbol| symbol.address));
+        let parent = self.stack.last().map(|frame| frame.node);
+        let children = match parent {
+            Some(parent) => &mut self.children[parent],
+            None => &mut self.roots,
+        };
+        let node = match children.get(&frame) {
+            Some(node) => *node,
+            None => {
+                let node = self.profile.nodes.len();
+                children.insert(frame, node);
+                self.profile.nodes.push(ProfileNode { frame, parent, ..Default::default() });
+                self.children.push(HashMap::new());
+                node
+            }
+        };
+        self.stack.push(Frame { node, return_addr });
+    }
+
+    /// The frame index of the symbol starting at `address`.
+    fn frame_id(&mut self, address: Option<u32>) -> usize {
+        if let Some(frame) = self.frame_ids.get(&address) {
+            return *frame;
+        }
+        let name = address
+            .and_then(|address| self.symbols.lookup(address))
+            .map_or_else(|| UNKNOWN_FRAME.to_string(), |symbol| symbol.demangled.clone());
+        let frame = self.profile.frames.len();
+        self.profile.frames.push(name);
+        self.frame_ids.insert(address, frame);
+        frame
+    }
+
+    /// The node of the frame on top of the stack.
+    fn current(&self) -> usize {
+        self.stack.last().expect("the stack is never empty").node
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_artifacts::FIBONACCI_ELF;
+    use zkm_stark::ZKMCoreOpts;
+
+    use super::ProfilerConfig;
+    use crate::{Executor, Program, SymbolTable, ZKMContext};
+
+    #[test]
+    fn test_profile_fibonacci() {
+        let program = Program::from(FIBONACCI_ELF).unwrap();
+        let symbols = SymbolTable::from_elf(FIBONACCI_ELF).unwrap();
+        let context = ZKMContext::builder().profiler(ProfilerConfig::new(symbols)).build();
+        let mut runtime = Executor::with_context(program, ZKMCoreOpts::default(), context);
+        runtime.run_fast().unwrap();
+
+        let profile = runtime.report.profile.as_deref().unwrap();
+        assert_eq!(profile.total_cycles(), runtime.report.total_instruction_count());
+        assert!(profile.self_cycles().iter().any(|(name, _)| name.contains("main")));
+
+        let mut folded = Vec::new();
+        profile.write_folded(&mut folded).unwrap();
+        let folded_cycles = String::from_utf8(folded)
+            .unwrap()
+            .lines()
+            .map(|line| line.rsplit_once(' ').unwrap().1.parse::<u64>().unwrap())
+            .sum::<u64>();
+        assert_eq!(folded_cycles, profile.total_cycles());
+    }
+}
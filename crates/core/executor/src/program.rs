@@ -21,7 +21,10 @@ use zkm_stark::septic_extension::SepticExtension;
 use zkm_stark::shape::Shape;
 use zkm_stark::LookupKind;
 
-use crate::{Instruction, MipsAirId, Register};
+use crate::{
+    vfs::{split_files, vfs_image, VFS_BASE},
+    Instruction, MipsAirId, Register,
+};
 
 pub const MAX_MEMORY: usize = 0x7F000000;
 pub const MAX_CODE_MEMORY: usize = 0x3F000000;
@@ -48,9 +51,11 @@ impl Program {
         Self { instructions, pc_start, pc_base, next_pc: pc_start + 4, ..Default::default() }
     }
 
-    /// Initialize a MIPS Program from an appropriate ELF file
+    /// Initialize a MIPS Program from an appropriate ELF file, with the files attached by
+    /// [`crate::attach_files`].
     pub fn from(elf_code: &[u8]) -> Result<Program> {
         let max_mem = MAX_CODE_MEMORY as u32;
+        let (elf_code, files) = split_files(elf_code)?;
 
         let mut image: BTreeMap<u32, u32> = BTreeMap::new();
         let elf = ElfBytes::<LittleEndian>::minimal_parse(elf_code)
@@ -157,14 +162,35 @@ impl Program {
         let instructions: Vec<_> =
             instructions.par_iter().map(|inst| Instruction::decode_from(*inst).unwrap()).collect();
 
-        Ok(Program {
+        Program {
             instructions,
             pc_start: entry,
             pc_base: base_address,
             next_pc: entry + 4,
             image,
             preprocessed_shape: None,
-        })
+        }
+        .with_files(&files)
+    }
+
+    /// Lay out `files`, indexed by their path, in the memory image, where the program can open
+    /// and read them. Relative paths are resolved against `/`.
+    ///
+    /// The memory image is left untouched without files.
+    pub fn with_files(mut self, files: &BTreeMap<String, Vec<u8>>) -> Result<Self> {
+        if !files.is_empty() {
+            self.image.extend(vfs_image(files)?);
+        }
+        Ok(self)
+    }
+
+    /// Whether files are laid out in the memory image by [`Self::with_files`].
+    ///
+    /// The heap and the stack must then stay out of the region from [`VFS_BASE`] to
+    /// [`VFS_END`](crate::VFS_END).
+    #[must_use]
+    pub fn has_files(&self) -> bool {
+        self.image.contains_key(&VFS_BASE)
     }
 
     /// Custom logic for padding the trace to a power of two according to the proof shape.
@@ -142,7 +142,16 @@ impl ExecutionRecord {
                 SyscallCode::KECCAK_SPONGE => opts.keccak,
                 SyscallCode::SHA_EXTEND => opts.sha_extend,
                 SyscallCode::SHA_COMPRESS => opts.sha_compress,
+                SyscallCode::SHA512_EXTEND => opts.sha512_extend,
+                SyscallCode::SHA512_COMPRESS => opts.sha512_compress,
+                SyscallCode::BLAKE3_COMPRESS_INNER => opts.blake3_compress_inner,
+                SyscallCode::BLAKE2B_COMPRESS_INNER => opts.blake2b_compress_inner,
+                SyscallCode::SECP256K1_SCALAR_MUL
+                | SyscallCode::SECP256R1_SCALAR_MUL
+                | SyscallCode::BN254_SCALAR_MUL
+                | SyscallCode::BLS12381_SCALAR_MUL => opts.weierstrass_scalar_mul,
                 SyscallCode::BOOLEAN_CIRCUIT_GARBLE => opts.boolean_circuit_garble,
+                SyscallCode::SYS_FILE => opts.sys_file,
                 _ => opts.deferred,
             };
 
@@ -192,6 +201,27 @@ impl ExecutionRecord {
                     }
                     current_shard
                 }
+                SyscallCode::SYS_FILE => {
+                    let mut current_shard = Vec::new();
+                    let mut current_len = 0;
+
+                    for (syscall_event, event) in events {
+                        if let PrecompileEvent::SysFile(event) = &event {
+                            let input_len = event.num_rows();
+
+                            if current_len + input_len > threshold && !current_shard.is_empty() {
+                                let mut record = ExecutionRecord::new(self.program.clone());
+                                record.precompile_events.insert(syscall_code, current_shard);
+                                shards_input.push(record);
+                                current_shard = Vec::new();
+                                current_len = 0;
+                            }
+                            current_len += input_len;
+                        }
+                        current_shard.push((syscall_event, event));
+                    }
+                    current_shard
+                }
                 _ => {
                     let chunks = events.chunks_exact(threshold);
                     let remainder = chunks.remainder().to_vec();
@@ -5,11 +5,17 @@ use std::{
 
 use enum_map::{EnumArray, EnumMap};
 use hashbrown::HashMap;
+use serde::{Deserialize, Serialize};
 
-use crate::{events::generate_execution_report, syscalls::SyscallCode, Opcode};
+use crate::{
+    events::generate_execution_report, syscalls::SyscallCode, CostEstimate, ExecutionTrace, Opcode,
+    Profile,
+};
 
 /// An execution report.
-#[derive(Default, Debug, Clone, PartialEq, Eq)]
+///
+/// The trace is not serialized.
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ExecutionReport {
     /// The opcode counts.
     pub opcode_counts: Box<EnumMap<Opcode, u64>>,
@@ -19,6 +25,13 @@ pub struct ExecutionReport {
     pub cycle_tracker: HashMap<String, u64>,
     /// The unique memory address counts.
     pub touched_memory_addresses: u64,
+    /// The call-stack profile, if profiling was enabled.
+    pub profile: Option<Box<Profile>>,
+    /// The estimated proving cost, if cost estimation was enabled.
+    pub cost_estimate: Option<Box<CostEstimate>>,
+    /// The per-instruction trace, if tracing was enabled.
+    #[serde(skip)]
+    pub trace: Option<Box<ExecutionTrace>>,
 }
 
 impl ExecutionReport {
@@ -0,0 +1,118 @@
+//! Function symbols of a guest ELF.
+
+use anyhow::{anyhow, Result};
+use elf::{endian::LittleEndian, ElfBytes};
+use serde::{Deserialize, Serialize};
+
+/// A function symbol of the guest program.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Symbol {
+    /// The raw (possibly mangled) name of the symbol.
+    pub name: String,
+    /// The demangled name of the symbol, without the trailing hash.
+    pub demangled: String,
+    /// The start address of the symbol.
+    pub address: u32,
+    /// The size of the symbol in bytes. May be zero for hand-written assembly.
+    pub size: u32,
+}
+
+impl Symbol {
+    /// Returns whether the given address falls inside this symbol.
+    #[must_use]
+    pub fn contains(&self, addr: u32) -> bool {
+        addr >= self.address && addr - self.address < self.size.max(4)
+    }
+}
+
+/// The function symbols of a guest ELF, sorted by address.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SymbolTable {
+    symbols: Vec<Symbol>,
+}
+
+impl SymbolTable {
+    /// Parse the function symbols out of a MIPS ELF.
+    ///
+    /// A stripped ELF yields an empty table.
+    pub fn from_elf(elf_code: &[u8]) -> Result<Self> {
+        let elf = ElfBytes::<LittleEndian>::minimal_parse(elf_code)
+            .map_err(|err| anyhow!("Elf parse error: {err}"))?;
+        let Some((symtab, strtab)) =
+            elf.symbol_table().map_err(|err| anyhow!("Elf symbol table error: {err}"))?
+        else {
+            return Ok(Self::default());
+        };
+
+        let mut symbols = symtab
+            .iter()
+            .filter(|symbol| symbol.st_symtype() == elf::abi::STT_FUNC && !symbol.is_undefined())
+            .filter_map(|symbol| {
+                let name = strtab.get(symbol.st_name as usize).ok()?;
+                Some(Symbol {
+                    name: name.to_string(),
+                    demangled: format!("{:#}", rustc_demangle::demangle(name)),
+                    address: symbol.st_value as u32,
+                    size: symbol.st_size as u32,
+                })
+            })
+            .collect::<Vec<_>>();
+        symbols.sort_by_key(|symbol| symbol.address);
+        symbols.dedup_by_key(|symbol| symbol.address);
+
+        Ok(Self { symbols })
+    }
+
+    /// Returns all symbols, sorted by address.
+    #[must_use]
+    pub fn symbols(&self) -> &[Symbol] {
+        &self.symbols
+    }
+
+    /// Returns whether the table has no symbols.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.symbols.is_empty()
+    }
+
+    /// Find the symbol containing the given address.
+    #[must_use]
+    pub fn lookup(&self, addr: u32) -> Option<&Symbol> {
+        let idx = self.symbols.partition_point(|symbol| symbol.address <= addr);
+        let symbol = self.symbols.get(idx.checked_sub(1)?)?;
+        symbol.contains(addr).then_some(symbol)
+    }
+
+    /// Find a symbol by its raw or demangled name.
+    #[must_use]
+    pub fn find(&self, name: &str) -> Option<&Symbol> {
+        self.symbols.iter().find(|symbol| symbol.name == name || symbol.demangled == name)
+    }
+
+    /// Format an address as `symbol+offset`, falling back to the hex address.
+    #[must_use]
+    pub fn symbolize(&self, addr: u32) -> String {
+        match self.lookup(addr) {
+            Some(symbol) if addr == symbol.address => symbol.demangled.clone(),
+            Some(symbol) => format!("{}+{:#x}", symbol.demangled, addr - symbol.address),
+            None => format!("{addr:#010x}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_artifacts::FIBONACCI_ELF;
+
+    use super::SymbolTable;
+
+    #[test]
+    fn test_lookup_main() {
+        let symbols = SymbolTable::from_elf(FIBONACCI_ELF).unwrap();
+        assert!(!symbols.is_empty());
+
+        let main = symbols.find("_main").unwrap();
+        assert_eq!(symbols.lookup(main.address).unwrap().name, main.name);
+        assert_eq!(symbols.symbolize(main.address), main.demangled);
+    }
+}
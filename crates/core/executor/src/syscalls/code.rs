@@ -2,6 +2,8 @@ use enum_map::Enum;
 use serde::{Deserialize, Serialize};
 use strum_macros::EnumIter;
 
+use crate::MipsAirId;
+
 /// System Calls.
 ///
 /// A system call is invoked by the `syscall` instruction with a specific value in register V0.
@@ -162,20 +164,28 @@ pub enum SyscallCode {
     /// Fcntl
     SYS_FCNTL = 4055,
 
-    /// follows are executed as NOP syscalls
+    /// Open, served from the virtual filesystem
     SYS_OPEN = 4005,
+
+    /// Close, served from the virtual filesystem
     SYS_CLOSE = 4006,
+
+    /// Openat, served from the virtual filesystem
+    SYS_OPENAT = 4288,
+
+    /// Fstat64, served from the virtual filesystem
+    SYS_FSTAT64 = 4215,
+
+    /// follows are executed as NOP syscalls
     SYS_MUNMAP = 4091,
     SYS_RT_SIGACTION = 4194,
     SYS_RT_SIGPROCMASK = 4195,
     SYS_SIGALTSTACK = 4206,
-    SYS_FSTAT64 = 4215,
     SYS_MADVISE = 4218,
     SYS_GETTID = 4222,
     SYS_SCHED_GETAFFINITY = 4240,
     SYS_CLOCK_GETTIME = 4263,
     SYS_NANOSLEEP = 4166,
-    SYS_OPENAT = 4288,
     SYS_PRLIMIT64 = 4338,
 
     /// Executes the `POSEIDON2_PERMUTE` precompile.
@@ -184,7 +194,38 @@ pub enum SyscallCode {
     /// Executes the `BOOLEAN_CIRCUIT_GARBLE` precompile.
     BOOLEAN_CIRCUIT_GARBLE = 0x00_01_00_31,
 
+    /// Executes the `SHA512_EXTEND` precompile.
+    SHA512_EXTEND = 0x40_01_00_32,
+
+    /// Executes the `SHA512_COMPRESS` precompile.
+    SHA512_COMPRESS = 0x01_01_00_33,
+
+    /// Executes the `BLAKE3_COMPRESS_INNER` precompile.
+    BLAKE3_COMPRESS_INNER = 0x38_01_00_34,
+
+    /// Executes the `BLAKE2B_COMPRESS_INNER` precompile.
+    BLAKE2B_COMPRESS_INNER = 0x60_01_00_35,
+
+    /// Executes the `BN254_FP6_MUL` precompile.
+    BN254_FP6_MUL = 0x01_01_00_36,
+
+    /// Executes the `BLS12381_FP6_MUL` precompile.
+    BLS12381_FP6_MUL = 0x01_01_00_37,
+
+    /// Executes the `SECP256K1_SCALAR_MUL` precompile.
+    SECP256K1_SCALAR_MUL = 0x01_01_00_38,
+
+    /// Executes the `SECP256R1_SCALAR_MUL` precompile.
+    SECP256R1_SCALAR_MUL = 0x01_01_00_39,
+
+    /// Executes the `BN254_SCALAR_MUL` precompile.
+    BN254_SCALAR_MUL = 0x01_01_00_3A,
+
+    /// Executes the `BLS12381_SCALAR_MUL` precompile.
+    BLS12381_SCALAR_MUL = 0x01_01_00_3B,
+
     SYS_LINUX = 4000, // not real syscall, used for represent all linux syscalls
+    SYS_FILE = 4001,  // not real syscall, used for represent the virtual file syscalls
 
     UNIMPLEMENTED = 0xFF_FF_FF_FF,
 }
@@ -237,6 +278,16 @@ impl SyscallCode {
             0x00_01_00_2E => SyscallCode::SECP256R1_DECOMPRESS,
             0x01_01_00_2F => SyscallCode::U256XU2048_MUL,
             0x00_01_00_31 => SyscallCode::BOOLEAN_CIRCUIT_GARBLE,
+            0x40_01_00_32 => SyscallCode::SHA512_EXTEND,
+            0x01_01_00_33 => SyscallCode::SHA512_COMPRESS,
+            0x38_01_00_34 => SyscallCode::BLAKE3_COMPRESS_INNER,
+            0x60_01_00_35 => SyscallCode::BLAKE2B_COMPRESS_INNER,
+            0x01_01_00_36 => SyscallCode::BN254_FP6_MUL,
+            0x01_01_00_37 => SyscallCode::BLS12381_FP6_MUL,
+            0x01_01_00_38 => SyscallCode::SECP256K1_SCALAR_MUL,
+            0x01_01_00_39 => SyscallCode::SECP256R1_SCALAR_MUL,
+            0x01_01_00_3A => SyscallCode::BN254_SCALAR_MUL,
+            0x01_01_00_3B => SyscallCode::BLS12381_SCALAR_MUL,
             4000 => SyscallCode::SYS_LINUX,
             4003 => SyscallCode::SYS_READ,
             4004 => SyscallCode::SYS_WRITE,
@@ -303,6 +354,82 @@ impl SyscallCode {
             _ => *self,
         }
     }
+
+    /// Get the AIR that proves the syscall, if it has its own table.
+    #[must_use]
+    pub fn as_air_id(self) -> Option<MipsAirId> {
+        Some(match self {
+            SyscallCode::SHA_EXTEND => MipsAirId::ShaExtend,
+            SyscallCode::SHA_COMPRESS => MipsAirId::ShaCompress,
+            SyscallCode::SHA512_EXTEND => MipsAirId::Sha512Extend,
+            SyscallCode::SHA512_COMPRESS => MipsAirId::Sha512Compress,
+            SyscallCode::BLAKE3_COMPRESS_INNER => MipsAirId::Blake3CompressInner,
+            SyscallCode::BLAKE2B_COMPRESS_INNER => MipsAirId::Blake2bCompressInner,
+            SyscallCode::ED_ADD => MipsAirId::EdAddAssign,
+            SyscallCode::ED_DECOMPRESS => MipsAirId::EdDecompress,
+            SyscallCode::KECCAK_SPONGE => MipsAirId::KeccakSponge,
+            SyscallCode::SECP256K1_ADD => MipsAirId::Secp256k1AddAssign,
+            SyscallCode::SECP256K1_DOUBLE => MipsAirId::Secp256k1DoubleAssign,
+            SyscallCode::SECP256K1_DECOMPRESS => MipsAirId::Secp256k1Decompress,
+            SyscallCode::SECP256R1_ADD => MipsAirId::Secp256r1AddAssign,
+            SyscallCode::SECP256R1_DOUBLE => MipsAirId::Secp256r1DoubleAssign,
+            SyscallCode::SECP256R1_DECOMPRESS => MipsAirId::Secp256r1Decompress,
+            SyscallCode::BN254_ADD => MipsAirId::Bn254AddAssign,
+            SyscallCode::BN254_DOUBLE => MipsAirId::Bn254DoubleAssign,
+            SyscallCode::BLS12381_ADD => MipsAirId::Bls12381AddAssign,
+            SyscallCode::BLS12381_DOUBLE => MipsAirId::Bls12381DoubleAssign,
+            SyscallCode::BLS12381_DECOMPRESS => MipsAirId::Bls12381Decompress,
+            SyscallCode::UINT256_MUL => MipsAirId::Uint256MulMod,
+            SyscallCode::U256XU2048_MUL => MipsAirId::U256XU2048Mul,
+            SyscallCode::BLS12381_FP_ADD
+            | SyscallCode::BLS12381_FP_SUB
+            | SyscallCode::BLS12381_FP_MUL => MipsAirId::Bls12381FpOpAssign,
+            SyscallCode::BLS12381_FP2_ADD | SyscallCode::BLS12381_FP2_SUB => {
+                MipsAirId::Bls12831Fp2AddSubAssign
+            }
+            SyscallCode::BLS12381_FP2_MUL => MipsAirId::Bls12831Fp2MulAssign,
+            SyscallCode::BN254_FP_ADD | SyscallCode::BN254_FP_SUB | SyscallCode::BN254_FP_MUL => {
+                MipsAirId::Bn254FpOpAssign
+            }
+            SyscallCode::BN254_FP2_ADD | SyscallCode::BN254_FP2_SUB => {
+                MipsAirId::Bn254Fp2AddSubAssign
+            }
+            SyscallCode::BN254_FP2_MUL => MipsAirId::Bn254Fp2MulAssign,
+            SyscallCode::BN254_FP6_MUL => MipsAirId::Bn254Fp6MulAssign,
+            SyscallCode::BLS12381_FP6_MUL => MipsAirId::Bls12381Fp6MulAssign,
+            SyscallCode::SECP256K1_SCALAR_MUL => MipsAirId::Secp256k1ScalarMulAssign,
+            SyscallCode::SECP256R1_SCALAR_MUL => MipsAirId::Secp256r1ScalarMulAssign,
+            SyscallCode::BN254_SCALAR_MUL => MipsAirId::Bn254ScalarMulAssign,
+            SyscallCode::BLS12381_SCALAR_MUL => MipsAirId::Bls12381ScalarMulAssign,
+            SyscallCode::POSEIDON2_PERMUTE => MipsAirId::Poseidon2Permute,
+            SyscallCode::BOOLEAN_CIRCUIT_GARBLE => MipsAirId::BooleanCircuitGarble,
+            SyscallCode::SYS_LINUX
+            | SyscallCode::SYS_MMAP
+            | SyscallCode::SYS_MMAP2
+            | SyscallCode::SYS_BRK
+            | SyscallCode::SYS_CLONE
+            | SyscallCode::SYS_EXT_GROUP
+            | SyscallCode::SYS_READ
+            | SyscallCode::SYS_WRITE
+            | SyscallCode::SYS_FCNTL
+            | SyscallCode::SYS_MUNMAP
+            | SyscallCode::SYS_RT_SIGACTION
+            | SyscallCode::SYS_RT_SIGPROCMASK
+            | SyscallCode::SYS_SIGALTSTACK
+            | SyscallCode::SYS_MADVISE
+            | SyscallCode::SYS_GETTID
+            | SyscallCode::SYS_SCHED_GETAFFINITY
+            | SyscallCode::SYS_CLOCK_GETTIME
+            | SyscallCode::SYS_NANOSLEEP
+            | SyscallCode::SYS_PRLIMIT64 => MipsAirId::SysLinux,
+            SyscallCode::SYS_FILE
+            | SyscallCode::SYS_OPEN
+            | SyscallCode::SYS_CLOSE
+            | SyscallCode::SYS_OPENAT
+            | SyscallCode::SYS_FSTAT64 => MipsAirId::SysFile,
+            _ => return None,
+        })
+    }
 }
 
 impl std::fmt::Display for SyscallCode {
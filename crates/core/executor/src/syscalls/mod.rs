@@ -22,22 +22,25 @@ pub use code::*;
 pub use context::*;
 use hint::{HintLenSyscall, HintReadSyscall};
 use precompiles::{
+    blake2b::compress_inner::Blake2bCompressInnerSyscall,
+    blake3::compress_inner::Blake3CompressInnerSyscall,
     boolean_circuit::garble::BooleanCircuitGarbleSyscall,
     edwards::{add::EdwardsAddAssignSyscall, decompress::EdwardsDecompressSyscall},
-    fptower::{Fp2AddSubSyscall, Fp2MulSyscall, FpOpSyscall},
+    fptower::{Fp2AddSubSyscall, Fp2MulSyscall, Fp6MulSyscall, FpOpSyscall},
     keccak::sponge::KeccakSpongeSyscall,
     poseidon2::permute::Poseidon2PermuteSyscall,
     sha256::{compress::Sha256CompressSyscall, extend::Sha256ExtendSyscall},
+    sha512::{compress::Sha512CompressSyscall, extend::Sha512ExtendSyscall},
     sys_linux::{
         sysbrk::SysBrkSyscall, sysclone::SysCloneSyscall, sysexitgroup::SysExitGroupSyscall,
-        sysfcntl::SysFcntlSyscall, sysmmap::SysMmapSyscall, sysnop::SysNopSyscall,
-        sysread::SysReadSyscall, syswrite::SysWriteSyscall,
+        sysfcntl::SysFcntlSyscall, sysfile::SysFileSyscall, sysmmap::SysMmapSyscall,
+        sysnop::SysNopSyscall, sysread::SysReadSyscall, syswrite::SysWriteSyscall,
     },
     u256x2048_mul::U256xU2048MulSyscall,
     uint256::Uint256MulSyscall,
     weierstrass::{
         add::WeierstrassAddAssignSyscall, decompress::WeierstrassDecompressSyscall,
-        double::WeierstrassDoubleAssignSyscall,
+        double::WeierstrassDoubleAssignSyscall, scalar_mul::WeierstrassScalarMulAssignSyscall,
     },
 };
 
@@ -92,6 +95,14 @@ pub fn default_syscall_map() -> HashMap<SyscallCode, Arc<dyn Syscall>> {
 
     syscall_map.insert(SyscallCode::SHA_COMPRESS, Arc::new(Sha256CompressSyscall));
 
+    syscall_map.insert(SyscallCode::SHA512_EXTEND, Arc::new(Sha512ExtendSyscall));
+
+    syscall_map.insert(SyscallCode::SHA512_COMPRESS, Arc::new(Sha512CompressSyscall));
+
+    syscall_map.insert(SyscallCode::BLAKE3_COMPRESS_INNER, Arc::new(Blake3CompressInnerSyscall));
+
+    syscall_map.insert(SyscallCode::BLAKE2B_COMPRESS_INNER, Arc::new(Blake2bCompressInnerSyscall));
+
     syscall_map.insert(SyscallCode::ED_ADD, Arc::new(EdwardsAddAssignSyscall::<Ed25519>::new()));
 
     syscall_map.insert(
@@ -122,6 +133,11 @@ pub fn default_syscall_map() -> HashMap<SyscallCode, Arc<dyn Syscall>> {
         Arc::new(WeierstrassDecompressSyscall::<Secp256k1>::new()),
     );
 
+    syscall_map.insert(
+        SyscallCode::SECP256K1_SCALAR_MUL,
+        Arc::new(WeierstrassScalarMulAssignSyscall::<Secp256k1>::new()),
+    );
+
     syscall_map.insert(
         SyscallCode::SECP256R1_ADD,
         Arc::new(WeierstrassAddAssignSyscall::<Secp256r1>::new()),
@@ -137,6 +153,11 @@ pub fn default_syscall_map() -> HashMap<SyscallCode, Arc<dyn Syscall>> {
         Arc::new(WeierstrassDecompressSyscall::<Secp256r1>::new()),
     );
 
+    syscall_map.insert(
+        SyscallCode::SECP256R1_SCALAR_MUL,
+        Arc::new(WeierstrassScalarMulAssignSyscall::<Secp256r1>::new()),
+    );
+
     syscall_map
         .insert(SyscallCode::BN254_ADD, Arc::new(WeierstrassAddAssignSyscall::<Bn254>::new()));
 
@@ -145,6 +166,11 @@ pub fn default_syscall_map() -> HashMap<SyscallCode, Arc<dyn Syscall>> {
         Arc::new(WeierstrassDoubleAssignSyscall::<Bn254>::new()),
     );
 
+    syscall_map.insert(
+        SyscallCode::BN254_SCALAR_MUL,
+        Arc::new(WeierstrassScalarMulAssignSyscall::<Bn254>::new()),
+    );
+
     syscall_map.insert(
         SyscallCode::BLS12381_ADD,
         Arc::new(WeierstrassAddAssignSyscall::<Bls12381>::new()),
@@ -155,6 +181,11 @@ pub fn default_syscall_map() -> HashMap<SyscallCode, Arc<dyn Syscall>> {
         Arc::new(WeierstrassDoubleAssignSyscall::<Bls12381>::new()),
     );
 
+    syscall_map.insert(
+        SyscallCode::BLS12381_SCALAR_MUL,
+        Arc::new(WeierstrassScalarMulAssignSyscall::<Bls12381>::new()),
+    );
+
     syscall_map.insert(SyscallCode::UINT256_MUL, Arc::new(Uint256MulSyscall));
 
     syscall_map.insert(SyscallCode::U256XU2048_MUL, Arc::new(U256xU2048MulSyscall));
@@ -187,6 +218,9 @@ pub fn default_syscall_map() -> HashMap<SyscallCode, Arc<dyn Syscall>> {
     syscall_map
         .insert(SyscallCode::BLS12381_FP2_MUL, Arc::new(Fp2MulSyscall::<Bls12381BaseField>::new()));
 
+    syscall_map
+        .insert(SyscallCode::BLS12381_FP6_MUL, Arc::new(Fp6MulSyscall::<Bls12381BaseField>::new()));
+
     syscall_map.insert(
         SyscallCode::BN254_FP_ADD,
         Arc::new(FpOpSyscall::<Bn254BaseField>::new(FieldOperation::Add)),
@@ -215,6 +249,9 @@ pub fn default_syscall_map() -> HashMap<SyscallCode, Arc<dyn Syscall>> {
     syscall_map
         .insert(SyscallCode::BN254_FP2_MUL, Arc::new(Fp2MulSyscall::<Bn254BaseField>::new()));
 
+    syscall_map
+        .insert(SyscallCode::BN254_FP6_MUL, Arc::new(Fp6MulSyscall::<Bn254BaseField>::new()));
+
     syscall_map.insert(SyscallCode::ENTER_UNCONSTRAINED, Arc::new(EnterUnconstrainedSyscall));
 
     syscall_map.insert(SyscallCode::EXIT_UNCONSTRAINED, Arc::new(ExitUnconstrainedSyscall));
@@ -246,8 +283,8 @@ pub fn default_syscall_map() -> HashMap<SyscallCode, Arc<dyn Syscall>> {
     syscall_map.insert(SyscallCode::SYS_MMAP2, Arc::new(SysMmapSyscall));
     syscall_map.insert(SyscallCode::SYS_CLONE, Arc::new(SysCloneSyscall));
     syscall_map.insert(SyscallCode::SYS_FCNTL, Arc::new(SysFcntlSyscall));
-    syscall_map.insert(SyscallCode::SYS_OPEN, Arc::new(SysNopSyscall));
-    syscall_map.insert(SyscallCode::SYS_CLOSE, Arc::new(SysNopSyscall));
+    syscall_map.insert(SyscallCode::SYS_OPEN, Arc::new(SysFileSyscall));
+    syscall_map.insert(SyscallCode::SYS_CLOSE, Arc::new(SysFileSyscall));
     syscall_map.insert(SyscallCode::SYS_RT_SIGACTION, Arc::new(SysNopSyscall));
     syscall_map.insert(SyscallCode::SYS_RT_SIGPROCMASK, Arc::new(SysNopSyscall));
     syscall_map.insert(SyscallCode::SYS_MADVISE, Arc::new(SysNopSyscall));
@@ -257,8 +294,8 @@ pub fn default_syscall_map() -> HashMap<SyscallCode, Arc<dyn Syscall>> {
     syscall_map.insert(SyscallCode::SYS_NANOSLEEP, Arc::new(SysNopSyscall));
     syscall_map.insert(SyscallCode::SYS_PRLIMIT64, Arc::new(SysNopSyscall));
     syscall_map.insert(SyscallCode::SYS_SIGALTSTACK, Arc::new(SysNopSyscall));
-    syscall_map.insert(SyscallCode::SYS_OPENAT, Arc::new(SysNopSyscall));
-    syscall_map.insert(SyscallCode::SYS_FSTAT64, Arc::new(SysNopSyscall));
+    syscall_map.insert(SyscallCode::SYS_OPENAT, Arc::new(SysFileSyscall));
+    syscall_map.insert(SyscallCode::SYS_FSTAT64, Arc::new(SysFileSyscall));
     syscall_map.insert(SyscallCode::SYS_MUNMAP, Arc::new(SysNopSyscall));
 
     syscall_map
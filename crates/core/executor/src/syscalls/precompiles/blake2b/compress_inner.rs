@@ -0,0 +1,117 @@
+use crate::{
+    events::{Blake2bCompressInnerEvent, PrecompileEvent},
+    syscalls::{
+        precompiles::blake3::compress_inner::G_INDEX, Syscall, SyscallCode, SyscallContext,
+    },
+    ExecutionError,
+};
+
+/// The number of rounds of the BLAKE2b compression function.
+pub const BLAKE2B_NUM_ROUNDS: usize = 12;
+
+/// The message permutations of BLAKE2b. Round `r` uses `SIGMA[r % 10]`, and `G` application `i`
+/// of a round uses the message words `SIGMA[r % 10][2 * i]` and `SIGMA[r % 10][2 * i + 1]`.
+pub const SIGMA: [[usize; 16]; 10] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3],
+    [11, 8, 12, 0, 5, 2, 15, 13, 10, 14, 3, 6, 7, 1, 9, 4],
+    [7, 9, 3, 1, 13, 12, 11, 14, 2, 6, 5, 10, 4, 0, 15, 8],
+    [9, 0, 5, 7, 2, 4, 10, 15, 14, 1, 11, 12, 6, 8, 3, 13],
+    [2, 12, 6, 10, 0, 11, 8, 3, 4, 13, 7, 5, 15, 14, 1, 9],
+    [12, 5, 1, 15, 14, 13, 4, 10, 0, 7, 6, 3, 9, 2, 8, 11],
+    [13, 11, 7, 14, 12, 1, 3, 9, 5, 0, 15, 4, 8, 6, 2, 10],
+    [6, 15, 14, 9, 11, 3, 0, 8, 12, 2, 13, 7, 1, 4, 10, 5],
+    [10, 2, 8, 4, 7, 6, 1, 5, 15, 11, 9, 14, 3, 12, 13, 0],
+];
+
+/// Applies the BLAKE2b `G` function to the state words `(a, b, c, d)` with the message words `x`
+/// and `y`.
+#[must_use]
+pub fn blake2b_g(state: [u64; 4], x: u64, y: u64) -> [u64; 4] {
+    let [mut a, mut b, mut c, mut d] = state;
+    a = a.wrapping_add(b).wrapping_add(x);
+    d = (d ^ a).rotate_right(32);
+    c = c.wrapping_add(d);
+    b = (b ^ c).rotate_right(24);
+    a = a.wrapping_add(b).wrapping_add(y);
+    d = (d ^ a).rotate_right(16);
+    c = c.wrapping_add(d);
+    b = (b ^ c).rotate_right(63);
+    [a, b, c, d]
+}
+
+pub(crate) struct Blake2bCompressInnerSyscall;
+
+impl Syscall for Blake2bCompressInnerSyscall {
+    fn num_extra_cycles(&self) -> u32 {
+        (BLAKE2B_NUM_ROUNDS * G_INDEX.len()) as u32
+    }
+
+    fn execute(
+        &self,
+        rt: &mut SyscallContext,
+        syscall_code: SyscallCode,
+        arg1: u32,
+        arg2: u32,
+    ) -> Result<Option<u32>, ExecutionError> {
+        let state_ptr = arg1;
+        let message_ptr = arg2;
+        assert_ne!(state_ptr, message_ptr);
+
+        let start_clk = rt.clk;
+
+        let mut message_reads = Vec::with_capacity(BLAKE2B_NUM_ROUNDS * G_INDEX.len());
+        let mut state_writes = Vec::with_capacity(BLAKE2B_NUM_ROUNDS * G_INDEX.len());
+        for round in 0..BLAKE2B_NUM_ROUNDS {
+            let schedule = &SIGMA[round % 10];
+            for (i, index) in G_INDEX.iter().enumerate() {
+                // Read the two message words used by this `G` application.
+                let x_addr = message_ptr + schedule[2 * i] as u32 * 8;
+                let y_addr = message_ptr + schedule[2 * i + 1] as u32 * 8;
+                let (x_lo_record, x_lo) = rt.mr(x_addr);
+                let (x_hi_record, x_hi) = rt.mr(x_addr + 4);
+                let (y_lo_record, y_lo) = rt.mr(y_addr);
+                let (y_hi_record, y_hi) = rt.mr(y_addr + 4);
+                message_reads.push([x_lo_record, x_hi_record, y_lo_record, y_hi_record]);
+
+                // Mix the four state words and write them back in place.
+                let state = index.map(|j| {
+                    let addr = state_ptr + j as u32 * 8;
+                    join_u64(rt.word_unsafe(addr), rt.word_unsafe(addr + 4))
+                });
+                let result = blake2b_g(state, join_u64(x_lo, x_hi), join_u64(y_lo, y_hi));
+                let mut records = Vec::with_capacity(8);
+                for (j, value) in index.iter().zip(result) {
+                    let addr = state_ptr + *j as u32 * 8;
+                    records.push(rt.mw(addr, value as u32));
+                    records.push(rt.mw(addr + 4, (value >> 32) as u32));
+                }
+                state_writes.push(records.try_into().unwrap());
+
+                rt.clk += 1;
+            }
+        }
+
+        // Push the BLAKE2b compress inner event.
+        let shard = rt.current_shard();
+        let event = PrecompileEvent::Blake2bCompressInner(Blake2bCompressInnerEvent {
+            shard,
+            clk: start_clk,
+            state_ptr,
+            message_ptr,
+            message_reads,
+            state_writes,
+            local_mem_access: rt.postprocess(),
+        });
+        let syscall_event =
+            rt.rt.syscall_event(start_clk, None, rt.next_pc, syscall_code.syscall_id(), arg1, arg2);
+        rt.add_precompile_event(syscall_code, syscall_event, event);
+
+        Ok(None)
+    }
+}
+
+/// Join the low and high 32-bit halves of a little-endian 64-bit word.
+fn join_u64(lo: u32, hi: u32) -> u64 {
+    u64::from(lo) | (u64::from(hi) << 32)
+}
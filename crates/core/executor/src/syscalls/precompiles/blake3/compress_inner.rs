@@ -0,0 +1,108 @@
+use crate::{
+    events::{Blake3CompressInnerEvent, PrecompileEvent},
+    syscalls::{Syscall, SyscallCode, SyscallContext},
+    ExecutionError,
+};
+
+/// The number of rounds of the BLAKE3 compression function.
+pub const BLAKE3_NUM_ROUNDS: usize = 7;
+
+/// The indices of the state words `(a, b, c, d)` mixed by each of the eight `G` applications of a
+/// round: first the four columns, then the four diagonals.
+pub const G_INDEX: [[usize; 4]; 8] = [
+    [0, 4, 8, 12],
+    [1, 5, 9, 13],
+    [2, 6, 10, 14],
+    [3, 7, 11, 15],
+    [0, 5, 10, 15],
+    [1, 6, 11, 12],
+    [2, 7, 8, 13],
+    [3, 4, 9, 14],
+];
+
+/// The order in which the message words are consumed in each round. `G` application `i` of round
+/// `r` uses the message words `MSG_SCHEDULE[r][2 * i]` and `MSG_SCHEDULE[r][2 * i + 1]`.
+pub const MSG_SCHEDULE: [[usize; 16]; BLAKE3_NUM_ROUNDS] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [2, 6, 3, 10, 7, 0, 4, 13, 1, 11, 12, 5, 9, 14, 15, 8],
+    [3, 4, 10, 12, 13, 2, 7, 14, 6, 5, 9, 0, 11, 15, 8, 1],
+    [10, 7, 12, 9, 14, 3, 13, 15, 4, 0, 11, 2, 5, 8, 1, 6],
+    [12, 13, 9, 11, 15, 10, 14, 8, 7, 2, 5, 3, 0, 1, 6, 4],
+    [9, 14, 11, 5, 8, 12, 15, 1, 13, 3, 0, 10, 2, 6, 4, 7],
+    [11, 15, 5, 0, 1, 9, 8, 6, 14, 10, 2, 12, 3, 4, 7, 13],
+];
+
+/// Applies the BLAKE3 `G` function to the state words `(a, b, c, d)` with the message words `x`
+/// and `y`.
+#[must_use]
+pub fn blake3_g(state: [u32; 4], x: u32, y: u32) -> [u32; 4] {
+    let [mut a, mut b, mut c, mut d] = state;
+    a = a.wrapping_add(b).wrapping_add(x);
+    d = (d ^ a).rotate_right(16);
+    c = c.wrapping_add(d);
+    b = (b ^ c).rotate_right(12);
+    a = a.wrapping_add(b).wrapping_add(y);
+    d = (d ^ a).rotate_right(8);
+    c = c.wrapping_add(d);
+    b = (b ^ c).rotate_right(7);
+    [a, b, c, d]
+}
+
+pub(crate) struct Blake3CompressInnerSyscall;
+
+impl Syscall for Blake3CompressInnerSyscall {
+    fn num_extra_cycles(&self) -> u32 {
+        (BLAKE3_NUM_ROUNDS * G_INDEX.len()) as u32
+    }
+
+    fn execute(
+        &self,
+        rt: &mut SyscallContext,
+        syscall_code: SyscallCode,
+        arg1: u32,
+        arg2: u32,
+    ) -> Result<Option<u32>, ExecutionError> {
+        let state_ptr = arg1;
+        let message_ptr = arg2;
+        assert_ne!(state_ptr, message_ptr);
+
+        let start_clk = rt.clk;
+
+        let mut message_reads = Vec::with_capacity(BLAKE3_NUM_ROUNDS * G_INDEX.len());
+        let mut state_writes = Vec::with_capacity(BLAKE3_NUM_ROUNDS * G_INDEX.len());
+        for schedule in MSG_SCHEDULE.iter() {
+            for (i, index) in G_INDEX.iter().enumerate() {
+                // Read the two message words used by this `G` application.
+                let (x_record, x) = rt.mr(message_ptr + schedule[2 * i] as u32 * 4);
+                let (y_record, y) = rt.mr(message_ptr + schedule[2 * i + 1] as u32 * 4);
+                message_reads.push([x_record, y_record]);
+
+                // Mix the four state words and write them back in place.
+                let state = index.map(|j| rt.word_unsafe(state_ptr + j as u32 * 4));
+                let result = blake3_g(state, x, y);
+                state_writes.push(std::array::from_fn(|k| {
+                    rt.mw(state_ptr + index[k] as u32 * 4, result[k])
+                }));
+
+                rt.clk += 1;
+            }
+        }
+
+        // Push the BLAKE3 compress inner event.
+        let shard = rt.current_shard();
+        let event = PrecompileEvent::Blake3CompressInner(Blake3CompressInnerEvent {
+            shard,
+            clk: start_clk,
+            state_ptr,
+            message_ptr,
+            message_reads,
+            state_writes,
+            local_mem_access: rt.postprocess(),
+        });
+        let syscall_event =
+            rt.rt.syscall_event(start_clk, None, rt.next_pc, syscall_code.syscall_id(), arg1, arg2);
+        rt.add_precompile_event(syscall_code, syscall_event, event);
+
+        Ok(None)
+    }
+}
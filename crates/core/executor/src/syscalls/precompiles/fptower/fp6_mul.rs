@@ -0,0 +1,140 @@
+use std::marker::PhantomData;
+
+use num::BigUint;
+use typenum::Unsigned;
+use zkm_curves::{
+    params::NumWords,
+    weierstrass::{FieldType, FpOpField},
+};
+
+use crate::{
+    events::{Fp6MulEvent, PrecompileEvent},
+    syscalls::{Syscall, SyscallCode, SyscallContext},
+    ExecutionError,
+};
+
+pub struct Fp6MulSyscall<P> {
+    _marker: PhantomData<P>,
+}
+
+impl<P> Fp6MulSyscall<P> {
+    pub const fn new() -> Self {
+        Self { _marker: PhantomData }
+    }
+}
+
+/// Multiplies two `Fp2` elements given as `(c0, c1)` pairs.
+fn fp2_mul(a: &[BigUint; 2], b: &[BigUint; 2], modulus: &BigUint) -> [BigUint; 2] {
+    let c0 = ((&a[0] * &b[0]) % modulus + modulus - (&a[1] * &b[1]) % modulus) % modulus;
+    let c1 = (&a[0] * &b[1] + &a[1] * &b[0]) % modulus;
+    [c0, c1]
+}
+
+fn fp2_add(a: &[BigUint; 2], b: &[BigUint; 2], modulus: &BigUint) -> [BigUint; 2] {
+    [(&a[0] + &b[0]) % modulus, (&a[1] + &b[1]) % modulus]
+}
+
+/// Multiplies an `Fp2` element by the non-residue `ξ = k + u`.
+fn fp2_mul_by_non_residue(a: &[BigUint; 2], k: u32, modulus: &BigUint) -> [BigUint; 2] {
+    let c0 = (&a[0] * k + modulus - &a[1]) % modulus;
+    let c1 = (&a[1] * k + &a[0]) % modulus;
+    [c0, c1]
+}
+
+impl<P: FpOpField> Syscall for Fp6MulSyscall<P> {
+    fn execute(
+        &self,
+        rt: &mut SyscallContext,
+        syscall_code: SyscallCode,
+        arg1: u32,
+        arg2: u32,
+    ) -> Result<Option<u32>, ExecutionError> {
+        let clk = rt.clk;
+        let x_ptr = arg1;
+        if !x_ptr.is_multiple_of(4) {
+            panic!();
+        }
+        let y_ptr = arg2;
+        if !y_ptr.is_multiple_of(4) {
+            panic!();
+        }
+
+        let num_words_fp = <P as NumWords>::WordsFieldElement::USIZE;
+        let num_words = 6 * num_words_fp;
+
+        let x = rt.slice_unsafe(x_ptr, num_words);
+        let (y_memory_records, y) = rt.mr_slice(y_ptr, num_words);
+        rt.clk += 1;
+
+        let modulus = &BigUint::from_bytes_le(P::MODULUS);
+        let to_fp2 = |words: &[u32], i: usize| -> [BigUint; 2] {
+            let c = &words[2 * i * num_words_fp..2 * (i + 1) * num_words_fp];
+            [BigUint::from_slice(&c[..num_words_fp]), BigUint::from_slice(&c[num_words_fp..])]
+        };
+        let a = [to_fp2(&x, 0), to_fp2(&x, 1), to_fp2(&x, 2)];
+        let b = [to_fp2(&y, 0), to_fp2(&y, 1), to_fp2(&y, 2)];
+        let k = P::FP6_NON_RESIDUE_C0;
+
+        // c0 = a0 b0 + ξ (a1 b2 + a2 b1)
+        // c1 = a0 b1 + a1 b0 + ξ a2 b2
+        // c2 = a0 b2 + a1 b1 + a2 b0
+        let cross =
+            fp2_add(&fp2_mul(&a[1], &b[2], modulus), &fp2_mul(&a[2], &b[1], modulus), modulus);
+        let c0 = fp2_add(
+            &fp2_mul(&a[0], &b[0], modulus),
+            &fp2_mul_by_non_residue(&cross, k, modulus),
+            modulus,
+        );
+        let c1 = fp2_add(
+            &fp2_add(&fp2_mul(&a[0], &b[1], modulus), &fp2_mul(&a[1], &b[0], modulus), modulus),
+            &fp2_mul_by_non_residue(&fp2_mul(&a[2], &b[2], modulus), k, modulus),
+            modulus,
+        );
+        let c2 = fp2_add(
+            &fp2_add(&fp2_mul(&a[0], &b[2], modulus), &fp2_mul(&a[1], &b[1], modulus), modulus),
+            &fp2_mul(&a[2], &b[0], modulus),
+            modulus,
+        );
+
+        let mut result = Vec::with_capacity(num_words);
+        for c in [c0, c1, c2].iter().flatten() {
+            let mut limbs = c.to_u32_digits();
+            limbs.resize(num_words_fp, 0);
+            result.extend_from_slice(&limbs);
+        }
+
+        let x_memory_records = rt.mw_slice(x_ptr, &result);
+
+        let shard = rt.current_shard();
+        let event = Fp6MulEvent {
+            shard,
+            clk,
+            x_ptr,
+            x,
+            y_ptr,
+            y,
+            x_memory_records,
+            y_memory_records,
+            local_mem_access: rt.postprocess(),
+        };
+        let syscall_event =
+            rt.rt.syscall_event(clk, None, rt.next_pc, syscall_code.syscall_id(), arg1, arg2);
+        match P::FIELD_TYPE {
+            FieldType::Bn254 => rt.add_precompile_event(
+                syscall_code,
+                syscall_event,
+                PrecompileEvent::Bn254Fp6Mul(event),
+            ),
+            FieldType::Bls12381 => rt.add_precompile_event(
+                syscall_code,
+                syscall_event,
+                PrecompileEvent::Bls12381Fp6Mul(event),
+            ),
+        };
+        Ok(None)
+    }
+
+    fn num_extra_cycles(&self) -> u32 {
+        1
+    }
+}
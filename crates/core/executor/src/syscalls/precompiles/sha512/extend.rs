@@ -0,0 +1,91 @@
+use crate::{
+    events::{MemoryReadRecord, PrecompileEvent, Sha512ExtendEvent},
+    syscalls::{Syscall, SyscallCode, SyscallContext},
+    ExecutionError,
+};
+
+pub(crate) struct Sha512ExtendSyscall;
+
+impl Syscall for Sha512ExtendSyscall {
+    fn num_extra_cycles(&self) -> u32 {
+        64
+    }
+
+    fn execute(
+        &self,
+        rt: &mut SyscallContext,
+        syscall_code: SyscallCode,
+        arg1: u32,
+        arg2: u32,
+    ) -> Result<Option<u32>, ExecutionError> {
+        let clk_init = rt.clk;
+        let w_ptr = arg1;
+        assert!(arg2 == 0, "arg2 must be 0");
+
+        let w_ptr_init = w_ptr;
+        let mut w_i_minus_15_reads = Vec::with_capacity(64);
+        let mut w_i_minus_2_reads = Vec::with_capacity(64);
+        let mut w_i_minus_16_reads = Vec::with_capacity(64);
+        let mut w_i_minus_7_reads = Vec::with_capacity(64);
+        let mut w_i_writes = Vec::with_capacity(64);
+        for i in 16..80 {
+            // Read w[i-15].
+            let (records, w_i_minus_15) = read_u64(rt, w_ptr + (i - 15) * 8);
+            w_i_minus_15_reads.push(records);
+
+            // Compute `s0`.
+            let s0 =
+                w_i_minus_15.rotate_right(1) ^ w_i_minus_15.rotate_right(8) ^ (w_i_minus_15 >> 7);
+
+            // Read w[i-2].
+            let (records, w_i_minus_2) = read_u64(rt, w_ptr + (i - 2) * 8);
+            w_i_minus_2_reads.push(records);
+
+            // Compute `s1`.
+            let s1 =
+                w_i_minus_2.rotate_right(19) ^ w_i_minus_2.rotate_right(61) ^ (w_i_minus_2 >> 6);
+
+            // Read w[i-16].
+            let (records, w_i_minus_16) = read_u64(rt, w_ptr + (i - 16) * 8);
+            w_i_minus_16_reads.push(records);
+
+            // Read w[i-7].
+            let (records, w_i_minus_7) = read_u64(rt, w_ptr + (i - 7) * 8);
+            w_i_minus_7_reads.push(records);
+
+            // Compute `w_i`.
+            let w_i = s1.wrapping_add(w_i_minus_16).wrapping_add(s0).wrapping_add(w_i_minus_7);
+
+            // Write w[i].
+            let addr = w_ptr + i * 8;
+            w_i_writes.push([rt.mw(addr, w_i as u32), rt.mw(addr + 4, (w_i >> 32) as u32)]);
+            rt.clk += 1;
+        }
+
+        // Push the SHA-512 extend event.
+        let shard = rt.current_shard();
+        let event = PrecompileEvent::Sha512Extend(Sha512ExtendEvent {
+            shard,
+            clk: clk_init,
+            w_ptr: w_ptr_init,
+            w_i_minus_15_reads,
+            w_i_minus_2_reads,
+            w_i_minus_16_reads,
+            w_i_minus_7_reads,
+            w_i_writes,
+            local_mem_access: rt.postprocess(),
+        });
+        let syscall_event =
+            rt.rt.syscall_event(clk_init, None, rt.next_pc, syscall_code.syscall_id(), arg1, arg2);
+        rt.add_precompile_event(syscall_code, syscall_event, event);
+
+        Ok(None)
+    }
+}
+
+/// Read a little-endian 64-bit word stored as two 32-bit words at `addr`.
+fn read_u64(rt: &mut SyscallContext, addr: u32) -> ([MemoryReadRecord; 2], u64) {
+    let (lo_record, lo) = rt.mr(addr);
+    let (hi_record, hi) = rt.mr(addr + 4);
+    ([lo_record, hi_record], u64::from(lo) | (u64::from(hi) << 32))
+}
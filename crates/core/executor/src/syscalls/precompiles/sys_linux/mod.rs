@@ -2,6 +2,7 @@ pub mod sysbrk;
 pub mod sysclone;
 pub mod sysexitgroup;
 pub mod sysfcntl;
+pub mod sysfile;
 pub mod sysmmap;
 pub mod sysnop;
 pub mod sysread;
@@ -0,0 +1,286 @@
+use std::cmp::Ordering;
+
+use crate::{
+    events::{
+        ByteCopyEvent, MemoryReadRecord, MemoryRecordEnum, PathLookupEvent, PrecompileEvent,
+        SysFileEvent,
+    },
+    syscalls::{Syscall, SyscallCode, SyscallContext},
+    ExecutionError, Register, AT_FDCWD, FIRST_VIRTUAL_FD, MIPS_EBADF, MIPS_EMFILE, MIPS_ENOENT,
+    MIPS_ENOTDIR, MIPS_EROFS, O_ACCMODE, STAT64_SIZE, VFS_BASE, VFS_ENTRIES, VFS_FDS, VFS_MAX_FDS,
+    VFS_MAX_READ,
+};
+pub use zkm_primitives::consts::fd::*;
+
+/// Serves `open`, `openat`, `close`, `fstat64` and the reads of files from the virtual files.
+///
+/// The files are part of the memory image, and the accesses below mirror the SysFile AIR, which
+/// constrains the results against them.
+pub(crate) struct SysFileSyscall;
+
+impl Syscall for SysFileSyscall {
+    fn num_extra_cycles(&self) -> u32 {
+        0
+    }
+
+    fn execute(
+        &self,
+        rt: &mut SyscallContext,
+        syscall_code: SyscallCode,
+        a0: u32,
+        a1: u32,
+    ) -> Result<Option<u32>, ExecutionError> {
+        let start_clk = rt.clk;
+        let mut event = SysFileEvent {
+            shard: rt.current_shard(),
+            clk: start_clk,
+            syscall_code: syscall_code.syscall_id(),
+            a0,
+            a1,
+            ..Default::default()
+        };
+        let result = match syscall_code {
+            SyscallCode::SYS_OPEN => open(rt, &mut event, None, a0, a1),
+            SyscallCode::SYS_OPENAT => {
+                let (a2_record, flags) = rt.rr_traced(Register::A2);
+                event.a2_record = Some(a2_record);
+                open(rt, &mut event, Some(a0), a1, flags)
+            }
+            SyscallCode::SYS_CLOSE => close(rt, &mut event, a0),
+            SyscallCode::SYS_FSTAT64 => fstat64(rt, &mut event, a0, a1),
+            SyscallCode::SYS_READ => {
+                let (a2_record, count) = rt.rr_traced(Register::A2);
+                event.a2_record = Some(a2_record);
+                read(rt, &mut event, a0, a1, count)
+            }
+            _ => unreachable!("{syscall_code:?} is not a file syscall"),
+        };
+        let (v0, errno) = match result {
+            Ok(v0) => (v0, 0),
+            Err(errno) => (0xffffffff, errno),
+        };
+
+        event.v0 = v0;
+        event.errno_record = rt.rw_traced(Register::A3, errno);
+        event.local_mem_access = rt.postprocess();
+        let syscall_event =
+            rt.rt.syscall_event(start_clk, None, rt.next_pc, syscall_code.syscall_id(), a0, a1);
+        rt.add_precompile_event(
+            SyscallCode::SYS_FILE,
+            syscall_event,
+            PrecompileEvent::SysFile(event),
+        );
+        Ok(Some(v0))
+    }
+}
+
+/// Open the file at the path `ptr`, resolving relative paths against `dirfd`.
+fn open(
+    rt: &mut SyscallContext,
+    event: &mut SysFileEvent,
+    dirfd: Option<u32>,
+    ptr: u32,
+    flags: u32,
+) -> Result<u32, u32> {
+    let (count_record, count) = rt.mr(VFS_BASE);
+    // Relative paths are compared to the paths of the files without their leading slash.
+    let relative = rt.byte_unsafe(ptr) != b'/';
+    let not_dir = relative && dirfd.is_some_and(|dirfd| dirfd != AT_FDCWD);
+    let skip = u32::from(relative);
+    let (index, found) = if not_dir { (0, false) } else { lower_bound(rt, ptr, skip, count) };
+
+    // The path is compared to the paths of the files around `index` until it differs from both.
+    let mut lookup = PathLookupEvent { index, found, count_record, ..Default::default() };
+    let mut lo = None;
+    let mut hi = None;
+    if !not_dir && !found && index > 0 {
+        let (record, path) = rt.mr(VFS_ENTRIES + 16 * (index - 1));
+        lookup.lo_record = Some(record);
+        lo = Some(path + skip);
+    }
+    if !not_dir && index < count {
+        let (record, path) = rt.mr(VFS_ENTRIES + 16 * index);
+        lookup.hi_record = Some(record);
+        hi = Some(path + skip);
+    }
+    let (mut word, mut lo_word, mut hi_word) = (0, 0, 0);
+    for i in 0.. {
+        let byte = next_byte(rt, ptr.wrapping_add(i), i == 0, &mut word, &mut lookup.path_records);
+        lookup.path.push(byte);
+        lo = lo.filter(|&lo| {
+            next_byte(rt, lo + i, i == 0, &mut lo_word, &mut lookup.lo_records) == byte
+        });
+        hi = hi.filter(|&hi| {
+            next_byte(rt, hi + i, i == 0, &mut hi_word, &mut lookup.hi_records) == byte && byte != 0
+        });
+        if lo.is_none() && hi.is_none() {
+            break;
+        }
+    }
+
+    let result = if not_dir {
+        Err(MIPS_ENOTDIR)
+    } else if !found {
+        Err(MIPS_ENOENT)
+    } else if flags & O_ACCMODE != 0 {
+        Err(MIPS_EROFS)
+    } else {
+        allocate(rt, &mut lookup, index + 1)
+    };
+    event.lookup = Some(lookup);
+    result
+}
+
+/// Close the file descriptor `fd`.
+fn close(rt: &mut SyscallContext, event: &mut SysFileEvent, fd: u32) -> Result<u32, u32> {
+    if fd <= FD_STDERR {
+        return Ok(0);
+    }
+    let slot = slot_addr(fd).ok_or(MIPS_EBADF)?;
+    if rt.word_unsafe(slot) == 0 {
+        event.fd_record = Some(MemoryRecordEnum::Read(rt.mr(slot).0));
+        return Err(MIPS_EBADF);
+    }
+    event.fd_record = Some(MemoryRecordEnum::Write(rt.mw(slot, 0)));
+    Ok(0)
+}
+
+/// Copy the `stat64` buffer of the file open as `fd` to `buf`.
+fn fstat64(
+    rt: &mut SyscallContext,
+    event: &mut SysFileEvent,
+    fd: u32,
+    buf: u32,
+) -> Result<u32, u32> {
+    if fd <= FD_STDERR {
+        return Ok(0);
+    }
+    let (_, entry) = open_file(rt, event, fd)?;
+    let (stat_record, stat) = rt.mr(entry + 12);
+    event.stat_record = Some(stat_record);
+    event.copy = Some(copy(rt, stat, buf, STAT64_SIZE));
+    Ok(0)
+}
+
+/// Read up to `count` bytes of the file open as `fd` into `buf`, at most [`VFS_MAX_READ`].
+fn read(
+    rt: &mut SyscallContext,
+    event: &mut SysFileEvent,
+    fd: u32,
+    buf: u32,
+    count: u32,
+) -> Result<u32, u32> {
+    let (slot, entry) = open_file(rt, event, fd)?;
+    let (data_record, data) = rt.mr(entry + 4);
+    let (size_record, size) = rt.mr(entry + 8);
+    let offset = rt.word_unsafe(slot + 4);
+    let len = count.min(VFS_MAX_READ).min(size.saturating_sub(offset));
+    event.data_record = Some(data_record);
+    event.size_record = Some(size_record);
+    event.offset_record = Some(rt.mw(slot + 4, offset + len));
+    event.copy = (len > 0).then(|| copy(rt, data + offset, buf, len));
+    Ok(len)
+}
+
+/// Read the file descriptor `fd`, returning its address and the address of the entry of its file.
+fn open_file(
+    rt: &mut SyscallContext,
+    event: &mut SysFileEvent,
+    fd: u32,
+) -> Result<(u32, u32), u32> {
+    let slot = slot_addr(fd).ok_or(MIPS_EBADF)?;
+    let (record, file) = rt.mr(slot);
+    event.fd_record = Some(MemoryRecordEnum::Read(record));
+    if file == 0 {
+        return Err(MIPS_EBADF);
+    }
+    Ok((slot, VFS_ENTRIES + 16 * (file - 1)))
+}
+
+/// Allocate the lowest free file descriptor to `file`, the index of the file plus one.
+fn allocate(rt: &mut SyscallContext, lookup: &mut PathLookupEvent, file: u32) -> Result<u32, u32> {
+    for k in 0..VFS_MAX_FDS {
+        let slot = VFS_FDS + 8 * k;
+        if rt.word_unsafe(slot) == 0 {
+            lookup.slot_records.push(MemoryRecordEnum::Write(rt.mw(slot, file)));
+            lookup.offset_record = Some(rt.mw(slot + 4, 0));
+            return Ok(FIRST_VIRTUAL_FD + k);
+        }
+        lookup.slot_records.push(MemoryRecordEnum::Read(rt.mr(slot).0));
+    }
+    Err(MIPS_EMFILE)
+}
+
+/// The address of the file descriptor `fd` in the table, if it can be a virtual file.
+fn slot_addr(fd: u32) -> Option<u32> {
+    (FIRST_VIRTUAL_FD..FIRST_VIRTUAL_FD + VFS_MAX_FDS)
+        .contains(&fd)
+        .then(|| VFS_FDS + 8 * (fd - FIRST_VIRTUAL_FD))
+}
+
+/// Find the first file whose path without its first `skip` bytes isn't less than the path at
+/// `ptr`, and whether they are equal.
+fn lower_bound(rt: &mut SyscallContext, ptr: u32, skip: u32, count: u32) -> (u32, bool) {
+    let (mut lo, mut hi) = (0, count);
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        if compare_path(rt, mid, skip, ptr) == Ordering::Less {
+            lo = mid + 1;
+        } else {
+            hi = mid;
+        }
+    }
+    (lo, lo < count && compare_path(rt, lo, skip, ptr) == Ordering::Equal)
+}
+
+/// Compare the path of the file at `index` without its first `skip` bytes to the path at `ptr`.
+fn compare_path(rt: &mut SyscallContext, index: u32, skip: u32, ptr: u32) -> Ordering {
+    let path = rt.word_unsafe(VFS_ENTRIES + 16 * index) + skip;
+    let mut i = 0u32;
+    loop {
+        let a = rt.byte_unsafe(path.wrapping_add(i));
+        let b = rt.byte_unsafe(ptr.wrapping_add(i));
+        if a != b || a == 0 {
+            return a.cmp(&b);
+        }
+        i += 1;
+    }
+}
+
+/// Copy `len` bytes from `src` to `dst`, reading and writing each word once.
+fn copy(rt: &mut SyscallContext, src: u32, dst: u32, len: u32) -> ByteCopyEvent {
+    let mut event = ByteCopyEvent { src, dst, ..Default::default() };
+    let mut src_word = 0;
+    let mut dst_word = [0; 4];
+    for i in 0..len {
+        let byte = next_byte(rt, src + i, i == 0, &mut src_word, &mut event.src_records);
+        event.bytes.push(byte);
+
+        let addr = dst.wrapping_add(i);
+        if i == 0 || addr % 4 == 0 {
+            dst_word = rt.word_unsafe(addr & !3).to_le_bytes();
+        }
+        dst_word[(addr % 4) as usize] = byte;
+        if addr % 4 == 3 || i == len - 1 {
+            event.dst_records.push(rt.mw(addr & !3, u32::from_le_bytes(dst_word)));
+        }
+    }
+    event
+}
+
+/// Get the byte at `addr`, reading the word holding it first if it's the first byte read or the
+/// first byte of the word.
+fn next_byte(
+    rt: &mut SyscallContext,
+    addr: u32,
+    first: bool,
+    word: &mut u32,
+    records: &mut Vec<MemoryReadRecord>,
+) -> u8 {
+    if first || addr % 4 == 0 {
+        let (record, value) = rt.mr(addr & !3);
+        records.push(record);
+        *word = value;
+    }
+    word.to_le_bytes()[(addr % 4) as usize]
+}
@@ -1,7 +1,7 @@
 use crate::{
     events::{LinuxEvent, PrecompileEvent},
     syscalls::{Syscall, SyscallCode, SyscallContext},
-    ExecutionError, Register,
+    ExecutionError, Register, VFS_BASE,
 };
 
 pub(crate) struct SysMmapSyscall;
@@ -33,6 +33,10 @@ impl Syscall for SysMmapSyscall {
 
         let (v0, write_records) = if a0 == 0 {
             let v0 = rt.rt.register(Register::HEAP);
+            // The heap must stay below the virtual files.
+            if rt.rt.program.has_files() && v0.saturating_add(size) > VFS_BASE {
+                return Err(ExecutionError::VirtualFilesOverlap(VFS_BASE));
+            }
             let w_record = rt.rw_traced(Register::HEAP, v0.wrapping_add(size));
             (v0, vec![a3_record, w_record])
         } else {
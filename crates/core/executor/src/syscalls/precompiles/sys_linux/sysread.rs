@@ -1,3 +1,4 @@
+use super::sysfile::SysFileSyscall;
 use crate::{
     events::{LinuxEvent, PrecompileEvent},
     syscalls::{Syscall, SyscallCode, SyscallContext},
@@ -5,7 +6,6 @@ use crate::{
 };
 pub use zkm_primitives::consts::fd::*;
 
-pub const MIPS_EBADF: u32 = 9;
 pub(crate) struct SysReadSyscall;
 
 impl Syscall for SysReadSyscall {
@@ -20,23 +20,20 @@ impl Syscall for SysReadSyscall {
         a0: u32,
         a1: u32,
     ) -> Result<Option<u32>, ExecutionError> {
-        let start_clk = rt.clk;
-        let fd = a0;
-        let mut v0 = 0;
-        let a3_record = if fd != FD_STDIN {
-            v0 = 0xffffffff; // Return error for non-stdin reads.
-            rt.rw_traced(Register::A3, MIPS_EBADF)
-        } else {
-            rt.rw_traced(Register::A3, 0)
-        };
+        if a0 != FD_STDIN {
+            // Files other than stdin are virtual files.
+            return SysFileSyscall.execute(rt, syscall_code, a0, a1);
+        }
 
+        let start_clk = rt.clk;
+        let a3_record = rt.rw_traced(Register::A3, 0);
         let shard = rt.current_shard();
         let event = PrecompileEvent::Linux(LinuxEvent {
             shard,
             clk: start_clk,
             a0,
             a1,
-            v0,
+            v0: 0,
             syscall_code: syscall_code.syscall_id(),
             read_records: vec![],
             write_records: vec![a3_record],
@@ -45,6 +42,6 @@ impl Syscall for SysReadSyscall {
         let syscall_event =
             rt.rt.syscall_event(start_clk, None, rt.next_pc, syscall_code.syscall_id(), a0, a1);
         rt.add_precompile_event(SyscallCode::SYS_LINUX, syscall_event, event);
-        Ok(Some(v0))
+        Ok(Some(0))
     }
 }
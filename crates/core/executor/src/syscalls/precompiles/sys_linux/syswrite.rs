@@ -3,9 +3,12 @@ use std::vec;
 use crate::{
     events::{LinuxEvent, PrecompileEvent},
     syscalls::{write::write_fd, Syscall, SyscallCode, SyscallContext},
-    ExecutionError, Register,
+    ExecutionError, Register, FIRST_VIRTUAL_FD, MIPS_EBADF, VFS_MAX_FDS,
 };
 
+/// Writes to stdout, stderr and the file descriptors of the zkVM.
+///
+/// The virtual files are read-only, so writing to their file descriptors fails with EBADF.
 pub(crate) struct SysWriteSyscall;
 
 impl Syscall for SysWriteSyscall {
@@ -26,11 +29,15 @@ impl Syscall for SysWriteSyscall {
         let fd = a0;
         let write_buf = a1;
         let nbytes = v0;
-        let bytes = (0..nbytes).map(|i| rt.rt.byte(write_buf + i)).collect::<Vec<u8>>();
-        let slice = bytes.as_slice();
-        write_fd(rt, fd, slice)?;
+        let (v0, errno) = if (FIRST_VIRTUAL_FD..FIRST_VIRTUAL_FD + VFS_MAX_FDS).contains(&fd) {
+            (0xffffffff, MIPS_EBADF)
+        } else {
+            let bytes = (0..nbytes).map(|i| rt.rt.byte(write_buf + i)).collect::<Vec<u8>>();
+            write_fd(rt, fd, &bytes)?;
+            (v0, 0)
+        };
 
-        let a3_record = rt.rw_traced(Register::A3, 0);
+        let a3_record = rt.rw_traced(Register::A3, errno);
         let shard = rt.current_shard();
         let event = PrecompileEvent::Linux(LinuxEvent {
             shard,
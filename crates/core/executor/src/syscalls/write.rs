@@ -1,6 +1,6 @@
 use zkm_primitives::consts::num_to_comma_separated;
 
-use crate::{ExecutionError, Executor, Register};
+use crate::{ExecutionError, Executor, OutputSink, Register};
 
 use super::{Syscall, SyscallCode, SyscallContext};
 
@@ -32,32 +32,22 @@ impl Syscall for WriteSyscall {
 pub fn write_fd(ctx: &mut SyscallContext, fd: u32, slice: &[u8]) -> Result<(), ExecutionError> {
     let rt = &mut ctx.rt;
     if fd == FD_STDOUT {
-        if let Ok(s) = core::str::from_utf8(slice) {
-            match parse_cycle_tracker_command(s) {
-                Some(command) => handle_cycle_tracker_command(rt, command),
-                None => {
-                    let flush_s = update_io_buf(ctx, fd, s);
-                    if !flush_s.is_empty() {
-                        flush_s.into_iter().for_each(|line| println!("stdout: {line}"));
-                    }
-                }
-            }
-        } else {
-            eprintln!("Warning: Stdout Received invalid UTF-8 data in slice: {slice:?}");
+        let command = core::str::from_utf8(slice).ok().and_then(parse_cycle_tracker_command);
+        match command {
+            Some(command) => handle_cycle_tracker_command(rt, command),
+            None => rt.stdout.lock().unwrap().write(slice),
         }
     } else if fd == FD_STDERR {
-        if let Ok(s) = core::str::from_utf8(slice) {
-            let flush_s = update_io_buf(ctx, fd, s);
-            if !flush_s.is_empty() {
-                flush_s.into_iter().for_each(|line| println!("stderr: {line}"));
-            }
-        } else {
-            eprintln!("Warning: Stderr Received invalid UTF-8 data in slice: {slice:?}");
-        }
+        rt.stderr.lock().unwrap().write(slice);
     } else if fd == FD_PUBLIC_VALUES {
         rt.state.public_values_stream.extend_from_slice(slice);
     } else if fd == FD_HINT {
         rt.state.input_stream.push(slice.to_vec());
+    } else if fd == FD_PANIC {
+        match bincode::deserialize(slice) {
+            Ok(report) => rt.panic_report = Some(report),
+            Err(e) => tracing::warn!("failed to deserialize the guest panic report: {e}"),
+        }
     } else if let Some(mut hook) = rt.hook_registry.get(fd) {
         let res = hook.invoke_hook(rt.hook_env(), slice)?;
         // Add result vectors to the beginning of the stream.
@@ -135,21 +125,3 @@ fn end_cycle_tracker(rt: &mut Executor, name: &str) -> Option<u64> {
     }
     None
 }
-
-/// Update the io buffer for the given file descriptor with the given string.
-#[allow(clippy::mut_mut)]
-fn update_io_buf(ctx: &mut SyscallContext, fd: u32, s: &str) -> Vec<String> {
-    let rt = &mut ctx.rt;
-    let entry = rt.io_buf.entry(fd).or_default();
-    entry.push_str(s);
-    if entry.contains('\n') {
-        // Return lines except for the last from buf.
-        let prev_buf = std::mem::take(entry);
-        let mut lines = prev_buf.split('\n').collect::<Vec<&str>>();
-        let last = lines.pop().unwrap_or("");
-        *entry = last.to_string();
-        lines.into_iter().map(std::string::ToString::to_string).collect::<Vec<String>>()
-    } else {
-        vec![]
-    }
-}
@@ -0,0 +1,146 @@
+//! A per-instruction trace of an execution.
+//!
+//! The [`ExecutionTrace`] records, for every executed instruction, its program counter, its
+//! instruction word, and the registers and memory words it wrote, in the order they were written.
+//! The trace only depends on the program and its input, so two executions can be compared entry by
+//! entry. It is written in a compact little-endian binary form with [`ExecutionTrace::write`], or
+//! as one line per instruction with its [`Display`] implementation.
+
+use std::{
+    fmt::{Display, Formatter, Result as FmtResult},
+    io::{self, Read, Write},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::Register;
+
+/// The effects of an executed instruction.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TraceEntry {
+    /// The program counter of the instruction.
+    pub pc: u32,
+    /// The instruction word, or zero if the program has no memory image at `pc`.
+    pub instruction: u32,
+    /// The registers written by the instruction and the written values. `LO` is register 32 and
+    /// `HI` is register 33. Writes to `$zero` are omitted.
+    pub register_writes: Vec<(u8, u32)>,
+    /// The aligned addresses of the memory words written by the instruction, including the
+    /// writes of syscalls, and the written values.
+    pub memory_writes: Vec<(u32, u32)>,
+}
+
+/// The trace of an execution, one entry per instruction.
+///
+/// The cycles run in unconstrained mode are not traced. If the execution failed, the last entry
+/// is the failing instruction, with the writes it made before failing.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExecutionTrace {
+    /// The entries of the executed instructions, in order.
+    pub entries: Vec<TraceEntry>,
+}
+
+impl ExecutionTrace {
+    /// Start the entry of the instruction `instruction` at `pc`.
+    pub(crate) fn begin(&mut self, pc: u32, instruction: u32) {
+        self.entries.push(TraceEntry { pc, instruction, ..Default::default() });
+    }
+
+    /// Record a register write of the current instruction.
+    pub(crate) fn register_write(&mut self, register: Register, value: u32) {
+        if register == Register::ZERO {
+            return;
+        }
+        if let Some(entry) = self.entries.last_mut() {
+            entry.register_writes.push((register as u8, value));
+        }
+    }
+
+    /// Record a memory write of the current instruction.
+    pub(crate) fn memory_write(&mut self, addr: u32, value: u32) {
+        if let Some(entry) = self.entries.last_mut() {
+            entry.memory_writes.push((addr, value));
+        }
+    }
+
+    /// Write the trace in its binary form.
+    ///
+    /// Every entry is written as its `pc` and instruction word, the number of register writes as
+    /// a byte, the number of memory writes, then the register writes as a register byte and a
+    /// value, and the memory writes as an address and a value. All the words are little-endian.
+    pub fn write(&self, mut writer: impl Write) -> io::Result<()> {
+        for entry in &self.entries {
+            writer.write_all(&entry.pc.to_le_bytes())?;
+            writer.write_all(&entry.instruction.to_le_bytes())?;
+            writer.write_all(&[entry.register_writes.len() as u8])?;
+            writer.write_all(&(entry.memory_writes.len() as u32).to_le_bytes())?;
+            for &(register, value) in &entry.register_writes {
+                writer.write_all(&[register])?;
+                writer.write_all(&value.to_le_bytes())?;
+            }
+            for &(addr, value) in &entry.memory_writes {
+                writer.write_all(&addr.to_le_bytes())?;
+                writer.write_all(&value.to_le_bytes())?;
+            }
+        }
+        writer.flush()
+    }
+
+    /// Read a trace written by [`Self::write`].
+    pub fn read(mut reader: impl Read) -> io::Result<Self> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes)?;
+        let mut bytes = bytes.as_slice();
+
+        let mut entries = Vec::new();
+        while !bytes.is_empty() {
+            let pc = read_u32(&mut bytes)?;
+            let instruction = read_u32(&mut bytes)?;
+            let num_register_writes = read_u8(&mut bytes)?;
+            let num_memory_writes = read_u32(&mut bytes)?;
+            let register_writes = (0..num_register_writes)
+                .map(|_| Ok((read_u8(&mut bytes)?, read_u32(&mut bytes)?)))
+                .collect::<io::Result<_>>()?;
+            let memory_writes = (0..num_memory_writes)
+                .map(|_| Ok((read_u32(&mut bytes)?, read_u32(&mut bytes)?)))
+                .collect::<io::Result<_>>()?;
+            entries.push(TraceEntry { pc, instruction, register_writes, memory_writes });
+        }
+        Ok(Self { entries })
+    }
+}
+
+fn read_u8(bytes: &mut &[u8]) -> io::Result<u8> {
+    let mut buf = [0; 1];
+    bytes.read_exact(&mut buf)?;
+    Ok(buf[0])
+}
+
+fn read_u32(bytes: &mut &[u8]) -> io::Result<u32> {
+    let mut buf = [0; 4];
+    bytes.read_exact(&mut buf)?;
+    Ok(u32::from_le_bytes(buf))
+}
+
+impl Display for TraceEntry {
+    /// Format the entry as `pc instruction r<n>=value... [addr]=value...`, in hex.
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "{:08x} {:08x}", self.pc, self.instruction)?;
+        for (register, value) in &self.register_writes {
+            write!(f, " r{register}={value:08x}")?;
+        }
+        for (addr, value) in &self.memory_writes {
+            write!(f, " [{addr:08x}]={value:08x}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for ExecutionTrace {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        for entry in &self.entries {
+            writeln!(f, "{entry}")?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,263 @@
+//! The read-only filesystem served to the guest through the Linux syscalls.
+//!
+//! The files are part of the memory image of the program, so the verifying key commits to them.
+//! They are appended to the ELF with [`attach_files`] and laid out from [`VFS_BASE`]:
+//!
+//! | Address                   | Contents                                                    |
+//! | ------------------------- | ----------------------------------------------------------- |
+//! | `VFS_BASE`                | The number of files `N`.                                    |
+//! | `VFS_FDS + 8 * k`         | File descriptor `FIRST_VIRTUAL_FD + k`: the index of its    |
+//! |                           | file plus one, or zero when closed, then the offset of the  |
+//! |                           | next read.                                                  |
+//! | `VFS_ENTRIES + 16 * i`    | File `i`: the addresses of its path and data, its size and  |
+//! |                           | the address of its `stat64` buffer.                         |
+//!
+//! The entries are sorted by path, which lets the AIR prove that a path is missing from its
+//! neighbours. The paths, data and `stat64` buffers of the files follow the entries, each aligned
+//! to a word.
+
+use std::collections::BTreeMap;
+
+use anyhow::{bail, Context, Result};
+use zkm_primitives::consts::fd::FD_PANIC;
+
+/// The address of the number of files.
+pub const VFS_BASE: u32 = 0x7000_0000;
+/// The address of the file descriptor table.
+pub const VFS_FDS: u32 = VFS_BASE + 4;
+/// The number of file descriptors that can be open at once.
+pub const VFS_MAX_FDS: u32 = 16;
+/// The address of the file entries.
+pub const VFS_ENTRIES: u32 = VFS_FDS + 8 * VFS_MAX_FDS;
+/// The end of the region holding the files, below the stack.
+pub const VFS_END: u32 = 0x7E00_0000;
+/// The maximum number of bytes copied by a single read.
+pub const VFS_MAX_READ: u32 = 4096;
+/// The size of the MIPS o32 `stat64` buffer.
+pub const STAT64_SIZE: u32 = 104;
+
+/// The `dirfd` of `openat` resolving relative paths against the working directory.
+pub const AT_FDCWD: u32 = -100i32 as u32;
+/// The access mode bits of the flags of `open`.
+pub const O_ACCMODE: u32 = 3;
+
+/// The first file descriptor handed out for virtual files.
+///
+/// The virtual files are numbered above the file descriptors reserved for the public values, the
+/// hints, the hooks and the panic reports, and the SysFile AIR compares them as single bytes.
+pub const FIRST_VIRTUAL_FD: u32 = 32;
+const _: () = assert!(FD_PANIC < FIRST_VIRTUAL_FD && FIRST_VIRTUAL_FD + VFS_MAX_FDS <= 256);
+
+/// No such file or directory.
+pub const MIPS_ENOENT: u32 = 2;
+/// Bad file descriptor.
+pub const MIPS_EBADF: u32 = 9;
+/// Not a directory.
+pub const MIPS_ENOTDIR: u32 = 20;
+/// Too many open files.
+pub const MIPS_EMFILE: u32 = 24;
+/// Read-only file system.
+pub const MIPS_EROFS: u32 = 30;
+
+/// The magic number ending an ELF with attached files.
+const VFS_MAGIC: [u8; 8] = *b"ZKMVFS\0\x01";
+
+/// The mode of the files: a regular file readable by everyone.
+const VFS_FILE_MODE: u32 = 0o100444;
+
+/// Append `files`, indexed by their path, to `elf`.
+///
+/// [`crate::Program::from`] lays them out in the memory image, where the guest can open and read
+/// them. Relative paths are resolved against `/`.
+///
+/// The files are attached to the ELF rather than to the stdin so that the verifying key commits
+/// to them: the proof doesn't constrain the stdin.
+#[must_use]
+pub fn attach_files(elf: &[u8], files: &BTreeMap<String, Vec<u8>>) -> Vec<u8> {
+    let (elf, _) = split_files(elf).unwrap_or((elf, BTreeMap::new()));
+    let files = bincode::serialize(files).expect("failed to serialize the files");
+    let mut bytes = Vec::with_capacity(elf.len() + files.len() + 16);
+    bytes.extend_from_slice(elf);
+    bytes.extend_from_slice(&files);
+    bytes.extend_from_slice(&(files.len() as u64).to_le_bytes());
+    bytes.extend_from_slice(&VFS_MAGIC);
+    bytes
+}
+
+/// Split the files attached by [`attach_files`] from `elf`.
+pub(crate) fn split_files(elf: &[u8]) -> Result<(&[u8], BTreeMap<String, Vec<u8>>)> {
+    let Some(rest) = elf.strip_suffix(&VFS_MAGIC) else {
+        return Ok((elf, BTreeMap::new()));
+    };
+    let (rest, len) = rest.split_at(rest.len().checked_sub(8).context("Invalid attached files")?);
+    let len = usize::try_from(u64::from_le_bytes(len.try_into()?))?;
+    let (elf, files) =
+        rest.split_at(rest.len().checked_sub(len).context("Invalid attached files")?);
+    let files = bincode::deserialize(files).context("Invalid attached files")?;
+    Ok((elf, files))
+}
+
+/// Lay out `files`, indexed by their path, from [`VFS_BASE`].
+///
+/// Only programs with attached files get this layout, so that the verifying keys of the other
+/// programs don't change. Their region is uninitialized memory like any other, so the files they
+/// open are chosen by the prover and are as untrusted as the stdin.
+pub(crate) fn vfs_image(files: &BTreeMap<String, Vec<u8>>) -> Result<BTreeMap<u32, u32>> {
+    let mut files: Vec<_> =
+        files.iter().map(|(path, bytes)| (normalize_path(path).into_bytes(), bytes)).collect();
+    files.sort_by(|(a, _), (b, _)| a.cmp(b));
+    for pair in files.windows(2) {
+        if pair[0].0 == pair[1].0 {
+            bail!("Duplicate virtual file {}", String::from_utf8_lossy(&pair[0].0));
+        }
+    }
+    if files.len() >= 1 << 16 {
+        bail!("Too many virtual files");
+    }
+
+    let mut image = BTreeMap::new();
+    image.insert(VFS_BASE, files.len() as u32);
+    for addr in (VFS_FDS..VFS_ENTRIES).step_by(4) {
+        image.insert(addr, 0);
+    }
+
+    let mut next = VFS_ENTRIES as u64 + 16 * files.len() as u64;
+    for (i, (path, bytes)) in files.iter().enumerate() {
+        if path.contains(&0) {
+            bail!("Invalid virtual file {}", String::from_utf8_lossy(path));
+        }
+        let size = bytes.len() as u64;
+        let stat_addr = next;
+        let path_addr = stat_addr + STAT64_SIZE as u64;
+        let data_addr = path_addr + (path.len() as u64 + 1).next_multiple_of(4);
+        next = data_addr + size.next_multiple_of(4);
+        if next > VFS_END as u64 {
+            bail!("The virtual files exceed {:#x} bytes", VFS_END - VFS_BASE);
+        }
+
+        let entry = VFS_ENTRIES + 16 * i as u32;
+        image.insert(entry, path_addr as u32);
+        image.insert(entry + 4, data_addr as u32);
+        image.insert(entry + 8, size as u32);
+        image.insert(entry + 12, stat_addr as u32);
+
+        insert_bytes(&mut image, stat_addr as u32, &stat64(i as u32 + 1, size as u32));
+        insert_bytes(&mut image, path_addr as u32, &[path.as_slice(), &[0]].concat());
+        insert_bytes(&mut image, data_addr as u32, bytes);
+    }
+    Ok(image)
+}
+
+/// The MIPS o32 `stat64` buffer of the file with inode `ino` and `size` bytes.
+fn stat64(ino: u32, size: u32) -> [u8; STAT64_SIZE as usize] {
+    let mut stat = [0; STAT64_SIZE as usize];
+    let mut put = |offset: usize, value: u32| {
+        stat[offset..offset + 4].copy_from_slice(&value.to_le_bytes());
+    };
+    put(16, ino);
+    put(24, VFS_FILE_MODE);
+    put(28, 1); // st_nlink
+    put(56, size);
+    put(88, 4096); // st_blksize
+    put(96, size.div_ceil(512)); // st_blocks
+    stat
+}
+
+/// Insert `bytes` from the word at `addr`, padding the last word with zeros.
+fn insert_bytes(image: &mut BTreeMap<u32, u32>, addr: u32, bytes: &[u8]) {
+    for (i, chunk) in bytes.chunks(4).enumerate() {
+        let mut word = [0; 4];
+        word[..chunk.len()].copy_from_slice(chunk);
+        image.insert(addr + 4 * i as u32, u32::from_le_bytes(word));
+    }
+}
+
+/// Resolve `path` against `/`, removing the `.` and `..` components.
+fn normalize_path(path: &str) -> String {
+    let mut components = Vec::new();
+    for component in path.split('/') {
+        match component {
+            "" | "." => {}
+            ".." => {
+                components.pop();
+            }
+            component => components.push(component),
+        }
+    }
+    format!("/{}", components.join("/"))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::{
+        attach_files, split_files, vfs_image, VFS_BASE, VFS_ENTRIES, VFS_FDS, VFS_FILE_MODE,
+    };
+    use crate::Program;
+
+    fn files() -> BTreeMap<String, Vec<u8>> {
+        BTreeMap::from([
+            ("data/../data/input.bin".to_string(), vec![1, 2, 3, 4, 5]),
+            ("/config.toml".to_string(), b"key = 1\n".to_vec()),
+        ])
+    }
+
+    #[test]
+    fn attached_files_round_trip() {
+        let elf = b"\x7fELF".to_vec();
+        let attached = attach_files(&elf, &files());
+        let (stripped, attached_files) = split_files(&attached).unwrap();
+        assert_eq!(stripped, elf.as_slice());
+        assert_eq!(attached_files, files());
+
+        // Attaching again replaces the files.
+        let reattached = attach_files(&attached, &BTreeMap::new());
+        assert_eq!(split_files(&reattached).unwrap(), (elf.as_slice(), BTreeMap::new()));
+        assert_eq!(split_files(&elf).unwrap(), (elf.as_slice(), BTreeMap::new()));
+    }
+
+    #[test]
+    fn image_is_sorted_and_aligned() {
+        let image = vfs_image(&files()).unwrap();
+        assert_eq!(image[&VFS_BASE], 2);
+        assert!((VFS_FDS..VFS_ENTRIES).step_by(4).all(|addr| image[&addr] == 0));
+
+        let read = |addr: u32, len: usize| -> Vec<u8> {
+            (0..len as u32)
+                .map(|i| image[&((addr + i) & !3)].to_le_bytes()[((addr + i) % 4) as usize])
+                .collect()
+        };
+        let entry = |i: u32| -> [u32; 4] {
+            std::array::from_fn(|k| image[&(VFS_ENTRIES + 16 * i + 4 * k as u32)])
+        };
+
+        let [path, data, size, stat] = entry(0);
+        assert_eq!(read(path, 13), b"/config.toml\0");
+        assert_eq!(read(data, size as usize), b"key = 1\n");
+        assert_eq!(read(stat + 24, 4), VFS_FILE_MODE.to_le_bytes());
+
+        let [path, data, size, stat] = entry(1);
+        assert_eq!(read(path, 16), b"/data/input.bin\0");
+        assert_eq!(read(data, size as usize), [1, 2, 3, 4, 5]);
+        assert_eq!(read(stat + 56, 4), 5u32.to_le_bytes());
+        assert!(path % 4 == 0 && data % 4 == 0 && stat % 4 == 0);
+
+        let empty = vfs_image(&BTreeMap::new()).unwrap();
+        assert_eq!(empty[&VFS_BASE], 0);
+        assert!(vfs_image(&BTreeMap::from([
+            ("a".to_string(), vec![]),
+            ("/a".to_string(), vec![1]),
+        ]))
+        .is_err());
+    }
+
+    #[test]
+    fn image_is_untouched_without_files() {
+        let program = Program::new(vec![], 0, 0);
+        let with_files = program.clone().with_files(&BTreeMap::new()).unwrap();
+        assert_eq!(with_files.image, program.image);
+        assert!(!with_files.has_files());
+        assert!(program.with_files(&files()).unwrap().has_files());
+    }
+}
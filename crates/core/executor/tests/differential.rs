@@ -0,0 +1,382 @@
+//! Differential tests of the executor against the reference interpreter in [`reference`].
+//!
+//! The executor runs a program with tracing enabled, and the reference replays it instruction by
+//! instruction. After every instruction, the registers and memory words written by either side
+//! must hold the same values. The effects of the syscalls are taken from the trace.
+
+mod reference;
+
+use std::collections::HashSet;
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use reference::{Outcome, Reference};
+use test_artifacts::{FIBONACCI_ELF, HELLO_WORLD_ELF};
+use zkm_core_executor::{ExecutionTrace, Executor, Instruction, Program, ZKMContext};
+use zkm_stark::ZKMCoreOpts;
+
+/// The first instruction on which the executor and the reference disagree.
+#[derive(Debug)]
+struct Divergence {
+    step: usize,
+    pc: u32,
+    instruction: u32,
+    reason: String,
+}
+
+/// Run `program` in the executor, returning its trace and whether the execution failed.
+fn run_executor(program: Program) -> (ExecutionTrace, bool) {
+    let context = ZKMContext::builder().trace(true).build();
+    let mut runtime = Executor::with_context(program, ZKMCoreOpts::default(), context);
+    let failed = runtime.run_very_fast().is_err();
+    let trace = runtime.report.trace.take().map(|trace| *trace);
+    (trace.or_else(|| runtime.execution_trace.take()).unwrap(), failed)
+}
+
+/// Replay `trace` in the reference, returning the first divergence.
+///
+/// The comparison stops without a divergence on an UNPREDICTABLE instruction.
+fn first_divergence(program: &Program, trace: &ExecutionTrace, failed: bool) -> Option<Divergence> {
+    let mut reference = Reference::new(&program.image, program.pc_start);
+    // The state of the executor, rebuilt from the writes of the trace.
+    let mut executor = reference.clone();
+
+    for (step, entry) in trace.entries.iter().enumerate() {
+        let divergence = |reason: String| {
+            Some(Divergence { step, pc: entry.pc, instruction: entry.instruction, reason })
+        };
+        let last = step + 1 == trace.entries.len();
+
+        if reference.pc != entry.pc {
+            return divergence(format!("the reference is at {:08x}", reference.pc));
+        }
+        if reference.word(entry.pc) != entry.instruction {
+            return divergence(format!("the reference reads {:08x}", reference.word(entry.pc)));
+        }
+
+        for &(register, value) in &entry.register_writes {
+            executor.set_register(register as usize, value);
+        }
+        for &(addr, value) in &entry.memory_writes {
+            executor.set_word(addr, value);
+        }
+
+        match reference.step() {
+            Outcome::Executed => {}
+            Outcome::Syscall => {
+                for &(register, value) in &entry.register_writes {
+                    reference.set_register(register as usize, value);
+                }
+                for &(addr, value) in &entry.memory_writes {
+                    reference.set_word(addr, value);
+                }
+                reference.finish_syscall();
+            }
+            Outcome::Exception(_) if failed && last => return None,
+            Outcome::Exception(exception) => {
+                return divergence(format!("the reference raised: {exception}"))
+            }
+            Outcome::Unpredictable(_) => return None,
+        }
+        if failed && last {
+            return divergence("the executor failed".to_string());
+        }
+
+        let registers: HashSet<usize> = entry
+            .register_writes
+            .iter()
+            .map(|&(register, _)| register as usize)
+            .chain(reference.register_writes.iter().copied())
+            .collect();
+        for register in registers {
+            let (actual, expected) = (executor.register(register), reference.register(register));
+            if actual != expected {
+                return divergence(format!("r{register} is {actual:08x}, expected {expected:08x}"));
+            }
+        }
+        let addrs: HashSet<u32> = entry
+            .memory_writes
+            .iter()
+            .map(|&(addr, _)| addr)
+            .chain(reference.memory_writes.iter().copied())
+            .collect();
+        for addr in addrs {
+            let (actual, expected) = (executor.word(addr), reference.word(addr));
+            if actual != expected {
+                return divergence(format!(
+                    "[{addr:08x}] is {actual:08x}, expected {expected:08x}"
+                ));
+            }
+        }
+    }
+    None
+}
+
+fn assert_no_divergence(program: Program) {
+    let (trace, failed) = run_executor(program.clone());
+    if let Some(divergence) = first_divergence(&program, &trace, failed) {
+        let Divergence { step, pc, instruction, reason } = divergence;
+        let decoded = Instruction::decode_from(instruction).unwrap();
+        panic!("divergence at step {step}, pc {pc:08x}, instruction {instruction:08x} ({decoded:?}): {reason}");
+    }
+}
+
+#[test]
+fn fibonacci_matches_the_reference() {
+    assert_no_divergence(Program::from(FIBONACCI_ELF).unwrap());
+}
+
+#[test]
+fn hello_world_matches_the_reference() {
+    assert_no_divergence(Program::from(HELLO_WORLD_ELF).unwrap());
+}
+
+/// The address of the first instruction of the generated programs.
+const CODE_BASE: u32 = 0x1000;
+/// The base address of the memory accesses of the generated programs.
+const DATA_BASE: u32 = 0x0010_0000;
+/// The register holding [`DATA_BASE`], which the generated programs never write.
+const BASE_REGISTER: u32 = 16;
+/// The number of random instructions of the generated programs.
+const BODY_LEN: usize = 64;
+
+fn special(rs: u32, rt: u32, rd: u32, sa: u32, funct: u32) -> u32 {
+    (rs << 21) | (rt << 16) | (rd << 11) | (sa << 6) | funct
+}
+
+fn immediate(opcode: u32, rs: u32, rt: u32, imm: u32) -> u32 {
+    (opcode << 26) | (rs << 21) | (rt << 16) | (imm & 0xffff)
+}
+
+/// A generator of random programs.
+///
+/// The programs only use the instructions the executor supports without deviating from the
+/// architecture, so they exclude `ADD`, `ADDI` and `SUB`, which do not trap on overflow in the
+/// executor, and `SC`, which always succeeds in the executor.
+struct Generator {
+    rng: StdRng,
+    words: Vec<u32>,
+}
+
+impl Generator {
+    /// A random element of `options`.
+    fn pick(&mut self, options: &[u32]) -> u32 {
+        options[self.rng.gen_range(0..options.len())]
+    }
+
+    fn source(&mut self) -> u32 {
+        self.rng.gen_range(0..32)
+    }
+
+    fn destination(&mut self) -> u32 {
+        loop {
+            let register = self.rng.gen_range(0..32);
+            if register != BASE_REGISTER {
+                return register;
+            }
+        }
+    }
+
+    /// The address of the `index`-th word of the program.
+    fn addr(index: usize) -> u32 {
+        CODE_BASE + 4 * index as u32
+    }
+
+    /// Set every register but `$zero` to a random value.
+    fn prelude(&mut self) {
+        for register in 1..32 {
+            let value: u32 = if register == BASE_REGISTER { DATA_BASE } else { self.rng.gen() };
+            self.words.push(immediate(0x0f, 0, register, value >> 16));
+            self.words.push(immediate(0x0d, register, register, value));
+        }
+        let (hi, lo) = (self.source(), self.source());
+        self.words.push(special(hi, 0, 0, 0, 0x11));
+        self.words.push(special(lo, 0, 0, 0, 0x13));
+    }
+
+    /// A random instruction that does not transfer control.
+    fn straight(&mut self) -> u32 {
+        let (s, t, d) = (self.source(), self.source(), self.destination());
+        let imm: u32 = self.rng.gen();
+        let sa = self.rng.gen_range(0..32);
+        match self.rng.gen_range(0..16) {
+            // ADDU, SUBU, AND, OR, XOR, NOR, SLT, SLTU, MOVZ and MOVN.
+            0 => {
+                let funct =
+                    self.pick(&[0x21, 0x23, 0x24, 0x25, 0x26, 0x27, 0x2a, 0x2b, 0x0a, 0x0b]);
+                special(s, t, d, 0, funct)
+            }
+            // SLL, SRL, ROTR and SRA.
+            1 => match self.rng.gen_range(0..4) {
+                0 => special(0, t, d, sa, 0x00),
+                1 => special(0, t, d, sa, 0x02),
+                2 => special(1, t, d, sa, 0x02),
+                _ => special(0, t, d, sa, 0x03),
+            },
+            // SLLV, SRLV, ROTRV and SRAV.
+            2 => match self.rng.gen_range(0..4) {
+                0 => special(s, t, d, 0, 0x04),
+                1 => special(s, t, d, 0, 0x06),
+                2 => special(s, t, d, 1, 0x06),
+                _ => special(s, t, d, 0, 0x07),
+            },
+            // ADDIU, SLTI, SLTIU, ANDI, ORI, XORI and LUI.
+            3 => {
+                let opcode = self.pick(&[0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f]);
+                immediate(opcode, if opcode == 0x0f { 0 } else { s }, d, imm)
+            }
+            // MULT, MULTU, DIV, DIVU, MFHI, MTHI, MFLO and MTLO.
+            4 => match self.rng.gen_range(0..8) {
+                0 => special(s, t, 0, 0, 0x18),
+                1 => special(s, t, 0, 0, 0x19),
+                2 => special(s, t, 0, 0, 0x1a),
+                3 => special(s, t, 0, 0, 0x1b),
+                4 => special(0, 0, d, 0, 0x10),
+                5 => special(s, 0, 0, 0, 0x11),
+                6 => special(0, 0, d, 0, 0x12),
+                _ => special(s, 0, 0, 0, 0x13),
+            },
+            // MOD and MODU.
+            5 => special(s, t, d, 3, self.pick(&[0x1a, 0x1b])),
+            // MADD, MADDU, MUL, MSUB, MSUBU, CLZ and CLO. CLZ and CLO encode `rd` in `rt` too.
+            6 => match self.pick(&[0x00, 0x01, 0x02, 0x04, 0x05, 0x20, 0x21]) {
+                0x02 => (0x1c << 26) | special(s, t, d, 0, 0x02),
+                funct @ (0x20 | 0x21) => (0x1c << 26) | special(s, d, d, 0, funct),
+                funct => (0x1c << 26) | special(s, t, 0, 0, funct),
+            },
+            // EXT, within the bounds of the register.
+            7 => {
+                let pos = self.rng.gen_range(0..32);
+                let size = self.rng.gen_range(1..=32 - pos);
+                (0x1f << 26) | special(s, d, size - 1, pos, 0x00)
+            }
+            // INS, within the bounds of the register.
+            8 => {
+                let pos = self.rng.gen_range(0..32);
+                let msb = self.rng.gen_range(pos..32);
+                (0x1f << 26) | special(s, d, msb, pos, 0x04)
+            }
+            // WSBH, SEB and SEH.
+            9 => (0x1f << 26) | special(0, t, d, self.pick(&[0x02, 0x10, 0x18]), 0x20),
+            // Loads and stores relative to the base register, mostly aligned.
+            _ => {
+                let mut offset = self.rng.gen_range(-64i32..64) * 4;
+                if self.rng.gen_ratio(1, 4) {
+                    offset += self.rng.gen_range(1..4);
+                }
+                let offset = offset as u32;
+                if self.rng.gen() {
+                    let opcode = self.pick(&[0x20, 0x21, 0x22, 0x23, 0x24, 0x25, 0x26, 0x30]);
+                    immediate(opcode, BASE_REGISTER, d, offset)
+                } else {
+                    let opcode = self.pick(&[0x28, 0x29, 0x2a, 0x2b, 0x2e]);
+                    immediate(opcode, BASE_REGISTER, t, offset)
+                }
+            }
+        }
+    }
+
+    /// A random forward branch or jump from `index`, followed by its delay slot.
+    fn control(&mut self, index: usize, end: usize) -> Vec<u32> {
+        // The target is after the delay slot, up to the end of the program.
+        let target = self.rng.gen_range(index + 2..=end);
+        let offset = (target - (index + 1)) as u32;
+        let (s, t) = (self.source(), self.source());
+        let control = match self.rng.gen_range(0..4) {
+            // BEQ, BNE, BLEZ and BGTZ.
+            0 => immediate(self.pick(&[0x04, 0x05, 0x06, 0x07]), s, t, offset),
+            // BLTZ, BGEZ and BAL.
+            1 => match self.rng.gen_range(0..3) {
+                0 => immediate(0x01, s, 0x00, offset),
+                1 => immediate(0x01, s, 0x01, offset),
+                _ => immediate(0x01, 0, 0x11, offset),
+            },
+            // J and JAL.
+            2 => (self.pick(&[0x02, 0x03]) << 26) | (Self::addr(target) >> 2),
+            // JALR through a register loaded with the target, taking the place of the delay slot
+            // of the previous instructions.
+            _ => {
+                let register = self.destination().max(1);
+                let link = loop {
+                    let link = self.destination();
+                    if link != register {
+                        break link;
+                    }
+                };
+                let target = Self::addr(target + 2);
+                return vec![
+                    immediate(0x0f, 0, register, target >> 16),
+                    immediate(0x0d, register, register, target),
+                    special(register, 0, link, 0, 0x09),
+                    self.straight(),
+                ];
+            }
+        };
+        vec![control, self.straight()]
+    }
+
+    /// Generate a random program.
+    fn program(seed: u64) -> Program {
+        let mut generator = Self { rng: StdRng::seed_from_u64(seed), words: Vec::new() };
+        generator.prelude();
+        let end = generator.words.len() + BODY_LEN;
+        while generator.words.len() < end {
+            let index = generator.words.len();
+            if index + 4 < end && generator.rng.gen_ratio(1, 8) {
+                let words = generator.control(index, end);
+                generator.words.extend(words);
+            } else {
+                let word = generator.straight();
+                generator.words.push(word);
+            }
+        }
+
+        let instructions =
+            generator.words.iter().map(|&word| Instruction::decode_from(word).unwrap()).collect();
+        let mut program = Program::new(instructions, CODE_BASE, CODE_BASE);
+        program.image = generator
+            .words
+            .iter()
+            .enumerate()
+            .map(|(index, &word)| (Self::addr(index), word))
+            .collect();
+        program
+    }
+}
+
+/// Whether the reference runs `program` to its end without an UNPREDICTABLE instruction.
+fn is_predictable(program: &Program) -> bool {
+    let end = Generator::addr(program.instructions.len());
+    let mut reference = Reference::new(&program.image, program.pc_start);
+    while (CODE_BASE..end).contains(&reference.pc) {
+        match reference.step() {
+            Outcome::Executed => {}
+            Outcome::Exception(_) => return true,
+            Outcome::Syscall | Outcome::Unpredictable(_) => return false,
+        }
+    }
+    true
+}
+
+#[test]
+fn random_programs_match_the_reference() {
+    let mut tested = 0;
+    for seed in 0..256 {
+        let program = Generator::program(seed);
+        // The executor is allowed to panic on the UNPREDICTABLE instructions, such as overflowing
+        // divisions.
+        if !is_predictable(&program) {
+            continue;
+        }
+        let (trace, failed) = run_executor(program.clone());
+        if let Some(divergence) = first_divergence(&program, &trace, failed) {
+            let Divergence { step, pc, instruction, reason } = divergence;
+            let decoded = Instruction::decode_from(instruction).unwrap();
+            panic!(
+                "seed {seed}: divergence at step {step}, pc {pc:08x}, instruction {instruction:08x} \
+                 ({decoded:?}): {reason}"
+            );
+        }
+        tested += 1;
+    }
+    assert!(tested >= 128, "only {tested} of the random programs were predictable");
+}
@@ -0,0 +1,20 @@
+use zkm_core_executor::{Executor, Register};
+use zkm_instruction_test_defs::{fuzz_program, FUZZ_DATA_BASE, FUZZ_OPCODES};
+use zkm_stark::ZKMCoreOpts;
+
+#[test]
+fn fuzz_programs_execute_every_opcode() {
+    for seed in 0..64 {
+        let mut runtime = Executor::new(fuzz_program(seed, 128), ZKMCoreOpts::default());
+        runtime.run().unwrap_or_else(|err| panic!("seed {seed} failed: {err:?}"));
+
+        assert!(runtime.state.exited, "seed {seed} did not halt");
+        assert_eq!(runtime.register(Register::S0), FUZZ_DATA_BASE, "seed {seed}: base register");
+        for opcode in FUZZ_OPCODES {
+            assert!(
+                runtime.report.opcode_counts[opcode] > 0,
+                "seed {seed}: {opcode:?} was not executed"
+            );
+        }
+    }
+}
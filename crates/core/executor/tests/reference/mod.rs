@@ -0,0 +1,535 @@
+//! A reference interpreter of the MIPS32 release 2 instructions, written from the architecture
+//! manual independently of the executor, to differentially test its decoding and semantics.
+//!
+//! The registers are numbered like in the executor: the general purpose registers are 0 to 31,
+//! `LO` is 32 and `HI` is 33. Registers 34 and 35 hold the program break and the heap pointer of
+//! the syscalls. The interpreter does not implement the syscalls: it stops on a `SYSCALL`, for the
+//! caller to apply its effects and resume with [`Reference::finish_syscall`].
+//!
+//! Besides release 2, the interpreter supports the `MOD` and `MODU` instructions of release 6,
+//! which the executor accepts in the encoding of `DIV` and `DIVU` with a shift amount of 3.
+
+use std::collections::{BTreeMap, HashMap};
+
+/// The number of registers.
+pub const NUM_REGISTERS: usize = 36;
+
+const RA: usize = 31;
+const LO: usize = 32;
+const HI: usize = 33;
+
+/// The outcome of the execution of an instruction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    /// The instruction was executed.
+    Executed,
+    /// The instruction is a `SYSCALL`, which is left to the caller.
+    Syscall,
+    /// The instruction raised an exception: a trap, an address error, an integer overflow or a
+    /// reserved instruction.
+    Exception(String),
+    /// The result of the instruction is UNPREDICTABLE in the architecture.
+    Unpredictable(String),
+}
+
+/// The state of the reference interpreter.
+#[derive(Debug, Clone)]
+pub struct Reference {
+    registers: [u32; NUM_REGISTERS],
+    memory: HashMap<u32, u32>,
+    /// The address of the next instruction to execute.
+    pub pc: u32,
+    /// The address of the instruction after it, which differs from `pc + 4` in a delay slot.
+    pub next_pc: u32,
+    in_delay_slot: bool,
+    ll_bit: bool,
+    /// The registers written by the last instruction.
+    pub register_writes: Vec<usize>,
+    /// The memory words written by the last instruction.
+    pub memory_writes: Vec<u32>,
+}
+
+impl Reference {
+    /// Create an interpreter starting at `pc_start`, with the registers and the memory words of
+    /// `image`. The addresses of the image below [`NUM_REGISTERS`] are registers.
+    pub fn new(image: &BTreeMap<u32, u32>, pc_start: u32) -> Self {
+        let mut registers = [0; NUM_REGISTERS];
+        let mut memory = HashMap::new();
+        for (&addr, &value) in image {
+            if addr < NUM_REGISTERS as u32 {
+                registers[addr as usize] = value;
+            } else {
+                memory.insert(addr, value);
+            }
+        }
+        registers[0] = 0;
+        Self {
+            registers,
+            memory,
+            pc: pc_start,
+            next_pc: pc_start.wrapping_add(4),
+            in_delay_slot: false,
+            ll_bit: false,
+            register_writes: Vec::new(),
+            memory_writes: Vec::new(),
+        }
+    }
+
+    /// The value of a register.
+    pub fn register(&self, register: usize) -> u32 {
+        self.registers[register]
+    }
+
+    /// The value of the memory word at the aligned address `addr`.
+    pub fn word(&self, addr: u32) -> u32 {
+        self.memory.get(&addr).copied().unwrap_or_default()
+    }
+
+    /// Write a register, ignoring the writes to `$zero`.
+    pub fn set_register(&mut self, register: usize, value: u32) {
+        if register != 0 {
+            self.registers[register] = value;
+            self.register_writes.push(register);
+        }
+    }
+
+    /// Write the memory word at the aligned address `addr`.
+    pub fn set_word(&mut self, addr: u32, value: u32) {
+        self.memory.insert(addr, value);
+        self.memory_writes.push(addr);
+    }
+
+    /// Move past a `SYSCALL` once its effects are applied.
+    pub fn finish_syscall(&mut self) {
+        self.advance(None, false);
+    }
+
+    /// Execute the instruction at `pc`.
+    #[allow(clippy::too_many_lines)]
+    pub fn step(&mut self) -> Outcome {
+        self.register_writes.clear();
+        self.memory_writes.clear();
+
+        let word = self.word(self.pc);
+        let opcode = word >> 26;
+        let rs = ((word >> 21) & 0x1f) as usize;
+        let rt = ((word >> 16) & 0x1f) as usize;
+        let rd = ((word >> 11) & 0x1f) as usize;
+        let sa = (word >> 6) & 0x1f;
+        let funct = word & 0x3f;
+        let imm = word & 0xffff;
+        let simm = imm as u16 as i16 as i32 as u32;
+
+        let s = self.registers[rs];
+        let t = self.registers[rt];
+
+        // The address of the delay slot, and the target of the taken branches.
+        let delay_slot = self.next_pc;
+        let branch_target = delay_slot.wrapping_add(simm << 2);
+        let mut target = None;
+        let mut is_control = false;
+
+        macro_rules! exception {
+            ($($arg:tt)*) => {
+                return Outcome::Exception(format!($($arg)*))
+            };
+        }
+        macro_rules! unpredictable {
+            ($($arg:tt)*) => {
+                return Outcome::Unpredictable(format!($($arg)*))
+            };
+        }
+
+        match opcode {
+            // SPECIAL
+            0x00 => match funct {
+                // SLL
+                0x00 => self.set_register(rd, t << sa),
+                // SRL and ROTR
+                0x02 => match rs {
+                    0 => self.set_register(rd, t >> sa),
+                    1 => self.set_register(rd, t.rotate_right(sa)),
+                    _ => exception!("reserved instruction {word:08x}"),
+                },
+                // SRA
+                0x03 => self.set_register(rd, ((t as i32) >> sa) as u32),
+                // SLLV
+                0x04 => self.set_register(rd, t << (s & 0x1f)),
+                // SRLV and ROTRV
+                0x06 => match sa {
+                    0 => self.set_register(rd, t >> (s & 0x1f)),
+                    1 => self.set_register(rd, t.rotate_right(s & 0x1f)),
+                    _ => exception!("reserved instruction {word:08x}"),
+                },
+                // SRAV
+                0x07 => self.set_register(rd, ((t as i32) >> (s & 0x1f)) as u32),
+                // JR
+                0x08 => {
+                    is_control = true;
+                    target = Some(s);
+                }
+                // JALR
+                0x09 => {
+                    if rs == rd {
+                        unpredictable!("JALR with rs = rd");
+                    }
+                    is_control = true;
+                    target = Some(s);
+                    self.set_register(rd, delay_slot.wrapping_add(4));
+                }
+                // MOVZ
+                0x0a => {
+                    if t == 0 {
+                        self.set_register(rd, s);
+                    }
+                }
+                // MOVN
+                0x0b => {
+                    if t != 0 {
+                        self.set_register(rd, s);
+                    }
+                }
+                // SYSCALL
+                0x0c => {
+                    if self.in_delay_slot {
+                        unpredictable!("SYSCALL in a delay slot");
+                    }
+                    return Outcome::Syscall;
+                }
+                // SYNC
+                0x0f => {}
+                // MFHI
+                0x10 => self.set_register(rd, self.registers[HI]),
+                // MTHI
+                0x11 => self.set_register(HI, s),
+                // MFLO
+                0x12 => self.set_register(rd, self.registers[LO]),
+                // MTLO
+                0x13 => self.set_register(LO, s),
+                // MULT
+                0x18 => {
+                    let product = (s as i32 as i64 * t as i32 as i64) as u64;
+                    self.set_register(LO, product as u32);
+                    self.set_register(HI, (product >> 32) as u32);
+                }
+                // MULTU
+                0x19 => {
+                    let product = s as u64 * t as u64;
+                    self.set_register(LO, product as u32);
+                    self.set_register(HI, (product >> 32) as u32);
+                }
+                // DIV and MOD
+                0x1a => {
+                    let (dividend, divisor) = (s as i32, t as i32);
+                    if divisor == 0 || (dividend == i32::MIN && divisor == -1) {
+                        unpredictable!("signed division of {dividend} by {divisor}");
+                    }
+                    match sa {
+                        0 => {
+                            self.set_register(LO, (dividend / divisor) as u32);
+                            self.set_register(HI, (dividend % divisor) as u32);
+                        }
+                        3 => self.set_register(rd, (dividend % divisor) as u32),
+                        _ => exception!("reserved instruction {word:08x}"),
+                    }
+                }
+                // DIVU and MODU
+                0x1b => {
+                    if t == 0 {
+                        unpredictable!("unsigned division of {s} by zero");
+                    }
+                    match sa {
+                        0 => {
+                            self.set_register(LO, s / t);
+                            self.set_register(HI, s % t);
+                        }
+                        3 => self.set_register(rd, s % t),
+                        _ => exception!("reserved instruction {word:08x}"),
+                    }
+                }
+                // ADD
+                0x20 => match (s as i32).checked_add(t as i32) {
+                    Some(sum) => self.set_register(rd, sum as u32),
+                    None => exception!("integer overflow"),
+                },
+                // ADDU
+                0x21 => self.set_register(rd, s.wrapping_add(t)),
+                // SUB
+                0x22 => match (s as i32).checked_sub(t as i32) {
+                    Some(difference) => self.set_register(rd, difference as u32),
+                    None => exception!("integer overflow"),
+                },
+                // SUBU
+                0x23 => self.set_register(rd, s.wrapping_sub(t)),
+                // AND
+                0x24 => self.set_register(rd, s & t),
+                // OR
+                0x25 => self.set_register(rd, s | t),
+                // XOR
+                0x26 => self.set_register(rd, s ^ t),
+                // NOR
+                0x27 => self.set_register(rd, !(s | t)),
+                // SLT
+                0x2a => self.set_register(rd, ((s as i32) < (t as i32)) as u32),
+                // SLTU
+                0x2b => self.set_register(rd, (s < t) as u32),
+                // TEQ
+                0x34 => {
+                    if s == t {
+                        exception!("trap");
+                    }
+                }
+                _ => exception!("reserved instruction {word:08x}"),
+            },
+            // REGIMM
+            0x01 => {
+                let (taken, link) = match rt {
+                    // BLTZ
+                    0x00 => ((s as i32) < 0, false),
+                    // BGEZ
+                    0x01 => ((s as i32) >= 0, false),
+                    // BLTZAL
+                    0x10 => ((s as i32) < 0, true),
+                    // BGEZAL, and BAL for rs = 0
+                    0x11 => ((s as i32) >= 0, true),
+                    // SYNCI
+                    0x1f => return self.advance(None, false),
+                    _ => exception!("reserved instruction {word:08x}"),
+                };
+                is_control = true;
+                if link {
+                    if rs == RA {
+                        unpredictable!("branch and link reading $ra");
+                    }
+                    self.set_register(RA, delay_slot.wrapping_add(4));
+                }
+                if taken {
+                    target = Some(branch_target);
+                }
+            }
+            // J and JAL
+            0x02 | 0x03 => {
+                is_control = true;
+                target = Some((delay_slot & 0xf000_0000) | ((word & 0x03ff_ffff) << 2));
+                if opcode == 0x03 {
+                    self.set_register(RA, delay_slot.wrapping_add(4));
+                }
+            }
+            // BEQ, BNE, BLEZ and BGTZ
+            0x04..=0x07 => {
+                let taken = match opcode {
+                    0x04 => s == t,
+                    0x05 => s != t,
+                    0x06 => (s as i32) <= 0,
+                    _ => (s as i32) > 0,
+                };
+                is_control = true;
+                if taken {
+                    target = Some(branch_target);
+                }
+            }
+            // ADDI
+            0x08 => match (s as i32).checked_add(simm as i32) {
+                Some(sum) => self.set_register(rt, sum as u32),
+                None => exception!("integer overflow"),
+            },
+            // ADDIU
+            0x09 => self.set_register(rt, s.wrapping_add(simm)),
+            // SLTI
+            0x0a => self.set_register(rt, ((s as i32) < (simm as i32)) as u32),
+            // SLTIU
+            0x0b => self.set_register(rt, (s < simm) as u32),
+            // ANDI
+            0x0c => self.set_register(rt, s & imm),
+            // ORI
+            0x0d => self.set_register(rt, s | imm),
+            // XORI
+            0x0e => self.set_register(rt, s ^ imm),
+            // LUI
+            0x0f => self.set_register(rt, imm << 16),
+            // SPECIAL2
+            0x1c => match funct {
+                // MADD and MSUB
+                0x00 | 0x04 => {
+                    let accumulator =
+                        ((self.registers[HI] as u64) << 32 | self.registers[LO] as u64) as i64;
+                    let product = s as i32 as i64 * t as i32 as i64;
+                    let result = if funct == 0x00 {
+                        accumulator.wrapping_add(product)
+                    } else {
+                        accumulator.wrapping_sub(product)
+                    } as u64;
+                    self.set_register(LO, result as u32);
+                    self.set_register(HI, (result >> 32) as u32);
+                }
+                // MADDU and MSUBU
+                0x01 | 0x05 => {
+                    let accumulator = (self.registers[HI] as u64) << 32 | self.registers[LO] as u64;
+                    let product = s as u64 * t as u64;
+                    let result = if funct == 0x01 {
+                        accumulator.wrapping_add(product)
+                    } else {
+                        accumulator.wrapping_sub(product)
+                    };
+                    self.set_register(LO, result as u32);
+                    self.set_register(HI, (result >> 32) as u32);
+                }
+                // MUL
+                0x02 => self.set_register(rd, (s as i32).wrapping_mul(t as i32) as u32),
+                // CLZ
+                0x20 => self.set_register(rd, s.leading_zeros()),
+                // CLO
+                0x21 => self.set_register(rd, s.leading_ones()),
+                _ => exception!("reserved instruction {word:08x}"),
+            },
+            // SPECIAL3
+            0x1f => match funct {
+                // EXT
+                0x00 => {
+                    let (pos, size) = (sa, rd as u32 + 1);
+                    if pos + size > 32 {
+                        unpredictable!("EXT of {size} bits at {pos}");
+                    }
+                    let mask = if size == 32 { u32::MAX } else { (1 << size) - 1 };
+                    self.set_register(rt, (s >> pos) & mask);
+                }
+                // INS
+                0x04 => {
+                    let (pos, msb) = (sa, rd as u32);
+                    if msb < pos {
+                        unpredictable!("INS up to bit {msb} from bit {pos}");
+                    }
+                    let size = msb - pos + 1;
+                    let mask = if size == 32 { u32::MAX } else { ((1 << size) - 1) << pos };
+                    self.set_register(rt, (t & !mask) | ((s << pos) & mask));
+                }
+                // BSHFL
+                0x20 => match sa {
+                    // WSBH
+                    0x02 => {
+                        let swapped = ((t & 0x00ff_00ff) << 8) | ((t & 0xff00_ff00) >> 8);
+                        self.set_register(rd, swapped);
+                    }
+                    // SEB
+                    0x10 => self.set_register(rd, t as u8 as i8 as i32 as u32),
+                    // SEH
+                    0x18 => self.set_register(rd, t as u16 as i16 as i32 as u32),
+                    _ => exception!("reserved instruction {word:08x}"),
+                },
+                _ => exception!("reserved instruction {word:08x}"),
+            },
+            // Loads
+            0x20..=0x26 | 0x30 => {
+                let addr = s.wrapping_add(simm);
+                let aligned = addr & !3;
+                let shift = 8 * (addr & 3);
+                let memory = self.word(aligned);
+                let value = match opcode {
+                    // LB
+                    0x20 => (memory >> shift) as u8 as i8 as i32 as u32,
+                    // LH
+                    0x21 => {
+                        if addr & 1 != 0 {
+                            exception!("unaligned LH at {addr:08x}");
+                        }
+                        (memory >> shift) as u16 as i16 as i32 as u32
+                    }
+                    // LWL: the bytes from `addr` down to the aligned address, to the most
+                    // significant bytes of rt.
+                    0x22 => {
+                        let kept = 24 - shift;
+                        let mask = if kept == 0 { 0 } else { u32::MAX >> (32 - kept) };
+                        (memory << kept) | (t & mask)
+                    }
+                    // LW and LL
+                    0x23 | 0x30 => {
+                        if addr & 3 != 0 {
+                            exception!("unaligned word load at {addr:08x}");
+                        }
+                        if opcode == 0x30 {
+                            self.ll_bit = true;
+                        }
+                        memory
+                    }
+                    // LBU
+                    0x24 => (memory >> shift) & 0xff,
+                    // LHU
+                    0x25 => {
+                        if addr & 1 != 0 {
+                            exception!("unaligned LHU at {addr:08x}");
+                        }
+                        (memory >> shift) & 0xffff
+                    }
+                    // LWR: the bytes from `addr` up to the end of the word, to the least
+                    // significant bytes of rt.
+                    _ => {
+                        let mask = if shift == 0 { 0 } else { u32::MAX << (32 - shift) };
+                        (memory >> shift) | (t & mask)
+                    }
+                };
+                self.set_register(rt, value);
+            }
+            // Stores
+            0x28..=0x2b | 0x2e | 0x38 => {
+                let addr = s.wrapping_add(simm);
+                let aligned = addr & !3;
+                let shift = 8 * (addr & 3);
+                let memory = self.word(aligned);
+                let value = match opcode {
+                    // SB
+                    0x28 => (memory & !(0xff << shift)) | ((t & 0xff) << shift),
+                    // SH
+                    0x29 => {
+                        if addr & 1 != 0 {
+                            exception!("unaligned SH at {addr:08x}");
+                        }
+                        (memory & !(0xffff << shift)) | ((t & 0xffff) << shift)
+                    }
+                    // SWL: the most significant bytes of rt, to the bytes from `addr` down to
+                    // the aligned address.
+                    0x2a => {
+                        let dropped = 24 - shift;
+                        (memory & !(u32::MAX >> dropped)) | (t >> dropped)
+                    }
+                    // SW and SC
+                    0x2b | 0x38 => {
+                        if addr & 3 != 0 {
+                            exception!("unaligned word store at {addr:08x}");
+                        }
+                        t
+                    }
+                    // SWR: the least significant bytes of rt, to the bytes from `addr` up to the
+                    // end of the word.
+                    _ => (memory & !(u32::MAX << shift)) | (t << shift),
+                };
+                if opcode == 0x38 {
+                    // The guest is single threaded, so the store only fails without a prior LL.
+                    let success = std::mem::take(&mut self.ll_bit);
+                    if success {
+                        self.set_word(aligned, value);
+                    }
+                    self.set_register(rt, success as u32);
+                } else {
+                    self.set_word(aligned, value);
+                }
+            }
+            // PREF
+            0x33 => {}
+            _ => exception!("reserved instruction {word:08x}"),
+        }
+
+        if is_control && self.in_delay_slot {
+            unpredictable!("control transfer in a delay slot");
+        }
+        self.advance(target, is_control)
+    }
+
+    /// Move to the next instruction, jumping to `target` after the delay slot if any.
+    fn advance(&mut self, target: Option<u32>, is_control: bool) -> Outcome {
+        let next_next_pc = target.unwrap_or(self.next_pc.wrapping_add(4));
+        self.pc = self.next_pc;
+        self.next_pc = next_next_pc;
+        self.in_delay_slot = is_control;
+        Outcome::Executed
+    }
+}
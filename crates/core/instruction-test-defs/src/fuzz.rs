@@ -0,0 +1,349 @@
+//! Random programs covering every opcode, for fuzzing the executor and the prover.
+//!
+//! The programs are generated as MIPS words and decoded with [`Instruction::decode_from`], so they
+//! exercise the same instruction forms as compiled guests. Every program executes every opcode of
+//! [`FUZZ_OPCODES`] at least once and halts with exit code 0:
+//! - the operands of `DIV`, `DIVU`, `MOD` and `MODU` are set up to never divide by zero or
+//!   overflow, and the operands of `TEQ` to never trap;
+//! - the loads and stores access the words around [`FUZZ_DATA_BASE`], aligned as they require;
+//! - the branches and jumps target the instruction after their delay slot, so that no instruction
+//!   is skipped whether they are taken or not.
+
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+use zkm_core_executor::{Instruction, Opcode, Program};
+
+/// The opcodes covered by the programs of [`fuzz_program`]: every opcode but `UNIMPL`.
+pub const FUZZ_OPCODES: [Opcode; 56] = [
+    Opcode::ADD,
+    Opcode::SUB,
+    Opcode::MUL,
+    Opcode::MULT,
+    Opcode::MULTU,
+    Opcode::DIV,
+    Opcode::DIVU,
+    Opcode::MOD,
+    Opcode::MODU,
+    Opcode::SLL,
+    Opcode::SRL,
+    Opcode::SRA,
+    Opcode::ROR,
+    Opcode::SLT,
+    Opcode::SLTU,
+    Opcode::AND,
+    Opcode::OR,
+    Opcode::XOR,
+    Opcode::NOR,
+    Opcode::CLZ,
+    Opcode::CLO,
+    Opcode::BEQ,
+    Opcode::BGEZ,
+    Opcode::BGTZ,
+    Opcode::BLEZ,
+    Opcode::BLTZ,
+    Opcode::BNE,
+    Opcode::Jump,
+    Opcode::Jumpi,
+    Opcode::JumpDirect,
+    Opcode::SYSCALL,
+    Opcode::LB,
+    Opcode::LBU,
+    Opcode::LH,
+    Opcode::LHU,
+    Opcode::LW,
+    Opcode::LWL,
+    Opcode::LWR,
+    Opcode::LL,
+    Opcode::SB,
+    Opcode::SH,
+    Opcode::SW,
+    Opcode::SWL,
+    Opcode::SWR,
+    Opcode::SC,
+    Opcode::INS,
+    Opcode::MADDU,
+    Opcode::MSUBU,
+    Opcode::MADD,
+    Opcode::MSUB,
+    Opcode::MEQ,
+    Opcode::MNE,
+    Opcode::WSBH,
+    Opcode::EXT,
+    Opcode::TEQ,
+    Opcode::SEXT,
+];
+
+/// The base address of the loads and stores of the programs.
+pub const FUZZ_DATA_BASE: u32 = 0x0010_0000;
+
+/// The register holding [`FUZZ_DATA_BASE`], which the programs never write.
+const BASE_REGISTER: u32 = 16;
+
+/// Divisors exercising the edge cases of the division chip, besides random ones.
+const DIVISORS: [u32; 8] = [1, 2, 3, 0xffff_ffff, 0xffff_fffe, 0x7fff_ffff, 0x8000_0000, 0x1_0000];
+
+/// Generate the random program of `seed`, with `len` opcodes between the setup of the registers
+/// and the final `HALT`.
+///
+/// The first opcodes are every opcode of [`FUZZ_OPCODES`] once, in random order, and the rest are
+/// random, so `len` is raised to the number of covered opcodes. Some opcodes are emitted with the
+/// setup of their operands or a delay slot, so the program has more than `len` instructions.
+pub fn fuzz_program(seed: u64, len: usize) -> Program {
+    let mut generator = Generator { rng: StdRng::seed_from_u64(seed), words: Vec::new() };
+    generator.prelude();
+
+    let mut opcodes = FUZZ_OPCODES
+        .iter()
+        .copied()
+        .filter(|&opcode| opcode != Opcode::SYSCALL)
+        .collect::<Vec<_>>();
+    opcodes.shuffle(&mut generator.rng);
+    while opcodes.len() < len {
+        let opcode = *FUZZ_OPCODES.choose(&mut generator.rng).unwrap();
+        if opcode != Opcode::SYSCALL {
+            opcodes.push(opcode);
+        }
+    }
+    for opcode in opcodes {
+        generator.emit(opcode);
+    }
+    generator.halt();
+
+    let instructions = generator
+        .words
+        .iter()
+        .map(|&word| Instruction::decode_from(word).expect("the generated words are valid"))
+        .collect();
+    Program::new(instructions, 0, 0)
+}
+
+fn special(rs: u32, rt: u32, rd: u32, sa: u32, funct: u32) -> u32 {
+    (rs << 21) | (rt << 16) | (rd << 11) | (sa << 6) | funct
+}
+
+fn special2(rs: u32, rt: u32, rd: u32, sa: u32, funct: u32) -> u32 {
+    (0x1c << 26) | special(rs, rt, rd, sa, funct)
+}
+
+fn special3(rs: u32, rt: u32, rd: u32, sa: u32, funct: u32) -> u32 {
+    (0x1f << 26) | special(rs, rt, rd, sa, funct)
+}
+
+fn immediate(opcode: u32, rs: u32, rt: u32, imm: u32) -> u32 {
+    (opcode << 26) | (rs << 21) | (rt << 16) | (imm & 0xffff)
+}
+
+struct Generator {
+    rng: StdRng,
+    words: Vec<u32>,
+}
+
+impl Generator {
+    /// A register to read.
+    fn source(&mut self) -> u32 {
+        self.rng.gen_range(0..32)
+    }
+
+    /// A register to write, other than `$zero` and the base register.
+    fn destination(&mut self) -> u32 {
+        loop {
+            let register = self.rng.gen_range(1..32);
+            if register != BASE_REGISTER {
+                return register;
+            }
+        }
+    }
+
+    /// A register to write, other than `register`.
+    fn other_destination(&mut self, register: u32) -> u32 {
+        loop {
+            let other = self.destination();
+            if other != register {
+                return other;
+            }
+        }
+    }
+
+    /// An offset from the base register, aligned to `align` bytes.
+    fn offset(&mut self, align: i32) -> u32 {
+        (self.rng.gen_range(-256..256) / align * align) as u32
+    }
+
+    /// The address of the word after the next `words` words.
+    fn addr_after(&self, words: usize) -> u32 {
+        4 * (self.words.len() + words) as u32
+    }
+
+    /// Load `value` into `register`.
+    fn load(&mut self, register: u32, value: u32) {
+        self.words.push(immediate(0x0f, 0, register, value >> 16));
+        self.words.push(immediate(0x0d, register, register, value));
+    }
+
+    /// Set every register, `HI` and `LO` to random values, and the base register to
+    /// [`FUZZ_DATA_BASE`].
+    fn prelude(&mut self) {
+        for register in 1..32 {
+            let value = if register == BASE_REGISTER { FUZZ_DATA_BASE } else { self.rng.gen() };
+            self.load(register, value);
+        }
+        let (hi, lo) = (self.source(), self.source());
+        self.words.push(special(hi, 0, 0, 0, 0x11));
+        self.words.push(special(lo, 0, 0, 0, 0x13));
+    }
+
+    /// Exit with code 0.
+    fn halt(&mut self) {
+        self.words.push(immediate(0x09, 0, 2, 0));
+        self.words.push(immediate(0x09, 0, 4, 0));
+        self.words.push(special(0, 0, 0, 0, 0x0c));
+    }
+
+    /// A random instruction to fill a delay slot, which neither transfers control nor traps.
+    fn delay_slot(&mut self) -> u32 {
+        let (s, t, d) = (self.source(), self.source(), self.destination());
+        let funct = [0x21, 0x23, 0x24, 0x25, 0x26, 0x27, 0x2a, 0x2b];
+        special(s, t, d, 0, *funct.choose(&mut self.rng).unwrap())
+    }
+
+    /// Emit a branch or a jump with an offset of 1 to the instruction after its delay slot.
+    fn branch(&mut self, word: u32) {
+        self.words.push(word);
+        let delay_slot = self.delay_slot();
+        self.words.push(delay_slot);
+    }
+
+    /// Set up the operands of a division: a divisor in `rt` that is neither zero nor, for a
+    /// dividend in `rs` of `0x80000000`, `-1`.
+    fn division_operands(&mut self) -> (u32, u32) {
+        let rt = self.destination();
+        let divisor =
+            if self.rng.gen() { *DIVISORS.choose(&mut self.rng).unwrap() } else { self.rng.gen() };
+        let divisor = divisor.max(1);
+        self.load(rt, divisor);
+
+        let rs = self.source();
+        if divisor == u32::MAX && rs != rt && rs != 0 && rs != BASE_REGISTER {
+            let dividend: u32 = self.rng.gen();
+            self.load(rs, if dividend == 0x8000_0000 { 0 } else { dividend });
+        }
+        (rs, rt)
+    }
+
+    /// Emit the instructions for `opcode`, in one of their random forms.
+    fn emit(&mut self, opcode: Opcode) {
+        let (s, t, d) = (self.source(), self.source(), self.destination());
+        let imm: u32 = self.rng.gen();
+        let sa = self.rng.gen_range(0..32);
+        let word = match opcode {
+            Opcode::ADD => match self.rng.gen_range(0..8) {
+                0 => special(s, t, d, 0, 0x20),
+                1 => special(s, t, d, 0, 0x21),
+                2 => immediate(0x08, s, d, imm),
+                3 => immediate(0x09, s, d, imm),
+                4 => special(0, 0, d, 0, 0x10),
+                5 => special(s, 0, 0, 0, 0x11),
+                6 => special(0, 0, d, 0, 0x12),
+                _ => special(s, 0, 0, 0, 0x13),
+            },
+            Opcode::SUB => special(s, t, d, 0, if self.rng.gen() { 0x22 } else { 0x23 }),
+            Opcode::MUL => special2(s, t, d, 0, 0x02),
+            Opcode::MULT => special(s, t, 0, 0, 0x18),
+            Opcode::MULTU => special(s, t, 0, 0, 0x19),
+            Opcode::DIV | Opcode::DIVU | Opcode::MOD | Opcode::MODU => {
+                let (rs, rt) = self.division_operands();
+                match opcode {
+                    Opcode::DIV => special(rs, rt, 0, 0, 0x1a),
+                    Opcode::DIVU => special(rs, rt, 0, 0, 0x1b),
+                    Opcode::MOD => special(rs, rt, d, 3, 0x1a),
+                    _ => special(rs, rt, d, 3, 0x1b),
+                }
+            }
+            Opcode::SLL => match self.rng.gen_range(0..3) {
+                0 => special(0, t, d, sa, 0x00),
+                1 => special(s, t, d, 0, 0x04),
+                _ => immediate(0x0f, 0, d, imm),
+            },
+            Opcode::SRL if self.rng.gen() => special(0, t, d, sa, 0x02),
+            Opcode::SRL => special(s, t, d, 0, 0x06),
+            Opcode::SRA if self.rng.gen() => special(0, t, d, sa, 0x03),
+            Opcode::SRA => special(s, t, d, 0, 0x07),
+            Opcode::ROR if self.rng.gen() => special(1, t, d, sa, 0x02),
+            Opcode::ROR => special(s, t, d, 1, 0x06),
+            Opcode::SLT if self.rng.gen() => special(s, t, d, 0, 0x2a),
+            Opcode::SLT => immediate(0x0a, s, d, imm),
+            Opcode::SLTU if self.rng.gen() => special(s, t, d, 0, 0x2b),
+            Opcode::SLTU => immediate(0x0b, s, d, imm),
+            Opcode::AND if self.rng.gen() => special(s, t, d, 0, 0x24),
+            Opcode::AND => immediate(0x0c, s, d, imm),
+            Opcode::OR if self.rng.gen() => special(s, t, d, 0, 0x25),
+            Opcode::OR => immediate(0x0d, s, d, imm),
+            Opcode::XOR if self.rng.gen() => special(s, t, d, 0, 0x26),
+            Opcode::XOR => immediate(0x0e, s, d, imm),
+            Opcode::NOR => special(s, t, d, 0, 0x27),
+            Opcode::CLZ => special2(s, d, d, 0, 0x20),
+            Opcode::CLO => special2(s, d, d, 0, 0x21),
+            Opcode::BEQ => return self.branch(immediate(0x04, s, t, 1)),
+            Opcode::BNE => return self.branch(immediate(0x05, s, t, 1)),
+            Opcode::BLEZ => return self.branch(immediate(0x06, s, 0, 1)),
+            Opcode::BGTZ => return self.branch(immediate(0x07, s, 0, 1)),
+            Opcode::BLTZ => return self.branch(immediate(0x01, s, 0x00, 1)),
+            Opcode::BGEZ => return self.branch(immediate(0x01, s, 0x01, 1)),
+            Opcode::JumpDirect => return self.branch(immediate(0x01, 0, 0x11, 1)),
+            Opcode::Jumpi => {
+                // J or JAL.
+                let opcode = if self.rng.gen() { 0x02 } else { 0x03 };
+                return self.branch((opcode << 26) | (self.addr_after(2) >> 2));
+            }
+            Opcode::Jump => {
+                // JR or JALR to a register loaded with the address after the delay slot.
+                let target = self.addr_after(4);
+                self.load(d, target);
+                let link = if self.rng.gen() { 0 } else { self.other_destination(d) };
+                return self.branch(special(d, 0, link, 0, if link == 0 { 0x08 } else { 0x09 }));
+            }
+            Opcode::LB => immediate(0x20, BASE_REGISTER, d, self.offset(1)),
+            Opcode::LH => immediate(0x21, BASE_REGISTER, d, self.offset(2)),
+            Opcode::LWL => immediate(0x22, BASE_REGISTER, d, self.offset(1)),
+            Opcode::LW => immediate(0x23, BASE_REGISTER, d, self.offset(4)),
+            Opcode::LBU => immediate(0x24, BASE_REGISTER, d, self.offset(1)),
+            Opcode::LHU => immediate(0x25, BASE_REGISTER, d, self.offset(2)),
+            Opcode::LWR => immediate(0x26, BASE_REGISTER, d, self.offset(1)),
+            Opcode::LL => immediate(0x30, BASE_REGISTER, d, self.offset(4)),
+            Opcode::SB => immediate(0x28, BASE_REGISTER, t, self.offset(1)),
+            Opcode::SH => immediate(0x29, BASE_REGISTER, t, self.offset(2)),
+            Opcode::SWL => immediate(0x2a, BASE_REGISTER, t, self.offset(1)),
+            Opcode::SW => immediate(0x2b, BASE_REGISTER, t, self.offset(4)),
+            Opcode::SWR => immediate(0x2e, BASE_REGISTER, t, self.offset(1)),
+            // SC writes its result to `rt`.
+            Opcode::SC => immediate(0x38, BASE_REGISTER, d, self.offset(4)),
+            Opcode::INS => {
+                let pos = self.rng.gen_range(0..32);
+                let msb = self.rng.gen_range(pos..32);
+                special3(s, d, msb, pos, 0x04)
+            }
+            Opcode::EXT => {
+                let pos = self.rng.gen_range(0..32);
+                let size = self.rng.gen_range(1..=32 - pos);
+                special3(s, d, size - 1, pos, 0x00)
+            }
+            Opcode::MADD => special2(s, t, 0, 0, 0x00),
+            Opcode::MADDU => special2(s, t, 0, 0, 0x01),
+            Opcode::MSUB => special2(s, t, 0, 0, 0x04),
+            Opcode::MSUBU => special2(s, t, 0, 0, 0x05),
+            Opcode::MEQ => special(s, t, d, 0, 0x0a),
+            Opcode::MNE => special(s, t, d, 0, 0x0b),
+            Opcode::WSBH => special3(0, t, d, 0x02, 0x20),
+            Opcode::SEXT => special3(0, t, d, if self.rng.gen() { 0x10 } else { 0x18 }, 0x20),
+            Opcode::TEQ => {
+                // Two registers holding different values.
+                let rt = self.other_destination(d);
+                let (value, difference) = (self.rng.gen::<u32>(), self.rng.gen::<u32>().max(1));
+                self.load(d, value);
+                self.load(rt, value ^ difference);
+                special(d, rt, 0, 0, 0x34)
+            }
+            Opcode::SYSCALL | Opcode::UNIMPL => unreachable!("{opcode:?} is not generated"),
+        };
+        self.words.push(word);
+    }
+}
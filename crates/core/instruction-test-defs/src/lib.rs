@@ -11,17 +11,22 @@
 //!
 //! Executor-only edge cases that intentionally do not have prover parity should stay outside this
 //! crate.
+//!
+//! The [`fuzz_program`] generator complements the suites with random programs covering every
+//! opcode, for property-based tests of the same executor and prover paths.
 
 use zkm_core_executor::{Instruction, Opcode, Program, Register};
 
 mod cloclz;
 mod div;
+mod fuzz;
 mod maddsub;
 mod misc;
 mod rotate;
 
 pub use cloclz::{N80_CLO, N81_CLZ};
 pub use div::{N44_DIV, N45_DIVU};
+pub use fuzz::{fuzz_program, FUZZ_DATA_BASE, FUZZ_OPCODES};
 pub use maddsub::{N67_MADD, N68_MADDU, N69_MSUB, N70_MSUBU};
 pub use misc::{N71_SEB, N72_SEH, N73_WSBH, N74_INS, N75_EXT};
 pub use rotate::{N78_ROTR, N79_ROTRV};
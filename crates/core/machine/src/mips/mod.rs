@@ -3,7 +3,7 @@ use crate::{
     global::GlobalChip,
     memory::{MemoryChipType, MemoryLocalChip, NUM_LOCAL_MEMORY_ENTRIES_PER_ROW},
     syscall::precompiles::{
-        fptower::{Fp2AddSubAssignChip, Fp2MulAssignChip, FpOpChip},
+        fptower::{Fp2AddSubAssignChip, Fp2MulAssignChip, Fp6MulAssignChip, FpOpChip},
         poseidon2::Poseidon2PermuteChip,
     },
 };
@@ -40,15 +40,19 @@ pub(crate) mod mips_chips {
             chip::SyscallChip,
             instructions::SyscallInstrsChip,
             precompiles::{
+                blake2b::Blake2bCompressInnerChip,
+                blake3::Blake3CompressInnerChip,
                 edwards::{EdAddAssignChip, EdDecompressChip},
                 keccak_sponge::KeccakSpongeChip,
                 sha256::{ShaCompressChip, ShaExtendChip},
+                sha512::{Sha512CompressChip, Sha512ExtendChip},
+                sys_file::SysFileChip,
                 sys_linux::SysLinuxChip,
                 u256x2048_mul::U256x2048MulChip,
                 uint256::Uint256MulChip,
                 weierstrass::{
                     WeierstrassAddAssignChip, WeierstrassDecompressChip,
-                    WeierstrassDoubleAssignChip,
+                    WeierstrassDoubleAssignChip, WeierstrassScalarMulAssignChip,
                 },
             },
         },
@@ -126,6 +130,14 @@ pub enum MipsAir<F: PrimeField32> {
     Sha256Extend(ShaExtendChip),
     /// A precompile for sha256 compress.
     Sha256Compress(ShaCompressChip),
+    /// A precompile for sha512 extend.
+    Sha512Extend(Sha512ExtendChip),
+    /// A precompile for sha512 compress.
+    Sha512Compress(Sha512CompressChip),
+    /// A precompile for the rounds of the blake3 compression function.
+    Blake3CompressInner(Blake3CompressInnerChip),
+    /// A precompile for the rounds of the blake2b compression function.
+    Blake2bCompressInner(Blake2bCompressInnerChip),
     /// A precompile for addition on the Elliptic curve ed25519.
     Ed25519Add(EdAddAssignChip<EdwardsCurve<Ed25519Parameters>>),
     /// A precompile for decompressing a point on the Edwards curve ed25519.
@@ -174,8 +186,22 @@ pub enum MipsAir<F: PrimeField32> {
     Bn254Fp2Mul(Fp2MulAssignChip<Bn254BaseField>),
     /// A precompile for BN-254 fp2 addition/subtraction.
     Bn254Fp2AddSub(Fp2AddSubAssignChip<Bn254BaseField>),
+    /// A precompile for BN-254 fp6 multiplication.
+    Bn254Fp6Mul(Fp6MulAssignChip<Bn254BaseField>),
+    /// A precompile for BLS12-381 fp6 multiplication.
+    Bls12381Fp6Mul(Fp6MulAssignChip<Bls12381BaseField>),
+    /// A precompile for scalar multiplication on the Elliptic curve secp256k1.
+    Secp256k1ScalarMul(WeierstrassScalarMulAssignChip<SwCurve<Secp256k1Parameters>>),
+    /// A precompile for scalar multiplication on the Elliptic curve secp256r1.
+    Secp256r1ScalarMul(WeierstrassScalarMulAssignChip<SwCurve<Secp256r1Parameters>>),
+    /// A precompile for scalar multiplication on the Elliptic curve bn254.
+    Bn254ScalarMul(WeierstrassScalarMulAssignChip<SwCurve<Bn254Parameters>>),
+    /// A precompile for scalar multiplication on the Elliptic curve bls12_381.
+    Bls12381ScalarMul(WeierstrassScalarMulAssignChip<SwCurve<Bls12381Parameters>>),
     /// A precompile for Linux Syscall.
     SysLinux(SysLinuxChip),
+    /// A precompile for the file syscalls on the virtual files.
+    SysFile(SysFileChip),
 }
 
 impl<F: PrimeField32> MipsAir<F> {
@@ -444,6 +470,66 @@ impl<F: PrimeField32> MipsAir<F> {
         costs.insert(boolean_circuit_garble.name(), boolean_circuit_garble.cost());
         chips.push(boolean_circuit_garble);
 
+        let sha512_extend = Chip::new(MipsAir::Sha512Extend(Sha512ExtendChip::default()));
+        costs.insert(sha512_extend.name(), 64 * sha512_extend.cost());
+        chips.push(sha512_extend);
+
+        let sha512_compress = Chip::new(MipsAir::Sha512Compress(Sha512CompressChip::default()));
+        costs.insert(sha512_compress.name(), 96 * sha512_compress.cost());
+        chips.push(sha512_compress);
+
+        let blake3_compress_inner =
+            Chip::new(MipsAir::Blake3CompressInner(Blake3CompressInnerChip::default()));
+        costs.insert(blake3_compress_inner.name(), 56 * blake3_compress_inner.cost());
+        chips.push(blake3_compress_inner);
+
+        let blake2b_compress_inner =
+            Chip::new(MipsAir::Blake2bCompressInner(Blake2bCompressInnerChip::default()));
+        costs.insert(blake2b_compress_inner.name(), 96 * blake2b_compress_inner.cost());
+        chips.push(blake2b_compress_inner);
+
+        let bn254_fp6_mul =
+            Chip::new(MipsAir::Bn254Fp6Mul(Fp6MulAssignChip::<Bn254BaseField>::new()));
+        costs.insert(bn254_fp6_mul.name(), bn254_fp6_mul.cost());
+        chips.push(bn254_fp6_mul);
+
+        let bls12381_fp6_mul =
+            Chip::new(MipsAir::Bls12381Fp6Mul(Fp6MulAssignChip::<Bls12381BaseField>::new()));
+        costs.insert(bls12381_fp6_mul.name(), bls12381_fp6_mul.cost());
+        chips.push(bls12381_fp6_mul);
+
+        let secp256k1_scalar_mul =
+            Chip::new(MipsAir::Secp256k1ScalarMul(WeierstrassScalarMulAssignChip::<
+                SwCurve<Secp256k1Parameters>,
+            >::new()));
+        costs.insert(secp256k1_scalar_mul.name(), 256 * secp256k1_scalar_mul.cost());
+        chips.push(secp256k1_scalar_mul);
+
+        let secp256r1_scalar_mul =
+            Chip::new(MipsAir::Secp256r1ScalarMul(WeierstrassScalarMulAssignChip::<
+                SwCurve<Secp256r1Parameters>,
+            >::new()));
+        costs.insert(secp256r1_scalar_mul.name(), 256 * secp256r1_scalar_mul.cost());
+        chips.push(secp256r1_scalar_mul);
+
+        let bn254_scalar_mul =
+            Chip::new(MipsAir::Bn254ScalarMul(WeierstrassScalarMulAssignChip::<
+                SwCurve<Bn254Parameters>,
+            >::new()));
+        costs.insert(bn254_scalar_mul.name(), 256 * bn254_scalar_mul.cost());
+        chips.push(bn254_scalar_mul);
+
+        let bls12381_scalar_mul =
+            Chip::new(MipsAir::Bls12381ScalarMul(WeierstrassScalarMulAssignChip::<
+                SwCurve<Bls12381Parameters>,
+            >::new()));
+        costs.insert(bls12381_scalar_mul.name(), 256 * bls12381_scalar_mul.cost());
+        chips.push(bls12381_scalar_mul);
+
+        let sys_file = Chip::new(MipsAir::SysFile(SysFileChip::default()));
+        costs.insert(sys_file.name(), sys_file.cost());
+        chips.push(sys_file);
+
         (chips, costs)
     }
 
@@ -498,6 +584,7 @@ impl<F: PrimeField32> MipsAir<F> {
                 let events_len = match self {
                     Self::KeccakSponge(_) => self.keccak_permutation_in_record(record),
                     Self::BooleanCircuitGarble(_) => self.boolean_circuit_garble_in_record(record),
+                    Self::SysFile(_) => self.sys_file_rows_in_record(record),
                     _ => events.len(),
                 };
                 let num_rows = events_len * self.rows_per_event();
@@ -590,7 +677,15 @@ impl<F: PrimeField32> MipsAir<F> {
         match self {
             Self::Sha256Compress(_) => 80,
             Self::Sha256Extend(_) => 48,
+            Self::Sha512Compress(_) => 96,
+            Self::Sha512Extend(_) => 64,
+            Self::Blake3CompressInner(_) => 56,
+            Self::Blake2bCompressInner(_) => 96,
             Self::KeccakSponge(_) => 24,
+            Self::Secp256k1ScalarMul(_)
+            | Self::Secp256r1ScalarMul(_)
+            | Self::Bn254ScalarMul(_)
+            | Self::Bls12381ScalarMul(_) => 256,
             _ => 1,
         }
     }
@@ -633,6 +728,25 @@ impl<F: PrimeField32> MipsAir<F> {
             .unwrap_or(0)
     }
 
+    fn sys_file_rows_in_record(&self, record: &ExecutionRecord) -> usize {
+        record
+            .precompile_events
+            .get_events(SyscallCode::SYS_FILE)
+            .map(|events| {
+                events
+                    .iter()
+                    .map(|(_, pre_e)| {
+                        if let PrecompileEvent::SysFile(event) = pre_e {
+                            event.num_rows()
+                        } else {
+                            unreachable!()
+                        }
+                    })
+                    .sum::<usize>()
+            })
+            .unwrap_or(0)
+    }
+
     pub(crate) fn syscall_code(&self) -> SyscallCode {
         match self {
             Self::Bls12381Add(_) => SyscallCode::BLS12381_ADD,
@@ -649,6 +763,16 @@ impl<F: PrimeField32> MipsAir<F> {
             Self::Secp256r1Double(_) => SyscallCode::SECP256R1_DOUBLE,
             Self::Sha256Compress(_) => SyscallCode::SHA_COMPRESS,
             Self::Sha256Extend(_) => SyscallCode::SHA_EXTEND,
+            Self::Sha512Compress(_) => SyscallCode::SHA512_COMPRESS,
+            Self::Sha512Extend(_) => SyscallCode::SHA512_EXTEND,
+            Self::Blake3CompressInner(_) => SyscallCode::BLAKE3_COMPRESS_INNER,
+            Self::Blake2bCompressInner(_) => SyscallCode::BLAKE2B_COMPRESS_INNER,
+            Self::Bn254Fp6Mul(_) => SyscallCode::BN254_FP6_MUL,
+            Self::Bls12381Fp6Mul(_) => SyscallCode::BLS12381_FP6_MUL,
+            Self::Secp256k1ScalarMul(_) => SyscallCode::SECP256K1_SCALAR_MUL,
+            Self::Secp256r1ScalarMul(_) => SyscallCode::SECP256R1_SCALAR_MUL,
+            Self::Bn254ScalarMul(_) => SyscallCode::BN254_SCALAR_MUL,
+            Self::Bls12381ScalarMul(_) => SyscallCode::BLS12381_SCALAR_MUL,
             Self::Uint256Mul(_) => SyscallCode::UINT256_MUL,
             Self::U256x2048Mul(_) => SyscallCode::U256XU2048_MUL,
             Self::Bls12381Decompress(_) => SyscallCode::BLS12381_DECOMPRESS,
@@ -662,6 +786,7 @@ impl<F: PrimeField32> MipsAir<F> {
             Self::BooleanCircuitGarble(_) => SyscallCode::BOOLEAN_CIRCUIT_GARBLE,
             Self::KeccakSponge(_) => SyscallCode::KECCAK_SPONGE,
             Self::SysLinux(_) => SyscallCode::SYS_LINUX,
+            Self::SysFile(_) => SyscallCode::SYS_FILE,
             Self::Add(_) => unreachable!("Invalid for core chip"),
             Self::Bitwise(_) => unreachable!("Invalid for core chip"),
             Self::DivRem(_) => unreachable!("Invalid for core chip"),
@@ -721,8 +846,12 @@ pub mod tests {
     use crate::{
         io::ZKMStdin,
         mips::MipsAir,
+        shape::CoreShapeConfig,
         utils,
-        utils::{prove, run_test, setup_logger},
+        utils::{
+            dry_run, prove, prove_with_context_resumable, run_test, setup_logger, ShardKind,
+            ZKMCoreProverError,
+        },
     };
 
     use hashbrown::HashMap;
@@ -733,8 +862,8 @@ pub mod tests {
     use zkm_core_executor::{Instruction, MipsAirId, Opcode, Program};
     use zkm_stark::air::MachineAir;
     use zkm_stark::{
-        koala_bear_poseidon2::KoalaBearPoseidon2, CpuProver, StarkProvingKey, StarkVerifyingKey,
-        ZKMCoreOpts,
+        koala_bear_poseidon2::KoalaBearPoseidon2, CpuProver, MachineProver, StarkGenericConfig,
+        StarkProvingKey, StarkVerifyingKey, ZKMCoreOpts,
     };
 
     #[test]
@@ -1124,6 +1253,108 @@ pub mod tests {
             .unwrap();
     }
 
+    #[test]
+    fn test_fibonacci_prove_resumable() {
+        setup_logger();
+
+        let program = fibonacci_program();
+        let stdin = ZKMStdin::new();
+        let mut opts = ZKMCoreOpts::default();
+        opts.shard_size = 1024;
+        opts.shard_batch_size = 2;
+
+        let prover = CpuProver::new(MipsAir::machine(KoalaBearPoseidon2::new()));
+        let (pk, vk) = prover.setup(&program);
+        let dir = tempfile::tempdir().unwrap();
+        let prove_resumable = |stdin: &ZKMStdin| {
+            prove_with_context_resumable::<_, CpuProver<_, _>>(
+                &prover,
+                &pk,
+                program.clone(),
+                stdin,
+                opts,
+                Default::default(),
+                None,
+                dir.path(),
+            )
+        };
+
+        let (proof, public_values, cycles) = prove_resumable(&stdin).unwrap();
+
+        // Drop the proofs of the last checkpoint, as if the prover crashed while proving it.
+        let last = (0..)
+            .take_while(|i| dir.path().join(format!("proofs-{i}.bin")).exists())
+            .last()
+            .unwrap();
+        std::fs::remove_file(dir.path().join(format!("proofs-{last}.bin"))).unwrap();
+
+        let (resumed, resumed_public_values, resumed_cycles) = prove_resumable(&stdin).unwrap();
+        assert_eq!(resumed.shard_proofs.len(), proof.shard_proofs.len());
+        assert_eq!(resumed_public_values, public_values);
+        assert_eq!(resumed_cycles, cycles);
+        let mut challenger = prover.config().challenger();
+        prover.machine().verify(&vk, &resumed, &mut challenger).unwrap();
+
+        // The directory can't be used to prove another input.
+        let mut other_stdin = ZKMStdin::new();
+        other_stdin.write(&1u32);
+        assert!(matches!(
+            prove_resumable(&other_stdin),
+            Err(ZKMCoreProverError::CheckpointMismatch(_))
+        ));
+    }
+
+    #[test]
+    fn test_fibonacci_dry_run() {
+        setup_logger();
+
+        let mut program = fibonacci_program();
+        let shape_config = CoreShapeConfig::<KoalaBear>::default();
+        shape_config.fix_preprocessed_shape(&mut program).unwrap();
+        let machine = MipsAir::machine(KoalaBearPoseidon2::new());
+        let report = dry_run(
+            &machine,
+            program,
+            &ZKMStdin::new(),
+            ZKMCoreOpts::default(),
+            Default::default(),
+            &shape_config,
+        )
+        .unwrap();
+        assert!(report.fits(), "{report}");
+        assert!(report.shards.iter().any(|shard| shard.kind == ShardKind::Core));
+        assert!(report.precompile_shards.is_empty());
+    }
+
+    #[test]
+    fn test_sha3_chain_dry_run() {
+        setup_logger();
+
+        let mut program = sha3_chain_program();
+        let shape_config = CoreShapeConfig::<KoalaBear>::default();
+        shape_config.fix_preprocessed_shape(&mut program).unwrap();
+        let machine = MipsAir::machine(KoalaBearPoseidon2::new());
+        let report = dry_run(
+            &machine,
+            program,
+            &ZKMStdin::new(),
+            ZKMCoreOpts::default(),
+            Default::default(),
+            &shape_config,
+        )
+        .unwrap();
+        assert!(report.fits(), "{report}");
+        for precompile in report.precompile_shards.iter() {
+            let shards = report
+                .shards
+                .iter()
+                .filter(|shard| shard.kind == ShardKind::Precompile(precompile.syscall))
+                .count();
+            assert_eq!(shards, precompile.shards);
+        }
+        assert!(!report.precompile_shards.is_empty());
+    }
+
     #[test]
     fn test_fibonacci_prove_batch() {
         setup_logger();
@@ -0,0 +1,155 @@
+use p3_air::AirBuilder;
+use p3_field::{Field, FieldAlgebra};
+use zkm_derive::AlignedBorrow;
+
+use zkm_core_executor::events::ByteRecord;
+use zkm_primitives::consts::WORD_SIZE;
+use zkm_stark::{air::ZKMAirBuilder, Word};
+
+use crate::air::WordAirBuilder;
+
+/// The number of bytes in a 64-bit word.
+const U64_SIZE: usize = 2 * WORD_SIZE;
+
+/// A set of columns needed to compute the sum of up to five 64-bit words.
+///
+/// The 64-bit words are stored as two little-endian words, low word first.
+#[derive(AlignedBorrow, Default, Debug, Clone, Copy)]
+#[repr(C)]
+pub struct Add5U64Operation<T> {
+    /// The result of the sum.
+    pub value: [Word<T>; 2],
+
+    /// Indicates if the carry for the `i`th limb is 0.
+    pub is_carry_0: [T; U64_SIZE],
+
+    /// Indicates if the carry for the `i`th limb is 1.
+    pub is_carry_1: [T; U64_SIZE],
+
+    /// Indicates if the carry for the `i`th limb is 2.
+    pub is_carry_2: [T; U64_SIZE],
+
+    /// Indicates if the carry for the `i`th limb is 3.
+    pub is_carry_3: [T; U64_SIZE],
+
+    /// Indicates if the carry for the `i`th limb is 4. The carry when adding 5 words is at most 4.
+    pub is_carry_4: [T; U64_SIZE],
+
+    /// The carry for the `i`th limb.
+    pub carry: [T; U64_SIZE],
+}
+
+impl<F: Field> Add5U64Operation<F> {
+    pub fn populate(&mut self, record: &mut impl ByteRecord, summands: &[u64]) -> u64 {
+        assert!(summands.len() <= 5, "at most five 64-bit words can be added");
+        let expected = summands.iter().fold(0u64, |acc, x| acc.wrapping_add(*x));
+        self.value = [Word::from(expected as u32), Word::from((expected >> 32) as u32)];
+
+        let base = 256;
+        let mut carry = [0u8; U64_SIZE];
+        for i in 0..U64_SIZE {
+            let mut res: u32 = summands.iter().map(|x| x.to_le_bytes()[i] as u32).sum();
+            if i > 0 {
+                res += carry[i - 1] as u32;
+            }
+            carry[i] = (res / base) as u8;
+            self.is_carry_0[i] = F::from_bool(carry[i] == 0);
+            self.is_carry_1[i] = F::from_bool(carry[i] == 1);
+            self.is_carry_2[i] = F::from_bool(carry[i] == 2);
+            self.is_carry_3[i] = F::from_bool(carry[i] == 3);
+            self.is_carry_4[i] = F::from_bool(carry[i] == 4);
+            self.carry[i] = F::from_canonical_u8(carry[i]);
+            debug_assert!(carry[i] <= 4);
+            debug_assert_eq!(
+                self.value[i / WORD_SIZE][i % WORD_SIZE],
+                F::from_canonical_u32(res % base)
+            );
+        }
+
+        // Range check.
+        {
+            for summand in summands {
+                record.add_u8_range_checks(&summand.to_le_bytes());
+            }
+            record.add_u8_range_checks(&expected.to_le_bytes());
+        }
+
+        expected
+    }
+
+    pub fn eval<AB: ZKMAirBuilder>(
+        builder: &mut AB,
+        summands: &[[Word<AB::Var>; 2]],
+        is_real: AB::Var,
+        cols: Add5U64Operation<AB::Var>,
+    ) {
+        assert!(summands.len() <= 5, "at most five 64-bit words can be added");
+        builder.assert_bool(is_real);
+        // Range check each byte.
+        {
+            summands
+                .iter()
+                .flatten()
+                .for_each(|word| builder.slice_range_check_u8(&word.0, is_real));
+            cols.value.iter().for_each(|word| builder.slice_range_check_u8(&word.0, is_real));
+        }
+        let mut builder_is_real = builder.when(is_real);
+
+        // Each value in is_carry_{0,1,2,3,4} is 0 or 1, and exactly one of them is 1 per digit.
+        {
+            for i in 0..U64_SIZE {
+                builder_is_real.assert_bool(cols.is_carry_0[i]);
+                builder_is_real.assert_bool(cols.is_carry_1[i]);
+                builder_is_real.assert_bool(cols.is_carry_2[i]);
+                builder_is_real.assert_bool(cols.is_carry_3[i]);
+                builder_is_real.assert_bool(cols.is_carry_4[i]);
+                builder_is_real.assert_eq(
+                    cols.is_carry_0[i]
+                        + cols.is_carry_1[i]
+                        + cols.is_carry_2[i]
+                        + cols.is_carry_3[i]
+                        + cols.is_carry_4[i],
+                    AB::Expr::one(),
+                );
+            }
+        }
+
+        // Calculates carry from is_carry_{0,1,2,3,4}.
+        {
+            let one = AB::Expr::one();
+            let two = AB::F::from_canonical_u32(2);
+            let three = AB::F::from_canonical_u32(3);
+            let four = AB::F::from_canonical_u32(4);
+
+            for i in 0..U64_SIZE {
+                builder_is_real.assert_eq(
+                    cols.carry[i],
+                    cols.is_carry_1[i] * one.clone()
+                        + cols.is_carry_2[i] * two
+                        + cols.is_carry_3[i] * three
+                        + cols.is_carry_4[i] * four,
+                );
+            }
+        }
+
+        // Compare the sum and summands by looking at carry.
+        {
+            let base = AB::F::from_canonical_u32(256);
+            // For each limb, assert that difference between the carried result and the non-carried
+            // result is the product of carry and base.
+            for i in 0..U64_SIZE {
+                let (word, byte) = (i / WORD_SIZE, i % WORD_SIZE);
+                let mut overflow: AB::Expr = AB::F::ZERO.into();
+                for summand in summands {
+                    overflow = overflow.clone() + summand[word][byte].into();
+                }
+                overflow = overflow.clone() - cols.value[word][byte].into();
+
+                if i > 0 {
+                    overflow = overflow.clone() + cols.carry[i - 1].into();
+                }
+                builder_is_real.assert_eq(cols.carry[i] * base, overflow.clone());
+            }
+        }
+    }
+}
@@ -0,0 +1,93 @@
+use zkm_core_executor::events::ByteRecord;
+use zkm_primitives::consts::WORD_SIZE;
+use zkm_stark::{air::ZKMAirBuilder, Word};
+
+use p3_air::AirBuilder;
+use p3_field::{Field, FieldAlgebra};
+use zkm_derive::AlignedBorrow;
+
+use crate::air::WordAirBuilder;
+
+/// The number of bytes in a 64-bit word.
+const U64_SIZE: usize = 2 * WORD_SIZE;
+
+/// A set of columns needed to compute the add of two 64-bit words.
+///
+/// The 64-bit words are stored as two little-endian words, low word first.
+#[derive(AlignedBorrow, Default, Debug, Clone, Copy)]
+#[repr(C)]
+pub struct AddU64Operation<T> {
+    /// The result of `a + b`.
+    pub value: [Word<T>; 2],
+
+    /// Trace.
+    pub carry: [T; U64_SIZE - 1],
+}
+
+impl<F: Field> AddU64Operation<F> {
+    pub fn populate(&mut self, record: &mut impl ByteRecord, a_u64: u64, b_u64: u64) -> u64 {
+        let expected = a_u64.wrapping_add(b_u64);
+        self.value = [Word::from(expected as u32), Word::from((expected >> 32) as u32)];
+
+        let a = a_u64.to_le_bytes();
+        let b = b_u64.to_le_bytes();
+
+        let mut carry = 0u32;
+        for i in 0..U64_SIZE - 1 {
+            carry = u32::from((a[i] as u32) + (b[i] as u32) + carry > 255);
+            self.carry[i] = F::from_canonical_u32(carry);
+        }
+
+        // Range check
+        {
+            record.add_u8_range_checks(&a);
+            record.add_u8_range_checks(&b);
+            record.add_u8_range_checks(&expected.to_le_bytes());
+        }
+        expected
+    }
+
+    pub fn eval<AB: ZKMAirBuilder>(
+        builder: &mut AB,
+        a: [Word<AB::Var>; 2],
+        b: [Word<AB::Var>; 2],
+        cols: AddU64Operation<AB::Var>,
+        is_real: AB::Expr,
+    ) {
+        let one = AB::Expr::one();
+        let base = AB::F::from_canonical_u32(256);
+
+        let mut builder_is_real = builder.when(is_real.clone());
+
+        // For each limb, assert that difference between the carried result and the non-carried
+        // result is either zero or the base.
+        for i in 0..U64_SIZE {
+            let (word, byte) = (i / WORD_SIZE, i % WORD_SIZE);
+            let mut overflow = a[word][byte] + b[word][byte] - cols.value[word][byte];
+            if i > 0 {
+                overflow = overflow + cols.carry[i - 1];
+            }
+
+            if i == U64_SIZE - 1 {
+                builder_is_real.assert_zero(overflow.clone() * (overflow - base));
+            } else {
+                // If the carry is one, then the overflow must be the base.
+                builder_is_real.assert_zero(cols.carry[i] * (overflow.clone() - base));
+
+                // If the carry is not one, then the overflow must be zero.
+                builder_is_real.assert_zero((cols.carry[i] - one.clone()) * overflow);
+
+                // Assert that the carry is either zero or one.
+                builder_is_real.assert_bool(cols.carry[i]);
+            }
+        }
+        builder_is_real.assert_bool(is_real.clone());
+
+        // Range check each byte.
+        {
+            for word in a.iter().chain(b.iter()).chain(cols.value.iter()) {
+                builder.slice_range_check_u8(&word.0, is_real.clone());
+            }
+        }
+    }
+}
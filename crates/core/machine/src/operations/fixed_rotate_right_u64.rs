@@ -0,0 +1,140 @@
+use p3_field::{Field, FieldAlgebra};
+use zkm_core_executor::{
+    events::{ByteLookupEvent, ByteRecord},
+    ByteOpcode,
+};
+use zkm_derive::AlignedBorrow;
+use zkm_primitives::consts::WORD_SIZE;
+use zkm_stark::{air::ZKMAirBuilder, Word};
+
+use crate::bytes::utils::shr_carry;
+
+/// The number of bytes in a 64-bit word.
+const U64_SIZE: usize = 2 * WORD_SIZE;
+
+/// A set of columns needed to compute `rotateright` of a 64-bit word with a fixed offset R.
+///
+/// The 64-bit word is stored as two little-endian words, low word first. Note that we decompose
+/// shifts into a byte shift and a bit shift.
+#[derive(AlignedBorrow, Default, Debug, Clone, Copy)]
+#[repr(C)]
+pub struct FixedRotateRightU64Operation<T> {
+    /// The output value.
+    pub value: [Word<T>; 2],
+
+    /// The shift output of `shrcarry` on each byte of the 64-bit word.
+    pub shift: [Word<T>; 2],
+
+    /// The carry output of `shrcarry` on each byte of the 64-bit word.
+    pub carry: [Word<T>; 2],
+}
+
+impl<F: Field> FixedRotateRightU64Operation<F> {
+    pub const fn nb_bytes_to_shift(rotation: usize) -> usize {
+        rotation / 8
+    }
+
+    pub const fn nb_bits_to_shift(rotation: usize) -> usize {
+        rotation % 8
+    }
+
+    pub const fn carry_multiplier(rotation: usize) -> u32 {
+        let nb_bits_to_shift = Self::nb_bits_to_shift(rotation);
+        1 << (8 - nb_bits_to_shift)
+    }
+
+    pub fn populate(&mut self, record: &mut impl ByteRecord, input: u64, rotation: usize) -> u64 {
+        let input_bytes = input.to_le_bytes();
+        let expected = input.rotate_right(rotation as u32);
+
+        // Compute some constants with respect to the rotation needed for the rotation.
+        let nb_bytes_to_shift = Self::nb_bytes_to_shift(rotation);
+        let nb_bits_to_shift = Self::nb_bits_to_shift(rotation);
+        let carry_multiplier = F::from_canonical_u32(Self::carry_multiplier(rotation));
+
+        // For each byte, calculate the shift and carry. If it's not the first byte, calculate the
+        // new byte value using the current shifted byte and the last carry.
+        let mut first_shift = F::ZERO;
+        let mut last_carry = F::ZERO;
+        for i in (0..U64_SIZE).rev() {
+            // Perform the byte shift.
+            let b = input_bytes[(i + nb_bytes_to_shift) % U64_SIZE];
+            let c = nb_bits_to_shift as u8;
+
+            let (shift, carry) = shr_carry(b, c);
+
+            let byte_event =
+                ByteLookupEvent { opcode: ByteOpcode::ShrCarry, a1: shift as u16, a2: carry, b, c };
+            record.add_byte_lookup_event(byte_event);
+
+            let (word, byte) = (i / WORD_SIZE, i % WORD_SIZE);
+            self.shift[word][byte] = F::from_canonical_u8(shift);
+            self.carry[word][byte] = F::from_canonical_u8(carry);
+
+            if i == U64_SIZE - 1 {
+                first_shift = self.shift[word][byte];
+            } else {
+                self.value[word][byte] = self.shift[word][byte] + last_carry * carry_multiplier;
+            }
+
+            last_carry = self.carry[word][byte];
+        }
+
+        // For the first byte, we didn't know the last carry so compute the rotated byte here.
+        self.value[1][WORD_SIZE - 1] = first_shift + last_carry * carry_multiplier;
+
+        // Check that the value is correct.
+        assert_eq!(self.value[0].to_u32(), expected as u32);
+        assert_eq!(self.value[1].to_u32(), (expected >> 32) as u32);
+
+        expected
+    }
+
+    pub fn eval<AB: ZKMAirBuilder>(
+        builder: &mut AB,
+        input: [Word<AB::Var>; 2],
+        rotation: usize,
+        cols: FixedRotateRightU64Operation<AB::Var>,
+        is_real: AB::Var,
+    ) {
+        // Compute some constants with respect to the rotation needed for the rotation.
+        let nb_bytes_to_shift = Self::nb_bytes_to_shift(rotation);
+        let nb_bits_to_shift = Self::nb_bits_to_shift(rotation);
+        let carry_multiplier = AB::F::from_canonical_u32(Self::carry_multiplier(rotation));
+
+        // For each byte, calculate the shift and carry. If it's not the first byte, calculate the
+        // new byte value using the current shifted byte and the last carry.
+        let mut first_shift = AB::Expr::zero();
+        let mut last_carry = AB::Expr::zero();
+        for i in (0..U64_SIZE).rev() {
+            // Perform the byte shift.
+            let j = (i + nb_bytes_to_shift) % U64_SIZE;
+            let input_byte = input[j / WORD_SIZE][j % WORD_SIZE];
+
+            let (word, byte) = (i / WORD_SIZE, i % WORD_SIZE);
+            builder.send_byte_pair(
+                AB::F::from_canonical_u32(ByteOpcode::ShrCarry as u32),
+                cols.shift[word][byte],
+                cols.carry[word][byte],
+                input_byte,
+                AB::F::from_canonical_usize(nb_bits_to_shift),
+                is_real,
+            );
+
+            if i == U64_SIZE - 1 {
+                first_shift = cols.shift[word][byte].into();
+            } else {
+                builder.assert_eq(
+                    cols.value[word][byte],
+                    cols.shift[word][byte] + last_carry * carry_multiplier,
+                );
+            }
+
+            last_carry = cols.carry[word][byte].into();
+        }
+
+        // For the first byte, we didn't know the last carry so compute the rotated byte here.
+        builder
+            .assert_eq(cols.value[1][WORD_SIZE - 1], first_shift + last_carry * carry_multiplier);
+    }
+}
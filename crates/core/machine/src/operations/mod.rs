@@ -7,12 +7,16 @@
 mod add;
 mod add4;
 mod add5;
+mod add5_u64;
+mod add_u64;
 mod adddouble;
 mod and;
 mod cmp;
 pub mod field;
 mod fixed_rotate_right;
+mod fixed_rotate_right_u64;
 mod fixed_shift_right;
+mod fixed_shift_right_u64;
 mod global_accumulation;
 mod global_lookup;
 mod is_equal_word;
@@ -28,11 +32,15 @@ mod xor;
 pub use add::*;
 pub use add4::*;
 pub use add5::*;
+pub use add5_u64::*;
+pub use add_u64::*;
 pub use adddouble::*;
 pub use and::*;
 pub use cmp::*;
 pub use fixed_rotate_right::*;
+pub use fixed_rotate_right_u64::*;
 pub use fixed_shift_right::*;
+pub use fixed_shift_right_u64::*;
 pub use global_accumulation::*;
 pub use global_lookup::*;
 pub use is_equal_word::*;
@@ -243,6 +243,84 @@ impl<F: PrimeField32> CoreShapeConfig<F> {
         Err(CoreShapeError::PrecompileNotIncluded(record.stats()))
     }
 
+    /// The height of each chip of `record` that [`Self::fix_shape`] fits into the allowed shapes.
+    pub fn record_heights(&self, record: &ExecutionRecord) -> Vec<(MipsAirId, usize)> {
+        let has_memory_events = !record.global_memory_initialize_events.is_empty()
+            || !record.global_memory_finalize_events.is_empty();
+        if record.contains_cpu() {
+            let mut heights = MipsAir::<F>::core_heights(record);
+            if has_memory_events {
+                heights.extend(MipsAir::<F>::memory_heights(record));
+            }
+            return heights;
+        }
+        if has_memory_events {
+            return MipsAir::<F>::memory_heights(record);
+        }
+        for air in self.partial_precompile_shapes.keys() {
+            if let Some((height, num_memory_local_events, num_global_events)) =
+                air.precompile_heights(record)
+            {
+                return vec![
+                    (MipsAirId::from_str(&air.name()).unwrap(), height),
+                    (
+                        MipsAirId::MemoryLocal,
+                        num_memory_local_events.div_ceil(NUM_LOCAL_MEMORY_ENTRIES_PER_ROW),
+                    ),
+                    (MipsAirId::Global, num_global_events),
+                ];
+            }
+        }
+        vec![]
+    }
+
+    /// The largest log2 height each chip has in the shapes [`Self::fix_shape`] may pick for
+    /// `record`.
+    ///
+    /// A chip of `record` taller than this can't fit any of the allowed shapes.
+    pub fn max_log2_heights(&self, record: &ExecutionRecord) -> HashMap<MipsAirId, usize> {
+        let has_memory_events = !record.global_memory_initialize_events.is_empty()
+            || !record.global_memory_finalize_events.is_empty();
+        if record.contains_cpu() && has_memory_events {
+            return cluster_max_log2_heights(&self.partial_small_shapes);
+        }
+        if record.contains_cpu() {
+            let log2_shard_size = record.cpu_events.len().next_power_of_two().ilog2() as usize;
+            return cluster_max_log2_heights(
+                self.partial_core_shapes
+                    .range(log2_shard_size..)
+                    .flat_map(|(_, clusters)| clusters),
+            );
+        }
+        if has_memory_events {
+            return cluster_max_log2_heights([&self.partial_memory_shapes]);
+        }
+
+        let mut max_log2_heights = HashMap::new();
+        for (air, (memory_events_per_row, allowed_log2_heights)) in
+            self.partial_precompile_shapes.iter()
+        {
+            if air.precompile_heights(record).is_none() {
+                continue;
+            }
+            let Some(allowed_log2_height) = allowed_log2_heights.iter().max() else {
+                break;
+            };
+            for shape in
+                self.get_precompile_shapes(air, *memory_events_per_row, *allowed_log2_height)
+            {
+                for (name, log2_height) in shape {
+                    let max_log2_height = max_log2_heights
+                        .entry(MipsAirId::from_str(&name).unwrap())
+                        .or_insert(log2_height);
+                    *max_log2_height = (*max_log2_height).max(log2_height);
+                }
+            }
+            break;
+        }
+        max_log2_heights
+    }
+
     fn get_precompile_shapes(
         &self,
         air: &MipsAir<F>,
@@ -530,6 +608,22 @@ impl<F: PrimeField32> Default for CoreShapeConfig<F> {
     }
 }
 
+/// The largest log2 height each chip is allowed to have in any of `clusters`.
+fn cluster_max_log2_heights<'a>(
+    clusters: impl IntoIterator<Item = &'a ShapeCluster<MipsAirId>>,
+) -> HashMap<MipsAirId, usize> {
+    let mut max_log2_heights = HashMap::new();
+    for cluster in clusters {
+        for (air, log2_heights) in cluster.iter() {
+            if let Some(&log2_height) = log2_heights.iter().flatten().max() {
+                let max_log2_height = max_log2_heights.entry(*air).or_insert(log2_height);
+                *max_log2_height = (*max_log2_height).max(log2_height);
+            }
+        }
+    }
+    max_log2_heights
+}
+
 fn derive_cluster_from_maximal_shape(shape: &Shape<MipsAirId>) -> ShapeCluster<MipsAirId> {
     // We first define a heuristic to derive the log heights from the maximal shape.
     let log2_gap_from_22 = 22 - shape.log2_height(&MipsAirId::Cpu).unwrap();
@@ -146,6 +146,7 @@ impl<F: PrimeField32> MachineAir<F> for SyscallChip {
                 .map(|(event, precompile)| {
                     let (rlo, rhi) = match precompile {
                         PrecompileEvent::Linux(le) => Self::pack_result_halves(le.v0),
+                        PrecompileEvent::SysFile(fe) => Self::pack_result_halves(fe.v0),
                         _ => (0, 0),
                     };
                     (event, rlo, rhi)
@@ -224,7 +225,7 @@ impl<F: PrimeField32> MachineAir<F> for SyscallChip {
             // For Precompile shard, a_record is default (prev_value=0), so detect
             // linux from the PrecompileEvent variant instead.
             let is_linux = match precompile_event {
-                Some(PrecompileEvent::Linux(_)) => true,
+                Some(PrecompileEvent::Linux(_) | PrecompileEvent::SysFile(_)) => true,
                 Some(_) => false,
                 None => syscall_event.a_record.prev_value.to_le_bytes()[1] != 0,
             };
@@ -232,6 +233,7 @@ impl<F: PrimeField32> MachineAir<F> for SyscallChip {
             if is_linux {
                 let result = match precompile_event {
                     Some(PrecompileEvent::Linux(linux_event)) => linux_event.v0,
+                    Some(PrecompileEvent::SysFile(file_event)) => file_event.v0,
                     _ => syscall_event.a_record.value,
                 };
                 let rb = result.to_le_bytes();
@@ -0,0 +1,328 @@
+use core::borrow::Borrow;
+
+use p3_air::{Air, AirBuilder, BaseAir};
+use p3_field::FieldAlgebra;
+use p3_matrix::Matrix;
+use zkm_core_executor::syscalls::SyscallCode;
+use zkm_stark::air::{BaseAirBuilder, LookupScope, ZKMAirBuilder};
+
+use super::{
+    Blake2bCompressInnerChip, Blake2bCompressInnerCols, BLAKE2B_NUM_ROUNDS,
+    NUM_BLAKE2B_COMPRESS_INNER_COLS, SIGMA,
+};
+use crate::{
+    air::{MemoryAirBuilder, WordAirBuilder},
+    memory::MemoryCols,
+    operations::{AddU64Operation, FixedRotateRightU64Operation},
+    syscall::precompiles::{
+        blake3::{G_INDEX, NUM_G_PER_ROUND},
+        sha512::{eval_xor_u64, u64_value},
+    },
+};
+
+impl<F> BaseAir<F> for Blake2bCompressInnerChip {
+    fn width(&self) -> usize {
+        NUM_BLAKE2B_COMPRESS_INNER_COLS
+    }
+}
+
+impl<AB> Air<AB> for Blake2bCompressInnerChip
+where
+    AB: ZKMAirBuilder,
+{
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let (local, next) = (main.row_slice(0), main.row_slice(1));
+        let local: &Blake2bCompressInnerCols<AB::Var> = (*local).borrow();
+        let next: &Blake2bCompressInnerCols<AB::Var> = (*next).borrow();
+
+        self.eval_control_flow_flags(builder, local, next);
+
+        self.eval_memory(builder, local);
+
+        self.eval_g(builder, local);
+
+        builder.assert_eq(local.start, local.is_real * local.round[0] * local.g[0]);
+        builder.receive_syscall(
+            local.shard,
+            local.clk,
+            AB::F::from_canonical_u32(SyscallCode::BLAKE2B_COMPRESS_INNER.syscall_id()),
+            local.state_ptr,
+            local.message_ptr,
+            local.start,
+            LookupScope::Local,
+        );
+    }
+}
+
+impl Blake2bCompressInnerChip {
+    fn eval_control_flow_flags<AB: ZKMAirBuilder>(
+        &self,
+        builder: &mut AB,
+        local: &Blake2bCompressInnerCols<AB::Var>,
+        next: &Blake2bCompressInnerCols<AB::Var>,
+    ) {
+        // Verify that the `g` columns are a one-hot encoding.
+        let mut g_sum = AB::Expr::zero();
+        for i in 0..NUM_G_PER_ROUND {
+            builder.assert_bool(local.g[i]);
+            g_sum = g_sum.clone() + local.g[i].into();
+        }
+        builder.assert_one(g_sum);
+
+        // Verify that the `round` columns are a one-hot encoding.
+        let mut round_sum = AB::Expr::zero();
+        for r in 0..BLAKE2B_NUM_ROUNDS {
+            builder.assert_bool(local.round[r]);
+            round_sum = round_sum.clone() + local.round[r].into();
+        }
+        builder.assert_one(round_sum);
+
+        // The first row starts the first round.
+        builder.when_first_row().assert_one(local.g[0]);
+        builder.when_first_row().assert_one(local.round[0]);
+
+        // The `g` columns rotate by one every row.
+        for i in 0..NUM_G_PER_ROUND {
+            builder
+                .when_transition()
+                .when(local.g[i])
+                .assert_one(next.g[(i + 1) % NUM_G_PER_ROUND]);
+        }
+
+        // The `round` columns stay the same within a round, and rotate by one after its last `G`.
+        for r in 0..BLAKE2B_NUM_ROUNDS {
+            builder
+                .when_transition()
+                .when_not(local.g[NUM_G_PER_ROUND - 1])
+                .assert_eq(local.round[r], next.round[r]);
+            builder
+                .when_transition()
+                .when(local.g[NUM_G_PER_ROUND - 1])
+                .assert_eq(local.round[r], next.round[(r + 1) % BLAKE2B_NUM_ROUNDS]);
+        }
+
+        // Constrain the indices of the message words used by each `G`.
+        for r in 0..BLAKE2B_NUM_ROUNDS {
+            for i in 0..NUM_G_PER_ROUND {
+                for j in 0..2 {
+                    builder.when(local.round[r] * local.g[i]).assert_eq(
+                        local.message_index[j],
+                        AB::F::from_canonical_usize(SIGMA[r % 10][2 * i + j]),
+                    );
+                }
+            }
+        }
+
+        builder.assert_eq(
+            local.is_last_row,
+            local.round[BLAKE2B_NUM_ROUNDS - 1] * local.g[NUM_G_PER_ROUND - 1],
+        );
+
+        // If this row is real and not the last of a syscall, then the next row has the same inputs.
+        builder
+            .when_transition()
+            .when(local.is_real)
+            .when_not(local.is_last_row)
+            .assert_eq(local.shard, next.shard);
+        builder
+            .when_transition()
+            .when(local.is_real)
+            .when_not(local.is_last_row)
+            .assert_eq(local.clk, next.clk);
+        builder
+            .when_transition()
+            .when(local.is_real)
+            .when_not(local.is_last_row)
+            .assert_eq(local.state_ptr, next.state_ptr);
+        builder
+            .when_transition()
+            .when(local.is_real)
+            .when_not(local.is_last_row)
+            .assert_eq(local.message_ptr, next.message_ptr);
+
+        // Assert that is_real is a bool.
+        builder.assert_bool(local.is_real);
+
+        // If this row is real and not the last of a syscall, then the next row is also real.
+        builder
+            .when_transition()
+            .when(local.is_real)
+            .when_not(local.is_last_row)
+            .assert_one(next.is_real);
+
+        // Once the is_real flag is changed to false, it should not be changed back.
+        builder.when_transition().when_not(local.is_real).assert_zero(next.is_real);
+
+        // Each syscall takes 96 rows, which is never a power of two, so the last row of the padded
+        // table is always padding.
+        builder.when_last_row().assert_zero(local.is_real);
+    }
+
+    /// Constrains the memory accesses of the state and message words.
+    fn eval_memory<AB: ZKMAirBuilder>(
+        &self,
+        builder: &mut AB,
+        local: &Blake2bCompressInnerCols<AB::Var>,
+    ) {
+        // Each `G` is executed one cycle after the previous one.
+        let mut clk: AB::Expr = local.clk.into();
+        for r in 0..BLAKE2B_NUM_ROUNDS {
+            clk = clk + local.round[r] * AB::Expr::from_canonical_usize(r * NUM_G_PER_ROUND);
+        }
+        for i in 0..NUM_G_PER_ROUND {
+            clk = clk + local.g[i] * AB::Expr::from_canonical_usize(i);
+        }
+
+        for k in 0..4 {
+            let mut index = AB::Expr::zero();
+            for i in 0..NUM_G_PER_ROUND {
+                index = index + local.g[i] * AB::Expr::from_canonical_usize(G_INDEX[i][k]);
+            }
+            let addr = local.state_ptr + index * AB::Expr::from_canonical_u32(8);
+            for half in 0..2 {
+                builder.eval_memory_access(
+                    local.shard,
+                    clk.clone(),
+                    addr.clone() + AB::Expr::from_canonical_usize(4 * half),
+                    &local.state[k][half],
+                    local.is_real,
+                );
+            }
+        }
+
+        for j in 0..2 {
+            let addr = local.message_ptr + local.message_index[j] * AB::Expr::from_canonical_u32(8);
+            for half in 0..2 {
+                builder.eval_memory_access(
+                    local.shard,
+                    clk.clone(),
+                    addr.clone() + AB::Expr::from_canonical_usize(4 * half),
+                    &local.message[j][half],
+                    local.is_real,
+                );
+            }
+        }
+    }
+
+    /// Constrains the `G` function, and that its output is written back to the state.
+    fn eval_g<AB: ZKMAirBuilder>(
+        &self,
+        builder: &mut AB,
+        local: &Blake2bCompressInnerCols<AB::Var>,
+    ) {
+        let [a, b, c, d] =
+            local.state.map(|access| [*access[0].prev_value(), *access[1].prev_value()]);
+        let [x, y] = local.message.map(|access| u64_value(&access));
+
+        // a := a + b + x.
+        AddU64Operation::<AB::F>::eval(builder, a, b, local.a_add_b, local.is_real.into());
+        AddU64Operation::<AB::F>::eval(
+            builder,
+            local.a_add_b.value,
+            x,
+            local.a_add_b_add_x,
+            local.is_real.into(),
+        );
+
+        // d := (d xor a) rightrotate 32.
+        eval_xor_u64(builder, d, local.a_add_b_add_x.value, local.d_xor_a, local.is_real);
+        FixedRotateRightU64Operation::<AB::F>::eval(
+            builder,
+            local.d_xor_a.map(|op| op.value),
+            32,
+            local.d_rr_32,
+            local.is_real,
+        );
+
+        // c := c + d.
+        AddU64Operation::<AB::F>::eval(
+            builder,
+            c,
+            local.d_rr_32.value,
+            local.c_add_d,
+            local.is_real.into(),
+        );
+
+        // b := (b xor c) rightrotate 24.
+        eval_xor_u64(builder, b, local.c_add_d.value, local.b_xor_c, local.is_real);
+        FixedRotateRightU64Operation::<AB::F>::eval(
+            builder,
+            local.b_xor_c.map(|op| op.value),
+            24,
+            local.b_rr_24,
+            local.is_real,
+        );
+
+        // a := a + b + y.
+        AddU64Operation::<AB::F>::eval(
+            builder,
+            local.a_add_b_add_x.value,
+            local.b_rr_24.value,
+            local.a_add_b_2,
+            local.is_real.into(),
+        );
+        AddU64Operation::<AB::F>::eval(
+            builder,
+            local.a_add_b_2.value,
+            y,
+            local.a_add_b_add_y,
+            local.is_real.into(),
+        );
+
+        // d := (d xor a) rightrotate 16.
+        eval_xor_u64(
+            builder,
+            local.d_rr_32.value,
+            local.a_add_b_add_y.value,
+            local.d_xor_a_2,
+            local.is_real,
+        );
+        FixedRotateRightU64Operation::<AB::F>::eval(
+            builder,
+            local.d_xor_a_2.map(|op| op.value),
+            16,
+            local.d_rr_16,
+            local.is_real,
+        );
+
+        // c := c + d.
+        AddU64Operation::<AB::F>::eval(
+            builder,
+            local.c_add_d.value,
+            local.d_rr_16.value,
+            local.c_add_d_2,
+            local.is_real.into(),
+        );
+
+        // b := (b xor c) rightrotate 63.
+        eval_xor_u64(
+            builder,
+            local.b_rr_24.value,
+            local.c_add_d_2.value,
+            local.b_xor_c_2,
+            local.is_real,
+        );
+        FixedRotateRightU64Operation::<AB::F>::eval(
+            builder,
+            local.b_xor_c_2.map(|op| op.value),
+            63,
+            local.b_rr_63,
+            local.is_real,
+        );
+
+        // The mixed state words are written back in place.
+        let result = [
+            local.a_add_b_add_y.value,
+            local.b_rr_63.value,
+            local.c_add_d_2.value,
+            local.d_rr_16.value,
+        ];
+        for k in 0..4 {
+            let value = u64_value(&local.state[k]);
+            for half in 0..2 {
+                builder.when(local.is_real).assert_word_eq(value[half], result[k][half]);
+            }
+        }
+    }
+}
@@ -0,0 +1,80 @@
+use std::mem::size_of;
+
+use zkm_derive::AlignedBorrow;
+
+use super::BLAKE2B_NUM_ROUNDS;
+use crate::{
+    memory::{MemoryReadCols, MemoryWriteCols},
+    operations::{AddU64Operation, FixedRotateRightU64Operation, XorOperation},
+    syscall::precompiles::blake3::NUM_G_PER_ROUND,
+};
+
+pub const NUM_BLAKE2B_COMPRESS_INNER_COLS: usize = size_of::<Blake2bCompressInnerCols<u8>>();
+
+/// A set of columns needed to compute a single application of the BLAKE2b `G` function.
+///
+/// Each row reads the state words `(a, b, c, d)` and the two message words of its `G` application
+/// from memory, and writes the mixed state words back in place. Every 64-bit word is represented
+/// by its low and high 32-bit halves, in that order.
+#[derive(AlignedBorrow, Default, Debug, Clone, Copy)]
+#[repr(C)]
+pub struct Blake2bCompressInnerCols<T> {
+    /// Inputs.
+    pub shard: T,
+    pub clk: T,
+    pub state_ptr: T,
+    pub message_ptr: T,
+
+    pub start: T,
+
+    /// Which round we are currently processing.
+    pub round: [T; BLAKE2B_NUM_ROUNDS],
+
+    /// Which `G` application within the round we are currently processing. The first four mix
+    /// the columns of the state and the last four mix its diagonals.
+    pub g: [T; NUM_G_PER_ROUND],
+
+    /// The indices of the two message words used by the current `G` application.
+    pub message_index: [T; 2],
+
+    /// Memory access of the state words `(a, b, c, d)`. The previous value is the input of `G`
+    /// and the new value is its output.
+    pub state: [[MemoryWriteCols<T>; 2]; 4],
+
+    /// Memory access of the message words `x` and `y`.
+    pub message: [[MemoryReadCols<T>; 2]; 2],
+
+    /// `a := a + b + x`.
+    pub a_add_b: AddU64Operation<T>,
+    pub a_add_b_add_x: AddU64Operation<T>,
+
+    /// `d := (d xor a) rightrotate 32`.
+    pub d_xor_a: [XorOperation<T>; 2],
+    pub d_rr_32: FixedRotateRightU64Operation<T>,
+
+    /// `c := c + d`.
+    pub c_add_d: AddU64Operation<T>,
+
+    /// `b := (b xor c) rightrotate 24`.
+    pub b_xor_c: [XorOperation<T>; 2],
+    pub b_rr_24: FixedRotateRightU64Operation<T>,
+
+    /// `a := a + b + y`.
+    pub a_add_b_2: AddU64Operation<T>,
+    pub a_add_b_add_y: AddU64Operation<T>,
+
+    /// `d := (d xor a) rightrotate 16`.
+    pub d_xor_a_2: [XorOperation<T>; 2],
+    pub d_rr_16: FixedRotateRightU64Operation<T>,
+
+    /// `c := c + d`.
+    pub c_add_d_2: AddU64Operation<T>,
+
+    /// `b := (b xor c) rightrotate 63`.
+    pub b_xor_c_2: [XorOperation<T>; 2],
+    pub b_rr_63: FixedRotateRightU64Operation<T>,
+
+    pub is_last_row: T,
+
+    pub is_real: T,
+}
@@ -0,0 +1,108 @@
+//! The BLAKE2b compression precompile.
+//!
+//! The syscall only applies the twelve rounds of the compression function to a 16-word working
+//! state in place. Initializing the working state from the chaining value, the offset counter and
+//! the final block flag, and the final feed-forward, are left to the guest.
+
+mod air;
+mod columns;
+mod trace;
+
+pub use columns::*;
+
+/// The number of rounds of the BLAKE2b compression function.
+pub const BLAKE2B_NUM_ROUNDS: usize = 12;
+
+/// The message permutations of BLAKE2b. Round `r` uses `SIGMA[r % 10]`.
+pub const SIGMA: [[usize; 16]; 10] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3],
+    [11, 8, 12, 0, 5, 2, 15, 13, 10, 14, 3, 6, 7, 1, 9, 4],
+    [7, 9, 3, 1, 13, 12, 11, 14, 2, 6, 5, 10, 4, 0, 15, 8],
+    [9, 0, 5, 7, 2, 4, 10, 15, 14, 1, 11, 12, 6, 8, 3, 13],
+    [2, 12, 6, 10, 0, 11, 8, 3, 4, 13, 7, 5, 15, 14, 1, 9],
+    [12, 5, 1, 15, 14, 13, 4, 10, 0, 7, 6, 3, 9, 2, 8, 11],
+    [13, 11, 7, 14, 12, 1, 3, 9, 5, 0, 15, 4, 8, 6, 2, 10],
+    [6, 15, 14, 9, 11, 3, 0, 8, 12, 2, 13, 7, 1, 4, 10, 5],
+    [10, 2, 8, 4, 7, 6, 1, 5, 15, 11, 9, 14, 3, 12, 13, 0],
+];
+
+/// Implements the rounds of the BLAKE2b compression function. The inputs to the syscall are a
+/// pointer to the 16 u64 working state, which is updated in place, and a pointer to the 16 u64
+/// message block.
+///
+/// In the AIR, each syscall takes up 96 rows, one for each application of the `G` function. Each
+/// row operates over four 64-bit state words and two 64-bit message words.
+#[derive(Default)]
+pub struct Blake2bCompressInnerChip;
+
+impl Blake2bCompressInnerChip {
+    pub const fn new() -> Self {
+        Self {}
+    }
+}
+
+#[cfg(test)]
+pub mod compress_inner_tests {
+    use test_artifacts::BLAKE2B_ELF;
+    use zkm_core_executor::{syscalls::SyscallCode, Executor, Instruction, Opcode, Program};
+    use zkm_stark::{CpuProver, ZKMCoreOpts};
+
+    use crate::utils::{run_test, setup_logger};
+
+    pub fn blake2b_compress_inner_program() -> Program {
+        let state_ptr = 100;
+        let message_ptr = 1000;
+        let mut instructions = vec![Instruction::new(Opcode::ADD, 29, 0, 5, false, true)];
+        for i in 0..32 {
+            instructions.extend(vec![
+                Instruction::new(Opcode::ADD, 30, 0, state_ptr + i * 4, false, true),
+                Instruction::new(Opcode::SW, 29, 30, 0, false, true),
+                Instruction::new(Opcode::ADD, 30, 0, message_ptr + i * 4, false, true),
+                Instruction::new(Opcode::SW, 29, 30, 0, false, true),
+            ]);
+        }
+        instructions.extend(vec![
+            Instruction::new(
+                Opcode::ADD,
+                2,
+                0,
+                SyscallCode::BLAKE2B_COMPRESS_INNER as u32,
+                false,
+                true,
+            ),
+            Instruction::new(Opcode::ADD, 4, 0, state_ptr, false, true),
+            Instruction::new(Opcode::ADD, 5, 0, message_ptr, false, true),
+            Instruction::new(Opcode::SYSCALL, 2, 4, 5, false, false),
+        ]);
+        Program::new(instructions, 0, 0)
+    }
+
+    #[test]
+    fn test_blake2b_compress_inner_execute() {
+        let mut runtime = Executor::new(blake2b_compress_inner_program(), ZKMCoreOpts::default());
+        runtime.run_fast().unwrap();
+
+        let expected =
+            [0xb559f3c4d71939b4u64, 0x81835ca5f38b388b, 0x2d42c741af7c6d8c, 0x487682f7587e1ed1];
+        for i in 0..16 {
+            let addr = 100 + 8 * i as u32;
+            let value = u64::from(runtime.word(addr)) | (u64::from(runtime.word(addr + 4)) << 32);
+            assert_eq!(value, expected[i / 4]);
+        }
+    }
+
+    #[test]
+    fn prove_koalabear() {
+        setup_logger();
+        let program = blake2b_compress_inner_program();
+        run_test::<CpuProver<_, _>>(program).unwrap();
+    }
+
+    #[test]
+    fn test_blake2b_program() {
+        setup_logger();
+        let program = Program::from(BLAKE2B_ELF).unwrap();
+        run_test::<CpuProver<_, _>>(program).unwrap();
+    }
+}
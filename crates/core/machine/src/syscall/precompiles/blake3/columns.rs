@@ -0,0 +1,78 @@
+use std::mem::size_of;
+
+use zkm_derive::AlignedBorrow;
+
+use super::{BLAKE3_NUM_ROUNDS, NUM_G_PER_ROUND};
+use crate::{
+    memory::{MemoryReadCols, MemoryWriteCols},
+    operations::{AddOperation, FixedRotateRightOperation, XorOperation},
+};
+
+pub const NUM_BLAKE3_COMPRESS_INNER_COLS: usize = size_of::<Blake3CompressInnerCols<u8>>();
+
+/// A set of columns needed to compute a single application of the BLAKE3 `G` function.
+///
+/// Each row reads the state words `(a, b, c, d)` and the two message words of its `G` application
+/// from memory, and writes the mixed state words back in place.
+#[derive(AlignedBorrow, Default, Debug, Clone, Copy)]
+#[repr(C)]
+pub struct Blake3CompressInnerCols<T> {
+    /// Inputs.
+    pub shard: T,
+    pub clk: T,
+    pub state_ptr: T,
+    pub message_ptr: T,
+
+    pub start: T,
+
+    /// Which round we are currently processing.
+    pub round: [T; BLAKE3_NUM_ROUNDS],
+
+    /// Which `G` application within the round we are currently processing. The first four mix
+    /// the columns of the state and the last four mix its diagonals.
+    pub g: [T; NUM_G_PER_ROUND],
+
+    /// The indices of the two message words used by the current `G` application.
+    pub message_index: [T; 2],
+
+    /// Memory access of the state words `(a, b, c, d)`. The previous value is the input of `G`
+    /// and the new value is its output.
+    pub state: [MemoryWriteCols<T>; 4],
+
+    /// Memory access of the message words `x` and `y`.
+    pub message: [MemoryReadCols<T>; 2],
+
+    /// `a := a + b + x`.
+    pub a_add_b: AddOperation<T>,
+    pub a_add_b_add_x: AddOperation<T>,
+
+    /// `d := (d xor a) rightrotate 16`.
+    pub d_xor_a: XorOperation<T>,
+    pub d_rr_16: FixedRotateRightOperation<T>,
+
+    /// `c := c + d`.
+    pub c_add_d: AddOperation<T>,
+
+    /// `b := (b xor c) rightrotate 12`.
+    pub b_xor_c: XorOperation<T>,
+    pub b_rr_12: FixedRotateRightOperation<T>,
+
+    /// `a := a + b + y`.
+    pub a_add_b_2: AddOperation<T>,
+    pub a_add_b_add_y: AddOperation<T>,
+
+    /// `d := (d xor a) rightrotate 8`.
+    pub d_xor_a_2: XorOperation<T>,
+    pub d_rr_8: FixedRotateRightOperation<T>,
+
+    /// `c := c + d`.
+    pub c_add_d_2: AddOperation<T>,
+
+    /// `b := (b xor c) rightrotate 7`.
+    pub b_xor_c_2: XorOperation<T>,
+    pub b_rr_7: FixedRotateRightOperation<T>,
+
+    pub is_last_row: T,
+
+    pub is_real: T,
+}
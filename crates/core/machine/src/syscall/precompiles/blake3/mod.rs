@@ -0,0 +1,116 @@
+//! The BLAKE3 compression precompile.
+//!
+//! The syscall only applies the seven rounds of the compression function to a 16-word working
+//! state in place. Building the working state from the chaining value, the counter, the block
+//! length and the flags, and the final feed-forward, are left to the guest.
+
+mod air;
+mod columns;
+mod trace;
+
+pub use columns::*;
+
+/// The number of rounds of the BLAKE3 compression function.
+pub const BLAKE3_NUM_ROUNDS: usize = 7;
+
+/// The number of applications of the `G` function in a round.
+pub const NUM_G_PER_ROUND: usize = 8;
+
+/// The indices of the state words `(a, b, c, d)` mixed by each `G` application of a round.
+pub const G_INDEX: [[usize; 4]; NUM_G_PER_ROUND] = [
+    [0, 4, 8, 12],
+    [1, 5, 9, 13],
+    [2, 6, 10, 14],
+    [3, 7, 11, 15],
+    [0, 5, 10, 15],
+    [1, 6, 11, 12],
+    [2, 7, 8, 13],
+    [3, 4, 9, 14],
+];
+
+/// The order in which the message words are consumed in each round.
+pub const MSG_SCHEDULE: [[usize; 16]; BLAKE3_NUM_ROUNDS] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [2, 6, 3, 10, 7, 0, 4, 13, 1, 11, 12, 5, 9, 14, 15, 8],
+    [3, 4, 10, 12, 13, 2, 7, 14, 6, 5, 9, 0, 11, 15, 8, 1],
+    [10, 7, 12, 9, 14, 3, 13, 15, 4, 0, 11, 2, 5, 8, 1, 6],
+    [12, 13, 9, 11, 15, 10, 14, 8, 7, 2, 5, 3, 0, 1, 6, 4],
+    [9, 14, 11, 5, 8, 12, 15, 1, 13, 3, 0, 10, 2, 6, 4, 7],
+    [11, 15, 5, 0, 1, 9, 8, 6, 14, 10, 2, 12, 3, 4, 7, 13],
+];
+
+/// Implements the rounds of the BLAKE3 compression function. The inputs to the syscall are a
+/// pointer to the 16 u32 working state, which is updated in place, and a pointer to the 16 u32
+/// message block.
+///
+/// In the AIR, each syscall takes up 56 rows, one for each application of the `G` function.
+#[derive(Default)]
+pub struct Blake3CompressInnerChip;
+
+impl Blake3CompressInnerChip {
+    pub const fn new() -> Self {
+        Self {}
+    }
+}
+
+#[cfg(test)]
+pub mod compress_inner_tests {
+    use test_artifacts::BLAKE3_ELF;
+    use zkm_core_executor::{syscalls::SyscallCode, Executor, Instruction, Opcode, Program};
+    use zkm_stark::{CpuProver, ZKMCoreOpts};
+
+    use crate::utils::{run_test, setup_logger};
+
+    pub fn blake3_compress_inner_program() -> Program {
+        let state_ptr = 100;
+        let message_ptr = 1000;
+        let mut instructions = vec![Instruction::new(Opcode::ADD, 29, 0, 5, false, true)];
+        for i in 0..16 {
+            instructions.extend(vec![
+                Instruction::new(Opcode::ADD, 30, 0, state_ptr + i * 4, false, true),
+                Instruction::new(Opcode::SW, 29, 30, 0, false, true),
+                Instruction::new(Opcode::ADD, 30, 0, message_ptr + i * 4, false, true),
+                Instruction::new(Opcode::SW, 29, 30, 0, false, true),
+            ]);
+        }
+        instructions.extend(vec![
+            Instruction::new(
+                Opcode::ADD,
+                2,
+                0,
+                SyscallCode::BLAKE3_COMPRESS_INNER as u32,
+                false,
+                true,
+            ),
+            Instruction::new(Opcode::ADD, 4, 0, state_ptr, false, true),
+            Instruction::new(Opcode::ADD, 5, 0, message_ptr, false, true),
+            Instruction::new(Opcode::SYSCALL, 2, 4, 5, false, false),
+        ]);
+        Program::new(instructions, 0, 0)
+    }
+
+    #[test]
+    fn test_blake3_compress_inner_execute() {
+        let mut runtime = Executor::new(blake3_compress_inner_program(), ZKMCoreOpts::default());
+        runtime.run_fast().unwrap();
+
+        let expected = [0x60eb76a0, 0xcafaf7d9, 0x67baff9d, 0xc55a9675];
+        for i in 0..16 {
+            assert_eq!(runtime.word(100 + 4 * i as u32), expected[i / 4]);
+        }
+    }
+
+    #[test]
+    fn prove_koalabear() {
+        setup_logger();
+        let program = blake3_compress_inner_program();
+        run_test::<CpuProver<_, _>>(program).unwrap();
+    }
+
+    #[test]
+    fn test_blake3_program() {
+        setup_logger();
+        let program = Program::from(BLAKE3_ELF).unwrap();
+        run_test::<CpuProver<_, _>>(program).unwrap();
+    }
+}
@@ -0,0 +1,175 @@
+use std::borrow::BorrowMut;
+
+use hashbrown::HashMap;
+use itertools::Itertools;
+use p3_field::PrimeField32;
+use p3_matrix::dense::RowMajorMatrix;
+use p3_maybe_rayon::prelude::{ParallelIterator, ParallelSlice};
+use zkm_core_executor::{
+    events::{Blake3CompressInnerEvent, ByteLookupEvent, ByteRecord, PrecompileEvent},
+    syscalls::SyscallCode,
+    ExecutionRecord, Program,
+};
+use zkm_stark::air::MachineAir;
+
+use super::{
+    Blake3CompressInnerChip, Blake3CompressInnerCols, BLAKE3_NUM_ROUNDS, MSG_SCHEDULE,
+    NUM_BLAKE3_COMPRESS_INNER_COLS, NUM_G_PER_ROUND,
+};
+use crate::{utils::pad_rows_fixed, CoreChipError};
+
+impl<F: PrimeField32> MachineAir<F> for Blake3CompressInnerChip {
+    type Record = ExecutionRecord;
+
+    type Program = Program;
+
+    type Error = CoreChipError;
+
+    fn name(&self) -> String {
+        "Blake3CompressInner".to_string()
+    }
+
+    fn generate_trace(
+        &self,
+        input: &ExecutionRecord,
+        _: &mut ExecutionRecord,
+    ) -> Result<RowMajorMatrix<F>, Self::Error> {
+        let rows = Vec::new();
+
+        let mut wrapped_rows = Some(rows);
+        for (_, event) in input.get_precompile_events(SyscallCode::BLAKE3_COMPRESS_INNER) {
+            let event = if let PrecompileEvent::Blake3CompressInner(event) = event {
+                event
+            } else {
+                unreachable!()
+            };
+            self.event_to_rows(event, &mut wrapped_rows, &mut Vec::new());
+        }
+        let mut rows = wrapped_rows.unwrap();
+
+        let num_real_rows = rows.len();
+
+        pad_rows_fixed(
+            &mut rows,
+            || [F::ZERO; NUM_BLAKE3_COMPRESS_INNER_COLS],
+            input.fixed_log2_rows::<F, _>(self),
+            <Blake3CompressInnerChip as MachineAir<F>>::name(self).as_str(),
+        );
+
+        // Set the control flow columns for the padded rows.
+        for (i, row) in rows.iter_mut().enumerate().skip(num_real_rows) {
+            let cols: &mut Blake3CompressInnerCols<F> = row.as_mut_slice().borrow_mut();
+            cols.populate_flags(i % (BLAKE3_NUM_ROUNDS * NUM_G_PER_ROUND));
+        }
+
+        // Convert the trace to a row major matrix.
+        Ok(RowMajorMatrix::new(
+            rows.into_iter().flatten().collect::<Vec<_>>(),
+            NUM_BLAKE3_COMPRESS_INNER_COLS,
+        ))
+    }
+
+    fn generate_dependencies(
+        &self,
+        input: &Self::Record,
+        output: &mut Self::Record,
+    ) -> Result<(), Self::Error> {
+        let events = input.get_precompile_events(SyscallCode::BLAKE3_COMPRESS_INNER);
+        let chunk_size = std::cmp::max(events.len() / num_cpus::get(), 1);
+
+        let blu_batches = events
+            .par_chunks(chunk_size)
+            .map(|events| {
+                let mut blu: HashMap<ByteLookupEvent, usize> = HashMap::new();
+                events.iter().for_each(|(_, event)| {
+                    let event = if let PrecompileEvent::Blake3CompressInner(event) = event {
+                        event
+                    } else {
+                        unreachable!()
+                    };
+                    self.event_to_rows::<F>(event, &mut None, &mut blu);
+                });
+                blu
+            })
+            .collect::<Vec<_>>();
+
+        output.add_byte_lookup_events_from_maps(blu_batches.iter().collect_vec());
+        Ok(())
+    }
+
+    fn included(&self, shard: &Self::Record) -> bool {
+        if let Some(shape) = shard.shape.as_ref() {
+            shape.included::<F, _>(self)
+        } else {
+            !shard.get_precompile_events(SyscallCode::BLAKE3_COMPRESS_INNER).is_empty()
+        }
+    }
+}
+
+impl Blake3CompressInnerChip {
+    fn event_to_rows<F: PrimeField32>(
+        &self,
+        event: &Blake3CompressInnerEvent,
+        rows: &mut Option<Vec<[F; NUM_BLAKE3_COMPRESS_INNER_COLS]>>,
+        blu: &mut impl ByteRecord,
+    ) {
+        for (i, (message_reads, state_writes)) in
+            event.message_reads.iter().zip_eq(event.state_writes.iter()).enumerate()
+        {
+            let mut row = [F::ZERO; NUM_BLAKE3_COMPRESS_INNER_COLS];
+            let cols: &mut Blake3CompressInnerCols<F> = row.as_mut_slice().borrow_mut();
+
+            cols.shard = F::from_canonical_u32(event.shard);
+            cols.clk = F::from_canonical_u32(event.clk);
+            cols.state_ptr = F::from_canonical_u32(event.state_ptr);
+            cols.message_ptr = F::from_canonical_u32(event.message_ptr);
+            cols.is_real = F::ONE;
+            cols.populate_flags(i);
+            cols.start = cols.is_real * cols.round[0] * cols.g[0];
+
+            for k in 0..4 {
+                cols.state[k].populate(state_writes[k], blu);
+            }
+            for j in 0..2 {
+                cols.message[j].populate(message_reads[j], blu);
+            }
+
+            let [a, b, c, d] = state_writes.map(|record| record.prev_value);
+            let [x, y] = message_reads.map(|record| record.value);
+
+            let a_add_b = cols.a_add_b.populate(blu, a, b);
+            let a = cols.a_add_b_add_x.populate(blu, a_add_b, x);
+            let d_xor_a = cols.d_xor_a.populate(blu, d, a);
+            let d = cols.d_rr_16.populate(blu, d_xor_a, 16);
+            let c = cols.c_add_d.populate(blu, c, d);
+            let b_xor_c = cols.b_xor_c.populate(blu, b, c);
+            let b = cols.b_rr_12.populate(blu, b_xor_c, 12);
+
+            let a_add_b = cols.a_add_b_2.populate(blu, a, b);
+            let a = cols.a_add_b_add_y.populate(blu, a_add_b, y);
+            let d_xor_a = cols.d_xor_a_2.populate(blu, d, a);
+            let d = cols.d_rr_8.populate(blu, d_xor_a, 8);
+            let c = cols.c_add_d_2.populate(blu, c, d);
+            let b_xor_c = cols.b_xor_c_2.populate(blu, b, c);
+            let b = cols.b_rr_7.populate(blu, b_xor_c, 7);
+
+            debug_assert_eq!([a, b, c, d], state_writes.map(|record| record.value));
+
+            if let Some(rows) = rows.as_mut() {
+                rows.push(row);
+            }
+        }
+    }
+}
+
+impl<F: PrimeField32> Blake3CompressInnerCols<F> {
+    /// Populates the control flow columns for the `i`-th `G` application of a syscall.
+    fn populate_flags(&mut self, i: usize) {
+        let (round, g) = (i / NUM_G_PER_ROUND, i % NUM_G_PER_ROUND);
+        self.round[round] = F::ONE;
+        self.g[g] = F::ONE;
+        self.message_index =
+            [0, 1].map(|j| F::from_canonical_usize(MSG_SCHEDULE[round][2 * g + j]));
+        self.is_last_row = self.round[BLAKE3_NUM_ROUNDS - 1] * self.g[NUM_G_PER_ROUND - 1];
+    }
+}
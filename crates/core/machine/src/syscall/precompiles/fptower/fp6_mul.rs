@@ -0,0 +1,651 @@
+use std::{
+    borrow::{Borrow, BorrowMut},
+    marker::PhantomData,
+};
+
+use crate::{air::MemoryAirBuilder, utils::zeroed_f_vec, CoreChipError};
+use generic_array::GenericArray;
+use itertools::Itertools;
+use num::BigUint;
+use p3_air::{Air, BaseAir};
+use p3_field::{FieldAlgebra, PrimeField32};
+use p3_matrix::{dense::RowMajorMatrix, Matrix};
+use std::mem::size_of;
+use typenum::Unsigned;
+use zkm_core_executor::{
+    events::{ByteLookupEvent, ByteRecord, FieldOperation, PrecompileEvent},
+    syscalls::SyscallCode,
+    ExecutionRecord, Program,
+};
+use zkm_curves::{
+    params::{FieldParameters, Limbs, NumLimbs, NumWords},
+    weierstrass::{FieldType, FpOpField},
+};
+use zkm_derive::AlignedBorrow;
+use zkm_stark::air::{BaseAirBuilder, LookupScope, MachineAir, Polynomial, ZKMAirBuilder};
+
+use crate::{
+    memory::{value_as_limbs, MemoryReadCols, MemoryWriteCols},
+    operations::field::field_op::FieldOpCols,
+    utils::{limbs_from_prev_access, pad_rows_fixed, words_to_bytes_le_vec},
+};
+
+pub const fn num_fp6_mul_cols<P: FieldParameters + NumWords>() -> usize {
+    size_of::<Fp6MulAssignCols<u8, P>>()
+}
+
+/// A set of columns for the product of two Fp2 elements.
+#[derive(Debug, Clone, AlignedBorrow)]
+#[repr(C)]
+pub struct Fp2ProductCols<T, P: FieldParameters> {
+    pub(crate) a0_mul_b0: FieldOpCols<T, P>,
+    pub(crate) a1_mul_b1: FieldOpCols<T, P>,
+    pub(crate) a0_mul_b1: FieldOpCols<T, P>,
+    /// `a0 * b0 - a1 * b1`.
+    pub(crate) c0: FieldOpCols<T, P>,
+    /// `a1 * b0 + a0 * b1`.
+    pub(crate) c1: FieldOpCols<T, P>,
+}
+
+/// A set of columns for multiplying an Fp2 element by the non-residue `ξ = k + u`.
+#[derive(Debug, Clone, AlignedBorrow)]
+#[repr(C)]
+pub struct Fp2NonResidueCols<T, P: FieldParameters> {
+    pub(crate) k_mul_a0: FieldOpCols<T, P>,
+    /// `k * a0 - a1`.
+    pub(crate) c0: FieldOpCols<T, P>,
+    /// `k * a1 + a0`.
+    pub(crate) c1: FieldOpCols<T, P>,
+}
+
+/// A set of columns for the Fp6Mul operation.
+///
+/// An Fp6 element is laid out in memory as three consecutive Fp2 elements `(c0, c1, c2)`
+/// representing `c0 + c1 * v + c2 * v^2` with `v^3 = ξ`.
+#[derive(Debug, Clone, AlignedBorrow)]
+#[repr(C)]
+pub struct Fp6MulAssignCols<T, P: FieldParameters + NumWords> {
+    pub is_real: T,
+    pub shard: T,
+    pub clk: T,
+    pub x_ptr: T,
+    pub y_ptr: T,
+    pub x_access: [GenericArray<MemoryWriteCols<T>, P::WordsCurvePoint>; 3],
+    pub y_access: [GenericArray<MemoryReadCols<T>, P::WordsCurvePoint>; 3],
+    /// The products `a_i * b_j` for all pairs of Fp2 coefficients.
+    pub(crate) products: [[Fp2ProductCols<T, P>; 3]; 3],
+    /// `a1 * b2 + a2 * b1`.
+    pub(crate) c0_cross: [FieldOpCols<T, P>; 2],
+    pub(crate) c0_cross_mul_xi: Fp2NonResidueCols<T, P>,
+    pub(crate) c0: [FieldOpCols<T, P>; 2],
+    /// `a0 * b1 + a1 * b0`.
+    pub(crate) c1_cross: [FieldOpCols<T, P>; 2],
+    pub(crate) a2_b2_mul_xi: Fp2NonResidueCols<T, P>,
+    pub(crate) c1: [FieldOpCols<T, P>; 2],
+    /// `a0 * b2 + a1 * b1`.
+    pub(crate) c2_cross: [FieldOpCols<T, P>; 2],
+    pub(crate) c2: [FieldOpCols<T, P>; 2],
+}
+
+#[derive(Default)]
+pub struct Fp6MulAssignChip<P> {
+    _marker: PhantomData<P>,
+}
+
+type Fp2<T> = [T; 2];
+
+impl<P: FpOpField> Fp6MulAssignChip<P> {
+    pub const fn new() -> Self {
+        Self { _marker: PhantomData }
+    }
+
+    fn populate_fp2_product<F: PrimeField32>(
+        blu_events: &mut Vec<ByteLookupEvent>,
+        cols: &mut Fp2ProductCols<F, P>,
+        a: &Fp2<BigUint>,
+        b: &Fp2<BigUint>,
+        modulus: &BigUint,
+    ) -> Fp2<BigUint> {
+        let a0_mul_b0 = cols.a0_mul_b0.populate_with_modulus(
+            blu_events,
+            &a[0],
+            &b[0],
+            modulus,
+            FieldOperation::Mul,
+        );
+        let a1_mul_b1 = cols.a1_mul_b1.populate_with_modulus(
+            blu_events,
+            &a[1],
+            &b[1],
+            modulus,
+            FieldOperation::Mul,
+        );
+        let a0_mul_b1 = cols.a0_mul_b1.populate_with_modulus(
+            blu_events,
+            &a[0],
+            &b[1],
+            modulus,
+            FieldOperation::Mul,
+        );
+        let c0 = cols.c0.populate_with_modulus(
+            blu_events,
+            &a0_mul_b0,
+            &a1_mul_b1,
+            modulus,
+            FieldOperation::Sub,
+        );
+        let (c1, _) = cols.c1.populate_mul_and_carry(blu_events, &a[1], &b[0], &a0_mul_b1, modulus);
+        [c0, c1]
+    }
+
+    fn populate_fp2_add<F: PrimeField32>(
+        blu_events: &mut Vec<ByteLookupEvent>,
+        cols: &mut [FieldOpCols<F, P>; 2],
+        a: &Fp2<BigUint>,
+        b: &Fp2<BigUint>,
+        modulus: &BigUint,
+    ) -> Fp2<BigUint> {
+        let c0 =
+            cols[0].populate_with_modulus(blu_events, &a[0], &b[0], modulus, FieldOperation::Add);
+        let c1 =
+            cols[1].populate_with_modulus(blu_events, &a[1], &b[1], modulus, FieldOperation::Add);
+        [c0, c1]
+    }
+
+    fn populate_fp2_mul_by_non_residue<F: PrimeField32>(
+        blu_events: &mut Vec<ByteLookupEvent>,
+        cols: &mut Fp2NonResidueCols<F, P>,
+        a: &Fp2<BigUint>,
+        modulus: &BigUint,
+    ) -> Fp2<BigUint> {
+        let k = BigUint::from(P::FP6_NON_RESIDUE_C0);
+        let k_mul_a0 = cols.k_mul_a0.populate_with_modulus(
+            blu_events,
+            &k,
+            &a[0],
+            modulus,
+            FieldOperation::Mul,
+        );
+        let c0 = cols.c0.populate_with_modulus(
+            blu_events,
+            &k_mul_a0,
+            &a[1],
+            modulus,
+            FieldOperation::Sub,
+        );
+        let (c1, _) = cols.c1.populate_mul_and_carry(blu_events, &k, &a[1], &a[0], modulus);
+        [c0, c1]
+    }
+
+    fn populate_field_ops<F: PrimeField32>(
+        blu_events: &mut Vec<ByteLookupEvent>,
+        cols: &mut Fp6MulAssignCols<F, P>,
+        a: &[Fp2<BigUint>; 3],
+        b: &[Fp2<BigUint>; 3],
+    ) {
+        let modulus = BigUint::from_bytes_le(P::MODULUS);
+
+        let mut products: [[Fp2<BigUint>; 3]; 3] = Default::default();
+        for i in 0..3 {
+            for j in 0..3 {
+                products[i][j] = Self::populate_fp2_product(
+                    blu_events,
+                    &mut cols.products[i][j],
+                    &a[i],
+                    &b[j],
+                    &modulus,
+                );
+            }
+        }
+
+        // c0 = a0 * b0 + ξ * (a1 * b2 + a2 * b1)
+        let c0_cross = Self::populate_fp2_add(
+            blu_events,
+            &mut cols.c0_cross,
+            &products[1][2],
+            &products[2][1],
+            &modulus,
+        );
+        let c0_cross_mul_xi = Self::populate_fp2_mul_by_non_residue(
+            blu_events,
+            &mut cols.c0_cross_mul_xi,
+            &c0_cross,
+            &modulus,
+        );
+        Self::populate_fp2_add(
+            blu_events,
+            &mut cols.c0,
+            &products[0][0],
+            &c0_cross_mul_xi,
+            &modulus,
+        );
+
+        // c1 = a0 * b1 + a1 * b0 + ξ * a2 * b2
+        let c1_cross = Self::populate_fp2_add(
+            blu_events,
+            &mut cols.c1_cross,
+            &products[0][1],
+            &products[1][0],
+            &modulus,
+        );
+        let a2_b2_mul_xi = Self::populate_fp2_mul_by_non_residue(
+            blu_events,
+            &mut cols.a2_b2_mul_xi,
+            &products[2][2],
+            &modulus,
+        );
+        Self::populate_fp2_add(blu_events, &mut cols.c1, &c1_cross, &a2_b2_mul_xi, &modulus);
+
+        // c2 = a0 * b2 + a1 * b1 + a2 * b0
+        let c2_cross = Self::populate_fp2_add(
+            blu_events,
+            &mut cols.c2_cross,
+            &products[0][2],
+            &products[1][1],
+            &modulus,
+        );
+        Self::populate_fp2_add(blu_events, &mut cols.c2, &c2_cross, &products[2][0], &modulus);
+    }
+}
+
+impl<F: PrimeField32, P: FpOpField> MachineAir<F> for Fp6MulAssignChip<P> {
+    type Record = ExecutionRecord;
+
+    type Program = Program;
+
+    type Error = CoreChipError;
+
+    fn name(&self) -> String {
+        match P::FIELD_TYPE {
+            FieldType::Bn254 => "Bn254Fp6MulAssign".to_string(),
+            FieldType::Bls12381 => "Bls12381Fp6MulAssign".to_string(),
+        }
+    }
+
+    fn generate_trace(
+        &self,
+        input: &Self::Record,
+        output: &mut Self::Record,
+    ) -> Result<RowMajorMatrix<F>, Self::Error> {
+        let events = match P::FIELD_TYPE {
+            FieldType::Bn254 => input.get_precompile_events(SyscallCode::BN254_FP6_MUL),
+            FieldType::Bls12381 => input.get_precompile_events(SyscallCode::BLS12381_FP6_MUL),
+        };
+
+        let num_words_fp2 = <P as NumWords>::WordsCurvePoint::USIZE;
+        let num_words_fp = num_words_fp2 / 2;
+        let to_fp6 = |words: &[u32]| -> [Fp2<BigUint>; 3] {
+            core::array::from_fn(|i| {
+                let c = &words[i * num_words_fp2..(i + 1) * num_words_fp2];
+                [
+                    BigUint::from_bytes_le(&words_to_bytes_le_vec(&c[..num_words_fp])),
+                    BigUint::from_bytes_le(&words_to_bytes_le_vec(&c[num_words_fp..])),
+                ]
+            })
+        };
+
+        let mut rows = Vec::new();
+        let mut new_byte_lookup_events = Vec::new();
+
+        for (_, event) in events {
+            let event = match (P::FIELD_TYPE, event) {
+                (FieldType::Bn254, PrecompileEvent::Bn254Fp6Mul(event)) => event,
+                (FieldType::Bls12381, PrecompileEvent::Bls12381Fp6Mul(event)) => event,
+                _ => unreachable!(),
+            };
+
+            let mut row = zeroed_f_vec(num_fp6_mul_cols::<P>());
+            let cols: &mut Fp6MulAssignCols<F, P> = row.as_mut_slice().borrow_mut();
+
+            cols.is_real = F::ONE;
+            cols.shard = F::from_canonical_u32(event.shard);
+            cols.clk = F::from_canonical_u32(event.clk);
+            cols.x_ptr = F::from_canonical_u32(event.x_ptr);
+            cols.y_ptr = F::from_canonical_u32(event.y_ptr);
+
+            Self::populate_field_ops(
+                &mut new_byte_lookup_events,
+                cols,
+                &to_fp6(&event.x),
+                &to_fp6(&event.y),
+            );
+
+            // Populate the memory access columns.
+            for i in 0..3 {
+                for j in 0..num_words_fp2 {
+                    cols.y_access[i][j].populate(
+                        event.y_memory_records[i * num_words_fp2 + j],
+                        &mut new_byte_lookup_events,
+                    );
+                }
+            }
+            for i in 0..3 {
+                for j in 0..num_words_fp2 {
+                    cols.x_access[i][j].populate(
+                        event.x_memory_records[i * num_words_fp2 + j],
+                        &mut new_byte_lookup_events,
+                    );
+                }
+            }
+            rows.push(row);
+        }
+
+        output.add_byte_lookup_events(new_byte_lookup_events);
+
+        pad_rows_fixed(
+            &mut rows,
+            || {
+                let mut row = zeroed_f_vec(num_fp6_mul_cols::<P>());
+                let cols: &mut Fp6MulAssignCols<F, P> = row.as_mut_slice().borrow_mut();
+                let zero: [Fp2<BigUint>; 3] = Default::default();
+                Self::populate_field_ops(&mut vec![], cols, &zero, &zero);
+                row
+            },
+            input.fixed_log2_rows::<F, _>(self),
+            <Fp6MulAssignChip<P> as MachineAir<F>>::name(self).as_str(),
+        );
+
+        // Convert the trace to a row major matrix.
+        Ok(RowMajorMatrix::new(
+            rows.into_iter().flatten().collect::<Vec<_>>(),
+            num_fp6_mul_cols::<P>(),
+        ))
+    }
+
+    fn included(&self, shard: &Self::Record) -> bool {
+        if let Some(shape) = shard.shape.as_ref() {
+            shape.included::<F, _>(self)
+        } else {
+            match P::FIELD_TYPE {
+                FieldType::Bn254 => {
+                    !shard.get_precompile_events(SyscallCode::BN254_FP6_MUL).is_empty()
+                }
+                FieldType::Bls12381 => {
+                    !shard.get_precompile_events(SyscallCode::BLS12381_FP6_MUL).is_empty()
+                }
+            }
+        }
+    }
+
+    fn local_only(&self) -> bool {
+        true
+    }
+}
+
+impl<F, P: FpOpField> BaseAir<F> for Fp6MulAssignChip<P> {
+    fn width(&self) -> usize {
+        num_fp6_mul_cols::<P>()
+    }
+}
+
+impl<P: FpOpField> Fp6MulAssignChip<P> {
+    fn eval_fp2_product<AB>(
+        builder: &mut AB,
+        cols: &Fp2ProductCols<AB::Var, P>,
+        a: &Fp2<Limbs<AB::Var, <P as NumLimbs>::Limbs>>,
+        b: &Fp2<Limbs<AB::Var, <P as NumLimbs>::Limbs>>,
+        p_modulus: &Polynomial<AB::Expr>,
+        is_real: AB::Var,
+    ) -> Fp2<Limbs<AB::Var, <P as NumLimbs>::Limbs>>
+    where
+        AB: ZKMAirBuilder,
+        Limbs<AB::Var, <P as NumLimbs>::Limbs>: Copy,
+    {
+        cols.a0_mul_b0.eval_with_modulus(
+            builder,
+            &a[0],
+            &b[0],
+            p_modulus,
+            FieldOperation::Mul,
+            is_real,
+        );
+        cols.a1_mul_b1.eval_with_modulus(
+            builder,
+            &a[1],
+            &b[1],
+            p_modulus,
+            FieldOperation::Mul,
+            is_real,
+        );
+        cols.a0_mul_b1.eval_with_modulus(
+            builder,
+            &a[0],
+            &b[1],
+            p_modulus,
+            FieldOperation::Mul,
+            is_real,
+        );
+        cols.c0.eval_with_modulus(
+            builder,
+            &cols.a0_mul_b0.result,
+            &cols.a1_mul_b1.result,
+            p_modulus,
+            FieldOperation::Sub,
+            is_real,
+        );
+        cols.c1.eval_mul_and_carry(
+            builder,
+            &a[1],
+            &b[0],
+            &cols.a0_mul_b1.result,
+            p_modulus,
+            is_real,
+        );
+        [cols.c0.result, cols.c1.result]
+    }
+
+    fn eval_fp2_add<AB>(
+        builder: &mut AB,
+        cols: &[FieldOpCols<AB::Var, P>; 2],
+        a: &Fp2<Limbs<AB::Var, <P as NumLimbs>::Limbs>>,
+        b: &Fp2<Limbs<AB::Var, <P as NumLimbs>::Limbs>>,
+        p_modulus: &Polynomial<AB::Expr>,
+        is_real: AB::Var,
+    ) -> Fp2<Limbs<AB::Var, <P as NumLimbs>::Limbs>>
+    where
+        AB: ZKMAirBuilder,
+        Limbs<AB::Var, <P as NumLimbs>::Limbs>: Copy,
+    {
+        cols[0].eval_with_modulus(builder, &a[0], &b[0], p_modulus, FieldOperation::Add, is_real);
+        cols[1].eval_with_modulus(builder, &a[1], &b[1], p_modulus, FieldOperation::Add, is_real);
+        [cols[0].result, cols[1].result]
+    }
+
+    fn eval_fp2_mul_by_non_residue<AB>(
+        builder: &mut AB,
+        cols: &Fp2NonResidueCols<AB::Var, P>,
+        a: &Fp2<Limbs<AB::Var, <P as NumLimbs>::Limbs>>,
+        p_modulus: &Polynomial<AB::Expr>,
+        is_real: AB::Var,
+    ) -> Fp2<Limbs<AB::Var, <P as NumLimbs>::Limbs>>
+    where
+        AB: ZKMAirBuilder,
+        Limbs<AB::Var, <P as NumLimbs>::Limbs>: Copy,
+    {
+        // The constant `k` as a polynomial with the same number of limbs as a field element.
+        let mut k_coeffs = vec![AB::Expr::ZERO; <P as NumLimbs>::Limbs::USIZE];
+        k_coeffs[0] = AB::Expr::from_canonical_u32(P::FP6_NON_RESIDUE_C0);
+        let p_k = Polynomial::from_coefficients(&k_coeffs);
+
+        cols.k_mul_a0.eval_with_modulus(
+            builder,
+            &p_k,
+            &a[0],
+            p_modulus,
+            FieldOperation::Mul,
+            is_real,
+        );
+        cols.c0.eval_with_modulus(
+            builder,
+            &cols.k_mul_a0.result,
+            &a[1],
+            p_modulus,
+            FieldOperation::Sub,
+            is_real,
+        );
+        cols.c1.eval_mul_and_carry(builder, &p_k, &a[1], &a[0], p_modulus, is_real);
+        [cols.c0.result, cols.c1.result]
+    }
+}
+
+impl<AB, P: FpOpField> Air<AB> for Fp6MulAssignChip<P>
+where
+    AB: ZKMAirBuilder,
+    Limbs<AB::Var, <P as NumLimbs>::Limbs>: Copy,
+{
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let local = main.row_slice(0);
+        let local: &Fp6MulAssignCols<AB::Var, P> = (*local).borrow();
+
+        let num_words_field_element = <P as NumLimbs>::Limbs::USIZE / 4;
+        let a: [Fp2<Limbs<AB::Var, <P as NumLimbs>::Limbs>>; 3] = core::array::from_fn(|i| {
+            [
+                limbs_from_prev_access(&local.x_access[i][0..num_words_field_element]),
+                limbs_from_prev_access(&local.x_access[i][num_words_field_element..]),
+            ]
+        });
+        let b: [Fp2<Limbs<AB::Var, <P as NumLimbs>::Limbs>>; 3] = core::array::from_fn(|i| {
+            [
+                limbs_from_prev_access(&local.y_access[i][0..num_words_field_element]),
+                limbs_from_prev_access(&local.y_access[i][num_words_field_element..]),
+            ]
+        });
+
+        let modulus_coeffs =
+            P::MODULUS.iter().map(|&limbs| AB::Expr::from_canonical_u8(limbs)).collect_vec();
+        let p_modulus = Polynomial::from_coefficients(&modulus_coeffs);
+
+        let mut products = Vec::with_capacity(3);
+        for i in 0..3 {
+            let mut row = Vec::with_capacity(3);
+            for j in 0..3 {
+                row.push(Self::eval_fp2_product(
+                    builder,
+                    &local.products[i][j],
+                    &a[i],
+                    &b[j],
+                    &p_modulus,
+                    local.is_real,
+                ));
+            }
+            products.push(row);
+        }
+
+        // c0 = a0 * b0 + ξ * (a1 * b2 + a2 * b1)
+        let c0_cross = Self::eval_fp2_add(
+            builder,
+            &local.c0_cross,
+            &products[1][2],
+            &products[2][1],
+            &p_modulus,
+            local.is_real,
+        );
+        let c0_cross_mul_xi = Self::eval_fp2_mul_by_non_residue(
+            builder,
+            &local.c0_cross_mul_xi,
+            &c0_cross,
+            &p_modulus,
+            local.is_real,
+        );
+        let c0 = Self::eval_fp2_add(
+            builder,
+            &local.c0,
+            &products[0][0],
+            &c0_cross_mul_xi,
+            &p_modulus,
+            local.is_real,
+        );
+
+        // c1 = a0 * b1 + a1 * b0 + ξ * a2 * b2
+        let c1_cross = Self::eval_fp2_add(
+            builder,
+            &local.c1_cross,
+            &products[0][1],
+            &products[1][0],
+            &p_modulus,
+            local.is_real,
+        );
+        let a2_b2_mul_xi = Self::eval_fp2_mul_by_non_residue(
+            builder,
+            &local.a2_b2_mul_xi,
+            &products[2][2],
+            &p_modulus,
+            local.is_real,
+        );
+        let c1 = Self::eval_fp2_add(
+            builder,
+            &local.c1,
+            &c1_cross,
+            &a2_b2_mul_xi,
+            &p_modulus,
+            local.is_real,
+        );
+
+        // c2 = a0 * b2 + a1 * b1 + a2 * b0
+        let c2_cross = Self::eval_fp2_add(
+            builder,
+            &local.c2_cross,
+            &products[0][2],
+            &products[1][1],
+            &p_modulus,
+            local.is_real,
+        );
+        let c2 = Self::eval_fp2_add(
+            builder,
+            &local.c2,
+            &c2_cross,
+            &products[2][0],
+            &p_modulus,
+            local.is_real,
+        );
+
+        for (i, c) in [c0, c1, c2].iter().enumerate() {
+            builder.when(local.is_real).assert_all_eq(
+                c[0],
+                value_as_limbs(&local.x_access[i][0..num_words_field_element]),
+            );
+            builder
+                .when(local.is_real)
+                .assert_all_eq(c[1], value_as_limbs(&local.x_access[i][num_words_field_element..]));
+        }
+
+        let num_bytes_fp2 = 2 * <P as NumLimbs>::Limbs::USIZE;
+        for i in 0..3 {
+            builder.eval_memory_access_slice(
+                local.shard,
+                local.clk.into(),
+                local.y_ptr + AB::F::from_canonical_usize(i * num_bytes_fp2),
+                &local.y_access[i],
+                local.is_real,
+            );
+        }
+        for i in 0..3 {
+            builder.eval_memory_access_slice(
+                local.shard,
+                local.clk + AB::F::from_canonical_u32(1), /* We read x at +1 since x, y could be
+                                                           * the same. */
+                local.x_ptr + AB::F::from_canonical_usize(i * num_bytes_fp2),
+                &local.x_access[i],
+                local.is_real,
+            );
+        }
+
+        let syscall_id_felt = match P::FIELD_TYPE {
+            FieldType::Bn254 => AB::F::from_canonical_u32(SyscallCode::BN254_FP6_MUL.syscall_id()),
+            FieldType::Bls12381 => {
+                AB::F::from_canonical_u32(SyscallCode::BLS12381_FP6_MUL.syscall_id())
+            }
+        };
+
+        builder.receive_syscall(
+            local.shard,
+            local.clk,
+            syscall_id_felt,
+            local.x_ptr,
+            local.y_ptr,
+            local.is_real,
+            LookupScope::Local,
+        );
+    }
+}
@@ -1,18 +1,21 @@
 mod fp;
 mod fp2_addsub;
 mod fp2_mul;
+mod fp6_mul;
 
 pub use fp::*;
 pub use fp2_addsub::*;
 pub use fp2_mul::*;
+pub use fp6_mul::*;
 
 #[cfg(test)]
 mod tests {
     use zkm_stark::CpuProver;
 
     use test_artifacts::{
-        BLS12381_FP2_ADDSUB_ELF, BLS12381_FP2_MUL_ELF, BLS12381_FP_ELF, BN254_FP2_ADDSUB_ELF,
-        BN254_FP2_MUL_ELF, BN254_FP_ELF,
+        BLS12381_FP2_ADDSUB_ELF, BLS12381_FP2_MUL_ELF, BLS12381_FP6_MUL_ELF, BLS12381_FP_ELF,
+        BLS12381_PAIRING_ELF, BN254_FP2_ADDSUB_ELF, BN254_FP2_MUL_ELF, BN254_FP6_MUL_ELF,
+        BN254_FP_ELF, BN254_PAIRING_ELF,
     };
     use zkm_core_executor::Program;
 
@@ -39,6 +42,20 @@ mod tests {
         utils::run_test::<CpuProver<_, _>>(program).unwrap();
     }
 
+    #[test]
+    fn test_bls12381_fp6_mul() {
+        utils::setup_logger();
+        let program = Program::from(BLS12381_FP6_MUL_ELF).unwrap();
+        utils::run_test::<CpuProver<_, _>>(program).unwrap();
+    }
+
+    #[test]
+    fn test_bls12381_pairing() {
+        utils::setup_logger();
+        let program = Program::from(BLS12381_PAIRING_ELF).unwrap();
+        utils::run_test::<CpuProver<_, _>>(program).unwrap();
+    }
+
     #[test]
     fn test_bn254_fp_ops() {
         utils::setup_logger();
@@ -59,4 +76,18 @@ mod tests {
         let program = Program::from(BN254_FP2_MUL_ELF).unwrap();
         utils::run_test::<CpuProver<_, _>>(program).unwrap();
     }
+
+    #[test]
+    fn test_bn254_fp6_mul() {
+        utils::setup_logger();
+        let program = Program::from(BN254_FP6_MUL_ELF).unwrap();
+        utils::run_test::<CpuProver<_, _>>(program).unwrap();
+    }
+
+    #[test]
+    fn test_bn254_pairing() {
+        utils::setup_logger();
+        let program = Program::from(BN254_PAIRING_ELF).unwrap();
+        utils::run_test::<CpuProver<_, _>>(program).unwrap();
+    }
 }
@@ -1,9 +1,13 @@
+pub mod blake2b;
+pub mod blake3;
 pub mod boolean_circuit_garble;
 pub mod edwards;
 pub mod fptower;
 pub mod keccak_sponge;
 pub mod poseidon2;
 pub mod sha256;
+pub mod sha512;
+pub mod sys_file;
 pub mod sys_linux;
 pub mod u256x2048_mul;
 pub mod uint256;
@@ -0,0 +1,294 @@
+use std::borrow::BorrowMut;
+
+use hashbrown::HashMap;
+use itertools::Itertools;
+use p3_field::PrimeField32;
+use p3_matrix::dense::RowMajorMatrix;
+use p3_maybe_rayon::prelude::{ParallelIterator, ParallelSlice};
+use zkm_core_executor::{
+    events::{ByteLookupEvent, ByteRecord, PrecompileEvent, Sha512CompressEvent},
+    syscalls::SyscallCode,
+    ExecutionRecord, Program,
+};
+use zkm_stark::air::MachineAir;
+
+use super::{
+    columns::{Sha512CompressCols, NUM_SHA512_COMPRESS_COLS},
+    Sha512CompressChip, SHA512_COMPRESS_K,
+};
+use crate::{
+    syscall::precompiles::sha512::{
+        populate_and_u64, populate_not_u64, populate_xor_u64, u64_to_words,
+    },
+    utils::pad_rows_fixed,
+    CoreChipError,
+};
+
+impl<F: PrimeField32> MachineAir<F> for Sha512CompressChip {
+    type Record = ExecutionRecord;
+
+    type Program = Program;
+
+    type Error = CoreChipError;
+
+    fn name(&self) -> String {
+        "Sha512Compress".to_string()
+    }
+
+    fn generate_trace(
+        &self,
+        input: &ExecutionRecord,
+        _: &mut ExecutionRecord,
+    ) -> Result<RowMajorMatrix<F>, Self::Error> {
+        let rows = Vec::new();
+
+        let mut wrapped_rows = Some(rows);
+        for (_, event) in input.get_precompile_events(SyscallCode::SHA512_COMPRESS) {
+            let event = if let PrecompileEvent::Sha512Compress(event) = event {
+                event
+            } else {
+                unreachable!()
+            };
+            self.event_to_rows(event, &mut wrapped_rows, &mut Vec::new());
+        }
+        let mut rows = wrapped_rows.unwrap();
+
+        let num_real_rows = rows.len();
+
+        pad_rows_fixed(
+            &mut rows,
+            || [F::ZERO; NUM_SHA512_COMPRESS_COLS],
+            input.fixed_log2_rows::<F, _>(self),
+            <Sha512CompressChip as MachineAir<F>>::name(self).as_str(),
+        );
+
+        // Set the octet_num and octet columns for the padded rows.
+        let mut octet_num = 0;
+        let mut octet = 0;
+        for row in rows[num_real_rows..].iter_mut() {
+            let cols: &mut Sha512CompressCols<F> = row.as_mut_slice().borrow_mut();
+            cols.octet_num[octet_num] = F::ONE;
+            cols.octet[octet] = F::ONE;
+
+            // If in the compression phase, set the k value.
+            if octet_num != 0 && octet_num != 11 {
+                let compression_idx = octet_num - 1;
+                let k_idx = compression_idx * 8 + octet;
+                cols.k = u64_to_words(SHA512_COMPRESS_K[k_idx]);
+            }
+
+            octet = (octet + 1) % 8;
+            if octet == 0 {
+                octet_num = (octet_num + 1) % 12;
+            }
+
+            cols.is_last_row = cols.octet[7] * cols.octet_num[11];
+        }
+
+        // Convert the trace to a row major matrix.
+        Ok(RowMajorMatrix::new(
+            rows.into_iter().flatten().collect::<Vec<_>>(),
+            NUM_SHA512_COMPRESS_COLS,
+        ))
+    }
+
+    fn generate_dependencies(
+        &self,
+        input: &Self::Record,
+        output: &mut Self::Record,
+    ) -> Result<(), Self::Error> {
+        let events = input.get_precompile_events(SyscallCode::SHA512_COMPRESS);
+        let chunk_size = std::cmp::max(events.len() / num_cpus::get(), 1);
+
+        let blu_batches = events
+            .par_chunks(chunk_size)
+            .map(|events| {
+                let mut blu: HashMap<ByteLookupEvent, usize> = HashMap::new();
+                events.iter().for_each(|(_, event)| {
+                    let event = if let PrecompileEvent::Sha512Compress(event) = event {
+                        event
+                    } else {
+                        unreachable!()
+                    };
+                    self.event_to_rows::<F>(event, &mut None, &mut blu);
+                });
+                blu
+            })
+            .collect::<Vec<_>>();
+
+        output.add_byte_lookup_events_from_maps(blu_batches.iter().collect_vec());
+        Ok(())
+    }
+
+    fn included(&self, shard: &Self::Record) -> bool {
+        if let Some(shape) = shard.shape.as_ref() {
+            shape.included::<F, _>(self)
+        } else {
+            !shard.get_precompile_events(SyscallCode::SHA512_COMPRESS).is_empty()
+        }
+    }
+}
+
+impl Sha512CompressChip {
+    fn event_to_rows<F: PrimeField32>(
+        &self,
+        event: &Sha512CompressEvent,
+        rows: &mut Option<Vec<[F; NUM_SHA512_COMPRESS_COLS]>>,
+        blu: &mut impl ByteRecord,
+    ) {
+        let og_h = event.h;
+
+        let mut octet_num_idx = 0;
+
+        // Load a, b, c, d, e, f, g, h.
+        for j in 0..8usize {
+            let mut row = [F::ZERO; NUM_SHA512_COMPRESS_COLS];
+            let cols: &mut Sha512CompressCols<F> = row.as_mut_slice().borrow_mut();
+
+            cols.shard = F::from_canonical_u32(event.shard);
+            cols.clk = F::from_canonical_u32(event.clk);
+            cols.w_ptr = F::from_canonical_u32(event.w_ptr);
+            cols.h_ptr = F::from_canonical_u32(event.h_ptr);
+
+            cols.octet[j] = F::ONE;
+            cols.octet_num[octet_num_idx] = F::ONE;
+            cols.is_initialize = F::ONE;
+
+            for k in 0..2 {
+                cols.mem[k].populate_read(event.h_read_records[2 * j + k], blu);
+            }
+            cols.mem_addr = F::from_canonical_u32(event.h_ptr + (j * 8) as u32);
+
+            cols.a = u64_to_words(og_h[0]);
+            cols.b = u64_to_words(og_h[1]);
+            cols.c = u64_to_words(og_h[2]);
+            cols.d = u64_to_words(og_h[3]);
+            cols.e = u64_to_words(og_h[4]);
+            cols.f = u64_to_words(og_h[5]);
+            cols.g = u64_to_words(og_h[6]);
+            cols.h = u64_to_words(og_h[7]);
+
+            cols.is_real = F::ONE;
+            cols.start = cols.is_real * cols.octet_num[0] * cols.octet[0];
+            if rows.as_ref().is_some() {
+                rows.as_mut().unwrap().push(row);
+            }
+        }
+
+        // Performs the compress operation.
+        let mut h_array = event.h;
+        for j in 0..80 {
+            if j.is_multiple_of(8) {
+                octet_num_idx += 1;
+            }
+            let mut row = [F::ZERO; NUM_SHA512_COMPRESS_COLS];
+            let cols: &mut Sha512CompressCols<F> = row.as_mut_slice().borrow_mut();
+
+            cols.k = u64_to_words(SHA512_COMPRESS_K[j]);
+            cols.is_compression = F::ONE;
+            cols.octet[j % 8] = F::ONE;
+            cols.octet_num[octet_num_idx] = F::ONE;
+
+            cols.shard = F::from_canonical_u32(event.shard);
+            cols.clk = F::from_canonical_u32(event.clk);
+            cols.w_ptr = F::from_canonical_u32(event.w_ptr);
+            cols.h_ptr = F::from_canonical_u32(event.h_ptr);
+            for k in 0..2 {
+                cols.mem[k].populate_read(event.w_i_read_records[2 * j + k], blu);
+            }
+            cols.mem_addr = F::from_canonical_u32(event.w_ptr + (j * 8) as u32);
+
+            let [a, b, c, d, e, f, g, h] = h_array;
+            cols.a = u64_to_words(a);
+            cols.b = u64_to_words(b);
+            cols.c = u64_to_words(c);
+            cols.d = u64_to_words(d);
+            cols.e = u64_to_words(e);
+            cols.f = u64_to_words(f);
+            cols.g = u64_to_words(g);
+            cols.h = u64_to_words(h);
+
+            let e_rr_14 = cols.e_rr_14.populate(blu, e, 14);
+            let e_rr_18 = cols.e_rr_18.populate(blu, e, 18);
+            let e_rr_41 = cols.e_rr_41.populate(blu, e, 41);
+            let s1_intermediate =
+                populate_xor_u64(&mut cols.s1_intermediate, blu, e_rr_14, e_rr_18);
+            let s1 = populate_xor_u64(&mut cols.s1, blu, s1_intermediate, e_rr_41);
+
+            let e_and_f = populate_and_u64(&mut cols.e_and_f, blu, e, f);
+            let e_not = populate_not_u64(&mut cols.e_not, blu, e);
+            let e_not_and_g = populate_and_u64(&mut cols.e_not_and_g, blu, e_not, g);
+            let ch = populate_xor_u64(&mut cols.ch, blu, e_and_f, e_not_and_g);
+
+            let temp1 = cols.temp1.populate(blu, &[h, s1, ch, SHA512_COMPRESS_K[j], event.w[j]]);
+
+            let a_rr_28 = cols.a_rr_28.populate(blu, a, 28);
+            let a_rr_34 = cols.a_rr_34.populate(blu, a, 34);
+            let a_rr_39 = cols.a_rr_39.populate(blu, a, 39);
+            let s0_intermediate =
+                populate_xor_u64(&mut cols.s0_intermediate, blu, a_rr_28, a_rr_34);
+            let s0 = populate_xor_u64(&mut cols.s0, blu, s0_intermediate, a_rr_39);
+
+            let a_and_b = populate_and_u64(&mut cols.a_and_b, blu, a, b);
+            let a_and_c = populate_and_u64(&mut cols.a_and_c, blu, a, c);
+            let b_and_c = populate_and_u64(&mut cols.b_and_c, blu, b, c);
+            let maj_intermediate =
+                populate_xor_u64(&mut cols.maj_intermediate, blu, a_and_b, a_and_c);
+            let maj = populate_xor_u64(&mut cols.maj, blu, maj_intermediate, b_and_c);
+
+            let temp2 = cols.temp2.populate(blu, s0, maj);
+
+            let d_add_temp1 = cols.d_add_temp1.populate(blu, d, temp1);
+            let temp1_add_temp2 = cols.temp1_add_temp2.populate(blu, temp1, temp2);
+
+            h_array = [temp1_add_temp2, a, b, c, d_add_temp1, e, f, g];
+
+            cols.is_real = F::ONE;
+            cols.start = cols.is_real * cols.octet_num[0] * cols.octet[0];
+
+            if rows.as_ref().is_some() {
+                rows.as_mut().unwrap().push(row);
+            }
+        }
+
+        octet_num_idx += 1;
+        // Store a, b, c, d, e, f, g, h.
+        for j in 0..8usize {
+            let mut row = [F::ZERO; NUM_SHA512_COMPRESS_COLS];
+            let cols: &mut Sha512CompressCols<F> = row.as_mut_slice().borrow_mut();
+
+            cols.shard = F::from_canonical_u32(event.shard);
+            cols.clk = F::from_canonical_u32(event.clk);
+            cols.w_ptr = F::from_canonical_u32(event.w_ptr);
+            cols.h_ptr = F::from_canonical_u32(event.h_ptr);
+
+            cols.octet[j] = F::ONE;
+            cols.octet_num[octet_num_idx] = F::ONE;
+            cols.is_finalize = F::ONE;
+
+            cols.finalize_add.populate(blu, og_h[j], h_array[j]);
+            for k in 0..2 {
+                cols.mem[k].populate_write(event.h_write_records[2 * j + k], blu);
+            }
+            cols.mem_addr = F::from_canonical_u32(event.h_ptr + (j * 8) as u32);
+
+            cols.a = u64_to_words(h_array[0]);
+            cols.b = u64_to_words(h_array[1]);
+            cols.c = u64_to_words(h_array[2]);
+            cols.d = u64_to_words(h_array[3]);
+            cols.e = u64_to_words(h_array[4]);
+            cols.f = u64_to_words(h_array[5]);
+            cols.g = u64_to_words(h_array[6]);
+            cols.h = u64_to_words(h_array[7]);
+            cols.finalized_operand = u64_to_words(h_array[j]);
+
+            cols.is_real = F::ONE;
+            cols.is_last_row = cols.octet[7] * cols.octet_num[11];
+            cols.start = cols.is_real * cols.octet_num[0] * cols.octet[0];
+
+            if rows.as_ref().is_some() {
+                rows.as_mut().unwrap().push(row);
+            }
+        }
+    }
+}
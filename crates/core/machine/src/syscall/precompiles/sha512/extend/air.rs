@@ -0,0 +1,228 @@
+use p3_air::{Air, AirBuilder, BaseAir};
+use p3_field::FieldAlgebra;
+use p3_matrix::Matrix;
+use zkm_core_executor::syscalls::SyscallCode;
+use zkm_stark::air::{LookupScope, ZKMAirBuilder};
+
+use super::{Sha512ExtendChip, Sha512ExtendCols, NUM_SHA512_EXTEND_COLS};
+use crate::{
+    air::{MemoryAirBuilder, WordAirBuilder},
+    operations::{Add5U64Operation, FixedRotateRightU64Operation, FixedShiftRightU64Operation},
+    syscall::precompiles::sha512::{eval_xor_u64, u64_value},
+};
+
+use core::borrow::Borrow;
+use zkm_stark::air::BaseAirBuilder;
+
+impl<F> BaseAir<F> for Sha512ExtendChip {
+    fn width(&self) -> usize {
+        NUM_SHA512_EXTEND_COLS
+    }
+}
+
+impl<AB> Air<AB> for Sha512ExtendChip
+where
+    AB: ZKMAirBuilder,
+{
+    fn eval(&self, builder: &mut AB) {
+        // Initialize columns.
+        let main = builder.main();
+        let (local, next) = (main.row_slice(0), main.row_slice(1));
+        let local: &Sha512ExtendCols<AB::Var> = (*local).borrow();
+        let next: &Sha512ExtendCols<AB::Var> = (*next).borrow();
+
+        let i_start = AB::F::from_canonical_u32(16);
+        let nb_bytes_in_u64 = AB::F::from_canonical_u32(8);
+
+        // Evaluate the control flags.
+        self.eval_flags(builder);
+
+        // Copy over the inputs until the result has been computed (every 64 rows).
+        builder
+            .when_transition()
+            .when_not(local.cycle_64_end.result)
+            .assert_eq(local.shard, next.shard);
+        builder
+            .when_transition()
+            .when_not(local.cycle_64_end.result)
+            .assert_eq(local.clk, next.clk);
+        builder
+            .when_transition()
+            .when_not(local.cycle_64_end.result)
+            .assert_eq(local.w_ptr, next.w_ptr);
+
+        let clk = local.clk + (local.i - i_start);
+
+        // Read w[i-15].
+        builder.eval_memory_access_slice(
+            local.shard,
+            clk.clone(),
+            local.w_ptr + (local.i - AB::F::from_canonical_u32(15)) * nb_bytes_in_u64,
+            &local.w_i_minus_15,
+            local.is_real,
+        );
+
+        // Read w[i-2].
+        builder.eval_memory_access_slice(
+            local.shard,
+            clk.clone(),
+            local.w_ptr + (local.i - AB::F::from_canonical_u32(2)) * nb_bytes_in_u64,
+            &local.w_i_minus_2,
+            local.is_real,
+        );
+
+        // Read w[i-16].
+        builder.eval_memory_access_slice(
+            local.shard,
+            clk.clone(),
+            local.w_ptr + (local.i - AB::F::from_canonical_u32(16)) * nb_bytes_in_u64,
+            &local.w_i_minus_16,
+            local.is_real,
+        );
+
+        // Read w[i-7].
+        builder.eval_memory_access_slice(
+            local.shard,
+            clk.clone(),
+            local.w_ptr + (local.i - AB::F::from_canonical_u32(7)) * nb_bytes_in_u64,
+            &local.w_i_minus_7,
+            local.is_real,
+        );
+
+        // Compute `s0`.
+        let w_i_minus_15 = u64_value(&local.w_i_minus_15);
+        // w[i-15] rightrotate 1.
+        FixedRotateRightU64Operation::<AB::F>::eval(
+            builder,
+            w_i_minus_15,
+            1,
+            local.w_i_minus_15_rr_1,
+            local.is_real,
+        );
+        // w[i-15] rightrotate 8.
+        FixedRotateRightU64Operation::<AB::F>::eval(
+            builder,
+            w_i_minus_15,
+            8,
+            local.w_i_minus_15_rr_8,
+            local.is_real,
+        );
+        // w[i-15] rightshift 7.
+        FixedShiftRightU64Operation::<AB::F>::eval(
+            builder,
+            w_i_minus_15,
+            7,
+            local.w_i_minus_15_rs_7,
+            local.is_real.into(),
+        );
+        // (w[i-15] rightrotate 1) xor (w[i-15] rightrotate 8)
+        eval_xor_u64(
+            builder,
+            local.w_i_minus_15_rr_1.value,
+            local.w_i_minus_15_rr_8.value,
+            local.s0_intermediate,
+            local.is_real,
+        );
+        // s0 := (w[i-15] rightrotate 1) xor (w[i-15] rightrotate 8) xor (w[i-15] rightshift 7)
+        eval_xor_u64(
+            builder,
+            local.s0_intermediate.map(|op| op.value),
+            local.w_i_minus_15_rs_7.value,
+            local.s0,
+            local.is_real,
+        );
+
+        // Compute `s1`.
+        let w_i_minus_2 = u64_value(&local.w_i_minus_2);
+        // w[i-2] rightrotate 19.
+        FixedRotateRightU64Operation::<AB::F>::eval(
+            builder,
+            w_i_minus_2,
+            19,
+            local.w_i_minus_2_rr_19,
+            local.is_real,
+        );
+        // w[i-2] rightrotate 61.
+        FixedRotateRightU64Operation::<AB::F>::eval(
+            builder,
+            w_i_minus_2,
+            61,
+            local.w_i_minus_2_rr_61,
+            local.is_real,
+        );
+        // w[i-2] rightshift 6.
+        FixedShiftRightU64Operation::<AB::F>::eval(
+            builder,
+            w_i_minus_2,
+            6,
+            local.w_i_minus_2_rs_6,
+            local.is_real.into(),
+        );
+        // (w[i-2] rightrotate 19) xor (w[i-2] rightrotate 61)
+        eval_xor_u64(
+            builder,
+            local.w_i_minus_2_rr_19.value,
+            local.w_i_minus_2_rr_61.value,
+            local.s1_intermediate,
+            local.is_real,
+        );
+        // s1 := (w[i-2] rightrotate 19) xor (w[i-2] rightrotate 61) xor (w[i-2] rightshift 6)
+        eval_xor_u64(
+            builder,
+            local.s1_intermediate.map(|op| op.value),
+            local.w_i_minus_2_rs_6.value,
+            local.s1,
+            local.is_real,
+        );
+
+        // s2 := w[i-16] + s0 + w[i-7] + s1.
+        Add5U64Operation::<AB::F>::eval(
+            builder,
+            &[
+                u64_value(&local.w_i_minus_16),
+                local.s0.map(|op| op.value),
+                u64_value(&local.w_i_minus_7),
+                local.s1.map(|op| op.value),
+            ],
+            local.is_real,
+            local.s2,
+        );
+
+        // Write `s2` to `w[i]`.
+        builder.eval_memory_access_slice(
+            local.shard,
+            clk,
+            local.w_ptr + local.i * nb_bytes_in_u64,
+            &local.w_i,
+            local.is_real,
+        );
+
+        let w_i = u64_value(&local.w_i);
+        builder.assert_word_eq(w_i[0], local.s2.value[0]);
+        builder.assert_word_eq(w_i[1], local.s2.value[1]);
+
+        // Receive syscall event in first row of 64-cycle.
+        builder.receive_syscall(
+            local.shard,
+            local.clk,
+            AB::F::from_canonical_u32(SyscallCode::SHA512_EXTEND.syscall_id()),
+            local.w_ptr,
+            AB::Expr::zero(),
+            local.start,
+            LookupScope::Local,
+        );
+
+        // Assert that is_real is a bool.
+        builder.assert_bool(local.is_real);
+
+        // Ensure that all rows in a 64 row cycle has the same `is_real` values.
+        builder
+            .when_transition()
+            .when_not(local.cycle_64_end.result * local.is_real)
+            .assert_eq(local.is_real, next.is_real);
+
+        // Since each extend syscall is 64 cycles, a padded table may end with a real row. In that
+        // case, the last row must close a 64-row cycle.
+        builder.when_last_row().when(local.is_real).assert_one(local.cycle_64_end.result);
+    }
+}
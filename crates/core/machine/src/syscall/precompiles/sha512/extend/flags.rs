@@ -0,0 +1,83 @@
+use core::borrow::Borrow;
+use p3_air::AirBuilder;
+use p3_field::{Field, FieldAlgebra, PrimeField32, TwoAdicField};
+use p3_koala_bear::KoalaBear;
+use p3_matrix::Matrix;
+use zkm_stark::air::{BaseAirBuilder, ZKMAirBuilder};
+
+use crate::operations::IsZeroOperation;
+
+use super::{Sha512ExtendChip, Sha512ExtendCols};
+
+impl<F: Field> Sha512ExtendCols<F> {
+    pub fn populate_flags(&mut self, i: usize) {
+        // The generator of the multiplicative subgroup.
+        let g = F::from_canonical_u32(KoalaBear::two_adic_generator(6).as_canonical_u32());
+
+        // Populate the columns needed to keep track of cycles of 64 rows.
+        self.cycle_64 = g.exp_u64((i + 1) as u64);
+
+        // Populate the columns needed to track the start of a cycle of 64 rows.
+        self.cycle_64_start.populate_from_field_element(self.cycle_64 - g);
+
+        // Populate the columns needed to track the end of a cycle of 64 rows.
+        self.cycle_64_end.populate_from_field_element(self.cycle_64 - F::ONE);
+
+        self.i = F::from_canonical_usize(16 + (i % 64));
+        self.start = self.cycle_64_start.result * self.is_real;
+    }
+}
+
+impl Sha512ExtendChip {
+    pub fn eval_flags<AB: ZKMAirBuilder>(&self, builder: &mut AB) {
+        let main = builder.main();
+        let (local, next) = (main.row_slice(0), main.row_slice(1));
+        let local: &Sha512ExtendCols<AB::Var> = (*local).borrow();
+        let next: &Sha512ExtendCols<AB::Var> = (*next).borrow();
+
+        let one = AB::Expr::from(AB::F::ONE);
+
+        // Generator with order 64 within KoalaBear.
+        let g = AB::F::from_canonical_u32(KoalaBear::two_adic_generator(6).as_canonical_u32());
+
+        // First row of the table must have g^1.
+        builder.when_first_row().assert_eq(local.cycle_64, g);
+
+        // First row of the table must have i = 16.
+        builder.when_first_row().assert_eq(local.i, AB::F::from_canonical_u32(16));
+
+        // Every row's `cycle_64` must be previous multiplied by `g`.
+        builder.when_transition().assert_eq(local.cycle_64 * g, next.cycle_64);
+
+        // Constrain `cycle_64_start.result` to be `cycle_64 - g == 0`.
+        IsZeroOperation::<AB::F>::eval(
+            builder,
+            local.cycle_64 - AB::Expr::from(g),
+            local.cycle_64_start,
+            one.clone(),
+        );
+
+        // Constrain `cycle_64_end.result` to be `cycle_64 - 1 == 0`. Intuitively g^64 is 1.
+        IsZeroOperation::<AB::F>::eval(
+            builder,
+            local.cycle_64 - AB::Expr::one(),
+            local.cycle_64_end,
+            one.clone(),
+        );
+
+        // start == start of 64-cycle AND is_real.
+        builder.assert_eq(local.cycle_64_start.result * local.is_real, local.start);
+
+        // When it's the end of a 64-cycle, the next `i` must be 16.
+        builder
+            .when_transition()
+            .when(local.cycle_64_end.result)
+            .assert_eq(next.i, AB::F::from_canonical_u32(16));
+
+        // When it's not the end of a 64-cycle, the next `i` must be the current plus one.
+        builder
+            .when_transition()
+            .when_not(local.cycle_64_end.result)
+            .assert_eq(local.i + one, next.i);
+    }
+}
@@ -0,0 +1,91 @@
+mod air;
+mod columns;
+mod flags;
+mod trace;
+
+pub use columns::*;
+
+/// Implements the SHA-512 extension operation which loops over i = [16, 79] and modifies w[i] in
+/// each iteration. The only input to the syscall is the 4byte-aligned pointer to the w array.
+///
+/// In the AIR, each SHA-512 extend syscall takes up 64 rows, where each row corresponds to a single
+/// iteration of the loop.
+#[derive(Default)]
+pub struct Sha512ExtendChip;
+
+impl Sha512ExtendChip {
+    pub const fn new() -> Self {
+        Self {}
+    }
+}
+
+pub fn sha512_extend(w: &mut [u64]) {
+    for i in 16..80 {
+        let s0 = w[i - 15].rotate_right(1) ^ w[i - 15].rotate_right(8) ^ (w[i - 15] >> 7);
+        let s1 = w[i - 2].rotate_right(19) ^ w[i - 2].rotate_right(61) ^ (w[i - 2] >> 6);
+        w[i] = w[i - 16].wrapping_add(s0).wrapping_add(w[i - 7]).wrapping_add(s1);
+    }
+}
+
+#[cfg(test)]
+pub mod extend_tests {
+    use p3_koala_bear::KoalaBear;
+
+    use p3_matrix::dense::RowMajorMatrix;
+    use zkm_core_executor::{
+        syscalls::SyscallCode, ExecutionRecord, Executor, Instruction, Opcode, Program,
+    };
+    use zkm_stark::{air::MachineAir, CpuProver, ZKMCoreOpts};
+
+    use crate::utils::{self, run_test};
+
+    use super::{sha512_extend, Sha512ExtendChip};
+
+    pub fn sha512_extend_program() -> Program {
+        let w_ptr = 100;
+        let mut instructions = vec![Instruction::new(Opcode::ADD, 29, 0, 5, false, true)];
+        for i in 0..160 {
+            instructions.extend(vec![
+                Instruction::new(Opcode::ADD, 30, 0, w_ptr + i * 4, false, true),
+                Instruction::new(Opcode::SW, 29, 30, 0, false, true),
+            ]);
+        }
+        instructions.extend(vec![
+            Instruction::new(Opcode::ADD, 2, 0, SyscallCode::SHA512_EXTEND as u32, false, true),
+            Instruction::new(Opcode::ADD, 4, 0, w_ptr, false, true),
+            Instruction::new(Opcode::ADD, 5, 0, 0, false, true),
+            Instruction::new(Opcode::SYSCALL, 2, 4, 5, false, false),
+        ]);
+        Program::new(instructions, 0, 0)
+    }
+
+    #[test]
+    fn generate_trace() {
+        let shard = ExecutionRecord::default();
+        let chip = Sha512ExtendChip::new();
+        let trace: RowMajorMatrix<KoalaBear> =
+            chip.generate_trace(&shard, &mut ExecutionRecord::default()).unwrap();
+        println!("{:?}", trace.values)
+    }
+
+    #[test]
+    fn test_sha512_extend_execute() {
+        let mut runtime = Executor::new(sha512_extend_program(), ZKMCoreOpts::default());
+        runtime.run_fast().unwrap();
+
+        let mut w = [0x0000_0005_0000_0005u64; 80];
+        sha512_extend(&mut w);
+        for (i, expected) in w.iter().enumerate() {
+            let addr = 100 + 8 * i as u32;
+            let value = u64::from(runtime.word(addr)) | (u64::from(runtime.word(addr + 4)) << 32);
+            assert_eq!(value, *expected);
+        }
+    }
+
+    #[test]
+    fn test_sha512_extend_prove() {
+        utils::setup_logger();
+        let program = sha512_extend_program();
+        run_test::<CpuProver<_, _>>(program).unwrap();
+    }
+}
@@ -0,0 +1,109 @@
+//! The SHA-512 precompiles.
+//!
+//! SHA-512 operates on 64-bit words, which the guest stores in memory as two little-endian 32-bit
+//! words, low word first. In the AIR, a 64-bit word is represented as `[Word<T>; 2]` in the same
+//! order, and the bitwise operations are computed by the 32-bit operations over each half.
+
+mod compress;
+mod extend;
+
+pub use compress::*;
+pub use extend::*;
+
+use p3_field::{Field, PrimeField32};
+use zkm_core_executor::events::{ByteRecord, MemoryReadRecord};
+use zkm_stark::{air::ZKMAirBuilder, Word};
+
+use crate::{
+    memory::MemoryCols,
+    operations::{AndOperation, NotOperation, XorOperation},
+};
+
+/// Splits a 64-bit word into its low and high 32-bit halves.
+pub(crate) const fn split_u64(value: u64) -> [u32; 2] {
+    [value as u32, (value >> 32) as u32]
+}
+
+/// Joins the low and high 32-bit halves of a 64-bit word.
+pub(crate) const fn join_u64([lo, hi]: [u32; 2]) -> u64 {
+    lo as u64 | ((hi as u64) << 32)
+}
+
+/// The 64-bit word read by a pair of memory reads.
+const fn read_u64(records: &[MemoryReadRecord; 2]) -> u64 {
+    join_u64([records[0].value, records[1].value])
+}
+
+/// Converts a 64-bit word into its column representation.
+fn u64_to_words<F: PrimeField32>(value: u64) -> [Word<F>; 2] {
+    split_u64(value).map(Word::from)
+}
+
+/// The 64-bit word held by a pair of memory columns.
+pub(crate) fn u64_value<T: Copy>(cols: &[impl MemoryCols<T>; 2]) -> [Word<T>; 2] {
+    [*cols[0].value(), *cols[1].value()]
+}
+
+pub(crate) fn populate_xor_u64<F: Field>(
+    cols: &mut [XorOperation<F>; 2],
+    record: &mut impl ByteRecord,
+    x: u64,
+    y: u64,
+) -> u64 {
+    let ([x_lo, x_hi], [y_lo, y_hi]) = (split_u64(x), split_u64(y));
+    join_u64([cols[0].populate(record, x_lo, y_lo), cols[1].populate(record, x_hi, y_hi)])
+}
+
+fn populate_and_u64<F: Field>(
+    cols: &mut [AndOperation<F>; 2],
+    record: &mut impl ByteRecord,
+    x: u64,
+    y: u64,
+) -> u64 {
+    let ([x_lo, x_hi], [y_lo, y_hi]) = (split_u64(x), split_u64(y));
+    join_u64([cols[0].populate(record, x_lo, y_lo), cols[1].populate(record, x_hi, y_hi)])
+}
+
+fn populate_not_u64<F: Field>(
+    cols: &mut [NotOperation<F>; 2],
+    record: &mut impl ByteRecord,
+    x: u64,
+) -> u64 {
+    let [x_lo, x_hi] = split_u64(x);
+    join_u64([cols[0].populate(record, x_lo), cols[1].populate(record, x_hi)])
+}
+
+pub(crate) fn eval_xor_u64<AB: ZKMAirBuilder>(
+    builder: &mut AB,
+    a: [Word<AB::Var>; 2],
+    b: [Word<AB::Var>; 2],
+    cols: [XorOperation<AB::Var>; 2],
+    is_real: AB::Var,
+) {
+    for k in 0..2 {
+        XorOperation::<AB::F>::eval(builder, a[k], b[k], cols[k], is_real);
+    }
+}
+
+fn eval_and_u64<AB: ZKMAirBuilder>(
+    builder: &mut AB,
+    a: [Word<AB::Var>; 2],
+    b: [Word<AB::Var>; 2],
+    cols: [AndOperation<AB::Var>; 2],
+    is_real: AB::Var,
+) {
+    for k in 0..2 {
+        AndOperation::<AB::F>::eval(builder, a[k], b[k], cols[k], is_real);
+    }
+}
+
+fn eval_not_u64<AB: ZKMAirBuilder>(
+    builder: &mut AB,
+    a: [Word<AB::Var>; 2],
+    cols: [NotOperation<AB::Var>; 2],
+    is_real: AB::Var,
+) {
+    for k in 0..2 {
+        NotOperation::<AB::F>::eval(builder, a[k], cols[k], is_real);
+    }
+}
@@ -0,0 +1,848 @@
+use core::borrow::Borrow;
+
+use p3_air::{Air, AirBuilder, BaseAir};
+use p3_field::FieldAlgebra;
+use p3_matrix::Matrix;
+use zkm_core_executor::{
+    syscalls::SyscallCode, ByteOpcode, Register, AT_FDCWD, FIRST_VIRTUAL_FD, MIPS_EBADF,
+    MIPS_EMFILE, MIPS_ENOENT, MIPS_ENOTDIR, MIPS_EROFS, O_ACCMODE, STAT64_SIZE, VFS_BASE,
+    VFS_ENTRIES, VFS_FDS, VFS_MAX_FDS, VFS_MAX_READ,
+};
+use zkm_primitives::consts::fd::FD_STDERR;
+use zkm_stark::{
+    air::{BaseAirBuilder, LookupScope, ZKMAirBuilder},
+    Word,
+};
+
+use super::{
+    columns::{SysFileCols, NUM_SYS_FILE_COLS},
+    SysFileChip,
+};
+use crate::{
+    air::{MemoryAirBuilder, WordAirBuilder},
+    memory::MemoryCols,
+    operations::{AddOperation, IsZeroOperation},
+};
+
+impl<F> BaseAir<F> for SysFileChip {
+    fn width(&self) -> usize {
+        NUM_SYS_FILE_COLS
+    }
+}
+
+impl<AB> Air<AB> for SysFileChip
+where
+    AB: ZKMAirBuilder,
+{
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let (local, next) = (main.row_slice(0), main.row_slice(1));
+        let local: &SysFileCols<AB::Var> = (*local).borrow();
+        let next: &SysFileCols<AB::Var> = (*next).borrow();
+
+        self.eval_rows(builder, local, next);
+        self.eval_header(builder, local);
+        self.eval_fd(builder, local);
+        self.eval_read(builder, local);
+        self.eval_open(builder, local);
+        self.eval_streams(builder, local, next);
+        self.eval_path(builder, local, next);
+        self.eval_slot(builder, local, next);
+        self.eval_copy(builder, local, next);
+        self.eval_memory(builder, local, next);
+
+        // ── Cross-chip interactions ────────────────────────────────────
+        builder.receive_syscall(
+            local.shard,
+            local.clk,
+            local.syscall_id,
+            local.a0.reduce::<AB>(),
+            local.a1.reduce::<AB>(),
+            local.is_header,
+            LookupScope::Local,
+        );
+        builder.receive_syscall_result(
+            local.shard,
+            local.clk,
+            local.result,
+            local.a0,
+            local.a1,
+            local.is_header,
+            LookupScope::Local,
+        );
+    }
+}
+
+impl SysFileChip {
+    /// The rows following a header are fixed by the header, and carry its constants.
+    fn eval_rows<AB: ZKMAirBuilder>(
+        &self,
+        builder: &mut AB,
+        local: &SysFileCols<AB::Var>,
+        next: &SysFileCols<AB::Var>,
+    ) {
+        builder.assert_bool(local.is_header);
+        builder.assert_bool(local.is_path);
+        builder.assert_bool(local.is_slot);
+        builder.assert_bool(local.is_copy);
+        builder.assert_bool(local.first);
+        builder.assert_bool(local.is_header + local.is_path + local.is_slot + local.is_copy);
+
+        let path_next: AB::Expr = local.is_open + local.is_openat + local.is_path * local.path_more;
+        let slot_next: AB::Expr = local.is_path * (AB::Expr::one() - local.path_more) * local.alloc
+            + local.is_slot
+                * (AB::Expr::one() - local.slot_target.result)
+                * (AB::Expr::one() - local.slot_last.result);
+        let copy_next: AB::Expr = local.is_stat_copy
+            + local.is_read_ok * (AB::Expr::one() - local.len_zero.result)
+            + local.is_copy * (AB::Expr::one() - local.copy_last.result);
+
+        builder.when_first_row().assert_zero(local.is_path + local.is_slot + local.is_copy);
+        builder.when_transition().assert_eq(next.is_path, path_next.clone());
+        builder.when_transition().assert_eq(next.is_slot, slot_next.clone());
+        builder.when_transition().assert_eq(next.is_copy, copy_next.clone());
+        builder.assert_eq(local.more, path_next + slot_next + copy_next);
+        builder.when_last_row().assert_zero(local.more);
+
+        // The path and copy rows follow the header, and the slot rows follow the path rows.
+        builder
+            .when_transition()
+            .when(next.is_path + next.is_copy)
+            .assert_eq(next.first, local.is_header);
+        builder.when_transition().when(next.is_slot).assert_eq(next.first, local.is_path);
+
+        let mut transition = builder.when_transition();
+        let mut when_event = transition.when(local.more);
+        when_event.assert_eq(next.shard, local.shard);
+        when_event.assert_eq(next.clk, local.clk);
+        when_event.assert_eq(next.found, local.found);
+        when_event.assert_eq(next.relative, local.relative);
+        when_event.assert_eq(next.alloc, local.alloc);
+        when_event.assert_eq(next.index, local.index);
+        when_event.assert_eq(next.slot_k, local.slot_k);
+        when_event.assert_eq(next.len, local.len);
+    }
+
+    /// The syscall, its outcome and its errno.
+    fn eval_header<AB: ZKMAirBuilder>(&self, builder: &mut AB, local: &SysFileCols<AB::Var>) {
+        let ops = [
+            (local.is_open, SyscallCode::SYS_OPEN),
+            (local.is_openat, SyscallCode::SYS_OPENAT),
+            (local.is_close, SyscallCode::SYS_CLOSE),
+            (local.is_fstat64, SyscallCode::SYS_FSTAT64),
+            (local.is_read, SyscallCode::SYS_READ),
+        ];
+        let mut op_sum = AB::Expr::zero();
+        let mut syscall_id = AB::Expr::zero();
+        for (flag, code) in ops {
+            builder.assert_bool(flag);
+            op_sum = op_sum + flag;
+            syscall_id = syscall_id + flag * AB::F::from_canonical_u32(code.syscall_id());
+        }
+        builder.assert_eq(op_sum, local.is_header);
+        builder.assert_eq(local.syscall_id, syscall_id);
+
+        let outcomes = [
+            (local.is_ok, 0),
+            (local.is_ebadf, MIPS_EBADF),
+            (local.is_enoent, MIPS_ENOENT),
+            (local.is_enotdir, MIPS_ENOTDIR),
+            (local.is_erofs, MIPS_EROFS),
+            (local.is_emfile, MIPS_EMFILE),
+        ];
+        let mut outcome_sum = AB::Expr::zero();
+        let mut errno = AB::Expr::zero();
+        for (flag, code) in outcomes {
+            builder.assert_bool(flag);
+            outcome_sum = outcome_sum + flag;
+            errno = errno + flag * AB::F::from_canonical_u32(code);
+        }
+        builder.assert_eq(outcome_sum, local.is_header);
+
+        builder.slice_range_check_u8(&local.a0.0, local.is_header);
+        builder.slice_range_check_u8(&local.a1.0, local.is_header);
+        builder.slice_range_check_u8(&local.result.0, local.is_header);
+
+        builder.when(local.is_header).assert_word_eq(
+            *local.mem[0].value(),
+            Word([errno, AB::Expr::zero(), AB::Expr::zero(), AB::Expr::zero()]),
+        );
+        builder
+            .when(local.is_header - local.is_ok)
+            .assert_word_eq(local.result, Word::<AB::Expr>::from(0xFFFFFFFFu32));
+        builder
+            .when(local.is_ok)
+            .when(local.is_close + local.is_fstat64)
+            .assert_word_zero(local.result);
+        builder.when(local.is_ok).when(local.is_open + local.is_openat).assert_word_eq(
+            local.result,
+            Word([
+                local.slot_k + AB::F::from_canonical_u32(FIRST_VIRTUAL_FD),
+                AB::Expr::zero(),
+                AB::Expr::zero(),
+                AB::Expr::zero(),
+            ]),
+        );
+    }
+
+    /// Decode the file descriptor of `close`, `fstat64` and `read`, and look it up in the table.
+    fn eval_fd<AB: ZKMAirBuilder>(&self, builder: &mut AB, local: &SysFileCols<AB::Var>) {
+        let is_fd_op: AB::Expr = local.is_close + local.is_fstat64 + local.is_read;
+
+        IsZeroOperation::<AB::F>::eval(
+            builder,
+            local.a0[1] + local.a0[2] + local.a0[3],
+            local.fd_hi_zero,
+            local.is_header.into(),
+        );
+        for (lt, bound) in [
+            (local.fd_lt_1, 1),
+            (local.fd_lt_3, FD_STDERR + 1),
+            (local.fd_lt_first, FIRST_VIRTUAL_FD),
+            (local.fd_lt_end, FIRST_VIRTUAL_FD + VFS_MAX_FDS),
+        ] {
+            builder.send_byte(
+                ByteOpcode::LTU.as_field::<AB::F>(),
+                lt,
+                local.a0[0],
+                AB::F::from_canonical_u32(bound),
+                local.is_header,
+            );
+        }
+        builder
+            .assert_eq(local.is_stdio, is_fd_op.clone() * local.fd_hi_zero.result * local.fd_lt_3);
+        builder.assert_eq(
+            local.is_fd_slot,
+            is_fd_op.clone() * local.fd_hi_zero.result * (local.fd_lt_end - local.fd_lt_first),
+        );
+        // Reads from stdin are served by the SysLinux chip.
+        builder.assert_zero(local.is_read * local.fd_hi_zero.result * local.fd_lt_1);
+
+        IsZeroOperation::<AB::F>::eval(
+            builder,
+            local.mem[2].prev_value.reduce::<AB>(),
+            local.slot_free,
+            local.is_fd_slot + local.is_slot,
+        );
+        builder.assert_eq(
+            local.is_fd_open,
+            local.is_fd_slot * (AB::Expr::one() - local.slot_free.result),
+        );
+        builder.assert_eq(local.is_closing, local.is_close * local.is_fd_open);
+        builder.assert_eq(local.is_stat_copy, local.is_fstat64 * local.is_fd_open);
+        builder.assert_eq(local.is_read_ok, local.is_read * local.is_fd_open);
+
+        // stdin, stdout and stderr can be closed and stat, but not read. The other descriptors
+        // fail unless open.
+        builder.assert_eq(
+            local.is_ebadf,
+            is_fd_op - local.is_stdio + local.is_read * local.is_stdio - local.is_fd_open,
+        );
+        builder.when(local.is_closing).assert_word_zero(*local.mem[2].value());
+    }
+
+    /// Read up to `count` bytes, at most [`VFS_MAX_READ`], from the offset of the file descriptor.
+    fn eval_read<AB: ZKMAirBuilder>(&self, builder: &mut AB, local: &SysFileCols<AB::Var>) {
+        let is_read_ok = local.is_read_ok;
+        let base = AB::F::from_canonical_u32(256);
+
+        let count = *local.mem[1].value();
+        IsZeroOperation::<AB::F>::eval(
+            builder,
+            count[2] + count[3],
+            local.count_hi_zero,
+            is_read_ok.into(),
+        );
+        builder.send_byte(
+            ByteOpcode::LTU.as_field::<AB::F>(),
+            local.count_lt_16,
+            count[1],
+            AB::F::from_canonical_u32(16),
+            is_read_ok,
+        );
+        builder.assert_eq(local.count_fits, local.count_hi_zero.result * local.count_lt_16);
+        builder.when(is_read_ok).assert_eq(
+            local.count_eff,
+            local.count_fits * (count[0] + count[1] * base)
+                + (AB::Expr::one() - local.count_fits) * AB::F::from_canonical_u32(VFS_MAX_READ),
+        );
+
+        builder.when(is_read_ok).assert_eq(local.len, local.result[0] + local.result[1] * base);
+        builder.when(is_read_ok).assert_zero(local.result[2]);
+        builder.when(is_read_ok).assert_zero(local.result[3]);
+
+        // len = min(count_eff, size - offset): the bytes left after the read are a 28-bit number,
+        // which is zero unless len = count_eff.
+        let room: AB::Expr = local.room_lo + local.room_hi * AB::F::from_canonical_u32(1 << 16);
+        let offset = local.mem[3].prev_value;
+        builder.when(is_read_ok).assert_eq(
+            local.mem[5].value().reduce::<AB>() - offset.reduce::<AB>() - local.len,
+            room.clone(),
+        );
+        for limb in [
+            local.room_lo.into(),
+            local.room_hi.into(),
+            local.room_hi * AB::F::from_canonical_u32(16),
+            local.count_eff - local.len,
+        ] {
+            builder.send_byte(
+                ByteOpcode::U16Range.as_field::<AB::F>(),
+                limb,
+                AB::Expr::zero(),
+                AB::Expr::zero(),
+                is_read_ok,
+            );
+        }
+        builder.when(is_read_ok).assert_zero((local.count_eff - local.len) * room);
+
+        AddOperation::<AB::F>::eval(
+            builder,
+            offset,
+            local.result,
+            local.new_offset,
+            is_read_ok.into(),
+        );
+        builder.when(is_read_ok).assert_word_eq(*local.mem[3].value(), local.new_offset.value);
+
+        IsZeroOperation::<AB::F>::eval(
+            builder,
+            local.len.into(),
+            local.len_zero,
+            is_read_ok.into(),
+        );
+        builder
+            .when(local.is_stat_copy)
+            .assert_eq(local.len, AB::F::from_canonical_u32(STAT64_SIZE));
+    }
+
+    /// Resolve the path of `open` and `openat` to the index of its file, and check the flags.
+    fn eval_open<AB: ZKMAirBuilder>(&self, builder: &mut AB, local: &SysFileCols<AB::Var>) {
+        let is_open_any: AB::Expr = local.is_open + local.is_openat;
+
+        // The flags are a1 for open, and a2 for openat.
+        builder.when(local.is_header).assert_eq(
+            local.flags,
+            local.is_open * local.a1[0] + local.is_openat * local.mem[1].value()[0],
+        );
+        builder.send_byte(
+            ByteOpcode::AND.as_field::<AB::F>(),
+            local.acc_mode,
+            local.flags,
+            AB::F::from_canonical_u32(O_ACCMODE),
+            is_open_any.clone(),
+        );
+        IsZeroOperation::<AB::F>::eval(
+            builder,
+            local.acc_mode.into(),
+            local.is_rdonly,
+            is_open_any.clone(),
+        );
+
+        // Relative paths resolve against the working directory only. The high bytes of AT_FDCWD
+        // are 0xff, so they are the only bytes summing to theirs.
+        let at_fdcwd = AT_FDCWD.to_le_bytes();
+        IsZeroOperation::<AB::F>::eval(
+            builder,
+            AB::Expr::from_canonical_u32(at_fdcwd[1..].iter().map(|&b| b as u32).sum())
+                - local.a0[1]
+                - local.a0[2]
+                - local.a0[3],
+            local.cwd_hi,
+            local.is_openat.into(),
+        );
+        IsZeroOperation::<AB::F>::eval(
+            builder,
+            local.a0[0] - AB::F::from_canonical_u8(at_fdcwd[0]),
+            local.cwd_lo,
+            local.is_openat.into(),
+        );
+        builder.assert_eq(local.is_cwd, local.cwd_hi.result * local.cwd_lo.result);
+        builder.assert_eq(
+            local.is_enotdir,
+            local.is_openat * (AB::Expr::one() - local.is_cwd) * local.relative,
+        );
+
+        // 0 <= index <= N, where N is the number of files.
+        let num_files = local.mem[2].value().reduce::<AB>();
+        for limb in [local.index.into(), num_files.clone() - local.index] {
+            builder.send_byte(
+                ByteOpcode::U16Range.as_field::<AB::F>(),
+                limb,
+                AB::Expr::zero(),
+                AB::Expr::zero(),
+                is_open_any.clone(),
+            );
+        }
+        IsZeroOperation::<AB::F>::eval(
+            builder,
+            local.index.into(),
+            local.index_zero,
+            is_open_any.clone(),
+        );
+        IsZeroOperation::<AB::F>::eval(
+            builder,
+            num_files - local.index,
+            local.index_last,
+            is_open_any.clone(),
+        );
+
+        // A file is found at the index, unless the directory is wrong. It's opened unless it's
+        // opened for writing, or unless every file descriptor is open.
+        builder.assert_bool(local.found);
+        builder.assert_bool(local.relative);
+        builder.assert_bool(local.alloc);
+        builder
+            .when(local.is_header)
+            .assert_zero(local.found * (AB::Expr::one() - is_open_any.clone()));
+        builder.assert_zero(local.found * local.is_enotdir);
+        builder.when(local.is_header).assert_zero(local.found * local.index_last.result);
+        builder
+            .when(local.is_header)
+            .assert_eq(local.is_enoent, is_open_any.clone() - local.is_enotdir - local.found);
+        builder.when(local.is_header).assert_eq(local.alloc, local.found * local.is_rdonly.result);
+        builder.when(local.is_header).assert_eq(local.is_erofs, local.found - local.alloc);
+        builder
+            .when(local.is_header)
+            .assert_eq(local.is_emfile, local.alloc * (AB::Expr::one() - local.slot_lt_16));
+
+        // The descriptor allocated is at most 16, which stands for none.
+        let is_alloc: AB::Expr = local.is_header * local.alloc;
+        builder.send_byte(
+            ByteOpcode::LTU.as_field::<AB::F>(),
+            local.slot_lt_16,
+            local.slot_k,
+            AB::F::from_canonical_u32(VFS_MAX_FDS),
+            is_alloc.clone(),
+        );
+        builder.send_byte(
+            ByteOpcode::LTU.as_field::<AB::F>(),
+            AB::Expr::one(),
+            local.slot_k,
+            AB::F::from_canonical_u32(VFS_MAX_FDS + 1),
+            is_alloc,
+        );
+
+        // Unless found, the path lies strictly between the paths of the files around the index.
+        // When found, it's equal to the path of the file at the index.
+        builder
+            .assert_eq(local.cmp_lo, local.is_enoent * (AB::Expr::one() - local.index_zero.result));
+        builder.assert_eq(
+            local.cmp_hi,
+            (is_open_any - local.is_enotdir) * (AB::Expr::one() - local.index_last.result),
+        );
+    }
+
+    /// The one-hot positions and the word addresses of the byte streams.
+    fn eval_streams<AB: ZKMAirBuilder>(
+        &self,
+        builder: &mut AB,
+        local: &SysFileCols<AB::Var>,
+        next: &SysFileCols<AB::Var>,
+    ) {
+        let four = AB::F::from_canonical_u32(4);
+        let has_ptr: AB::Expr =
+            local.is_open + local.is_openat + local.is_read_ok + local.is_stat_copy;
+        let has_src: AB::Expr = local.is_read_ok + local.is_stat_copy;
+
+        let mut ptr_sum = AB::Expr::zero();
+        let mut ptr_low = AB::Expr::zero();
+        let mut src_sum = AB::Expr::zero();
+        let mut src_low = AB::Expr::zero();
+        for k in 0..4 {
+            builder.assert_bool(local.ptr_pos[k]);
+            builder.assert_bool(local.src_pos[k]);
+            ptr_sum = ptr_sum + local.ptr_pos[k];
+            ptr_low = ptr_low + local.ptr_pos[k] * AB::F::from_canonical_usize(k);
+            src_sum = src_sum + local.src_pos[k];
+            src_low = src_low + local.src_pos[k] * AB::F::from_canonical_usize(k);
+        }
+        builder.assert_eq(ptr_sum, has_ptr.clone() + local.is_path + local.is_copy);
+        builder.assert_eq(src_sum, has_src + local.is_copy);
+
+        // The path is at a0 for open and at a1 for openat, and the buffer is at a1.
+        builder.when(has_ptr.clone()).assert_eq(
+            ptr_low.clone() + local.ptr_hi * four,
+            local.is_open * local.a0[0] + (AB::Expr::one() - local.is_open) * local.a1[0],
+        );
+        builder.when(has_ptr.clone()).assert_eq(
+            local.ptr_addr + ptr_low,
+            local.is_open * local.a0.reduce::<AB>()
+                + (AB::Expr::one() - local.is_open) * local.a1.reduce::<AB>(),
+        );
+        builder.slice_range_check_u8(&[local.ptr_hi.into(), local.ptr_hi * four], has_ptr);
+
+        // The data is read from its offset, and the `stat64` buffer from its start.
+        let offset = local.mem[3].prev_value;
+        builder.when(local.is_read_ok).assert_eq(offset[0], src_low.clone() + local.src_hi * four);
+        builder.when(local.is_read_ok).assert_eq(
+            local.src_addr + src_low,
+            local.mem[4].value().reduce::<AB>() + offset.reduce::<AB>(),
+        );
+        builder.slice_range_check_u8(&[local.src_hi.into(), local.src_hi * four], local.is_read_ok);
+        builder.when(local.is_stat_copy).assert_one(local.src_pos[0]);
+        builder
+            .when(local.is_stat_copy)
+            .assert_eq(local.src_addr, local.mem[3].value().reduce::<AB>());
+
+        // Each stream starts at the position of the header, and moves one byte per row.
+        let mut transition = builder.when_transition();
+        let mut when_ptr = transition.when(next.is_path + next.is_copy);
+        let mut when_start = when_ptr.when(next.first);
+        for k in 0..4 {
+            when_start.assert_eq(next.ptr_pos[k], local.ptr_pos[k]);
+        }
+        when_start.assert_eq(next.ptr_addr, local.ptr_addr);
+        let mut when_step = when_ptr.when_not(next.first);
+        for k in 0..4 {
+            when_step.assert_eq(next.ptr_pos[k], local.ptr_pos[(k + 3) % 4]);
+        }
+        when_step.assert_eq(next.ptr_addr, local.ptr_addr + next.ptr_pos[0] * four);
+
+        let mut transition = builder.when_transition();
+        let mut when_src = transition.when(next.is_copy);
+        let mut when_start = when_src.when(next.first);
+        for k in 0..4 {
+            when_start.assert_eq(next.src_pos[k], local.src_pos[k]);
+        }
+        when_start.assert_eq(next.src_addr, local.src_addr);
+        let mut when_step = when_src.when_not(next.first);
+        for k in 0..4 {
+            when_step.assert_eq(next.src_pos[k], local.src_pos[(k + 3) % 4]);
+        }
+        when_step.assert_eq(next.src_addr, local.src_addr + next.src_pos[0] * four);
+    }
+
+    /// Compare the path byte by byte to the paths of the files around the index.
+    fn eval_path<AB: ZKMAirBuilder>(
+        &self,
+        builder: &mut AB,
+        local: &SysFileCols<AB::Var>,
+        next: &SysFileCols<AB::Var>,
+    ) {
+        let one = AB::Expr::one();
+        let four = AB::F::from_canonical_u32(4);
+
+        builder.assert_eq(
+            local.path_read,
+            local.is_path * (local.first + local.ptr_pos[0] - local.first * local.ptr_pos[0]),
+        );
+        let path_byte = (0..4)
+            .fold(AB::Expr::zero(), |acc, k| acc + local.ptr_pos[k] * local.mem[0].value()[k]);
+        builder.when(local.is_path).assert_eq(local.path_byte, path_byte);
+        IsZeroOperation::<AB::F>::eval(
+            builder,
+            local.path_byte.into(),
+            local.path_zero,
+            local.is_path.into(),
+        );
+        IsZeroOperation::<AB::F>::eval(
+            builder,
+            local.path_byte - AB::F::from_canonical_u8(b'/'),
+            local.is_slash,
+            local.is_path.into(),
+        );
+        // Relative paths are compared to the paths of the files without their leading slash.
+        builder
+            .when(local.is_path)
+            .when(local.first)
+            .assert_eq(local.relative, one.clone() - local.is_slash.result);
+
+        // The path before the index is less: at the first byte that differs, its byte is less,
+        // and it can't be equal up to the end of the path.
+        builder.assert_bool(local.eq_lo);
+        builder.assert_zero(local.eq_lo * (one.clone() - local.is_path));
+        builder.assert_eq(
+            local.lo_read,
+            local.eq_lo * (local.first + local.lo_pos[0] - local.first * local.lo_pos[0]),
+        );
+        let lo_byte =
+            (0..4).fold(AB::Expr::zero(), |acc, k| acc + local.lo_pos[k] * local.mem[1].value()[k]);
+        builder.when(local.eq_lo).assert_eq(local.lo_byte, lo_byte);
+        IsZeroOperation::<AB::F>::eval(
+            builder,
+            local.lo_byte - local.path_byte,
+            local.same_lo,
+            local.eq_lo.into(),
+        );
+        builder.assert_eq(local.next_lo, local.eq_lo * local.same_lo.result);
+        builder.send_byte(
+            ByteOpcode::LTU.as_field::<AB::F>(),
+            AB::Expr::one(),
+            local.lo_byte,
+            local.path_byte,
+            local.eq_lo - local.next_lo,
+        );
+        builder.assert_zero(local.next_lo * local.path_zero.result);
+
+        // The path at the index is greater unless found, when it's equal up to the end of the
+        // path.
+        builder.assert_bool(local.eq_hi);
+        builder.assert_zero(local.eq_hi * (one.clone() - local.is_path));
+        builder.assert_eq(
+            local.hi_read,
+            local.eq_hi * (local.first + local.hi_pos[0] - local.first * local.hi_pos[0]),
+        );
+        let hi_byte =
+            (0..4).fold(AB::Expr::zero(), |acc, k| acc + local.hi_pos[k] * local.mem[2].value()[k]);
+        builder.when(local.eq_hi).assert_eq(local.hi_byte, hi_byte);
+        IsZeroOperation::<AB::F>::eval(
+            builder,
+            local.hi_byte - local.path_byte,
+            local.same_hi,
+            local.eq_hi.into(),
+        );
+        builder.assert_eq(local.hi_same, local.eq_hi * local.same_hi.result);
+        builder.assert_eq(local.next_hi, local.hi_same * (one.clone() - local.path_zero.result));
+        builder.send_byte(
+            ByteOpcode::LTU.as_field::<AB::F>(),
+            AB::Expr::one(),
+            local.path_byte,
+            local.hi_byte,
+            local.eq_hi - local.hi_same,
+        );
+        builder.assert_zero(local.found * (local.eq_hi - local.hi_same));
+        builder.assert_zero((one.clone() - local.found) * (local.hi_same - local.next_hi));
+
+        builder.assert_eq(
+            local.path_more,
+            local.next_lo + local.next_hi - local.next_lo * local.next_hi,
+        );
+
+        // The paths of the files start after their leading slash when the path is relative.
+        let mut transition = builder.when_transition();
+        let mut when_path = transition.when(next.is_path);
+        let mut when_start = when_path.when(next.first);
+        when_start.assert_eq(next.eq_lo, local.cmp_lo);
+        when_start.assert_eq(next.eq_hi, local.cmp_hi);
+        for pos in [next.lo_pos, next.hi_pos] {
+            when_start.assert_eq(pos[0], one.clone() - next.relative);
+            when_start.assert_eq(pos[1], next.relative);
+            when_start.assert_zero(pos[2]);
+            when_start.assert_zero(pos[3]);
+        }
+        when_start.assert_eq(next.lo_addr, local.mem[3].value().reduce::<AB>());
+        when_start.assert_eq(next.hi_addr, local.mem[4].value().reduce::<AB>());
+        let mut when_step = when_path.when_not(next.first);
+        when_step.assert_eq(next.eq_lo, local.next_lo);
+        when_step.assert_eq(next.eq_hi, local.next_hi);
+        for k in 0..4 {
+            when_step.assert_eq(next.lo_pos[k], local.lo_pos[(k + 3) % 4]);
+            when_step.assert_eq(next.hi_pos[k], local.hi_pos[(k + 3) % 4]);
+        }
+        when_step.assert_eq(next.lo_addr, local.lo_addr + next.lo_pos[0] * four);
+        when_step.assert_eq(next.hi_addr, local.hi_addr + next.hi_pos[0] * four);
+    }
+
+    /// Scan the file descriptors for the lowest free one.
+    fn eval_slot<AB: ZKMAirBuilder>(
+        &self,
+        builder: &mut AB,
+        local: &SysFileCols<AB::Var>,
+        next: &SysFileCols<AB::Var>,
+    ) {
+        builder.when_transition().when(next.is_slot).when(next.first).assert_zero(next.slot_idx);
+        builder
+            .when_transition()
+            .when(next.is_slot)
+            .when_not(next.first)
+            .assert_eq(next.slot_idx, local.slot_idx + AB::Expr::one());
+
+        IsZeroOperation::<AB::F>::eval(
+            builder,
+            local.slot_idx - local.slot_k,
+            local.slot_target,
+            local.is_slot.into(),
+        );
+        IsZeroOperation::<AB::F>::eval(
+            builder,
+            local.slot_idx - AB::F::from_canonical_u32(VFS_MAX_FDS - 1),
+            local.slot_last,
+            local.is_slot.into(),
+        );
+        builder.assert_eq(local.slot_write, local.is_slot * local.slot_target.result);
+
+        // The descriptors before the one allocated are open, and the one allocated is free.
+        builder.assert_zero((local.is_slot - local.slot_write) * local.slot_free.result);
+        builder.assert_zero(local.slot_write * (AB::Expr::one() - local.slot_free.result));
+
+        // It's set to the index of the file plus one, at offset zero.
+        let file = *local.mem[2].value();
+        builder.when(local.slot_write).assert_eq(
+            file[0] + file[1] * AB::F::from_canonical_u32(256),
+            local.index + AB::Expr::one(),
+        );
+        builder.when(local.slot_write).assert_zero(file[2]);
+        builder.when(local.slot_write).assert_zero(file[3]);
+        builder.slice_range_check_u8(&[file[0], file[1]], local.slot_write);
+        builder.when(local.slot_write).assert_word_zero(*local.mem[3].value());
+    }
+
+    /// Copy the bytes to the buffer, writing each word of the buffer once.
+    fn eval_copy<AB: ZKMAirBuilder>(
+        &self,
+        builder: &mut AB,
+        local: &SysFileCols<AB::Var>,
+        next: &SysFileCols<AB::Var>,
+    ) {
+        builder.when_transition().when(next.is_copy).when(next.first).assert_zero(next.copy_idx);
+        builder
+            .when_transition()
+            .when(next.is_copy)
+            .when_not(next.first)
+            .assert_eq(next.copy_idx, local.copy_idx + AB::Expr::one());
+        IsZeroOperation::<AB::F>::eval(
+            builder,
+            local.len - local.copy_idx - AB::Expr::one(),
+            local.copy_last,
+            local.is_copy.into(),
+        );
+
+        builder.assert_eq(
+            local.src_read,
+            local.is_copy * (local.first + local.src_pos[0] - local.first * local.src_pos[0]),
+        );
+        let byte = (0..4)
+            .fold(AB::Expr::zero(), |acc, k| acc + local.src_pos[k] * local.mem[0].value()[k]);
+        builder.when(local.is_copy).assert_eq(local.byte, byte);
+
+        // A word of the buffer starts at its first byte or at the first byte copied, and ends at
+        // its last byte or at the last byte copied, where it's written.
+        builder.assert_eq(
+            local.dst_new,
+            local.is_copy * (local.first + local.ptr_pos[0] - local.first * local.ptr_pos[0]),
+        );
+        builder.assert_eq(
+            local.dst_flush,
+            local.is_copy
+                * (local.ptr_pos[3] + local.copy_last.result
+                    - local.ptr_pos[3] * local.copy_last.result),
+        );
+        let mut transition = builder.when_transition();
+        let mut when_copy = transition.when(next.is_copy);
+        for k in 0..4 {
+            when_copy.assert_eq(
+                next.mask[k],
+                (AB::Expr::one() - next.dst_new) * local.mask[k] + next.ptr_pos[k],
+            );
+            when_copy.assert_eq(
+                next.acc[k],
+                (AB::Expr::one() - next.dst_new) * local.acc[k] + next.ptr_pos[k] * next.byte,
+            );
+        }
+        for k in 0..4 {
+            builder.when(local.dst_flush).assert_eq(
+                local.mem[1].value()[k],
+                local.acc[k] + (AB::Expr::one() - local.mask[k]) * local.mem[1].prev_value[k],
+            );
+        }
+        builder.slice_range_check_u8(&local.mem[1].value().0, local.dst_flush);
+    }
+
+    /// The memory accesses of each kind of row.
+    fn eval_memory<AB: ZKMAirBuilder>(
+        &self,
+        builder: &mut AB,
+        local: &SysFileCols<AB::Var>,
+        next: &SysFileCols<AB::Var>,
+    ) {
+        let is_open_any: AB::Expr = local.is_open + local.is_openat;
+        let is_fd_op: AB::Expr = local.is_close + local.is_fstat64 + local.is_read;
+        let f = AB::F::from_canonical_u32;
+
+        builder.assert_eq(local.mem_check[0], local.is_header + local.path_read + local.src_read);
+        builder.assert_eq(
+            local.mem_check[1],
+            local.is_read + local.is_openat + local.lo_read + local.dst_flush,
+        );
+        builder.assert_eq(
+            local.mem_check[2],
+            local.is_fd_slot + is_open_any.clone() + local.hi_read + local.is_slot,
+        );
+        builder.assert_eq(
+            local.mem_check[3],
+            local.is_read_ok + local.is_stat_copy + local.cmp_lo + local.slot_write,
+        );
+        builder.assert_eq(local.mem_check[4], local.is_read_ok + local.cmp_hi);
+        builder.assert_eq(local.mem_check[5], local.is_read_ok);
+
+        // Header: A3, A2, then the file descriptor and the entry of its file, or the number of
+        // files and the entries around the index.
+        let slot = AB::Expr::from(f(VFS_FDS)) + (local.a0[0] - f(FIRST_VIRTUAL_FD)) * f(8);
+        let entry = AB::Expr::from(f(VFS_ENTRIES))
+            + (local.mem[2].value().reduce::<AB>() - AB::Expr::one()) * f(16);
+        builder.when(local.is_header).assert_eq(local.mem_addr[0], f(Register::A3 as u32));
+        builder.when(local.is_header).assert_eq(local.mem_addr[1], f(Register::A2 as u32));
+        builder
+            .when(local.is_header)
+            .assert_eq(local.mem_addr[2], is_open_any.clone() * f(VFS_BASE) + is_fd_op * slot);
+        builder.when(local.is_read).assert_eq(local.mem_addr[3], local.mem_addr[2] + f(4));
+        builder.when(local.is_read).assert_eq(local.mem_addr[4], entry.clone() + f(4));
+        builder.when(local.is_read).assert_eq(local.mem_addr[5], entry.clone() + f(8));
+        builder.when(local.is_fstat64).assert_eq(local.mem_addr[3], entry + f(12));
+        builder.when(is_open_any.clone()).assert_eq(
+            local.mem_addr[3],
+            AB::Expr::from(f(VFS_ENTRIES)) + (local.index - AB::Expr::one()) * f(16),
+        );
+        builder
+            .when(is_open_any)
+            .assert_eq(local.mem_addr[4], AB::Expr::from(f(VFS_ENTRIES)) + local.index * f(16));
+
+        builder.when(local.is_path).assert_eq(local.mem_addr[0], local.ptr_addr);
+        builder.when(local.is_path).assert_eq(local.mem_addr[1], local.lo_addr);
+        builder.when(local.is_path).assert_eq(local.mem_addr[2], local.hi_addr);
+        builder
+            .when(local.is_slot)
+            .assert_eq(local.mem_addr[2], AB::Expr::from(f(VFS_FDS)) + local.slot_idx * f(8));
+        builder.when(local.is_slot).assert_eq(local.mem_addr[3], local.mem_addr[2] + f(4));
+        builder.when(local.is_copy).assert_eq(local.mem_addr[0], local.src_addr);
+        builder.when(local.is_copy).assert_eq(local.mem_addr[1], local.ptr_addr);
+
+        for k in 0..6 {
+            builder.eval_memory_access(
+                local.shard,
+                local.clk,
+                local.mem_addr[k],
+                &local.mem[k],
+                local.mem_check[k],
+            );
+        }
+
+        // Only A3, the file descriptors, their offsets and the buffer are written.
+        builder
+            .when(local.is_path + local.is_copy)
+            .assert_word_eq(*local.mem[0].value(), local.mem[0].prev_value);
+        builder
+            .when(local.is_header + local.is_path)
+            .assert_word_eq(*local.mem[1].value(), local.mem[1].prev_value);
+        builder
+            .when(
+                local.is_header + local.is_path + local.is_slot
+                    - local.is_closing
+                    - local.slot_write,
+            )
+            .assert_word_eq(*local.mem[2].value(), local.mem[2].prev_value);
+        builder
+            .when(local.is_header - local.is_read_ok)
+            .assert_word_eq(*local.mem[3].value(), local.mem[3].prev_value);
+        builder
+            .when(local.is_header)
+            .assert_word_eq(*local.mem[4].value(), local.mem[4].prev_value);
+        builder
+            .when(local.is_header)
+            .assert_word_eq(*local.mem[5].value(), local.mem[5].prev_value);
+
+        // A word is read once, and its bytes are taken from the following rows.
+        builder
+            .when_transition()
+            .when(next.is_path - next.path_read + next.is_copy - next.src_read)
+            .assert_word_eq(*next.mem[0].value(), *local.mem[0].value());
+        builder
+            .when_transition()
+            .when(next.eq_lo - next.lo_read)
+            .assert_word_eq(*next.mem[1].value(), *local.mem[1].value());
+        builder
+            .when_transition()
+            .when(next.eq_hi - next.hi_read)
+            .assert_word_eq(*next.mem[2].value(), *local.mem[2].value());
+    }
+}
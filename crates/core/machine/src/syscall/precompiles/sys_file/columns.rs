@@ -0,0 +1,188 @@
+use std::mem::size_of;
+
+use zkm_derive::AlignedBorrow;
+use zkm_stark::Word;
+
+use crate::{
+    memory::MemoryReadWriteCols,
+    operations::{AddOperation, IsZeroOperation},
+};
+
+pub const NUM_SYS_FILE_COLS: usize = size_of::<SysFileCols<u8>>();
+
+/// Virtual file syscall AIR columns.
+///
+/// An event takes a header row, which proves the syscall and its result, followed by:
+/// - for `open` and `openat`, one path row per byte of the path compared to the paths of the files
+///   around it, then one slot row per file descriptor scanned for a free one;
+/// - for `fstat64` and `read`, one copy row per byte copied to the buffer.
+///
+/// The memory accesses are shared between the kinds of rows.
+#[derive(AlignedBorrow, Default, Debug, Clone, Copy)]
+#[repr(C)]
+pub struct SysFileCols<T> {
+    // ── Row kind ───────────────────────────────────────────────────────
+    pub shard: T,
+    pub clk: T,
+    pub is_header: T,
+    pub is_path: T,
+    pub is_slot: T,
+    pub is_copy: T,
+    /// Whether the row is the first path, slot or copy row of the event.
+    pub first: T,
+    /// Stored: whether the next row belongs to the event too.
+    pub more: T,
+
+    // ── Shared memory accesses ─────────────────────────────────────────
+    /// Header: A3, A2, the file descriptor or the number of files, then the offset, the
+    /// `stat64` buffer or the path before the one looked up, the data or the path at the index
+    /// looked up, and the size. Path rows: the path looked up and the paths around it. Slot rows:
+    /// the file descriptor and its offset. Copy rows: the source and the destination.
+    pub mem_addr: [T; 6],
+    pub mem_check: [T; 6],
+    pub mem: [MemoryReadWriteCols<T>; 6],
+
+    // ── Event constants, carried to every row of the event ─────────────
+    /// Whether the path looked up names a file.
+    pub found: T,
+    /// Whether the path looked up is relative, so compared without the leading slash.
+    pub relative: T,
+    /// Whether a file descriptor is allocated to the file found.
+    pub alloc: T,
+    /// The index of the first file whose path isn't less than the path looked up.
+    pub index: T,
+    /// The file descriptor allocated minus `FIRST_VIRTUAL_FD`, or 16 when none is free.
+    pub slot_k: T,
+    /// The number of bytes copied.
+    pub len: T,
+
+    // ── Header ─────────────────────────────────────────────────────────
+    pub syscall_id: T,
+    pub a0: Word<T>,
+    pub a1: Word<T>,
+    pub result: Word<T>,
+    pub is_open: T,
+    pub is_openat: T,
+    pub is_close: T,
+    pub is_fstat64: T,
+    pub is_read: T,
+    pub is_ok: T,
+    pub is_ebadf: T,
+    pub is_enoent: T,
+    pub is_enotdir: T,
+    pub is_erofs: T,
+    pub is_emfile: T,
+
+    // ── File descriptor decoder ────────────────────────────────────────
+    /// IsZero on a0[1] + a0[2] + a0[3], i.e. whether the file descriptor fits in a byte.
+    pub fd_hi_zero: IsZeroOperation<T>,
+    pub fd_lt_1: T,
+    pub fd_lt_3: T,
+    /// Whether the file descriptor is below `FIRST_VIRTUAL_FD`.
+    pub fd_lt_first: T,
+    /// Whether the file descriptor is below `FIRST_VIRTUAL_FD + VFS_MAX_FDS`.
+    pub fd_lt_end: T,
+    /// Stored: a file descriptor operation on stdin, stdout or stderr.
+    pub is_stdio: T,
+    /// Stored: a file descriptor operation on a descriptor of the table.
+    pub is_fd_slot: T,
+    /// IsZero on the file descriptor read from the table, on header and slot rows.
+    pub slot_free: IsZeroOperation<T>,
+    /// Stored: is_fd_slot * (1 - slot_free.result).
+    pub is_fd_open: T,
+    /// Stored: is_close * is_fd_open.
+    pub is_closing: T,
+    /// Stored: is_fstat64 * is_fd_open.
+    pub is_stat_copy: T,
+    /// Stored: is_read * is_fd_open.
+    pub is_read_ok: T,
+
+    // ── read ───────────────────────────────────────────────────────────
+    /// IsZero on count[2] + count[3].
+    pub count_hi_zero: IsZeroOperation<T>,
+    pub count_lt_16: T,
+    /// Stored: whether the count is less than 4096.
+    pub count_fits: T,
+    /// The count, capped at 4096.
+    pub count_eff: T,
+    /// The bytes left after the read: size - offset - len = room_lo + 65536 * room_hi.
+    pub room_lo: T,
+    pub room_hi: T,
+    pub new_offset: AddOperation<T>,
+    pub len_zero: IsZeroOperation<T>,
+
+    // ── open and openat ────────────────────────────────────────────────
+    pub flags: T,
+    /// flags & O_ACCMODE.
+    pub acc_mode: T,
+    pub is_rdonly: IsZeroOperation<T>,
+    /// IsZero on 765 - a0[1] - a0[2] - a0[3] and on a0[0] - 0x9c, i.e. whether dirfd is AT_FDCWD.
+    pub cwd_hi: IsZeroOperation<T>,
+    pub cwd_lo: IsZeroOperation<T>,
+    pub is_cwd: T,
+    pub index_zero: IsZeroOperation<T>,
+    /// IsZero on the number of files minus the index.
+    pub index_last: IsZeroOperation<T>,
+    pub slot_lt_16: T,
+    /// Whether the path is compared to the path before the index, and to the path at the index.
+    pub cmp_lo: T,
+    pub cmp_hi: T,
+
+    // ── Byte streams ───────────────────────────────────────────────────
+    /// The path looked up, or the buffer copied to: the one-hot position of the byte in its word,
+    /// and the address of the word.
+    pub ptr_pos: [T; 4],
+    pub ptr_hi: T,
+    pub ptr_addr: T,
+    /// The data or the `stat64` buffer copied from.
+    pub src_pos: [T; 4],
+    pub src_hi: T,
+    pub src_addr: T,
+
+    // ── Path rows ──────────────────────────────────────────────────────
+    pub path_read: T,
+    pub path_byte: T,
+    pub path_zero: IsZeroOperation<T>,
+    pub is_slash: IsZeroOperation<T>,
+    /// Whether the path before the index is still equal to the path looked up.
+    pub eq_lo: T,
+    pub lo_pos: [T; 4],
+    pub lo_addr: T,
+    pub lo_read: T,
+    pub lo_byte: T,
+    pub same_lo: IsZeroOperation<T>,
+    /// Stored: eq_lo * same_lo.result, the next eq_lo.
+    pub next_lo: T,
+    /// Whether the path at the index is still equal to the path looked up.
+    pub eq_hi: T,
+    pub hi_pos: [T; 4],
+    pub hi_addr: T,
+    pub hi_read: T,
+    pub hi_byte: T,
+    pub same_hi: IsZeroOperation<T>,
+    /// Stored: eq_hi * same_hi.result.
+    pub hi_same: T,
+    /// Stored: hi_same * (1 - path_zero.result), the next eq_hi.
+    pub next_hi: T,
+    /// Stored: whether another path row follows.
+    pub path_more: T,
+
+    // ── Slot rows ──────────────────────────────────────────────────────
+    pub slot_idx: T,
+    pub slot_target: IsZeroOperation<T>,
+    pub slot_last: IsZeroOperation<T>,
+    /// Stored: is_slot * slot_target.result.
+    pub slot_write: T,
+
+    // ── Copy rows ──────────────────────────────────────────────────────
+    pub copy_idx: T,
+    pub copy_last: IsZeroOperation<T>,
+    pub src_read: T,
+    pub byte: T,
+    /// Stored: whether the byte starts, and whether it ends, a word of the destination.
+    pub dst_new: T,
+    pub dst_flush: T,
+    /// The bytes of the destination word copied so far, and their values.
+    pub mask: [T; 4],
+    pub acc: [T; 4],
+}
@@ -0,0 +1,89 @@
+mod air;
+mod columns;
+mod trace;
+
+/// A chip that proves the file syscalls against the virtual files of the memory image.
+#[derive(Default)]
+pub struct SysFileChip;
+
+impl SysFileChip {
+    pub const fn new() -> Self {
+        Self {}
+    }
+}
+
+#[cfg(test)]
+pub mod sys_file_tests {
+    use std::collections::BTreeMap;
+
+    use zkm_core_executor::{syscalls::SyscallCode, Instruction, Opcode, Program, AT_FDCWD};
+    use zkm_primitives::consts::fd::FD_PUBLIC_VALUES;
+    use zkm_stark::CpuProver;
+
+    use crate::utils::{run_test, setup_logger};
+
+    /// Opens, reads, stats and closes the files `/a` and `/c`, and fails to open the others.
+    pub fn sys_file_program() -> Program {
+        let buf_ptr = 0x300;
+        let stat_ptr = 0x400;
+        let mut instructions = vec![];
+        // "/a\0" at 0x200, "/b\0" at 0x204 and "c\0" at 0x209.
+        for (addr, word) in [(0x200, 0x612f), (0x204, 0x622f), (0x208, 0x6300)] {
+            instructions.extend(vec![
+                Instruction::new(Opcode::ADD, 29, 0, word, false, true),
+                Instruction::new(Opcode::ADD, 30, 0, addr, false, true),
+                Instruction::new(Opcode::SW, 29, 30, 0, false, true),
+            ]);
+        }
+
+        let syscall = |code: SyscallCode, a0: Instruction, a1: u32, a2: u32| {
+            vec![
+                Instruction::new(Opcode::ADD, 2, 0, code as u32, false, true),
+                a0,
+                Instruction::new(Opcode::ADD, 5, 0, a1, false, true),
+                Instruction::new(Opcode::ADD, 6, 0, a2, false, true),
+                Instruction::new(Opcode::SYSCALL, 2, 4, 5, false, false),
+            ]
+        };
+        let a0 = |value: u32| Instruction::new(Opcode::ADD, 4, 0, value, false, true);
+        let a0_fd = |reg: u32| Instruction::new(Opcode::ADD, 4, reg, 0, false, true);
+
+        instructions.extend(syscall(SyscallCode::SYS_OPEN, a0(0x200), 0, 0));
+        instructions.push(Instruction::new(Opcode::ADD, 16, 2, 0, false, true));
+        // Reads the 6 bytes of the file into an unaligned buffer, then nothing.
+        instructions.extend(syscall(SyscallCode::SYS_READ, a0_fd(16), buf_ptr + 1, 8));
+        instructions.extend(syscall(SyscallCode::SYS_READ, a0_fd(16), buf_ptr, 8));
+        instructions.extend(syscall(SyscallCode::SYS_FSTAT64, a0_fd(16), stat_ptr, 0));
+        // The file is read-only, so writing to it fails with EBADF.
+        instructions.extend(syscall(SyscallCode::SYS_WRITE, a0_fd(16), buf_ptr, 4));
+        // "c" resolves to "/c", which gets the next file descriptor.
+        instructions.extend(syscall(SyscallCode::SYS_OPENAT, a0(AT_FDCWD), 0x209, 0));
+        instructions.push(Instruction::new(Opcode::ADD, 17, 2, 0, false, true));
+        instructions.extend(syscall(SyscallCode::SYS_CLOSE, a0_fd(16), 0, 0));
+        // The file descriptor is closed now.
+        instructions.extend(syscall(SyscallCode::SYS_READ, a0_fd(16), buf_ptr, 8));
+        instructions.extend(syscall(SyscallCode::SYS_CLOSE, a0_fd(17), 0, 0));
+        // ENOENT, ENOTDIR and EROFS.
+        instructions.extend(syscall(SyscallCode::SYS_OPEN, a0(0x204), 0, 0));
+        instructions.extend(syscall(SyscallCode::SYS_OPENAT, a0(5), 0x209, 0));
+        instructions.extend(syscall(SyscallCode::SYS_OPEN, a0(0x200), 1, 0));
+        instructions.extend(syscall(SyscallCode::SYS_FSTAT64, a0(1), stat_ptr, 0));
+        // The file descriptors of the zkVM aren't in the table.
+        instructions.extend(syscall(SyscallCode::SYS_CLOSE, a0(FD_PUBLIC_VALUES), 0, 0));
+        instructions.extend(syscall(SyscallCode::SYS_EXT_GROUP, a0(0), 0, 0));
+
+        Program::new(instructions, 0, 0)
+            .with_files(&BTreeMap::from([
+                ("/a".to_string(), vec![1, 2, 3, 4, 5, 6]),
+                ("/c".to_string(), b"file c".to_vec()),
+            ]))
+            .unwrap()
+    }
+
+    #[test]
+    fn prove_koalabear() {
+        setup_logger();
+        let program = sys_file_program();
+        run_test::<CpuProver<_, _>>(program).unwrap();
+    }
+}
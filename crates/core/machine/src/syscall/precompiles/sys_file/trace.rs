@@ -0,0 +1,582 @@
+use std::borrow::BorrowMut;
+
+use hashbrown::HashMap;
+use itertools::Itertools;
+use p3_field::PrimeField32;
+use p3_matrix::dense::RowMajorMatrix;
+use p3_maybe_rayon::prelude::{IntoParallelRefIterator, ParallelIterator, ParallelSlice};
+use zkm_core_executor::{
+    events::{
+        ByteCopyEvent, ByteLookupEvent, ByteRecord, MemoryReadRecord, MemoryRecordEnum,
+        PathLookupEvent, PrecompileEvent, SysFileEvent,
+    },
+    syscalls::SyscallCode,
+    ByteOpcode, ExecutionRecord, Program, Register, AT_FDCWD, FIRST_VIRTUAL_FD, MIPS_EBADF,
+    MIPS_EMFILE, MIPS_ENOENT, MIPS_ENOTDIR, MIPS_EROFS, O_ACCMODE, STAT64_SIZE, VFS_BASE,
+    VFS_ENTRIES, VFS_FDS, VFS_MAX_FDS, VFS_MAX_READ,
+};
+use zkm_primitives::consts::fd::FD_STDERR;
+use zkm_stark::air::MachineAir;
+
+use super::{
+    columns::{SysFileCols, NUM_SYS_FILE_COLS},
+    SysFileChip,
+};
+use crate::{memory::MemoryReadWriteCols, utils::pad_rows_fixed, CoreChipError};
+
+impl<F: PrimeField32> MachineAir<F> for SysFileChip {
+    type Record = ExecutionRecord;
+
+    type Program = Program;
+
+    type Error = CoreChipError;
+
+    fn name(&self) -> String {
+        "SysFile".to_string()
+    }
+
+    fn generate_trace(
+        &self,
+        input: &ExecutionRecord,
+        _: &mut ExecutionRecord,
+    ) -> Result<RowMajorMatrix<F>, Self::Error> {
+        let events = input.get_precompile_events(SyscallCode::SYS_FILE);
+
+        let mut rows = events
+            .par_iter()
+            .flat_map(|(_, event)| {
+                let event = if let PrecompileEvent::SysFile(event) = event {
+                    event
+                } else {
+                    unreachable!();
+                };
+
+                self.event_to_rows(event, &mut Vec::new())
+            })
+            .collect::<Vec<_>>();
+
+        pad_rows_fixed(
+            &mut rows,
+            || [F::ZERO; NUM_SYS_FILE_COLS],
+            input.fixed_log2_rows::<F, _>(self),
+            <SysFileChip as MachineAir<F>>::name(self).as_str(),
+        );
+
+        Ok(RowMajorMatrix::new(rows.into_iter().flatten().collect::<Vec<_>>(), NUM_SYS_FILE_COLS))
+    }
+
+    fn generate_dependencies(
+        &self,
+        input: &Self::Record,
+        output: &mut Self::Record,
+    ) -> Result<(), Self::Error> {
+        let events = input.get_precompile_events(SyscallCode::SYS_FILE);
+        let chunk_size = std::cmp::max(events.len() / num_cpus::get(), 1);
+
+        let blu_batches = events
+            .par_chunks(chunk_size)
+            .map(|events| {
+                let mut blu: HashMap<ByteLookupEvent, usize> = HashMap::new();
+                events.iter().for_each(|(_, event)| {
+                    let event = if let PrecompileEvent::SysFile(event) = event {
+                        event
+                    } else {
+                        unreachable!()
+                    };
+                    let _ = self.event_to_rows::<F>(event, &mut blu);
+                });
+                blu
+            })
+            .collect::<Vec<_>>();
+
+        output.add_byte_lookup_events_from_maps(blu_batches.iter().collect_vec());
+        Ok(())
+    }
+
+    fn included(&self, shard: &Self::Record) -> bool {
+        if let Some(shape) = shard.shape.as_ref() {
+            shape.included::<F, _>(self)
+        } else {
+            !shard.get_precompile_events(SyscallCode::SYS_FILE).is_empty()
+        }
+    }
+}
+
+impl SysFileChip {
+    pub fn event_to_rows<F: PrimeField32>(
+        &self,
+        event: &SysFileEvent,
+        blu: &mut impl ByteRecord,
+    ) -> Vec<[F; NUM_SYS_FILE_COLS]> {
+        let mut rows = vec![[F::ZERO; NUM_SYS_FILE_COLS]; event.num_rows()];
+        let (header, rest) = rows.split_first_mut().unwrap();
+        let header: &mut SysFileCols<F> = header.as_mut_slice().borrow_mut();
+        self.populate_header(event, header, blu);
+        let header = *header;
+
+        if let Some(lookup) = &event.lookup {
+            let ptr = if header.is_open == F::ONE { event.a0 } else { event.a1 };
+            let (path_rows, slot_rows) = rest.split_at_mut(lookup.path.len());
+            self.populate_path(lookup, ptr, path_rows, blu);
+            self.populate_slots(lookup, header.slot_k.as_canonical_u32(), slot_rows, blu);
+        } else if let Some(copy) = &event.copy {
+            self.populate_copy(copy, rest, blu);
+        }
+
+        // The constants of the event are carried to every row.
+        let num_rows = rows.len();
+        for (i, row) in rows.iter_mut().enumerate() {
+            let cols: &mut SysFileCols<F> = row.as_mut_slice().borrow_mut();
+            cols.shard = header.shard;
+            cols.clk = header.clk;
+            cols.more = F::from_bool(i + 1 < num_rows);
+            cols.found = header.found;
+            cols.relative = header.relative;
+            cols.alloc = header.alloc;
+            cols.index = header.index;
+            cols.slot_k = header.slot_k;
+            cols.len = header.len;
+        }
+        rows
+    }
+
+    /// The syscall, its outcome, the file descriptor and the accesses to the table.
+    fn populate_header<F: PrimeField32>(
+        &self,
+        event: &SysFileEvent,
+        cols: &mut SysFileCols<F>,
+        blu: &mut impl ByteRecord,
+    ) {
+        let is_code = |code: SyscallCode| event.syscall_code == code.syscall_id();
+        let is_open = is_code(SyscallCode::SYS_OPEN);
+        let is_openat = is_code(SyscallCode::SYS_OPENAT);
+        let is_close = is_code(SyscallCode::SYS_CLOSE);
+        let is_fstat64 = is_code(SyscallCode::SYS_FSTAT64);
+        let is_read = is_code(SyscallCode::SYS_READ);
+
+        cols.shard = F::from_canonical_u32(event.shard);
+        cols.clk = F::from_canonical_u32(event.clk);
+        cols.is_header = F::ONE;
+        cols.syscall_id = F::from_canonical_u32(event.syscall_code);
+        cols.a0 = event.a0.into();
+        cols.a1 = event.a1.into();
+        cols.result = event.v0.into();
+        blu.add_u8_range_checks(&event.a0.to_le_bytes());
+        blu.add_u8_range_checks(&event.a1.to_le_bytes());
+        blu.add_u8_range_checks(&event.v0.to_le_bytes());
+        cols.is_open = F::from_bool(is_open);
+        cols.is_openat = F::from_bool(is_openat);
+        cols.is_close = F::from_bool(is_close);
+        cols.is_fstat64 = F::from_bool(is_fstat64);
+        cols.is_read = F::from_bool(is_read);
+
+        let errno = event.errno_record.value;
+        cols.is_ok = F::from_bool(errno == 0);
+        cols.is_ebadf = F::from_bool(errno == MIPS_EBADF);
+        cols.is_enoent = F::from_bool(errno == MIPS_ENOENT);
+        cols.is_enotdir = F::from_bool(errno == MIPS_ENOTDIR);
+        cols.is_erofs = F::from_bool(errno == MIPS_EROFS);
+        cols.is_emfile = F::from_bool(errno == MIPS_EMFILE);
+
+        cols.mem_addr[0] = F::from_canonical_u32(Register::A3 as u32);
+        cols.mem_check[0] = F::ONE;
+        cols.mem[0].populate_write(event.errno_record, blu);
+        cols.mem_addr[1] = F::from_canonical_u32(Register::A2 as u32);
+        if let Some(record) = event.a2_record {
+            cols.mem_check[1] = F::ONE;
+            cols.mem[1].populate_read(record, blu);
+        }
+
+        // ── File descriptor ────────────────────────────────────────────
+        let a0 = event.a0.to_le_bytes();
+        let is_fd_op = is_close || is_fstat64 || is_read;
+        let fd_hi_zero = cols.fd_hi_zero.populate(a0[1..].iter().map(|&b| b as u32).sum()) == 1;
+        cols.fd_lt_1 = F::from_bool(add_ltu(blu, a0[0], 1));
+        let fd_lt_3 = add_ltu(blu, a0[0], (FD_STDERR + 1) as u8);
+        let fd_lt_first = add_ltu(blu, a0[0], FIRST_VIRTUAL_FD as u8);
+        let fd_lt_end = add_ltu(blu, a0[0], (FIRST_VIRTUAL_FD + VFS_MAX_FDS) as u8);
+        cols.fd_lt_3 = F::from_bool(fd_lt_3);
+        cols.fd_lt_first = F::from_bool(fd_lt_first);
+        cols.fd_lt_end = F::from_bool(fd_lt_end);
+        let is_fd_slot = is_fd_op && fd_hi_zero && fd_lt_end && !fd_lt_first;
+        cols.is_stdio = F::from_bool(is_fd_op && fd_hi_zero && fd_lt_3);
+        cols.is_fd_slot = F::from_bool(is_fd_slot);
+
+        let slot = F::from_canonical_u32(VFS_FDS)
+            + F::from_canonical_u32(8)
+                * (F::from_canonical_u8(a0[0]) - F::from_canonical_u32(FIRST_VIRTUAL_FD));
+        let mut file = 0;
+        if let Some(record) = event.fd_record {
+            file = match record {
+                MemoryRecordEnum::Read(record) => record.value,
+                MemoryRecordEnum::Write(record) => record.prev_value,
+            };
+            cols.slot_free.populate(file);
+            cols.mem_check[2] = F::ONE;
+            cols.mem[2].populate(record, blu);
+        }
+        let is_fd_open = is_fd_slot && file != 0;
+        let is_read_ok = is_read && is_fd_open;
+        let is_stat_copy = is_fstat64 && is_fd_open;
+        cols.is_fd_open = F::from_bool(is_fd_open);
+        cols.is_closing = F::from_bool(is_close && is_fd_open);
+        cols.is_stat_copy = F::from_bool(is_stat_copy);
+        cols.is_read_ok = F::from_bool(is_read_ok);
+
+        let entry = F::from_canonical_u32(VFS_ENTRIES)
+            + F::from_canonical_u32(16) * (F::from_canonical_u32(file) - F::ONE);
+        if is_fd_op {
+            cols.mem_addr[2] = slot;
+        }
+        if is_read {
+            cols.mem_addr[3] = slot + F::from_canonical_u32(4);
+            cols.mem_addr[4] = entry + F::from_canonical_u32(4);
+            cols.mem_addr[5] = entry + F::from_canonical_u32(8);
+        }
+        if is_fstat64 {
+            cols.mem_addr[3] = entry + F::from_canonical_u32(12);
+        }
+
+        // ── read ───────────────────────────────────────────────────────
+        if is_read_ok {
+            let count = event.a2_record.unwrap().value;
+            let count_bytes = count.to_le_bytes();
+            cols.count_hi_zero.populate(count_bytes[2] as u32 + count_bytes[3] as u32);
+            cols.count_lt_16 = F::from_bool(add_ltu(blu, count_bytes[1], 16));
+            let count_eff = count.min(VFS_MAX_READ);
+            cols.count_fits = F::from_bool(count < VFS_MAX_READ);
+            cols.count_eff = F::from_canonical_u32(count_eff);
+
+            let len = event.v0;
+            let offset_record = event.offset_record.unwrap();
+            let data_record = event.data_record.unwrap();
+            let size_record = event.size_record.unwrap();
+            let offset = offset_record.prev_value;
+            let room = size_record.value - offset - len;
+            cols.room_lo = F::from_canonical_u32(room & 0xffff);
+            cols.room_hi = F::from_canonical_u32(room >> 16);
+            blu.add_u16_range_checks(&[
+                (room & 0xffff) as u16,
+                (room >> 16) as u16,
+                ((room >> 16) * 16) as u16,
+                (count_eff - len) as u16,
+            ]);
+            cols.new_offset.populate(blu, offset, len);
+            cols.len_zero.populate(len);
+            cols.len = F::from_canonical_u32(len);
+
+            cols.mem_check[3] = F::ONE;
+            cols.mem[3].populate_write(offset_record, blu);
+            cols.mem_check[4] = F::ONE;
+            cols.mem[4].populate_read(data_record, blu);
+            cols.mem_check[5] = F::ONE;
+            cols.mem[5].populate_read(size_record, blu);
+
+            // The data is aligned, so the first byte is at the position of the offset.
+            let src = data_record.value + offset;
+            let src_hi = (offset & 0xff) >> 2;
+            cols.src_pos[(offset % 4) as usize] = F::ONE;
+            cols.src_hi = F::from_canonical_u32(src_hi);
+            cols.src_addr = F::from_canonical_u32(src & !3);
+            blu.add_u8_range_checks(&[src_hi as u8, (src_hi * 4) as u8]);
+        }
+
+        // ── fstat64 ────────────────────────────────────────────────────
+        if is_stat_copy {
+            let stat_record = event.stat_record.unwrap();
+            cols.mem_check[3] = F::ONE;
+            cols.mem[3].populate_read(stat_record, blu);
+            cols.src_pos[0] = F::ONE;
+            cols.src_addr = F::from_canonical_u32(stat_record.value);
+            cols.len = F::from_canonical_u32(STAT64_SIZE);
+        }
+
+        // ── open and openat ────────────────────────────────────────────
+        if let Some(lookup) = &event.lookup {
+            let flags = if is_open { event.a1 } else { event.a2_record.unwrap().value } & 0xff;
+            let acc_mode = flags & O_ACCMODE;
+            blu.add_byte_lookup_event(ByteLookupEvent {
+                opcode: ByteOpcode::AND,
+                a1: acc_mode as u16,
+                a2: 0,
+                b: flags as u8,
+                c: O_ACCMODE as u8,
+            });
+            cols.flags = F::from_canonical_u32(flags);
+            cols.acc_mode = F::from_canonical_u32(acc_mode);
+            cols.is_rdonly.populate(acc_mode);
+
+            if is_openat {
+                let at_fdcwd = AT_FDCWD.to_le_bytes();
+                let hi_sum = |bytes: [u8; 4]| bytes[1..].iter().map(|&b| b as u32).sum::<u32>();
+                let cwd_hi = cols.cwd_hi.populate_from_field_element(
+                    F::from_canonical_u32(hi_sum(at_fdcwd)) - F::from_canonical_u32(hi_sum(a0)),
+                );
+                let cwd_lo = cols.cwd_lo.populate_from_field_element(
+                    F::from_canonical_u8(a0[0]) - F::from_canonical_u8(at_fdcwd[0]),
+                );
+                cols.is_cwd = F::from_canonical_u32(cwd_hi * cwd_lo);
+            }
+            cols.relative = F::from_bool(lookup.path[0] != b'/');
+
+            let index = lookup.index;
+            let num_files = lookup.count_record.value;
+            blu.add_u16_range_checks(&[index as u16, (num_files - index) as u16]);
+            cols.index = F::from_canonical_u32(index);
+            cols.index_zero.populate(index);
+            cols.index_last.populate(num_files - index);
+
+            let alloc = lookup.found && acc_mode == 0;
+            cols.found = F::from_bool(lookup.found);
+            cols.alloc = F::from_bool(alloc);
+            if alloc {
+                // The descriptor allocated, or 16 when every descriptor is open.
+                let slot_k = if errno == 0 { event.v0 - FIRST_VIRTUAL_FD } else { VFS_MAX_FDS };
+                cols.slot_k = F::from_canonical_u32(slot_k);
+                cols.slot_lt_16 = F::from_bool(add_ltu(blu, slot_k as u8, VFS_MAX_FDS as u8));
+                add_ltu(blu, slot_k as u8, (VFS_MAX_FDS + 1) as u8);
+            }
+            cols.cmp_lo = F::from_bool(lookup.lo_record.is_some());
+            cols.cmp_hi = F::from_bool(lookup.hi_record.is_some());
+
+            cols.mem_addr[2] = F::from_canonical_u32(VFS_BASE);
+            cols.mem_check[2] = F::ONE;
+            cols.mem[2].populate_read(lookup.count_record, blu);
+            cols.mem_addr[3] = F::from_canonical_u32(VFS_ENTRIES)
+                + F::from_canonical_u32(16) * (F::from_canonical_u32(index) - F::ONE);
+            cols.mem_addr[4] = F::from_canonical_u32(VFS_ENTRIES + 16 * index);
+            if let Some(record) = lookup.lo_record {
+                cols.mem_check[3] = F::ONE;
+                cols.mem[3].populate_read(record, blu);
+            }
+            if let Some(record) = lookup.hi_record {
+                cols.mem_check[4] = F::ONE;
+                cols.mem[4].populate_read(record, blu);
+            }
+        }
+
+        // ── Destination of the path or the copy ────────────────────────
+        if is_open || is_openat || is_read_ok || is_stat_copy {
+            let ptr = if is_open { event.a0 } else { event.a1 };
+            let ptr_hi = (ptr & 0xff) >> 2;
+            cols.ptr_pos[(ptr % 4) as usize] = F::ONE;
+            cols.ptr_hi = F::from_canonical_u32(ptr_hi);
+            cols.ptr_addr = F::from_canonical_u32(ptr & !3);
+            blu.add_u8_range_checks(&[ptr_hi as u8, (ptr_hi * 4) as u8]);
+        }
+    }
+
+    /// One row per byte of the path, compared to the paths of the files around the index.
+    fn populate_path<F: PrimeField32>(
+        &self,
+        lookup: &PathLookupEvent,
+        ptr: u32,
+        rows: &mut [[F; NUM_SYS_FILE_COLS]],
+        blu: &mut impl ByteRecord,
+    ) {
+        let skip = u32::from(lookup.path[0] != b'/');
+        let lo = lookup.lo_record.map_or(0, |record| record.value) + skip;
+        let hi = lookup.hi_record.map_or(0, |record| record.value) + skip;
+        let (mut eq_lo, mut eq_hi) = (lookup.lo_record.is_some(), lookup.hi_record.is_some());
+        let (mut path_records, mut lo_records, mut hi_records) =
+            (lookup.path_records.iter(), lookup.lo_records.iter(), lookup.hi_records.iter());
+        let (mut path_word, mut lo_word, mut hi_word) = (0, 0, 0);
+
+        for (i, (row, &byte)) in rows.iter_mut().zip(&lookup.path).enumerate() {
+            let cols: &mut SysFileCols<F> = row.as_mut_slice().borrow_mut();
+            let i = i as u32;
+            cols.is_path = F::ONE;
+            cols.first = F::from_bool(i == 0);
+
+            let addr = ptr.wrapping_add(i);
+            cols.ptr_pos[(addr % 4) as usize] = F::ONE;
+            cols.ptr_addr = F::from_canonical_u32(addr & !3);
+            cols.mem_addr[0] = cols.ptr_addr;
+            let (read, _) =
+                next_byte(&mut cols.mem[0], addr, i == 0, &mut path_word, &mut path_records, blu);
+            cols.path_read = F::from_bool(read);
+            cols.mem_check[0] = F::from_bool(read);
+            cols.path_byte = F::from_canonical_u8(byte);
+            cols.path_zero.populate(byte as u32);
+            cols.is_slash.populate_from_field_element(
+                F::from_canonical_u8(byte) - F::from_canonical_u8(b'/'),
+            );
+
+            // The path before the index stops at the first byte that differs.
+            let addr = lo.wrapping_add(i);
+            cols.lo_pos[(addr % 4) as usize] = F::ONE;
+            cols.lo_addr = F::from_canonical_u32(addr & !3);
+            cols.mem_addr[1] = cols.lo_addr;
+            cols.eq_lo = F::from_bool(eq_lo);
+            if eq_lo {
+                let (read, lo_byte) =
+                    next_byte(&mut cols.mem[1], addr, i == 0, &mut lo_word, &mut lo_records, blu);
+                cols.lo_read = F::from_bool(read);
+                cols.mem_check[1] = F::from_bool(read);
+                cols.lo_byte = F::from_canonical_u8(lo_byte);
+                cols.same_lo.populate_from_field_element(
+                    F::from_canonical_u8(lo_byte) - F::from_canonical_u8(byte),
+                );
+                eq_lo = lo_byte == byte;
+                if !eq_lo {
+                    add_ltu(blu, lo_byte, byte);
+                }
+            }
+            cols.next_lo = F::from_bool(eq_lo);
+
+            // The path at the index stops at the first byte that differs, or at the end.
+            let addr = hi.wrapping_add(i);
+            cols.hi_pos[(addr % 4) as usize] = F::ONE;
+            cols.hi_addr = F::from_canonical_u32(addr & !3);
+            cols.mem_addr[2] = cols.hi_addr;
+            cols.eq_hi = F::from_bool(eq_hi);
+            if eq_hi {
+                let (read, hi_byte) =
+                    next_byte(&mut cols.mem[2], addr, i == 0, &mut hi_word, &mut hi_records, blu);
+                cols.hi_read = F::from_bool(read);
+                cols.mem_check[2] = F::from_bool(read);
+                cols.hi_byte = F::from_canonical_u8(hi_byte);
+                cols.same_hi.populate_from_field_element(
+                    F::from_canonical_u8(hi_byte) - F::from_canonical_u8(byte),
+                );
+                let hi_same = hi_byte == byte;
+                if !hi_same {
+                    add_ltu(blu, byte, hi_byte);
+                }
+                cols.hi_same = F::from_bool(hi_same);
+                eq_hi = hi_same && byte != 0;
+            }
+            cols.next_hi = F::from_bool(eq_hi);
+            cols.path_more = F::from_bool(eq_lo || eq_hi);
+        }
+    }
+
+    /// One row per file descriptor scanned for a free one.
+    fn populate_slots<F: PrimeField32>(
+        &self,
+        lookup: &PathLookupEvent,
+        slot_k: u32,
+        rows: &mut [[F; NUM_SYS_FILE_COLS]],
+        blu: &mut impl ByteRecord,
+    ) {
+        for (k, (row, &record)) in rows.iter_mut().zip(&lookup.slot_records).enumerate() {
+            let cols: &mut SysFileCols<F> = row.as_mut_slice().borrow_mut();
+            let k = k as u32;
+            cols.is_slot = F::ONE;
+            cols.first = F::from_bool(k == 0);
+            cols.slot_idx = F::from_canonical_u32(k);
+            cols.slot_target.populate_from_field_element(
+                F::from_canonical_u32(k) - F::from_canonical_u32(slot_k),
+            );
+            cols.slot_last.populate_from_field_element(
+                F::from_canonical_u32(k) - F::from_canonical_u32(VFS_MAX_FDS - 1),
+            );
+
+            let slot = VFS_FDS + 8 * k;
+            cols.mem_addr[2] = F::from_canonical_u32(slot);
+            cols.mem_addr[3] = F::from_canonical_u32(slot + 4);
+            cols.mem_check[2] = F::ONE;
+            cols.mem[2].populate(record, blu);
+            match record {
+                MemoryRecordEnum::Read(record) => {
+                    cols.slot_free.populate(record.value);
+                }
+                MemoryRecordEnum::Write(record) => {
+                    cols.slot_free.populate(record.prev_value);
+                    cols.slot_write = F::ONE;
+                    cols.mem_check[3] = F::ONE;
+                    cols.mem[3].populate_write(lookup.offset_record.unwrap(), blu);
+                    blu.add_u8_range_checks(&record.value.to_le_bytes()[..2]);
+                }
+            }
+        }
+    }
+
+    /// One row per byte copied, writing each word of the destination at its last byte.
+    fn populate_copy<F: PrimeField32>(
+        &self,
+        copy: &ByteCopyEvent,
+        rows: &mut [[F; NUM_SYS_FILE_COLS]],
+        blu: &mut impl ByteRecord,
+    ) {
+        let len = copy.bytes.len() as u32;
+        let (mut src_records, mut dst_records) = (copy.src_records.iter(), copy.dst_records.iter());
+        let mut src_word = 0;
+        let (mut mask, mut acc) = ([F::ZERO; 4], [F::ZERO; 4]);
+
+        for (i, (row, &byte)) in rows.iter_mut().zip(&copy.bytes).enumerate() {
+            let cols: &mut SysFileCols<F> = row.as_mut_slice().borrow_mut();
+            let i = i as u32;
+            cols.is_copy = F::ONE;
+            cols.first = F::from_bool(i == 0);
+            cols.copy_idx = F::from_canonical_u32(i);
+            cols.copy_last.populate(len - i - 1);
+
+            let src = copy.src + i;
+            cols.src_pos[(src % 4) as usize] = F::ONE;
+            cols.src_addr = F::from_canonical_u32(src & !3);
+            cols.mem_addr[0] = cols.src_addr;
+            let (read, _) =
+                next_byte(&mut cols.mem[0], src, i == 0, &mut src_word, &mut src_records, blu);
+            cols.src_read = F::from_bool(read);
+            cols.mem_check[0] = F::from_bool(read);
+            cols.byte = F::from_canonical_u8(byte);
+
+            let dst = copy.dst.wrapping_add(i);
+            let pos = (dst % 4) as usize;
+            cols.ptr_pos[pos] = F::ONE;
+            cols.ptr_addr = F::from_canonical_u32(dst & !3);
+            cols.mem_addr[1] = cols.ptr_addr;
+            let dst_new = i == 0 || pos == 0;
+            if dst_new {
+                (mask, acc) = ([F::ZERO; 4], [F::ZERO; 4]);
+            }
+            mask[pos] = F::ONE;
+            acc[pos] = F::from_canonical_u8(byte);
+            cols.dst_new = F::from_bool(dst_new);
+            cols.mask = mask;
+            cols.acc = acc;
+            if pos == 3 || i == len - 1 {
+                let record = *dst_records.next().unwrap();
+                cols.dst_flush = F::ONE;
+                cols.mem_check[1] = F::ONE;
+                cols.mem[1].populate_write(record, blu);
+                blu.add_u8_range_checks(&record.value.to_le_bytes());
+            }
+        }
+    }
+}
+
+/// Add the lookup of `b < c`, returning it.
+fn add_ltu(blu: &mut impl ByteRecord, b: u8, c: u8) -> bool {
+    blu.add_byte_lookup_event(ByteLookupEvent {
+        opcode: ByteOpcode::LTU,
+        a1: (b < c) as u16,
+        a2: 0,
+        b,
+        c,
+    });
+    b < c
+}
+
+/// Get the byte at `addr`, reading the word holding it if it's the first byte or the first byte of
+/// the word, and carrying the word read before otherwise. Returns whether the word was read.
+fn next_byte<'a, F: PrimeField32>(
+    mem: &mut MemoryReadWriteCols<F>,
+    addr: u32,
+    first: bool,
+    word: &mut u32,
+    records: &mut impl Iterator<Item = &'a MemoryReadRecord>,
+    blu: &mut impl ByteRecord,
+) -> (bool, u8) {
+    let read = first || addr % 4 == 0;
+    if read {
+        let record = *records.next().unwrap();
+        mem.populate_read(record, blu);
+        *word = record.value;
+    } else {
+        mem.prev_value = (*word).into();
+        mem.access.value = (*word).into();
+    }
+    (read, word.to_le_bytes()[(addr % 4) as usize])
+}
@@ -3,7 +3,9 @@ use core::borrow::Borrow;
 use p3_air::{Air, AirBuilder, BaseAir};
 use p3_field::FieldAlgebra;
 use p3_matrix::Matrix;
-use zkm_core_executor::{syscalls::SyscallCode, Register};
+use zkm_core_executor::{
+    syscalls::SyscallCode, ByteOpcode, Register, FIRST_VIRTUAL_FD, MIPS_EBADF, VFS_MAX_FDS,
+};
 use zkm_stark::{
     air::{LookupScope, ZKMAirBuilder},
     Word,
@@ -82,10 +84,34 @@ where
         );
         IsZeroOperation::<AB::F>::eval(
             builder,
-            sid - AB::Expr::from_canonical_u32(SyscallCode::SYS_WRITE as u32),
+            sid.clone() - AB::Expr::from_canonical_u32(SyscallCode::SYS_WRITE as u32),
             local.decode_write,
             local.is_real.into(),
         );
+        IsZeroOperation::<AB::F>::eval(
+            builder,
+            sid.clone() - AB::Expr::from_canonical_u32(SyscallCode::SYS_OPEN as u32),
+            local.decode_open,
+            local.is_real.into(),
+        );
+        IsZeroOperation::<AB::F>::eval(
+            builder,
+            sid.clone() - AB::Expr::from_canonical_u32(SyscallCode::SYS_OPENAT as u32),
+            local.decode_openat,
+            local.is_real.into(),
+        );
+        IsZeroOperation::<AB::F>::eval(
+            builder,
+            sid.clone() - AB::Expr::from_canonical_u32(SyscallCode::SYS_CLOSE as u32),
+            local.decode_close,
+            local.is_real.into(),
+        );
+        IsZeroOperation::<AB::F>::eval(
+            builder,
+            sid - AB::Expr::from_canonical_u32(SyscallCode::SYS_FSTAT64 as u32),
+            local.decode_fstat64,
+            local.is_real.into(),
+        );
 
         let is_clone = local.decode_clone.result;
         let is_exit_group = local.decode_exit_group.result;
@@ -94,6 +120,12 @@ where
         let is_read = local.decode_read.result;
         let is_write = local.decode_write.result;
 
+        // The file syscalls are served by the SysFile chip, so they can't be proven here as nops.
+        builder.when(local.is_real).assert_zero(local.decode_open.result);
+        builder.when(local.is_real).assert_zero(local.decode_openat.result);
+        builder.when(local.is_real).assert_zero(local.decode_close.result);
+        builder.when(local.is_real).assert_zero(local.decode_fstat64.result);
+
         builder
             .when(local.is_real)
             .assert_eq(local.is_mmap, local.decode_mmap.result + local.decode_mmap2.result);
@@ -437,16 +469,13 @@ impl SysLinuxChip {
         is_read: AB::Var,
         is_a0_0: AB::Var,
     ) {
+        // Reads from the other file descriptors are served by the SysFile chip. The bytes of a0
+        // are range-checked so that they only sum to zero when a0 is stdin.
+        builder.slice_range_check_u8(&local.a0.0, is_read);
+        builder.when(is_read).assert_zero(local.a0[0] + local.a0[1] + local.a0[2] + local.a0[3]);
+
         builder.when(is_read).when(is_a0_0).assert_word_zero(local.result);
         builder.when(is_read).when(is_a0_0).assert_word_zero(*local.output.value());
-        builder
-            .when(is_read)
-            .when_not(is_a0_0)
-            .assert_word_eq(local.result, Word::<AB::Expr>::from(0xFFFFFFFFu32));
-        builder
-            .when(is_read)
-            .when_not(is_a0_0)
-            .assert_word_eq(*local.output.value(), Word::<AB::Expr>::from(9));
     }
 
     fn eval_write<AB: ZKMAirBuilder>(
@@ -462,8 +491,49 @@ impl SysLinuxChip {
             &local.inorout,
             is_write,
         );
-        builder.when(is_write).assert_word_eq(local.result, *local.inorout.value());
-        builder.when(is_write).assert_word_zero(*local.output.value());
+
+        // The virtual files are read-only, so writes to their file descriptors fail with EBADF.
+        // The bytes of a0 are range-checked so that the high ones only sum to zero when they are.
+        builder.slice_range_check_u8(&local.a0.0, is_write);
+        IsZeroOperation::<AB::F>::eval(
+            builder,
+            local.a0[1] + local.a0[2] + local.a0[3],
+            local.write_fd_hi_zero,
+            is_write.into(),
+        );
+        for (lt, bound) in [
+            (local.write_fd_lt_first, FIRST_VIRTUAL_FD),
+            (local.write_fd_lt_end, FIRST_VIRTUAL_FD + VFS_MAX_FDS),
+        ] {
+            builder.send_byte(
+                ByteOpcode::LTU.as_field::<AB::F>(),
+                lt,
+                local.a0[0],
+                AB::F::from_canonical_u32(bound),
+                is_write,
+            );
+        }
+        builder.assert_eq(
+            local.is_write_ebadf,
+            is_write
+                * local.write_fd_hi_zero.result
+                * (local.write_fd_lt_end - local.write_fd_lt_first),
+        );
+
+        builder
+            .when(is_write)
+            .when_not(local.is_write_ebadf)
+            .assert_word_eq(local.result, *local.inorout.value());
+        builder
+            .when(is_write)
+            .when_not(local.is_write_ebadf)
+            .assert_word_zero(*local.output.value());
+        builder
+            .when(local.is_write_ebadf)
+            .assert_word_eq(local.result, Word::<AB::Expr>::from(0xFFFFFFFFu32));
+        builder
+            .when(local.is_write_ebadf)
+            .assert_word_eq(*local.output.value(), Word::<AB::Expr>::from(MIPS_EBADF));
     }
 
     fn eval_nop<AB: ZKMAirBuilder>(
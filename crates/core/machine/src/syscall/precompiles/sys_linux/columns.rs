@@ -33,7 +33,7 @@ pub struct SysLinuxCols<T> {
     /// A3 output register write.
     pub output: MemoryReadWriteCols<T>,
 
-    // ── Canonical syscall decoder (17 cols) ────────────────────────────
+    // ── Canonical syscall decoder (25 cols) ────────────────────────────
     pub decode_mmap: IsZeroOperation<T>,
     pub decode_mmap2: IsZeroOperation<T>,
     pub decode_clone: IsZeroOperation<T>,
@@ -42,6 +42,11 @@ pub struct SysLinuxCols<T> {
     pub decode_fnctl: IsZeroOperation<T>,
     pub decode_read: IsZeroOperation<T>,
     pub decode_write: IsZeroOperation<T>,
+    /// The file syscalls are served by the SysFile chip, so these are asserted to be zero.
+    pub decode_open: IsZeroOperation<T>,
+    pub decode_openat: IsZeroOperation<T>,
+    pub decode_close: IsZeroOperation<T>,
+    pub decode_fstat64: IsZeroOperation<T>,
     /// Stored: decode_mmap.result + decode_mmap2.result (for degree).
     pub is_mmap: T,
 
@@ -78,6 +83,16 @@ pub struct SysLinuxCols<T> {
     // ── brk columns (8 cols) ───────────────────────────────────────────
     pub is_a0_gt_brk: GtColsBytes<T>,
 
+    // ── write columns (5 cols) ─────────────────────────────────────────
+    /// IsZero on a0[1] + a0[2] + a0[3], i.e. whether the file descriptor fits in a byte.
+    pub write_fd_hi_zero: IsZeroOperation<T>,
+    /// Whether the file descriptor is below `FIRST_VIRTUAL_FD`.
+    pub write_fd_lt_first: T,
+    /// Whether the file descriptor is below `FIRST_VIRTUAL_FD + VFS_MAX_FDS`.
+    pub write_fd_lt_end: T,
+    /// Stored: a write to the file descriptor of a virtual file, which fails with EBADF.
+    pub is_write_ebadf: T,
+
     // ── bookkeeping (1 col) ────────────────────────────────────────────
     pub is_real: T,
 }
@@ -8,7 +8,7 @@ use p3_maybe_rayon::prelude::{IntoParallelRefIterator, ParallelIterator, Paralle
 use zkm_core_executor::{
     events::{ByteLookupEvent, ByteRecord, LinuxEvent, PrecompileEvent},
     syscalls::SyscallCode,
-    ExecutionRecord, Program,
+    ByteOpcode, ExecutionRecord, Program, FIRST_VIRTUAL_FD, VFS_MAX_FDS,
 };
 use zkm_stark::{air::MachineAir, Word};
 
@@ -141,6 +141,17 @@ impl SysLinuxChip {
         cols.decode_write.populate_from_field_element(
             sid - F::from_canonical_u32(SyscallCode::SYS_WRITE as u32),
         );
+        cols.decode_open
+            .populate_from_field_element(sid - F::from_canonical_u32(SyscallCode::SYS_OPEN as u32));
+        cols.decode_openat.populate_from_field_element(
+            sid - F::from_canonical_u32(SyscallCode::SYS_OPENAT as u32),
+        );
+        cols.decode_close.populate_from_field_element(
+            sid - F::from_canonical_u32(SyscallCode::SYS_CLOSE as u32),
+        );
+        cols.decode_fstat64.populate_from_field_element(
+            sid - F::from_canonical_u32(SyscallCode::SYS_FSTAT64 as u32),
+        );
 
         let is_mmap = event.syscall_code == SyscallCode::SYS_MMAP as u32
             || event.syscall_code == SyscallCode::SYS_MMAP2 as u32;
@@ -222,9 +233,35 @@ impl SysLinuxChip {
                 // write: read A2 register.
                 assert!(event.read_records.len() == 1);
                 cols.inorout.populate_read(event.read_records[0], blu);
+
+                // Decode whether a0 is the file descriptor of a virtual file.
+                let a0 = event.a0.to_le_bytes();
+                blu.add_u8_range_checks(&a0);
+                let fd_hi_zero =
+                    cols.write_fd_hi_zero.populate(a0[1..].iter().map(|&b| b as u32).sum()) == 1;
+                let mut lt = |bound: u32| {
+                    let lt = u32::from(a0[0]) < bound;
+                    blu.add_byte_lookup_event(ByteLookupEvent::new(
+                        ByteOpcode::LTU,
+                        lt as u16,
+                        0,
+                        a0[0],
+                        bound as u8,
+                    ));
+                    lt
+                };
+                let lt_first = lt(FIRST_VIRTUAL_FD);
+                let lt_end = lt(FIRST_VIRTUAL_FD + VFS_MAX_FDS);
+                cols.write_fd_lt_first = F::from_bool(lt_first);
+                cols.write_fd_lt_end = F::from_bool(lt_end);
+                cols.is_write_ebadf = F::from_bool(fd_hi_zero && lt_end && !lt_first);
+            }
+            4003 => {
+                // stdin read: range-check a0, which is asserted to be zero.
+                blu.add_u8_range_checks(&event.a0.to_le_bytes());
             }
-            4120 | 4246 | 4055 | 4003 => {
-                // clone, exit_group, fnctl, read: no extra memory access needed.
+            4120 | 4246 | 4055 => {
+                // clone, exit_group, fnctl: no extra memory access needed.
             }
             _ => {
                 // nop: unrecognized linux syscall.
@@ -1,7 +1,9 @@
 mod weierstrass_add;
 mod weierstrass_decompress;
 mod weierstrass_double;
+mod weierstrass_scalar_mul;
 
 pub use weierstrass_add::*;
 pub use weierstrass_decompress::*;
 pub use weierstrass_double::*;
+pub use weierstrass_scalar_mul::*;
@@ -0,0 +1,856 @@
+use core::{
+    borrow::{Borrow, BorrowMut},
+    mem::size_of,
+};
+use std::{fmt::Debug, marker::PhantomData};
+
+use crate::{
+    air::MemoryAirBuilder, operations::IsZeroOperation, utils::zeroed_f_vec, CoreChipError,
+};
+use generic_array::GenericArray;
+use num::{BigUint, One, Zero};
+use p3_air::{Air, AirBuilder, BaseAir};
+use p3_field::{Field, FieldAlgebra, PrimeField32, TwoAdicField};
+use p3_koala_bear::KoalaBear;
+use p3_matrix::{dense::RowMajorMatrix, Matrix};
+use p3_maybe_rayon::prelude::{
+    IndexedParallelIterator, ParallelIterator, ParallelSlice, ParallelSliceMut,
+};
+use typenum::Unsigned;
+use zkm_core_executor::{
+    events::{
+        ByteLookupEvent, ByteRecord, EllipticCurveScalarMulEvent, FieldOperation, PrecompileEvent,
+        NUM_WORDS_EC_SCALAR,
+    },
+    syscalls::SyscallCode,
+    ExecutionRecord, Program,
+};
+use zkm_curves::{
+    params::{FieldParameters, Limbs, NumLimbs, NumWords},
+    weierstrass::WeierstrassParameters,
+    AffinePoint, CurveType, EllipticCurve,
+};
+use zkm_derive::AlignedBorrow;
+use zkm_stark::air::{BaseAirBuilder, LookupScope, MachineAir, ZKMAirBuilder};
+
+use crate::{
+    memory::{MemoryCols, MemoryReadCols, MemoryWriteCols},
+    operations::field::field_op::FieldOpCols,
+    utils::{limbs_from_access, limbs_from_prev_access},
+};
+
+/// The number of scalar bits processed by a single scalar multiplication, one per row.
+pub const NUM_SCALAR_MUL_ROWS: usize = 32 * NUM_WORDS_EC_SCALAR;
+
+pub const fn num_weierstrass_scalar_mul_cols<P: FieldParameters + NumWords>() -> usize {
+    size_of::<WeierstrassScalarMulAssignCols<u8, P>>()
+}
+
+/// The columns of an affine point addition `p + q` with `p.x != q.x`.
+#[derive(Debug, Clone, AlignedBorrow)]
+#[repr(C)]
+pub struct ScalarMulAddCols<T, P: FieldParameters> {
+    pub p_x: Limbs<T, P::Limbs>,
+    pub p_y: Limbs<T, P::Limbs>,
+    pub q_x: Limbs<T, P::Limbs>,
+    pub q_y: Limbs<T, P::Limbs>,
+    pub(crate) slope_denominator: FieldOpCols<T, P>,
+    pub(crate) slope_numerator: FieldOpCols<T, P>,
+    pub(crate) slope: FieldOpCols<T, P>,
+    pub(crate) slope_squared: FieldOpCols<T, P>,
+    pub(crate) p_x_plus_q_x: FieldOpCols<T, P>,
+    pub(crate) x3_ins: FieldOpCols<T, P>,
+    pub(crate) p_x_minus_x: FieldOpCols<T, P>,
+    pub(crate) y3_ins: FieldOpCols<T, P>,
+    pub(crate) slope_times_p_x_minus_x: FieldOpCols<T, P>,
+}
+
+/// The columns of an affine point doubling `2p` with `p.y != 0`.
+#[derive(Debug, Clone, AlignedBorrow)]
+#[repr(C)]
+pub struct ScalarMulDoubleCols<T, P: FieldParameters> {
+    pub(crate) slope_denominator: FieldOpCols<T, P>,
+    pub(crate) slope_numerator: FieldOpCols<T, P>,
+    pub(crate) slope: FieldOpCols<T, P>,
+    pub(crate) p_x_squared: FieldOpCols<T, P>,
+    pub(crate) p_x_squared_times_3: FieldOpCols<T, P>,
+    pub(crate) slope_squared: FieldOpCols<T, P>,
+    pub(crate) p_x_plus_p_x: FieldOpCols<T, P>,
+    pub(crate) x3_ins: FieldOpCols<T, P>,
+    pub(crate) p_x_minus_x: FieldOpCols<T, P>,
+    pub(crate) y3_ins: FieldOpCols<T, P>,
+    pub(crate) slope_times_p_x_minus_x: FieldOpCols<T, P>,
+}
+
+/// A set of columns to compute `[k]P` for a point `P` on a Weierstrass curve.
+///
+/// Each scalar multiplication takes up [`NUM_SCALAR_MUL_ROWS`] rows, where row `i` handles bit `i`
+/// of the scalar in a right-to-left double-and-add loop: if the bit is set, `temp = [2^i]P` is
+/// added to the accumulator, and `temp` is doubled for the next row. The scalar and the point are
+/// read, and the result written, on the first row of the cycle.
+#[derive(Debug, Clone, AlignedBorrow)]
+#[repr(C)]
+pub struct WeierstrassScalarMulAssignCols<T, P: FieldParameters + NumWords> {
+    pub is_real: T,
+    pub shard: T,
+    pub clk: T,
+    pub p_ptr: T,
+    pub k_ptr: T,
+
+    /// g^n where g is a generator with order 256 and n is the row number.
+    pub cycle_256: T,
+
+    /// Checks whether current row is start of a 256-row cycle.
+    pub cycle_256_start: IsZeroOperation<T>,
+
+    /// Checks whether current row is end of a 256-row cycle.
+    pub cycle_256_end: IsZeroOperation<T>,
+
+    /// Whether the current row is the first of a 256-row cycle and is real.
+    pub start: T,
+
+    /// Whether the current row is the last of a 256-row cycle and is real.
+    pub end: T,
+
+    pub k_access: [MemoryReadCols<T>; NUM_WORDS_EC_SCALAR],
+    pub p_access: GenericArray<MemoryWriteCols<T>, P::WordsCurvePoint>,
+
+    /// The result `[k]P`, copied over all the rows of the cycle.
+    pub result_x: Limbs<T, P::Limbs>,
+    pub result_y: Limbs<T, P::Limbs>,
+
+    /// The scalar bits not processed yet, least significant first. Shifted by one on every row.
+    pub k_bits: [T; NUM_SCALAR_MUL_ROWS],
+
+    /// Whether the accumulator is still the point at infinity.
+    pub acc_is_infinity: T,
+    pub acc_x: Limbs<T, P::Limbs>,
+    pub acc_y: Limbs<T, P::Limbs>,
+
+    /// `[2^i]P` for the current row `i`.
+    pub temp_x: Limbs<T, P::Limbs>,
+    pub temp_y: Limbs<T, P::Limbs>,
+
+    /// The bit is set and the accumulator is the point at infinity, so it is replaced by `temp`.
+    pub is_set: T,
+
+    /// The bit is set and the accumulator is not the point at infinity, so `temp` is added to it.
+    pub is_add: T,
+
+    /// The accumulator after the current row.
+    pub next_acc_x: Limbs<T, P::Limbs>,
+    pub next_acc_y: Limbs<T, P::Limbs>,
+
+    pub(crate) add: ScalarMulAddCols<T, P>,
+    pub(crate) double: ScalarMulDoubleCols<T, P>,
+}
+
+/// A chip computing `[k]P` on a Weierstrass curve.
+///
+/// The additions of the double-and-add loop are incomplete, so the chip assumes that `P` is in the
+/// prime-order subgroup and that `0 < k < n`, for which they are never exceptional. Guests only
+/// reach it through the `unsafe` functions of `zkm_lib::utils::WeierstrassScalarMul`, which
+/// document this precondition.
+#[derive(Default)]
+pub struct WeierstrassScalarMulAssignChip<E> {
+    _marker: PhantomData<E>,
+}
+
+impl<E: EllipticCurve + WeierstrassParameters> WeierstrassScalarMulAssignChip<E> {
+    pub const fn new() -> Self {
+        Self { _marker: PhantomData }
+    }
+
+    fn syscall_code() -> SyscallCode {
+        match E::CURVE_TYPE {
+            CurveType::Secp256k1 => SyscallCode::SECP256K1_SCALAR_MUL,
+            CurveType::Secp256r1 => SyscallCode::SECP256R1_SCALAR_MUL,
+            CurveType::Bn254 => SyscallCode::BN254_SCALAR_MUL,
+            CurveType::Bls12381 => SyscallCode::BLS12381_SCALAR_MUL,
+            _ => panic!("Unsupported curve"),
+        }
+    }
+
+    fn populate_add<F: PrimeField32>(
+        blu_events: &mut Vec<ByteLookupEvent>,
+        cols: &mut ScalarMulAddCols<F, E::BaseField>,
+        p: (&BigUint, &BigUint),
+        q: (&BigUint, &BigUint),
+    ) -> (BigUint, BigUint) {
+        let (p_x, p_y) = p;
+        let (q_x, q_y) = q;
+        cols.p_x = E::BaseField::to_limbs_field::<F, _>(p_x);
+        cols.p_y = E::BaseField::to_limbs_field::<F, _>(p_y);
+        cols.q_x = E::BaseField::to_limbs_field::<F, _>(q_x);
+        cols.q_y = E::BaseField::to_limbs_field::<F, _>(q_y);
+
+        // slope = (q.y - p.y) / (q.x - p.x).
+        let slope = {
+            let slope_numerator =
+                cols.slope_numerator.populate(blu_events, q_y, p_y, FieldOperation::Sub);
+            let slope_denominator =
+                cols.slope_denominator.populate(blu_events, q_x, p_x, FieldOperation::Sub);
+            cols.slope.populate(
+                blu_events,
+                &slope_numerator,
+                &slope_denominator,
+                FieldOperation::Div,
+            )
+        };
+
+        // x = slope * slope - (p.x + q.x).
+        let x = {
+            let slope_squared =
+                cols.slope_squared.populate(blu_events, &slope, &slope, FieldOperation::Mul);
+            let p_x_plus_q_x =
+                cols.p_x_plus_q_x.populate(blu_events, p_x, q_x, FieldOperation::Add);
+            cols.x3_ins.populate(blu_events, &slope_squared, &p_x_plus_q_x, FieldOperation::Sub)
+        };
+
+        // y = slope * (p.x - x) - p.y.
+        let y = {
+            let p_x_minus_x = cols.p_x_minus_x.populate(blu_events, p_x, &x, FieldOperation::Sub);
+            let slope_times_p_x_minus_x = cols.slope_times_p_x_minus_x.populate(
+                blu_events,
+                &slope,
+                &p_x_minus_x,
+                FieldOperation::Mul,
+            );
+            cols.y3_ins.populate(blu_events, &slope_times_p_x_minus_x, p_y, FieldOperation::Sub)
+        };
+
+        (x, y)
+    }
+
+    fn populate_double<F: PrimeField32>(
+        blu_events: &mut Vec<ByteLookupEvent>,
+        cols: &mut ScalarMulDoubleCols<F, E::BaseField>,
+        p_x: &BigUint,
+        p_y: &BigUint,
+    ) -> (BigUint, BigUint) {
+        let a = E::a_int();
+
+        // slope = (a + 3 * p.x^2) / (2 * p.y).
+        let slope = {
+            let p_x_squared = cols.p_x_squared.populate(blu_events, p_x, p_x, FieldOperation::Mul);
+            let p_x_squared_times_3 = cols.p_x_squared_times_3.populate(
+                blu_events,
+                &p_x_squared,
+                &BigUint::from(3u32),
+                FieldOperation::Mul,
+            );
+            let slope_numerator = cols.slope_numerator.populate(
+                blu_events,
+                &a,
+                &p_x_squared_times_3,
+                FieldOperation::Add,
+            );
+            let slope_denominator = cols.slope_denominator.populate(
+                blu_events,
+                &BigUint::from(2u32),
+                p_y,
+                FieldOperation::Mul,
+            );
+            cols.slope.populate(
+                blu_events,
+                &slope_numerator,
+                &slope_denominator,
+                FieldOperation::Div,
+            )
+        };
+
+        // x = slope * slope - (p.x + p.x).
+        let x = {
+            let slope_squared =
+                cols.slope_squared.populate(blu_events, &slope, &slope, FieldOperation::Mul);
+            let p_x_plus_p_x =
+                cols.p_x_plus_p_x.populate(blu_events, p_x, p_x, FieldOperation::Add);
+            cols.x3_ins.populate(blu_events, &slope_squared, &p_x_plus_p_x, FieldOperation::Sub)
+        };
+
+        // y = slope * (p.x - x) - p.y.
+        let y = {
+            let p_x_minus_x = cols.p_x_minus_x.populate(blu_events, p_x, &x, FieldOperation::Sub);
+            let slope_times_p_x_minus_x = cols.slope_times_p_x_minus_x.populate(
+                blu_events,
+                &slope,
+                &p_x_minus_x,
+                FieldOperation::Mul,
+            );
+            cols.y3_ins.populate(blu_events, &slope_times_p_x_minus_x, p_y, FieldOperation::Sub)
+        };
+
+        (x, y)
+    }
+
+    /// Populates the rows of a single scalar multiplication.
+    fn populate_event<F: PrimeField32>(
+        event: &EllipticCurveScalarMulEvent,
+        rows: &mut [F],
+        blu_events: &mut Vec<ByteLookupEvent>,
+    ) {
+        let num_cols = num_weierstrass_scalar_mul_cols::<E::BaseField>();
+        let zero = BigUint::zero();
+
+        let p = AffinePoint::<E>::from_words_le(&event.p);
+        let result_words =
+            event.p_memory_records.iter().map(|record| record.value).collect::<Vec<_>>();
+        let result = AffinePoint::<E>::from_words_le(&result_words);
+        let k = BigUint::from_slice(&event.k);
+        let k_bits = (0..NUM_SCALAR_MUL_ROWS).map(|i| k.bit(i as u64)).collect::<Vec<_>>();
+
+        let mut acc: Option<(BigUint, BigUint)> = None;
+        let mut temp = (p.x, p.y);
+
+        for (i, row) in rows.chunks_mut(num_cols).enumerate() {
+            let cols: &mut WeierstrassScalarMulAssignCols<F, E::BaseField> = row.borrow_mut();
+
+            cols.is_real = F::ONE;
+            cols.populate_flags(i);
+            cols.shard = F::from_canonical_u32(event.shard);
+            cols.clk = F::from_canonical_u32(event.clk);
+            cols.p_ptr = F::from_canonical_u32(event.p_ptr);
+            cols.k_ptr = F::from_canonical_u32(event.k_ptr);
+
+            if i == 0 {
+                for (access, record) in cols.k_access.iter_mut().zip(event.k_memory_records.iter())
+                {
+                    access.populate(*record, blu_events);
+                }
+                for (access, record) in cols.p_access.iter_mut().zip(event.p_memory_records.iter())
+                {
+                    access.populate(*record, blu_events);
+                }
+            }
+
+            cols.result_x = E::BaseField::to_limbs_field::<F, _>(&result.x);
+            cols.result_y = E::BaseField::to_limbs_field::<F, _>(&result.y);
+            for (j, bit) in cols.k_bits.iter_mut().enumerate() {
+                *bit = F::from_bool(k_bits.get(i + j).copied().unwrap_or(false));
+            }
+
+            let bit = k_bits[i];
+            cols.acc_is_infinity = F::from_bool(acc.is_none());
+            cols.is_set = F::from_bool(bit && acc.is_none());
+            cols.is_add = F::from_bool(bit && acc.is_some());
+            if let Some((acc_x, acc_y)) = &acc {
+                cols.acc_x = E::BaseField::to_limbs_field::<F, _>(acc_x);
+                cols.acc_y = E::BaseField::to_limbs_field::<F, _>(acc_y);
+            }
+            cols.temp_x = E::BaseField::to_limbs_field::<F, _>(&temp.0);
+            cols.temp_y = E::BaseField::to_limbs_field::<F, _>(&temp.1);
+
+            // The addition is only constrained to its inputs when `is_add` is set, so it runs on
+            // zeros otherwise to avoid a division by zero.
+            acc = match (bit, acc) {
+                (false, acc) => {
+                    Self::populate_add(blu_events, &mut cols.add, (&zero, &zero), (&zero, &zero));
+                    acc
+                }
+                (true, None) => {
+                    Self::populate_add(blu_events, &mut cols.add, (&zero, &zero), (&zero, &zero));
+                    Some(temp.clone())
+                }
+                (true, Some((acc_x, acc_y))) => Some(Self::populate_add(
+                    blu_events,
+                    &mut cols.add,
+                    (&acc_x, &acc_y),
+                    (&temp.0, &temp.1),
+                )),
+            };
+            if let Some((acc_x, acc_y)) = &acc {
+                cols.next_acc_x = E::BaseField::to_limbs_field::<F, _>(acc_x);
+                cols.next_acc_y = E::BaseField::to_limbs_field::<F, _>(acc_y);
+            }
+
+            temp = Self::populate_double(blu_events, &mut cols.double, &temp.0, &temp.1);
+        }
+    }
+}
+
+impl<F: Field, P: FieldParameters + NumWords> WeierstrassScalarMulAssignCols<F, P> {
+    pub fn populate_flags(&mut self, i: usize) {
+        // The generator of the multiplicative subgroup.
+        let g = F::from_canonical_u32(KoalaBear::two_adic_generator(8).as_canonical_u32());
+
+        // Populate the columns needed to keep track of cycles of 256 rows.
+        self.cycle_256 = g.exp_u64((i + 1) as u64);
+
+        // Populate the columns needed to track the start of a cycle of 256 rows.
+        self.cycle_256_start.populate_from_field_element(self.cycle_256 - g);
+
+        // Populate the columns needed to track the end of a cycle of 256 rows.
+        self.cycle_256_end.populate_from_field_element(self.cycle_256 - F::ONE);
+
+        self.start = self.cycle_256_start.result * self.is_real;
+        self.end = self.cycle_256_end.result * self.is_real;
+    }
+}
+
+impl<F: PrimeField32, E: EllipticCurve + WeierstrassParameters> MachineAir<F>
+    for WeierstrassScalarMulAssignChip<E>
+{
+    type Record = ExecutionRecord;
+    type Program = Program;
+    type Error = CoreChipError;
+
+    fn name(&self) -> String {
+        match E::CURVE_TYPE {
+            CurveType::Secp256k1 => "Secp256k1ScalarMulAssign".to_string(),
+            CurveType::Secp256r1 => "Secp256r1ScalarMulAssign".to_string(),
+            CurveType::Bn254 => "Bn254ScalarMulAssign".to_string(),
+            CurveType::Bls12381 => "Bls12381ScalarMulAssign".to_string(),
+            _ => panic!("Unsupported curve"),
+        }
+    }
+
+    fn generate_dependencies(
+        &self,
+        input: &Self::Record,
+        output: &mut Self::Record,
+    ) -> Result<(), Self::Error> {
+        let events = input.get_precompile_events(Self::syscall_code());
+        let num_cols = num_weierstrass_scalar_mul_cols::<E::BaseField>();
+        let chunk_size = std::cmp::max(events.len() / num_cpus::get(), 1);
+
+        let blu_events: Vec<Vec<ByteLookupEvent>> = events
+            .par_chunks(chunk_size)
+            .map(|ops| {
+                let mut blu = Vec::new();
+                let mut rows = zeroed_f_vec::<F>(NUM_SCALAR_MUL_ROWS * num_cols);
+                ops.iter().for_each(|(_, op)| match op {
+                    PrecompileEvent::Secp256k1ScalarMul(event)
+                    | PrecompileEvent::Secp256r1ScalarMul(event)
+                    | PrecompileEvent::Bn254ScalarMul(event)
+                    | PrecompileEvent::Bls12381ScalarMul(event) => {
+                        Self::populate_event(event, &mut rows, &mut blu);
+                    }
+                    _ => unreachable!(),
+                });
+                blu
+            })
+            .collect();
+
+        for blu in blu_events {
+            output.add_byte_lookup_events(blu);
+        }
+        Ok(())
+    }
+
+    fn generate_trace(
+        &self,
+        input: &ExecutionRecord,
+        _: &mut ExecutionRecord,
+    ) -> Result<RowMajorMatrix<F>, Self::Error> {
+        let events = input.get_precompile_events(Self::syscall_code());
+
+        let num_cols = num_weierstrass_scalar_mul_cols::<E::BaseField>();
+        let num_real_rows = events.len() * NUM_SCALAR_MUL_ROWS;
+        let num_rows = input
+            .fixed_log2_rows::<F, _>(self)
+            .map(|x| 1 << x)
+            .unwrap_or(std::cmp::max(num_real_rows.next_power_of_two(), 4));
+        let mut values = zeroed_f_vec(num_rows * num_cols);
+
+        // The padding rows double the point `(0, 1)`, so that the doubling slope is well defined.
+        let mut dummy_row = zeroed_f_vec(num_cols);
+        let cols: &mut WeierstrassScalarMulAssignCols<F, E::BaseField> =
+            dummy_row.as_mut_slice().borrow_mut();
+        let zero = BigUint::zero();
+        let one = BigUint::one();
+        cols.temp_y = E::BaseField::to_limbs_field::<F, _>(&one);
+        Self::populate_add(&mut vec![], &mut cols.add, (&zero, &zero), (&zero, &zero));
+        Self::populate_double(&mut vec![], &mut cols.double, &zero, &one);
+
+        let (real_values, padding_values) = values.split_at_mut(num_real_rows * num_cols);
+        real_values.par_chunks_mut(NUM_SCALAR_MUL_ROWS * num_cols).enumerate().for_each(
+            |(i, rows)| match &events[i].1 {
+                PrecompileEvent::Secp256k1ScalarMul(event)
+                | PrecompileEvent::Secp256r1ScalarMul(event)
+                | PrecompileEvent::Bn254ScalarMul(event)
+                | PrecompileEvent::Bls12381ScalarMul(event) => {
+                    Self::populate_event(event, rows, &mut vec![]);
+                }
+                _ => unreachable!(),
+            },
+        );
+        padding_values.par_chunks_mut(num_cols).enumerate().for_each(|(i, row)| {
+            row.copy_from_slice(&dummy_row);
+            let cols: &mut WeierstrassScalarMulAssignCols<F, E::BaseField> = row.borrow_mut();
+            cols.populate_flags(num_real_rows + i);
+        });
+
+        // Convert the trace to a row major matrix.
+        Ok(RowMajorMatrix::new(values, num_cols))
+    }
+
+    fn included(&self, shard: &Self::Record) -> bool {
+        if let Some(shape) = shard.shape.as_ref() {
+            shape.included::<F, _>(self)
+        } else {
+            !shard.get_precompile_events(Self::syscall_code()).is_empty()
+        }
+    }
+
+    fn local_only(&self) -> bool {
+        true
+    }
+}
+
+impl<F, E: EllipticCurve + WeierstrassParameters> BaseAir<F> for WeierstrassScalarMulAssignChip<E> {
+    fn width(&self) -> usize {
+        num_weierstrass_scalar_mul_cols::<E::BaseField>()
+    }
+}
+
+impl<AB, E: EllipticCurve + WeierstrassParameters> Air<AB> for WeierstrassScalarMulAssignChip<E>
+where
+    AB: ZKMAirBuilder,
+    Limbs<AB::Var, <E::BaseField as NumLimbs>::Limbs>: Copy,
+{
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let (local, next) = (main.row_slice(0), main.row_slice(1));
+        let local: &WeierstrassScalarMulAssignCols<AB::Var, E::BaseField> = (*local).borrow();
+        let next: &WeierstrassScalarMulAssignCols<AB::Var, E::BaseField> = (*next).borrow();
+
+        let num_limbs = E::BaseField::NB_LIMBS;
+        let num_words_field_element = <E::BaseField as NumLimbs>::Limbs::USIZE / 4;
+
+        self.eval_flags(builder, local, next);
+
+        // On the first row, the accumulator is the point at infinity, `temp` is the input point and
+        // the point is overwritten by the result.
+        let p_x: Limbs<AB::Var, <E::BaseField as NumLimbs>::Limbs> =
+            limbs_from_prev_access(&local.p_access[0..num_words_field_element]);
+        let p_y: Limbs<AB::Var, <E::BaseField as NumLimbs>::Limbs> =
+            limbs_from_prev_access(&local.p_access[num_words_field_element..]);
+        let result_x: Limbs<AB::Var, <E::BaseField as NumLimbs>::Limbs> =
+            limbs_from_access(&local.p_access[0..num_words_field_element]);
+        let result_y: Limbs<AB::Var, <E::BaseField as NumLimbs>::Limbs> =
+            limbs_from_access(&local.p_access[num_words_field_element..]);
+        builder.when(local.start).assert_one(local.acc_is_infinity);
+        for i in 0..num_limbs {
+            builder.when(local.start).assert_eq(local.temp_x[i], p_x[i]);
+            builder.when(local.start).assert_eq(local.temp_y[i], p_y[i]);
+            builder.when(local.start).assert_eq(local.result_x[i], result_x[i]);
+            builder.when(local.start).assert_eq(local.result_y[i], result_y[i]);
+        }
+
+        // On the first row, the scalar bits must match the scalar read from memory.
+        for (i, access) in local.k_access.iter().enumerate() {
+            for (j, byte) in access.value().0.iter().enumerate() {
+                let bits = &local.k_bits[32 * i + 8 * j..32 * i + 8 * (j + 1)];
+                let value = bits.iter().enumerate().fold(AB::Expr::zero(), |acc, (k, bit)| {
+                    acc + *bit * AB::F::from_canonical_u32(1 << k)
+                });
+                builder.when(local.start).assert_eq(value, *byte);
+            }
+        }
+        for bit in local.k_bits.iter() {
+            builder.when(local.start).assert_bool(*bit);
+        }
+
+        // Select between keeping, setting or adding to the accumulator based on the current bit.
+        let bit = local.k_bits[0];
+        builder.assert_bool(local.acc_is_infinity);
+        builder.assert_eq(local.is_set, bit * local.acc_is_infinity);
+        builder.assert_eq(local.is_add, bit - local.is_set);
+        for i in 0..num_limbs {
+            builder.when(local.is_add).assert_eq(local.add.p_x[i], local.acc_x[i]);
+            builder.when(local.is_add).assert_eq(local.add.p_y[i], local.acc_y[i]);
+            builder.when(local.is_add).assert_eq(local.add.q_x[i], local.temp_x[i]);
+            builder.when(local.is_add).assert_eq(local.add.q_y[i], local.temp_y[i]);
+
+            builder.assert_eq(
+                local.next_acc_x[i],
+                AB::Expr::from(local.acc_x[i])
+                    + local.is_set * (local.temp_x[i] - local.acc_x[i])
+                    + local.is_add * (local.add.x3_ins.result[i] - local.acc_x[i]),
+            );
+            builder.assert_eq(
+                local.next_acc_y[i],
+                AB::Expr::from(local.acc_y[i])
+                    + local.is_set * (local.temp_y[i] - local.acc_y[i])
+                    + local.is_add * (local.add.y3_ins.result[i] - local.acc_y[i]),
+            );
+        }
+
+        self.eval_add(builder, &local.add, local.is_real);
+        self.eval_double(builder, &local.double, &local.temp_x, &local.temp_y, local.is_real);
+
+        // Within a cycle, the next row continues the loop with `[2^(i+1)]P`, the updated
+        // accumulator and the remaining bits.
+        let mut transition = builder.when_transition();
+        let mut transition = transition.when(local.is_real);
+        let mut transition = transition.when_not(local.end);
+        transition.assert_eq(local.shard, next.shard);
+        transition.assert_eq(local.clk, next.clk);
+        transition.assert_eq(local.p_ptr, next.p_ptr);
+        transition.assert_eq(local.k_ptr, next.k_ptr);
+        transition.assert_eq(local.is_real, next.is_real);
+        transition.assert_eq(local.acc_is_infinity - local.is_set, next.acc_is_infinity);
+        for i in 0..num_limbs {
+            transition.assert_eq(local.result_x[i], next.result_x[i]);
+            transition.assert_eq(local.result_y[i], next.result_y[i]);
+            transition.assert_eq(local.next_acc_x[i], next.acc_x[i]);
+            transition.assert_eq(local.next_acc_y[i], next.acc_y[i]);
+            transition.assert_eq(local.double.x3_ins.result[i], next.temp_x[i]);
+            transition.assert_eq(local.double.y3_ins.result[i], next.temp_y[i]);
+        }
+        for i in 0..NUM_SCALAR_MUL_ROWS - 1 {
+            transition.assert_eq(local.k_bits[i + 1], next.k_bits[i]);
+        }
+
+        // On the last row, the accumulator is the result and is not the point at infinity.
+        builder.when(local.end).assert_zero(local.acc_is_infinity - local.is_set);
+        for i in 0..num_limbs {
+            builder.when(local.end).assert_eq(local.next_acc_x[i], local.result_x[i]);
+            builder.when(local.end).assert_eq(local.next_acc_y[i], local.result_y[i]);
+        }
+
+        builder.eval_memory_access_slice(
+            local.shard,
+            local.clk.into(),
+            local.k_ptr,
+            &local.k_access,
+            local.start,
+        );
+        builder.eval_memory_access_slice(
+            local.shard,
+            local.clk + AB::F::from_canonical_u32(1), /* We write p at +1 since p, k could
+                                                       * overlap. */
+            local.p_ptr,
+            &local.p_access,
+            local.start,
+        );
+
+        builder.receive_syscall(
+            local.shard,
+            local.clk,
+            AB::F::from_canonical_u32(Self::syscall_code().syscall_id()),
+            local.p_ptr,
+            local.k_ptr,
+            local.start,
+            LookupScope::Local,
+        );
+    }
+}
+
+impl<E: EllipticCurve + WeierstrassParameters> WeierstrassScalarMulAssignChip<E> {
+    fn eval_flags<AB: ZKMAirBuilder>(
+        &self,
+        builder: &mut AB,
+        local: &WeierstrassScalarMulAssignCols<AB::Var, E::BaseField>,
+        next: &WeierstrassScalarMulAssignCols<AB::Var, E::BaseField>,
+    ) {
+        // Generator with order 256 within KoalaBear.
+        let g = AB::F::from_canonical_u32(KoalaBear::two_adic_generator(8).as_canonical_u32());
+
+        // First row of the table must have g^1.
+        builder.when_first_row().assert_eq(local.cycle_256, g);
+
+        // Every row's `cycle_256` must be previous multiplied by `g`.
+        builder.when_transition().assert_eq(local.cycle_256 * g, next.cycle_256);
+
+        // Constrain `cycle_256_start.result` to be `cycle_256 - g == 0`.
+        IsZeroOperation::<AB::F>::eval(
+            builder,
+            local.cycle_256 - AB::Expr::from(g),
+            local.cycle_256_start,
+            AB::Expr::one(),
+        );
+
+        // Constrain `cycle_256_end.result` to be `cycle_256 - 1 == 0`. Intuitively g^256 is 1.
+        IsZeroOperation::<AB::F>::eval(
+            builder,
+            local.cycle_256 - AB::Expr::one(),
+            local.cycle_256_end,
+            AB::Expr::one(),
+        );
+
+        builder.assert_bool(local.is_real);
+        builder.assert_eq(local.cycle_256_start.result * local.is_real, local.start);
+        builder.assert_eq(local.cycle_256_end.result * local.is_real, local.end);
+
+        // A real cycle must start on the first row of a 256-row cycle.
+        builder
+            .when_transition()
+            .when_not(local.is_real)
+            .when(next.is_real)
+            .assert_one(next.cycle_256_start.result);
+
+        // Since each scalar multiplication is 256 rows, a padded table may end with a real row. In
+        // that case, the last row must close a 256-row cycle.
+        builder.when_last_row().when(local.is_real).assert_one(local.cycle_256_end.result);
+    }
+
+    fn eval_add<AB: ZKMAirBuilder>(
+        &self,
+        builder: &mut AB,
+        cols: &ScalarMulAddCols<AB::Var, E::BaseField>,
+        is_real: AB::Var,
+    ) where
+        Limbs<AB::Var, <E::BaseField as NumLimbs>::Limbs>: Copy,
+    {
+        // slope = (q.y - p.y) / (q.x - p.x).
+        cols.slope_numerator.eval(builder, &cols.q_y, &cols.p_y, FieldOperation::Sub, is_real);
+        cols.slope_denominator.eval(builder, &cols.q_x, &cols.p_x, FieldOperation::Sub, is_real);
+        cols.slope.eval(
+            builder,
+            &cols.slope_numerator.result,
+            &cols.slope_denominator.result,
+            FieldOperation::Div,
+            is_real,
+        );
+        let slope = &cols.slope.result;
+
+        // x = slope * slope - (p.x + q.x).
+        cols.slope_squared.eval(builder, slope, slope, FieldOperation::Mul, is_real);
+        cols.p_x_plus_q_x.eval(builder, &cols.p_x, &cols.q_x, FieldOperation::Add, is_real);
+        cols.x3_ins.eval(
+            builder,
+            &cols.slope_squared.result,
+            &cols.p_x_plus_q_x.result,
+            FieldOperation::Sub,
+            is_real,
+        );
+
+        // y = slope * (p.x - x) - p.y.
+        cols.p_x_minus_x.eval(
+            builder,
+            &cols.p_x,
+            &cols.x3_ins.result,
+            FieldOperation::Sub,
+            is_real,
+        );
+        cols.slope_times_p_x_minus_x.eval(
+            builder,
+            slope,
+            &cols.p_x_minus_x.result,
+            FieldOperation::Mul,
+            is_real,
+        );
+        cols.y3_ins.eval(
+            builder,
+            &cols.slope_times_p_x_minus_x.result,
+            &cols.p_y,
+            FieldOperation::Sub,
+            is_real,
+        );
+    }
+
+    fn eval_double<AB: ZKMAirBuilder>(
+        &self,
+        builder: &mut AB,
+        cols: &ScalarMulDoubleCols<AB::Var, E::BaseField>,
+        p_x: &Limbs<AB::Var, <E::BaseField as NumLimbs>::Limbs>,
+        p_y: &Limbs<AB::Var, <E::BaseField as NumLimbs>::Limbs>,
+        is_real: AB::Var,
+    ) where
+        Limbs<AB::Var, <E::BaseField as NumLimbs>::Limbs>: Copy,
+    {
+        // `a` in the Weierstrass form: y^2 = x^3 + a * x + b.
+        let a = E::BaseField::to_limbs_field::<AB::Expr, _>(&E::a_int());
+
+        // slope = (a + 3 * p.x^2) / (2 * p.y).
+        cols.p_x_squared.eval(builder, p_x, p_x, FieldOperation::Mul, is_real);
+        cols.p_x_squared_times_3.eval(
+            builder,
+            &cols.p_x_squared.result,
+            &E::BaseField::to_limbs_field::<AB::Expr, _>(&BigUint::from(3u32)),
+            FieldOperation::Mul,
+            is_real,
+        );
+        cols.slope_numerator.eval(
+            builder,
+            &a,
+            &cols.p_x_squared_times_3.result,
+            FieldOperation::Add,
+            is_real,
+        );
+        cols.slope_denominator.eval(
+            builder,
+            &E::BaseField::to_limbs_field::<AB::Expr, _>(&BigUint::from(2u32)),
+            p_y,
+            FieldOperation::Mul,
+            is_real,
+        );
+        cols.slope.eval(
+            builder,
+            &cols.slope_numerator.result,
+            &cols.slope_denominator.result,
+            FieldOperation::Div,
+            is_real,
+        );
+        let slope = &cols.slope.result;
+
+        // x = slope * slope - (p.x + p.x).
+        cols.slope_squared.eval(builder, slope, slope, FieldOperation::Mul, is_real);
+        cols.p_x_plus_p_x.eval(builder, p_x, p_x, FieldOperation::Add, is_real);
+        cols.x3_ins.eval(
+            builder,
+            &cols.slope_squared.result,
+            &cols.p_x_plus_p_x.result,
+            FieldOperation::Sub,
+            is_real,
+        );
+
+        // y = slope * (p.x - x) - p.y.
+        cols.p_x_minus_x.eval(builder, p_x, &cols.x3_ins.result, FieldOperation::Sub, is_real);
+        cols.slope_times_p_x_minus_x.eval(
+            builder,
+            slope,
+            &cols.p_x_minus_x.result,
+            FieldOperation::Mul,
+            is_real,
+        );
+        cols.y3_ins.eval(
+            builder,
+            &cols.slope_times_p_x_minus_x.result,
+            p_y,
+            FieldOperation::Sub,
+            is_real,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_artifacts::{
+        BLS12381_SCALAR_MUL_ELF, BN254_SCALAR_MUL_ELF, SECP256K1_SCALAR_MUL_ELF,
+        SECP256R1_SCALAR_MUL_ELF,
+    };
+    use zkm_core_executor::Program;
+    use zkm_stark::CpuProver;
+
+    use crate::utils::{run_test, setup_logger};
+
+    #[test]
+    fn test_secp256k1_scalar_mul() {
+        setup_logger();
+        let program = Program::from(SECP256K1_SCALAR_MUL_ELF).unwrap();
+        run_test::<CpuProver<_, _>>(program).unwrap();
+    }
+
+    #[test]
+    fn test_secp256r1_scalar_mul() {
+        setup_logger();
+        let program = Program::from(SECP256R1_SCALAR_MUL_ELF).unwrap();
+        run_test::<CpuProver<_, _>>(program).unwrap();
+    }
+
+    #[test]
+    fn test_bn254_scalar_mul() {
+        setup_logger();
+        let program = Program::from(BN254_SCALAR_MUL_ELF).unwrap();
+        run_test::<CpuProver<_, _>>(program).unwrap();
+    }
+
+    #[test]
+    fn test_bls12381_scalar_mul() {
+        setup_logger();
+        let program = Program::from(BLS12381_SCALAR_MUL_ELF).unwrap();
+        run_test::<CpuProver<_, _>>(program).unwrap();
+    }
+}
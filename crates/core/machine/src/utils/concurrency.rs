@@ -9,7 +9,12 @@ pub struct TurnBasedSync {
 impl TurnBasedSync {
     /// Creates a new [TurnBasedSync].
     pub fn new() -> Self {
-        TurnBasedSync { current_turn: Mutex::new(0), cv: Condvar::new() }
+        Self::starting_at(0)
+    }
+
+    /// Creates a new [TurnBasedSync] whose first turn is `turn`.
+    pub fn starting_at(turn: usize) -> Self {
+        TurnBasedSync { current_turn: Mutex::new(turn), cv: Condvar::new() }
     }
 
     /// Waits for the current turn to be equal to the given turn.
@@ -0,0 +1,298 @@
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+use p3_field::PrimeField32;
+use p3_util::log2_ceil_usize;
+use strum::IntoEnumIterator;
+use zkm_core_executor::{
+    syscalls::SyscallCode, ExecutionRecord, ExecutionReport, Executor, MipsAirId, Program,
+    ZKMContext,
+};
+use zkm_stark::{MachineRecord, StarkGenericConfig, StarkMachine, ZKMCoreOpts};
+
+use crate::{io::ZKMStdin, mips::MipsAir, shape::CoreShapeConfig};
+
+use super::{trace_checkpoint, ZKMCoreProverError};
+
+/// The kind of events a shard proves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShardKind {
+    /// A shard of cpu cycles, possibly packed with the memory init and finalize events.
+    Core,
+    /// A shard of deferred memory init and finalize events.
+    Memory,
+    /// A shard of deferred events of a precompile.
+    Precompile(SyscallCode),
+}
+
+/// A chip of a shard that is taller than in any of the allowed shapes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChipOverflow {
+    /// The chip.
+    pub air: MipsAirId,
+    /// The log2 height of the chip in the shard.
+    pub log2_height: usize,
+    /// The largest log2 height of the chip in the allowed shapes, or `None` if no allowed shape
+    /// includes it.
+    pub max_log2_height: Option<usize>,
+}
+
+/// How a shard fits into the allowed shapes.
+#[derive(Debug, Clone)]
+pub struct ShardFit {
+    /// The index of the shard, starting at 1 like the shard public value.
+    pub shard: u32,
+    /// The kind of events the shard proves.
+    pub kind: ShardKind,
+    /// The log2 height of each chip of the shard.
+    pub log2_heights: Vec<(MipsAirId, usize)>,
+    /// The reason the shard fits no allowed shape, or `None` if it fits.
+    pub error: Option<String>,
+    /// The chips taller than in any allowed shape.
+    pub overflows: Vec<ChipOverflow>,
+}
+
+impl ShardFit {
+    /// Whether the shard fits an allowed shape.
+    #[must_use]
+    pub fn fits(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// The shards the deferred events of a precompile were split into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PrecompileShards {
+    /// The syscall of the precompile.
+    pub syscall: SyscallCode,
+    /// The number of deferred events.
+    pub events: usize,
+    /// The number of shards holding the events.
+    pub shards: usize,
+}
+
+/// The result of fitting every shard of an execution into the allowed shapes, as returned by
+/// [`dry_run`].
+#[derive(Debug, Clone, Default)]
+pub struct DryRunReport {
+    /// Every shard, in proving order.
+    pub shards: Vec<ShardFit>,
+    /// The precompiles whose deferred events got shards of their own.
+    pub precompile_shards: Vec<PrecompileShards>,
+    /// The report of the execution.
+    pub report: ExecutionReport,
+}
+
+impl DryRunReport {
+    /// Whether every shard fits an allowed shape.
+    #[must_use]
+    pub fn fits(&self) -> bool {
+        self.shards.iter().all(ShardFit::fits)
+    }
+
+    /// The shards that fit no allowed shape.
+    pub fn misfits(&self) -> impl Iterator<Item = &ShardFit> {
+        self.shards.iter().filter(|shard| !shard.fits())
+    }
+}
+
+impl Display for DryRunReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let core_shards = self.shards.iter().filter(|shard| shard.kind == ShardKind::Core).count();
+        writeln!(
+            f,
+            "shards: {} ({} core, {} misfit)",
+            self.shards.len(),
+            core_shards,
+            self.misfits().count()
+        )?;
+        for shard in self.misfits() {
+            writeln!(
+                f,
+                "shard {} ({:?}): {}",
+                shard.shard,
+                shard.kind,
+                shard.error.as_ref().unwrap()
+            )?;
+            for overflow in &shard.overflows {
+                match overflow.max_log2_height {
+                    Some(max) => {
+                        writeln!(f, "  {}: 2^{} > 2^{}", overflow.air, overflow.log2_height, max)?
+                    }
+                    None => writeln!(
+                        f,
+                        "  {}: 2^{} but no allowed shape includes it",
+                        overflow.air, overflow.log2_height
+                    )?,
+                }
+            }
+        }
+        if !self.precompile_shards.is_empty() {
+            writeln!(f, "precompile shards:")?;
+            for precompile in &self.precompile_shards {
+                writeln!(
+                    f,
+                    "  {}: {} events in {} shards",
+                    precompile.syscall, precompile.events, precompile.shards
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Executes `program` and fits the shards the prover would create into the shapes of
+/// `shape_config`, without generating any traces.
+///
+/// The records are split and deferred like in [`super::prove_with_context`], so the shards match
+/// the ones of a proving job with the same options. The preprocessed shape of `program` must
+/// already be fixed.
+pub fn dry_run<SC: StarkGenericConfig>(
+    machine: &StarkMachine<SC, MipsAir<SC::Val>>,
+    program: Program,
+    stdin: &ZKMStdin,
+    opts: ZKMCoreOpts,
+    context: ZKMContext,
+    shape_config: &CoreShapeConfig<SC::Val>,
+) -> Result<DryRunReport, ZKMCoreProverError>
+where
+    SC::Val: PrimeField32,
+{
+    let mut runtime = Executor::with_context(program.clone(), opts, context);
+    runtime.maximal_shapes = Some(
+        shape_config.maximal_core_shapes(opts.shard_size.ilog2() as usize).into_iter().collect(),
+    );
+    runtime.write_vecs(&stdin.buffer);
+    for (proof, vk) in stdin.proofs.iter() {
+        runtime.write_proof(proof.clone(), vk.clone());
+    }
+
+    let mut dry_run = DryRunReport::default();
+    let mut deferred = ExecutionRecord::new(program.clone().into());
+    loop {
+        // Execute the next checkpoint and regenerate its events.
+        let (checkpoint, done) =
+            runtime.execute_state(false).map_err(ZKMCoreProverError::ExecutionError)?;
+        let (mut records, report) =
+            trace_checkpoint::<SC>(program.clone(), checkpoint, opts, Some(shape_config));
+        dry_run.report += report;
+
+        for record in records.iter_mut() {
+            deferred.append(&mut record.defer());
+        }
+
+        // Pack the memory init/finalize events into the last record if they are few, falling back
+        // to separate shards if the packed records do not fit.
+        let pack = done
+            && runtime.state.global_clk < 1 << 21
+            && deferred.global_memory_initialize_events.len()
+                < opts.split_opts.combine_memory_threshold
+            && deferred.global_memory_finalize_events.len()
+                < opts.split_opts.combine_memory_threshold;
+        let mut shards = None;
+        if pack {
+            let mut packed = records.clone();
+            let mut packed_deferred = deferred.clone();
+            let mut deferred_records =
+                packed_deferred.split(done, packed.last_mut(), opts.split_opts);
+            packed.append(&mut deferred_records);
+            let fits = fit_records(machine, &mut packed, &opts, shape_config, &dry_run)?;
+            if fits.iter().all(ShardFit::fits) {
+                deferred = packed_deferred;
+                shards = Some(fits);
+            }
+        }
+        let shards = match shards {
+            Some(shards) => shards,
+            None => {
+                let mut deferred_records = deferred.split(done, None, opts.split_opts);
+                records.append(&mut deferred_records);
+                fit_records(machine, &mut records, &opts, shape_config, &dry_run)?
+            }
+        };
+
+        for shard in shards.iter() {
+            if let ShardKind::Precompile(syscall) = shard.kind {
+                match dry_run.precompile_shards.iter_mut().find(|p| p.syscall == syscall) {
+                    Some(precompile) => precompile.shards += 1,
+                    None => dry_run.precompile_shards.push(PrecompileShards {
+                        syscall,
+                        events: 0,
+                        shards: 1,
+                    }),
+                }
+            }
+        }
+        dry_run.shards.extend(shards);
+
+        if done {
+            break;
+        }
+    }
+
+    for precompile in dry_run.precompile_shards.iter_mut() {
+        precompile.events = dry_run.report.syscall_counts[precompile.syscall] as usize;
+    }
+    Ok(dry_run)
+}
+
+/// Generates the dependencies of `records` and fits each of them into the allowed shapes.
+fn fit_records<SC: StarkGenericConfig>(
+    machine: &StarkMachine<SC, MipsAir<SC::Val>>,
+    records: &mut [ExecutionRecord],
+    opts: &ZKMCoreOpts,
+    shape_config: &CoreShapeConfig<SC::Val>,
+    dry_run: &DryRunReport,
+) -> Result<Vec<ShardFit>, ZKMCoreProverError>
+where
+    SC::Val: PrimeField32,
+{
+    machine.generate_dependencies(records, opts, None).map_err(|e| {
+        tracing::error!("Error generating dependencies: {:?}", e);
+        ZKMCoreProverError::DependenciesGenerationError
+    })?;
+
+    let first_shard = dry_run.shards.len() as u32 + 1;
+    Ok(records
+        .iter_mut()
+        .zip(first_shard..)
+        .map(|(record, shard)| {
+            record.public_values.shard = shard;
+            let kind = if record.contains_cpu() {
+                ShardKind::Core
+            } else if let Some(syscall) = SyscallCode::iter().find(|syscall| {
+                syscall.as_air_id().is_some()
+                    && record.precompile_events.get_events(*syscall).is_some_and(|e| !e.is_empty())
+            }) {
+                ShardKind::Precompile(syscall)
+            } else {
+                ShardKind::Memory
+            };
+
+            let log2_heights = shape_config
+                .record_heights(record)
+                .into_iter()
+                .filter(|(_, height)| *height > 0)
+                .map(|(air, height)| (air, log2_ceil_usize(height)))
+                .collect::<Vec<_>>();
+            let error = shape_config.fix_shape(record).err().map(|e| e.to_string());
+            let overflows = if error.is_some() {
+                let max_log2_heights = shape_config.max_log2_heights(record);
+                log2_heights
+                    .iter()
+                    .filter_map(|&(air, log2_height)| {
+                        let max_log2_height = max_log2_heights.get(&air).copied();
+                        (max_log2_height < Some(log2_height)).then_some(ChipOverflow {
+                            air,
+                            log2_height,
+                            max_log2_height,
+                        })
+                    })
+                    .collect()
+            } else {
+                Vec::new()
+            };
+
+            ShardFit { shard, kind, log2_heights, error, overflows }
+        })
+        .collect())
+}
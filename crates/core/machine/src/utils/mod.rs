@@ -1,12 +1,16 @@
 pub mod concurrency;
+mod dry_run;
 mod logger;
 mod prove;
+mod resume;
 mod span;
 mod tracer;
 
+pub use dry_run::*;
 pub use logger::*;
 use p3_field::Field;
 pub use prove::*;
+pub use resume::*;
 pub use span::*;
 pub use tracer::*;
 use zkm_curves::params::Limbs;
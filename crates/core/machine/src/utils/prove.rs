@@ -9,6 +9,7 @@ use std::{
     io::{
         Seek, {self},
     },
+    path::Path,
     sync::{mpsc::sync_channel, Arc, Mutex},
 };
 use thiserror::Error;
@@ -23,22 +24,26 @@ use p3_koala_bear::KoalaBear;
 use crate::shape::CoreShapeConfig;
 use crate::{
     io::ZKMStdin,
-    utils::{chunk_vec, concurrency::TurnBasedSync},
+    utils::{
+        chunk_vec,
+        concurrency::TurnBasedSync,
+        resume::{CheckpointDir, CheckpointManifest, RecordGenState},
+    },
 };
 use zkm_core_executor::{
     events::{format_table_line, sorted_table_lines},
     subproof::NoOpSubproofVerifier,
     ExecutionError, ExecutionRecord, ExecutionReport, ExecutionState, Executor, Program,
-    ZKMContext,
+    ProveProgress, ZKMContext,
 };
 use zkm_primitives::io::ZKMPublicValues;
 
 use zkm_stark::{
     air::{MachineAir, PublicValues},
-    Com, CpuProver, DebugConstraintBuilder, LookupBuilder, MachineProof, MachineProver,
-    MachineRecord, OpeningProof, PcsProverData, ProverConstraintFolder, StarkGenericConfig,
-    StarkMachine, StarkProvingKey, StarkVerifyingKey, UniConfig, Val, VerifierConstraintFolder,
-    ZKMCoreOpts,
+    Com, CpuProver, DebugConstraintBuilder, FriParameters, LookupBuilder, MachineProof,
+    MachineProver, MachineRecord, OpeningProof, PcsProverData, ProverConstraintFolder,
+    SecurityLevel, StarkGenericConfig, StarkMachine, StarkProvingKey, StarkVerifyingKey, UniConfig,
+    Val, VerifierConstraintFolder, ZKMCoreOpts,
 };
 
 #[derive(Error, Debug)]
@@ -53,6 +58,23 @@ pub enum ZKMCoreProverError {
     TracesGenerationError,
     #[error("dependencies generation error")]
     DependenciesGenerationError,
+    #[error("checkpoint directory {0} belongs to a different program, stdin or options")]
+    CheckpointMismatch(std::path::PathBuf),
+    #[error("the FRI parameters {0:?} do not reach the security level {1:?}")]
+    InsufficientSecurity(FriParameters, SecurityLevel),
+    #[error("the prover uses the FRI parameters {0:?}, but the options require {1:?}")]
+    FriParametersMismatch(FriParameters, FriParameters),
+}
+
+impl ZKMCoreProverError {
+    /// Symbolize the backtrace of a guest panic with `elf`, the ELF of the guest that panicked.
+    #[must_use]
+    pub fn symbolize(self, elf: &[u8]) -> Self {
+        match self {
+            Self::ExecutionError(err) => Self::ExecutionError(err.symbolize(elf)),
+            err => err,
+        }
+    }
 }
 
 pub fn prove_simple<SC: StarkGenericConfig, P: MachineProver<SC, MipsAir<SC::Val>>>(
@@ -141,16 +163,126 @@ where
     Com<SC>: Send + Sync,
     PcsProverData<SC>: Send + Sync,
 {
+    prove_with_checkpoint_dir::<SC, P>(
+        prover,
+        pk,
+        program,
+        stdin,
+        opts,
+        context,
+        shape_config,
+        None,
+    )
+}
+
+/// Proves the program like [`prove_with_context`], saving the executor states and the shard
+/// proofs of every checkpoint to `checkpoint_dir`.
+///
+/// If the directory holds the progress of a previous run of the same job, proving resumes from
+/// the first checkpoint whose shard proofs are missing. The directory is rejected with
+/// [`ZKMCoreProverError::CheckpointMismatch`] if it was created for another program, stdin or
+/// sharding options.
+#[allow(clippy::too_many_arguments)]
+pub fn prove_with_context_resumable<
+    SC: StarkGenericConfig,
+    P: MachineProver<SC, MipsAir<SC::Val>>,
+>(
+    prover: &P,
+    pk: &P::DeviceProvingKey,
+    program: Program,
+    stdin: &ZKMStdin,
+    opts: ZKMCoreOpts,
+    context: ZKMContext,
+    shape_config: Option<&CoreShapeConfig<SC::Val>>,
+    checkpoint_dir: &Path,
+) -> Result<(MachineProof<SC>, Vec<u8>, u64), ZKMCoreProverError>
+where
+    SC::Val: PrimeField32,
+    SC::Challenger: 'static + Clone + Send,
+    OpeningProof<SC>: Send,
+    Com<SC>: Send + Sync,
+    PcsProverData<SC>: Send + Sync,
+{
+    let manifest = CheckpointManifest::new(&program, stdin, opts, shape_config.is_some())?;
+    let checkpoint_dir = CheckpointDir::open(checkpoint_dir, &manifest)?;
+    prove_with_checkpoint_dir::<SC, P>(
+        prover,
+        pk,
+        program,
+        stdin,
+        opts,
+        context,
+        shape_config,
+        Some(&checkpoint_dir),
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+fn prove_with_checkpoint_dir<SC: StarkGenericConfig, P: MachineProver<SC, MipsAir<SC::Val>>>(
+    prover: &P,
+    pk: &P::DeviceProvingKey,
+    program: Program,
+    stdin: &ZKMStdin,
+    opts: ZKMCoreOpts,
+    context: ZKMContext,
+    shape_config: Option<&CoreShapeConfig<SC::Val>>,
+    checkpoint_dir: Option<&CheckpointDir>,
+) -> Result<(MachineProof<SC>, Vec<u8>, u64), ZKMCoreProverError>
+where
+    SC::Val: PrimeField32,
+    SC::Challenger: 'static + Clone + Send,
+    OpeningProof<SC>: Send,
+    Com<SC>: Send + Sync,
+    PcsProverData<SC>: Send + Sync,
+{
+    let resume_point = match checkpoint_dir {
+        Some(dir) => dir.resume_point::<SC>()?,
+        None => None,
+    };
+
+    // The executor stops at the next batch once cancelled, the prover skips the pending shards.
+    let progress = context.progress.clone();
+    let progress = progress.as_ref();
+    let cancellation_token = context.cancellation_token.clone();
+    let cancellation_token = cancellation_token.as_ref();
+
     // Setup the runtime.
     let mut runtime = Executor::with_context(program.clone(), opts, context);
     runtime.maximal_shapes = shape_config.map(|config| {
         config.maximal_core_shapes(opts.shard_size.ilog2() as usize).into_iter().collect()
     });
 
-    runtime.write_vecs(&stdin.buffer);
-    for proof in stdin.proofs.iter() {
-        let (proof, vk) = proof.clone();
-        runtime.write_proof(proof, vk);
+    let first_index;
+    let resumed_shard_proofs;
+    let resumed_record_gen_state;
+    if let Some(resume_point) = resume_point {
+        tracing::info!(
+            "resuming from checkpoint {} with {} shard proofs",
+            resume_point.index,
+            resume_point.shard_proofs.len()
+        );
+
+        // The whole execution was already proven by a previous run.
+        if resume_point.record_gen_state.done {
+            let cycles = resume_point.record_gen_state.report.total_instruction_count();
+            let proof = MachineProof::<SC> { shard_proofs: resume_point.shard_proofs };
+            return Ok((proof, resume_point.execution_state.public_values_stream, cycles));
+        }
+
+        // The executor state already holds the stdin.
+        runtime.state = resume_point.execution_state;
+        first_index = resume_point.index;
+        resumed_shard_proofs = resume_point.shard_proofs;
+        resumed_record_gen_state = Some(resume_point.record_gen_state);
+    } else {
+        runtime.write_vecs(&stdin.buffer);
+        for proof in stdin.proofs.iter() {
+            let (proof, vk) = proof.clone();
+            runtime.write_proof(proof, vk);
+        }
+        first_index = 0;
+        resumed_shard_proofs = Vec::new();
+        resumed_record_gen_state = None;
     }
 
     #[cfg(feature = "debug")]
@@ -170,7 +302,7 @@ where
             s.spawn(move || {
                 let _span = checkpoint_generator_span.enter();
                 tracing::debug_span!("checkpoint generator").in_scope(|| {
-                    let mut index = 0;
+                    let mut index = first_index;
                     loop {
                         // Enter the span.
                         let span = tracing::debug_span!("batch");
@@ -188,6 +320,14 @@ where
                             .save(&mut checkpoint_file)
                             .map_err(ZKMCoreProverError::IoError)?;
 
+                        // Save the state the next checkpoint starts from, which also holds the
+                        // public values stream once the execution is done.
+                        if let Some(dir) = checkpoint_dir {
+                            dir.save_execution_state(index + 1, &runtime.state).unwrap_or_else(
+                                |e| tracing::warn!("failed to save execution state: {}", e),
+                            );
+                        }
+
                         // Send the checkpoint.
                         checkpoints_tx
                             .send((index, checkpoint_file, done, runtime.state.global_clk))
@@ -209,18 +349,32 @@ where
         pk.observe_into(&mut challenger);
 
         // Spawn the phase 2 record generator thread.
-        let p2_record_gen_sync = Arc::new(TurnBasedSync::new());
-        let p2_trace_gen_sync = Arc::new(TurnBasedSync::new());
+        let p2_record_gen_sync = Arc::new(TurnBasedSync::starting_at(first_index));
+        let p2_trace_gen_sync = Arc::new(TurnBasedSync::starting_at(first_index));
         let checkpoints_rx = Arc::new(Mutex::new(checkpoints_rx));
+        // Each batch of records is tagged with its checkpoint index and whether it is the last
+        // batch of the checkpoint.
+        #[allow(clippy::type_complexity)]
         let (p2_records_and_traces_tx, p2_records_and_traces_rx) =
-            sync_channel::<(Vec<ExecutionRecord>, Vec<Vec<(String, RowMajorMatrix<Val<SC>>)>>)>(
-                opts.records_and_traces_channel_capacity,
-            );
+            sync_channel::<(
+                usize,
+                bool,
+                Vec<ExecutionRecord>,
+                Vec<Vec<(String, RowMajorMatrix<Val<SC>>)>>,
+            )>(opts.records_and_traces_channel_capacity);
         let p2_records_and_traces_tx = Arc::new(Mutex::new(p2_records_and_traces_tx));
 
-        let report_aggregate = Arc::new(Mutex::new(ExecutionReport::default()));
-        let state = Arc::new(Mutex::new(PublicValues::<u32, u32>::default().reset()));
-        let deferred = Arc::new(Mutex::new(ExecutionRecord::new(program.clone().into())));
+        let (report_aggregate, state, deferred) = match resumed_record_gen_state {
+            Some(RecordGenState { state, deferred, report, .. }) => (report, state, deferred),
+            None => (
+                ExecutionReport::default(),
+                PublicValues::<u32, u32>::default().reset(),
+                ExecutionRecord::new(program.clone().into()),
+            ),
+        };
+        let report_aggregate = Arc::new(Mutex::new(report_aggregate));
+        let state = Arc::new(Mutex::new(state));
+        let deferred = Arc::new(Mutex::new(deferred));
         let mut p2_record_and_trace_gen_handles = Vec::new();
         for _ in 0..opts.trace_gen_workers {
             let record_gen_sync = Arc::clone(&p2_record_gen_sync);
@@ -260,11 +414,11 @@ where
                                     )
                                 });
                             log::debug!("generated {} records", records.len());
-                            *report_aggregate.lock().unwrap() += report;
                             reset_seek(&mut checkpoint);
 
                             // Wait for our turn to update the state.
                             record_gen_sync.wait_for_turn(index);
+                            *report_aggregate.lock().unwrap() += report;
 
                             // Update the public values & prover state for the shards which contain
                             // "cpu events".
@@ -299,9 +453,9 @@ where
                                 let mut records_clone = records.clone();
                                 let last_record = records_clone.last_mut();
                                 // See if any deferred shards are ready to be committed to.
-                                let mut deferred =
+                                let mut deferred_records =
                                     deferred.split(done, last_record, opts.split_opts);
-                                tracing::debug!("deferred {} records", deferred.len());
+                                tracing::debug!("deferred {} records", deferred_records.len());
 
                                 // Update the public values & prover state for the shards which do
                                 // not contain "cpu events" before
@@ -309,7 +463,7 @@ where
                                 if !done {
                                     state.execution_shard += 1;
                                 }
-                                for record in deferred.iter_mut() {
+                                for record in deferred_records.iter_mut() {
                                     state.shard += 1;
                                     state.previous_init_addr_bits =
                                         record.public_values.previous_init_addr_bits;
@@ -322,7 +476,7 @@ where
                                     state.start_pc = state.next_pc;
                                     record.public_values = *state;
                                 }
-                                records_clone.append(&mut deferred);
+                                records_clone.append(&mut deferred_records);
 
                                 // Generate the dependencies.
                                 tracing::debug_span!("generate dependencies", index).in_scope(
@@ -344,6 +498,18 @@ where
                                     },
                                 )?;
 
+                                save_record_gen_state(
+                                    checkpoint_dir,
+                                    index,
+                                    &state,
+                                    &deferred,
+                                    &report_aggregate,
+                                    done,
+                                );
+                                if let Some(progress) = progress {
+                                    progress(ProveProgress::ShardsExecuted(state.shard as usize));
+                                }
+
                                 // Let another worker update the state.
                                 record_gen_sync.advance_turn();
 
@@ -363,15 +529,16 @@ where
 
                             if shape_fixed_records.is_none() {
                                 // See if any deferred shards are ready to be committed to.
-                                let mut deferred = deferred.split(done, None, opts.split_opts);
-                                log::debug!("deferred {} records", deferred.len());
+                                let mut deferred_records =
+                                    deferred.split(done, None, opts.split_opts);
+                                log::debug!("deferred {} records", deferred_records.len());
 
                                 // Update the public values & prover state for the shards which do not
                                 // contain "cpu events" before committing to them.
                                 if !done {
                                     state.execution_shard += 1;
                                 }
-                                for record in deferred.iter_mut() {
+                                for record in deferred_records.iter_mut() {
                                     state.shard += 1;
                                     state.previous_init_addr_bits =
                                         record.public_values.previous_init_addr_bits;
@@ -384,7 +551,7 @@ where
                                     state.start_pc = state.next_pc;
                                     record.public_values = *state;
                                 }
-                                records.append(&mut deferred);
+                                records.append(&mut deferred_records);
 
                                 // Generate the dependencies.
                                 tracing::debug_span!("generate dependencies", index).in_scope(
@@ -406,6 +573,18 @@ where
                                     },
                                 )?;
 
+                                save_record_gen_state(
+                                    checkpoint_dir,
+                                    index,
+                                    &state,
+                                    &deferred,
+                                    &report_aggregate,
+                                    done,
+                                );
+                                if let Some(progress) = progress {
+                                    progress(ProveProgress::ShardsExecuted(state.shard as usize));
+                                }
+
                                 // Let another worker update the state.
                                 record_gen_sync.advance_turn();
 
@@ -447,19 +626,27 @@ where
 
                             trace_gen_sync.wait_for_turn(index);
 
-                            // Send the records to the phase 2 prover.
+                            // Send the records to the phase 2 prover. An empty batch is still
+                            // sent so that the prover sees the end of every checkpoint.
                             let chunked_records = chunk_vec(records, opts.shard_batch_size);
                             let chunked_main_traces = chunk_vec(main_traces, opts.shard_batch_size);
-                            chunked_records
+                            let mut chunks = chunked_records
                                 .into_iter()
                                 .zip(chunked_main_traces.into_iter())
-                                .for_each(|(records, main_traces)| {
+                                .collect::<Vec<_>>();
+                            if chunks.is_empty() {
+                                chunks.push((Vec::new(), Vec::new()));
+                            }
+                            let num_chunks = chunks.len();
+                            chunks.into_iter().enumerate().for_each(
+                                |(i, (records, main_traces))| {
                                     records_and_traces_tx
                                         .lock()
                                         .unwrap()
-                                        .send((records, main_traces))
+                                        .send((index, i + 1 == num_chunks, records, main_traces))
                                         .unwrap();
-                                });
+                                },
+                            );
 
                             trace_gen_sync.advance_turn();
                         } else {
@@ -479,9 +666,14 @@ where
         let p2_prover_span = tracing::Span::current().clone();
         let p2_prover_handle = s.spawn(move || {
             let _span = p2_prover_span.enter();
-            let mut shard_proofs = Vec::new();
+            let mut shard_proofs = resumed_shard_proofs;
+            let mut checkpoint_start = shard_proofs.len();
             tracing::debug_span!("phase 2 prover").in_scope(|| {
-                for (records, traces) in p2_records_and_traces_rx.into_iter() {
+                for (index, last, records, traces) in p2_records_and_traces_rx.into_iter() {
+                    // Keep draining the channel so that the trace generators are not blocked.
+                    if cancellation_token.is_some_and(|token| token.is_cancelled()) {
+                        continue;
+                    }
                     tracing::debug_span!("batch").in_scope(|| {
                         let span = tracing::Span::current().clone();
                         shard_proofs.par_extend(
@@ -520,6 +712,20 @@ where
                             ),
                         );
                     });
+                    if let Some(progress) = progress {
+                        progress(ProveProgress::ShardsProven(shard_proofs.len()));
+                    }
+
+                    // Save the proofs once every shard of the checkpoint has been proven.
+                    if last {
+                        if let Some(dir) = checkpoint_dir {
+                            dir.save_shard_proofs(index, &shard_proofs[checkpoint_start..])
+                                .unwrap_or_else(|e| {
+                                    tracing::warn!("failed to save shard proofs: {}", e)
+                                });
+                        }
+                        checkpoint_start = shard_proofs.len();
+                    }
                 }
             });
             shard_proofs
@@ -535,6 +741,9 @@ where
 
         // Wait until the phase 2 prover has finished.
         let shard_proofs = p2_prover_handle.join().unwrap();
+        if cancellation_token.is_some_and(|token| token.is_cancelled()) {
+            return Err(ZKMCoreProverError::ExecutionError(ExecutionError::Cancelled()));
+        }
 
         // Log some of the `ExecutionReport` information.
         let report_aggregate = report_aggregate.lock().unwrap();
@@ -655,6 +864,47 @@ pub fn run_test_core<P: MachineProver<KoalaBearPoseidon2, MipsAir<KoalaBear>>>(
     Ok(proof)
 }
 
+/// Prove `program` with the main traces returned by `generate_traces` for each shard, and verify
+/// the proof.
+///
+/// Used to check that tampered witnesses are rejected: `generate_traces` can modify the traces
+/// generated by the prover before they are committed to.
+pub fn run_malicious_test<P: MachineProver<KoalaBearPoseidon2, MipsAir<KoalaBear>>>(
+    mut program: Program,
+    inputs: ZKMStdin,
+    generate_traces: impl Fn(&P, &ExecutionRecord) -> Vec<(String, RowMajorMatrix<KoalaBear>)>,
+) -> Result<MachineProof<KoalaBearPoseidon2>, MachineVerificationError<KoalaBearPoseidon2>> {
+    let shape_config = CoreShapeConfig::default();
+    shape_config.fix_preprocessed_shape(&mut program).unwrap();
+    let mut runtime = Executor::new(program, ZKMCoreOpts::default());
+    runtime.write_vecs(&inputs.buffer);
+    runtime.run().unwrap();
+    let mut records = std::mem::take(&mut runtime.records);
+
+    let config = KoalaBearPoseidon2::new();
+    let machine = MipsAir::machine(config);
+    let prover = P::new(machine);
+    let (pk, vk) = prover.setup(runtime.program.as_ref());
+    prover.machine().generate_dependencies(&mut records, &ZKMCoreOpts::default(), None).unwrap();
+
+    let mut challenger = prover.config().challenger();
+    pk.observe_into(&mut challenger);
+    let shard_proofs = records
+        .iter()
+        .map(|record| {
+            let named_traces = generate_traces(&prover, record);
+            let shard_data = prover.commit(record, named_traces);
+            prover.open(&pk, shard_data, &mut challenger.clone()).unwrap()
+        })
+        .collect();
+    let proof = MachineProof { shard_proofs };
+
+    let mut challenger = prover.config().challenger();
+    prover.machine().verify(&vk, &proof, &mut challenger)?;
+
+    Ok(proof)
+}
+
 #[allow(unused_variables)]
 pub fn run_test_machine_with_prover<SC, A, P: MachineProver<SC, A>>(
     prover: &P,
@@ -728,6 +978,19 @@ pub fn trace_checkpoint<SC: StarkGenericConfig>(
     opts: ZKMCoreOpts,
     shape_config: Option<&CoreShapeConfig<SC::Val>>,
 ) -> (Vec<ExecutionRecord>, ExecutionReport)
+where
+    <SC as StarkGenericConfig>::Val: PrimeField32,
+{
+    try_trace_checkpoint::<SC>(program, state, opts, shape_config).unwrap()
+}
+
+/// Like [`trace_checkpoint`], but returns the execution error instead of panicking.
+pub fn try_trace_checkpoint<SC: StarkGenericConfig>(
+    program: Program,
+    state: ExecutionState,
+    opts: ZKMCoreOpts,
+    shape_config: Option<&CoreShapeConfig<SC::Val>>,
+) -> Result<(Vec<ExecutionRecord>, ExecutionReport), ExecutionError>
 where
     <SC as StarkGenericConfig>::Val: PrimeField32,
 {
@@ -743,9 +1006,31 @@ where
     runtime.subproof_verifier = Some(&noop);
 
     // Execute from the checkpoint.
-    let (records, _) = runtime.execute_record(true).unwrap();
+    let (records, _) = runtime.execute_record(true)?;
+
+    Ok((records, runtime.report))
+}
 
-    (records, runtime.report)
+/// Saves the prover state after the records of checkpoint `index`, if proving is resumable.
+fn save_record_gen_state(
+    checkpoint_dir: Option<&CheckpointDir>,
+    index: usize,
+    state: &PublicValues<u32, u32>,
+    deferred: &ExecutionRecord,
+    report_aggregate: &Mutex<ExecutionReport>,
+    done: bool,
+) {
+    let Some(dir) = checkpoint_dir else {
+        return;
+    };
+    let record_gen_state = RecordGenState {
+        state: *state,
+        deferred: deferred.clone(),
+        report: report_aggregate.lock().unwrap().clone(),
+        done,
+    };
+    dir.save_record_gen_state(index, &record_gen_state)
+        .unwrap_or_else(|e| tracing::warn!("failed to save record generation state: {}", e));
 }
 
 fn reset_seek(file: &mut File) {
@@ -0,0 +1,253 @@
+use std::{
+    fs::{self, File},
+    io::{self, BufReader, BufWriter},
+    path::{Path, PathBuf},
+};
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use tiny_keccak::{Hasher, Keccak};
+use zkm_core_executor::{ExecutionRecord, ExecutionReport, ExecutionState, Program};
+use zkm_stark::{air::PublicValues, ShardProof, StarkGenericConfig, ZKMCoreOpts};
+
+use crate::io::ZKMStdin;
+
+use super::ZKMCoreProverError;
+
+/// The name of the file identifying the proving job a checkpoint directory belongs to.
+const MANIFEST_FILE: &str = "manifest.bin";
+
+/// Identifies the proving job a checkpoint directory belongs to.
+///
+/// A directory can only be resumed by a job with the same program, stdin and sharding options,
+/// since the shards of the stored proofs would otherwise not line up with the new ones.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CheckpointManifest {
+    /// The keccak256 digest of the serialized program.
+    pub program_digest: [u8; 32],
+    /// The keccak256 digest of the serialized stdin.
+    pub stdin_digest: [u8; 32],
+    /// The shard size used to split the execution.
+    pub shard_size: usize,
+    /// The number of shards executed per checkpoint.
+    pub shard_batch_size: usize,
+    /// Whether the shapes of the shards were fixed.
+    pub fixed_shapes: bool,
+}
+
+impl CheckpointManifest {
+    /// Creates the manifest of a proving job.
+    pub fn new(
+        program: &Program,
+        stdin: &ZKMStdin,
+        opts: ZKMCoreOpts,
+        fixed_shapes: bool,
+    ) -> Result<Self, ZKMCoreProverError> {
+        Ok(Self {
+            program_digest: program_digest(program)?,
+            stdin_digest: stdin_digest(stdin)?,
+            shard_size: opts.shard_size,
+            shard_batch_size: opts.shard_batch_size,
+            fixed_shapes,
+        })
+    }
+}
+
+/// The prover state right after the records of a checkpoint have been generated.
+#[derive(Serialize, Deserialize)]
+pub struct RecordGenState {
+    /// The public values of the last shard.
+    pub state: PublicValues<u32, u32>,
+    /// The events deferred to later shards.
+    pub deferred: ExecutionRecord,
+    /// The execution report of all the checkpoints so far.
+    pub report: ExecutionReport,
+    /// Whether this was the last checkpoint of the execution.
+    pub done: bool,
+}
+
+/// Where a proving job picks up from a checkpoint directory.
+pub struct ResumePoint<SC: StarkGenericConfig> {
+    /// The index of the first checkpoint that still has to be proven.
+    pub index: usize,
+    /// The proofs of the shards of all the previous checkpoints.
+    pub shard_proofs: Vec<ShardProof<SC>>,
+    /// The prover state after the previous checkpoint.
+    pub record_gen_state: RecordGenState,
+    /// The executor state at the start of the checkpoint.
+    pub execution_state: ExecutionState,
+}
+
+/// A directory holding the progress of a core proving job, so that it can be resumed after a
+/// crash.
+///
+/// For every checkpoint `i` of the execution, the directory holds:
+/// - `execution-{i}.bin`: the full executor state at the start of the checkpoint.
+/// - `record-gen-{i}.bin`: the [`RecordGenState`] after the records of the checkpoint.
+/// - `proofs-{i}.bin`: the proofs of the shards of the checkpoint.
+///
+/// The states are pruned once the proofs of the following checkpoint have been written.
+#[derive(Debug, Clone)]
+pub struct CheckpointDir {
+    path: PathBuf,
+}
+
+impl CheckpointDir {
+    /// Opens the checkpoint directory of the job described by `manifest`, creating it if needed.
+    ///
+    /// Returns [`ZKMCoreProverError::CheckpointMismatch`] if the directory belongs to another job.
+    pub fn open(
+        path: impl Into<PathBuf>,
+        manifest: &CheckpointManifest,
+    ) -> Result<Self, ZKMCoreProverError> {
+        let dir = Self { path: path.into() };
+        fs::create_dir_all(&dir.path).map_err(ZKMCoreProverError::IoError)?;
+
+        let manifest_path = dir.path.join(MANIFEST_FILE);
+        if manifest_path.exists() {
+            let existing: CheckpointManifest = dir.read(&manifest_path)?;
+            if existing != *manifest {
+                return Err(ZKMCoreProverError::CheckpointMismatch(dir.path));
+            }
+        } else {
+            dir.write(&manifest_path, manifest)?;
+        }
+        Ok(dir)
+    }
+
+    /// The path of the directory.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Finds the first checkpoint whose proofs are missing, and loads everything needed to start
+    /// proving from it. Returns `None` if no checkpoint has been proven yet.
+    pub fn resume_point<SC: StarkGenericConfig>(
+        &self,
+    ) -> Result<Option<ResumePoint<SC>>, ZKMCoreProverError> {
+        let mut shard_proofs = Vec::new();
+        let mut index = 0;
+        while self.proofs_path(index).exists() {
+            let mut proofs: Vec<ShardProof<SC>> = self.read(&self.proofs_path(index))?;
+            shard_proofs.append(&mut proofs);
+            index += 1;
+        }
+        if index == 0 {
+            return Ok(None);
+        }
+
+        let record_gen_state = self.read(&self.record_gen_path(index - 1))?;
+        let execution_state = self.read(&self.execution_path(index))?;
+        Ok(Some(ResumePoint { index, shard_proofs, record_gen_state, execution_state }))
+    }
+
+    /// Saves the executor state at the start of checkpoint `index`.
+    pub fn save_execution_state(
+        &self,
+        index: usize,
+        state: &ExecutionState,
+    ) -> Result<(), ZKMCoreProverError> {
+        self.write(&self.execution_path(index), state)
+    }
+
+    /// Saves the prover state after the records of checkpoint `index` have been generated.
+    pub fn save_record_gen_state(
+        &self,
+        index: usize,
+        state: &RecordGenState,
+    ) -> Result<(), ZKMCoreProverError> {
+        self.write(&self.record_gen_path(index), state)
+    }
+
+    /// Saves the shard proofs of checkpoint `index`, and prunes the states that are no longer
+    /// needed to resume.
+    ///
+    /// The states needed to re-prove checkpoint `index` itself are kept, so that the job can still
+    /// be resumed if its proofs are lost.
+    pub fn save_shard_proofs<SC: StarkGenericConfig>(
+        &self,
+        index: usize,
+        proofs: &[ShardProof<SC>],
+    ) -> Result<(), ZKMCoreProverError> {
+        self.write(&self.proofs_path(index), &proofs)?;
+
+        if index > 0 {
+            let _ = fs::remove_file(self.execution_path(index - 1));
+        }
+        if index > 1 {
+            let _ = fs::remove_file(self.record_gen_path(index - 2));
+        }
+        Ok(())
+    }
+
+    fn execution_path(&self, index: usize) -> PathBuf {
+        self.path.join(format!("execution-{index}.bin"))
+    }
+
+    fn record_gen_path(&self, index: usize) -> PathBuf {
+        self.path.join(format!("record-gen-{index}.bin"))
+    }
+
+    fn proofs_path(&self, index: usize) -> PathBuf {
+        self.path.join(format!("proofs-{index}.bin"))
+    }
+
+    fn read<T: DeserializeOwned>(&self, path: &Path) -> Result<T, ZKMCoreProverError> {
+        let file = File::open(path).map_err(ZKMCoreProverError::IoError)?;
+        bincode::deserialize_from(BufReader::new(file))
+            .map_err(ZKMCoreProverError::SerializationError)
+    }
+
+    /// Writes to a temporary file first, so that a crash never leaves a truncated file behind.
+    fn write<T: Serialize + ?Sized>(
+        &self,
+        path: &Path,
+        value: &T,
+    ) -> Result<(), ZKMCoreProverError> {
+        let tmp_path = path.with_extension("tmp");
+        let file = File::create(&tmp_path).map_err(ZKMCoreProverError::IoError)?;
+        let mut writer = BufWriter::new(file);
+        bincode::serialize_into(&mut writer, value)
+            .map_err(ZKMCoreProverError::SerializationError)?;
+        writer
+            .into_inner()
+            .map_err(io::IntoInnerError::into_error)
+            .and_then(|file| file.sync_all())
+            .map_err(ZKMCoreProverError::IoError)?;
+        fs::rename(&tmp_path, path).map_err(ZKMCoreProverError::IoError)
+    }
+}
+
+/// Hashes the parts of the program that determine its execution.
+///
+/// The preprocessed shape is skipped since it is derived from the rest of the program, and its
+/// hash map would not serialize deterministically.
+fn program_digest(program: &Program) -> Result<[u8; 32], ZKMCoreProverError> {
+    keccak256_serialized(&(
+        &program.instructions,
+        program.pc_start,
+        program.pc_base,
+        program.next_pc,
+        &program.image,
+    ))
+}
+
+/// Hashes the stdin, identifying the deferred proofs by their commitments and public values.
+fn stdin_digest(stdin: &ZKMStdin) -> Result<[u8; 32], ZKMCoreProverError> {
+    let proofs = stdin
+        .proofs
+        .iter()
+        .map(|(proof, vk)| {
+            (&proof.vk.commit, &proof.proof.commitment, &proof.proof.public_values, &vk.commit)
+        })
+        .collect::<Vec<_>>();
+    keccak256_serialized(&(&stdin.buffer, proofs))
+}
+
+fn keccak256_serialized<T: Serialize>(value: &T) -> Result<[u8; 32], ZKMCoreProverError> {
+    let bytes = bincode::serialize(value).map_err(ZKMCoreProverError::SerializationError)?;
+    let mut hasher = Keccak::v256();
+    hasher.update(&bytes);
+    let mut digest = [0u8; 32];
+    hasher.finalize(&mut digest);
+    Ok(digest)
+}
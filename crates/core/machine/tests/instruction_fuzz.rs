@@ -0,0 +1,214 @@
+use std::{
+    borrow::{Borrow, BorrowMut},
+    cell::Cell,
+};
+
+use p3_field::FieldAlgebra;
+use p3_koala_bear::KoalaBear;
+use p3_matrix::{dense::RowMajorMatrix, Matrix};
+use zkm_core_executor::Program;
+use zkm_core_machine::{
+    alu::{AddSubCols, CloClzCols, DivRemCols, MulCols, ShiftLeftCols},
+    io::ZKMStdin,
+    misc::{others::columns::MiscInstrColumns, MovCondCols},
+    utils::{run_malicious_test, run_test, setup_logger},
+    MipsAir,
+};
+use zkm_instruction_test_defs::fuzz_program;
+use zkm_stark::{koala_bear_poseidon2::KoalaBearPoseidon2, CpuProver, MachineProver};
+
+type F = KoalaBear;
+type Prover = CpuProver<KoalaBearPoseidon2, MipsAir<F>>;
+
+/// The number of opcodes of the fuzzed programs.
+const PROGRAM_LEN: usize = 96;
+
+#[test]
+fn fuzz_programs_prove() {
+    setup_logger();
+    for seed in 0..4 {
+        run_test::<CpuProver<_, _>>(fuzz_program(seed, PROGRAM_LEN))
+            .unwrap_or_else(|err| panic!("seed {seed} failed: {err:?}"));
+    }
+}
+
+/// A tampering of a witness column of a chip.
+struct Mutation {
+    name: &'static str,
+    chip: &'static str,
+    /// Tamper with a real row of the trace, returning whether one was found.
+    mutate: fn(&mut RowMajorMatrix<F>) -> bool,
+}
+
+/// Apply `mutate` to the first row of `trace` satisfying `is_target`.
+fn mutate_row<C>(
+    trace: &mut RowMajorMatrix<F>,
+    is_target: impl Fn(&C) -> bool,
+    mutate: impl Fn(&mut C),
+) -> bool
+where
+    [F]: Borrow<C> + BorrowMut<C>,
+{
+    let width = trace.width();
+    for row in trace.values.chunks_mut(width) {
+        if is_target(row.borrow()) {
+            mutate(row.borrow_mut());
+            return true;
+        }
+    }
+    false
+}
+
+const MUTATIONS: &[Mutation] = &[
+    Mutation {
+        name: "divrem_quotient",
+        chip: "DivRem",
+        mutate: |trace| {
+            mutate_row(
+                trace,
+                |cols: &DivRemCols<F>| {
+                    cols.is_div + cols.is_divu + cols.is_mod + cols.is_modu == F::ONE
+                },
+                |cols| cols.quotient[0] += F::ONE,
+            )
+        },
+    },
+    Mutation {
+        name: "divrem_remainder",
+        chip: "DivRem",
+        mutate: |trace| {
+            mutate_row(
+                trace,
+                |cols: &DivRemCols<F>| cols.is_divu == F::ONE,
+                |cols| cols.remainder[0] += F::ONE,
+            )
+        },
+    },
+    Mutation {
+        name: "misc_ext_result",
+        chip: "MiscInstrs",
+        mutate: |trace| {
+            mutate_row(
+                trace,
+                |cols: &MiscInstrColumns<F>| cols.is_ext == F::ONE,
+                |cols| cols.op_a_value[0] += F::ONE,
+            )
+        },
+    },
+    Mutation {
+        name: "misc_ins_result",
+        chip: "MiscInstrs",
+        mutate: |trace| {
+            mutate_row(
+                trace,
+                |cols: &MiscInstrColumns<F>| cols.is_ins == F::ONE,
+                |cols| cols.op_a_value[3] += F::ONE,
+            )
+        },
+    },
+    Mutation {
+        name: "misc_maddu_result",
+        chip: "MiscInstrs",
+        mutate: |trace| {
+            mutate_row(
+                trace,
+                |cols: &MiscInstrColumns<F>| cols.is_maddu == F::ONE,
+                |cols| cols.op_a_value[0] += F::ONE,
+            )
+        },
+    },
+    Mutation {
+        name: "movcond_result",
+        chip: "MovCond",
+        mutate: |trace| {
+            mutate_row(
+                trace,
+                |cols: &MovCondCols<F>| cols.is_meq + cols.is_mne == F::ONE,
+                |cols| cols.op_a_value[0] += F::ONE,
+            )
+        },
+    },
+    Mutation {
+        name: "addsub_result",
+        chip: "AddSub",
+        mutate: |trace| {
+            mutate_row(
+                trace,
+                |cols: &AddSubCols<F>| cols.is_add == F::ONE,
+                |cols| cols.add_operation.value[0] += F::ONE,
+            )
+        },
+    },
+    Mutation {
+        name: "mul_result",
+        chip: "Mul",
+        mutate: |trace| {
+            mutate_row(
+                trace,
+                |cols: &MulCols<F>| cols.is_real == F::ONE,
+                |cols| cols.a[0] += F::ONE,
+            )
+        },
+    },
+    Mutation {
+        name: "shift_left_result",
+        chip: "ShiftLeft",
+        mutate: |trace| {
+            mutate_row(
+                trace,
+                |cols: &ShiftLeftCols<F>| cols.is_real == F::ONE,
+                |cols| cols.a[1] += F::ONE,
+            )
+        },
+    },
+    Mutation {
+        name: "cloclz_result",
+        chip: "CloClz",
+        mutate: |trace| {
+            mutate_row(
+                trace,
+                |cols: &CloClzCols<F>| cols.is_real == F::ONE,
+                |cols| cols.a[0] += F::ONE,
+            )
+        },
+    },
+];
+
+/// Prove `program` with the traces of `chip` modified by `mutate`.
+fn prove_mutated(
+    program: Program,
+    chip: &str,
+    mutate: fn(&mut RowMajorMatrix<F>) -> bool,
+) -> (bool, bool) {
+    let mutated = Cell::new(false);
+    let result = run_malicious_test::<Prover>(program, ZKMStdin::new(), |prover, record| {
+        let mut traces = prover.generate_traces(record).unwrap();
+        for (name, trace) in traces.iter_mut() {
+            if name == chip && !mutated.get() {
+                mutated.set(mutate(trace));
+            }
+        }
+        traces
+    });
+    (mutated.get(), result.is_ok())
+}
+
+#[test]
+fn fuzz_programs_prove_unmodified_witnesses() {
+    setup_logger();
+    let (_, verified) = prove_mutated(fuzz_program(0, PROGRAM_LEN), "", |_| false);
+    assert!(verified, "the unmodified witness was rejected");
+}
+
+#[test]
+fn fuzz_programs_reject_mutated_witnesses() {
+    setup_logger();
+    for seed in 0..2 {
+        for mutation in MUTATIONS {
+            let program = fuzz_program(seed, PROGRAM_LEN);
+            let (mutated, verified) = prove_mutated(program, mutation.chip, mutation.mutate);
+            assert!(mutated, "seed {seed}: {} found no row to mutate", mutation.name);
+            assert!(!verified, "seed {seed}: {} was accepted", mutation.name);
+        }
+    }
+}
@@ -279,6 +279,9 @@ pub enum FieldType {
 
 pub trait FpOpField: FieldParameters + NumWords {
     const FIELD_TYPE: FieldType;
+
+    /// The real part `k` of the non-residue `ξ = k + u` used to build `Fp6 = Fp2[v] / (v^3 - ξ)`.
+    const FP6_NON_RESIDUE_C0: u32;
 }
 
 #[cfg(test)]
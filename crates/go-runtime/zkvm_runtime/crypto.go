@@ -147,6 +147,78 @@ func bls12381AddSafe(p, q *Bls12381G1Point) {
 	Bls12381G1Add(p, q)
 }
 
+// Bls12381Decompress decompresses a 48-byte compressed BLS12-381 G1 point into its 96-byte
+// big-endian uncompressed encoding (x || y).
+func Bls12381Decompress(compressed [48]byte) [96]byte {
+	// Back the buffer with words so the precompile sees a 4-byte aligned pointer.
+	var words [24]uint32
+	buf := (*[96]byte)(unsafe.Pointer(&words[0]))
+	copy(buf[:48], compressed[:])
+
+	signBit := uint32((buf[0] & 0b0010_0000) >> 5)
+	buf[0] &= 0b0001_1111
+
+	// The precompile works on little-endian values, so flip the whole buffer around the call.
+	reverseBytes(buf[:])
+	SyscallBls12381Decompress(unsafe.Pointer(&words[0]), signBit)
+	reverseBytes(buf[:])
+	return *buf
+}
+
+// U256xU2048Mul computes a * b, returning the low 2048 bits and the high 256 bits of the product.
+// All values are little-endian limbs.
+func U256xU2048Mul(a *[8]uint32, b *[64]uint32) (lo [64]uint32, hi [8]uint32) {
+	SyscallU256xU2048Mul(unsafe.Pointer(a), unsafe.Pointer(b), unsafe.Pointer(&lo[0]), unsafe.Pointer(&hi[0]))
+	return lo, hi
+}
+
+const (
+	// GarbleGateInfoBytes is the size of one garbled gate: type, h0, h1, label_b and ciphertext.
+	GarbleGateInfoBytes = 68
+
+	// GarbleMaxGatesPerSyscall bounds the number of gates checked by one precompile call.
+	GarbleMaxGatesPerSyscall = 62500
+)
+
+// BooleanCircuitGarble checks the garbled gates in gatesInfo, which holds a 16-byte delta
+// followed by GarbleGateInfoBytes bytes per gate. It matches
+// `zkm_lib::boolean_circuit_garble::boolean_circuit_garble`.
+func BooleanCircuitGarble(gatesInfo []byte) bool {
+	if len(gatesInfo)%GarbleGateInfoBytes != 16 {
+		panic("boolean circuit garble: malformed gates info")
+	}
+
+	delta := gatesInfo[:16]
+	gates := gatesInfo[16:]
+	numGates := len(gates) / GarbleGateInfoBytes
+	for start := 0; start < numGates || start == 0; start += GarbleMaxGatesPerSyscall {
+		count := numGates - start
+		if count > GarbleMaxGatesPerSyscall {
+			count = GarbleMaxGatesPerSyscall
+		}
+
+		// The input is the gate count, delta and the gates, backed by words for alignment.
+		words := make([]uint32, 5+count*GarbleGateInfoBytes/4)
+		input := unsafe.Slice((*byte)(unsafe.Pointer(&words[0])), len(words)*4)
+		binary.LittleEndian.PutUint32(input[0:4], uint32(count))
+		copy(input[4:20], delta)
+		copy(input[20:], gates[start*GarbleGateInfoBytes:(start+count)*GarbleGateInfoBytes])
+
+		var output uint32
+		SyscallBooleanCircuitGarble(unsafe.Pointer(&words[0]), unsafe.Pointer(&output))
+		if output != 1 {
+			return false
+		}
+	}
+	return true
+}
+
+func reverseBytes(b []byte) {
+	for i, j := 0, len(b)-1; i < j; i, j = i+1, j-1 {
+		b[i], b[j] = b[j], b[i]
+	}
+}
+
 func isZeroPoint(limbs []uint32) bool {
 	for _, v := range limbs {
 		if v != 0 {
@@ -0,0 +1,167 @@
+//go:build mipsle
+// +build mipsle
+
+package zkvm_runtime
+
+import (
+	"math/big"
+	"unsafe"
+)
+
+// FD_ECRECOVER_HOOK is the file descriptor of the executor's ecrecover hook.
+// It mirrors `zkm_primitives::consts::fd::FD_ECRECOVER_HOOK`.
+const FD_ECRECOVER_HOOK int = 15
+
+// ecrecoverCurveSecp256k1 selects secp256k1 in the ecrecover hook request.
+const ecrecoverCurveSecp256k1 byte = 1
+
+var (
+	secp256k1P, _ = new(big.Int).SetString("fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f", 16)
+	secp256k1N, _ = new(big.Int).SetString("fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141", 16)
+
+	// secp256k1NQR is the non-quadratic residue used by the hook to prove that no root exists.
+	secp256k1NQR = big.NewInt(3)
+)
+
+// Secp256k1Point represents a secp256k1 affine point as [16]uint32 in little-endian (x||y).
+// The all-zero value is used as the point at infinity.
+type Secp256k1Point [16]uint32
+
+// Secp256k1Generator is the secp256k1 generator.
+var Secp256k1Generator = Secp256k1Point{
+	0x16f81798, 0x59f2815b, 0x2dce28d9, 0x029bfcdb, 0xce870b07, 0x55a06295, 0xf9dcbbac, 0x79be667e,
+	0xfb10d4b8, 0x9c47d08f, 0xa6855419, 0xfd17b448, 0x0e1108a8, 0x5da4fbfc, 0x26a3c465, 0x483ada77,
+}
+
+// Secp256k1Add computes p = p + q on secp256k1. The points must be distinct and not negations.
+func Secp256k1Add(p, q *Secp256k1Point) {
+	SyscallSecp256k1Add(unsafe.Pointer(p), unsafe.Pointer(q))
+}
+
+// Secp256k1Double computes p = 2*p on secp256k1.
+func Secp256k1Double(p *Secp256k1Point) {
+	SyscallSecp256k1Double(unsafe.Pointer(p), unsafe.Pointer(nil))
+}
+
+// Secp256k1ScalarMul computes result = scalar * base using double-and-add.
+// scalar is a big-endian value.
+func Secp256k1ScalarMul(base *Secp256k1Point, scalar []byte) Secp256k1Point {
+	var result Secp256k1Point
+	temp := *base
+
+	for i := len(scalar) - 1; i >= 0; i-- {
+		b := scalar[i]
+		for bit := 0; bit < 8; bit++ {
+			if (b>>uint(bit))&1 == 1 {
+				secp256k1AddSafe(&result, &temp)
+			}
+			Secp256k1Double(&temp)
+		}
+	}
+	return result
+}
+
+// secp256k1AddSafe computes p = p + q, handling the point at infinity, doubling and negation.
+func secp256k1AddSafe(p, q *Secp256k1Point) {
+	if isZeroPoint(p[:]) {
+		*p = *q
+		return
+	}
+	if isZeroPoint(q[:]) {
+		return
+	}
+	if *p == *q {
+		Secp256k1Double(p)
+		return
+	}
+	if [8]uint32(p[:8]) == [8]uint32(q[:8]) {
+		*p = Secp256k1Point{}
+		return
+	}
+	Secp256k1Add(p, q)
+}
+
+// Ecrecover recovers the uncompressed secp256k1 public key (0x04 || x || y) that produced
+// signature over hash. The signature is r || s || v, with v in {0, 1} or {27, 28}.
+//
+// The square root of r^3 + 7 and r^-1 are supplied by the executor through FD_ECRECOVER_HOOK
+// and checked here, so the hook does not need to be trusted.
+func Ecrecover(hash [32]byte, signature [65]byte) ([65]byte, bool) {
+	var pubkey [65]byte
+
+	r := new(big.Int).SetBytes(signature[:32])
+	s := new(big.Int).SetBytes(signature[32:64])
+	v := signature[64]
+	if v >= 27 {
+		v -= 27
+	}
+	if v > 1 || r.Sign() == 0 || r.Cmp(secp256k1N) >= 0 || s.Sign() == 0 || s.Cmp(secp256k1N) >= 0 {
+		return pubkey, false
+	}
+
+	// alpha = r^3 + 7 is the square of the y-coordinate of R.
+	alpha := new(big.Int).Exp(r, big.NewInt(3), secp256k1P)
+	alpha.Add(alpha, big.NewInt(7))
+	alpha.Mod(alpha, secp256k1P)
+
+	request := make([]byte, 65)
+	request[0] = ecrecoverCurveSecp256k1 | v<<7
+	r.FillBytes(request[1:33])
+	alpha.FillBytes(request[33:65])
+	SyscallWrite(FD_ECRECOVER_HOOK, request, len(request))
+
+	status := ReadHint()
+	if len(status) != 1 {
+		panic("ecrecover: malformed hook response")
+	}
+	if status[0] == 0 {
+		// alpha is not a square: check the hook's proof that alpha * NQR is one.
+		root := new(big.Int).SetBytes(ReadHint())
+		lhs := new(big.Int).Mul(root, root)
+		lhs.Mod(lhs, secp256k1P)
+		rhs := new(big.Int).Mul(alpha, secp256k1NQR)
+		rhs.Mod(rhs, secp256k1P)
+		if lhs.Cmp(rhs) != 0 {
+			panic("ecrecover: invalid non-residue proof")
+		}
+		return pubkey, false
+	}
+
+	y := new(big.Int).SetBytes(ReadHint())
+	rInv := new(big.Int).SetBytes(ReadHint())
+
+	ySquared := new(big.Int).Mul(y, y)
+	ySquared.Mod(ySquared, secp256k1P)
+	if y.Cmp(secp256k1P) >= 0 || ySquared.Cmp(alpha) != 0 || y.Bit(0) != uint(v) {
+		panic("ecrecover: invalid y-coordinate hint")
+	}
+	check := new(big.Int).Mul(r, rInv)
+	check.Mod(check, secp256k1N)
+	if check.Cmp(big.NewInt(1)) != 0 {
+		panic("ecrecover: invalid r inverse hint")
+	}
+
+	// Q = r^-1 (s R - z G) = u1 G + u2 R.
+	z := new(big.Int).SetBytes(hash[:])
+	u1 := new(big.Int).Mul(z, rInv)
+	u1.Neg(u1)
+	u1.Mod(u1, secp256k1N)
+	u2 := new(big.Int).Mul(s, rInv)
+	u2.Mod(u2, secp256k1N)
+
+	var point Secp256k1Point
+	BeToLeU32(r.FillBytes(make([]byte, 32)), point[:8])
+	BeToLeU32(y.FillBytes(make([]byte, 32)), point[8:])
+
+	q := Secp256k1ScalarMul(&Secp256k1Generator, u1.FillBytes(make([]byte, 32)))
+	t := Secp256k1ScalarMul(&point, u2.FillBytes(make([]byte, 32)))
+	secp256k1AddSafe(&q, &t)
+	if isZeroPoint(q[:]) {
+		return pubkey, false
+	}
+
+	pubkey[0] = 0x04
+	LeU32ToBe(q[:8], pubkey[1:33])
+	LeU32ToBe(q[8:], pubkey[33:65])
+	return pubkey, true
+}
@@ -0,0 +1,143 @@
+//go:build mipsle
+// +build mipsle
+
+package zkvm_runtime
+
+import (
+	"crypto/sha512"
+	"math/big"
+	"unsafe"
+)
+
+// Ed25519Point represents an Ed25519 affine point as [16]uint32 in little-endian (x||y).
+type Ed25519Point [16]uint32
+
+// Ed25519Identity is the neutral element (0, 1) of the Ed25519 group.
+var Ed25519Identity = Ed25519Point{8: 1}
+
+// Ed25519Base is the Ed25519 base point from RFC 8032.
+var Ed25519Base = Ed25519Point{
+	0x8f25d51a, 0xc9562d60, 0x9525a7b2, 0x692cc760, 0xfdd6dc5c, 0xc0a4e231, 0xcd6e53fe, 0x216936d3,
+	0x66666658, 0x66666666, 0x66666666, 0x66666666, 0x66666666, 0x66666666, 0x66666666, 0x66666666,
+}
+
+// ed25519P is the prime p = 2^255 - 19 of the Ed25519 base field.
+var ed25519P, _ = new(big.Int).SetString("7fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffed", 16)
+
+// ed25519D is the curve constant d = -121665/121666 mod p.
+var ed25519D, _ = new(big.Int).SetString("52036cee2b6ffe738cc740797779e89800700a4d4141d8ab75eb4dca135978a3", 16)
+
+// ed25519Order is the order L of the Ed25519 prime-order subgroup.
+var ed25519Order, _ = new(big.Int).SetString("1000000000000000000000000000000014def9dea2f79cd65812631a5cf5d3ed", 16)
+
+// Ed25519Add computes p = p + q on Ed25519. The addition law is complete, so p and q may be equal.
+func Ed25519Add(p, q *Ed25519Point) {
+	SyscallEdAdd(unsafe.Pointer(p), unsafe.Pointer(q))
+}
+
+// Ed25519Decompress decompresses a 32-byte Ed25519 point encoding.
+// The executor aborts execution on encodings that are not points on the curve, so untrusted
+// encodings must be checked with ed25519ValidEncoding first.
+func Ed25519Decompress(compressed [32]byte) Ed25519Point {
+	var point Ed25519Point
+	buf := (*[64]byte)(unsafe.Pointer(&point[0]))
+	copy(buf[32:], compressed[:])
+
+	sign := uint32(buf[63] >> 7)
+	buf[63] &= 0x7f
+	SyscallEdDecompress(unsafe.Pointer(&point[0]), sign)
+	return point
+}
+
+// Ed25519ScalarMul computes result = scalar * base using double-and-add.
+// scalar is a little-endian byte value, following the Ed25519 convention.
+func Ed25519ScalarMul(base *Ed25519Point, scalar []byte) Ed25519Point {
+	result := Ed25519Identity
+	temp := *base
+
+	for _, b := range scalar {
+		for bit := 0; bit < 8; bit++ {
+			if (b>>uint(bit))&1 == 1 {
+				Ed25519Add(&result, &temp)
+			}
+			Ed25519Add(&temp, &temp)
+		}
+	}
+	return result
+}
+
+// Ed25519Verify reports whether signature is a valid RFC 8032 signature of message by publicKey.
+//
+// The check is the cofactorless equation [S]B = R + [k]A with k = SHA-512(R || A || M) mod L.
+// Malformed encodings of A and R are rejected before they reach the decompression precompile.
+func Ed25519Verify(publicKey [32]byte, message []byte, signature [64]byte) bool {
+	// S must be reduced modulo L.
+	s := leBytesToInt(signature[32:])
+	if s.Cmp(ed25519Order) >= 0 {
+		return false
+	}
+
+	var rBytes [32]byte
+	copy(rBytes[:], signature[:32])
+	if !ed25519ValidEncoding(publicKey) || !ed25519ValidEncoding(rBytes) {
+		return false
+	}
+	a := Ed25519Decompress(publicKey)
+	r := Ed25519Decompress(rBytes)
+
+	h := sha512.New()
+	h.Write(signature[:32])
+	h.Write(publicKey[:])
+	h.Write(message)
+	k := leBytesToInt(h.Sum(nil))
+	k.Mod(k, ed25519Order)
+
+	lhs := Ed25519ScalarMul(&Ed25519Base, intToLeBytes(s, 32))
+	rhs := Ed25519ScalarMul(&a, intToLeBytes(k, 32))
+	Ed25519Add(&rhs, &r)
+
+	return lhs == rhs
+}
+
+// ed25519ValidEncoding reports whether enc is the encoding of an Ed25519 point, following the
+// decoding rules of RFC 8032 section 5.1.3: y must be reduced modulo p, x^2 = (y^2 - 1) / (d y^2 + 1)
+// must be a square, and the sign bit must be clear when x = 0.
+func ed25519ValidEncoding(enc [32]byte) bool {
+	sign := enc[31] >> 7
+	enc[31] &= 0x7f
+	y := leBytesToInt(enc[:])
+	if y.Cmp(ed25519P) >= 0 {
+		return false
+	}
+
+	y2 := new(big.Int).Mul(y, y)
+	y2.Mod(y2, ed25519P)
+	u := new(big.Int).Sub(y2, big.NewInt(1))
+	u.Mod(u, ed25519P)
+	v := new(big.Int).Mul(ed25519D, y2)
+	v.Add(v, big.NewInt(1))
+	v.Mod(v, ed25519P)
+	// -1/d is not a square, so v is never zero.
+	x2 := new(big.Int).ModInverse(v, ed25519P)
+	x2.Mul(x2, u)
+	x2.Mod(x2, ed25519P)
+
+	if x2.Sign() == 0 {
+		return sign == 0
+	}
+	return big.Jacobi(x2, ed25519P) == 1
+}
+
+// leBytesToInt interprets b as a little-endian unsigned integer.
+func leBytesToInt(b []byte) *big.Int {
+	be := append([]byte(nil), b...)
+	reverseBytes(be)
+	return new(big.Int).SetBytes(be)
+}
+
+// intToLeBytes encodes x as a little-endian unsigned integer of size bytes.
+func intToLeBytes(x *big.Int, size int) []byte {
+	le := x.FillBytes(make([]byte, size))
+	reverseBytes(le)
+	return le
+}
@@ -0,0 +1,60 @@
+//go:build mipsle
+// +build mipsle
+
+package zkvm_runtime
+
+import (
+	"encoding/binary"
+	"unsafe"
+)
+
+const (
+	poseidon2Width     = 16 // Number of field elements in the Poseidon2 state
+	poseidon2Rate      = 8  // Number of field elements processed per permutation
+	poseidon2FieldSize = 3  // Number of bytes that can be safely converted to a field element
+)
+
+// Poseidon2Permute executes the Poseidon2 permutation on the given state in place.
+// Every element of the state must be a canonical KoalaBear field element.
+func Poseidon2Permute(state *[poseidon2Width]uint32) {
+	SyscallPoseidon2Permute(unsafe.Pointer(state))
+}
+
+// Poseidon2Hash computes the 32-byte Poseidon2 hash of data.
+// It matches `zkm_lib::poseidon2::poseidon2`, so Rust and Go guests agree on digests.
+func Poseidon2Hash(data []byte) [32]byte {
+	// Pad the input to a multiple of 3 bytes with 1*01.
+	l := len(data)
+	newSize := (l + poseidon2FieldSize) / poseidon2FieldSize * poseidon2FieldSize
+	padded := make([]byte, newSize)
+	copy(padded, data)
+	if l%poseidon2FieldSize == poseidon2FieldSize-1 {
+		padded[l] = 0b10000001
+	} else {
+		padded[l] = 1
+		padded[newSize-1] = 0b10000000
+	}
+
+	// Each 3 bytes of input are converted to a field element.
+	elements := make([]uint32, 0, newSize/poseidon2FieldSize)
+	for i := 0; i < newSize; i += poseidon2FieldSize {
+		elements = append(elements,
+			uint32(padded[i])|uint32(padded[i+1])<<8|uint32(padded[i+2])<<16)
+	}
+
+	var state [poseidon2Width]uint32
+	for offset := 0; offset < len(elements); offset += poseidon2Rate {
+		end := offset + poseidon2Rate
+		if end > len(elements) {
+			end = len(elements)
+		}
+		copy(state[:], elements[offset:end])
+		Poseidon2Permute(&state)
+	}
+
+	var result [32]byte
+	for i := 0; i < 8; i++ {
+		binary.LittleEndian.PutUint32(result[i*4:i*4+4], state[i])
+	}
+	return result
+}
@@ -63,6 +63,22 @@ func SyscallSecp256r1Decompress(point unsafe.Pointer, isOdd uint32)
 // uint256 multiplication
 func SyscallUint256Mul(x unsafe.Pointer, y unsafe.Pointer)
 
+// uint256 x uint2048 multiplication
+func SyscallU256xU2048Mul(a unsafe.Pointer, b unsafe.Pointer, lo unsafe.Pointer, hi unsafe.Pointer)
+
+// Ed25519 precompiles
+func SyscallEdAdd(p unsafe.Pointer, q unsafe.Pointer)
+func SyscallEdDecompress(point unsafe.Pointer, sign uint32)
+
+// BLS12-381 decompression
+func SyscallBls12381Decompress(point unsafe.Pointer, signBit uint32)
+
+// Poseidon2 permutation
+func SyscallPoseidon2Permute(state unsafe.Pointer)
+
+// Boolean circuit garbling
+func SyscallBooleanCircuitGarble(input unsafe.Pointer, output unsafe.Pointer)
+
 // SyscallHintWrite writes to the hint stream (fd=4) using Ziren WRITE syscall.
 func SyscallHintWrite(write_buf []byte, nbytes int)
 
@@ -78,6 +94,14 @@ func HintSlice(data []byte) {
 	SyscallHintWrite(data, len(data))
 }
 
+// ReadHint reads the next item of the hint stream as raw bytes.
+func ReadHint() []byte {
+	length := SyscallHintLen()
+	buf := make([]byte, (length+3)/4*4)
+	SyscallHintRead(buf, length)
+	return buf[:length]
+}
+
 // ReadHintVec reads a hint vector from the hint stream.
 // Reads two items: first a 4-byte LE length, then the actual data.
 func ReadHintVec() []byte {
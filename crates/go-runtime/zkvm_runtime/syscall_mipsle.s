@@ -266,3 +266,57 @@ TEXT ·SyscallUint256Mul(SB), $0-8
 	MOVW y+4(FP), R5       // a1 = y pointer ([8]u32 LE)
 	SYSCALL
 	RET
+
+// uint256 x uint2048 multiplication
+
+TEXT ·SyscallU256xU2048Mul(SB), $0-16
+	MOVW $0x0101002F, R2   // v0 = U256XU2048_MUL
+	MOVW a+0(FP), R4       // a0 = a pointer ([8]u32 LE)
+	MOVW b+4(FP), R5       // a1 = b pointer ([64]u32 LE)
+	MOVW lo+8(FP), R6      // a2 = lo pointer ([64]u32 LE), low 2048 bits stored here
+	MOVW hi+12(FP), R7     // a3 = hi pointer ([8]u32 LE), high 256 bits stored here
+	SYSCALL
+	RET
+
+// Ed25519 precompiles
+
+TEXT ·SyscallEdAdd(SB), $0-8
+	MOVW $0x01010007, R2   // v0 = ED_ADD
+	MOVW p+0(FP), R4       // a0 = p pointer ([16]u32, x||y LE)
+	MOVW q+4(FP), R5       // a1 = q pointer ([16]u32, x||y LE)
+	SYSCALL
+	RET
+
+TEXT ·SyscallEdDecompress(SB), $0-8
+	MOVW $0x00010008, R2   // v0 = ED_DECOMPRESS
+	MOVW point+0(FP), R4   // a0 = point pointer (64 bytes, y in the upper half)
+	MOVW sign+4(FP), R5    // a1 = sign of x (0 or 1)
+	SYSCALL
+	RET
+
+// BLS12-381 decompression
+
+TEXT ·SyscallBls12381Decompress(SB), $0-8
+	MOVW $0x0001001C, R2   // v0 = BLS12381_DECOMPRESS
+	MOVW point+0(FP), R4   // a0 = point pointer (96 bytes, LE)
+	MOVW signBit+4(FP), R5 // a1 = sign bit (0 or 1)
+	SYSCALL
+	RET
+
+// Poseidon2 permutation
+
+TEXT ·SyscallPoseidon2Permute(SB), $0-4
+	MOVW $0x00010030, R2   // v0 = POSEIDON2_PERMUTE
+	MOVW state+0(FP), R4   // a0 = state pointer ([16]u32)
+	MOVW $0, R5
+	SYSCALL
+	RET
+
+// Boolean circuit garbling
+
+TEXT ·SyscallBooleanCircuitGarble(SB), $0-8
+	MOVW $0x00010031, R2   // v0 = BOOLEAN_CIRCUIT_GARBLE
+	MOVW input+0(FP), R4   // a0 = input pointer (gate count || delta || gates)
+	MOVW output+4(FP), R5  // a1 = output pointer (u32), 1 if every gate checks
+	SYSCALL
+	RET
@@ -60,6 +60,9 @@ pub mod fd {
         /// The file descriptor through which to access `hook_bls12_381_inverse`.
         pub const FD_BLS12_381_INVERSE: u32 = 10;
 
+        /// The file descriptor through which the guest reports a panic.
+        pub const FD_PANIC: u32 = 11;
+
     }
 }
 
@@ -63,3 +63,21 @@ impl Default for Buffer {
         Self::new()
     }
 }
+
+/// A panic of the guest, written to [`FD_PANIC`](crate::consts::fd::FD_PANIC) by the panic hook
+/// of the zkVM entrypoint.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PanicReport {
+    /// The panic message.
+    pub message: String,
+    /// The source file of the panic.
+    pub file: String,
+    /// The line of the panic.
+    pub line: u32,
+    /// The column of the panic.
+    pub column: u32,
+    /// The words of the stack above the panic hook, innermost first.
+    ///
+    /// The host picks the return addresses out of them to rebuild the backtrace.
+    pub stack: Vec<u32>,
+}
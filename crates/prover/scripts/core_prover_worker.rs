@@ -0,0 +1,31 @@
+use clap::Parser;
+use zkm_core_machine::utils::setup_logger;
+use zkm_prover::{
+    components::DefaultProverComponents,
+    distributed::{serve_worker, WorkerAddress, WorkerListener},
+    ZKMProver,
+};
+
+/// Serves core proving jobs for a coordinator calling `ZKMProver::prove_core_distributed`.
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+struct Args {
+    /// The address to listen on, either tcp://<host>:<port> or unix://<path>.
+    #[clap(short, long)]
+    listen: WorkerAddress,
+}
+
+fn main() {
+    // Setup logger.
+    setup_logger();
+
+    // Parse arguments.
+    let args = Args::parse();
+
+    // Setup the prover, which must use the same shape configuration as the coordinator.
+    let prover = ZKMProver::<DefaultProverComponents>::new();
+
+    let listener = WorkerListener::bind(&args.listen).expect("failed to bind listener");
+    tracing::info!("listening on {}", args.listen);
+    serve_worker(&prover, &listener).expect("failed to accept connection");
+}
@@ -0,0 +1,232 @@
+//! Incremental aggregation of compressed proofs.
+
+use std::{borrow::Borrow, cell::OnceCell};
+
+use p3_field::{FieldAlgebra, PrimeField32};
+use p3_koala_bear::KoalaBear;
+use zkm_core_executor::Program;
+use zkm_core_machine::{io::ZKMStdin, reduce::ZKMReduceProof};
+use zkm_primitives::io::ZKMPublicValues;
+use zkm_recursion_core::air::RecursionPublicValues;
+use zkm_stark::{air::PublicValues, StarkVerifyingKey, Word, ZKMProverOpts, DIGEST_SIZE};
+
+use crate::{
+    aggregation::{aggregation_hints, AggregationNode},
+    components::ZKMProverComponents,
+    utils::words_to_bytes,
+    CoreSC, DeviceProvingKey, HashableKey, InnerSC, ZKMAggregationError, ZKMCoreProof, ZKMProver,
+    ZKMRecursionProverError, ZKMVerifyingKey,
+};
+
+/// A proof pending in a [ZKMAccumulator].
+#[derive(Clone)]
+struct PendingProof {
+    proof: ZKMReduceProof<InnerSC>,
+    vk: StarkVerifyingKey<CoreSC>,
+    /// The public values of a proof of the aggregation program, or `None` for an added proof.
+    aggregated: Option<ZKMPublicValues>,
+}
+
+impl PendingProof {
+    /// The vkey digest and public values digest the proof is verified with.
+    fn pair(&self) -> ([u32; 8], [u8; 32]) {
+        let pv: &RecursionPublicValues<KoalaBear> =
+            self.proof.proof.public_values.as_slice().borrow();
+        let vk_digest = pv.zkm_vk_digest.map(|x| x.as_canonical_u32());
+        let pv_digest = words_to_bytes(&pv.committed_value_digest)
+            .into_iter()
+            .map(|x| x.as_canonical_u32() as u8)
+            .collect::<Vec<_>>();
+        (vk_digest, pv_digest.try_into().unwrap())
+    }
+
+    fn node(&self) -> AggregationNode<'_> {
+        match &self.aggregated {
+            Some(public_values) => AggregationNode::Aggregated(public_values),
+            None => AggregationNode::Pair(self.pair()),
+        }
+    }
+}
+
+/// An accumulator of compressed proofs, which are folded as they arrive.
+///
+/// The accumulator is a counter in base `compress_arity` of the prover: once `compress_arity`
+/// proofs are pending at a level, they are aggregated by the built-in
+/// [`crate::aggregation_program`] into a proof of the next level. At most `compress_arity - 1`
+/// proofs are pending per level, so that the memory used by the accumulator and the work left
+/// when folding grow logarithmically with the number of added proofs.
+///
+/// The pending proofs are folded as the deferred proofs of an aggregation program, which calls
+/// `verify_zkm_proof` with the [`ZKMAccumulator::verify_inputs`] of each pending proof in order,
+/// or by the built-in aggregation program with [`ZKMAccumulator::aggregate`]. A folded proof is a
+/// compressed proof itself, so that it can be added to another accumulator.
+pub struct ZKMAccumulator<'a, C: ZKMProverComponents> {
+    prover: &'a ZKMProver<C>,
+    /// The proofs pending at each level, from the added proofs up.
+    levels: Vec<Vec<PendingProof>>,
+    /// The number of added proofs.
+    len: usize,
+    /// The keys of the aggregation program, set up on first use.
+    aggregation: OnceCell<(DeviceProvingKey<C>, Program, ZKMVerifyingKey)>,
+}
+
+impl<'a, C: ZKMProverComponents> ZKMAccumulator<'a, C> {
+    /// Creates an empty accumulator folding proofs with `prover`.
+    pub fn new(prover: &'a ZKMProver<C>) -> Self {
+        Self { prover, levels: Vec::new(), len: 0, aggregation: OnceCell::new() }
+    }
+
+    /// Verifies `proof` against `vk` and adds it to the accumulated proofs, aggregating the
+    /// pending proofs of every level it fills up.
+    pub fn add(
+        &mut self,
+        proof: ZKMReduceProof<InnerSC>,
+        vk: &ZKMVerifyingKey,
+        opts: ZKMProverOpts,
+    ) -> Result<(), ZKMAggregationError> {
+        self.prover.verify_compressed(&proof, vk)?;
+        if self.levels.is_empty() {
+            self.levels.push(Vec::new());
+        }
+        self.levels[0].push(PendingProof { proof, vk: vk.vk.clone(), aggregated: None });
+        self.len += 1;
+
+        let arity = self.prover.compress_arity;
+        let mut level = 0;
+        while level < self.levels.len() {
+            // A failed aggregation leaves the proofs pending, so that the next call retries it.
+            if self.levels[level].len() >= arity {
+                let aggregated = self.aggregate_proofs(&self.levels[level][..arity], opts)?;
+                self.levels[level].drain(..arity);
+                if level + 1 == self.levels.len() {
+                    self.levels.push(Vec::new());
+                }
+                self.levels[level + 1].push(aggregated);
+            }
+            level += 1;
+        }
+        Ok(())
+    }
+
+    /// The pending proofs, in the order of the proofs they aggregate.
+    fn pending(&self) -> impl Iterator<Item = &PendingProof> {
+        self.levels.iter().rev().flatten()
+    }
+
+    /// The pending proofs, which are the added proofs or proofs of the aggregation program, in
+    /// the order of the proofs they aggregate.
+    pub fn proofs(&self) -> impl Iterator<Item = &ZKMReduceProof<InnerSC>> {
+        self.pending().map(|pending| &pending.proof)
+    }
+
+    /// The number of added proofs.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether no proof was added.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The deferred proofs digest of an aggregation program verifying the pending proofs.
+    pub fn deferred_proofs_digest(&self) -> [KoalaBear; DIGEST_SIZE] {
+        self.pending().fold([KoalaBear::ZERO; DIGEST_SIZE], |digest, pending| {
+            ZKMProver::<C>::hash_deferred_proofs(digest, std::slice::from_ref(&pending.proof))
+        })
+    }
+
+    /// The vkey digest and public values digest the aggregation program passes to
+    /// `verify_zkm_proof` for each pending proof, in order.
+    pub fn verify_inputs(&self) -> Vec<([u32; 8], [u8; 32])> {
+        self.pending().map(PendingProof::pair).collect()
+    }
+
+    /// Folds the pending proofs into a single compressed proof.
+    ///
+    /// `proof` is the core proof of the aggregation program, whose verifying key is `vk`. It must
+    /// have verified the pending proofs in order, and only them.
+    pub fn fold(
+        &self,
+        vk: &ZKMVerifyingKey,
+        proof: ZKMCoreProof,
+        opts: ZKMProverOpts,
+    ) -> Result<ZKMReduceProof<InnerSC>, ZKMRecursionProverError> {
+        let last_proof_pv: &PublicValues<Word<KoalaBear>, KoalaBear> =
+            proof.proof.0.last().unwrap().public_values.as_slice().borrow();
+        let deferred_proofs_digest = self.deferred_proofs_digest();
+        if last_proof_pv.deferred_proofs_digest != deferred_proofs_digest {
+            return Err(ZKMRecursionProverError::DeferredProofsDigestMismatch(
+                last_proof_pv.deferred_proofs_digest.map(|x| x.as_canonical_u32()),
+                deferred_proofs_digest.map(|x| x.as_canonical_u32()),
+            ));
+        }
+        let deferred_proofs = self.proofs().cloned().collect();
+        self.prover.compress(vk, proof, deferred_proofs, opts)
+    }
+
+    /// Folds the pending proofs with a proof of the built-in [`crate::aggregation_program`],
+    /// whose public values commit to the vkey digest and public values digest of every added
+    /// proof, in order.
+    ///
+    /// Returns the folded proof, the verifying key of the aggregation program and the public
+    /// values, which [`crate::aggregated_pairs`] decodes.
+    pub fn aggregate(
+        &self,
+        opts: ZKMProverOpts,
+    ) -> Result<(ZKMReduceProof<InnerSC>, ZKMVerifyingKey, ZKMPublicValues), ZKMAggregationError>
+    {
+        let pending = self.pending().collect::<Vec<_>>();
+        let aggregated = match pending.as_slice() {
+            // A single proof of the aggregation program already commits to every added proof.
+            [pending] if pending.aggregated.is_some() => PendingProof::clone(pending),
+            _ => self.aggregate_proofs(pending, opts)?,
+        };
+        let (_, _, vk) = self.aggregation_keys();
+        Ok((aggregated.proof, vk.clone(), aggregated.aggregated.unwrap()))
+    }
+
+    /// Removes the accumulated proofs, so that the accumulator can aggregate the next ones.
+    pub fn clear(&mut self) {
+        self.levels.clear();
+        self.len = 0;
+    }
+
+    /// The keys of the aggregation program.
+    fn aggregation_keys(&self) -> &(DeviceProvingKey<C>, Program, ZKMVerifyingKey) {
+        self.aggregation.get_or_init(|| self.prover.aggregation_setup())
+    }
+
+    /// Aggregates `proofs` into a compressed proof of the aggregation program.
+    fn aggregate_proofs<'b>(
+        &self,
+        proofs: impl IntoIterator<Item = &'b PendingProof>,
+        opts: ZKMProverOpts,
+    ) -> Result<PendingProof, ZKMAggregationError> {
+        let proofs = proofs.into_iter().collect::<Vec<_>>();
+        let (pk_d, program, vk) = self.aggregation_keys();
+
+        let (nodes, pairs) =
+            aggregation_hints(&vk.vk.hash_u32(), proofs.iter().map(|pending| pending.node()));
+        let mut stdin = ZKMStdin::new();
+        stdin.write_vec(nodes);
+        stdin.write_vec(pairs);
+        for pending in &proofs {
+            stdin.write_proof(pending.proof.clone(), pending.vk.clone());
+        }
+
+        let proof =
+            self.prover.prove_core(pk_d, program.clone(), &stdin, opts, Default::default())?;
+        let public_values = proof.public_values.clone();
+        let deferred_proofs = proofs.iter().map(|pending| pending.proof.clone()).collect();
+        let proof = self.prover.compress(vk, proof, deferred_proofs, opts)?;
+        Ok(PendingProof { proof, vk: vk.vk.clone(), aggregated: Some(public_values) })
+    }
+}
+
+impl<C: ZKMProverComponents> ZKMProver<C> {
+    /// Creates an empty [ZKMAccumulator] folding proofs with this prover.
+    pub fn accumulator(&self) -> ZKMAccumulator<'_, C> {
+        ZKMAccumulator::new(self)
+    }
+}
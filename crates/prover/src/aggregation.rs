@@ -0,0 +1,511 @@
+//! A built-in program aggregating compressed proofs without a guest.
+//!
+//! The program reads the vkey digests and public values digests of the proofs from the hint
+//! stream, verifies each proof with `VERIFY_ZKM_PROOF`, and commits to the list. It is assembled
+//! by hand so that its verifying key does not depend on a guest toolchain.
+//!
+//! A proof of the program can be aggregated again together with the pairs it commits to, so that
+//! the pairs of the aggregated proofs are committed as a flat list however deep the aggregation
+//! goes.
+
+use zkm_core_executor::{syscalls::SyscallCode, Instruction, Opcode, Program, Register};
+use zkm_primitives::{consts::fd::FD_PUBLIC_VALUES, io::ZKMPublicValues};
+use zkm_stark::MachineProver;
+
+use crate::{
+    components::ZKMProverComponents, DeviceProvingKey, HashableKey, ZKMProver, ZKMVerifyingKey,
+};
+
+/// The number of bytes of an aggregated pair: the words of the vkey digest followed by the
+/// public values digest.
+pub const AGGREGATION_PAIR_BYTES: usize = 64;
+
+/// The address of the SHA-256 message schedule.
+const SHA_W_ADDR: u32 = 0x1000_0000;
+/// The address of the SHA-256 state of the public values.
+const SHA_H_ADDR: u32 = 0x1000_0100;
+/// The address of the Poseidon2 sponge state.
+const SPONGE_ADDR: u32 = 0x1000_0200;
+/// The address of the deferred proofs digest.
+const DEFERRED_DIGEST_ADDR: u32 = 0x1000_0300;
+/// The address of the header of the public values.
+const HEADER_ADDR: u32 = 0x1000_0400;
+/// The address of the SHA-256 state after hashing the header.
+const NESTED_IV_ADDR: u32 = 0x1000_0500;
+/// The address of the SHA-256 state of the public values of a nested aggregation proof.
+const NESTED_H_ADDR: u32 = 0x1000_0600;
+/// The address of the public values digest of a nested aggregation proof.
+const NESTED_DIGEST_ADDR: u32 = 0x1000_0700;
+/// The address the nodes are read to.
+const NODES_ADDR: u32 = 0x1800_0000;
+/// The address the pairs are read to.
+const PAIRS_ADDR: u32 = 0x2000_0000;
+
+/// The initial SHA-256 state.
+const SHA_IV: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+const ZERO: u8 = Register::ZERO as u8;
+const V0: u8 = Register::V0 as u8;
+const A0: u8 = Register::A0 as u8;
+const A1: u8 = Register::A1 as u8;
+const A2: u8 = Register::A2 as u8;
+const T0: u8 = Register::T0 as u8;
+const T1: u8 = Register::T1 as u8;
+/// The length of the hint.
+const S0: u8 = Register::S0 as u8;
+/// The address of the current pair.
+const S2: u8 = Register::S2 as u8;
+/// The address of the current node.
+const S4: u8 = Register::S4 as u8;
+/// The address past the last node.
+const S5: u8 = Register::S5 as u8;
+/// The number of pairs left in the current node.
+const S6: u8 = Register::S6 as u8;
+/// The address of the first pair of the current node.
+const S7: u8 = Register::S7 as u8;
+
+/// A minimal assembler for the aggregation program.
+#[derive(Default)]
+struct Assembler {
+    instructions: Vec<Instruction>,
+}
+
+impl Assembler {
+    /// The index of the next instruction.
+    fn position(&self) -> usize {
+        self.instructions.len()
+    }
+
+    fn emit(&mut self, opcode: Opcode, a: u8, b: u32, c: u32, imm_b: bool, imm_c: bool) {
+        self.instructions.push(Instruction::new(opcode, a, b, c, imm_b, imm_c));
+    }
+
+    fn nop(&mut self) {
+        self.emit(Opcode::ADD, ZERO, 0, 0, true, true);
+    }
+
+    /// `rd = rs + imm`.
+    fn addi(&mut self, rd: u8, rs: u8, imm: u32) {
+        self.emit(Opcode::ADD, rd, rs as u32, imm, false, true);
+    }
+
+    /// `rd = imm`.
+    fn li(&mut self, rd: u8, imm: u32) {
+        self.addi(rd, ZERO, imm);
+    }
+
+    fn lw(&mut self, rt: u8, base: u8, offset: u32) {
+        self.emit(Opcode::LW, rt, base as u32, offset, false, true);
+    }
+
+    fn lbu(&mut self, rt: u8, base: u8, offset: u32) {
+        self.emit(Opcode::LBU, rt, base as u32, offset, false, true);
+    }
+
+    fn sw(&mut self, rt: u8, base: u8, offset: u32) {
+        self.emit(Opcode::SW, rt, base as u32, offset, false, true);
+    }
+
+    /// Reverse the bytes of `rs` into `rd`.
+    fn bswap(&mut self, rd: u8, rs: u8) {
+        self.emit(Opcode::WSBH, rd, rs as u32, 0, false, true);
+        self.emit(Opcode::ROR, rd, rd as u32, 16, false, true);
+    }
+
+    /// Call `syscall` with the arguments already in `a0` and `a1`.
+    fn syscall(&mut self, syscall: SyscallCode) {
+        self.li(V0, syscall as u32);
+        self.emit(Opcode::SYSCALL, V0, A0 as u32, A1 as u32, false, false);
+    }
+
+    /// Emit a branch to the instruction at `target`, followed by its delay slot.
+    fn branch(&mut self, opcode: Opcode, rs: u8, rt: u8, target: usize) {
+        // The offset is relative to the delay slot.
+        let offset = (target as u32 * 4).wrapping_sub((self.position() as u32 + 1) * 4);
+        self.emit(opcode, rs, rt as u32, offset, false, true);
+        self.nop();
+    }
+
+    /// Emit a branch to an instruction that is not emitted yet, returning the branch to
+    /// [`Assembler::patch`].
+    fn branch_forward(&mut self, opcode: Opcode, rs: u8, rt: u8) -> usize {
+        let branch = self.position();
+        self.branch(opcode, rs, rt, branch + 1);
+        branch
+    }
+
+    /// Point the `branch` to the next instruction.
+    fn patch(&mut self, branch: usize) {
+        self.instructions[branch].op_c = ((self.position() - branch - 1) * 4) as u32;
+    }
+
+    /// Copy `len` words from `src` to `dst`.
+    fn copy_words(&mut self, dst: (u8, u32), src: (u8, u32), len: u32) {
+        for i in 0..len {
+            self.lw(T0, src.0, src.1 + 4 * i);
+            self.sw(T0, dst.0, dst.1 + 4 * i);
+        }
+    }
+
+    /// Write the 64 bytes at `src` to the public values.
+    fn write_public_values(&mut self, src: u8) {
+        self.li(A0, FD_PUBLIC_VALUES);
+        self.addi(A1, src, 0);
+        self.li(A2, AGGREGATION_PAIR_BYTES as u32);
+        self.syscall(SyscallCode::WRITE);
+    }
+
+    /// Permute the Poseidon2 sponge state.
+    fn permute(&mut self) {
+        self.li(A0, SPONGE_ADDR);
+        self.li(A1, 0);
+        self.syscall(SyscallCode::POSEIDON2_PERMUTE);
+    }
+
+    /// Hash the vkey digest at `vk` and the bytes of the public values digest at `pv` into the
+    /// deferred proofs digest like `hash_deferred_proof`.
+    fn hash_deferred_proof(&mut self, vk: (u8, u32), pv: (u8, u32)) {
+        self.copy_words((ZERO, SPONGE_ADDR), (ZERO, DEFERRED_DIGEST_ADDR), 8);
+        for i in 8..16 {
+            self.sw(ZERO, ZERO, SPONGE_ADDR + 4 * i);
+        }
+        self.permute();
+        self.copy_words((ZERO, SPONGE_ADDR), vk, 8);
+        self.permute();
+        for chunk in 0..4 {
+            for i in 0..8 {
+                self.lbu(T0, pv.0, pv.1 + 8 * chunk + i);
+                self.sw(T0, ZERO, SPONGE_ADDR + 4 * i);
+            }
+            self.permute();
+        }
+        self.copy_words((ZERO, DEFERRED_DIGEST_ADDR), (ZERO, SPONGE_ADDR), 8);
+    }
+
+    /// Load the 64 bytes at `src` into the message schedule as a message block and extend it.
+    fn sha_block(&mut self, src: (u8, u32)) {
+        for i in 0..16 {
+            self.lw(T0, src.0, src.1 + 4 * i);
+            self.bswap(T0, T0);
+            self.sw(T0, ZERO, SHA_W_ADDR + 4 * i);
+        }
+        self.sha_extend();
+    }
+
+    /// Load the padding block of a message of `len` bytes into the message schedule and extend
+    /// it.
+    fn sha_padding(&mut self, len: u8) {
+        self.li(T0, 0x8000_0000);
+        self.sw(T0, ZERO, SHA_W_ADDR);
+        for i in 1..14 {
+            self.sw(ZERO, ZERO, SHA_W_ADDR + 4 * i);
+        }
+        self.emit(Opcode::SRL, T0, len as u32, 29, false, true);
+        self.sw(T0, ZERO, SHA_W_ADDR + 4 * 14);
+        self.emit(Opcode::SLL, T0, len as u32, 3, false, true);
+        self.sw(T0, ZERO, SHA_W_ADDR + 4 * 15);
+        self.sha_extend();
+    }
+
+    fn sha_extend(&mut self) {
+        self.li(A0, SHA_W_ADDR);
+        self.li(A1, 0);
+        self.syscall(SyscallCode::SHA_EXTEND);
+    }
+
+    /// Compress the extended message block into the SHA-256 state at `state`.
+    fn sha_compress(&mut self, state: u32) {
+        self.li(A0, SHA_W_ADDR);
+        self.li(A1, state);
+        self.syscall(SyscallCode::SHA_COMPRESS);
+    }
+
+    /// Read the next hint to `addr`, returning its length in `s0`.
+    fn read_hint(&mut self, addr: u32) {
+        self.syscall(SyscallCode::SYSHINTLEN);
+        self.addi(S0, V0, 0);
+        self.li(A0, addr);
+        self.addi(A1, S0, 0);
+        self.syscall(SyscallCode::SYSHINTREAD);
+    }
+}
+
+/// The program aggregating the proofs whose pairs are hinted to it.
+///
+/// The program reads two hints:
+/// - the nodes: the vkey digest of the program itself, followed by a little-endian `u32` per
+///   verified proof, which is zero for a proof verified as is, and the number of pairs its public
+///   values commit to for a proof of this program;
+/// - the pairs, [`AGGREGATION_PAIR_BYTES`] bytes each, of the proofs verified as is and of the
+///   pairs committed to by the proofs of this program, in order.
+///
+/// The public values are a header, which is the vkey digest padded to a pair with zeros, followed
+/// by the pairs, so that the committed value digest is their SHA-256 hash. The program verifies
+/// each proof with `VERIFY_ZKM_PROOF` and hashes its vkey digest and public values digest into
+/// the deferred proofs digest like `hash_deferred_proof`. The public values digest of a proof of
+/// this program is recomputed from the header and its pairs, so that the pairs are only
+/// committed to if they were aggregated.
+#[must_use]
+pub fn aggregation_program() -> Program {
+    let mut asm = Assembler::default();
+
+    // Read the nodes, starting with the vkey digest of the program.
+    asm.read_hint(NODES_ADDR);
+    asm.emit(Opcode::AND, T0, S0 as u32, !3, false, true);
+    asm.addi(S5, T0, NODES_ADDR);
+    asm.li(S4, NODES_ADDR + 32);
+
+    // Read the pairs.
+    asm.read_hint(PAIRS_ADDR);
+    asm.li(S2, PAIRS_ADDR);
+
+    // Write the header to the public values.
+    asm.copy_words((ZERO, HEADER_ADDR), (ZERO, NODES_ADDR), 8);
+    asm.li(T1, HEADER_ADDR);
+    asm.write_public_values(T1);
+
+    // Initialize the SHA-256 state with the header, which starts the public values of the
+    // nested proofs too. The deferred proofs digest starts at zero.
+    for (i, word) in SHA_IV.into_iter().enumerate() {
+        asm.li(T0, word);
+        asm.sw(T0, ZERO, SHA_H_ADDR + 4 * i as u32);
+    }
+    asm.sha_block((ZERO, HEADER_ADDR));
+    asm.sha_compress(SHA_H_ADDR);
+    asm.copy_words((ZERO, NESTED_IV_ADDR), (ZERO, SHA_H_ADDR), 8);
+
+    let node_loop = asm.position();
+    let nodes_end = asm.branch_forward(Opcode::BEQ, S4, S5);
+    asm.lw(S6, S4, 0);
+    asm.addi(S4, S4, 4);
+    let nested = asm.branch_forward(Opcode::BNE, S6, ZERO);
+    {
+        // Verify the proof of the pair.
+        asm.addi(A0, S2, 0);
+        asm.addi(A1, S2, 32);
+        asm.syscall(SyscallCode::VERIFY_ZKM_PROOF);
+        asm.hash_deferred_proof((S2, 0), (S2, 32));
+
+        // Commit to the pair, which is a message block of its own.
+        asm.write_public_values(S2);
+        asm.sha_block((S2, 0));
+        asm.sha_compress(SHA_H_ADDR);
+        asm.addi(S2, S2, AGGREGATION_PAIR_BYTES as u32);
+        asm.branch(Opcode::BEQ, ZERO, ZERO, node_loop);
+    }
+
+    asm.patch(nested);
+    {
+        // Commit to the pairs of the nested proof, hashing them into its public values too.
+        asm.copy_words((ZERO, NESTED_H_ADDR), (ZERO, NESTED_IV_ADDR), 8);
+        asm.addi(S7, S2, 0);
+        let pair_loop = asm.position();
+        asm.write_public_values(S2);
+        asm.sha_block((S2, 0));
+        asm.sha_compress(SHA_H_ADDR);
+        asm.sha_compress(NESTED_H_ADDR);
+        asm.addi(S2, S2, AGGREGATION_PAIR_BYTES as u32);
+        asm.emit(Opcode::SUB, S6, S6 as u32, 1, false, true);
+        asm.branch(Opcode::BNE, S6, ZERO, pair_loop);
+
+        // Hash the padding block of the nested public values, which are the header and the pairs.
+        asm.emit(Opcode::SUB, T1, S2 as u32, S7 as u32, false, false);
+        asm.addi(T1, T1, AGGREGATION_PAIR_BYTES as u32);
+        asm.sha_padding(T1);
+        asm.sha_compress(NESTED_H_ADDR);
+        for i in 0..8 {
+            asm.lw(T0, ZERO, NESTED_H_ADDR + 4 * i);
+            asm.bswap(T0, T0);
+            asm.sw(T0, ZERO, NESTED_DIGEST_ADDR + 4 * i);
+        }
+
+        // Verify the nested proof against the vkey digest of the program.
+        asm.li(A0, HEADER_ADDR);
+        asm.li(A1, NESTED_DIGEST_ADDR);
+        asm.syscall(SyscallCode::VERIFY_ZKM_PROOF);
+        asm.hash_deferred_proof((ZERO, HEADER_ADDR), (ZERO, NESTED_DIGEST_ADDR));
+        asm.branch(Opcode::BEQ, ZERO, ZERO, node_loop);
+    }
+
+    // Hash the padding block of the public values.
+    asm.patch(nodes_end);
+    asm.emit(Opcode::SUB, T1, S2 as u32, PAIRS_ADDR - AGGREGATION_PAIR_BYTES as u32, false, true);
+    asm.sha_padding(T1);
+    asm.sha_compress(SHA_H_ADDR);
+
+    // Commit to the digests and halt.
+    for i in 0..8 {
+        asm.lw(T0, ZERO, SHA_H_ADDR + 4 * i);
+        asm.bswap(A1, T0);
+        asm.li(A0, i);
+        asm.syscall(SyscallCode::COMMIT);
+    }
+    for i in 0..8 {
+        asm.lw(A1, ZERO, DEFERRED_DIGEST_ADDR + 4 * i);
+        asm.li(A0, i);
+        asm.syscall(SyscallCode::COMMIT_DEFERRED_PROOFS);
+    }
+    asm.li(A0, 0);
+    asm.syscall(SyscallCode::HALT);
+
+    Program::new(asm.instructions, 0, 0)
+}
+
+/// Encodes the hints of the [`aggregation_program`] with the given vkey digest.
+///
+/// Each node is the pair of a proof verified as is, or the public values of a proof of the
+/// program, which are aggregated with the pairs they commit to.
+pub(crate) fn aggregation_hints<'a>(
+    vk_digest: &[u32; 8],
+    nodes: impl IntoIterator<Item = AggregationNode<'a>>,
+) -> (Vec<u8>, Vec<u8>) {
+    let mut node_bytes = vk_digest.iter().flat_map(|word| word.to_le_bytes()).collect::<Vec<_>>();
+    let mut pair_bytes = Vec::new();
+    for node in nodes {
+        match node {
+            AggregationNode::Pair((vk_digest, pv_digest)) => {
+                node_bytes.extend(0u32.to_le_bytes());
+                pair_bytes.extend(vk_digest.iter().flat_map(|word| word.to_le_bytes()));
+                pair_bytes.extend(pv_digest);
+            }
+            AggregationNode::Aggregated(public_values) => {
+                let pairs = &public_values.as_slice()[AGGREGATION_PAIR_BYTES..];
+                node_bytes.extend(((pairs.len() / AGGREGATION_PAIR_BYTES) as u32).to_le_bytes());
+                pair_bytes.extend(pairs);
+            }
+        }
+    }
+    (node_bytes, pair_bytes)
+}
+
+/// A proof verified by the [`aggregation_program`].
+pub(crate) enum AggregationNode<'a> {
+    /// A proof with the given vkey digest and public values digest.
+    Pair(([u32; 8], [u8; 32])),
+    /// A proof of the program with the given public values.
+    Aggregated(&'a ZKMPublicValues),
+}
+
+/// The vkey digests and public values digests committed by a proof of the aggregation program
+/// with the verifying key `vk`, in order.
+///
+/// Returns `None` if the public values were not committed by the program with the verifying key
+/// `vk`.
+#[must_use]
+pub fn aggregated_pairs(
+    public_values: &ZKMPublicValues,
+    vk: &ZKMVerifyingKey,
+) -> Option<Vec<([u32; 8], [u8; 32])>> {
+    let (vk_digest, pairs) = decode_public_values(public_values)?;
+    (vk_digest == vk.vk.hash_u32()).then_some(pairs)
+}
+
+/// Decodes the public values of the aggregation program into the vkey digest of the header and
+/// the pairs.
+fn decode_public_values(
+    public_values: &ZKMPublicValues,
+) -> Option<([u32; 8], Vec<([u32; 8], [u8; 32])>)> {
+    let bytes = public_values.as_slice();
+    if bytes.len() < AGGREGATION_PAIR_BYTES || bytes.len() % AGGREGATION_PAIR_BYTES != 0 {
+        return None;
+    }
+    let mut pairs = bytes.chunks_exact(AGGREGATION_PAIR_BYTES).map(|pair| {
+        let vk_digest = core::array::from_fn(|i| {
+            u32::from_le_bytes(pair[4 * i..4 * i + 4].try_into().unwrap())
+        });
+        (vk_digest, <[u8; 32]>::try_from(&pair[32..]).unwrap())
+    });
+    let (vk_digest, padding) = pairs.next().unwrap();
+    (padding == [0; 32]).then(|| (vk_digest, pairs.collect()))
+}
+
+impl<C: ZKMProverComponents> ZKMProver<C> {
+    /// Creates the proving key and the verifying key of the [`aggregation_program`].
+    pub fn aggregation_setup(&self) -> (DeviceProvingKey<C>, Program, ZKMVerifyingKey) {
+        let mut program = aggregation_program();
+        if let Some(core_shape_config) = &self.core_shape_config {
+            core_shape_config
+                .fix_preprocessed_shape(&mut program)
+                .expect("the aggregation program has no allowed preprocessed shape");
+        }
+        let (pk_d, vk) = self.core_prover.setup(&program);
+        let vk = ZKMVerifyingKey { vk, fri_parameters: self.core_fri_parameters() };
+        (pk_d, program, vk)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use p3_field::{FieldAlgebra, PrimeField32};
+    use p3_koala_bear::KoalaBear;
+    use zkm_core_executor::{Executor, ZKMContext};
+    use zkm_primitives::hash_deferred_proof;
+    use zkm_stark::ZKMCoreOpts;
+
+    use super::*;
+
+    /// Encodes the public values of the aggregation program with the given vkey digest.
+    fn encode_public_values(vk_digest: &[u32; 8], pairs: &[([u32; 8], [u8; 32])]) -> Vec<u8> {
+        let header = (*vk_digest, [0; 32]);
+        core::iter::once(&header)
+            .chain(pairs)
+            .flat_map(|(vk_digest, pv_digest)| {
+                vk_digest.iter().flat_map(|word| word.to_le_bytes()).chain(*pv_digest)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_aggregation_program() {
+        let vk_digest = core::array::from_fn(|j| 1000 + j as u32);
+        let pairs = (0..4u32)
+            .map(|i| {
+                let vk_digest = core::array::from_fn(|j| i * 8 + j as u32);
+                let pv_digest = core::array::from_fn(|j| (i * 32 + j as u32) as u8);
+                (vk_digest, pv_digest)
+            })
+            .collect::<Vec<([u32; 8], [u8; 32])>>();
+
+        // The middle pairs are committed to by a nested proof of the program.
+        let nested = ZKMPublicValues::from(&encode_public_values(&vk_digest, &pairs[1..3]));
+        let nested_pair = (vk_digest, <[u8; 32]>::try_from(nested.hash()).unwrap());
+        let (nodes, pair_bytes) = aggregation_hints(
+            &vk_digest,
+            [
+                AggregationNode::Pair(pairs[0]),
+                AggregationNode::Aggregated(&nested),
+                AggregationNode::Pair(pairs[3]),
+            ],
+        );
+
+        let context = ZKMContext::builder().set_skip_deferred_proof_verification(true).build();
+        let mut runtime =
+            Executor::with_context(aggregation_program(), ZKMCoreOpts::default(), context);
+        runtime.write_vecs(&[nodes, pair_bytes]);
+        runtime.run().unwrap();
+
+        let public_values = ZKMPublicValues::from(&runtime.state.public_values_stream);
+        assert_eq!(public_values.as_slice(), encode_public_values(&vk_digest, &pairs));
+        assert_eq!(decode_public_values(&public_values), Some((vk_digest, pairs.clone())));
+
+        let committed_value_digest = runtime.record.public_values.committed_value_digest;
+        assert_eq!(committed_value_digest.map(u32::to_le_bytes).concat(), public_values.hash());
+
+        let deferred_proofs_digest = [pairs[0], nested_pair, pairs[3]].iter().fold(
+            [KoalaBear::ZERO; 8],
+            |digest, (vk_digest, pv_digest)| {
+                hash_deferred_proof(
+                    &digest,
+                    &vk_digest.map(KoalaBear::from_canonical_u32),
+                    &pv_digest.map(KoalaBear::from_canonical_u8),
+                )
+            },
+        );
+        assert_eq!(
+            runtime.record.public_values.deferred_proofs_digest,
+            deferred_proofs_digest.map(|x| x.as_canonical_u32())
+        );
+    }
+}
@@ -1,10 +1,5 @@
 use p3_koala_bear::KoalaBear;
-use std::{
-    borrow::Borrow,
-    fs::{metadata, File},
-    io::Write,
-    path::PathBuf,
-};
+use std::{borrow::Borrow, fs::File, io::Write, path::PathBuf};
 use zkm_core_executor::ZKMContext;
 use zkm_core_machine::io::ZKMStdin;
 use zkm_recursion_circuit::{
@@ -22,7 +17,9 @@ use zkm_recursion_core::{air::RecursionPublicValues, hash_vkey_with_part_vk};
 
 pub use zkm_recursion_circuit::witness::{OuterWitness, Witnessable};
 
-use zkm_recursion_gnark_ffi::{DvSnarkBn254Prover, Groth16Bn254Prover, PlonkBn254Prover};
+use zkm_recursion_gnark_ffi::Groth16Bn254Prover;
+#[cfg(feature = "native-gnark")]
+use zkm_recursion_gnark_ffi::{DvSnarkBn254Prover, PlonkBn254Prover};
 use zkm_stark::{ShardProof, StarkVerifyingKey, ZKMProverOpts};
 
 use crate::{
@@ -33,6 +30,7 @@ use crate::{
 pub const PART_STARK_VK_PATH: &str = "part_stark_vk.bin";
 
 /// Tries to build the PLONK artifacts inside the development directory.
+#[cfg(feature = "native-gnark")]
 pub fn try_build_plonk_bn254_artifacts_dev(
     template_vk: &StarkVerifyingKey<OuterSC>,
     template_proof: &ShardProof<OuterSC>,
@@ -55,6 +53,7 @@ pub fn try_build_groth16_bn254_artifacts_dev(
 }
 
 /// Tries to build the dv-snark bn254 artifacts in the current environment.
+#[cfg(feature = "native-gnark")]
 pub fn try_build_dvsnark_bn254_artifacts_dev(
     template_vk: &StarkVerifyingKey<OuterSC>,
     template_proof: &ShardProof<OuterSC>,
@@ -67,14 +66,14 @@ pub fn try_build_dvsnark_bn254_artifacts_dev(
     let r1cs_cached_path = store_dir.join("r1cs_cached");
 
     let mut r1cs_to_dvsnark_content_exist = false;
-    if let Ok(md) = metadata(&r1cs_to_dvsnark_path) {
+    if let Ok(md) = std::fs::metadata(&r1cs_to_dvsnark_path) {
         if md.len() > 1024 {
             r1cs_to_dvsnark_content_exist = true;
         }
     }
 
     let mut r1cs_cached_content_exist = false;
-    if let Ok(md) = metadata(&r1cs_cached_path) {
+    if let Ok(md) = std::fs::metadata(&r1cs_cached_path) {
         if md.len() > 1024 {
             r1cs_cached_content_exist = true;
         }
@@ -107,6 +106,7 @@ pub fn dvsnark_bn254_artifacts_dev_dir() -> PathBuf {
 
 /// Build the plonk bn254 artifacts to the given directory for the given verification key and
 /// template proof.
+#[cfg(feature = "native-gnark")]
 pub fn build_plonk_bn254_artifacts(
     template_vk: &StarkVerifyingKey<OuterSC>,
     template_proof: &ShardProof<OuterSC>,
@@ -139,6 +139,7 @@ pub fn build_groth16_bn254_artifacts(
 
 /// Build the dv-snark bn254 artifacts to the given directory for the given verification key and
 /// template proof.
+#[cfg(feature = "native-gnark")]
 pub fn build_dvsnark_bn254_artifacts(
     template_vk: &StarkVerifyingKey<OuterSC>,
     template_proof: &ShardProof<OuterSC>,
@@ -157,6 +158,7 @@ pub fn build_dvsnark_bn254_artifacts(
 ///
 /// This may take a while as it needs to first generate a dummy proof and then it needs to compile
 /// the circuit.
+#[cfg(feature = "native-gnark")]
 pub fn build_plonk_bn254_artifacts_with_dummy(build_dir: impl Into<PathBuf>) {
     let (wrap_vk, wrapped_proof) = dummy_proof();
     crate::build::build_plonk_bn254_artifacts(&wrap_vk, &wrapped_proof, build_dir.into());
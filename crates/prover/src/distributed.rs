@@ -0,0 +1,753 @@
+//! Distributed core proving with a coordinator and local worker processes.
+//!
+//! The coordinator executes the program in checkpoint mode and hands every checkpoint to one of
+//! the workers, which traces it into shards. The shards are numbered by the coordinator in
+//! checkpoint order, since the public values and the deferred events of a shard depend on all the
+//! previous ones. The workers then prove their shards and send the proofs back, and the
+//! coordinator assembles them into a [`ZKMCoreProof`] that can be fed into
+//! [`ZKMProver::compress`].
+//!
+//! Workers are started with [`serve_worker`], and listen on a TCP or Unix socket.
+
+use std::{
+    collections::BTreeMap,
+    fmt,
+    io::{self, BufReader, BufWriter, Read, Write},
+    net::{TcpListener, TcpStream},
+    path::PathBuf,
+    str::FromStr,
+    sync::{mpsc::sync_channel, Arc, Condvar, Mutex},
+};
+
+#[cfg(unix)]
+use std::os::unix::net::{UnixListener, UnixStream};
+
+use rayon::prelude::*;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use thiserror::Error;
+use zkm_core_executor::{
+    ExecutionError, ExecutionRecord, ExecutionReport, ExecutionState, Executor, Program, ZKMContext,
+};
+use zkm_core_machine::{
+    io::ZKMStdin,
+    utils::{try_trace_checkpoint, ZKMCoreProverError},
+};
+use zkm_primitives::io::ZKMPublicValues;
+use zkm_stark::{
+    air::PublicValues, MachineProver, MachineProvingKey, MachineRecord, ShardProof,
+    StarkGenericConfig, ZKMCoreOpts, ZKMProverOpts,
+};
+
+use crate::{
+    components::ZKMProverComponents, CoreSC, DeviceProvingKey, ZKMCoreProof, ZKMCoreProofData,
+    ZKMProver,
+};
+
+#[derive(Error, Debug)]
+pub enum DistributedProverError {
+    #[error("failed to execute program: {0}")]
+    Execution(#[from] ExecutionError),
+    #[error("io error: {0}")]
+    Io(#[from] io::Error),
+    #[error("serialization error: {0}")]
+    Serialization(#[from] bincode::Error),
+    #[error("core prover error: {0}")]
+    Core(#[from] ZKMCoreProverError),
+    #[error("worker {0} failed: {1}")]
+    Worker(WorkerAddress, String),
+    #[error("unexpected message from {0}")]
+    UnexpectedMessage(WorkerAddress),
+    #[error("no worker addresses were given")]
+    NoWorkers,
+    #[error("proving was aborted by another worker")]
+    Aborted,
+}
+
+/// The address of a core proving worker.
+///
+/// Parsed from `tcp://<host>:<port>` or `unix://<path>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WorkerAddress {
+    Tcp(String),
+    #[cfg(unix)]
+    Unix(PathBuf),
+}
+
+impl FromStr for WorkerAddress {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(addr) = s.strip_prefix("tcp://") {
+            return Ok(Self::Tcp(addr.to_string()));
+        }
+        #[cfg(unix)]
+        if let Some(path) = s.strip_prefix("unix://") {
+            return Ok(Self::Unix(PathBuf::from(path)));
+        }
+        Err(format!("invalid worker address {s}, expected tcp://<host>:<port> or unix://<path>"))
+    }
+}
+
+impl fmt::Display for WorkerAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Tcp(addr) => write!(f, "tcp://{addr}"),
+            #[cfg(unix)]
+            Self::Unix(path) => write!(f, "unix://{}", path.display()),
+        }
+    }
+}
+
+/// A message sent by the coordinator to a worker.
+#[derive(Serialize, Deserialize)]
+enum CoordinatorMessage {
+    /// Starts a proving job. Sent once, at the start of the connection.
+    Setup { program: Box<Program>, opts: ZKMCoreOpts },
+    /// Traces a checkpoint into shards.
+    Trace { index: usize, state: Box<ExecutionState>, done: bool },
+    /// Proves the shards of the last traced checkpoint, followed by the deferred shards.
+    ///
+    /// If `packed_memory` is set, the worker first tries to pack its memory events into the last
+    /// shard instead of proving the deferred shards, like [`ZKMProver::prove_core`] does.
+    Prove {
+        public_values: Vec<PublicValues<u32, u32>>,
+        deferred: Vec<ExecutionRecord>,
+        packed_memory: Option<Box<ExecutionRecord>>,
+    },
+    /// Ends the proving job.
+    Finish,
+}
+
+/// A message sent by a worker to the coordinator.
+#[derive(Serialize, Deserialize)]
+#[serde(bound = "")]
+enum WorkerMessage<SC: StarkGenericConfig> {
+    /// The public values of the traced shards, and the events they deferred.
+    Traced {
+        public_values: Vec<PublicValues<u32, u32>>,
+        deferred: Box<ExecutionRecord>,
+        report: Box<ExecutionReport>,
+    },
+    /// The proofs of the shards.
+    Proved(Vec<ShardProof<SC>>),
+    /// The worker failed to handle the last message.
+    Failed(String),
+}
+
+/// A bidirectional connection between the coordinator and a worker.
+struct Connection {
+    reader: BufReader<Box<dyn Read + Send>>,
+    writer: BufWriter<Box<dyn Write + Send>>,
+}
+
+impl Connection {
+    fn connect(address: &WorkerAddress) -> io::Result<Self> {
+        match address {
+            WorkerAddress::Tcp(addr) => Self::from_tcp(TcpStream::connect(addr)?),
+            #[cfg(unix)]
+            WorkerAddress::Unix(path) => Self::from_unix(UnixStream::connect(path)?),
+        }
+    }
+
+    fn from_tcp(stream: TcpStream) -> io::Result<Self> {
+        stream.set_nodelay(true)?;
+        Ok(Self {
+            reader: BufReader::new(Box::new(stream.try_clone()?)),
+            writer: BufWriter::new(Box::new(stream)),
+        })
+    }
+
+    #[cfg(unix)]
+    fn from_unix(stream: UnixStream) -> io::Result<Self> {
+        Ok(Self {
+            reader: BufReader::new(Box::new(stream.try_clone()?)),
+            writer: BufWriter::new(Box::new(stream)),
+        })
+    }
+
+    fn send<T: Serialize>(&mut self, message: &T) -> Result<(), DistributedProverError> {
+        bincode::serialize_into(&mut self.writer, message)?;
+        self.writer.flush()?;
+        Ok(())
+    }
+
+    fn recv<T: DeserializeOwned>(&mut self) -> Result<T, DistributedProverError> {
+        Ok(bincode::deserialize_from(&mut self.reader)?)
+    }
+}
+
+/// A socket a worker listens on for coordinators.
+pub enum WorkerListener {
+    Tcp(TcpListener),
+    #[cfg(unix)]
+    Unix(UnixListener),
+}
+
+impl WorkerListener {
+    /// Binds a listener to the given address. An existing Unix socket file is replaced.
+    pub fn bind(address: &WorkerAddress) -> io::Result<Self> {
+        match address {
+            WorkerAddress::Tcp(addr) => Ok(Self::Tcp(TcpListener::bind(addr)?)),
+            #[cfg(unix)]
+            WorkerAddress::Unix(path) => {
+                if path.exists() {
+                    std::fs::remove_file(path)?;
+                }
+                Ok(Self::Unix(UnixListener::bind(path)?))
+            }
+        }
+    }
+
+    fn accept(&self) -> io::Result<Connection> {
+        match self {
+            Self::Tcp(listener) => Connection::from_tcp(listener.accept()?.0),
+            #[cfg(unix)]
+            Self::Unix(listener) => Connection::from_unix(listener.accept()?.0),
+        }
+    }
+}
+
+/// Serves core proving jobs on `listener`, one coordinator connection at a time.
+///
+/// Errors in a job are reported to its coordinator, and the worker keeps serving.
+pub fn serve_worker<C: ZKMProverComponents>(
+    prover: &ZKMProver<C>,
+    listener: &WorkerListener,
+) -> io::Result<()> {
+    loop {
+        let mut connection = listener.accept()?;
+        tracing::info!("accepted coordinator connection");
+        match handle_coordinator(prover, &mut connection) {
+            Ok(()) => tracing::info!("coordinator disconnected"),
+            Err(e) => tracing::error!("proving job failed: {}", e),
+        }
+    }
+}
+
+/// Handles the messages of a coordinator until it disconnects.
+fn handle_coordinator<C: ZKMProverComponents>(
+    prover: &ZKMProver<C>,
+    connection: &mut Connection,
+) -> Result<(), DistributedProverError> {
+    let (program, opts) = match connection.recv::<CoordinatorMessage>()? {
+        CoordinatorMessage::Setup { program, opts } => (Arc::new(*program), opts),
+        _ => return Err(io::Error::other("expected a setup message").into()),
+    };
+    let (pk, _) = prover.core_prover.setup(&program);
+    let mut challenger = prover.core_prover.config().challenger();
+    pk.observe_into(&mut challenger);
+
+    let mut traced: Option<Vec<ExecutionRecord>> = None;
+    loop {
+        let response = match connection.recv::<CoordinatorMessage>()? {
+            CoordinatorMessage::Trace { index, state, done } => {
+                tracing::info!("tracing checkpoint {} (done: {})", index, done);
+                let (mut records, report) = match try_trace_checkpoint::<CoreSC>(
+                    Program::clone(&program),
+                    *state,
+                    opts,
+                    prover.core_shape_config.as_ref(),
+                ) {
+                    Ok(traced) => traced,
+                    Err(e) => {
+                        connection.send(&WorkerMessage::<CoreSC>::Failed(format!(
+                            "failed to trace checkpoint {index}: {e}"
+                        )))?;
+                        continue;
+                    }
+                };
+
+                // Defer the events that are too expensive to include in every shard.
+                let mut deferred = ExecutionRecord::new(Arc::default());
+                for record in records.iter_mut() {
+                    deferred.append(&mut record.defer());
+                }
+                let public_values = records.iter().map(|record| record.public_values).collect();
+                traced = Some(records);
+
+                WorkerMessage::<CoreSC>::Traced {
+                    public_values,
+                    deferred: Box::new(deferred),
+                    report: Box::new(report),
+                }
+            }
+            CoordinatorMessage::Prove { public_values, deferred, packed_memory } => {
+                let mut records = traced.take().unwrap_or_default();
+                for (record, public_values) in records.iter_mut().zip(public_values) {
+                    record.public_values = public_values;
+                }
+                let deferred = deferred
+                    .into_iter()
+                    .map(|mut record| {
+                        record.program = program.clone();
+                        record
+                    })
+                    .collect();
+                match prepare_shards(prover, records, deferred, packed_memory, &opts)
+                    .and_then(|records| prove_shards(prover, &pk, &challenger, records))
+                {
+                    Ok(proofs) => WorkerMessage::Proved(proofs),
+                    Err(e) => WorkerMessage::Failed(e),
+                }
+            }
+            CoordinatorMessage::Setup { .. } => {
+                WorkerMessage::Failed("the job was already set up".to_string())
+            }
+            CoordinatorMessage::Finish => return Ok(()),
+        };
+        connection.send(&response)?;
+    }
+}
+
+/// Generates the dependencies of the shards of a checkpoint and fixes their shapes.
+///
+/// If `packed_memory` is set, its memory events are packed into the last record, and the deferred
+/// records are only used if the packed records do not fit in a shape.
+fn prepare_shards<C: ZKMProverComponents>(
+    prover: &ZKMProver<C>,
+    mut records: Vec<ExecutionRecord>,
+    deferred: Vec<ExecutionRecord>,
+    packed_memory: Option<Box<ExecutionRecord>>,
+    opts: &ZKMCoreOpts,
+) -> Result<Vec<ExecutionRecord>, String> {
+    if let Some(memory) = packed_memory {
+        let mut packed = records.clone();
+        if let Some(last) = packed.last_mut() {
+            let memory = *memory;
+            last.global_memory_initialize_events.extend(memory.global_memory_initialize_events);
+            last.global_memory_finalize_events.extend(memory.global_memory_finalize_events);
+            last.public_values.previous_init_addr_bits =
+                memory.public_values.previous_init_addr_bits;
+            last.public_values.last_init_addr_bits = memory.public_values.last_init_addr_bits;
+            last.public_values.previous_finalize_addr_bits =
+                memory.public_values.previous_finalize_addr_bits;
+            last.public_values.last_finalize_addr_bits =
+                memory.public_values.last_finalize_addr_bits;
+
+            generate_dependencies(prover, &mut packed, opts)?;
+            let fixed_shape = match &prover.core_shape_config {
+                Some(shape_config) => {
+                    packed.iter_mut().all(|record| shape_config.fix_shape(record).is_ok())
+                }
+                None => true,
+            };
+            if fixed_shape {
+                return Ok(packed);
+            }
+        }
+    }
+
+    records.extend(deferred);
+    generate_dependencies(prover, &mut records, opts)?;
+    if let Some(shape_config) = &prover.core_shape_config {
+        for record in records.iter_mut() {
+            shape_config.fix_shape(record).map_err(|e| format!("failed to fix shape: {e:?}"))?;
+        }
+    }
+    Ok(records)
+}
+
+fn generate_dependencies<C: ZKMProverComponents>(
+    prover: &ZKMProver<C>,
+    records: &mut [ExecutionRecord],
+    opts: &ZKMCoreOpts,
+) -> Result<(), String> {
+    prover
+        .core_prover
+        .machine()
+        .generate_dependencies(records, opts, None)
+        .map_err(|e| format!("failed to generate dependencies: {e:?}"))
+}
+
+/// Proves the prepared shards of a checkpoint.
+fn prove_shards<C: ZKMProverComponents>(
+    prover: &ZKMProver<C>,
+    pk: &DeviceProvingKey<C>,
+    challenger: &<CoreSC as StarkGenericConfig>::Challenger,
+    records: Vec<ExecutionRecord>,
+) -> Result<Vec<ShardProof<CoreSC>>, String> {
+    records
+        .into_par_iter()
+        .map(|record| {
+            let traces = prover
+                .core_prover
+                .generate_traces(&record)
+                .map_err(|e| format!("failed to generate traces: {e:?}"))?;
+            let main_data = prover.core_prover.commit(&record, traces);
+            prover
+                .core_prover
+                .open(pk, main_data, &mut challenger.clone())
+                .map_err(|e| format!("failed to open shard: {e:?}"))
+        })
+        .collect()
+}
+
+/// Lets the coordinator threads number the shards in checkpoint order, like
+/// [`zkm_core_machine::utils::concurrency::TurnBasedSync`], but can be aborted so that no thread
+/// waits forever for a failed checkpoint.
+struct Turns {
+    state: Mutex<(usize, bool)>,
+    cv: Condvar,
+}
+
+impl Turns {
+    fn new() -> Self {
+        Self { state: Mutex::new((0, false)), cv: Condvar::new() }
+    }
+
+    fn wait_for_turn(&self, turn: usize) -> Result<(), DistributedProverError> {
+        let mut state = self.state.lock().unwrap();
+        while state.0 != turn && !state.1 {
+            state = self.cv.wait(state).unwrap();
+        }
+        if state.1 {
+            return Err(DistributedProverError::Aborted);
+        }
+        Ok(())
+    }
+
+    fn advance_turn(&self) {
+        self.state.lock().unwrap().0 += 1;
+        self.cv.notify_all();
+    }
+
+    fn abort(&self) {
+        self.state.lock().unwrap().1 = true;
+        self.cv.notify_all();
+    }
+
+    fn is_aborted(&self) -> bool {
+        self.state.lock().unwrap().1
+    }
+}
+
+/// The state of the shards numbered so far by the coordinator.
+struct ShardState {
+    public_values: PublicValues<u32, u32>,
+    deferred: ExecutionRecord,
+    report: ExecutionReport,
+}
+
+impl<C: ZKMProverComponents> ZKMProver<C> {
+    /// Generate shard proofs like [`Self::prove_core`], tracing and proving the checkpoints on the
+    /// given workers.
+    ///
+    /// Each worker must be served by [`serve_worker`] with the same shape configuration as this
+    /// prover, so that the shards have the same shapes as with [`Self::prove_core`].
+    pub fn prove_core_distributed<'a>(
+        &'a self,
+        program: Program,
+        stdin: &ZKMStdin,
+        opts: ZKMProverOpts,
+        mut context: ZKMContext<'a>,
+        workers: &[WorkerAddress],
+    ) -> Result<ZKMCoreProof, DistributedProverError> {
+        if workers.is_empty() {
+            return Err(DistributedProverError::NoWorkers);
+        }
+        let opts = opts.core_opts;
+
+        // Connect to the workers before executing anything.
+        let mut connections = Vec::with_capacity(workers.len());
+        for address in workers {
+            let mut connection = Connection::connect(address)?;
+            connection
+                .send(&CoordinatorMessage::Setup { program: Box::new(program.clone()), opts })?;
+            connections.push((address.clone(), connection));
+        }
+
+        // Setup the runtime.
+        context.subproof_verifier = Some(self);
+        let mut runtime = Executor::with_context(program.clone(), opts, context);
+        runtime.maximal_shapes = self.core_shape_config.as_ref().map(|config| {
+            config.maximal_core_shapes(opts.shard_size.ilog2() as usize).into_iter().collect()
+        });
+        runtime.write_vecs(&stdin.buffer);
+        for (proof, vk) in stdin.proofs.iter() {
+            runtime.write_proof(proof.clone(), vk.clone());
+        }
+
+        let turns = Turns::new();
+        let shard_state = Mutex::new(ShardState {
+            public_values: PublicValues::<u32, u32>::default().reset(),
+            deferred: ExecutionRecord::new(Arc::new(program.clone())),
+            report: ExecutionReport::default(),
+        });
+        let shard_proofs = Mutex::new(BTreeMap::<usize, Vec<ShardProof<CoreSC>>>::new());
+        let (checkpoints_tx, checkpoints_rx) =
+            sync_channel::<(usize, ExecutionState, bool, u64)>(opts.checkpoints_channel_capacity);
+        // Shared by the worker threads, so that the generator stops once they have all exited.
+        let checkpoints_rx = Arc::new(Mutex::new(checkpoints_rx));
+
+        let public_values_stream = std::thread::scope(|s| {
+            // Execute the program, handing out the checkpoints to the worker threads.
+            let turns = &turns;
+            let generator = s.spawn(move || -> Result<_, DistributedProverError> {
+                let mut index = 0;
+                loop {
+                    if turns.is_aborted() {
+                        return Err(DistributedProverError::Aborted);
+                    }
+                    let (checkpoint, done) = runtime.execute_state(false)?;
+                    let num_cycles = runtime.state.global_clk;
+                    if checkpoints_tx.send((index, checkpoint, done, num_cycles)).is_err() {
+                        return Err(DistributedProverError::Aborted);
+                    }
+                    if done {
+                        return Ok(runtime.state.public_values_stream);
+                    }
+                    index += 1;
+                }
+            });
+
+            let handles = connections
+                .into_iter()
+                .map(|(address, mut connection)| {
+                    let checkpoints_rx = Arc::clone(&checkpoints_rx);
+                    let shard_state = &shard_state;
+                    let shard_proofs = &shard_proofs;
+                    s.spawn(move || {
+                        let result = prove_checkpoints(
+                            &address,
+                            &mut connection,
+                            &checkpoints_rx,
+                            turns,
+                            shard_state,
+                            shard_proofs,
+                            opts,
+                        );
+                        if result.is_err() {
+                            turns.abort();
+                        }
+                        result
+                    })
+                })
+                .collect::<Vec<_>>();
+            drop(checkpoints_rx);
+
+            // Report the error of the first failed worker rather than the aborts it caused.
+            let mut error = None;
+            for handle in handles {
+                if let Err(e) = handle.join().unwrap() {
+                    if error.is_none() || matches!(error, Some(DistributedProverError::Aborted)) {
+                        error = Some(e);
+                    }
+                }
+            }
+            let public_values_stream = generator.join().unwrap();
+            match error {
+                Some(e) => Err(e),
+                None => public_values_stream,
+            }
+        })?;
+
+        let shard_proofs =
+            shard_proofs.into_inner().unwrap().into_values().flatten().collect::<Vec<_>>();
+        let cycles = shard_state.into_inner().unwrap().report.total_instruction_count();
+        Self::check_for_high_cycles(cycles);
+        Ok(ZKMCoreProof {
+            proof: ZKMCoreProofData(shard_proofs),
+            stdin: stdin.clone(),
+            public_values: ZKMPublicValues::from(&public_values_stream),
+            cycles,
+        })
+    }
+}
+
+/// Hands checkpoints to a worker until the execution is done.
+fn prove_checkpoints(
+    address: &WorkerAddress,
+    connection: &mut Connection,
+    checkpoints_rx: &Mutex<std::sync::mpsc::Receiver<(usize, ExecutionState, bool, u64)>>,
+    turns: &Turns,
+    shard_state: &Mutex<ShardState>,
+    shard_proofs: &Mutex<BTreeMap<usize, Vec<ShardProof<CoreSC>>>>,
+    opts: ZKMCoreOpts,
+) -> Result<(), DistributedProverError> {
+    loop {
+        let received = { checkpoints_rx.lock().unwrap().recv() };
+        let Ok((index, checkpoint, done, num_cycles)) = received else {
+            return connection.send(&CoordinatorMessage::Finish);
+        };
+
+        connection.send(&CoordinatorMessage::Trace { index, state: Box::new(checkpoint), done })?;
+        let (mut public_values, mut deferred, report) =
+            match connection.recv::<WorkerMessage<CoreSC>>()? {
+                WorkerMessage::Traced { public_values, deferred, report } => {
+                    (public_values, deferred, report)
+                }
+                WorkerMessage::Failed(e) => {
+                    return Err(DistributedProverError::Worker(address.clone(), e))
+                }
+                WorkerMessage::Proved(_) => {
+                    return Err(DistributedProverError::UnexpectedMessage(address.clone()))
+                }
+            };
+
+        // Number the shards of the checkpoint, and split off the deferred shards that are ready,
+        // exactly like `prove_with_context` does.
+        turns.wait_for_turn(index)?;
+        let (mut deferred_records, packed_memory) = {
+            let mut shard_state = shard_state.lock().unwrap();
+            let ShardState { public_values: state, deferred: all_deferred, report: all_report } =
+                &mut *shard_state;
+            *all_report += *report;
+            for record_public_values in public_values.iter_mut() {
+                state.shard += 1;
+                state.execution_shard = record_public_values.execution_shard;
+                state.start_pc = record_public_values.start_pc;
+                state.next_pc = record_public_values.next_pc;
+                state.committed_value_digest = record_public_values.committed_value_digest;
+                state.deferred_proofs_digest = record_public_values.deferred_proofs_digest;
+                *record_public_values = *state;
+            }
+            all_deferred.append(&mut deferred);
+
+            // If the memory init/finalize events are few, let the worker try to pack them into
+            // the last shard. The split below still produces the memory shards, which the worker
+            // falls back to if the packed shard does not fit in a shape.
+            let packed_memory = if done
+                && !public_values.is_empty()
+                && num_cycles < 1 << 21
+                && all_deferred.global_memory_initialize_events.len()
+                    < opts.split_opts.combine_memory_threshold
+                && all_deferred.global_memory_finalize_events.len()
+                    < opts.split_opts.combine_memory_threshold
+            {
+                let mut packed_deferred = all_deferred.clone();
+                let mut memory = ExecutionRecord::new(Arc::default());
+                // The memory events are only packed if there are no precompile shards left.
+                packed_deferred
+                    .split(done, Some(&mut memory), opts.split_opts)
+                    .is_empty()
+                    .then(|| Box::new(memory))
+            } else {
+                None
+            };
+
+            let mut deferred_records = all_deferred.split(done, None, opts.split_opts);
+            if !done {
+                state.execution_shard += 1;
+            }
+            for record in deferred_records.iter_mut() {
+                state.shard += 1;
+                state.previous_init_addr_bits = record.public_values.previous_init_addr_bits;
+                state.last_init_addr_bits = record.public_values.last_init_addr_bits;
+                state.previous_finalize_addr_bits =
+                    record.public_values.previous_finalize_addr_bits;
+                state.last_finalize_addr_bits = record.public_values.last_finalize_addr_bits;
+                state.start_pc = state.next_pc;
+                record.public_values = *state;
+            }
+            (deferred_records, packed_memory)
+        };
+        turns.advance_turn();
+
+        // The worker has its own copy of the program.
+        for record in deferred_records.iter_mut() {
+            record.program = Arc::default();
+        }
+        connection.send(&CoordinatorMessage::Prove {
+            public_values,
+            deferred: deferred_records,
+            packed_memory,
+        })?;
+        match connection.recv::<WorkerMessage<CoreSC>>()? {
+            WorkerMessage::Proved(proofs) => {
+                tracing::info!("{} proved checkpoint {} ({} shards)", address, index, proofs.len());
+                shard_proofs.lock().unwrap().insert(index, proofs);
+            }
+            WorkerMessage::Failed(e) => {
+                return Err(DistributedProverError::Worker(address.clone(), e))
+            }
+            WorkerMessage::Traced { .. } => {
+                return Err(DistributedProverError::UnexpectedMessage(address.clone()))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{net::TcpListener, sync::Arc};
+
+    use serial_test::serial;
+    use zkm_core_executor::ZKMContext;
+    use zkm_core_machine::{io::ZKMStdin, utils::setup_logger};
+    use zkm_stark::ZKMProverOpts;
+
+    use super::{serve_worker, WorkerAddress, WorkerListener};
+    use crate::{components::DefaultProverComponents, ZKMProver};
+
+    /// Serves one TCP and one Unix socket worker on localhost.
+    fn spawn_workers(prover: &Arc<ZKMProver<DefaultProverComponents>>) -> Vec<WorkerAddress> {
+        let tcp_listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let tcp_address = WorkerAddress::Tcp(tcp_listener.local_addr().unwrap().to_string());
+        let unix_address = WorkerAddress::Unix(
+            std::env::temp_dir().join(format!("zkm-worker-{}.sock", std::process::id())),
+        );
+        let listeners =
+            [WorkerListener::Tcp(tcp_listener), WorkerListener::bind(&unix_address).unwrap()];
+        for listener in listeners {
+            let prover = Arc::clone(prover);
+            std::thread::spawn(move || serve_worker(&prover, &listener));
+        }
+        vec![tcp_address, unix_address]
+    }
+
+    /// Proves fibonacci with one TCP and one Unix socket worker on localhost, and compresses the
+    /// core proof.
+    #[test]
+    #[serial]
+    #[ignore]
+    fn test_prove_core_distributed() {
+        setup_logger();
+        let prover = Arc::new(ZKMProver::<DefaultProverComponents>::new());
+        let workers = spawn_workers(&prover);
+
+        let (_, _, program, vk) = prover.setup(test_artifacts::FIBONACCI_ELF);
+        let mut opts = ZKMProverOpts::default();
+        opts.core_opts.shard_size = 1 << 12;
+        opts.core_opts.shard_batch_size = 1;
+        let core_proof = prover
+            .prove_core_distributed(
+                program,
+                &ZKMStdin::new(),
+                opts,
+                ZKMContext::default(),
+                &workers,
+            )
+            .unwrap();
+        prover.verify(&core_proof.proof, &vk).unwrap();
+
+        let compressed_proof = prover.compress(&vk, core_proof, vec![], opts).unwrap();
+        prover.verify_compressed(&compressed_proof, &vk).unwrap();
+    }
+
+    /// Checks that the workers produce the same shards as the local prover, including the memory
+    /// events packed into the last shard.
+    #[test]
+    #[serial]
+    #[ignore]
+    fn test_prove_core_distributed_shards() {
+        setup_logger();
+        let prover = Arc::new(ZKMProver::<DefaultProverComponents>::new());
+        let workers = spawn_workers(&prover);
+
+        let (_, pk_d, program, vk) = prover.setup(test_artifacts::FIBONACCI_ELF);
+        let opts = ZKMProverOpts::default();
+        let stdin = ZKMStdin::new();
+        let local_proof =
+            prover.prove_core(&pk_d, program.clone(), &stdin, opts, ZKMContext::default()).unwrap();
+        let distributed_proof = prover
+            .prove_core_distributed(program, &stdin, opts, ZKMContext::default(), &workers)
+            .unwrap();
+        prover.verify(&distributed_proof.proof, &vk).unwrap();
+
+        let shard_public_values = |proof: &crate::ZKMCoreProof| {
+            proof.proof.0.iter().map(|shard| shard.public_values.clone()).collect::<Vec<_>>()
+        };
+        assert_eq!(distributed_proof.proof.0.len(), local_proof.proof.0.len());
+        assert_eq!(shard_public_values(&distributed_proof), shard_public_values(&local_proof));
+    }
+}
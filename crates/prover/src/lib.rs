@@ -11,8 +11,11 @@
 #![allow(clippy::new_without_default)]
 #![allow(clippy::collapsible_else_if)]
 
+pub mod accumulator;
+pub mod aggregation;
 pub mod build;
 pub mod components;
+pub mod distributed;
 pub mod shapes;
 pub mod types;
 pub mod utils;
@@ -26,10 +29,11 @@ use std::{
     path::Path,
     sync::{
         atomic::{AtomicUsize, Ordering},
-        mpsc::sync_channel,
+        mpsc::{sync_channel, RecvTimeoutError},
         Arc, Mutex, OnceLock,
     },
     thread,
+    time::Duration,
 };
 
 use lru::LruCache;
@@ -38,13 +42,16 @@ use p3_koala_bear::KoalaBear;
 use p3_matrix::dense::RowMajorMatrix;
 use shapes::ZKMProofShape;
 use tracing::instrument;
-use zkm_core_executor::{ExecutionError, ExecutionReport, Executor, Program, ZKMContext};
+use zkm_core_executor::{
+    CancellationToken, ExecutionError, ExecutionReport, Executor, Program, ProgressCallback,
+    ProveProgress, ZKMContext,
+};
 use zkm_core_machine::{
     io::ZKMStdin,
     mips::MipsAir,
     reduce::ZKMReduceProof,
     shape::CoreShapeConfig,
-    utils::{concurrency::TurnBasedSync, ZKMCoreProverError},
+    utils::{concurrency::TurnBasedSync, dry_run, DryRunReport, ZKMCoreProverError},
 };
 use zkm_primitives::{hash_deferred_proof, io::ZKMPublicValues};
 use zkm_recursion_circuit::{
@@ -74,17 +81,19 @@ use zkm_recursion_core::{
     stark::KoalaBearPoseidon2Outer,
     RecursionProgram, Runtime as RecursionRuntime,
 };
+use zkm_recursion_gnark_ffi::groth16_bn254::Groth16Bn254Prover;
 pub use zkm_recursion_gnark_ffi::proof::{DvSnarkBn254Proof, Groth16Bn254Proof, PlonkBn254Proof};
-use zkm_recursion_gnark_ffi::{
-    groth16_bn254::Groth16Bn254Prover, plonk_bn254::PlonkBn254Prover, DvSnarkBn254Prover,
-};
+#[cfg(feature = "native-gnark")]
+use zkm_recursion_gnark_ffi::{plonk_bn254::PlonkBn254Prover, DvSnarkBn254Prover};
 use zkm_stark::{
-    air::PublicValues, koala_bear_poseidon2::KoalaBearPoseidon2, Challenge, MachineProver,
-    ShardProof, StarkGenericConfig, StarkVerifyingKey, Val, Word, ZKMCoreOpts, ZKMProverOpts,
-    DIGEST_SIZE,
+    air::PublicValues, koala_bear_poseidon2::KoalaBearPoseidon2, Challenge, FriParameters,
+    MachineProver, ShardProof, StarkGenericConfig, StarkVerifyingKey, Val, Word, ZKMCoreOpts,
+    ZKMProverOpts, DIGEST_SIZE,
 };
 use zkm_stark::{shape::OrderedShape, MachineProvingKey};
 
+pub use accumulator::*;
+pub use aggregation::*;
 pub use types::*;
 use utils::{words_to_bytes, zkm_committed_values_digest_bn254, zkm_vkey_digest_bn254};
 
@@ -111,7 +120,13 @@ const SHRINK_DEGREE: usize = 3;
 const WRAP_DEGREE: usize = 9;
 
 const CORE_CACHE_SIZE: usize = 5;
+/// The number of compression programs of arities other than [`REDUCE_BATCH_SIZE`] kept compiled.
+const JOIN_CACHE_SIZE: usize = 16;
 pub const REDUCE_BATCH_SIZE: usize = 2;
+/// The largest number of proofs a compress program can join.
+pub const MAX_COMPRESS_ARITY: usize = 4;
+/// How often the compress tree checks the cancellation token while waiting for proofs.
+const CANCELLATION_POLL_INTERVAL: Duration = Duration::from_millis(100);
 
 // TODO: FIX
 //
@@ -148,6 +163,11 @@ pub struct ZKMProver<C: ZKMProverComponents = DefaultProverComponents> {
     /// The cache of compiled compression programs.
     pub join_programs_map: BTreeMap<ZKMCompressWithVkeyShape, Arc<RecursionProgram<KoalaBear>>>,
 
+    /// The cache of compression programs compiled on demand for arities other than
+    /// [`REDUCE_BATCH_SIZE`].
+    pub join_programs_lru:
+        Mutex<LruCache<ZKMCompressWithVkeyShape, Arc<RecursionProgram<KoalaBear>>>>,
+
     /// The number of cache misses for compression programs.
     pub join_cache_misses: AtomicUsize,
 
@@ -174,6 +194,9 @@ pub struct ZKMProver<C: ZKMProverComponents = DefaultProverComponents> {
 
     /// Whether to verify verification keys.
     pub vk_verification: bool,
+
+    /// The number of proofs joined by each compress program of the compress tree.
+    pub compress_arity: usize,
 }
 
 impl<C: ZKMProverComponents> ZKMProver<C> {
@@ -185,8 +208,64 @@ impl<C: ZKMProverComponents> ZKMProver<C> {
 
     /// Creates a new [ZKMProver] with lazily initialized components.
     pub fn uninitialized() -> Self {
+        Self::with_fri_parameters(FriParameters::CORE)
+    }
+
+    /// Creates a new [ZKMProver] generating the core proofs with the given FRI parameters.
+    ///
+    /// The recursion programs verifying the core proofs depend on the parameters, so that
+    /// parameters other than [`FriParameters::CORE`] require setting `VERIFY_VK=false`.
+    pub fn with_fri_parameters(core_fri_parameters: FriParameters) -> Self {
+        let compress_arity = env::var("COMPRESS_ARITY")
+            .map(|v| v.parse().expect("COMPRESS_ARITY must be a usize"))
+            .unwrap_or(REDUCE_BATCH_SIZE);
+        Self::with_parameters(core_fri_parameters, compress_arity)
+    }
+
+    /// Creates a new [ZKMProver] joining `compress_arity` proofs with each compress program.
+    ///
+    /// The allowed compress verification keys are generated for an arity of
+    /// [`REDUCE_BATCH_SIZE`], so that other arities panic unless `VERIFY_VK=false`. The join
+    /// programs of other arities are fixed to the shapes of
+    /// [`RecursionShapeConfig::for_compress_arity`] and compiled on demand.
+    pub fn with_compress_arity(compress_arity: usize) -> Self {
+        Self::with_parameters(FriParameters::CORE, compress_arity)
+    }
+
+    /// Creates a new [ZKMProver] generating the core proofs with the given FRI parameters and
+    /// joining `compress_arity` proofs with each compress program.
+    ///
+    /// The verification keys are verified unless `VERIFY_VK=false`.
+    pub fn with_parameters(core_fri_parameters: FriParameters, compress_arity: usize) -> Self {
+        let vk_verification =
+            env::var("VERIFY_VK").map(|v| v.eq_ignore_ascii_case("true")).unwrap_or(true);
+        Self::with_vk_verification(core_fri_parameters, compress_arity, vk_verification)
+    }
+
+    /// Creates a new [ZKMProver] like [`Self::with_parameters`], verifying the verification keys
+    /// if `vk_verification` is set.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `vk_verification` is set and `compress_arity` isn't [`REDUCE_BATCH_SIZE`], since
+    /// the allowed verification keys only cover the compress programs of that arity.
+    pub fn with_vk_verification(
+        core_fri_parameters: FriParameters,
+        compress_arity: usize,
+        vk_verification: bool,
+    ) -> Self {
+        assert!(
+            (2..=MAX_COMPRESS_ARITY).contains(&compress_arity),
+            "the compress arity must be between 2 and {MAX_COMPRESS_ARITY}, got {compress_arity}"
+        );
+        assert!(
+            !vk_verification || compress_arity == REDUCE_BATCH_SIZE,
+            "the allowed verification keys only cover a compress arity of {REDUCE_BATCH_SIZE}, \
+             set VERIFY_VK=false to use a compress arity of {compress_arity}"
+        );
+
         // Initialize the provers.
-        let core_machine = MipsAir::machine(CoreSC::default());
+        let core_machine = MipsAir::machine(CoreSC::with_fri_parameters(core_fri_parameters));
         let core_prover = C::CoreProver::new(core_machine);
 
         let compress_machine = CompressAir::compress_machine(InnerSC::default());
@@ -215,10 +294,7 @@ impl<C: ZKMProverComponents> ZKMProver<C> {
         let recursion_shape_config = env::var("FIX_RECURSION_SHAPES")
             .map(|v| v.eq_ignore_ascii_case("true"))
             .unwrap_or(true)
-            .then_some(RecursionShapeConfig::default());
-
-        let vk_verification =
-            env::var("VERIFY_VK").map(|v| v.eq_ignore_ascii_case("true")).unwrap_or(true);
+            .then_some(RecursionShapeConfig::for_compress_arity(compress_arity));
 
         tracing::debug!("vk verification: {}", vk_verification);
 
@@ -237,9 +313,13 @@ impl<C: ZKMProverComponents> ZKMProver<C> {
 
         let (root, merkle_tree) = MerkleTree::commit(allowed_vk_map.keys().copied().collect());
 
+        // The number of join programs grows exponentially with the arity, so that only the ones of
+        // the default arity are compiled up front.
         let mut compress_programs = BTreeMap::new();
-        if let Some(config) = &recursion_shape_config {
-            ZKMProofShape::generate_compress_shapes(config, REDUCE_BATCH_SIZE).for_each(|shape| {
+        if let Some(config) =
+            recursion_shape_config.as_ref().filter(|_| compress_arity == REDUCE_BATCH_SIZE)
+        {
+            ZKMProofShape::generate_compress_shapes(config, compress_arity).for_each(|shape| {
                 let compress_shape = ZKMCompressWithVkeyShape {
                     compress_shape: shape.into(),
                     merkle_tree_height: merkle_tree.height,
@@ -267,6 +347,9 @@ impl<C: ZKMProverComponents> ZKMProver<C> {
             lift_programs_lru: Mutex::new(LruCache::new(core_cache_size)),
             lift_cache_misses: AtomicUsize::new(0),
             join_programs_map: compress_programs,
+            join_programs_lru: Mutex::new(LruCache::new(
+                NonZeroUsize::new(JOIN_CACHE_SIZE).unwrap(),
+            )),
             join_cache_misses: AtomicUsize::new(0),
             recursion_vk_root: root,
             recursion_vk_tree: merkle_tree,
@@ -274,11 +357,32 @@ impl<C: ZKMProverComponents> ZKMProver<C> {
             core_shape_config,
             compress_shape_config: recursion_shape_config,
             vk_verification,
+            compress_arity,
             wrap_program: OnceLock::new(),
             wrap_vk: OnceLock::new(),
         }
     }
 
+    /// The FRI parameters of the core proofs.
+    pub fn core_fri_parameters(&self) -> FriParameters {
+        self.core_prover.config().fri_parameters()
+    }
+
+    /// The FRI parameters of the compress proofs.
+    pub fn compress_fri_parameters(&self) -> FriParameters {
+        self.compress_prover.config().fri_parameters()
+    }
+
+    /// The FRI parameters of the shrink proofs.
+    pub fn shrink_fri_parameters(&self) -> FriParameters {
+        self.shrink_prover.config().fri_parameters()
+    }
+
+    /// The FRI parameters of the wrap proofs.
+    pub fn wrap_fri_parameters(&self) -> FriParameters {
+        self.wrap_prover.config().fri_parameters()
+    }
+
     /// Fully initializes the programs, proving keys, and verifying keys that are normally
     /// lazily initialized. TODO: remove this.
     pub fn initialize(&mut self) {}
@@ -291,7 +395,7 @@ impl<C: ZKMProverComponents> ZKMProver<C> {
     ) -> (ZKMProvingKey, DeviceProvingKey<C>, Program, ZKMVerifyingKey) {
         let program = self.get_program(elf).unwrap();
         let (pk, vk) = self.core_prover.setup(&program);
-        let vk = ZKMVerifyingKey { vk };
+        let vk = ZKMVerifyingKey { vk, fri_parameters: self.core_fri_parameters() };
         let pk = ZKMProvingKey {
             pk: self.core_prover.pk_to_host(&pk),
             elf: elf.to_vec(),
@@ -321,19 +425,97 @@ impl<C: ZKMProverComponents> ZKMProver<C> {
         context.subproof_verifier = Some(self);
         let program = self.get_program(elf).unwrap();
         let opts = ZKMCoreOpts::default();
+        let estimate_cost = context.estimate_cost;
         let mut runtime = Executor::with_context(program, opts, context);
+        if estimate_cost {
+            // Split shards like the core prover does, so that the estimated shards match.
+            runtime.maximal_shapes = self.core_shape_config.as_ref().map(|config| {
+                config.maximal_core_shapes(opts.shard_size.ilog2() as usize).into_iter().collect()
+            });
+        }
         runtime.write_vecs(&stdin.buffer);
         for (proof, vkey) in stdin.proofs.iter() {
             runtime.write_proof(proof.clone(), vkey.clone());
         }
-        runtime.run_fast()?;
+        runtime.run_fast().map_err(|err| err.symbolize(elf))?;
         Ok((ZKMPublicValues::from(&runtime.state.public_values_stream), runtime.report))
     }
 
+    /// Execute a Ziren program and fit its shards into the allowed core shapes, without
+    /// generating any traces.
+    ///
+    /// The shards are split with `opts` like [`Self::prove_core`] does, so a shard that fails to
+    /// fit here would fail to prove.
+    #[instrument(name = "dry_run", level = "info", skip_all)]
+    pub fn dry_run<'a>(
+        &'a self,
+        elf: &[u8],
+        stdin: &ZKMStdin,
+        opts: ZKMCoreOpts,
+        mut context: ZKMContext<'a>,
+    ) -> Result<DryRunReport, ZKMCoreProverError> {
+        context.subproof_verifier = Some(self);
+        let default_shape_config;
+        let shape_config = match &self.core_shape_config {
+            Some(shape_config) => shape_config,
+            None => {
+                default_shape_config = CoreShapeConfig::default();
+                &default_shape_config
+            }
+        };
+        let mut program = Program::from(elf).unwrap();
+        if let Err(e) = shape_config.fix_preprocessed_shape(&mut program) {
+            tracing::warn!("no preprocessed shape fits the program: {}", e);
+        }
+        dry_run(self.core_prover.machine(), program, stdin, opts, context, shape_config)
+            .map_err(|err| err.symbolize(elf))
+    }
+
     /// Generate shard proofs which split up and prove the valid execution of a MIPS program with
     /// the core prover. Uses the provided context.
     #[instrument(name = "prove_core", level = "info", skip_all)]
     pub fn prove_core<'a>(
+        &'a self,
+        pk_d: &<<C as ZKMProverComponents>::CoreProver as MachineProver<
+            KoalaBearPoseidon2,
+            MipsAir<KoalaBear>,
+        >>::DeviceProvingKey,
+        program: Program,
+        stdin: &ZKMStdin,
+        opts: ZKMProverOpts,
+        context: ZKMContext<'a>,
+    ) -> Result<ZKMCoreProof, ZKMCoreProverError> {
+        self.prove_core_with_checkpoint_dir(pk_d, program, stdin, opts, context, None)
+    }
+
+    /// Generate shard proofs like [`Self::prove_core`], saving the progress to `checkpoint_dir`.
+    ///
+    /// If `checkpoint_dir` holds the progress of a previous run with the same program and stdin,
+    /// proving resumes from the first checkpoint that has not been proven yet.
+    #[instrument(name = "prove_core_resumable", level = "info", skip_all)]
+    pub fn prove_core_resumable<'a>(
+        &'a self,
+        pk_d: &<<C as ZKMProverComponents>::CoreProver as MachineProver<
+            KoalaBearPoseidon2,
+            MipsAir<KoalaBear>,
+        >>::DeviceProvingKey,
+        program: Program,
+        stdin: &ZKMStdin,
+        opts: ZKMProverOpts,
+        context: ZKMContext<'a>,
+        checkpoint_dir: &Path,
+    ) -> Result<ZKMCoreProof, ZKMCoreProverError> {
+        self.prove_core_with_checkpoint_dir(
+            pk_d,
+            program,
+            stdin,
+            opts,
+            context,
+            Some(checkpoint_dir),
+        )
+    }
+
+    fn prove_core_with_checkpoint_dir<'a>(
         &'a self,
         pk_d: &<<C as ZKMProverComponents>::CoreProver as MachineProver<
             KoalaBearPoseidon2,
@@ -343,11 +525,37 @@ impl<C: ZKMProverComponents> ZKMProver<C> {
         stdin: &ZKMStdin,
         opts: ZKMProverOpts,
         mut context: ZKMContext<'a>,
+        checkpoint_dir: Option<&Path>,
     ) -> Result<ZKMCoreProof, ZKMCoreProverError> {
+        let fri_parameters = self.core_fri_parameters();
+        if fri_parameters != opts.fri_parameters {
+            return Err(ZKMCoreProverError::FriParametersMismatch(
+                fri_parameters,
+                opts.fri_parameters,
+            ));
+        }
+        if !fri_parameters.satisfies(opts.security_level) {
+            return Err(ZKMCoreProverError::InsufficientSecurity(
+                fri_parameters,
+                opts.security_level,
+            ));
+        }
         context.subproof_verifier = Some(self);
         let pk = pk_d;
-        let (proof, public_values_stream, cycles) =
-            zkm_core_machine::utils::prove_with_context::<_, C::CoreProver>(
+        let (proof, public_values_stream, cycles) = match checkpoint_dir {
+            Some(checkpoint_dir) => {
+                zkm_core_machine::utils::prove_with_context_resumable::<_, C::CoreProver>(
+                    &self.core_prover,
+                    pk,
+                    program,
+                    stdin,
+                    opts.core_opts,
+                    context,
+                    self.core_shape_config.as_ref(),
+                    checkpoint_dir,
+                )?
+            }
+            None => zkm_core_machine::utils::prove_with_context::<_, C::CoreProver>(
                 &self.core_prover,
                 pk,
                 program,
@@ -355,7 +563,8 @@ impl<C: ZKMProverComponents> ZKMProver<C> {
                 opts.core_opts,
                 context,
                 self.core_shape_config.as_ref(),
-            )?;
+            )?,
+        };
         Self::check_for_high_cycles(cycles);
         let public_values = ZKMPublicValues::from(&public_values_stream);
         Ok(ZKMCoreProof {
@@ -402,16 +611,29 @@ impl<C: ZKMProverComponents> ZKMProver<C> {
         &self,
         input: &ZKMCompressWithVKeyWitnessValues<InnerSC>,
     ) -> Arc<RecursionProgram<KoalaBear>> {
-        self.join_programs_map.get(&input.shape()).cloned().unwrap_or_else(|| {
-            tracing::warn!("compress program not found in map, recomputing join program.");
-            // Get the operations.
+        if let Some(program) = self.join_programs_map.get(&input.shape()) {
+            return program.clone();
+        }
+        let compile = || {
             Arc::new(compress_program_from_input::<C>(
                 self.compress_shape_config.as_ref(),
                 &self.compress_prover,
                 self.vk_verification,
                 input,
             ))
-        })
+        };
+        if self.compress_arity == REDUCE_BATCH_SIZE {
+            tracing::warn!("compress program not found in map, recomputing join program.");
+            return compile();
+        }
+        let mut cache = self.join_programs_lru.lock().unwrap_or_else(|e| e.into_inner());
+        cache
+            .get_or_insert(input.shape(), || {
+                let misses = self.join_cache_misses.fetch_add(1, Ordering::Relaxed);
+                tracing::debug!("compress cache miss, misses: {}", misses);
+                compile()
+            })
+            .clone()
     }
 
     pub fn shrink_program(
@@ -557,7 +779,7 @@ impl<C: ZKMProverComponents> ZKMProver<C> {
         last_proof_pv: &PublicValues<Word<KoalaBear>, KoalaBear>,
         deferred_proofs: &[ZKMReduceProof<InnerSC>],
         batch_size: usize,
-    ) -> Vec<ZKMDeferredWitnessValues<InnerSC>> {
+    ) -> Result<Vec<ZKMDeferredWitnessValues<InnerSC>>, ZKMRecursionProverError> {
         // Prepare the inputs for the deferred proofs recursive verification.
         let mut deferred_digest = [Val::<InnerSC>::ZERO; DIGEST_SIZE];
         let mut deferred_inputs = Vec::new();
@@ -567,7 +789,7 @@ impl<C: ZKMProverComponents> ZKMProver<C> {
                 batch.iter().cloned().map(|proof| (proof.vk, proof.proof)).collect::<Vec<_>>();
 
             let input = ZKMCompressWitnessValues { vks_and_proofs, is_complete: true };
-            let input = self.make_merkle_proofs(input);
+            let input = self.make_merkle_proofs(input)?;
             let ZKMCompressWithVKeyWitnessValues { compress_val, merkle_val } = input;
 
             deferred_inputs.push(ZKMDeferredWitnessValues {
@@ -587,7 +809,7 @@ impl<C: ZKMProverComponents> ZKMProver<C> {
 
             deferred_digest = Self::hash_deferred_proofs(deferred_digest, batch);
         }
-        deferred_inputs
+        Ok(deferred_inputs)
     }
 
     /// Generate the inputs for the first layer of recursive proofs.
@@ -598,22 +820,21 @@ impl<C: ZKMProverComponents> ZKMProver<C> {
         shard_proofs: &[ShardProof<InnerSC>],
         deferred_proofs: &[ZKMReduceProof<InnerSC>],
         batch_size: usize,
-    ) -> Vec<ZKMCircuitWitness> {
+    ) -> Result<Vec<ZKMCircuitWitness>, ZKMRecursionProverError> {
         let is_complete = shard_proofs.len() == 1 && deferred_proofs.is_empty();
         let core_inputs =
             self.get_recursion_core_inputs(&vk.vk, shard_proofs, batch_size, is_complete);
         let last_proof_pv = shard_proofs.last().unwrap().public_values.as_slice().borrow();
         let deferred_inputs =
-            self.get_recursion_deferred_inputs(&vk.vk, last_proof_pv, deferred_proofs, batch_size);
+            self.get_recursion_deferred_inputs(&vk.vk, last_proof_pv, deferred_proofs, batch_size)?;
 
         let mut inputs = Vec::new();
         inputs.extend(core_inputs.into_iter().map(ZKMCircuitWitness::Core));
         inputs.extend(deferred_inputs.into_iter().map(ZKMCircuitWitness::Deferred));
-        inputs
+        Ok(inputs)
     }
 
     /// Reduce shard proofs to a single shard proof using the recursion prover.
-    #[instrument(name = "compress", level = "info", skip_all)]
     pub fn compress(
         &self,
         vk: &ZKMVerifyingKey,
@@ -621,24 +842,61 @@ impl<C: ZKMProverComponents> ZKMProver<C> {
         deferred_proofs: Vec<ZKMReduceProof<InnerSC>>,
         opts: ZKMProverOpts,
     ) -> Result<ZKMReduceProof<InnerSC>, ZKMRecursionProverError> {
+        self.compress_with_progress(vk, proof, deferred_proofs, opts, None, None)
+    }
+
+    /// Reduce shard proofs to a single shard proof, reporting every completed layer of the tree
+    /// to `progress`.
+    ///
+    /// Once `cancellation_token` is cancelled, the pending proofs of the tree are skipped and
+    /// [`ZKMRecursionProverError::Cancelled`] is returned.
+    #[instrument(name = "compress", level = "info", skip_all)]
+    pub fn compress_with_progress(
+        &self,
+        vk: &ZKMVerifyingKey,
+        proof: ZKMCoreProof,
+        deferred_proofs: Vec<ZKMReduceProof<InnerSC>>,
+        opts: ZKMProverOpts,
+        progress: Option<&ProgressCallback>,
+        cancellation_token: Option<&CancellationToken>,
+    ) -> Result<ZKMReduceProof<InnerSC>, ZKMRecursionProverError> {
+        let is_cancelled = || cancellation_token.is_some_and(CancellationToken::is_cancelled);
+        let fri_parameters = self.compress_prover.config().fri_parameters();
+        if !fri_parameters.satisfies(opts.security_level) {
+            return Err(ZKMRecursionProverError::InsufficientSecurity(
+                fri_parameters,
+                opts.security_level,
+            ));
+        }
+
         // The batch size for reducing two layers of recursion.
-        let batch_size = REDUCE_BATCH_SIZE;
+        let batch_size = self.compress_arity;
         // The batch size for reducing the first layer of recursion.
         let first_layer_batch_size = 1;
 
         let shard_proofs = &proof.proof.0;
 
-        let first_layer_inputs =
-            self.get_first_layer_inputs(vk, shard_proofs, &deferred_proofs, first_layer_batch_size);
+        let first_layer_inputs = self.get_first_layer_inputs(
+            vk,
+            shard_proofs,
+            &deferred_proofs,
+            first_layer_batch_size,
+        )?;
 
         // Calculate the expected height of the tree.
         let mut expected_height = if first_layer_inputs.len() == 1 { 0 } else { 1 };
         let num_first_layer_inputs = first_layer_inputs.len();
         let mut num_layer_inputs = num_first_layer_inputs;
+        let mut layer_sizes = vec![num_layer_inputs];
         while num_layer_inputs > batch_size {
-            num_layer_inputs = num_layer_inputs.div_ceil(2);
+            num_layer_inputs = num_layer_inputs.div_ceil(batch_size);
+            layer_sizes.push(num_layer_inputs);
             expected_height += 1;
         }
+        if expected_height > 0 {
+            layer_sizes.push(1);
+        }
+        let layers_proven = Mutex::new(vec![0; layer_sizes.len()]);
 
         // Generate the proofs.
         let span = tracing::Span::current().clone();
@@ -686,34 +944,49 @@ impl<C: ZKMProverComponents> ZKMProver<C> {
                     loop {
                         let received = { input_rx.lock().unwrap().recv() };
                         if let Ok((index, height, input)) = received {
+                            // Keep draining the inputs so that the turns keep advancing.
+                            if is_cancelled() {
+                                record_and_trace_sync.wait_for_turn(index);
+                                record_and_trace_sync.advance_turn();
+                                continue;
+                            }
+
                             // Get the program and witness stream.
                             let (program, witness_stream) = tracing::debug_span!(
                                 "get program and witness stream"
                             )
-                            .in_scope(|| match input {
-                                ZKMCircuitWitness::Core(input) => {
-                                    let mut witness_stream = Vec::new();
-                                    Witnessable::<InnerConfig>::write(&input, &mut witness_stream);
-                                    (self.recursion_program(&input), witness_stream)
-                                }
-                                ZKMCircuitWitness::Deferred(input) => {
-                                    let mut witness_stream = Vec::new();
-                                    Witnessable::<InnerConfig>::write(&input, &mut witness_stream);
-                                    (self.deferred_program(&input), witness_stream)
-                                }
-                                ZKMCircuitWitness::Compress(input) => {
-                                    let mut witness_stream = Vec::new();
+                            .in_scope(|| -> Result<_, ZKMRecursionProverError> {
+                                Ok(match input {
+                                    ZKMCircuitWitness::Core(input) => {
+                                        let mut witness_stream = Vec::new();
+                                        Witnessable::<InnerConfig>::write(
+                                            &input,
+                                            &mut witness_stream,
+                                        );
+                                        (self.recursion_program(&input), witness_stream)
+                                    }
+                                    ZKMCircuitWitness::Deferred(input) => {
+                                        let mut witness_stream = Vec::new();
+                                        Witnessable::<InnerConfig>::write(
+                                            &input,
+                                            &mut witness_stream,
+                                        );
+                                        (self.deferred_program(&input), witness_stream)
+                                    }
+                                    ZKMCircuitWitness::Compress(input) => {
+                                        let mut witness_stream = Vec::new();
 
-                                    let input_with_merkle = self.make_merkle_proofs(input);
+                                        let input_with_merkle = self.make_merkle_proofs(input)?;
 
-                                    Witnessable::<InnerConfig>::write(
-                                        &input_with_merkle,
-                                        &mut witness_stream,
-                                    );
+                                        Witnessable::<InnerConfig>::write(
+                                            &input_with_merkle,
+                                            &mut witness_stream,
+                                        );
 
-                                    (self.compress_program(&input_with_merkle), witness_stream)
-                                }
-                            });
+                                        (self.compress_program(&input_with_merkle), witness_stream)
+                                    }
+                                })
+                            })?;
 
                             // Execute the runtime.
                             let record = tracing::debug_span!("execute runtime").in_scope(|| {
@@ -798,12 +1071,20 @@ impl<C: ZKMProverComponents> ZKMProver<C> {
                 let prover_sync = Arc::clone(&proofs_sync);
                 let record_and_trace_rx = Arc::clone(&record_and_trace_rx);
                 let proofs_tx = Arc::clone(&proofs_tx);
+                let layer_sizes = &layer_sizes;
+                let layers_proven = &layers_proven;
                 let span = tracing::debug_span!("prove");
                 let handle = s.spawn(move || {
                     let _span = span.enter();
                     loop {
                         let received = { record_and_trace_rx.lock().unwrap().recv() };
                         if let Ok((index, height, program, record, traces)) = received {
+                            if is_cancelled() {
+                                prover_sync.wait_for_turn(index);
+                                prover_sync.advance_turn();
+                                continue;
+                            }
+
                             tracing::debug_span!("batch").in_scope(|| {
                                 // Get the keys.
                                 let (pk, vk) = tracing::debug_span!("Setup compress program")
@@ -850,6 +1131,18 @@ impl<C: ZKMProverComponents> ZKMProver<C> {
                                 // Send the proof.
                                 proofs_tx.lock().unwrap().send((index, height, vk, proof)).unwrap();
 
+                                // Report the layer once all of its proofs have been generated.
+                                if let Some(progress) = progress {
+                                    let mut layers_proven = layers_proven.lock().unwrap();
+                                    layers_proven[height] += 1;
+                                    if layers_proven[height] == layer_sizes[height] {
+                                        progress(ProveProgress::CompressLayerDone {
+                                            layer: height,
+                                            layers: layer_sizes.len(),
+                                        });
+                                    }
+                                }
+
                                 // Advance the turn.
                                 prover_sync.advance_turn();
                             });
@@ -865,50 +1158,50 @@ impl<C: ZKMProverComponents> ZKMProver<C> {
             let handle = {
                 let input_tx = Arc::clone(&input_tx);
                 let proofs_rx = Arc::clone(&proofs_rx);
+                let layer_sizes = &layer_sizes;
                 let span = tracing::debug_span!("generate next layer inputs");
                 s.spawn(move || {
                     let _span = span.enter();
                     let mut count = num_first_layer_inputs;
-                    let mut batch: Vec<(
-                        usize,
-                        usize,
-                        StarkVerifyingKey<InnerSC>,
-                        ShardProof<InnerSC>,
-                    )> = Vec::new();
+                    // The proofs are received in order, so that the batch only holds proofs of
+                    // the same layer.
+                    let mut batch: Vec<(StarkVerifyingKey<InnerSC>, ShardProof<InnerSC>)> =
+                        Vec::new();
+                    let mut layers_received = vec![0; layer_sizes.len()];
                     loop {
                         if expected_height == 0 {
                             break;
                         }
-                        let received = { proofs_rx.lock().unwrap().recv() };
-                        if let Ok((index, height, vk, proof)) = received {
-                            batch.push((index, height, vk, proof));
-
-                            // If we haven't reached the batch size, continue.
-                            if batch.len() < batch_size {
-                                continue;
+                        // The skipped proofs are never received, so wait for the proofs with a
+                        // timeout to notice the cancellation.
+                        let received = loop {
+                            match proofs_rx.lock().unwrap().recv_timeout(CANCELLATION_POLL_INTERVAL)
+                            {
+                                Err(RecvTimeoutError::Timeout) if !is_cancelled() => continue,
+                                received => break received,
                             }
-
-                            // Compute whether we're at the last input of a layer.
-                            let mut is_last = false;
-                            if let Some(first) = batch.first() {
-                                is_last = first.1 != height;
+                        };
+                        if is_cancelled() {
+                            break;
+                        }
+                        if let Ok((_, height, vk, proof)) = received {
+                            batch.push((vk, proof));
+                            layers_received[height] += 1;
+
+                            // If we haven't reached the batch size or the end of the layer,
+                            // continue.
+                            if batch.len() < batch_size
+                                && layers_received[height] < layer_sizes[height]
+                            {
+                                continue;
                             }
 
-                            // If we're at the last input of a layer, we need to only include the
-                            // first input, otherwise we include all inputs.
-                            let inputs =
-                                if is_last { vec![batch[0].clone()] } else { batch.clone() };
-
-                            let next_input_height = inputs[0].1 + 1;
+                            let next_input_height = height + 1;
 
                             let is_complete = next_input_height == expected_height;
 
-                            let vks_and_proofs = inputs
-                                .into_iter()
-                                .map(|(_, _, vk, proof)| (vk, proof))
-                                .collect::<Vec<_>>();
                             let input = ZKMCircuitWitness::Compress(ZKMCompressWitnessValues {
-                                vks_and_proofs,
+                                vks_and_proofs: std::mem::take(&mut batch),
                                 is_complete,
                             });
 
@@ -925,14 +1218,6 @@ impl<C: ZKMProverComponents> ZKMProver<C> {
                             if is_complete {
                                 break;
                             }
-
-                            // If we were at the last input of a layer, we keep everything but the
-                            // first input. Otherwise, we empty the batch.
-                            if is_last {
-                                batch = vec![batch[1].clone()];
-                            } else {
-                                batch = Vec::new();
-                            }
                         } else {
                             break;
                         }
@@ -949,9 +1234,12 @@ impl<C: ZKMProverComponents> ZKMProver<C> {
             }
             handle.join().unwrap();
 
+            if is_cancelled() {
+                return Err(ZKMRecursionProverError::Cancelled);
+            }
             let (_, _, vk, proof) = proofs_rx.lock().unwrap().recv().unwrap();
-            (vk, proof)
-        });
+            Ok((vk, proof))
+        })?;
 
         Ok(ZKMReduceProof { vk, proof })
     }
@@ -970,7 +1258,7 @@ impl<C: ZKMProverComponents> ZKMProver<C> {
             is_complete: true,
         };
 
-        let input_with_merkle = self.make_merkle_proofs(input);
+        let input_with_merkle = self.make_merkle_proofs(input)?;
 
         let program =
             self.shrink_program(ShrinkAir::<KoalaBear>::shrink_shape(), &input_with_merkle);
@@ -1016,7 +1304,7 @@ impl<C: ZKMProverComponents> ZKMProver<C> {
             vks_and_proofs: vec![(compressed_vk, compressed_proof)],
             is_complete: true,
         };
-        let input_with_vk = self.make_merkle_proofs(input);
+        let input_with_vk = self.make_merkle_proofs(input)?;
 
         let program = self.wrap_program();
 
@@ -1061,6 +1349,7 @@ impl<C: ZKMProverComponents> ZKMProver<C> {
     }
 
     /// Wrap the STARK proven over a SNARK-friendly field into a PLONK proof.
+    #[cfg(feature = "native-gnark")]
     #[instrument(name = "wrap_plonk_bn254", level = "info", skip_all)]
     pub fn wrap_plonk_bn254(
         &self,
@@ -1136,6 +1425,7 @@ impl<C: ZKMProverComponents> ZKMProver<C> {
     }
 
     /// Wrap the STARK proven over a SNARK-friendly field into a DV-SNARK proof.
+    #[cfg(feature = "native-gnark")]
     #[instrument(name = "wrap_dvsnark_bn254", level = "info", skip_all)]
     pub fn wrap_dvsnark_bn254(
         &self,
@@ -1178,10 +1468,14 @@ impl<C: ZKMProverComponents> ZKMProver<C> {
         digest
     }
 
+    /// Opens the Merkle proofs of the verification keys of the proofs in the allowed vk map.
+    ///
+    /// Returns [`ZKMRecursionProverError::VkNotAllowed`] if a verification key isn't in the map
+    /// while the verification keys are verified.
     pub fn make_merkle_proofs(
         &self,
         input: ZKMCompressWitnessValues<CoreSC>,
-    ) -> ZKMCompressWithVKeyWitnessValues<CoreSC> {
+    ) -> Result<ZKMCompressWithVKeyWitnessValues<CoreSC>, ZKMRecursionProverError> {
         let num_vks = self.recursion_vk_map.len();
         let (vk_indices, vk_digest_values): (Vec<_>, Vec<_>) = if self.vk_verification {
             input
@@ -1189,9 +1483,15 @@ impl<C: ZKMProverComponents> ZKMProver<C> {
                 .iter()
                 .map(|(vk, _)| {
                     let vk_digest = vk.hash_koalabear();
-                    let index = self.recursion_vk_map.get(&vk_digest).expect("vk not allowed");
-                    (index, vk_digest)
+                    let index = *self.recursion_vk_map.get(&vk_digest).ok_or_else(|| {
+                        ZKMRecursionProverError::VkNotAllowed(
+                            vk_digest.map(|x| x.as_canonical_u32()),
+                        )
+                    })?;
+                    Ok((index, vk_digest))
                 })
+                .collect::<Result<Vec<_>, _>>()?
+                .into_iter()
                 .unzip()
         } else {
             input
@@ -1219,7 +1519,7 @@ impl<C: ZKMProverComponents> ZKMProver<C> {
             vk_merkle_proofs: proofs,
         };
 
-        ZKMCompressWithVKeyWitnessValues { compress_val: input, merkle_val }
+        Ok(ZKMCompressWithVKeyWitnessValues { compress_val: input, merkle_val })
     }
 
     fn check_for_high_cycles(cycles: u64) {
@@ -1274,6 +1574,7 @@ pub mod tests {
 
     use super::*;
 
+    #[cfg(feature = "native-gnark")]
     use crate::build::try_build_plonk_bn254_artifacts_dev;
     use anyhow::Result;
     use build::{build_constraints_and_witness, try_build_groth16_bn254_artifacts_dev};
@@ -1419,8 +1720,11 @@ pub mod tests {
         let (constraints, witness) =
             build_constraints_and_witness(&wrapped_bn254_proof.vk, &wrapped_bn254_proof.proof);
         // test
-        PlonkBn254Prover::test(constraints.clone(), witness.clone());
-        tracing::info!("Circuit PLONK test succeeded");
+        #[cfg(feature = "native-gnark")]
+        {
+            PlonkBn254Prover::test(constraints.clone(), witness.clone());
+            tracing::info!("Circuit PLONK test succeeded");
+        }
         Groth16Bn254Prover::test(constraints, witness);
         tracing::info!("Circuit GROTH16 test succeeded");
 
@@ -1428,16 +1732,19 @@ pub mod tests {
             return Ok(());
         }
 
-        tracing::info!("generate plonk bn254 proof");
-        let artifacts_dir = try_build_plonk_bn254_artifacts_dev(
-            &wrapped_bn254_proof.vk,
-            &wrapped_bn254_proof.proof,
-        );
-        let plonk_bn254_proof =
-            prover.wrap_plonk_bn254(wrapped_bn254_proof.clone(), &artifacts_dir);
-        println!("{plonk_bn254_proof:?}");
+        #[cfg(feature = "native-gnark")]
+        {
+            tracing::info!("generate plonk bn254 proof");
+            let artifacts_dir = try_build_plonk_bn254_artifacts_dev(
+                &wrapped_bn254_proof.vk,
+                &wrapped_bn254_proof.proof,
+            );
+            let plonk_bn254_proof =
+                prover.wrap_plonk_bn254(wrapped_bn254_proof.clone(), &artifacts_dir);
+            println!("{plonk_bn254_proof:?}");
 
-        prover.verify_plonk_bn254(&plonk_bn254_proof, &vk, &public_values, &artifacts_dir)?;
+            prover.verify_plonk_bn254(&plonk_bn254_proof, &vk, &public_values, &artifacts_dir)?;
+        }
 
         tracing::info!("generate groth16 bn254 proof");
         let artifacts_dir = try_build_groth16_bn254_artifacts_dev(
@@ -1563,9 +1870,8 @@ pub mod tests {
     /// Tests an end-to-end workflow of proving a program across the entire proof generation
     /// pipeline.
     ///
-    /// Add `FRI_QUERIES`=1 to your environment for faster execution. Should only take a few minutes
-    /// on a Mac M2. Note: This test always re-builds the plonk bn254 artifacts, so setting ZKM_DEV
-    /// is not needed.
+    /// Note: This test always re-builds the plonk bn254 artifacts, so setting ZKM_DEV is not
+    /// needed.
     #[test]
     #[serial]
     #[ignore]
@@ -1589,9 +1895,8 @@ pub mod tests {
     /// Tests an end-to-end workflow of proving a program across the entire proof generation
     /// pipeline.
     ///
-    /// Add `FRI_QUERIES`=1 to your environment for faster execution. Should only take a few minutes
-    /// on a Mac M2. Note: This test always re-builds the plonk bn254 artifacts, so setting ZKM_DEV
-    /// is not needed.
+    /// Note: This test always re-builds the plonk bn254 artifacts, so setting ZKM_DEV is not
+    /// needed.
     #[test]
     #[serial]
     #[ignore]
@@ -1622,4 +1927,93 @@ pub mod tests {
         setup_logger();
         test_e2e_with_deferred_proofs_prover::<DefaultProverComponents>(ZKMProverOpts::default())
     }
+
+    /// Tests accumulating compressed proofs as they are generated with a compress arity of 3, so
+    /// that the first three proofs are aggregated before the last one is added.
+    #[test]
+    #[serial]
+    #[ignore]
+    fn test_accumulator_with_compress_arity() -> Result<()> {
+        setup_logger();
+        let opts = ZKMProverOpts::default();
+        // The allowed verification keys only cover the default arity.
+        let prover = ZKMProver::<DefaultProverComponents>::with_vk_verification(
+            FriParameters::CORE,
+            3,
+            false,
+        );
+
+        let (_, keccak_pk_d, keccak_program, keccak_vk) =
+            prover.setup(test_artifacts::KECCAK_SPONGE_ELF);
+
+        let mut accumulator = prover.accumulator();
+        let mut pairs = Vec::new();
+        for input in [vec![0u8, 0, 0], vec![0, 1, 2], vec![2, 3, 4], vec![4, 5, 6]] {
+            let mut stdin = ZKMStdin::new();
+            stdin.write(&1usize);
+            stdin.write(&input);
+            let proof = prover.prove_core(
+                &keccak_pk_d,
+                keccak_program.clone(),
+                &stdin,
+                opts,
+                Default::default(),
+            )?;
+            pairs.push((keccak_vk.vk.hash_u32(), proof.public_values.hash().try_into().unwrap()));
+            let proof = prover.compress(&keccak_vk, proof, vec![], opts)?;
+            accumulator.add(proof, &keccak_vk, opts)?;
+        }
+        assert_eq!(accumulator.len(), 4);
+        assert_eq!(accumulator.proofs().count(), 2);
+
+        let (proof, vk, public_values) = accumulator.aggregate(opts)?;
+        prover.verify_compressed(&proof, &vk)?;
+        assert_eq!(aggregated_pairs(&public_values, &vk), Some(pairs));
+
+        Ok(())
+    }
+
+    /// Tests that verifying the verification keys with a compress arity they don't cover fails up
+    /// front.
+    #[test]
+    #[should_panic(expected = "the allowed verification keys only cover a compress arity of 2")]
+    fn test_vk_verification_with_compress_arity() {
+        ZKMProver::<DefaultProverComponents>::with_vk_verification(FriParameters::CORE, 3, true);
+    }
+
+    /// Tests that the join programs of every compress arity fit into the shapes of that arity,
+    /// even when all the joined proofs are of the largest shape.
+    #[test]
+    fn test_compress_arity_shapes() {
+        let compress_prover = <DefaultProverComponents as ZKMProverComponents>::CompressProver::new(
+            CompressAir::compress_machine(InnerSC::default()),
+        );
+        let allowed_vk_map: BTreeMap<[KoalaBear; DIGEST_SIZE], usize> =
+            bincode::deserialize(include_bytes!("../vk_map.bin")).unwrap();
+        let (_, merkle_tree) =
+            MerkleTree::<KoalaBear, InnerSC>::commit(allowed_vk_map.into_keys().collect());
+
+        for compress_arity in REDUCE_BATCH_SIZE + 1..=MAX_COMPRESS_ARITY {
+            let config =
+                RecursionShapeConfig::<KoalaBear, CompressAir<KoalaBear>>::for_compress_arity(
+                    compress_arity,
+                );
+            let largest_shape = config.get_all_shape_combinations(1).last().unwrap().pop().unwrap();
+            let shape = ZKMCompressWithVkeyShape {
+                compress_shape: vec![largest_shape; compress_arity].into(),
+                merkle_tree_height: merkle_tree.height,
+            };
+            let input = ZKMCompressWithVKeyWitnessValues::dummy(compress_prover.machine(), &shape);
+            let program = compress_program_from_input::<DefaultProverComponents>(
+                Some(&config),
+                &compress_prover,
+                true,
+                &input,
+            );
+            assert!(
+                program.shape.is_some(),
+                "no shape for the join program of arity {compress_arity}"
+            );
+        }
+    }
 }
@@ -1,13 +1,14 @@
 use std::{fs::File, path::Path};
 
 use anyhow::Result;
+use bincode::Options;
 use clap::ValueEnum;
 use p3_bn254_fr::Bn254Fr;
 use p3_commit::{Pcs, TwoAdicMultiplicativeCoset};
 use p3_field::{FieldAlgebra, PrimeField, PrimeField32, TwoAdicField};
 use p3_koala_bear::KoalaBear;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
-use zkm_core_machine::{io::ZKMStdin, reduce::ZKMReduceProof};
+use zkm_core_machine::{io::ZKMStdin, reduce::ZKMReduceProof, utils::ZKMCoreProverError};
 use zkm_primitives::{io::ZKMPublicValues, poseidon2_hash};
 
 use zkm_recursion_circuit::machine::{
@@ -17,7 +18,10 @@ use zkm_recursion_circuit::machine::{
 use zkm_recursion_gnark_ffi::proof::{Groth16Bn254Proof, PlonkBn254Proof};
 
 use thiserror::Error;
-use zkm_stark::{ShardProof, StarkGenericConfig, StarkProvingKey, StarkVerifyingKey, DIGEST_SIZE};
+use zkm_stark::{
+    FriParameters, MachineVerificationError, SecurityLevel, ShardProof, StarkGenericConfig,
+    StarkProvingKey, StarkVerifyingKey, DIGEST_SIZE,
+};
 
 use crate::{
     utils::{koalabears_to_bn254, words_to_bytes_be},
@@ -33,10 +37,66 @@ pub struct ZKMProvingKey {
     pub vk: ZKMVerifyingKey,
 }
 
+/// The version of the [`ZKMVerifyingKey`] encoding, bumped whenever its fields change.
+pub const ZKM_VERIFYING_KEY_VERSION: u32 = 1;
+
 /// The information necessary to verify a proof for a given MIPS program.
+///
+/// The key is encoded as the STARK verifying key, followed by [`ZKM_VERIFYING_KEY_VERSION`] and
+/// the fields of that version. Keys encoded before the version was added are read with
+/// [`Self::from_bytes`].
 #[derive(Clone, Serialize, Deserialize)]
+#[serde(try_from = "EncodedVerifyingKey", into = "EncodedVerifyingKey")]
 pub struct ZKMVerifyingKey {
     pub vk: StarkVerifyingKey<CoreSC>,
+    /// The FRI parameters the core proofs of the program are generated with.
+    ///
+    /// They are not part of the vkey digest: verifiers check them against their own
+    /// configuration.
+    pub fri_parameters: FriParameters,
+}
+
+impl ZKMVerifyingKey {
+    /// Decodes a bincode-encoded key, either versioned or encoded before the version was added,
+    /// in which case the proofs were generated with [`FriParameters::CORE`].
+    pub fn from_bytes(bytes: &[u8]) -> bincode::Result<Self> {
+        bincode::deserialize(bytes).or_else(|err| {
+            // The legacy encoding is the bare STARK verifying key.
+            let vk = bincode::DefaultOptions::new()
+                .with_fixint_encoding()
+                .reject_trailing_bytes()
+                .deserialize(bytes)
+                .map_err(|_| err)?;
+            Ok(Self { vk, fri_parameters: FriParameters::CORE })
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct EncodedVerifyingKey {
+    vk: StarkVerifyingKey<CoreSC>,
+    version: u32,
+    fri_parameters: FriParameters,
+}
+
+impl TryFrom<EncodedVerifyingKey> for ZKMVerifyingKey {
+    type Error = String;
+
+    fn try_from(encoded: EncodedVerifyingKey) -> Result<Self, Self::Error> {
+        if encoded.version != ZKM_VERIFYING_KEY_VERSION {
+            return Err(format!(
+                "unsupported verifying key version {}, expected {ZKM_VERIFYING_KEY_VERSION}",
+                encoded.version
+            ));
+        }
+        Ok(Self { vk: encoded.vk, fri_parameters: encoded.fri_parameters })
+    }
+}
+
+impl From<ZKMVerifyingKey> for EncodedVerifyingKey {
+    fn from(key: ZKMVerifyingKey) -> Self {
+        Self { vk: key.vk, version: ZKM_VERIFYING_KEY_VERSION, fri_parameters: key.fri_parameters }
+    }
 }
 
 /// A trait for keys that can be hashed into a digest.
@@ -226,6 +286,24 @@ pub enum ZKMRecursionProverError {
     TracesGenerationError,
     #[error("Generate dependencies error")]
     DependenciesGenerationError,
+    #[error("The FRI parameters {0:?} do not reach the security level {1:?}")]
+    InsufficientSecurity(FriParameters, SecurityLevel),
+    #[error("The deferred proofs digest {0:?} does not match the accumulated digest {1:?}")]
+    DeferredProofsDigestMismatch([u32; DIGEST_SIZE], [u32; DIGEST_SIZE]),
+    #[error("The proof was cancelled")]
+    Cancelled,
+    #[error("The verification key {0:?} is not in the allowed vk map")]
+    VkNotAllowed([u32; DIGEST_SIZE]),
+}
+
+#[derive(Error, Debug)]
+pub enum ZKMAggregationError {
+    #[error("Verification error: {0}")]
+    Verification(#[from] MachineVerificationError<CoreSC>),
+    #[error("Core prover error: {0}")]
+    Core(#[from] ZKMCoreProverError),
+    #[error("Recursion prover error: {0}")]
+    Recursion(#[from] ZKMRecursionProverError),
 }
 
 #[allow(clippy::large_enum_variant)]
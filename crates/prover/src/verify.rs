@@ -13,9 +13,9 @@ use zkm_recursion_circuit::machine::RootPublicValues;
 use zkm_recursion_core::{
     air::RecursionPublicValues, hash_vkey_with_part_vk, stark::KoalaBearPoseidon2Outer,
 };
-use zkm_recursion_gnark_ffi::{
-    Groth16Bn254Proof, Groth16Bn254Prover, PlonkBn254Proof, PlonkBn254Prover,
-};
+#[cfg(feature = "native-gnark")]
+use zkm_recursion_gnark_ffi::PlonkBn254Prover;
+use zkm_recursion_gnark_ffi::{Groth16Bn254Proof, Groth16Bn254Prover, PlonkBn254Proof};
 use zkm_stark::{
     air::{PublicValues, POSEIDON_NUM_WORDS, PV_DIGEST_NUM_WORDS},
     koala_bear_poseidon2::KoalaBearPoseidon2,
@@ -287,6 +287,11 @@ impl<C: ZKMProverComponents> ZKMProver<C> {
             return Err(MachineVerificationError::TooManyShards);
         }
 
+        // The proof must have been generated with the FRI parameters of the core machine.
+        if vk.fri_parameters != self.core_fri_parameters() {
+            return Err(MachineVerificationError::InvalidVerificationKey);
+        }
+
         // Verify the shard proof.
         let mut challenger = self.core_prover.config().challenger();
         let machine_proof = MachineProof { shard_proofs: proof.0.to_vec() };
@@ -406,6 +411,7 @@ impl<C: ZKMProverComponents> ZKMProver<C> {
     }
 
     /// Verifies a PLONK proof using the circuit artifacts in the build directory.
+    #[cfg(feature = "native-gnark")]
     pub fn verify_plonk_bn254(
         &self,
         proof: &PlonkBn254Proof,
@@ -526,7 +532,7 @@ impl<C: ZKMProverComponents> SubproofVerifier for ZKMProver<C> {
         // Check that proof is valid.
         self.verify_compressed(
             &ZKMReduceProof { vk: proof.vk.clone(), proof: proof.proof.clone() },
-            &ZKMVerifyingKey { vk: vk.clone() },
+            &ZKMVerifyingKey { vk: vk.clone(), fri_parameters: self.core_fri_parameters() },
         )?;
         // Check that the committed value digest matches the one from syscall
         let public_values: &RecursionPublicValues<_> =
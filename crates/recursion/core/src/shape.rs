@@ -91,6 +91,25 @@ impl<F: PrimeField32 + BinomiallyExtendable<D>, const DEGREE: usize>
             .multi_cartesian_product()
     }
 
+    /// The allowed shapes when each compress program joins `compress_arity` proofs.
+    ///
+    /// The default shapes are sized for joining two proofs. Every extra proof adds a shape one
+    /// log-height above the previous largest one, leaving room for the joined proofs being of the
+    /// larger shapes themselves. The programs pick the smallest shape they fit in, so that the
+    /// extra shapes are only used by the larger join programs.
+    pub fn for_compress_arity(compress_arity: usize) -> Self {
+        let mut config = Self::default();
+        for _ in 2..compress_arity {
+            let mut shape = config.allowed_shapes.last().unwrap().clone();
+            let public_values = RecursionAir::<F, DEGREE>::PublicValues(PublicValuesChip).name();
+            shape.iter_mut().filter(|(name, _)| **name != public_values).for_each(|(_, height)| {
+                *height += 1;
+            });
+            config.allowed_shapes.push(shape);
+        }
+        config
+    }
+
     pub fn union_config_with_extra_room(&self) -> Self {
         let mut map = HashMap::new();
         for shape in self.allowed_shapes.clone() {
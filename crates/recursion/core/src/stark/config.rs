@@ -9,7 +9,7 @@ use p3_merkle_tree::MerkleTreeMmcs;
 use p3_poseidon2::ExternalLayerConstants;
 use p3_symmetric::{Hash, MultiField32PaddingFreeSponge, TruncatedPermutation};
 use serde::{Deserialize, Serialize};
-use zkm_stark::{Com, StarkGenericConfig, ZeroCommitment};
+use zkm_stark::{Com, FriParameters, SecurityLevel, StarkGenericConfig, ZeroCommitment};
 
 use super::{poseidon2::bn254_poseidon2_rc3, zkm_dev_mode};
 
@@ -65,40 +65,43 @@ pub fn outer_perm() -> OuterPerm {
     OuterPerm::new(external_round_constants, internal_round_constants)
 }
 
+/// The FRI parameters of outer recursion.
+pub const OUTER_FRI_PARAMETERS: FriParameters =
+    FriParameters { log_blowup: 4, num_queries: 21, proof_of_work_bits: 16 };
+
 /// The FRI config for outer recursion.
-/// This targets by default 100 bits of security.
+/// This targets 100 bits of conjectured security, or a single query in dev mode.
 pub fn outer_fri_config() -> FriConfig<OuterChallengeMmcs> {
-    let perm = outer_perm();
-    let hash = OuterHash::new(perm.clone()).unwrap();
-    let compress = OuterCompress::new(perm.clone());
-    let challenge_mmcs = OuterChallengeMmcs::new(OuterValMmcs::new(hash, compress));
-    let num_queries = if zkm_dev_mode() {
-        1
-    } else {
-        match std::env::var("FRI_QUERIES") {
-            Ok(value) => value.parse().unwrap(),
-            Err(_) => 21,
-        }
-    };
-    FriConfig { log_blowup: 4, num_queries, proof_of_work_bits: 16, mmcs: challenge_mmcs }
+    outer_fri_config_with_parameters(outer_fri_parameters(OUTER_FRI_PARAMETERS))
 }
 
 /// The FRI config for outer recursion.
-/// This targets by default 100 bits of security.
+/// This targets 100 bits of conjectured security, or a single query in dev mode.
 pub fn outer_fri_config_with_blowup(log_blowup: usize) -> FriConfig<OuterChallengeMmcs> {
+    outer_fri_config_with_parameters(outer_fri_parameters_with_blowup(log_blowup))
+}
+
+fn outer_fri_parameters_with_blowup(log_blowup: usize) -> FriParameters {
+    outer_fri_parameters(FriParameters::for_security_level_with_blowup(
+        SecurityLevel::default(),
+        log_blowup,
+    ))
+}
+
+/// The parameters actually used for outer recursion, with a single query in dev mode.
+fn outer_fri_parameters(mut parameters: FriParameters) -> FriParameters {
+    if zkm_dev_mode() {
+        parameters.num_queries = 1;
+    }
+    parameters
+}
+
+fn outer_fri_config_with_parameters(parameters: FriParameters) -> FriConfig<OuterChallengeMmcs> {
     let perm = outer_perm();
     let hash = OuterHash::new(perm.clone()).unwrap();
     let compress = OuterCompress::new(perm.clone());
     let challenge_mmcs = OuterChallengeMmcs::new(OuterValMmcs::new(hash, compress));
-    let num_queries = if zkm_dev_mode() {
-        1
-    } else {
-        match std::env::var("FRI_QUERIES") {
-            Ok(value) => value.parse().unwrap(),
-            Err(_) => 84 / log_blowup,
-        }
-    };
-    FriConfig { log_blowup, num_queries, proof_of_work_bits: 16, mmcs: challenge_mmcs }
+    parameters.fri_config(challenge_mmcs)
 }
 
 #[derive(Deserialize)]
@@ -106,11 +109,12 @@ pub fn outer_fri_config_with_blowup(log_blowup: usize) -> FriConfig<OuterChallen
 pub struct KoalaBearPoseidon2Outer {
     pub perm: OuterPerm,
     pub pcs: OuterPcs,
+    fri_parameters: FriParameters,
 }
 
 impl Clone for KoalaBearPoseidon2Outer {
     fn clone(&self) -> Self {
-        Self::new()
+        Self::with_fri_parameters(self.fri_parameters)
     }
 }
 
@@ -131,24 +135,27 @@ impl From<std::marker::PhantomData<KoalaBearPoseidon2Outer>> for KoalaBearPoseid
 
 impl KoalaBearPoseidon2Outer {
     pub fn new() -> Self {
-        let perm = outer_perm();
-        let hash = OuterHash::new(perm.clone()).unwrap();
-        let compress = OuterCompress::new(perm.clone());
-        let val_mmcs = OuterValMmcs::new(hash, compress);
-        let dft = OuterDft::default();
-        let fri_config = outer_fri_config();
-        let pcs = OuterPcs::new(dft, val_mmcs, fri_config);
-        Self { pcs, perm }
+        Self::with_fri_parameters(outer_fri_parameters(OUTER_FRI_PARAMETERS))
     }
+
     pub fn new_with_log_blowup(log_blowup: usize) -> Self {
+        Self::with_fri_parameters(outer_fri_parameters_with_blowup(log_blowup))
+    }
+
+    fn with_fri_parameters(fri_parameters: FriParameters) -> Self {
         let perm = outer_perm();
         let hash = OuterHash::new(perm.clone()).unwrap();
         let compress = OuterCompress::new(perm.clone());
         let val_mmcs = OuterValMmcs::new(hash, compress);
         let dft = OuterDft::default();
-        let fri_config = outer_fri_config_with_blowup(log_blowup);
+        let fri_config = outer_fri_config_with_parameters(fri_parameters);
         let pcs = OuterPcs::new(dft, val_mmcs, fri_config);
-        Self { pcs, perm }
+        Self { pcs, perm, fri_parameters }
+    }
+
+    /// The FRI parameters of the configuration.
+    pub fn fri_parameters(&self) -> FriParameters {
+        self.fri_parameters
     }
 }
 
@@ -0,0 +1,254 @@
+//! The outer circuit as an arkworks constraint synthesizer, mirroring the gnark `Circuit`.
+
+use std::collections::HashMap;
+
+use ark_bn254::Fr;
+use ark_r1cs_std::{fields::fp::FpVar, prelude::*};
+use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystemRef, SynthesisError};
+use zkm_recursion_compiler::constraints::{opcodes::ConstraintOpcode, Constraint};
+
+use super::{
+    koalabear::{select, Ext, Felt, KoalaBearChip, NUM_BITS},
+    parse_fr,
+    poseidon2::{Poseidon2Chip, Poseidon2KoalaBearChip},
+    to_binary,
+};
+use crate::witness::GnarkWitness;
+
+/// The outer circuit defined by the constraints of the wrap program, and its witness.
+///
+/// The public inputs are the vkey hash and the committed values digest, in this order. During the
+/// setup, only the lengths of the witness matter.
+#[derive(Debug, Clone)]
+pub struct Groth16Circuit {
+    pub constraints: Vec<Constraint>,
+    pub witness: GnarkWitness,
+}
+
+impl ConstraintSynthesizer<Fr> for Groth16Circuit {
+    fn generate_constraints(self, cs: ConstraintSystemRef<Fr>) -> Result<(), SynthesisError> {
+        let Groth16Circuit { constraints, witness } = self;
+
+        let vkey_hash = FpVar::new_input(cs.clone(), || Ok(parse_fr(&witness.vkey_hash)))?;
+        let committed_values_digest =
+            FpVar::new_input(cs.clone(), || Ok(parse_fr(&witness.committed_values_digest)))?;
+
+        // Allocate the witnesses and range check them, if necessary.
+        let witness_vars = witness
+            .vars
+            .iter()
+            .map(|x| FpVar::new_witness(cs.clone(), || Ok(parse_fr(x))))
+            .collect::<Result<Vec<_>, _>>()?;
+        let witness_felt = |x: &String| -> Result<Felt, SynthesisError> {
+            let value = FpVar::new_witness(cs.clone(), || Ok(parse_fr(x)))?;
+            to_binary(&cs, &value, NUM_BITS)?;
+            Ok(Felt::witness(value))
+        };
+        let witness_felts =
+            witness.felts.iter().map(witness_felt).collect::<Result<Vec<_>, _>>()?;
+        let witness_exts = witness
+            .exts
+            .iter()
+            .map(|x| {
+                Ok(Ext([
+                    witness_felt(&x[0])?,
+                    witness_felt(&x[1])?,
+                    witness_felt(&x[2])?,
+                    witness_felt(&x[3])?,
+                ]))
+            })
+            .collect::<Result<Vec<_>, SynthesisError>>()?;
+
+        let hash_chip = Poseidon2Chip::new();
+        let field_chip = KoalaBearChip::new(cs.clone());
+        let hash_koalabear_chip = Poseidon2KoalaBearChip::new(&field_chip);
+        let mut vars: HashMap<String, FpVar<Fr>> = HashMap::new();
+        let mut felts: HashMap<String, Felt> = HashMap::new();
+        let mut exts: HashMap<String, Ext> = HashMap::new();
+
+        // Iterate through the instructions and handle each opcode.
+        for constraint in constraints {
+            let args = &constraint.args;
+            let out = args[0][0].clone();
+            let var = |i: usize| vars[&args[i][0]].clone();
+            let felt = |i: usize| &felts[&args[i][0]];
+            let ext = |i: usize| &exts[&args[i][0]];
+            match constraint.opcode {
+                ConstraintOpcode::ImmV => {
+                    vars.insert(out, FpVar::Constant(parse_fr(&args[1][0])));
+                }
+                ConstraintOpcode::ImmF => {
+                    felts.insert(out, Felt::imm(&args[1][0]));
+                }
+                ConstraintOpcode::ImmE => {
+                    exts.insert(out, Ext::imm(&args[1]));
+                }
+                ConstraintOpcode::AddV => {
+                    let value = var(1) + var(2);
+                    vars.insert(out, value);
+                }
+                ConstraintOpcode::AddF => {
+                    let value = field_chip.add_f(felt(1), felt(2))?;
+                    felts.insert(out, value);
+                }
+                ConstraintOpcode::AddE => {
+                    let value = field_chip.add_e(ext(1), ext(2))?;
+                    exts.insert(out, value);
+                }
+                ConstraintOpcode::AddEF => {
+                    let value = field_chip.add_ef(ext(1), felt(2))?;
+                    exts.insert(out, value);
+                }
+                ConstraintOpcode::SubV => {
+                    let value = var(1) - var(2);
+                    vars.insert(out, value);
+                }
+                ConstraintOpcode::SubF => {
+                    let value = field_chip.sub_f(felt(1), felt(2))?;
+                    felts.insert(out, value);
+                }
+                ConstraintOpcode::DivF => {
+                    let value = field_chip.div_f(felt(1), felt(2))?;
+                    felts.insert(out, value);
+                }
+                ConstraintOpcode::SubE => {
+                    let value = field_chip.sub_e(ext(1), ext(2))?;
+                    exts.insert(out, value);
+                }
+                ConstraintOpcode::SubEF => {
+                    let value = field_chip.sub_ef(ext(1), felt(2))?;
+                    exts.insert(out, value);
+                }
+                ConstraintOpcode::MulV => {
+                    let value = var(1) * var(2);
+                    vars.insert(out, value);
+                }
+                ConstraintOpcode::MulF => {
+                    let value = field_chip.mul_f(felt(1), felt(2))?;
+                    felts.insert(out, value);
+                }
+                ConstraintOpcode::MulE => {
+                    let value = field_chip.mul_e(ext(1), ext(2))?;
+                    exts.insert(out, value);
+                }
+                ConstraintOpcode::MulEF => {
+                    let value = field_chip.mul_ef(ext(1), felt(2))?;
+                    exts.insert(out, value);
+                }
+                ConstraintOpcode::DivE => {
+                    let value = field_chip.div_e(ext(1), ext(2))?;
+                    exts.insert(out, value);
+                }
+                ConstraintOpcode::DivEF => {
+                    let value = field_chip.div_ef(ext(1), felt(2))?;
+                    exts.insert(out, value);
+                }
+                ConstraintOpcode::NegE => {
+                    let value = field_chip.neg_e(ext(1))?;
+                    exts.insert(out, value);
+                }
+                ConstraintOpcode::InvE => {
+                    let value = field_chip.inv_e(ext(1))?;
+                    exts.insert(out, value);
+                }
+                ConstraintOpcode::Num2BitsV => {
+                    let num_bits = args[2][0].parse().expect("invalid number of bits");
+                    let bits = to_binary(&cs, &var(1), num_bits)?;
+                    for (id, bit) in args[0].iter().zip(bits) {
+                        vars.insert(id.clone(), bit.into());
+                    }
+                }
+                ConstraintOpcode::Num2BitsF => {
+                    let bits = field_chip.to_binary(felt(1))?;
+                    for (id, bit) in args[0].iter().zip(bits) {
+                        vars.insert(id.clone(), bit.into());
+                    }
+                }
+                ConstraintOpcode::Permute => {
+                    let mut state = [var(0), var(1), var(2)];
+                    hash_chip.permute_mut(&mut state);
+                    for (arg, value) in args.iter().zip(state) {
+                        vars.insert(arg[0].clone(), value);
+                    }
+                }
+                ConstraintOpcode::PermuteKoalaBear => {
+                    let mut state: [Felt; 16] = core::array::from_fn(|i| felt(i).clone());
+                    hash_koalabear_chip.permute_mut(&mut state)?;
+                    for (arg, value) in args.iter().zip(state) {
+                        felts.insert(arg[0].clone(), value);
+                    }
+                }
+                ConstraintOpcode::SelectV => {
+                    let value = select(&var(1), &var(2), &var(3))?;
+                    vars.insert(out, value);
+                }
+                ConstraintOpcode::SelectF => {
+                    let value = field_chip.select_f(&var(1), felt(2), felt(3))?;
+                    felts.insert(out, value);
+                }
+                ConstraintOpcode::SelectE => {
+                    let value = field_chip.select_e(&var(1), ext(2), ext(3))?;
+                    exts.insert(out, value);
+                }
+                ConstraintOpcode::Ext2Felt => {
+                    let value = ext(4).clone();
+                    for (arg, felt) in args.iter().zip(value.0) {
+                        felts.insert(arg[0].clone(), felt);
+                    }
+                }
+                ConstraintOpcode::AssertEqV => var(0).enforce_equal(&var(1))?,
+                ConstraintOpcode::AssertEqF => field_chip.assert_eq_f(felt(0), felt(1))?,
+                ConstraintOpcode::AssertNeF => field_chip.assert_ne_f(felt(0), felt(1))?,
+                ConstraintOpcode::AssertEqE => field_chip.assert_eq_e(ext(0), ext(1))?,
+                ConstraintOpcode::PrintV => print_values(&[var(0)]),
+                ConstraintOpcode::PrintF => {
+                    print_values(&[field_chip.reduce_slow(felt(0))?.value]);
+                }
+                ConstraintOpcode::PrintE => {
+                    print_values(&field_chip.reduce_e(ext(0))?.0.map(|x| x.value));
+                }
+                ConstraintOpcode::WitnessV => {
+                    let index: usize = args[1][0].parse().unwrap();
+                    vars.insert(out, witness_vars[index].clone());
+                }
+                ConstraintOpcode::WitnessF => {
+                    let index: usize = args[1][0].parse().unwrap();
+                    felts.insert(out, witness_felts[index].clone());
+                }
+                ConstraintOpcode::WitnessE => {
+                    let index: usize = args[1][0].parse().unwrap();
+                    exts.insert(out, witness_exts[index].clone());
+                }
+                ConstraintOpcode::CommitVkeyHash => vkey_hash.enforce_equal(&var(0))?,
+                ConstraintOpcode::CommitCommittedValuesDigest => {
+                    committed_values_digest.enforce_equal(&var(0))?
+                }
+                ConstraintOpcode::CircuitFelts2Ext => {
+                    let value =
+                        Ext([felt(1).clone(), felt(2).clone(), felt(3).clone(), felt(4).clone()]);
+                    exts.insert(out, value);
+                }
+                ConstraintOpcode::CircuitFelt2Var => {
+                    let value = field_chip.reduce_slow(felt(1))?.value;
+                    vars.insert(out, value);
+                }
+                ConstraintOpcode::ReduceE => {
+                    let value = field_chip.reduce_e(ext(0))?;
+                    exts.insert(out, value);
+                }
+                opcode => panic!("unhandled opcode: {opcode:?}"),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Prints the values of the variables, if they are assigned.
+fn print_values(values: &[FpVar<Fr>]) {
+    for value in values {
+        if let Ok(value) = value.value() {
+            println!("{value}");
+        }
+    }
+}
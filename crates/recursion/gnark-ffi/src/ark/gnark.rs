@@ -0,0 +1,204 @@
+//! The gnark encodings of BN254 points, Groth16 proofs and Groth16 verifying keys, so that the
+//! artifacts of the arkworks backend are read by the same verifiers as the gnark ones.
+
+use ark_bn254::{Bn254, Fq, Fq2, G1Affine, G2Affine};
+use ark_ec::AffineRepr;
+use ark_ff::{BigInteger, PrimeField};
+use ark_groth16::{Proof, VerifyingKey};
+use num_bigint::BigUint;
+
+const MASK: u8 = 0b11 << 6;
+const UNCOMPRESSED_INFINITY: u8 = 0b01 << 6;
+const COMPRESSED_SMALLEST: u8 = 0b10 << 6;
+const COMPRESSED_LARGEST: u8 = 0b11 << 6;
+const COMPRESSED_INFINITY: u8 = 0b01 << 6;
+
+fn fq_to_bytes(x: &Fq) -> [u8; 32] {
+    x.into_bigint().to_bytes_be().try_into().unwrap()
+}
+
+fn fq_from_bytes(bytes: &[u8]) -> Result<Fq, String> {
+    let value = BigUint::from_bytes_be(bytes);
+    if value >= Fq::MODULUS.into() {
+        return Err("invalid field element".to_string());
+    }
+    Ok(Fq::from(value))
+}
+
+fn fq2_to_bytes(x: &Fq2) -> [u8; 64] {
+    let mut bytes = [0u8; 64];
+    bytes[..32].copy_from_slice(&fq_to_bytes(&x.c1));
+    bytes[32..].copy_from_slice(&fq_to_bytes(&x.c0));
+    bytes
+}
+
+fn fq2_from_bytes(bytes: &[u8]) -> Result<Fq2, String> {
+    Ok(Fq2::new(fq_from_bytes(&bytes[32..64])?, fq_from_bytes(&bytes[..32])?))
+}
+
+/// Encodes a G1 point as its big endian coordinates.
+pub fn g1_to_uncompressed_bytes(point: &G1Affine) -> [u8; 64] {
+    let mut bytes = [0u8; 64];
+    match point.xy() {
+        Some((x, y)) => {
+            bytes[..32].copy_from_slice(&fq_to_bytes(&x));
+            bytes[32..].copy_from_slice(&fq_to_bytes(&y));
+        }
+        None => bytes[0] = UNCOMPRESSED_INFINITY,
+    }
+    bytes
+}
+
+/// Encodes a G2 point as its big endian coordinates, the imaginary part of each first.
+pub fn g2_to_uncompressed_bytes(point: &G2Affine) -> [u8; 128] {
+    let mut bytes = [0u8; 128];
+    match point.xy() {
+        Some((x, y)) => {
+            bytes[..64].copy_from_slice(&fq2_to_bytes(&x));
+            bytes[64..].copy_from_slice(&fq2_to_bytes(&y));
+        }
+        None => bytes[0] = UNCOMPRESSED_INFINITY,
+    }
+    bytes
+}
+
+/// Encodes a G1 point as its x coordinate, flagged with the sign of its y coordinate.
+pub fn g1_to_compressed_bytes(point: &G1Affine) -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    match point.xy() {
+        Some((x, y)) => {
+            bytes.copy_from_slice(&fq_to_bytes(&x));
+            bytes[0] |= if y > -y { COMPRESSED_LARGEST } else { COMPRESSED_SMALLEST };
+        }
+        None => bytes[0] = COMPRESSED_INFINITY,
+    }
+    bytes
+}
+
+/// Encodes a G2 point as its x coordinate, flagged with the sign of its y coordinate.
+pub fn g2_to_compressed_bytes(point: &G2Affine) -> [u8; 64] {
+    let mut bytes = [0u8; 64];
+    match point.xy() {
+        Some((x, y)) => {
+            bytes.copy_from_slice(&fq2_to_bytes(&x));
+            bytes[0] |= if y > -y { COMPRESSED_LARGEST } else { COMPRESSED_SMALLEST };
+        }
+        None => bytes[0] = COMPRESSED_INFINITY,
+    }
+    bytes
+}
+
+fn g1_from_uncompressed_bytes(bytes: &[u8]) -> Result<G1Affine, String> {
+    if bytes[0] & MASK == UNCOMPRESSED_INFINITY {
+        return Ok(G1Affine::zero());
+    }
+    let point = G1Affine::new_unchecked(fq_from_bytes(&bytes[..32])?, fq_from_bytes(&bytes[32..])?);
+    if !point.is_on_curve() {
+        return Err("G1 point is not on the curve".to_string());
+    }
+    Ok(point)
+}
+
+fn g2_from_uncompressed_bytes(bytes: &[u8]) -> Result<G2Affine, String> {
+    if bytes[0] & MASK == UNCOMPRESSED_INFINITY {
+        return Ok(G2Affine::zero());
+    }
+    let point =
+        G2Affine::new_unchecked(fq2_from_bytes(&bytes[..64])?, fq2_from_bytes(&bytes[64..])?);
+    if !point.is_on_curve() || !point.is_in_correct_subgroup_assuming_on_curve() {
+        return Err("G2 point is not in the subgroup".to_string());
+    }
+    Ok(point)
+}
+
+fn g1_from_compressed_bytes(bytes: &[u8]) -> Result<G1Affine, String> {
+    let flag = bytes[0] & MASK;
+    if flag == COMPRESSED_INFINITY {
+        return Ok(G1Affine::zero());
+    }
+    let mut x = bytes.to_vec();
+    x[0] &= !MASK;
+    G1Affine::get_point_from_x_unchecked(fq_from_bytes(&x)?, flag == COMPRESSED_LARGEST)
+        .ok_or_else(|| "invalid compressed G1 point".to_string())
+}
+
+fn g2_from_compressed_bytes(bytes: &[u8]) -> Result<G2Affine, String> {
+    let flag = bytes[0] & MASK;
+    if flag == COMPRESSED_INFINITY {
+        return Ok(G2Affine::zero());
+    }
+    let mut x = bytes.to_vec();
+    x[0] &= !MASK;
+    G2Affine::get_point_from_x_unchecked(fq2_from_bytes(&x)?, flag == COMPRESSED_LARGEST)
+        .filter(|point| point.is_in_correct_subgroup_assuming_on_curve())
+        .ok_or_else(|| "invalid compressed G2 point".to_string())
+}
+
+/// Encodes a proof like gnark's `WriteRawTo`, for a circuit without commitments.
+pub fn proof_to_bytes(proof: &Proof<Bn254>) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(324);
+    bytes.extend_from_slice(&g1_to_uncompressed_bytes(&proof.a));
+    bytes.extend_from_slice(&g2_to_uncompressed_bytes(&proof.b));
+    bytes.extend_from_slice(&g1_to_uncompressed_bytes(&proof.c));
+    bytes.extend_from_slice(&0u32.to_be_bytes());
+    bytes.extend_from_slice(&g1_to_uncompressed_bytes(&G1Affine::zero()));
+    bytes
+}
+
+/// Decodes a proof encoded by [proof_to_bytes] or by gnark.
+pub fn proof_from_bytes(bytes: &[u8]) -> Result<Proof<Bn254>, String> {
+    if bytes.len() < 256 {
+        return Err("proof is too short".to_string());
+    }
+    Ok(Proof {
+        a: g1_from_uncompressed_bytes(&bytes[..64])?,
+        b: g2_from_uncompressed_bytes(&bytes[64..192])?,
+        c: g1_from_uncompressed_bytes(&bytes[192..256])?,
+    })
+}
+
+/// Encodes a verifying key like gnark's `WriteTo`, for a circuit without commitments.
+///
+/// The gnark verifying key also holds `beta` and `delta` in G1, which the arkworks one lacks.
+pub fn verifying_key_to_bytes(
+    vk: &VerifyingKey<Bn254>,
+    beta_g1: &G1Affine,
+    delta_g1: &G1Affine,
+) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    bytes.extend_from_slice(&g1_to_compressed_bytes(&vk.alpha_g1));
+    bytes.extend_from_slice(&g1_to_compressed_bytes(beta_g1));
+    bytes.extend_from_slice(&g2_to_compressed_bytes(&vk.beta_g2));
+    bytes.extend_from_slice(&g2_to_compressed_bytes(&vk.gamma_g2));
+    bytes.extend_from_slice(&g1_to_compressed_bytes(delta_g1));
+    bytes.extend_from_slice(&g2_to_compressed_bytes(&vk.delta_g2));
+    bytes.extend_from_slice(&(vk.gamma_abc_g1.len() as u32).to_be_bytes());
+    for point in vk.gamma_abc_g1.iter() {
+        bytes.extend_from_slice(&g1_to_compressed_bytes(point));
+    }
+    // No public and commitment committed indices, and no commitment keys.
+    bytes.extend_from_slice(&0u32.to_be_bytes());
+    bytes.extend_from_slice(&0u32.to_be_bytes());
+    bytes
+}
+
+/// Decodes a verifying key encoded by [verifying_key_to_bytes].
+pub fn verifying_key_from_bytes(bytes: &[u8]) -> Result<VerifyingKey<Bn254>, String> {
+    if bytes.len() < 292 {
+        return Err("verifying key is too short".to_string());
+    }
+    let num_k = u32::from_be_bytes(bytes[288..292].try_into().unwrap()) as usize;
+    if bytes.len() < 292 + 32 * num_k {
+        return Err("verifying key is too short".to_string());
+    }
+    Ok(VerifyingKey {
+        alpha_g1: g1_from_compressed_bytes(&bytes[..32])?,
+        beta_g2: g2_from_compressed_bytes(&bytes[64..128])?,
+        gamma_g2: g2_from_compressed_bytes(&bytes[128..192])?,
+        delta_g2: g2_from_compressed_bytes(&bytes[224..288])?,
+        gamma_abc_g1: bytes[292..292 + 32 * num_k]
+            .chunks_exact(32)
+            .map(g1_from_compressed_bytes)
+            .collect::<Result<_, _>>()?,
+    })
+}
@@ -0,0 +1,366 @@
+//! Emulated KoalaBear arithmetic over BN254, ported from the gnark `koalabear` chip.
+//!
+//! A [`Felt`] is a BN254 variable which is congruent to the KoalaBear element it represents, along
+//! with an upper bound on its value. Reductions are delayed until the bound gets close to the
+//! BN254 modulus, or until the canonical value is needed.
+
+use ark_bn254::Fr;
+use ark_r1cs_std::{fields::fp::FpVar, prelude::*};
+use ark_relations::r1cs::{ConstraintSystemRef, SynthesisError};
+use num_bigint::BigUint;
+use p3_field::{
+    extension::BinomialExtensionField, Field, FieldAlgebra, FieldExtensionAlgebra, PrimeField32,
+};
+use p3_koala_bear::KoalaBear;
+
+use super::{biguint_to_fr, to_binary, value_of};
+
+/// The KoalaBear modulus.
+const MODULUS: u64 = 2130706433;
+
+/// The number of bits of a reduced KoalaBear element.
+pub(crate) const NUM_BITS: usize = 31;
+
+/// An emulated KoalaBear element.
+#[derive(Clone)]
+pub struct Felt {
+    pub value: FpVar<Fr>,
+    pub upper_bound: BigUint,
+}
+
+/// An emulated element of the degree 4 extension of KoalaBear.
+#[derive(Clone)]
+pub struct Ext(pub [Felt; 4]);
+
+impl Felt {
+    /// A constant whose upper bound is its value.
+    pub fn constant(value: u64) -> Self {
+        Self { value: FpVar::Constant(Fr::from(value)), upper_bound: BigUint::from(value) }
+    }
+
+    /// A constant parsed from the compiler, bounded like a witness element.
+    pub fn imm(value: &str) -> Self {
+        let value: BigUint = value.parse().expect("invalid felt");
+        Self {
+            value: FpVar::Constant(biguint_to_fr(&value)),
+            upper_bound: BigUint::from(1u64 << 32),
+        }
+    }
+
+    /// A witness element, which must be range checked by the caller.
+    pub fn witness(value: FpVar<Fr>) -> Self {
+        Self { value, upper_bound: BigUint::from(1u64 << 32) }
+    }
+}
+
+impl Ext {
+    /// A constant parsed from the compiler.
+    pub fn imm(values: &[String]) -> Self {
+        Self(core::array::from_fn(|i| Felt::imm(&values[i])))
+    }
+}
+
+/// The gadgets of emulated KoalaBear arithmetic.
+pub struct KoalaBearChip {
+    cs: ConstraintSystemRef<Fr>,
+}
+
+impl KoalaBearChip {
+    pub fn new(cs: ConstraintSystemRef<Fr>) -> Self {
+        Self { cs }
+    }
+
+    pub fn add_f(&self, a: &Felt, b: &Felt) -> Result<Felt, SynthesisError> {
+        self.reduce_fast(Self::add_f_unreduced(a, b))
+    }
+
+    fn add_f_unreduced(a: &Felt, b: &Felt) -> Felt {
+        Felt { value: &a.value + &b.value, upper_bound: &a.upper_bound + &b.upper_bound }
+    }
+
+    pub fn sub_f(&self, a: &Felt, b: &Felt) -> Result<Felt, SynthesisError> {
+        let neg_b = self.neg_f(b)?;
+        self.add_f(a, &neg_b)
+    }
+
+    pub fn mul_f(&self, a: &Felt, b: &Felt) -> Result<Felt, SynthesisError> {
+        self.reduce_fast(Self::mul_f_unreduced(a, b))
+    }
+
+    fn mul_f_unreduced(a: &Felt, b: &Felt) -> Felt {
+        Felt { value: &a.value * &b.value, upper_bound: &a.upper_bound * &b.upper_bound }
+    }
+
+    pub fn mul_f_const(&self, a: &Felt, b: u64) -> Result<Felt, SynthesisError> {
+        self.reduce_fast(Self::mul_f_const_unreduced(a, b))
+    }
+
+    fn mul_f_const_unreduced(a: &Felt, b: u64) -> Felt {
+        Felt { value: &a.value * Fr::from(b), upper_bound: &a.upper_bound * b }
+    }
+
+    pub fn neg_f(&self, a: &Felt) -> Result<Felt, SynthesisError> {
+        let lifted_modulus = (&a.upper_bound / MODULUS + 1u64) * MODULUS;
+        self.reduce_fast(Felt {
+            value: FpVar::Constant(biguint_to_fr(&lifted_modulus)) - &a.value,
+            upper_bound: lifted_modulus,
+        })
+    }
+
+    fn inv_f(&self, a: &Felt) -> Result<Felt, SynthesisError> {
+        let value = value_of(&a.value);
+        let inv = FpVar::new_witness(self.cs.clone(), || {
+            let a = koalabear(value.as_ref().ok_or(SynthesisError::AssignmentMissing)?);
+            Ok(Fr::from(a.try_inverse().unwrap_or(KoalaBear::ZERO).as_canonical_u32()))
+        })?;
+        to_binary(&self.cs, &inv, NUM_BITS)?;
+        let inv = Felt { value: inv, upper_bound: BigUint::from(1u64 << 31) };
+
+        let product = self.mul_f(a, &inv)?;
+        self.assert_eq_f(&product, &Felt::constant(1))?;
+        Ok(inv)
+    }
+
+    pub fn div_f(&self, a: &Felt, b: &Felt) -> Result<Felt, SynthesisError> {
+        let b_inv = self.inv_f(b)?;
+        self.mul_f(a, &b_inv)
+    }
+
+    pub fn assert_eq_f(&self, a: &Felt, b: &Felt) -> Result<(), SynthesisError> {
+        let a = self.reduce_slow(a)?;
+        let b = self.reduce_slow(b)?;
+        a.value.enforce_equal(&b.value)
+    }
+
+    pub fn assert_ne_f(&self, a: &Felt, b: &Felt) -> Result<(), SynthesisError> {
+        let a = self.reduce_slow(a)?;
+        let b = self.reduce_slow(b)?;
+        a.value.enforce_not_equal(&b.value)
+    }
+
+    pub fn assert_eq_e(&self, a: &Ext, b: &Ext) -> Result<(), SynthesisError> {
+        for (a, b) in a.0.iter().zip(b.0.iter()) {
+            self.assert_eq_f(a, b)?;
+        }
+        Ok(())
+    }
+
+    pub fn select_f(&self, cond: &FpVar<Fr>, a: &Felt, b: &Felt) -> Result<Felt, SynthesisError> {
+        Ok(Felt {
+            value: select(cond, &a.value, &b.value)?,
+            upper_bound: a.upper_bound.clone().max(b.upper_bound.clone()),
+        })
+    }
+
+    pub fn select_e(&self, cond: &FpVar<Fr>, a: &Ext, b: &Ext) -> Result<Ext, SynthesisError> {
+        Ok(Ext([
+            self.select_f(cond, &a.0[0], &b.0[0])?,
+            self.select_f(cond, &a.0[1], &b.0[1])?,
+            self.select_f(cond, &a.0[2], &b.0[2])?,
+            self.select_f(cond, &a.0[3], &b.0[3])?,
+        ]))
+    }
+
+    pub fn add_ef(&self, a: &Ext, b: &Felt) -> Result<Ext, SynthesisError> {
+        let [a0, a1, a2, a3] = a.0.clone();
+        Ok(Ext([self.add_f(&a0, b)?, a1, a2, a3]))
+    }
+
+    pub fn add_e(&self, a: &Ext, b: &Ext) -> Result<Ext, SynthesisError> {
+        Ok(Ext([
+            self.add_f(&a.0[0], &b.0[0])?,
+            self.add_f(&a.0[1], &b.0[1])?,
+            self.add_f(&a.0[2], &b.0[2])?,
+            self.add_f(&a.0[3], &b.0[3])?,
+        ]))
+    }
+
+    pub fn sub_e(&self, a: &Ext, b: &Ext) -> Result<Ext, SynthesisError> {
+        Ok(Ext([
+            self.sub_f(&a.0[0], &b.0[0])?,
+            self.sub_f(&a.0[1], &b.0[1])?,
+            self.sub_f(&a.0[2], &b.0[2])?,
+            self.sub_f(&a.0[3], &b.0[3])?,
+        ]))
+    }
+
+    pub fn sub_ef(&self, a: &Ext, b: &Felt) -> Result<Ext, SynthesisError> {
+        let [a0, a1, a2, a3] = a.0.clone();
+        Ok(Ext([self.sub_f(&a0, b)?, a1, a2, a3]))
+    }
+
+    /// Multiplies in `F[X]/(X^4 - 3)`, reducing the coefficients once they are accumulated.
+    pub fn mul_e(&self, a: &Ext, b: &Ext) -> Result<Ext, SynthesisError> {
+        let mut product: [Felt; 4] = core::array::from_fn(|_| Felt::constant(0));
+        for i in 0..4 {
+            for j in 0..4 {
+                let term = Self::mul_f_unreduced(&a.0[i], &b.0[j]);
+                if i + j >= 4 {
+                    let term = Self::mul_f_const_unreduced(&term, 3);
+                    product[i + j - 4] = Self::add_f_unreduced(&product[i + j - 4], &term);
+                } else {
+                    product[i + j] = Self::add_f_unreduced(&product[i + j], &term);
+                }
+            }
+        }
+        let [p0, p1, p2, p3] = product;
+        Ok(Ext([
+            self.reduce_fast(p0)?,
+            self.reduce_fast(p1)?,
+            self.reduce_fast(p2)?,
+            self.reduce_fast(p3)?,
+        ]))
+    }
+
+    pub fn mul_ef(&self, a: &Ext, b: &Felt) -> Result<Ext, SynthesisError> {
+        Ok(Ext([
+            self.mul_f(&a.0[0], b)?,
+            self.mul_f(&a.0[1], b)?,
+            self.mul_f(&a.0[2], b)?,
+            self.mul_f(&a.0[3], b)?,
+        ]))
+    }
+
+    pub fn inv_e(&self, a: &Ext) -> Result<Ext, SynthesisError> {
+        let values = a.0.clone().map(|x| value_of(&x.value));
+        let inv = || -> Result<BinomialExtensionField<KoalaBear, 4>, SynthesisError> {
+            let coefficients = values
+                .iter()
+                .map(|x| x.as_ref().map(koalabear).ok_or(SynthesisError::AssignmentMissing))
+                .collect::<Result<Vec<_>, _>>()?;
+            let a = BinomialExtensionField::<KoalaBear, 4>::from_base_slice(&coefficients);
+            Ok(a.try_inverse().unwrap_or(BinomialExtensionField::ZERO))
+        };
+
+        let mut out: [Felt; 4] = core::array::from_fn(|_| Felt::constant(0));
+        for (i, out) in out.iter_mut().enumerate() {
+            let value = FpVar::new_witness(self.cs.clone(), || {
+                Ok(Fr::from(inv()?.as_base_slice()[i].as_canonical_u32()))
+            })?;
+            to_binary(&self.cs, &value, NUM_BITS)?;
+            *out = Felt { value, upper_bound: BigUint::from(1u64 << 31) };
+        }
+        let out = Ext(out);
+
+        let product = self.mul_e(a, &out)?;
+        let one = Ext([Felt::constant(1), Felt::constant(0), Felt::constant(0), Felt::constant(0)]);
+        self.assert_eq_e(&product, &one)?;
+        Ok(out)
+    }
+
+    pub fn div_e(&self, a: &Ext, b: &Ext) -> Result<Ext, SynthesisError> {
+        let b_inv = self.inv_e(b)?;
+        self.mul_e(a, &b_inv)
+    }
+
+    pub fn div_ef(&self, a: &Ext, b: &Felt) -> Result<Ext, SynthesisError> {
+        let b_inv = self.inv_f(b)?;
+        self.mul_ef(a, &b_inv)
+    }
+
+    pub fn neg_e(&self, a: &Ext) -> Result<Ext, SynthesisError> {
+        Ok(Ext([
+            self.neg_f(&a.0[0])?,
+            self.neg_f(&a.0[1])?,
+            self.neg_f(&a.0[2])?,
+            self.neg_f(&a.0[3])?,
+        ]))
+    }
+
+    /// The 31 bits of the canonical value of `a`, least significant first.
+    pub fn to_binary(&self, a: &Felt) -> Result<Vec<Boolean<Fr>>, SynthesisError> {
+        let a = self.reduce_slow(a)?;
+        to_binary(&self.cs, &a.value, NUM_BITS)
+    }
+
+    pub fn reduce_e(&self, a: &Ext) -> Result<Ext, SynthesisError> {
+        Ok(Ext([
+            self.reduce_slow(&a.0[0])?,
+            self.reduce_slow(&a.0[1])?,
+            self.reduce_slow(&a.0[2])?,
+            self.reduce_slow(&a.0[3])?,
+        ]))
+    }
+
+    /// Reduces `a` only if its upper bound is getting close to the BN254 modulus.
+    fn reduce_fast(&self, a: Felt) -> Result<Felt, SynthesisError> {
+        if a.upper_bound.bits() >= 120 {
+            return Ok(Felt {
+                value: self.reduce_with_max_bits(&a.value, a.upper_bound.bits() as usize)?,
+                upper_bound: BigUint::from(MODULUS - 1),
+            });
+        }
+        Ok(a)
+    }
+
+    /// Reduces `a` to its canonical value.
+    pub fn reduce_slow(&self, a: &Felt) -> Result<Felt, SynthesisError> {
+        if a.upper_bound < BigUint::from(MODULUS) {
+            return Ok(a.clone());
+        }
+        Ok(Felt {
+            value: self.reduce_with_max_bits(&a.value, a.upper_bound.bits() as usize)?,
+            upper_bound: BigUint::from(MODULUS - 1),
+        })
+    }
+
+    fn reduce_with_max_bits(
+        &self,
+        x: &FpVar<Fr>,
+        max_num_bits: usize,
+    ) -> Result<FpVar<Fr>, SynthesisError> {
+        if max_num_bits <= 30 {
+            return Ok(x.clone());
+        }
+
+        let value = value_of(x);
+        let witness = |f: fn(&BigUint) -> BigUint| {
+            FpVar::new_witness(self.cs.clone(), || {
+                Ok(biguint_to_fr(&f(value.as_ref().ok_or(SynthesisError::AssignmentMissing)?)))
+            })
+        };
+
+        let quotient = witness(|x| x / MODULUS)?;
+        let remainder = witness(|x| x % MODULUS)?;
+        to_binary(&self.cs, &quotient, max_num_bits - 30)?;
+
+        // Check that the remainder is less than the KoalaBear modulus, by decomposing it into a 24
+        // bit limb and a 7 bit limb.
+        let remainder_value = value.as_ref().map(|x| x % MODULUS);
+        let limb = |f: fn(&BigUint) -> BigUint| {
+            FpVar::new_witness(self.cs.clone(), || {
+                Ok(biguint_to_fr(&f(remainder_value
+                    .as_ref()
+                    .ok_or(SynthesisError::AssignmentMissing)?)))
+            })
+        };
+        let low_limb = limb(|x| x % (1u64 << 24))?;
+        let high_limb = limb(|x| x >> 24)?;
+        (&high_limb * Fr::from(1u64 << 24) + &low_limb).enforce_equal(&remainder)?;
+        to_binary(&self.cs, &high_limb, 7)?;
+        to_binary(&self.cs, &low_limb, 24)?;
+
+        // If the most significant bits are all 1, then the least significant bits must be zero
+        // for the element to be less than the KoalaBear modulus.
+        let should_check = high_limb.is_eq(&FpVar::Constant(Fr::from(127u64)))?;
+        (FpVar::from(should_check) * &low_limb).enforce_equal(&FpVar::zero())?;
+
+        (&quotient * Fr::from(MODULUS) + &remainder).enforce_equal(x)?;
+
+        Ok(remainder)
+    }
+}
+
+/// Selects `a` if `cond` is one and `b` if it is zero, constraining `cond` to be a bit.
+pub(crate) fn select(
+    cond: &FpVar<Fr>,
+    a: &FpVar<Fr>,
+    b: &FpVar<Fr>,
+) -> Result<FpVar<Fr>, SynthesisError> {
+    cond.mul_equals(&(cond - Fr::from(1u64)), &FpVar::zero())?;
+    Ok(b + cond * (a - b))
+}
+
+fn koalabear(value: &BigUint) -> KoalaBear {
+    KoalaBear::from_canonical_u32((value % MODULUS).try_into().unwrap())
+}
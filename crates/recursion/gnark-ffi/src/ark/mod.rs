@@ -0,0 +1,369 @@
+//! A Groth16 backend written with arkworks, which proves the outer circuit without the Go toolchain.
+//!
+//! The functions mirror the gnark bindings in `crate::ffi`, and read and write the same artifacts
+//! in the data directory, except for the proving key. The verifying key and the proofs use the
+//! gnark encodings, so that they are checked by the same verifiers. Only keys set up by
+//! [build_groth16_bn254] can be used to prove with this backend.
+//!
+//! The keys are set up by a single party with local randomness, so whoever ran
+//! [build_groth16_bn254] can forge proofs for them: they are meant for development and for
+//! deployments that trust the machine building the circuit. The verifying key also differs from
+//! the one of the trusted setup shipped with Ziren, so the proofs are rejected with
+//! `zkm_verifier::GROTH16_VK_BYTES` and by the deployed verifier contracts. Verify them with the
+//! `groth16_vk.bin` of the data directory instead, or with the `Groth16Verifier.sol` and
+//! `ZKMVerifierGroth16.sol` contracts written next to it.
+//!
+//! PLONK and DV-SNARK proofs are not supported by this backend yet, and still need gnark.
+
+mod circuit;
+mod gnark;
+mod koalabear;
+mod poseidon2;
+mod solidity;
+
+pub use circuit::Groth16Circuit;
+
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter, Write},
+    path::Path,
+};
+
+use ark_bn254::{Bn254, Fr};
+use ark_ff::PrimeField;
+use ark_groth16::{Groth16, PreparedVerifyingKey, ProvingKey};
+use ark_r1cs_std::{fields::fp::FpVar, prelude::*};
+use ark_relations::r1cs::{
+    ConstraintSynthesizer, ConstraintSystem, ConstraintSystemRef, SynthesisError,
+};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use num_bigint::BigUint;
+use rand::rngs::OsRng;
+use serde::de::DeserializeOwned;
+use zkm_recursion_compiler::constraints::Constraint;
+
+use crate::{witness::GnarkWitness, Groth16Bn254Proof};
+
+const CONSTRAINTS_JSON_PATH: &str = "constraints.json";
+const GROTH16_WITNESS_PATH: &str = "groth16_witness.json";
+const GROTH16_PK_PATH: &str = "groth16_ark_pk.bin";
+const GROTH16_VK_PATH: &str = "groth16_vk.bin";
+const GROTH16_VERIFIER_PATH: &str = "Groth16Verifier.sol";
+
+/// Sets up the Groth16 circuit in `data_dir` and writes its proving and verifying keys, and the
+/// Solidity verifier of the verifying key.
+///
+/// The constraints and the witness of the wrap program must already be in `data_dir`. The setup
+/// uses randomness of this machine only, see the [module documentation](self).
+pub fn build_groth16_bn254(data_dir: &str) {
+    let data_dir = Path::new(data_dir);
+    let circuit = Groth16Circuit {
+        constraints: read_json(&data_dir.join(CONSTRAINTS_JSON_PATH)),
+        witness: read_json(&data_dir.join(GROTH16_WITNESS_PATH)),
+    };
+
+    let mut rng = OsRng;
+    let pk = Groth16::<Bn254>::generate_random_parameters_with_reduction(circuit.clone(), &mut rng)
+        .expect("failed to set up the groth16 circuit");
+
+    // Prove and verify with the witness of the wrap program, as a sanity check.
+    let public_inputs = public_inputs(&circuit.witness);
+    let proof = Groth16::<Bn254>::create_random_proof_with_reduction(circuit, &pk, &mut rng)
+        .expect("failed to prove with the groth16 circuit");
+    let verified = Groth16::<Bn254>::verify_proof(&pk.vk.clone().into(), &proof, &public_inputs)
+        .expect("failed to verify the groth16 proof");
+    assert!(verified, "the groth16 proof of the wrap program is invalid");
+
+    let mut file = BufWriter::new(File::create(data_dir.join(GROTH16_PK_PATH)).unwrap());
+    pk.serialize_uncompressed(&mut file).unwrap();
+    file.flush().unwrap();
+
+    let vk_bytes = gnark::verifying_key_to_bytes(&pk.vk, &pk.beta_g1, &pk.delta_g1);
+    std::fs::write(data_dir.join(GROTH16_VK_PATH), vk_bytes).unwrap();
+    std::fs::write(data_dir.join(GROTH16_VERIFIER_PATH), solidity::groth16_verifier(&pk.vk))
+        .unwrap();
+}
+
+/// Generates a Groth16 proof for the witness at `witness_path` with the keys in `data_dir`.
+pub fn prove_groth16_bn254(data_dir: &str, witness_path: &str) -> Groth16Bn254Proof {
+    let data_dir = Path::new(data_dir);
+    let pk_path = data_dir.join(GROTH16_PK_PATH);
+    let file = File::open(&pk_path).unwrap_or_else(|_| {
+        panic!(
+            "{} not found, the groth16 circuit must be built with the ark feature",
+            pk_path.display()
+        )
+    });
+    let pk = ProvingKey::<Bn254>::deserialize_uncompressed_unchecked(BufReader::new(file))
+        .expect("failed to read the groth16 proving key");
+
+    let witness: GnarkWitness = read_json(Path::new(witness_path));
+    let public_inputs = [witness.vkey_hash.clone(), witness.committed_values_digest.clone()];
+    let circuit =
+        Groth16Circuit { constraints: read_json(&data_dir.join(CONSTRAINTS_JSON_PATH)), witness };
+    let proof = Groth16::<Bn254>::create_random_proof_with_reduction(circuit, &pk, &mut OsRng)
+        .expect("failed to prove with the groth16 circuit");
+
+    let raw_proof = gnark::proof_to_bytes(&proof);
+    Groth16Bn254Proof {
+        public_inputs,
+        encoded_proof: hex::encode(&raw_proof[..256]),
+        raw_proof: hex::encode(&raw_proof),
+        groth16_vkey_hash: [0; 32],
+    }
+}
+
+/// Verifies a hex encoded raw Groth16 proof against the verifying key in `data_dir`.
+pub fn verify_groth16_bn254(
+    data_dir: &str,
+    proof: &str,
+    vkey_hash: &str,
+    committed_values_digest: &str,
+) -> Result<(), String> {
+    let vk_bytes = std::fs::read(Path::new(data_dir).join(GROTH16_VK_PATH))
+        .map_err(|e| format!("failed to read the verifying key: {e}"))?;
+    let vk = gnark::verifying_key_from_bytes(&vk_bytes)?;
+    let proof = hex::decode(proof).map_err(|e| format!("invalid proof encoding: {e}"))?;
+    let proof = gnark::proof_from_bytes(&proof)?;
+
+    let public_inputs = [parse_fr(vkey_hash), parse_fr(committed_values_digest)];
+    let pvk = PreparedVerifyingKey::from(vk);
+    match Groth16::<Bn254>::verify_proof(&pvk, &proof, &public_inputs) {
+        Ok(true) => Ok(()),
+        Ok(false) => Err("invalid proof".to_string()),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// Checks that the witness at `witness_json` satisfies the constraints at `constraints_json`.
+pub fn test_groth16_bn254(witness_json: &str, constraints_json: &str) {
+    let circuit = Groth16Circuit {
+        constraints: read_json(Path::new(constraints_json)),
+        witness: read_json(Path::new(witness_json)),
+    };
+    test_circuit(circuit);
+}
+
+fn test_circuit(circuit: Groth16Circuit) {
+    let cs = ConstraintSystem::<Fr>::new_ref();
+    circuit.generate_constraints(cs.clone()).expect("failed to synthesize the groth16 circuit");
+    if !cs.is_satisfied().unwrap() {
+        panic!("Test failed: {:?} is unsatisfied", cs.which_is_unsatisfied().unwrap());
+    }
+}
+
+fn read_json<T: DeserializeOwned>(path: &Path) -> T {
+    let file =
+        File::open(path).unwrap_or_else(|e| panic!("failed to open {}: {e}", path.display()));
+    serde_json::from_reader(BufReader::new(file))
+        .unwrap_or_else(|e| panic!("failed to parse {}: {e}", path.display()))
+}
+
+fn public_inputs(witness: &GnarkWitness) -> [Fr; 2] {
+    [parse_fr(&witness.vkey_hash), parse_fr(&witness.committed_values_digest)]
+}
+
+fn parse_fr(value: &str) -> Fr {
+    biguint_to_fr(&value.parse().expect("invalid field element"))
+}
+
+fn biguint_to_fr(value: &BigUint) -> Fr {
+    Fr::from(value.clone())
+}
+
+/// The value of `x` if it is assigned, which it is not during the setup.
+fn value_of(x: &FpVar<Fr>) -> Option<BigUint> {
+    x.value().ok().map(|x| x.into_bigint().into())
+}
+
+/// Decomposes `x` into `num_bits` bits, least significant first, checking that it fits.
+fn to_binary(
+    cs: &ConstraintSystemRef<Fr>,
+    x: &FpVar<Fr>,
+    num_bits: usize,
+) -> Result<Vec<Boolean<Fr>>, SynthesisError> {
+    // The decomposition is only unique below the modulus.
+    if num_bits >= Fr::MODULUS_BIT_SIZE as usize {
+        let mut bits = x.to_bits_le()?;
+        bits.resize(num_bits, Boolean::FALSE);
+        return Ok(bits);
+    }
+
+    let value = value_of(x);
+    let bits = (0..num_bits)
+        .map(|i| {
+            Boolean::new_witness(cs.clone(), || {
+                value
+                    .as_ref()
+                    .map(|value| value.bit(i as u64))
+                    .ok_or(SynthesisError::AssignmentMissing)
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut sum = FpVar::zero();
+    let mut coefficient = Fr::from(1u64);
+    for bit in bits.iter() {
+        sum += FpVar::from(bit.clone()) * coefficient;
+        coefficient += coefficient;
+    }
+    sum.enforce_equal(x)?;
+    Ok(bits)
+}
+
+#[cfg(test)]
+mod tests {
+    use p3_field::{Field, FieldAlgebra, PrimeField, PrimeField32};
+    use p3_koala_bear::KoalaBear;
+    use p3_symmetric::Permutation;
+    use zkm_recursion_compiler::{
+        config::OuterConfig, constraints::opcodes::ConstraintOpcode, ir::Config,
+    };
+    use zkm_recursion_core::stark::outer_perm;
+    use zkm_stark::inner_perm;
+
+    use super::*;
+
+    fn constraint(opcode: ConstraintOpcode, args: &[&[&str]]) -> Constraint {
+        Constraint {
+            opcode,
+            args: args.iter().map(|arg| arg.iter().map(|x| x.to_string()).collect()).collect(),
+        }
+    }
+
+    fn witness(felts: &[u32]) -> GnarkWitness {
+        GnarkWitness {
+            felts: felts.iter().map(|x| x.to_string()).collect(),
+            vkey_hash: "7".to_string(),
+            committed_values_digest: "11".to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Constraints which check the field arithmetic on the witness felts `1` and `2`, and commit to
+    /// the public inputs `7` and `11`.
+    fn arithmetic_constraints() -> Vec<Constraint> {
+        use ConstraintOpcode::*;
+        let p = KoalaBear::ORDER_U32;
+        let half = KoalaBear::from_canonical_u32(2).inverse().as_canonical_u32().to_string();
+        vec![
+            constraint(WitnessF, &[&["a"], &["0"]]),
+            constraint(WitnessF, &[&["b"], &["1"]]),
+            constraint(DivF, &[&["c"], &["a"], &["b"]]),
+            constraint(ImmF, &[&["half"], &[&half]]),
+            constraint(AssertEqF, &[&["c"], &["half"]]),
+            constraint(SubF, &[&["d"], &["a"], &["b"]]),
+            constraint(ImmF, &[&["minus_one"], &[&(p - 1).to_string()]]),
+            constraint(AssertEqF, &[&["d"], &["minus_one"]]),
+            constraint(ImmE, &[&["x"], &["0", "1", "0", "0"]]),
+            constraint(InvE, &[&["x_inv"], &["x"]]),
+            constraint(MulE, &[&["one"], &["x"], &["x_inv"]]),
+            constraint(ImmE, &[&["expected_one"], &["1", "0", "0", "0"]]),
+            constraint(AssertEqE, &[&["one"], &["expected_one"]]),
+            constraint(CircuitFelt2Var, &[&["v"], &["b"]]),
+            constraint(Num2BitsV, &[&["b0", "b1", "b2"], &["v"], &["3"]]),
+            constraint(ImmV, &[&["seven"], &["7"]]),
+            constraint(AddV, &[&["s"], &["b0"], &["b1"]]),
+            constraint(SubV, &[&["s"], &["s"], &["b2"]]),
+            constraint(MulV, &[&["vkey_hash"], &["s"], &["seven"]]),
+            constraint(CommitVkeyHash, &[&["vkey_hash"]]),
+            constraint(ImmV, &[&["cvd"], &["11"]]),
+            constraint(CommitCommittedValuesDigest, &[&["cvd"]]),
+        ]
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        test_circuit(Groth16Circuit {
+            constraints: arithmetic_constraints(),
+            witness: witness(&[1, 2]),
+        });
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_arithmetic_invalid_witness() {
+        test_circuit(Groth16Circuit {
+            constraints: arithmetic_constraints(),
+            witness: witness(&[1, 3]),
+        });
+    }
+
+    #[test]
+    fn test_permute_koalabear() {
+        let input: [KoalaBear; 16] = core::array::from_fn(|i| KoalaBear::from_canonical_usize(i));
+        let output = inner_perm().permute(input);
+
+        let mut constraints = Vec::new();
+        for (i, x) in input.iter().enumerate() {
+            let x = x.as_canonical_u32().to_string();
+            constraints.push(constraint(ConstraintOpcode::ImmF, &[&[&format!("s{i}")], &[&x]]));
+        }
+        let ids = (0..16).map(|i| format!("s{i}")).collect::<Vec<_>>();
+        constraints.push(Constraint {
+            opcode: ConstraintOpcode::PermuteKoalaBear,
+            args: ids.iter().map(|id| vec![id.clone()]).collect(),
+        });
+        for (i, x) in output.iter().enumerate() {
+            let x = x.as_canonical_u32().to_string();
+            constraints.push(constraint(ConstraintOpcode::ImmF, &[&[&format!("e{i}")], &[&x]]));
+            constraints.push(constraint(
+                ConstraintOpcode::AssertEqF,
+                &[&[&format!("s{i}")], &[&format!("e{i}")]],
+            ));
+        }
+        test_circuit(Groth16Circuit { constraints, witness: witness(&[]) });
+    }
+
+    #[test]
+    fn test_permute_bn254() {
+        let input = [0, 1, 2].map(<<OuterConfig as Config>::N as FieldAlgebra>::from_canonical_u32);
+        let output = outer_perm().permute(input);
+
+        let mut constraints = Vec::new();
+        for (i, x) in input.iter().enumerate() {
+            let x = x.as_canonical_biguint().to_string();
+            constraints.push(constraint(ConstraintOpcode::ImmV, &[&[&format!("s{i}")], &[&x]]));
+        }
+        constraints.push(constraint(ConstraintOpcode::Permute, &[&["s0"], &["s1"], &["s2"]]));
+        for (i, x) in output.iter().enumerate() {
+            let x = x.as_canonical_biguint().to_string();
+            constraints.push(constraint(ConstraintOpcode::ImmV, &[&[&format!("e{i}")], &[&x]]));
+            constraints.push(constraint(
+                ConstraintOpcode::AssertEqV,
+                &[&[&format!("s{i}")], &[&format!("e{i}")]],
+            ));
+        }
+        test_circuit(Groth16Circuit { constraints, witness: witness(&[]) });
+    }
+
+    #[test]
+    fn test_prove_and_verify() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let data_dir_str = data_dir.path().to_str().unwrap();
+        let witness = witness(&[1, 2]);
+        std::fs::write(
+            data_dir.path().join(CONSTRAINTS_JSON_PATH),
+            serde_json::to_string(&arithmetic_constraints()).unwrap(),
+        )
+        .unwrap();
+        witness.save(data_dir.path().join(GROTH16_WITNESS_PATH).to_str().unwrap());
+
+        build_groth16_bn254(data_dir_str);
+        let witness_path = data_dir.path().join(GROTH16_WITNESS_PATH);
+        let proof = prove_groth16_bn254(data_dir_str, witness_path.to_str().unwrap());
+        verify_groth16_bn254(data_dir_str, &proof.raw_proof, "7", "11").unwrap();
+        assert!(verify_groth16_bn254(data_dir_str, &proof.raw_proof, "7", "12").is_err());
+
+        // The proof and the verifying key are read by the gnark verifier.
+        let raw_proof = hex::decode(&proof.raw_proof).unwrap();
+        let vk = std::fs::read(data_dir.path().join(GROTH16_VK_PATH)).unwrap();
+        let public_inputs = [7u8, 11].map(|x| {
+            let mut bytes = [0u8; 32];
+            bytes[31] = x;
+            bytes
+        });
+        zkm_verifier::Groth16Verifier::verify_gnark_proof(&raw_proof[..256], &public_inputs, &vk)
+            .unwrap();
+    }
+}
@@ -0,0 +1,237 @@
+//! The Poseidon2 permutations over BN254 and emulated KoalaBear, ported from the gnark `poseidon2`
+//! chips.
+
+use ark_bn254::Fr;
+use ark_r1cs_std::fields::fp::FpVar;
+use ark_relations::r1cs::SynthesisError;
+use num_bigint::BigUint;
+use p3_field::{PrimeField, PrimeField32};
+use zkm_primitives::RC_16_30;
+use zkm_recursion_core::stark::bn254_poseidon2_rc3;
+
+use super::{
+    biguint_to_fr,
+    koalabear::{Felt, KoalaBearChip},
+};
+
+const WIDTH: usize = 3;
+const NUM_EXTERNAL_ROUNDS: usize = 8;
+const NUM_INTERNAL_ROUNDS: usize = 56;
+
+/// The Poseidon2 permutation of a state of three BN254 elements.
+pub struct Poseidon2Chip {
+    round_constants: Vec<[Fr; WIDTH]>,
+}
+
+impl Poseidon2Chip {
+    pub fn new() -> Self {
+        let round_constants = bn254_poseidon2_rc3()
+            .into_iter()
+            .map(|rc| rc.map(|x| biguint_to_fr(&x.as_canonical_biguint())))
+            .collect();
+        Self { round_constants }
+    }
+
+    pub fn permute_mut(&self, state: &mut [FpVar<Fr>; WIDTH]) {
+        // The initial linear layer.
+        Self::matrix_permute_mut(state);
+
+        // The first half of the external rounds.
+        let rounds = NUM_EXTERNAL_ROUNDS + NUM_INTERNAL_ROUNDS;
+        let rounds_f_beginning = NUM_EXTERNAL_ROUNDS / 2;
+        for r in 0..rounds_f_beginning {
+            self.add_rc(state, r);
+            Self::sbox(state);
+            Self::matrix_permute_mut(state);
+        }
+
+        // The internal rounds.
+        let p_end = rounds_f_beginning + NUM_INTERNAL_ROUNDS;
+        for r in rounds_f_beginning..p_end {
+            state[0] = &state[0] + self.round_constants[r][0];
+            state[0] = Self::sbox_p(&state[0]);
+            Self::diffusion_permute_mut(state);
+        }
+
+        // The second half of the external rounds.
+        for r in p_end..rounds {
+            self.add_rc(state, r);
+            Self::sbox(state);
+            Self::matrix_permute_mut(state);
+        }
+    }
+
+    fn add_rc(&self, state: &mut [FpVar<Fr>; WIDTH], round: usize) {
+        for (x, rc) in state.iter_mut().zip(self.round_constants[round]) {
+            *x = &*x + rc;
+        }
+    }
+
+    fn sbox_p(input: &FpVar<Fr>) -> FpVar<Fr> {
+        let squared = input * input;
+        let input_4 = &squared * &squared;
+        input_4 * input
+    }
+
+    fn sbox(state: &mut [FpVar<Fr>; WIDTH]) {
+        for x in state.iter_mut() {
+            *x = Self::sbox_p(x);
+        }
+    }
+
+    fn diffusion_permute_mut(state: &mut [FpVar<Fr>; WIDTH]) {
+        let sum = &state[0] + &state[1] + &state[2];
+        state[2] = &state[2] * Fr::from(2u64);
+        for x in state.iter_mut() {
+            *x = &*x + &sum;
+        }
+    }
+
+    fn matrix_permute_mut(state: &mut [FpVar<Fr>; WIDTH]) {
+        let sum = &state[0] + &state[1] + &state[2];
+        for x in state.iter_mut() {
+            *x = &*x + &sum;
+        }
+    }
+}
+
+impl Default for Poseidon2Chip {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+const KOALABEAR_WIDTH: usize = 16;
+const KOALABEAR_NUM_EXTERNAL_ROUNDS: usize = 8;
+const KOALABEAR_NUM_INTERNAL_ROUNDS: usize = 13;
+
+/// The diagonal of the internal matrix of the KoalaBear permutation, minus one.
+///
+/// V = [-2, 1, 2, 1/2, 3, 4, -1/2, -3, -4, 1/2^8, 1/8, 1/2^24, -1/2^8, -1/8, -1/16, -1/2^24]
+const MAT_INTERNAL_DIAG_M1: [u64; KOALABEAR_WIDTH] = [
+    2130706431, 1, 2, 1065353217, 3, 4, 1065353216, 2130706430, 2130706429, 2122383361, 1864368129,
+    2130706306, 8323072, 266338304, 133169152, 127,
+];
+
+/// The Poseidon2 permutation of a state of 16 emulated KoalaBear elements.
+pub struct Poseidon2KoalaBearChip<'a> {
+    field_chip: &'a KoalaBearChip,
+}
+
+impl<'a> Poseidon2KoalaBearChip<'a> {
+    pub fn new(field_chip: &'a KoalaBearChip) -> Self {
+        Self { field_chip }
+    }
+
+    pub fn permute_mut(&self, state: &mut [Felt; KOALABEAR_WIDTH]) -> Result<(), SynthesisError> {
+        // The initial linear layer.
+        self.external_linear_layer(state)?;
+
+        // The first half of the external rounds.
+        let rounds = KOALABEAR_NUM_EXTERNAL_ROUNDS + KOALABEAR_NUM_INTERNAL_ROUNDS;
+        let rounds_f_beginning = KOALABEAR_NUM_EXTERNAL_ROUNDS / 2;
+        for r in 0..rounds_f_beginning {
+            self.add_rc(state, r)?;
+            self.sbox(state)?;
+            self.external_linear_layer(state)?;
+        }
+
+        // The internal rounds.
+        let p_end = rounds_f_beginning + KOALABEAR_NUM_INTERNAL_ROUNDS;
+        for r in rounds_f_beginning..p_end {
+            state[0] = self.field_chip.add_f(&state[0], &Self::round_constant(r, 0))?;
+            state[0] = self.sbox_p(&state[0])?;
+            self.diffusion_permute_mut(state)?;
+        }
+
+        // The second half of the external rounds.
+        for r in p_end..rounds {
+            self.add_rc(state, r)?;
+            self.sbox(state)?;
+            self.external_linear_layer(state)?;
+        }
+        Ok(())
+    }
+
+    fn round_constant(round: usize, i: usize) -> Felt {
+        Felt::constant(RC_16_30[round][i].as_canonical_u32() as u64)
+    }
+
+    fn add_rc(
+        &self,
+        state: &mut [Felt; KOALABEAR_WIDTH],
+        round: usize,
+    ) -> Result<(), SynthesisError> {
+        for (i, x) in state.iter_mut().enumerate() {
+            *x = self.field_chip.add_f(x, &Self::round_constant(round, i))?;
+        }
+        Ok(())
+    }
+
+    fn sbox_p(&self, input: &Felt) -> Result<Felt, SynthesisError> {
+        let input = self.field_chip.add_f(input, &Felt::constant(0))?;
+        let input = self.field_chip.reduce_slow(&input)?;
+        let i2 = &input.value * &input.value;
+        let i3 = i2 * &input.value;
+        self.field_chip
+            .reduce_slow(&Felt { value: i3, upper_bound: BigUint::from(2130706433u64).pow(3) })
+    }
+
+    fn sbox(&self, state: &mut [Felt; KOALABEAR_WIDTH]) -> Result<(), SynthesisError> {
+        for x in state.iter_mut() {
+            *x = self.sbox_p(x)?;
+        }
+        Ok(())
+    }
+
+    fn mds_light_permutation_4x4(&self, state: &mut [Felt]) -> Result<(), SynthesisError> {
+        let chip = self.field_chip;
+        let t01 = chip.add_f(&state[0], &state[1])?;
+        let t23 = chip.add_f(&state[2], &state[3])?;
+        let t0123 = chip.add_f(&t01, &t23)?;
+        let t01123 = chip.add_f(&t0123, &state[1])?;
+        let t01233 = chip.add_f(&t0123, &state[3])?;
+        state[3] = chip.add_f(&t01233, &chip.mul_f_const(&state[0], 2)?)?;
+        state[1] = chip.add_f(&t01123, &chip.mul_f_const(&state[2], 2)?)?;
+        state[0] = chip.add_f(&t01123, &t01)?;
+        state[2] = chip.add_f(&t01233, &t23)?;
+        Ok(())
+    }
+
+    fn external_linear_layer(
+        &self,
+        state: &mut [Felt; KOALABEAR_WIDTH],
+    ) -> Result<(), SynthesisError> {
+        for chunk in state.chunks_mut(4) {
+            self.mds_light_permutation_4x4(chunk)?;
+        }
+
+        let mut sums: [Felt; 4] = core::array::from_fn(|i| state[i].clone());
+        for i in (4..KOALABEAR_WIDTH).step_by(4) {
+            for (j, sum) in sums.iter_mut().enumerate() {
+                *sum = self.field_chip.add_f(sum, &state[i + j])?;
+            }
+        }
+
+        for (i, x) in state.iter_mut().enumerate() {
+            *x = self.field_chip.add_f(x, &sums[i % 4])?;
+        }
+        Ok(())
+    }
+
+    fn diffusion_permute_mut(
+        &self,
+        state: &mut [Felt; KOALABEAR_WIDTH],
+    ) -> Result<(), SynthesisError> {
+        let mut sum = Felt::constant(0);
+        for x in state.iter() {
+            sum = self.field_chip.add_f(&sum, x)?;
+        }
+
+        for (x, diag) in state.iter_mut().zip(MAT_INTERNAL_DIAG_M1) {
+            *x = self.field_chip.mul_f(x, &Felt::constant(diag))?;
+            *x = self.field_chip.add_f(x, &sum)?;
+        }
+        Ok(())
+    }
+}
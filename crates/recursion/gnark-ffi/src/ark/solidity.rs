@@ -0,0 +1,72 @@
+//! The Solidity verifier of the Groth16 circuit, with the verifying key of the arkworks backend.
+
+use ark_bn254::{Bn254, Fq, G1Affine, G2Affine};
+use ark_ec::AffineRepr;
+use ark_ff::PrimeField;
+use ark_groth16::VerifyingKey;
+use num_bigint::BigUint;
+
+/// Writes the `Groth16Verifier` contract checking proofs against `vk`, with the same `Verify`
+/// function as the one gnark exports.
+pub fn groth16_verifier(vk: &VerifyingKey<Bn254>) -> String {
+    // The vkey hash and the committed values digest.
+    assert_eq!(vk.gamma_abc_g1.len(), 3, "the groth16 circuit must have two public inputs");
+
+    let mut contract = include_str!("../../assets/Groth16Verifier.txt").to_string();
+    replace_g1(&mut contract, "ALPHA", &vk.alpha_g1);
+    replace_g2(&mut contract, "BETA_NEG", &(-vk.beta_g2));
+    replace_g2(&mut contract, "GAMMA_NEG", &(-vk.gamma_g2));
+    replace_g2(&mut contract, "DELTA_NEG", &(-vk.delta_g2));
+    replace_g1(&mut contract, "CONSTANT", &vk.gamma_abc_g1[0]);
+    replace_g1(&mut contract, "PUB_0", &vk.gamma_abc_g1[1]);
+    replace_g1(&mut contract, "PUB_1", &vk.gamma_abc_g1[2]);
+    contract
+}
+
+fn replace(contract: &mut String, name: &str, value: &Fq) {
+    let value: BigUint = value.into_bigint().into();
+    *contract = contract.replace(&format!("{{{name}}}"), &value.to_string());
+}
+
+fn replace_g1(contract: &mut String, name: &str, point: &G1Affine) {
+    let (x, y) = point.xy().expect("the verifying key has a point at infinity");
+    replace(contract, &format!("{name}_X"), &x);
+    replace(contract, &format!("{name}_Y"), &y);
+}
+
+/// Replaces the coordinates of a G2 point, the imaginary part of each first like the pairing
+/// precompile expects.
+fn replace_g2(contract: &mut String, name: &str, point: &G2Affine) {
+    let (x, y) = point.xy().expect("the verifying key has a point at infinity");
+    replace(contract, &format!("{name}_X_1"), &x.c1);
+    replace(contract, &format!("{name}_X_0"), &x.c0);
+    replace(contract, &format!("{name}_Y_1"), &y.c1);
+    replace(contract, &format!("{name}_Y_0"), &y.c0);
+}
+
+#[cfg(test)]
+mod tests {
+    use ark_bn254::{G1Projective, G2Projective};
+    use ark_ec::{CurveGroup, PrimeGroup};
+
+    use super::*;
+
+    #[test]
+    fn test_groth16_verifier() {
+        let g1 = G1Projective::generator();
+        let g2 = G2Projective::generator();
+        let vk = VerifyingKey::<Bn254> {
+            alpha_g1: g1.into_affine(),
+            beta_g2: (g2 + g2).into_affine(),
+            gamma_g2: g2.into_affine(),
+            delta_g2: (g2 + g2 + g2).into_affine(),
+            gamma_abc_g1: vec![g1.into_affine(), (g1 + g1).into_affine(), (g1 + g1).into_affine()],
+        };
+        let contract = groth16_verifier(&vk);
+        assert!(!contract.contains("= {"), "a constant of the verifying key was not replaced");
+        // The generator of G1 is (1, 2).
+        assert!(contract.contains("uint256 constant ALPHA_X = 1;"));
+        assert!(contract.contains("uint256 constant ALPHA_Y = 2;"));
+        assert!(contract.contains("uint256 constant CONSTANT_Y = 2;"));
+    }
+}
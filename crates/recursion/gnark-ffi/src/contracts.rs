@@ -0,0 +1,101 @@
+//! Exports the Solidity contracts verifying Groth16 and PLONK proofs on chain.
+//!
+//! The contracts are laid out like the imports of the verifier templates expect:
+//!
+//! ```text
+//! IZKMVerifier.sol
+//! ZKMGateway.sol
+//! groth16/Groth16Verifier.sol
+//! groth16/ZKMVerifierGroth16.sol
+//! plonk/PlonkVerifier.sol
+//! plonk/ZKMVerifierPlonk.sol
+//! ```
+
+use std::{fs, path::Path};
+
+use anyhow::{anyhow, bail, Context, Result};
+
+const IZKM_VERIFIER: &str = include_str!("../assets/IZKMVerifier.sol");
+
+/// Exports the Groth16 verifier contracts of the circuit built in `build_dir` to `output_dir`.
+pub fn export_groth16_verifier(build_dir: &Path, output_dir: &Path) -> Result<()> {
+    export_verifier(
+        build_dir,
+        &output_dir.join("groth16"),
+        &["Groth16Verifier.sol", "ZKMVerifierGroth16.sol"],
+    )?;
+    export_interface(output_dir)
+}
+
+/// Exports the PLONK verifier contracts of the circuit built in `build_dir` to `output_dir`.
+pub fn export_plonk_verifier(build_dir: &Path, output_dir: &Path) -> Result<()> {
+    export_verifier(
+        build_dir,
+        &output_dir.join("plonk"),
+        &["PlonkVerifier.sol", "ZKMVerifierPlonk.sol"],
+    )?;
+    export_interface(output_dir)
+}
+
+/// Exports the gateway routing the proofs of the program with the given vkey hash, formatted like
+/// `HashableKey::bytes32`, to the verifiers.
+pub fn export_gateway(program_vkey: &str, output_dir: &Path) -> Result<()> {
+    let vkey = hex::decode(program_vkey.trim_start_matches("0x"))
+        .map_err(|e| anyhow!("invalid program vkey {program_vkey}: {e}"))?;
+    if vkey.len() != 32 {
+        bail!("invalid program vkey {program_vkey}: expected 32 bytes, got {}", vkey.len());
+    }
+
+    let gateway_str = include_str!("../assets/ZKMGateway.txt")
+        .replace("{PROGRAM_VKEY}", format!("0x{}", hex::encode(vkey)).as_str());
+    fs::create_dir_all(output_dir)?;
+    fs::write(output_dir.join("ZKMGateway.sol"), gateway_str)?;
+    export_interface(output_dir)
+}
+
+fn export_interface(output_dir: &Path) -> Result<()> {
+    fs::write(output_dir.join("IZKMVerifier.sol"), IZKM_VERIFIER)?;
+    Ok(())
+}
+
+fn export_verifier(build_dir: &Path, output_dir: &Path, files: &[&str]) -> Result<()> {
+    fs::create_dir_all(output_dir)?;
+    for file in files {
+        let path = build_dir.join(file);
+        fs::copy(&path, output_dir.join(file)).with_context(|| {
+            format!("failed to copy {}, was the circuit built?", path.display())
+        })?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::export_gateway;
+
+    #[test]
+    fn test_export_gateway() {
+        let output_dir = tempfile::tempdir().unwrap();
+        let program_vkey = format!("0x{}", "ab".repeat(32));
+        export_gateway(&program_vkey, output_dir.path()).unwrap();
+
+        let gateway = fs::read_to_string(output_dir.path().join("ZKMGateway.sol")).unwrap();
+        assert!(
+            gateway.contains(&format!("bytes32 public constant PROGRAM_VKEY = {program_vkey};"))
+        );
+        assert!(!gateway.contains("{PROGRAM_VKEY}"));
+        assert!(output_dir.path().join("IZKMVerifier.sol").exists());
+    }
+
+    #[test]
+    fn test_export_gateway_invalid_vkey() {
+        let output_dir = tempfile::tempdir().unwrap();
+        // Not hex.
+        assert!(export_gateway(&format!("0x{}", "zz".repeat(32)), output_dir.path()).is_err());
+        // Too short.
+        assert!(export_gateway(&format!("0x{}", "ab".repeat(31)), output_dir.path()).is_err());
+        assert!(!output_dir.path().join("ZKMGateway.sol").exists());
+    }
+}
@@ -4,11 +4,15 @@ use std::{
     path::{Path, PathBuf},
 };
 
-use crate::{
-    ffi::{build_groth16_bn254, prove_groth16_bn254, test_groth16_bn254, verify_groth16_bn254},
-    witness::GnarkWitness,
-    Groth16Bn254Proof,
+#[cfg(feature = "ark")]
+use crate::ark::{
+    build_groth16_bn254, prove_groth16_bn254, test_groth16_bn254, verify_groth16_bn254,
 };
+#[cfg(not(feature = "ark"))]
+use crate::ffi::{
+    build_groth16_bn254, prove_groth16_bn254, test_groth16_bn254, verify_groth16_bn254,
+};
+use crate::{witness::GnarkWitness, Groth16Bn254Proof};
 
 use anyhow::Result;
 use num_bigint::BigUint;
@@ -19,11 +23,11 @@ use zkm_recursion_compiler::{
     ir::{Config, Witness},
 };
 
-/// A prover that can generate proofs with the PLONK protocol using bindings to Gnark.
+/// A prover that can generate proofs with the Groth16 protocol using bindings to Gnark, or with
+/// arkworks if the `ark` feature is enabled.
 #[derive(Debug, Clone)]
 pub struct Groth16Bn254Prover;
 
-/// A prover that can generate proofs with the Groth16 protocol using bindings to Gnark.
 impl Groth16Bn254Prover {
     /// Creates a new [Groth16Bn254Prover].
     pub fn new() -> Self {
@@ -90,7 +94,7 @@ impl Groth16Bn254Prover {
         // Build the circuit.
         build_groth16_bn254(build_dir.to_str().unwrap());
 
-        // Build the contracts.
+        // Build the contracts. The Groth16 verifier contract is exported by the backend.
         Self::build_contracts(build_dir);
     }
 
@@ -1,14 +1,28 @@
 mod koalabear;
 
+#[cfg(not(any(feature = "native", feature = "ark")))]
+compile_error!(
+    "zkm-recursion-gnark-ffi needs a Groth16 backend: enable the `native` feature for gnark or \
+     the `ark` feature for arkworks"
+);
+
+#[cfg(feature = "ark")]
+pub mod ark;
+pub mod contracts;
+#[cfg(feature = "native")]
 pub mod dvsnark_bn254;
+#[cfg(feature = "native")]
 pub mod ffi;
 pub mod groth16_bn254;
+#[cfg(feature = "native")]
 pub mod plonk_bn254;
 pub mod proof;
 pub mod witness;
 
+#[cfg(feature = "native")]
 pub use dvsnark_bn254::*;
 pub use groth16_bn254::*;
+#[cfg(feature = "native")]
 pub use plonk_bn254::*;
 pub use proof::*;
 pub use witness::*;
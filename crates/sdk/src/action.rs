@@ -1,14 +1,39 @@
-use zkm_core_executor::{ExecutionError, ExecutionReport, HookEnv, ZKMContextBuilder};
-use zkm_core_machine::io::ZKMStdin;
+use zkm_core_executor::{
+    CancellationToken, ExecutionError, ExecutionReport, HookEnv, OutputBuffer, OutputSink,
+    ProfilerConfig, ProveProgress, SymbolTable, ZKMContextBuilder,
+};
+use zkm_core_machine::{io::ZKMStdin, utils::DryRunReport};
 use zkm_primitives::io::ZKMPublicValues;
 use zkm_prover::{components::DefaultProverComponents, ZKMProvingKey};
 
 use anyhow::{Ok, Result};
-use std::time::Duration;
-use zkm_stark::{ZKMCoreOpts, ZKMProverOpts};
+use std::{path::PathBuf, time::Duration};
+use zkm_stark::{SecurityLevel, ZKMCoreOpts, ZKMProverOpts};
 
 use crate::{provers::ProofOpts, Prover, ZKMProofKind, ZKMProofWithPublicValues};
 
+/// The output a guest wrote to stdout and stderr.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GuestOutput {
+    /// The bytes written to stdout.
+    pub stdout: Vec<u8>,
+    /// The bytes written to stderr.
+    pub stderr: Vec<u8>,
+}
+
+impl GuestOutput {
+    /// Capture the output of the execution configured by `context_builder`.
+    fn capture(context_builder: &mut ZKMContextBuilder) -> (OutputBuffer, OutputBuffer) {
+        let (stdout, stderr) = (OutputBuffer::new(), OutputBuffer::new());
+        context_builder.stdout(stdout.clone()).stderr(stderr.clone());
+        (stdout, stderr)
+    }
+
+    fn from_buffers((stdout, stderr): (OutputBuffer, OutputBuffer)) -> Self {
+        Self { stdout: stdout.take(), stderr: stderr.take() }
+    }
+}
+
 /// Builder to prepare and configure execution of a program on an input.
 /// May be run with [Self::run].
 pub struct Execute<'a> {
@@ -16,6 +41,7 @@ pub struct Execute<'a> {
     context_builder: ZKMContextBuilder<'a>,
     elf: &'a [u8],
     stdin: &'a ZKMStdin,
+    profile_sample_interval: Option<u64>,
 }
 
 impl<'a> Execute<'a> {
@@ -28,14 +54,57 @@ impl<'a> Execute<'a> {
         elf: &'a [u8],
         stdin: &'a ZKMStdin,
     ) -> Self {
-        Self { prover, elf, stdin, context_builder: Default::default() }
+        Self {
+            prover,
+            elf,
+            stdin,
+            context_builder: Default::default(),
+            profile_sample_interval: None,
+        }
     }
 
     /// Execute the program on the input, consuming the built action `self`.
     pub fn run(self) -> Result<(ZKMPublicValues, ExecutionReport)> {
-        let Self { prover, elf, stdin, mut context_builder } = self;
+        let Self { prover, elf, stdin, mut context_builder, mut profile_sample_interval } = self;
+
+        // Profile the execution and write the profile to files if `ZKM_PROFILE` is set.
+        let profile_dir = crate::utils::zkm_profile_dir();
+        if profile_dir.is_some() {
+            profile_sample_interval.get_or_insert(1);
+        }
+        if let Some(interval) = profile_sample_interval {
+            let symbols = SymbolTable::from_elf(elf)?;
+            context_builder.profiler(ProfilerConfig::new(symbols).sample_interval(interval));
+        }
+
+        let context = context_builder.build();
+        let (public_values, report) = prover.zkm_prover().execute(elf, stdin, context)?;
+        if let (Some(dir), Some(profile)) = (profile_dir, report.profile.as_deref()) {
+            crate::utils::write_profile(&dir, profile)?;
+        }
+        Ok((public_values, report))
+    }
+
+    /// Execute the program on the input and fit every shard the prover would create into the
+    /// allowed shapes, without generating any traces.
+    ///
+    /// Use [`DryRunReport::fits`] to check that proving will not fail on a shape mismatch; the
+    /// report lists the shards that fit no shape, the chips that overflowed and the precompiles
+    /// whose events got shards of their own.
+    pub fn dry_run(self) -> Result<DryRunReport> {
+        let Self { prover, elf, stdin, mut context_builder, .. } = self;
         let context = context_builder.build();
-        Ok(prover.zkm_prover().execute(elf, stdin, context)?)
+        Ok(prover.zkm_prover().dry_run(elf, stdin, ZKMCoreOpts::default(), context)?)
+    }
+
+    /// Execute the program on the input, capturing the guest stdout and stderr instead of
+    /// printing them.
+    ///
+    /// This replaces the sinks set with [Self::stdout] and [Self::stderr].
+    pub fn run_with_output(mut self) -> Result<(ZKMPublicValues, ExecutionReport, GuestOutput)> {
+        let buffers = GuestOutput::capture(&mut self.context_builder);
+        let (public_values, report) = self.run()?;
+        Ok((public_values, report, GuestOutput::from_buffers(buffers)))
     }
 
     /// Add a runtime [Hook](super::Hook) into the context.
@@ -75,6 +144,58 @@ impl<'a> Execute<'a> {
         self.context_builder.set_skip_deferred_proof_verification(value);
         self
     }
+
+    /// Estimate the cost of proving the execution.
+    ///
+    /// The estimate is returned in [`ExecutionReport::cost_estimate`] and includes the number of
+    /// shards, the padded trace heights, the LDE area and the predicted shape. Use
+    /// [`CostEstimate::forecast`](zkm_core_executor::CostEstimate::forecast) to turn it into a
+    /// proving time and memory forecast.
+    pub fn estimate_cost(mut self) -> Self {
+        self.context_builder.estimate_cost(true);
+        self
+    }
+
+    /// Record the per-instruction trace of the execution.
+    ///
+    /// The trace is returned in [`ExecutionReport::trace`], and holds the program counter, the
+    /// instruction word and the register and memory writes of every executed instruction.
+    pub fn trace(mut self) -> Self {
+        self.context_builder.trace(true);
+        self
+    }
+
+    /// Profile the execution, sampling the guest call stack every `sample_interval` cycles.
+    ///
+    /// Use `1` to attribute every cycle. The profile is returned in
+    /// [`ExecutionReport::profile`]. Setting the `ZKM_PROFILE` environment variable to a
+    /// directory also enables profiling and writes the profile there.
+    pub fn profile(mut self, sample_interval: u64) -> Self {
+        self.profile_sample_interval = Some(sample_interval);
+        self
+    }
+
+    /// Send the guest stdout to `sink` instead of printing it.
+    ///
+    /// See [`ZKMContextBuilder::stdout`] for the available sinks.
+    pub fn stdout(mut self, sink: impl OutputSink + Send + 'a) -> Self {
+        self.context_builder.stdout(sink);
+        self
+    }
+
+    /// Send the guest stderr to `sink` instead of printing it.
+    ///
+    /// See [`ZKMContextBuilder::stdout`] for the available sinks.
+    pub fn stderr(mut self, sink: impl OutputSink + Send + 'a) -> Self {
+        self.context_builder.stderr(sink);
+        self
+    }
+
+    /// Stop the execution with [`ExecutionError::Cancelled`] once `token` is cancelled.
+    pub fn cancellation_token(mut self, token: CancellationToken) -> Self {
+        self.context_builder.cancellation_token(token);
+        self
+    }
 }
 
 /// Builder to prepare and configure proving execution of a program on an input.
@@ -87,7 +208,9 @@ pub struct Prove<'a> {
     stdin: ZKMStdin,
     core_opts: ZKMCoreOpts,
     recursion_opts: ZKMCoreOpts,
+    security_level: SecurityLevel,
     timeout: Option<Duration>,
+    checkpoint_dir: Option<PathBuf>,
 }
 
 impl<'a> Prove<'a> {
@@ -108,7 +231,9 @@ impl<'a> Prove<'a> {
             context_builder: Default::default(),
             core_opts: ZKMCoreOpts::default(),
             recursion_opts: ZKMCoreOpts::recursion(),
+            security_level: SecurityLevel::default(),
             timeout: None,
+            checkpoint_dir: None,
         }
     }
 
@@ -122,10 +247,17 @@ impl<'a> Prove<'a> {
             mut context_builder,
             core_opts,
             recursion_opts,
+            security_level,
             timeout,
+            checkpoint_dir,
         } = self;
-        let opts = ZKMProverOpts { core_opts, recursion_opts };
-        let proof_opts = ProofOpts { zkm_prover_opts: opts, timeout };
+        let opts = ZKMProverOpts {
+            core_opts,
+            recursion_opts,
+            security_level,
+            fri_parameters: pk.vk.fri_parameters,
+        };
+        let proof_opts = ProofOpts { zkm_prover_opts: opts, timeout, checkpoint_dir };
         let context = context_builder.build();
 
         // Dump the program and stdin to files for debugging if `ZKM_DUMP` is set.
@@ -134,6 +266,18 @@ impl<'a> Prove<'a> {
         Ok(prover.prove_impl(pk, stdin, proof_opts, context, kind, None)?.0)
     }
 
+    /// Prove the execution of the program on the input, capturing the guest stdout and stderr
+    /// instead of printing them.
+    ///
+    /// This replaces the sinks set with [Self::stdout] and [Self::stderr]. The output is only
+    /// captured by the local provers, and is not re-emitted for the checkpoints skipped when
+    /// resuming from a [checkpoint directory](Self::checkpoint_dir).
+    pub fn run_with_output(mut self) -> Result<(ZKMProofWithPublicValues, GuestOutput)> {
+        let buffers = GuestOutput::capture(&mut self.context_builder);
+        let proof = self.run()?;
+        Ok((proof, GuestOutput::from_buffers(buffers)))
+    }
+
     /// Set the proof kind to the core mode. This is the default.
     pub fn core(mut self) -> Self {
         self.kind = ZKMProofKind::Core;
@@ -220,6 +364,14 @@ impl<'a> Prove<'a> {
         self
     }
 
+    /// Require the proof to reach `level`, failing if the FRI parameters of the prover are weaker.
+    ///
+    /// This parameter is only used by the CPU prover.
+    pub fn security_level(mut self, level: SecurityLevel) -> Self {
+        self.security_level = level;
+        self
+    }
+
     /// Set the timeout for the proof's generation.
     ///
     /// This parameter is only used when the prover is run in network mode.
@@ -233,4 +385,47 @@ impl<'a> Prove<'a> {
         self.context_builder.set_skip_deferred_proof_verification(value);
         self
     }
+
+    /// Save the progress of the core proof to `dir`, and resume from it if it already holds the
+    /// progress of a previous run with the same program and stdin.
+    ///
+    /// This parameter is only used by the CPU prover.
+    pub fn checkpoint_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.checkpoint_dir = Some(dir.into());
+        self
+    }
+
+    /// Send the guest stdout to `sink` instead of printing it.
+    ///
+    /// See [`ZKMContextBuilder::stdout`] for the available sinks.
+    pub fn stdout(mut self, sink: impl OutputSink + Send + 'a) -> Self {
+        self.context_builder.stdout(sink);
+        self
+    }
+
+    /// Send the guest stderr to `sink` instead of printing it.
+    ///
+    /// See [`ZKMContextBuilder::stdout`] for the available sinks.
+    pub fn stderr(mut self, sink: impl OutputSink + Send + 'a) -> Self {
+        self.context_builder.stderr(sink);
+        self
+    }
+
+    /// Stop the proof once `token` is cancelled.
+    ///
+    /// The CPU prover checks the token between the batches of shards, between the proofs of the
+    /// compress tree and between the stages of the proof, and fails with
+    /// [`ExecutionError::Cancelled`] or [`zkm_prover::ZKMRecursionProverError::Cancelled`].
+    pub fn cancellation_token(mut self, token: CancellationToken) -> Self {
+        self.context_builder.cancellation_token(token);
+        self
+    }
+
+    /// Call `f` with the progress of the proof, from the proving threads.
+    ///
+    /// This parameter is only used by the CPU prover.
+    pub fn progress(mut self, f: impl Fn(ProveProgress) + Send + Sync + 'a) -> Self {
+        self.context_builder.progress(f);
+        self
+    }
 }
@@ -0,0 +1,45 @@
+//! # Ziren Contracts
+//!
+//! Exports the Solidity contracts verifying Ziren proofs on chain.
+
+use std::path::Path;
+
+use anyhow::Result;
+use zkm_prover::build::groth16_bn254_artifacts_dev_dir;
+#[cfg(feature = "native-gnark")]
+use zkm_prover::build::plonk_bn254_artifacts_dev_dir;
+#[cfg(feature = "native-gnark")]
+use zkm_recursion_gnark_ffi::contracts::export_plonk_verifier;
+use zkm_recursion_gnark_ffi::contracts::{export_gateway, export_groth16_verifier};
+
+use crate::install::try_install_circuit_artifacts;
+use crate::ZKM_CIRCUIT_VERSION;
+
+/// Exports the Groth16 and PLONK verifier contracts, and a `ZKMGateway` contract routing the
+/// proofs of the program with the given vkey hash to them, to `output_dir`.
+///
+/// The program vkey hash is formatted like [`HashableKey::bytes32`](crate::HashableKey::bytes32).
+/// In development mode, the contracts are taken from the locally built circuit artifacts,
+/// otherwise the circuit artifacts are installed if needed. PLONK proofs are only proven with
+/// gnark, so the PLONK verifier is only exported with the `native-gnark` feature.
+pub fn export_solidity_contracts(program_vkey: &str, output_dir: impl AsRef<Path>) -> Result<()> {
+    let output_dir = output_dir.as_ref();
+    let groth16_bn254_artifacts = if zkm_prover::build::zkm_dev_mode() {
+        groth16_bn254_artifacts_dev_dir()
+    } else {
+        try_install_circuit_artifacts("groth16", ZKM_CIRCUIT_VERSION)
+    };
+    export_groth16_verifier(&groth16_bn254_artifacts, output_dir)?;
+
+    #[cfg(feature = "native-gnark")]
+    {
+        let plonk_bn254_artifacts = if zkm_prover::build::zkm_dev_mode() {
+            plonk_bn254_artifacts_dev_dir()
+        } else {
+            try_install_circuit_artifacts("plonk", ZKM_CIRCUIT_VERSION)
+        };
+        export_plonk_verifier(&plonk_bn254_artifacts, output_dir)?;
+    }
+
+    export_gateway(program_vkey, output_dir)
+}
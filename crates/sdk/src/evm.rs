@@ -0,0 +1,237 @@
+//! # Ziren EVM
+//!
+//! An in-process EVM to test the Solidity verifier contracts against Ziren proofs.
+
+use std::{env, path::Path, process::Command, str::FromStr};
+
+use alloy_primitives::{Address, Bytes, TxKind, B256};
+use alloy_sol_types::{sol, SolCall, SolValue};
+use anyhow::{anyhow, bail, Context as _, Result};
+use revm::{
+    context::TxEnv,
+    context_interface::result::{ExecutionResult, Output},
+    database::{CacheDB, EmptyDB},
+    Context, ExecuteCommitEvm, MainBuilder, MainContext,
+};
+
+use crate::{ZKMProof, ZKMProofWithPublicValues};
+
+sol! {
+    interface IZKMVerifier {
+        function verifyProof(
+            bytes32 programVKey,
+            bytes calldata publicValues,
+            bytes calldata proofBytes
+        ) external view;
+    }
+
+    interface IZKMGateway {
+        function verifyProof(bytes calldata publicValues, bytes calldata proofBytes) external view;
+    }
+}
+
+/// The account sending the transactions.
+const CALLER: Address = Address::with_last_byte(1);
+
+/// The gas limit of the transactions.
+const GAS_LIMIT: u64 = 30_000_000;
+
+/// An in-process EVM with the contracts exported by
+/// [`export_solidity_contracts`](crate::contracts::export_solidity_contracts) deployed.
+pub struct EvmVerifier {
+    db: CacheDB<EmptyDB>,
+    nonce: u64,
+    groth16_verifier: Address,
+    plonk_verifier: Option<Address>,
+    gateway: Address,
+}
+
+impl EvmVerifier {
+    /// Compiles the contracts in `contracts_dir` and deploys them.
+    ///
+    /// The contracts are compiled with the `solc` binary, or the one pointed to by the `SOLC`
+    /// environment variable. The PLONK verifier is only deployed with the `native-gnark` feature,
+    /// like it is only exported with it.
+    pub fn deploy(contracts_dir: impl AsRef<Path>) -> Result<Self> {
+        let contracts_dir = contracts_dir.as_ref();
+        let mut evm = Self {
+            db: CacheDB::default(),
+            nonce: 0,
+            groth16_verifier: Address::ZERO,
+            plonk_verifier: None,
+            gateway: Address::ZERO,
+        };
+
+        evm.groth16_verifier =
+            evm.create(compile(contracts_dir, "groth16/ZKMVerifierGroth16.sol", "ZKMVerifier")?)?;
+        #[cfg(feature = "native-gnark")]
+        {
+            evm.plonk_verifier = Some(evm.create(compile(
+                contracts_dir,
+                "plonk/ZKMVerifierPlonk.sol",
+                "ZKMVerifier",
+            )?)?);
+        }
+
+        let gateway = compile(contracts_dir, "ZKMGateway.sol", "ZKMGateway")?;
+        let verifiers: Vec<Address> =
+            [Some(evm.groth16_verifier), evm.plonk_verifier].into_iter().flatten().collect();
+        let constructor_args = (verifiers,).abi_encode_params();
+        evm.gateway = evm.create([gateway, constructor_args].concat())?;
+
+        Ok(evm)
+    }
+
+    /// Verifies a Groth16 or PLONK proof of the program with the given vkey hash, formatted like
+    /// [`HashableKey::bytes32`](crate::HashableKey::bytes32), with the matching verifier.
+    ///
+    /// Returns the gas used by the verification.
+    pub fn verify(&mut self, proof: &ZKMProofWithPublicValues, program_vkey: &str) -> Result<u64> {
+        let verifier = match &proof.proof {
+            ZKMProof::Groth16(_) => self.groth16_verifier,
+            ZKMProof::Plonk(_) => self
+                .plonk_verifier
+                .ok_or_else(|| anyhow!("the PLONK verifier needs the `native-gnark` feature"))?,
+            _ => bail!("only Groth16 and PLONK proofs can be verified in the EVM"),
+        };
+        let call = IZKMVerifier::verifyProofCall {
+            programVKey: B256::from_str(program_vkey)
+                .map_err(|e| anyhow!("invalid program vkey {program_vkey}: {e}"))?,
+            publicValues: Bytes::copy_from_slice(proof.public_values.as_slice()),
+            proofBytes: proof_bytes(proof)?,
+        };
+        self.call(verifier, call.abi_encode())
+    }
+
+    /// Verifies a Groth16 or PLONK proof with the gateway, which routes it to the matching
+    /// verifier.
+    ///
+    /// Returns the gas used by the verification.
+    pub fn verify_with_gateway(&mut self, proof: &ZKMProofWithPublicValues) -> Result<u64> {
+        let call = IZKMGateway::verifyProofCall {
+            publicValues: Bytes::copy_from_slice(proof.public_values.as_slice()),
+            proofBytes: proof_bytes(proof)?,
+        };
+        self.call(self.gateway, call.abi_encode())
+    }
+
+    fn create(&mut self, bytecode: Vec<u8>) -> Result<Address> {
+        match self.transact(TxKind::Create, bytecode)? {
+            ExecutionResult::Success { output: Output::Create(_, Some(address)), .. } => {
+                Ok(address)
+            }
+            result => bail!("failed to deploy contract: {result:?}"),
+        }
+    }
+
+    fn call(&mut self, address: Address, calldata: Vec<u8>) -> Result<u64> {
+        match self.transact(TxKind::Call(address), calldata)? {
+            ExecutionResult::Success { gas_used, .. } => Ok(gas_used),
+            ExecutionResult::Revert { output, .. } => bail!("execution reverted: {output}"),
+            ExecutionResult::Halt { reason, .. } => bail!("execution halted: {reason:?}"),
+        }
+    }
+
+    fn transact(&mut self, kind: TxKind, data: Vec<u8>) -> Result<ExecutionResult> {
+        let tx = TxEnv {
+            caller: CALLER,
+            gas_limit: GAS_LIMIT,
+            kind,
+            data: data.into(),
+            nonce: self.nonce,
+            ..Default::default()
+        };
+        let result = {
+            let mut evm = Context::mainnet().with_db(&mut self.db).build_mainnet();
+            evm.transact_commit(tx).map_err(|e| anyhow!("failed to execute transaction: {e:?}"))?
+        };
+        self.nonce += 1;
+        Ok(result)
+    }
+}
+
+/// Returns the proof bytes passed to the verifiers.
+fn proof_bytes(proof: &ZKMProofWithPublicValues) -> Result<Bytes> {
+    match &proof.proof {
+        ZKMProof::Groth16(_) | ZKMProof::Plonk(_) => Ok(proof.bytes().into()),
+        _ => bail!("only Groth16 and PLONK proofs can be verified in the EVM"),
+    }
+}
+
+/// Compiles a contract of a source file in `contracts_dir`, returning its creation bytecode.
+fn compile(contracts_dir: &Path, source: &str, contract: &str) -> Result<Vec<u8>> {
+    let solc = env::var("SOLC").unwrap_or_else(|_| "solc".to_string());
+    let output = Command::new(&solc)
+        .args(["--optimize", "--combined-json", "bin", "--base-path", ".", source])
+        .current_dir(contracts_dir)
+        .output()
+        .with_context(|| format!("failed to run {solc}"))?;
+    if !output.status.success() {
+        bail!("failed to compile {source}: {}", String::from_utf8_lossy(&output.stderr));
+    }
+
+    let output: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    let bytecode = output["contracts"][format!("{source}:{contract}")]["bin"]
+        .as_str()
+        .ok_or_else(|| anyhow!("contract {contract} not found in {source}"))?;
+    Ok(hex::decode(bytecode)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::EvmVerifier;
+    use crate::contracts::export_solidity_contracts;
+    use crate::{
+        utils, HashableKey, ProverClient, ZKMProofWithPublicValues, ZKMPublicValues, ZKMStdin,
+        ZKMVerifyingKey,
+    };
+
+    fn verify_in_evm(mut proof: ZKMProofWithPublicValues, vk: &ZKMVerifyingKey) {
+        let program_vkey = vk.vk.bytes32();
+        let contracts_dir = tempfile::tempdir().unwrap();
+        export_solidity_contracts(&program_vkey, contracts_dir.path()).unwrap();
+        let mut evm = EvmVerifier::deploy(contracts_dir.path()).unwrap();
+
+        let gas_used = evm.verify(&proof, &program_vkey).unwrap();
+        tracing::info!("verified proof with {gas_used} gas");
+        evm.verify_with_gateway(&proof).unwrap();
+
+        // Test invalid public values.
+        proof.public_values = ZKMPublicValues::from(&[255, 4, 84]);
+        if evm.verify(&proof, &program_vkey).is_ok() {
+            panic!("verified proof with invalid public values")
+        }
+        if evm.verify_with_gateway(&proof).is_ok() {
+            panic!("verified proof with invalid public values")
+        }
+    }
+
+    #[test]
+    #[ignore]
+    fn test_e2e_evm_groth16() {
+        utils::setup_logger();
+        let client = ProverClient::cpu();
+        let elf = test_artifacts::FIBONACCI_ELF;
+        let (pk, vk) = client.setup(elf);
+        let mut stdin = ZKMStdin::new();
+        stdin.write(&10usize);
+
+        let proof = client.prove(&pk, stdin).groth16().run().unwrap();
+        verify_in_evm(proof, &vk);
+    }
+
+    #[test]
+    #[ignore]
+    #[cfg(feature = "native-gnark")]
+    fn test_e2e_evm_plonk() {
+        utils::setup_logger();
+        let client = ProverClient::cpu();
+        let elf = test_artifacts::FIBONACCI_ELF;
+        let (pk, vk) = client.setup(elf);
+        let mut stdin = ZKMStdin::new();
+        stdin.write(&10usize);
+
+        let proof = client.prove(&pk, stdin).plonk().run().unwrap();
+        verify_in_evm(proof, &vk);
+    }
+}
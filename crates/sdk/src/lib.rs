@@ -3,6 +3,7 @@
 //! A library for interacting with the Ziren zkVM.
 
 pub mod action;
+pub mod contracts;
 // pub mod artifacts;
 pub mod install;
 
@@ -12,6 +13,8 @@ use cfg_if::cfg_if;
 use std::env;
 use zkm_cuda::ZKMGpuServer;
 
+#[cfg(feature = "evm")]
+pub mod evm;
 #[cfg(feature = "network")]
 pub mod network;
 pub mod proof;
@@ -21,18 +24,29 @@ pub mod utils;
 pub use proof::*;
 pub use provers::ZKMVerificationError;
 use zkm_prover::components::DefaultProverComponents;
+use zkm_stark::ZKMProverOpts;
 
 //#[cfg(feature = "network")]
-pub use provers::{CpuProver, MockProver, Prover};
+pub use provers::{CpuProver, MockProver, Prover, ProverHandle};
 
+pub use action::GuestOutput;
 pub use zkm_build::include_elf;
-pub use zkm_core_executor::{ExecutionReport, HookEnv, ZKMContext, ZKMContextBuilder};
-pub use zkm_core_machine::{io::ZKMStdin, ZKM_CIRCUIT_VERSION};
+pub use zkm_core_executor::{
+    attach_files, BacktraceFrame, CancellationToken, ConsoleSink, ExecutionError, ExecutionReport,
+    ExecutionTrace, GuestPanic, HookEnv, OutputBuffer, OutputSink, ProgressCallback, ProveProgress,
+    TraceEntry, TracingSink, WriterSink, ZKMContext, ZKMContextBuilder,
+};
+pub use zkm_core_machine::{
+    io::ZKMStdin,
+    utils::{ChipOverflow, DryRunReport, PrecompileShards, ShardFit, ShardKind},
+    ZKM_CIRCUIT_VERSION,
+};
 pub use zkm_primitives::io::ZKMPublicValues;
 pub use zkm_prover::{
-    CoreSC, HashableKey, InnerSC, OuterSC, PlonkBn254Proof, ProverMode, ZKMProver, ZKMProvingKey,
-    ZKMVerifyingKey,
+    aggregated_pairs, CoreSC, HashableKey, InnerSC, OuterSC, PlonkBn254Proof, ProverMode,
+    ZKMProver, ZKMProvingKey, ZKMVerifyingKey,
 };
+pub use zkm_stark::{FriParameters, SecurityAssumption, SecurityLevel};
 
 // Re-export the utilities.
 #[cfg(feature = "network")]
@@ -261,6 +275,65 @@ impl ProverClient {
         self.prover.verify(proof, vk)
     }
 
+    /// Aggregates compressed proofs into one compressed proof, without an aggregation guest.
+    ///
+    /// Each proof is verified against its verification key in `vks`, and the proofs are folded
+    /// as they are added with the compress arity of the prover. The public values of the
+    /// aggregated proof commit to the ordered vkey hashes and public values digests of the proofs,
+    /// which [`aggregated_pairs`] decodes. The returned verification key is the one of the
+    /// built-in aggregation program.
+    ///
+    /// ### Examples
+    /// ```no_run
+    /// use zkm_sdk::{aggregated_pairs, ProverClient, ZKMStdin};
+    ///
+    /// let elf = test_artifacts::FIBONACCI_ELF;
+    /// let client = ProverClient::new();
+    /// let (pk, vk) = client.setup(elf);
+    /// let proofs = (0..2)
+    ///     .map(|n| {
+    ///         let mut stdin = ZKMStdin::new();
+    ///         stdin.write(&(10usize + n));
+    ///         client.prove(&pk, stdin).compressed().run().unwrap()
+    ///     })
+    ///     .collect::<Vec<_>>();
+    /// let (proof, aggregation_vk) = client.aggregate(&proofs, &[&vk, &vk]).unwrap();
+    /// client.verify(&proof, &aggregation_vk).unwrap();
+    /// let pairs = aggregated_pairs(&proof.public_values, &aggregation_vk).unwrap();
+    /// assert_eq!(pairs.len(), proofs.len());
+    /// ```
+    pub fn aggregate(
+        &self,
+        proofs: &[ZKMProofWithPublicValues],
+        vks: &[&ZKMVerifyingKey],
+    ) -> anyhow::Result<(ZKMProofWithPublicValues, ZKMVerifyingKey)> {
+        anyhow::ensure!(
+            proofs.len() == vks.len(),
+            "{} proofs but {} verification keys",
+            proofs.len(),
+            vks.len()
+        );
+        let prover = self.prover.zkm_prover();
+        let opts = ZKMProverOpts::default();
+        let mut accumulator = prover.accumulator();
+        for (i, (proof, vk)) in proofs.iter().zip(vks).enumerate() {
+            let ZKMProof::Compressed(proof) = &proof.proof else {
+                anyhow::bail!("proof {i} is not a compressed proof");
+            };
+            accumulator
+                .add(*proof.clone(), vk, opts)
+                .map_err(|e| anyhow::anyhow!("failed to add proof {i}: {e}"))?;
+        }
+
+        let (proof, vk, public_values) = accumulator.aggregate(opts)?;
+        let proof = ZKMProofWithPublicValues {
+            proof: ZKMProof::Compressed(Box::new(proof)),
+            public_values,
+            zkm_version: self.prover.version().to_string(),
+        };
+        Ok((proof, vk))
+    }
+
     /// Gets the current version of the Ziren zkVM.
     ///
     /// Note: This is not the same as the version of the Ziren SDK.
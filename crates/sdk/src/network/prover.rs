@@ -2,6 +2,7 @@ use stage_service::stage_service_client::StageServiceClient;
 use stage_service::{GenerateProofRequest, GetStatusRequest};
 
 use std::path::Path;
+use std::sync::Arc;
 use std::time::Instant;
 use std::{env, fs};
 
@@ -16,7 +17,7 @@ use crate::network::ProverInput;
 use crate::{block_on, CpuProver, Prover, ZKMProof, ZKMProofKind, ZKMProofWithPublicValues};
 use anyhow::{bail, Result};
 use async_trait::async_trait;
-use zkm_core_executor::ZKMContext;
+use zkm_core_executor::{CancellationToken, ZKMContext};
 use zkm_core_machine::io::ZKMStdin;
 use zkm_core_machine::ZKM_CIRCUIT_VERSION;
 use zkm_primitives::io::ZKMPublicValues;
@@ -34,7 +35,7 @@ pub mod stage_service {
 }
 
 use crate::network::prover::stage_service::{Status, Step};
-use crate::provers::{ProofOpts, ProverType};
+use crate::provers::{check_cancelled, ProofOpts, ProverHandle, ProverType};
 
 const DEFAULT_POLL_INTERVAL: u64 = 3000; // 3s
 const MIN_POLL_INTERVAL: u64 = 100; // 100ms
@@ -190,10 +191,13 @@ impl NetworkProver {
         proof_id: &str,
         kind: ZKMProofKind,
         timeout: Option<Duration>,
+        cancellation_token: Option<&CancellationToken>,
     ) -> Result<(ZKMProof, ZKMPublicValues, u64)> {
         let start_time = Instant::now();
         let mut client = self.connect().await;
         loop {
+            // The proof keeps running on the network, we only stop waiting for it.
+            check_cancelled(cancellation_token)?;
             if let Some(timeout) = timeout {
                 if start_time.elapsed() > timeout {
                     bail!("Proof generation timed out.");
@@ -255,6 +259,18 @@ impl NetworkProver {
         // If this field is not none, the network prover will use it to index the cached ELF.
         elf_id: Option<String>,
         timeout: Option<Duration>,
+    ) -> Result<(ZKMProofWithPublicValues, u64)> {
+        self.prove_cancellable(elf, stdin, kind, elf_id, timeout, None).await
+    }
+
+    async fn prove_cancellable(
+        &self,
+        elf: &[u8],
+        stdin: ZKMStdin,
+        kind: ZKMProofKind,
+        elf_id: Option<String>,
+        timeout: Option<Duration>,
+        cancellation_token: Option<&CancellationToken>,
     ) -> Result<(ZKMProofWithPublicValues, u64)> {
         let private_input = stdin.buffer.clone();
         let mut pri_buf = Vec::new();
@@ -277,7 +293,8 @@ impl NetworkProver {
         let proof_id = self.request_proof(prover_input, kind).await?;
 
         log::info!("calling wait_proof, proof_id={proof_id}");
-        let (proof, mut public_values, cycles) = self.wait_proof(&proof_id, kind, timeout).await?;
+        let (proof, mut public_values, cycles) =
+            self.wait_proof(&proof_id, kind, timeout, cancellation_token).await?;
 
         if kind == ZKMProofKind::CompressToGroth16 {
             assert_eq!(private_input.len(), 1);
@@ -315,11 +332,39 @@ impl Prover<DefaultProverComponents> for NetworkProver {
         pk: &ZKMProvingKey,
         stdin: ZKMStdin,
         _opts: ProofOpts,
-        _context: ZKMContext<'a>,
+        context: ZKMContext<'a>,
         kind: ZKMProofKind,
         elf_id: Option<String>,
     ) -> Result<(ZKMProofWithPublicValues, u64)> {
-        block_on(self.prove_with_cycles(&pk.elf, stdin, kind, elf_id, None))
+        block_on(self.prove_cancellable(
+            &pk.elf,
+            stdin,
+            kind,
+            elf_id,
+            None,
+            context.cancellation_token.as_ref(),
+        ))
+    }
+
+    /// Waits for the proof without blocking a thread. The handle must be awaited on a tokio
+    /// runtime, and cancelling it stops waiting for the proof.
+    fn prove_async(
+        self: Arc<Self>,
+        pk: Arc<ZKMProvingKey>,
+        stdin: ZKMStdin,
+        opts: ProofOpts,
+        mut context: ZKMContext<'static>,
+        kind: ZKMProofKind,
+    ) -> ProverHandle<(ZKMProofWithPublicValues, u64)> {
+        let cancellation_token =
+            context.cancellation_token.get_or_insert_with(CancellationToken::new).clone();
+        let token = cancellation_token.clone();
+        ProverHandle::new(
+            async move {
+                self.prove_cancellable(&pk.elf, stdin, kind, None, opts.timeout, Some(&token)).await
+            },
+            cancellation_token,
+        )
     }
 }
 
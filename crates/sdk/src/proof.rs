@@ -1,12 +1,18 @@
-use std::{fmt::Debug, fs::File, path::Path};
+use std::{borrow::Borrow, fmt::Debug, fs::File, path::Path, str::FromStr};
 
 use anyhow::Result;
+use num_bigint::BigUint;
+use p3_field::PrimeField;
 use serde::{Deserialize, Serialize};
 use strum_macros::{EnumDiscriminants, EnumTryAs};
 use zkm_core_executor::ZKMReduceProof;
 use zkm_primitives::io::ZKMPublicValues;
 
-use zkm_prover::{CoreSC, DvSnarkBn254Proof, Groth16Bn254Proof, InnerSC, PlonkBn254Proof};
+use zkm_prover::{
+    utils::koalabears_to_bn254, CoreSC, DvSnarkBn254Proof, Groth16Bn254Proof, InnerSC,
+    PlonkBn254Proof,
+};
+use zkm_recursion_core::air::{RecursionPublicValues, RECURSIVE_PROOF_NUM_PV_ELTS};
 use zkm_stark::{MachineVerificationError, ShardProof};
 
 /// A proof generated with Ziren of a particular proof mode.
@@ -55,6 +61,29 @@ impl ZKMProofWithPublicValues {
             .map_err(Into::into)
     }
 
+    /// Returns the hash of the vkey the proof was generated for, formatted like
+    /// [`HashableKey::bytes32`](zkm_prover::HashableKey::bytes32).
+    ///
+    /// Core proofs and mock proofs do not commit to a vkey, so `None` is returned for them.
+    pub fn vkey_hash(&self) -> Option<String> {
+        let vkey_hash = match &self.proof {
+            ZKMProof::Compressed(proof) => {
+                let public_values = &proof.proof.public_values;
+                if public_values.len() < RECURSIVE_PROOF_NUM_PV_ELTS {
+                    return None;
+                }
+                let public_values: &RecursionPublicValues<_> = public_values.as_slice().borrow();
+                koalabears_to_bn254(&public_values.zkm_vk_digest).as_canonical_biguint()
+            }
+            ZKMProof::Plonk(PlonkBn254Proof { public_inputs, .. })
+            | ZKMProof::Groth16(Groth16Bn254Proof { public_inputs, .. }) => {
+                BigUint::from_str(&public_inputs[0]).ok()?
+            }
+            _ => return None,
+        };
+        Some(format!("0x{:0>64}", vkey_hash.to_str_radix(16)))
+    }
+
     /// Returns the raw proof as a string.
     pub fn raw(&self) -> String {
         match &self.proof {
@@ -176,6 +205,28 @@ mod tests {
         assert_eq!(mock_groth16_proof.bytes(), Vec::<u8>::new());
     }
 
+    #[test]
+    fn test_groth16_proof_vkey_hash() {
+        let groth16_proof = ZKMProofWithPublicValues {
+            proof: ZKMProof::Groth16(Groth16Bn254Proof {
+                encoded_proof: "".to_string(),
+                groth16_vkey_hash: [0; 32],
+                public_inputs: ["255".to_string(), "".to_string()],
+                raw_proof: "".to_string(),
+            }),
+            public_values: ZKMPublicValues::new(),
+            zkm_version: "".to_string(),
+        };
+        assert_eq!(groth16_proof.vkey_hash().unwrap(), format!("0x{:0>64}", "ff"));
+
+        let core_proof = ZKMProofWithPublicValues {
+            proof: ZKMProof::Core(vec![]),
+            public_values: ZKMPublicValues::new(),
+            zkm_version: "".to_string(),
+        };
+        assert_eq!(core_proof.vkey_hash(), None);
+    }
+
     #[test]
     #[should_panic(
         expected = "only Compressed (STARK), Plonk and Groth16 proofs are supported by bytes()"
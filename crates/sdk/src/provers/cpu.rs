@@ -1,6 +1,6 @@
 use anyhow::Result;
-use std::fs;
-use std::path::PathBuf;
+#[cfg(feature = "native-gnark")]
+use std::{fs, path::PathBuf};
 use zkm_core_executor::ZKMContext;
 use zkm_core_machine::io::ZKMStdin;
 use zkm_prover::ZKM_CIRCUIT_VERSION;
@@ -8,8 +8,8 @@ use zkm_prover::{components::DefaultProverComponents, ZKMProver};
 
 use crate::install::try_install_circuit_artifacts;
 use crate::{
-    provers::ProofOpts, Prover, ZKMProof, ZKMProofKind, ZKMProofWithPublicValues, ZKMProvingKey,
-    ZKMVerifyingKey,
+    provers::{check_cancelled, check_gnark_available, ProofOpts},
+    Prover, ZKMProof, ZKMProofKind, ZKMProofWithPublicValues, ZKMProvingKey, ZKMVerifyingKey,
 };
 
 use super::ProverType;
@@ -89,15 +89,30 @@ impl Prover<DefaultProverComponents> for CpuProver {
         kind: ZKMProofKind,
         _elf_id: Option<String>,
     ) -> Result<(ZKMProofWithPublicValues, u64)> {
+        check_gnark_available(kind)?;
         if kind == ZKMProofKind::CompressToGroth16 {
             return Ok((self.compress_to_groth16(stdin, opts)?, 0));
         }
 
         let program = self.prover.get_program(&pk.elf).unwrap();
+        let cancellation_token = context.cancellation_token.clone();
+        let progress = context.progress.clone();
 
         // Generate the core proof.
         let proof: zkm_prover::ZKMProofWithMetadata<zkm_prover::ZKMCoreProofData> =
-            self.prover.prove_core(&pk.pk, program, &stdin, opts.zkm_prover_opts, context)?;
+            if let Some(checkpoint_dir) = opts.checkpoint_dir.as_deref() {
+                self.prover.prove_core_resumable(
+                    &pk.pk,
+                    program,
+                    &stdin,
+                    opts.zkm_prover_opts,
+                    context,
+                    checkpoint_dir,
+                )
+            } else {
+                self.prover.prove_core(&pk.pk, program, &stdin, opts.zkm_prover_opts, context)
+            }
+            .map_err(|err| err.symbolize(&pk.elf))?;
         let cycles = proof.cycles;
         if kind == ZKMProofKind::Core {
             return Ok((
@@ -115,8 +130,15 @@ impl Prover<DefaultProverComponents> for CpuProver {
         let public_values = proof.public_values.clone();
 
         // Generate the compressed proof.
-        let reduce_proof =
-            self.prover.compress(&pk.vk, proof, deferred_proofs, opts.zkm_prover_opts)?;
+        check_cancelled(cancellation_token.as_ref())?;
+        let reduce_proof = self.prover.compress_with_progress(
+            &pk.vk,
+            proof,
+            deferred_proofs,
+            opts.zkm_prover_opts,
+            progress.as_ref(),
+            cancellation_token.as_ref(),
+        )?;
         if kind == ZKMProofKind::Compressed {
             return Ok((
                 ZKMProofWithPublicValues {
@@ -129,11 +151,15 @@ impl Prover<DefaultProverComponents> for CpuProver {
         }
 
         // Generate the shrink proof.
+        check_cancelled(cancellation_token.as_ref())?;
         let compress_proof = self.prover.shrink(reduce_proof, opts.zkm_prover_opts)?;
 
         // Generate the wrap proof.
+        check_cancelled(cancellation_token.as_ref())?;
         let outer_proof = self.prover.wrap_bn254(compress_proof, opts.zkm_prover_opts)?;
+        check_cancelled(cancellation_token.as_ref())?;
 
+        #[cfg(feature = "native-gnark")]
         if kind == ZKMProofKind::Plonk {
             let plonk_bn254_artifacts = if zkm_prover::build::zkm_dev_mode() {
                 zkm_prover::build::try_build_plonk_bn254_artifacts_dev(
@@ -153,7 +179,9 @@ impl Prover<DefaultProverComponents> for CpuProver {
                 },
                 cycles,
             ));
-        } else if kind == ZKMProofKind::Groth16 {
+        }
+
+        if kind == ZKMProofKind::Groth16 {
             let groth16_bn254_artifacts = if zkm_prover::build::zkm_dev_mode() {
                 zkm_prover::build::try_build_groth16_bn254_artifacts_dev(
                     &outer_proof.vk,
@@ -172,7 +200,10 @@ impl Prover<DefaultProverComponents> for CpuProver {
                 },
                 cycles,
             ));
-        } else if kind == ZKMProofKind::DvSnark {
+        }
+
+        #[cfg(feature = "native-gnark")]
+        if kind == ZKMProofKind::DvSnark {
             // Get the store dvsnark assets dir via the environment variable.
             let store_dir: PathBuf = std::env::var("DVSNARK_DIR")
                 .map(PathBuf::from)
@@ -1,6 +1,6 @@
 use anyhow::Result;
-use std::fs;
-use std::path::PathBuf;
+#[cfg(feature = "native-gnark")]
+use std::{fs, path::PathBuf};
 use tonic::async_trait;
 use zkm_core_executor::ZKMContext;
 use zkm_core_machine::io::ZKMStdin;
@@ -10,8 +10,8 @@ use zkm_prover::{components::DefaultProverComponents, ZKMProver};
 
 use crate::install::try_install_circuit_artifacts;
 use crate::{
-    provers::ProofOpts, Prover, ZKMProof, ZKMProofKind, ZKMProofWithPublicValues, ZKMProvingKey,
-    ZKMVerifyingKey,
+    provers::{check_cancelled, check_gnark_available, ProofOpts},
+    Prover, ZKMProof, ZKMProofKind, ZKMProofWithPublicValues, ZKMProvingKey, ZKMVerifyingKey,
 };
 
 use super::ProverType;
@@ -41,6 +41,7 @@ impl CudaProver {
         stdin: &ZKMStdin,
         kind: ZKMProofKind,
     ) -> Result<(ZKMProofWithPublicValues, u64)> {
+        check_gnark_available(kind)?;
         if kind == ZKMProofKind::CompressToGroth16 {
             return Ok((self.compress_to_groth16(stdin.clone())?, 0));
         }
@@ -77,6 +78,7 @@ impl CudaProver {
         // Generate the wrap proof.
         let outer_proof = self.cuda_prover.wrap_bn254(compress_proof)?;
 
+        #[cfg(feature = "native-gnark")]
         if kind == ZKMProofKind::Plonk {
             let plonk_bn254_artifacts = if zkm_prover::build::zkm_dev_mode() {
                 zkm_prover::build::try_build_plonk_bn254_artifacts_dev(
@@ -93,7 +95,9 @@ impl CudaProver {
                 zkm_version: self.version().to_string(),
             };
             return Ok((proof_with_pv, cycles));
-        } else if kind == ZKMProofKind::Groth16 {
+        }
+
+        if kind == ZKMProofKind::Groth16 {
             let groth16_bn254_artifacts = if zkm_prover::build::zkm_dev_mode() {
                 zkm_prover::build::try_build_groth16_bn254_artifacts_dev(
                     &outer_proof.vk,
@@ -110,7 +114,10 @@ impl CudaProver {
                 zkm_version: self.version().to_string(),
             };
             return Ok((proof_with_pv, cycles));
-        } else if kind == ZKMProofKind::DvSnark {
+        }
+
+        #[cfg(feature = "native-gnark")]
+        if kind == ZKMProofKind::DvSnark {
             // Get the store dvsnark assets dir via the environment variable.
             let store_dir: PathBuf = std::env::var("DVSNARK_DIR")
                 .map(PathBuf::from)
@@ -189,10 +196,12 @@ impl Prover<DefaultProverComponents> for CudaProver {
         pk: &ZKMProvingKey,
         stdin: ZKMStdin,
         _opts: ProofOpts,
-        _context: ZKMContext<'a>,
+        context: ZKMContext<'a>,
         kind: ZKMProofKind,
         _elf_id: Option<String>,
     ) -> Result<(ZKMProofWithPublicValues, u64)> {
+        // The GPU prover can only be stopped before it starts.
+        check_cancelled(context.cancellation_token.as_ref())?;
         self.prove_with_cycles(pk, &stdin, kind)
     }
 }
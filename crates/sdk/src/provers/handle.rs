@@ -0,0 +1,77 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use anyhow::{anyhow, Result};
+use futures::channel::oneshot;
+use zkm_core_executor::{CancellationToken, ExecutionError};
+
+/// A proof, or an execution, running in the background.
+///
+/// The handle is a [`Future`] resolving to the result of the work. Calling [`Self::cancel`] stops
+/// the work at its next cancellation point, after which the handle resolves to an error.
+///
+/// Dropping the handle detaches the work instead of cancelling it.
+pub struct ProverHandle<T> {
+    future: Pin<Box<dyn Future<Output = Result<T>> + Send>>,
+    cancellation_token: CancellationToken,
+}
+
+impl<T: Send + 'static> ProverHandle<T> {
+    /// Creates a handle to the work driven by `future`, which stops once `cancellation_token` is
+    /// cancelled.
+    pub fn new(
+        future: impl Future<Output = Result<T>> + Send + 'static,
+        cancellation_token: CancellationToken,
+    ) -> Self {
+        Self { future: Box::pin(future), cancellation_token }
+    }
+
+    /// Runs the blocking `work` on a new thread.
+    pub(crate) fn spawn_blocking(
+        cancellation_token: CancellationToken,
+        work: impl FnOnce() -> Result<T> + Send + 'static,
+    ) -> Self {
+        let (tx, rx) = oneshot::channel();
+        std::thread::spawn(move || {
+            // The receiver is gone if the handle was dropped.
+            let _ = tx.send(work());
+        });
+        Self::new(
+            async move { rx.await.map_err(|_| anyhow!("the prover thread panicked"))? },
+            cancellation_token,
+        )
+    }
+}
+
+impl<T> ProverHandle<T> {
+    /// Requests the cancellation of the work.
+    pub fn cancel(&self) {
+        self.cancellation_token.cancel();
+    }
+
+    /// The token cancelling the work, to share with other threads.
+    pub fn cancellation_token(&self) -> &CancellationToken {
+        &self.cancellation_token
+    }
+}
+
+impl<T> Future for ProverHandle<T> {
+    type Output = Result<T>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.future.as_mut().poll(cx)
+    }
+}
+
+/// Returns [`ExecutionError::Cancelled`] if `cancellation_token` was cancelled.
+///
+/// Provers call this between the stages of a proof.
+pub(crate) fn check_cancelled(cancellation_token: Option<&CancellationToken>) -> Result<()> {
+    if cancellation_token.is_some_and(CancellationToken::is_cancelled) {
+        return Err(ExecutionError::Cancelled().into());
+    }
+    Ok(())
+}
@@ -1,28 +1,35 @@
 mod cpu;
 mod cuda;
+mod handle;
 mod mock;
 
 pub use cpu::CpuProver;
 pub use cuda::CudaProver;
+pub(crate) use handle::check_cancelled;
+pub use handle::ProverHandle;
 pub use mock::MockProver;
 
 use itertools::Itertools;
 use p3_field::PrimeField32;
 use std::borrow::Borrow;
+use std::path::PathBuf;
+use std::sync::Arc;
 use std::time::Duration;
 
 use anyhow::Result;
 use strum_macros::EnumString;
 use thiserror::Error;
 use zkm_core_executor::ExecutionReport;
-use zkm_core_executor::ZKMContext;
+use zkm_core_executor::{CancellationToken, ZKMContext};
 use zkm_core_machine::{io::ZKMStdin, ZKM_CIRCUIT_VERSION};
 use zkm_primitives::io::ZKMPublicValues;
 use zkm_prover::{
     components::{DefaultProverComponents, ZKMProverComponents},
     CoreSC, InnerSC, ZKMCoreProofData, ZKMProver, ZKMProvingKey, ZKMVerifyingKey,
 };
-use zkm_stark::{air::PublicValues, MachineVerificationError, Word, ZKMProverOpts};
+use zkm_stark::{
+    air::PublicValues, FriParameters, MachineVerificationError, SecurityLevel, Word, ZKMProverOpts,
+};
 
 use crate::install::try_install_circuit_artifacts;
 use crate::ProverClient;
@@ -44,6 +51,8 @@ pub struct ProofOpts {
     pub zkm_prover_opts: ZKMProverOpts,
     /// Optional timeout duration for proof generation.
     pub timeout: Option<Duration>,
+    /// Optional directory to save the core proving progress to, and resume it from.
+    pub checkpoint_dir: Option<PathBuf>,
 }
 
 #[derive(Error, Debug)]
@@ -60,6 +69,21 @@ pub enum ZKMVerificationError {
     Plonk(anyhow::Error),
     #[error("Groth16 verification error: {0}")]
     Groth16(anyhow::Error),
+    #[error("The FRI parameters {0:?} do not reach the security level {1:?}")]
+    InsufficientSecurity(FriParameters, SecurityLevel),
+}
+
+/// Fails if proofs of `kind` are proven with gnark and the SDK was built without it, so that the
+/// proof is not started.
+pub(crate) fn check_gnark_available(kind: ZKMProofKind) -> Result<()> {
+    if !cfg!(feature = "native-gnark")
+        && matches!(kind, ZKMProofKind::Plonk | ZKMProofKind::DvSnark)
+    {
+        anyhow::bail!(
+            "{kind:?} proofs are proven with gnark, which needs the `native-gnark` feature"
+        );
+    }
+    Ok(())
 }
 
 /// An implementation of [crate::ProverClient].
@@ -124,6 +148,48 @@ pub trait Prover<C: ZKMProverComponents>: Send + Sync {
         elf_id: Option<String>,
     ) -> Result<(ZKMProofWithPublicValues, u64)>;
 
+    /// Prove the execution of a MIPS ELF on a background thread, according to the given proof mode.
+    ///
+    /// The returned handle resolves to the proof and the cycle count, and cancels the proof with
+    /// the cancellation token of `context`, which is created if the context has none.
+    fn prove_async(
+        self: Arc<Self>,
+        pk: Arc<ZKMProvingKey>,
+        stdin: ZKMStdin,
+        opts: ProofOpts,
+        mut context: ZKMContext<'static>,
+        kind: ZKMProofKind,
+    ) -> ProverHandle<(ZKMProofWithPublicValues, u64)>
+    where
+        Self: 'static,
+    {
+        let cancellation_token =
+            context.cancellation_token.get_or_insert_with(CancellationToken::new).clone();
+        ProverHandle::spawn_blocking(cancellation_token, move || {
+            self.prove_impl(&pk, stdin, opts, context.shorten(), kind, None)
+        })
+    }
+
+    /// Executes the program on the given input on a background thread.
+    ///
+    /// The returned handle cancels the execution with the cancellation token of `context`, which
+    /// is created if the context has none.
+    fn execute_async(
+        self: Arc<Self>,
+        elf: Arc<[u8]>,
+        stdin: ZKMStdin,
+        mut context: ZKMContext<'static>,
+    ) -> ProverHandle<(ZKMPublicValues, ExecutionReport)>
+    where
+        Self: 'static,
+    {
+        let cancellation_token =
+            context.cancellation_token.get_or_insert_with(CancellationToken::new).clone();
+        ProverHandle::spawn_blocking(cancellation_token, move || {
+            Ok(self.zkm_prover().execute(&elf, &stdin, context.shorten())?)
+        })
+    }
+
     /// Verify that a Ziren proof is valid given its vkey and metadata.
     /// For Plonk proofs, verifies that the public inputs of the PlonkBn254 proof match
     /// the hash of the VK and the committed public values of the ZKMProofWithPublicValues.
@@ -185,6 +251,7 @@ pub trait Prover<C: ZKMProverComponents>: Send + Sync {
                     .verify_compressed(proof, vkey)
                     .map_err(ZKMVerificationError::Recursion)
             }
+            #[cfg(feature = "native-gnark")]
             ZKMProof::Plonk(proof) => self
                 .zkm_prover()
                 .verify_plonk_bn254(
@@ -198,6 +265,10 @@ pub trait Prover<C: ZKMProverComponents>: Send + Sync {
                     },
                 )
                 .map_err(ZKMVerificationError::Plonk),
+            #[cfg(not(feature = "native-gnark"))]
+            ZKMProof::Plonk(_) => Err(ZKMVerificationError::Plonk(anyhow::anyhow!(
+                "PLONK proofs are verified with gnark, which needs the `native-gnark` feature"
+            ))),
             ZKMProof::Groth16(proof) => self
                 .zkm_prover()
                 .verify_groth16_bn254(
@@ -214,6 +285,39 @@ pub trait Prover<C: ZKMProverComponents>: Send + Sync {
             _ => unreachable!(),
         }
     }
+
+    /// Verify a Ziren proof like [`Self::verify`], rejecting it if the FRI parameters of the
+    /// proofs it is built from do not reach `level`.
+    ///
+    /// The parameters are those the verifier checks the proofs with, not the ones recorded in
+    /// the vkey, which only have to match the core parameters of the verifier.
+    fn verify_with_security_level(
+        &self,
+        bundle: &ZKMProofWithPublicValues,
+        vkey: &ZKMVerifyingKey,
+        level: SecurityLevel,
+    ) -> Result<(), ZKMVerificationError> {
+        let prover = self.zkm_prover();
+        let core = prover.core_fri_parameters();
+        if vkey.fri_parameters != core {
+            return Err(ZKMVerificationError::Core(
+                MachineVerificationError::InvalidVerificationKey,
+            ));
+        }
+
+        let compress = prover.compress_fri_parameters();
+        let fri_parameters = match &bundle.proof {
+            ZKMProof::Core(_) => vec![core],
+            ZKMProof::Compressed(_) => vec![core, compress],
+            _ => vec![core, compress, prover.shrink_fri_parameters(), prover.wrap_fri_parameters()],
+        };
+        if let Some(weakest) =
+            fri_parameters.into_iter().find(|parameters| !parameters.satisfies(level))
+        {
+            return Err(ZKMVerificationError::InsufficientSecurity(weakest, level));
+        }
+        self.verify(bundle, vkey)
+    }
 }
 
 impl Prover<DefaultProverComponents> for ProverClient {
@@ -2,9 +2,16 @@
 //!
 //! A collection of utilities for the Ziren SDK.
 
+use std::{
+    fs::File,
+    io::BufWriter,
+    path::{Path, PathBuf},
+};
+
 use alloy_signer::k256::sha2::{Digest, Sha256};
 use p3_field::{FieldAlgebra, PrimeField};
 use p3_koala_bear::KoalaBear;
+use zkm_core_executor::Profile;
 use zkm_core_machine::io::ZKMStdin;
 pub use zkm_core_machine::utils::setup_logger;
 use zkm_prover::utils::koalabear_bytes_to_bn254;
@@ -19,6 +26,26 @@ pub(crate) fn zkm_dump(elf: &[u8], stdin: &ZKMStdin) {
     }
 }
 
+/// The directory to write execution profiles to, if `ZKM_PROFILE` is set.
+pub(crate) fn zkm_profile_dir() -> Option<PathBuf> {
+    std::env::var_os("ZKM_PROFILE").filter(|dir| !dir.is_empty()).map(PathBuf::from)
+}
+
+/// Write a profile to `dir` as folded stacks of cycles and precompile costs, and as a
+/// speedscope profile.
+pub(crate) fn write_profile(dir: &Path, profile: &Profile) -> std::io::Result<()> {
+    std::fs::create_dir_all(dir)?;
+    profile.write_folded(BufWriter::new(File::create(dir.join("cycles.folded"))?))?;
+    profile
+        .write_precompiles_folded(BufWriter::new(File::create(dir.join("precompiles.folded"))?))?;
+    profile.write_speedscope(
+        BufWriter::new(File::create(dir.join("profile.speedscope.json"))?),
+        "guest",
+    )?;
+    tracing::info!("wrote execution profile to {}", dir.display());
+    Ok(())
+}
+
 /// Utility method for blocking on an async function.
 ///
 /// If we're already in a tokio runtime, we'll block in place. Otherwise, we'll create a new
@@ -1,6 +1,6 @@
 #![allow(missing_docs)]
 
-use crate::{Com, StarkGenericConfig, ZeroCommitment};
+use crate::{Com, FriParameters, StarkGenericConfig, ZeroCommitment};
 use p3_challenger::DuplexChallenger;
 use p3_commit::ExtensionMmcs;
 use p3_dft::Radix2DitParallel;
@@ -56,26 +56,18 @@ pub fn zkm_fri_config() -> FriConfig<InnerChallengeMmcs> {
     let hash = InnerHash::new(perm.clone());
     let compress = InnerCompress::new(perm.clone());
     let challenge_mmcs = InnerChallengeMmcs::new(InnerValMmcs::new(hash, compress));
-    let num_queries = match std::env::var("FRI_QUERIES") {
-        Ok(value) => value.parse().unwrap(),
-        Err(_) => 84,
-    };
-    FriConfig { log_blowup: 1, num_queries, proof_of_work_bits: 16, mmcs: challenge_mmcs }
+    FriParameters::CORE.fri_config(challenge_mmcs)
 }
 
 /// The FRI config for inner recursion.
-/// This targets by default 100 bits of security.
+/// This targets 100 bits of conjectured security.
 #[must_use]
 pub fn inner_fri_config() -> FriConfig<InnerChallengeMmcs> {
     let perm = inner_perm();
     let hash = InnerHash::new(perm.clone());
     let compress = InnerCompress::new(perm.clone());
     let challenge_mmcs = InnerChallengeMmcs::new(InnerValMmcs::new(hash, compress));
-    let num_queries = match std::env::var("FRI_QUERIES") {
-        Ok(value) => value.parse().unwrap(),
-        Err(_) => 84,
-    };
-    FriConfig { log_blowup: 1, num_queries, proof_of_work_bits: 16, mmcs: challenge_mmcs }
+    FriParameters::CORE.fri_config(challenge_mmcs)
 }
 
 /// The recursion config used for recursive reduce circuit.
@@ -157,19 +149,27 @@ pub mod koala_bear_poseidon2 {
     use p3_dft::Radix2DitParallel;
     use p3_field::{extension::BinomialExtensionField, Field, FieldAlgebra};
     use p3_fri::{FriConfig, TwoAdicFriPcs};
-    use p3_koala_bear::{KoalaBear, Poseidon2KoalaBear};
+    use p3_koala_bear::KoalaBear;
+    #[cfg(not(target_os = "zkvm"))]
+    use p3_koala_bear::Poseidon2KoalaBear;
     use p3_merkle_tree::MerkleTreeMmcs;
+    #[cfg(not(target_os = "zkvm"))]
     use p3_poseidon2::ExternalLayerConstants;
     use p3_symmetric::{Hash, PaddingFreeSponge, TruncatedPermutation};
     use serde::{Deserialize, Serialize};
+    #[cfg(not(target_os = "zkvm"))]
     use zkm_primitives::RC_16_30;
 
-    use crate::{Com, StarkGenericConfig, ZeroCommitment, DIGEST_SIZE};
+    use crate::{Com, FriParameters, StarkGenericConfig, ZeroCommitment, DIGEST_SIZE};
 
     pub type Val = KoalaBear;
     pub type Challenge = BinomialExtensionField<Val, 4>;
 
+    #[cfg(not(target_os = "zkvm"))]
     pub type Perm = Poseidon2KoalaBear<16>;
+    /// Inside the zkVM, the permutation runs on the `POSEIDON2_PERMUTE` precompile.
+    #[cfg(target_os = "zkvm")]
+    pub type Perm = Poseidon2KoalaBearPrecompile;
     pub type MyHash = PaddingFreeSponge<Perm, 16, 8, DIGEST_SIZE>;
     pub type DigestHash = Hash<Val, Val, DIGEST_SIZE>;
     pub type MyCompress = TruncatedPermutation<Perm, 2, 8, 16>;
@@ -181,6 +181,7 @@ pub mod koala_bear_poseidon2 {
     type Pcs = TwoAdicFriPcs<Val, Dft, ValMmcs, ChallengeMmcs>;
 
     #[must_use]
+    #[cfg(not(target_os = "zkvm"))]
     pub fn my_perm() -> Perm {
         const ROUNDS_F: usize = 8;
         const ROUNDS_P: usize = 13;
@@ -199,50 +200,59 @@ pub mod koala_bear_poseidon2 {
     }
 
     #[must_use]
-    /// This targets by default 100 bits of security.
+    #[cfg(target_os = "zkvm")]
+    pub fn my_perm() -> Perm {
+        Poseidon2KoalaBearPrecompile
+    }
+
+    /// The Poseidon2 permutation of [`my_perm`], computed by the `POSEIDON2_PERMUTE` precompile.
+    ///
+    /// The precompile takes the state as canonical `u32` words, while field elements are stored in
+    /// Montgomery form, so the state is converted both ways around each call.
+    #[cfg(target_os = "zkvm")]
+    #[derive(Clone, Copy, Debug, Default)]
+    pub struct Poseidon2KoalaBearPrecompile;
+
+    #[cfg(target_os = "zkvm")]
+    impl p3_symmetric::Permutation<[Val; 16]> for Poseidon2KoalaBearPrecompile {
+        fn permute_mut(&self, input: &mut [Val; 16]) {
+            use p3_field::PrimeField32;
+
+            let mut state = input.map(|x| x.as_canonical_u32());
+            zkm_zkvm::syscalls::syscall_poseidon2_permute(&mut state);
+            *input = state.map(Val::from_canonical_u32);
+        }
+    }
+
+    #[cfg(target_os = "zkvm")]
+    impl p3_symmetric::CryptographicPermutation<[Val; 16]> for Poseidon2KoalaBearPrecompile {}
+
+    #[must_use]
+    /// This targets 100 bits of conjectured security.
     pub fn default_fri_config() -> FriConfig<ChallengeMmcs> {
-        let perm = my_perm();
-        let hash = MyHash::new(perm.clone());
-        let compress = MyCompress::new(perm.clone());
-        let challenge_mmcs = ChallengeMmcs::new(ValMmcs::new(hash, compress));
-        let num_queries = match std::env::var("FRI_QUERIES") {
-            Ok(value) => value.parse().unwrap(),
-            Err(_) => 84,
-        };
-        FriConfig { log_blowup: 1, num_queries, proof_of_work_bits: 16, mmcs: challenge_mmcs }
+        fri_config(FriParameters::CORE)
     }
 
     #[must_use]
-    /// This targets by default 100 bits of security.
+    /// This targets 100 bits of conjectured security.
     pub fn compressed_fri_config() -> FriConfig<ChallengeMmcs> {
-        let perm = my_perm();
-        let hash = MyHash::new(perm.clone());
-        let compress = MyCompress::new(perm.clone());
-        let challenge_mmcs = ChallengeMmcs::new(ValMmcs::new(hash, compress));
-        let num_queries = match std::env::var("FRI_QUERIES") {
-            Ok(value) => value.parse().unwrap(),
-            Err(_) => 42,
-        };
-        FriConfig { log_blowup: 2, num_queries, proof_of_work_bits: 16, mmcs: challenge_mmcs }
+        fri_config(FriParameters::COMPRESSED)
     }
 
     #[must_use]
-    /// This targets by default 100 bits of security.
+    /// This targets 100 bits of conjectured security.
     pub fn ultra_compressed_fri_config() -> FriConfig<ChallengeMmcs> {
+        fri_config(FriParameters::ULTRA_COMPRESSED)
+    }
+
+    /// The FRI config with the given parameters.
+    #[must_use]
+    pub fn fri_config(parameters: FriParameters) -> FriConfig<ChallengeMmcs> {
         let perm = my_perm();
         let hash = MyHash::new(perm.clone());
         let compress = MyCompress::new(perm.clone());
         let challenge_mmcs = ChallengeMmcs::new(ValMmcs::new(hash, compress));
-        let num_queries = match std::env::var("FRI_QUERIES") {
-            Ok(value) => value.parse().unwrap(),
-            Err(_) => 28,
-        };
-        FriConfig { log_blowup: 3, num_queries, proof_of_work_bits: 16, mmcs: challenge_mmcs }
-    }
-
-    enum KoalaBearPoseidon2Type {
-        Default,
-        Compressed,
+        parameters.fri_config(challenge_mmcs)
     }
 
     #[derive(Deserialize)]
@@ -250,53 +260,47 @@ pub mod koala_bear_poseidon2 {
     pub struct KoalaBearPoseidon2 {
         pub perm: Perm,
         pcs: Pcs,
-        config_type: KoalaBearPoseidon2Type,
+        fri_parameters: FriParameters,
     }
 
     impl KoalaBearPoseidon2 {
         #[must_use]
         pub fn new() -> Self {
-            let perm = my_perm();
-            let hash = MyHash::new(perm.clone());
-            let compress = MyCompress::new(perm.clone());
-            let val_mmcs = ValMmcs::new(hash, compress);
-            let dft = Dft::default();
-            let fri_config = default_fri_config();
-            let pcs = Pcs::new(dft, val_mmcs, fri_config);
-            Self { pcs, perm, config_type: KoalaBearPoseidon2Type::Default }
+            Self::with_fri_parameters(FriParameters::CORE)
         }
 
         #[must_use]
         pub fn compressed() -> Self {
-            let perm = my_perm();
-            let hash = MyHash::new(perm.clone());
-            let compress = MyCompress::new(perm.clone());
-            let val_mmcs = ValMmcs::new(hash, compress);
-            let dft = Dft::default();
-            let fri_config = compressed_fri_config();
-            let pcs = Pcs::new(dft, val_mmcs, fri_config);
-            Self { pcs, perm, config_type: KoalaBearPoseidon2Type::Compressed }
+            Self::with_fri_parameters(FriParameters::COMPRESSED)
         }
 
         #[must_use]
         pub fn ultra_compressed() -> Self {
+            Self::with_fri_parameters(FriParameters::ULTRA_COMPRESSED)
+        }
+
+        /// The configuration proving with the given FRI parameters.
+        #[must_use]
+        pub fn with_fri_parameters(fri_parameters: FriParameters) -> Self {
             let perm = my_perm();
             let hash = MyHash::new(perm.clone());
             let compress = MyCompress::new(perm.clone());
             let val_mmcs = ValMmcs::new(hash, compress);
             let dft = Dft::default();
-            let fri_config = ultra_compressed_fri_config();
-            let pcs = Pcs::new(dft, val_mmcs, fri_config);
-            Self { pcs, perm, config_type: KoalaBearPoseidon2Type::Compressed }
+            let pcs = Pcs::new(dft, val_mmcs, fri_config(fri_parameters));
+            Self { pcs, perm, fri_parameters }
+        }
+
+        /// The FRI parameters of the configuration.
+        #[must_use]
+        pub fn fri_parameters(&self) -> FriParameters {
+            self.fri_parameters
         }
     }
 
     impl Clone for KoalaBearPoseidon2 {
         fn clone(&self) -> Self {
-            match self.config_type {
-                KoalaBearPoseidon2Type::Default => Self::new(),
-                KoalaBearPoseidon2Type::Compressed => Self::compressed(),
-            }
+            Self::with_fri_parameters(self.fri_parameters)
         }
     }
 
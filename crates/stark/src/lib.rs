@@ -18,6 +18,7 @@ mod proof;
 mod prover;
 mod quotient;
 mod record;
+mod security;
 pub mod septic_curve;
 pub mod septic_digest;
 pub mod septic_extension;
@@ -43,6 +44,7 @@ pub use proof::*;
 pub use prover::*;
 pub use quotient::*;
 pub use record::*;
+pub use security::*;
 pub use types::*;
 pub use verifier::*;
 pub use word::*;
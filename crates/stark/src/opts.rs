@@ -3,6 +3,8 @@ use std::env;
 use serde::{Deserialize, Serialize};
 use sysinfo::System;
 
+use crate::{FriParameters, SecurityLevel};
+
 const MAX_SHARD_SIZE: usize = 1 << 21;
 const RECURSION_MAX_SHARD_SIZE: usize = 1 << 21;
 const MAX_SHARD_BATCH_SIZE: usize = 8;
@@ -20,15 +22,37 @@ pub struct ZKMProverOpts {
     pub core_opts: ZKMCoreOpts,
     /// Options for the recursion prover.
     pub recursion_opts: ZKMCoreOpts,
+    /// The minimum security of the proofs: proving fails if the FRI parameters of the prover do
+    /// not reach it.
+    #[serde(default)]
+    pub security_level: SecurityLevel,
+    /// The FRI parameters of the core proofs: proving fails if the prover was set up with other
+    /// parameters.
+    #[serde(default)]
+    pub fri_parameters: FriParameters,
 }
 
 impl Default for ZKMProverOpts {
     fn default() -> Self {
-        Self { core_opts: ZKMCoreOpts::default(), recursion_opts: ZKMCoreOpts::recursion() }
+        Self {
+            core_opts: ZKMCoreOpts::default(),
+            recursion_opts: ZKMCoreOpts::recursion(),
+            security_level: SecurityLevel::default(),
+            fri_parameters: FriParameters::default(),
+        }
     }
 }
 
 impl ZKMProverOpts {
+    /// Require the proofs to reach `level`, with the core FRI parameters derived from it by
+    /// [`FriParameters::for_security_level`].
+    #[must_use]
+    pub fn with_security_level(mut self, level: SecurityLevel) -> Self {
+        self.security_level = level;
+        self.fri_parameters = FriParameters::for_security_level(level);
+        self
+    }
+
     /// Get the default prover options.
     #[must_use]
     pub fn auto() -> Self {
@@ -68,7 +92,13 @@ impl ZKMProverOpts {
         opts.core_opts.split_opts.keccak /= divisor;
         opts.core_opts.split_opts.sha_extend /= divisor;
         opts.core_opts.split_opts.sha_compress /= divisor;
+        opts.core_opts.split_opts.sha512_extend /= divisor;
+        opts.core_opts.split_opts.sha512_compress /= divisor;
+        opts.core_opts.split_opts.blake3_compress_inner /= divisor;
+        opts.core_opts.split_opts.blake2b_compress_inner /= divisor;
+        opts.core_opts.split_opts.weierstrass_scalar_mul /= divisor;
         opts.core_opts.split_opts.boolean_circuit_garble /= divisor;
+        opts.core_opts.split_opts.sys_file /= divisor;
         opts.core_opts.split_opts.memory /= divisor;
 
         opts.recursion_opts.shard_batch_size = 2;
@@ -176,7 +206,13 @@ impl Default for ZKMCoreOpts {
         opts.split_opts.keccak /= divisor;
         opts.split_opts.sha_extend /= divisor;
         opts.split_opts.sha_compress /= divisor;
+        opts.split_opts.sha512_extend /= divisor;
+        opts.split_opts.sha512_compress /= divisor;
+        opts.split_opts.blake3_compress_inner /= divisor;
+        opts.split_opts.blake2b_compress_inner /= divisor;
+        opts.split_opts.weierstrass_scalar_mul /= divisor;
         opts.split_opts.boolean_circuit_garble /= divisor;
+        opts.split_opts.sys_file /= divisor;
         opts.split_opts.memory /= divisor;
 
         opts
@@ -243,8 +279,20 @@ pub struct SplitOpts {
     pub sha_extend: usize,
     /// The threshold for sha compress events.
     pub sha_compress: usize,
+    /// The threshold for sha512 extend events.
+    pub sha512_extend: usize,
+    /// The threshold for sha512 compress events.
+    pub sha512_compress: usize,
+    /// The threshold for blake3 compress inner events.
+    pub blake3_compress_inner: usize,
+    /// The threshold for blake2b compress inner events.
+    pub blake2b_compress_inner: usize,
+    /// The threshold for weierstrass scalar mul events.
+    pub weierstrass_scalar_mul: usize,
     /// The threshold for Boolean Circuit Garble events
     pub boolean_circuit_garble: usize,
+    /// The threshold for virtual file events, in rows.
+    pub sys_file: usize,
     /// The threshold for memory events.
     pub memory: usize,
     /// The threshold for combining the memory init/finalize events in to the current shard in
@@ -261,7 +309,13 @@ impl SplitOpts {
             keccak: 8 * deferred_split_threshold / 24,
             sha_extend: 32 * deferred_split_threshold / 48,
             sha_compress: 32 * deferred_split_threshold / 80,
+            sha512_extend: 32 * deferred_split_threshold / 64,
+            sha512_compress: 32 * deferred_split_threshold / 96,
+            blake3_compress_inner: 32 * deferred_split_threshold / 56,
+            blake2b_compress_inner: 32 * deferred_split_threshold / 96,
+            weierstrass_scalar_mul: deferred_split_threshold / 256,
             boolean_circuit_garble: deferred_split_threshold / 8,
+            sys_file: deferred_split_threshold / 8,
             memory: 64 * deferred_split_threshold,
             combine_memory_threshold: 1 << 17,
         }
@@ -0,0 +1,228 @@
+use p3_fri::FriConfig;
+use serde::{Deserialize, Serialize};
+
+/// The number of proof-of-work bits ground before the FRI queries of the default parameters.
+pub const DEFAULT_PROOF_OF_WORK_BITS: usize = 16;
+
+/// The largest number of proof-of-work bits the calculator asks the prover to grind.
+pub const MAX_PROOF_OF_WORK_BITS: usize = 20;
+
+/// The largest number of queries the calculator picks before raising the blowup, which bounds
+/// the size of the proofs and of the recursion programs verifying them.
+pub const MAX_NUM_QUERIES: usize = 84;
+
+/// The largest logarithm of the blowup factor the calculator picks.
+pub const MAX_LOG_BLOWUP: usize = 4;
+
+/// The assumption under which the security of FRI is computed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SecurityAssumption {
+    /// The ethSTARK conjecture: every query contributes `log_blowup` bits.
+    #[default]
+    Conjectured,
+    /// The unique decoding bound: every query contributes `-log2((1 + rate) / 2)` bits.
+    Proven,
+}
+
+/// A target number of bits of security, under a [`SecurityAssumption`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SecurityLevel {
+    /// The number of bits of security.
+    pub bits: usize,
+    /// The assumption the bits are computed under.
+    pub assumption: SecurityAssumption,
+}
+
+impl SecurityLevel {
+    /// A level of `bits` bits of conjectured security.
+    #[must_use]
+    pub const fn conjectured(bits: usize) -> Self {
+        Self { bits, assumption: SecurityAssumption::Conjectured }
+    }
+
+    /// A level of `bits` bits of proven security.
+    #[must_use]
+    pub const fn proven(bits: usize) -> Self {
+        Self { bits, assumption: SecurityAssumption::Proven }
+    }
+}
+
+impl Default for SecurityLevel {
+    /// The 100 bits of conjectured security targeted by the default configurations.
+    fn default() -> Self {
+        Self::conjectured(100)
+    }
+}
+
+/// The parameters of FRI which determine the security of a proof.
+///
+/// The error terms depending on the size of the challenge field are ignored, as they are
+/// negligible for the degree 4 extension of KoalaBear.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct FriParameters {
+    /// The logarithm of the blowup factor, the inverse of the code rate.
+    pub log_blowup: usize,
+    /// The number of queries.
+    pub num_queries: usize,
+    /// The number of proof-of-work bits.
+    pub proof_of_work_bits: usize,
+}
+
+impl FriParameters {
+    /// The parameters of the core proofs and of the recursive proofs compressing them.
+    pub const CORE: Self =
+        Self { log_blowup: 1, num_queries: 84, proof_of_work_bits: DEFAULT_PROOF_OF_WORK_BITS };
+
+    /// The parameters of the shrink proofs.
+    pub const COMPRESSED: Self =
+        Self { log_blowup: 2, num_queries: 42, proof_of_work_bits: DEFAULT_PROOF_OF_WORK_BITS };
+
+    /// The parameters of the most compact proofs.
+    pub const ULTRA_COMPRESSED: Self =
+        Self { log_blowup: 3, num_queries: 28, proof_of_work_bits: DEFAULT_PROOF_OF_WORK_BITS };
+
+    /// The parameters reaching `level`.
+    ///
+    /// The proof of work covers the same share of the bits as in the default parameters, 16 of
+    /// 100, up to [`MAX_PROOF_OF_WORK_BITS`]. The blowup is the smallest one reaching the rest of
+    /// the bits with at most [`MAX_NUM_QUERIES`] queries, up to [`MAX_LOG_BLOWUP`], as the cost of
+    /// the prover grows with the blowup.
+    #[must_use]
+    pub fn for_security_level(level: SecurityLevel) -> Self {
+        (1..MAX_LOG_BLOWUP)
+            .map(|log_blowup| Self::for_security_level_with_blowup(level, log_blowup))
+            .find(|parameters| parameters.num_queries <= MAX_NUM_QUERIES)
+            .unwrap_or_else(|| Self::for_security_level_with_blowup(level, MAX_LOG_BLOWUP))
+    }
+
+    /// The parameters reaching `level` with the fewest queries for a blowup of `2^log_blowup`.
+    ///
+    /// The proof of work is chosen like in [`Self::for_security_level`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `log_blowup` is zero.
+    #[must_use]
+    pub fn for_security_level_with_blowup(level: SecurityLevel, log_blowup: usize) -> Self {
+        assert!(log_blowup > 0, "the blowup factor must be at least 2");
+        let proof_of_work_bits =
+            (level.bits * DEFAULT_PROOF_OF_WORK_BITS / 100).min(MAX_PROOF_OF_WORK_BITS);
+        let query_bits = query_security_bits(log_blowup, level.assumption);
+        let num_queries = ((level.bits - proof_of_work_bits) as f64 / query_bits).ceil() as usize;
+        Self { log_blowup, num_queries, proof_of_work_bits }
+    }
+
+    /// The number of bits of security of the parameters under `assumption`.
+    #[must_use]
+    pub fn security_bits(&self, assumption: SecurityAssumption) -> usize {
+        let query_bits = query_security_bits(self.log_blowup, assumption);
+        (self.num_queries as f64 * query_bits).floor() as usize + self.proof_of_work_bits
+    }
+
+    /// Whether the parameters reach `level`.
+    #[must_use]
+    pub fn satisfies(&self, level: SecurityLevel) -> bool {
+        self.security_bits(level.assumption) >= level.bits
+    }
+
+    /// The FRI config with these parameters, committing with `mmcs`.
+    #[must_use]
+    pub fn fri_config<M>(&self, mmcs: M) -> FriConfig<M> {
+        FriConfig {
+            log_blowup: self.log_blowup,
+            num_queries: self.num_queries,
+            proof_of_work_bits: self.proof_of_work_bits,
+            mmcs,
+        }
+    }
+}
+
+impl Default for FriParameters {
+    fn default() -> Self {
+        Self::CORE
+    }
+}
+
+/// The number of bits of security contributed by a single query.
+fn query_security_bits(log_blowup: usize, assumption: SecurityAssumption) -> f64 {
+    match assumption {
+        SecurityAssumption::Conjectured => log_blowup as f64,
+        SecurityAssumption::Proven => {
+            let rate = 2f64.powi(-(log_blowup as i32));
+            -((1.0 + rate) / 2.0).log2()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_parameters_reach_100_conjectured_bits() {
+        for parameters in
+            [FriParameters::CORE, FriParameters::COMPRESSED, FriParameters::ULTRA_COMPRESSED]
+        {
+            assert_eq!(parameters.security_bits(SecurityAssumption::Conjectured), 100);
+            assert!(parameters.satisfies(SecurityLevel::default()));
+            assert!(!parameters.satisfies(SecurityLevel::proven(100)));
+        }
+    }
+
+    #[test]
+    fn calculator_reaches_the_level() {
+        for log_blowup in 1..=MAX_LOG_BLOWUP {
+            for level in [SecurityLevel::conjectured(100), SecurityLevel::proven(80)] {
+                let parameters = FriParameters::for_security_level_with_blowup(level, log_blowup);
+                assert!(parameters.satisfies(level));
+                let fewer_queries =
+                    FriParameters { num_queries: parameters.num_queries - 1, ..parameters };
+                assert!(!fewer_queries.satisfies(level));
+            }
+        }
+        for (parameters, log_blowup) in [
+            (FriParameters::CORE, 1),
+            (FriParameters::COMPRESSED, 2),
+            (FriParameters::ULTRA_COMPRESSED, 3),
+        ] {
+            let derived =
+                FriParameters::for_security_level_with_blowup(SecurityLevel::default(), log_blowup);
+            assert_eq!(derived, parameters);
+        }
+    }
+
+    #[test]
+    fn calculator_derives_the_blowup_and_proof_of_work() {
+        assert_eq!(
+            FriParameters::for_security_level(SecurityLevel::default()),
+            FriParameters::CORE
+        );
+
+        // 128 conjectured bits need 108 queries with a blowup of 2, over the budget.
+        let parameters = FriParameters::for_security_level(SecurityLevel::conjectured(128));
+        assert_eq!(
+            parameters,
+            FriParameters { log_blowup: 2, num_queries: 54, proof_of_work_bits: 20 }
+        );
+
+        for level in [
+            SecurityLevel::conjectured(80),
+            SecurityLevel::conjectured(128),
+            SecurityLevel::proven(80),
+            SecurityLevel::proven(100),
+        ] {
+            let parameters = FriParameters::for_security_level(level);
+            assert!(parameters.satisfies(level));
+            assert!(parameters.proof_of_work_bits <= MAX_PROOF_OF_WORK_BITS);
+            assert!(
+                parameters.num_queries <= MAX_NUM_QUERIES
+                    || parameters.log_blowup == MAX_LOG_BLOWUP
+            );
+            if parameters.log_blowup > 1 {
+                let smaller_blowup =
+                    FriParameters::for_security_level_with_blowup(level, parameters.log_blowup - 1);
+                assert!(smaller_blowup.num_queries > MAX_NUM_QUERIES);
+            }
+        }
+    }
+}
@@ -0,0 +1,32 @@
+#![no_main]
+zkm_zkvm::entrypoint!(main);
+
+use hex_literal::hex;
+use zkm_lib::blake2b::blake2b;
+
+pub fn main() {
+    assert_eq!(
+        blake2b(b""),
+        hex!(
+            "786a02f742015903c6c6fd852552d272912f4740e15847618a86e217f71f5419"
+            "d25e1031afee585313896444934eb04b903a685b1448b755d56f701afe9be2ce"
+        )
+    );
+
+    assert_eq!(
+        blake2b(b"abc"),
+        hex!(
+            "ba80a53f981c4d0d6a2797b69f12f6e94c212f14685ac4b74b12bb6fdbffa2d1"
+            "7d87c5392aab792dc252d5de4533cc9518d38aa8dbf1925ab92386edd4009923"
+        )
+    );
+
+    // Two full blocks, followed by a partial final block.
+    assert_eq!(
+        blake2b(&[b'a'; 300]),
+        hex!(
+            "a2ff3040eda405b929c2fc2fd93e8add6ac3bb5369b679bae170ac6956863ca0"
+            "06285f132a868000fc3fae5bc696e5d17fe3fddfb4a342876c40451184742986"
+        )
+    );
+}
@@ -0,0 +1,23 @@
+#![no_main]
+zkm_zkvm::entrypoint!(main);
+
+use hex_literal::hex;
+use zkm_lib::blake3::blake3;
+
+pub fn main() {
+    assert_eq!(
+        blake3(b""),
+        hex!("af1349b9f5f9a1a6a0404dea36dcc9499bcb25c9adc112b7cc9a93cae41f3262")
+    );
+
+    assert_eq!(
+        blake3(b"abc"),
+        hex!("6437b3ac38465133ffb63b75273a8db548c558465d79db03fd359c6cd5bd9d85")
+    );
+
+    // Five chunks, merged into a tree of parent nodes.
+    assert_eq!(
+        blake3(&[b'a'; 5000]),
+        hex!("09d0d29a5f2dc69dff0809823ca867836c3a3cfb00e12df06d92e3f0f70629e9")
+    );
+}
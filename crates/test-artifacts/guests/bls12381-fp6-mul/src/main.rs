@@ -0,0 +1,102 @@
+#![no_main]
+zkm_zkvm::entrypoint!(main);
+
+use core::str::FromStr;
+use num_bigint::BigUint;
+use zkm_zkvm::syscalls::syscall_bls12381_fp6_mulmod;
+
+const MODULUS: &str =
+    "4002409555221667393417789825735904156556882819939007885332058136124031650490837864442687629129015664037894272559787";
+
+/// The real part of the non-residue `ξ = k + u`.
+const NON_RESIDUE_C0: u32 = 1;
+
+/// The number of `u32` words in a base field element.
+const WORDS: usize = 12;
+
+type Fp2 = [BigUint; 2];
+
+fn fp2_mul(a: &Fp2, b: &Fp2, modulus: &BigUint) -> Fp2 {
+    let c0 = ((&a[0] * &b[0]) % modulus + modulus - (&a[1] * &b[1]) % modulus) % modulus;
+    let c1 = (&a[0] * &b[1] + &a[1] * &b[0]) % modulus;
+    [c0, c1]
+}
+
+fn fp2_add(a: &Fp2, b: &Fp2, modulus: &BigUint) -> Fp2 {
+    [(&a[0] + &b[0]) % modulus, (&a[1] + &b[1]) % modulus]
+}
+
+fn fp2_mul_by_non_residue(a: &Fp2, modulus: &BigUint) -> Fp2 {
+    let c0 = (&a[0] * NON_RESIDUE_C0 + modulus - &a[1]) % modulus;
+    let c1 = (&a[1] * NON_RESIDUE_C0 + &a[0]) % modulus;
+    [c0, c1]
+}
+
+fn fp6_mul(a: &[Fp2; 3], b: &[Fp2; 3], modulus: &BigUint) -> [Fp2; 3] {
+    let m = |x: &Fp2, y: &Fp2| fp2_mul(x, y, modulus);
+    let c0 = fp2_add(
+        &m(&a[0], &b[0]),
+        &fp2_mul_by_non_residue(&fp2_add(&m(&a[1], &b[2]), &m(&a[2], &b[1]), modulus), modulus),
+        modulus,
+    );
+    let c1 = fp2_add(
+        &fp2_add(&m(&a[0], &b[1]), &m(&a[1], &b[0]), modulus),
+        &fp2_mul_by_non_residue(&m(&a[2], &b[2]), modulus),
+        modulus,
+    );
+    let c2 =
+        fp2_add(&fp2_add(&m(&a[0], &b[2]), &m(&a[1], &b[1]), modulus), &m(&a[2], &b[0]), modulus);
+    [c0, c1, c2]
+}
+
+/// Generates a pseudo-random field element with a xorshift generator.
+fn next_fp(state: &mut u32, modulus: &BigUint) -> BigUint {
+    let mut words = [0u32; WORDS];
+    for word in words.iter_mut() {
+        *state ^= *state << 13;
+        *state ^= *state >> 17;
+        *state ^= *state << 5;
+        *word = *state;
+    }
+    BigUint::from_slice(&words) % modulus
+}
+
+fn to_words(a: &[Fp2; 3]) -> [u32; 6 * WORDS] {
+    let mut words = [0u32; 6 * WORDS];
+    for (chunk, x) in words.chunks_mut(WORDS).zip(a.iter().flatten()) {
+        let digits = x.to_u32_digits();
+        chunk[..digits.len()].copy_from_slice(&digits);
+    }
+    words
+}
+
+pub fn main() {
+    let modulus = BigUint::from_str(MODULUS).unwrap();
+    let mut state = 0x9e3779b9;
+
+    for _ in 0..10 {
+        let a: [Fp2; 3] = core::array::from_fn(|_| {
+            [next_fp(&mut state, &modulus), next_fp(&mut state, &modulus)]
+        });
+        let b: [Fp2; 3] = core::array::from_fn(|_| {
+            [next_fp(&mut state, &modulus), next_fp(&mut state, &modulus)]
+        });
+
+        let mut x = to_words(&a);
+        let y = to_words(&b);
+        unsafe {
+            syscall_bls12381_fp6_mulmod(x.as_mut_ptr(), y.as_ptr());
+        }
+
+        assert_eq!(x, to_words(&fp6_mul(&a, &b, &modulus)));
+    }
+
+    // Squaring, where both operands point to the same memory.
+    let a: [Fp2; 3] =
+        core::array::from_fn(|_| [next_fp(&mut state, &modulus), next_fp(&mut state, &modulus)]);
+    let mut x = to_words(&a);
+    unsafe {
+        syscall_bls12381_fp6_mulmod(x.as_mut_ptr(), x.as_ptr());
+    }
+    assert_eq!(x, to_words(&fp6_mul(&a, &a, &modulus)));
+}
@@ -0,0 +1,97 @@
+#![no_main]
+zkm_zkvm::entrypoint!(main);
+
+use zkm_lib::bls12381::{pairing, pairing_check, Fp2, G1Affine, G2Affine};
+
+/// The BLS12-381 G1 generator.
+pub const G1: G1Affine = G1Affine::new_unchecked(
+    [
+        3676489403, 4214943754, 4185529071, 1817569343, 387689560, 2706258495, 2541009157,
+        3278408783, 1336519695, 647324556, 832034708, 401724327,
+    ],
+    [
+        1187375073, 212476713, 2726857444, 3493644100, 738505709, 14358731, 3587181302, 4243972245,
+        1948093156, 2694721773, 3819610353, 146011265,
+    ],
+);
+
+/// `[2]G1`.
+pub const G1_DOUBLE: G1Affine = G1Affine::new_unchecked(
+    [
+        700387150, 3281685589, 2401008706, 3801052603, 3277366826, 1133522186, 2435318053,
+        2535578416, 1353270540, 2282277099, 2420991814, 91409386,
+    ],
+    [
+        2037685544, 3129378841, 3440819483, 1280760276, 492820330, 4147886338, 2255545441,
+        582631115, 2006814264, 587048563, 2881909667, 376085900,
+    ],
+);
+
+/// The BLS12-381 G2 generator.
+pub const G2: G2Affine = G2Affine::new_unchecked(
+    Fp2::new(
+        [
+            3240213944, 3565180616, 2818948079, 195822374, 2061750647, 3025210212, 4198513410,
+            3336862420, 767889489, 638059815, 4035906193, 38445746,
+        ],
+        [
+            1560554366, 3853286661, 328490327, 860680466, 3699331145, 3050987963, 2569057818,
+            1500238032, 2284277605, 2108478368, 1383178080, 333458272,
+        ],
+    ),
+    Fp2::new(
+        [
+            146286593, 3784529030, 1001169545, 2453326284, 1365299500, 1833081449, 2361250727,
+            2919078826, 3660461338, 2362035654, 1920822801, 216388903,
+        ],
+        [
+            4032788926, 2863204191, 1558977953, 1060572455, 1462671787, 645173931, 2242339759,
+            3409848446, 734170009, 850186928, 782709964, 101106848,
+        ],
+    ),
+);
+
+/// `[2]G2`.
+pub const G2_DOUBLE: G2Affine = G2Affine::new_unchecked(
+    Fp2::new(
+        [
+            3089604691, 3377638090, 4083227512, 2180074251, 1291901870, 3790216734, 3095551502,
+            1002486165, 3430864262, 3526823703, 1473593513, 372790073,
+        ],
+        [
+            1203975543, 1635264652, 3044984618, 3335030518, 52303218, 1921062097, 79302772,
+            3607519913, 1930039887, 2672954951, 3253567346, 172941049,
+        ],
+    ),
+    Fp2::new(
+        [
+            508336281, 2577241559, 1984689085, 3901615337, 508013194, 3219569954, 3213951132,
+            2590431849, 726816376, 183209418, 226668643, 73988932,
+        ],
+        [
+            1849085171, 2900295051, 2778499431, 1110317728, 331548917, 2533375866, 3328983318,
+            2754761689, 1065906486, 951279956, 4200979750, 258819410,
+        ],
+    ),
+);
+
+pub fn main() {
+    // The constructors check that the points are in G1 and G2.
+    assert_eq!(G1Affine::new(G1.x, G1.y), Some(G1));
+    assert_eq!(G2Affine::new(G2_DOUBLE.x, G2_DOUBLE.y), Some(G2_DOUBLE));
+    assert!(G1_DOUBLE.is_valid() && G2.is_valid() && G2.neg().is_valid());
+    assert_eq!(G1Affine::new(G1.x, G1.x), None);
+    assert_eq!(G2Affine::new(G2.y, G2.x), None);
+
+    let e = pairing(&G1, &G2);
+    assert!(!e.is_one());
+
+    // Bilinearity: e([2]P, Q) = e(P, [2]Q) = e(P, Q)^2.
+    let e_squared = e.mul(&e);
+    assert_eq!(pairing(&G1_DOUBLE, &G2), e_squared);
+    assert_eq!(pairing(&G1, &G2_DOUBLE), e_squared);
+
+    assert!(pairing_check(&[(G1_DOUBLE, G2), (G1.neg(), G2_DOUBLE)]));
+    assert!(pairing_check(&[(G1, G2), (G1, G2.neg()), (G1Affine::identity(), G2)]));
+    assert!(!pairing_check(&[(G1, G2), (G1, G2_DOUBLE)]));
+}
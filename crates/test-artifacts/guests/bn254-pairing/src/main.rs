@@ -0,0 +1,83 @@
+#![no_main]
+zkm_zkvm::entrypoint!(main);
+
+use zkm_lib::bn254::{pairing, pairing_check, Fp2, G1Affine, G2Affine};
+
+/// The BN254 G1 generator.
+pub const G1: G1Affine =
+    G1Affine::new_unchecked([1, 0, 0, 0, 0, 0, 0, 0], [2, 0, 0, 0, 0, 0, 0, 0]);
+
+/// `[2]G1`.
+pub const G1_DOUBLE: G1Affine = G1Affine::new_unchecked(
+    [1837617107, 3552708801, 377953448, 3648525993, 1746408837, 2609185883, 773003778, 50742503],
+    [1511563972, 4282302330, 3813904327, 1755751497, 2997787658, 3884212118, 235568274, 367883148],
+);
+
+/// The BN254 G2 generator.
+pub const G2: G2Affine = G2Affine::new_unchecked(
+    Fp2::new(
+        [
+            3650287341, 1189002588, 4150188765, 1732453076, 1583105145, 1114243174, 304029302,
+            402710255,
+        ],
+        [
+            2935165634, 2548336055, 900327186, 4054468915, 838556965, 1918943159, 2450343994,
+            428774291,
+        ],
+    ),
+    Fp2::new(
+        [
+            1727692202, 1290193921, 205771643, 3822184297, 2378907791, 1252749696, 3683413483,
+            315121317,
+        ],
+        [
+            3508705115, 1437391580, 1890815731, 3159044403, 1762407317, 3969817005, 1482682485,
+            151423440,
+        ],
+    ),
+);
+
+/// `[2]G2`.
+pub const G2_DOUBLE: G2Affine = G2Affine::new_unchecked(
+    Fp2::new(
+        [
+            1656009657, 1240994569, 2846239470, 2640106447, 2084466452, 3278657831, 4245804008,
+            668758580,
+        ],
+        [
+            958967161, 2572676492, 3336058244, 1840800817, 980628336, 3053527480, 3035732791,
+            540942429,
+        ],
+    ),
+    Fp2::new(
+        [
+            304831790, 1848274349, 3870408774, 1151703887, 1120174857, 2696689447, 2541641618,
+            79385528,
+        ],
+        [
+            1352524114, 2564916720, 1323256020, 889542251, 959535005, 1915456533, 3078780003,
+            425626277,
+        ],
+    ),
+);
+
+pub fn main() {
+    // The constructors check that the points are in G1 and G2.
+    assert_eq!(G1Affine::new(G1.x, G1.y), Some(G1));
+    assert_eq!(G2Affine::new(G2_DOUBLE.x, G2_DOUBLE.y), Some(G2_DOUBLE));
+    assert!(G1_DOUBLE.is_valid() && G2.is_valid() && G2.neg().is_valid());
+    assert_eq!(G1Affine::new(G1.x, G1.x), None);
+    assert_eq!(G2Affine::new(G2.y, G2.x), None);
+
+    let e = pairing(&G1, &G2);
+    assert!(!e.is_one());
+
+    // Bilinearity: e([2]P, Q) = e(P, [2]Q) = e(P, Q)^2.
+    let e_squared = e.mul(&e);
+    assert_eq!(pairing(&G1_DOUBLE, &G2), e_squared);
+    assert_eq!(pairing(&G1, &G2_DOUBLE), e_squared);
+
+    assert!(pairing_check(&[(G1_DOUBLE, G2), (G1.neg(), G2_DOUBLE)]));
+    assert!(pairing_check(&[(G1, G2), (G1, G2.neg()), (G1Affine::identity(), G2)]));
+    assert!(!pairing_check(&[(G1, G2), (G1, G2_DOUBLE)]));
+}
@@ -0,0 +1,61 @@
+#![no_std]
+#![no_main]
+zkm_zkvm::entrypoint!(main);
+
+use zkm_zkvm::lib::bn254::Bn254Point;
+use zkm_zkvm::lib::utils::{AffinePoint, WeierstrassAffinePoint, WeierstrassScalarMul};
+
+/// Computes `[k]P` with the double-and-add loop over the add and double precompiles.
+fn double_and_add(p: &Bn254Point, k: &[u32; 8]) -> Bn254Point {
+    let mut res = Bn254Point::infinity();
+    let mut temp = *p;
+    for &word in k.iter() {
+        for i in 0..32 {
+            if (word >> i) & 1 == 1 {
+                res.weierstrass_add_assign(&temp);
+            }
+            temp.double();
+        }
+    }
+    res
+}
+
+pub fn main() {
+    let generator = Bn254Point::GENERATOR_T;
+
+    let mut order_minus_one = Bn254Point::ORDER;
+    order_minus_one[0] -= 1;
+
+    let scalars: [[u32; 8]; 4] = [
+        [1, 0, 0, 0, 0, 0, 0, 0],
+        [3, 0, 0, 0, 0, 0, 0, 0],
+        [
+            0x9c2d41f7, 0x1b7e2a53, 0xe04c6d19, 0x55aa33cc, 0x0f1e2d3c, 0x89abcdef, 0x12345678,
+            0x0badc0de,
+        ],
+        order_minus_one,
+    ];
+
+    for k in scalars.iter() {
+        let expected = double_and_add(&generator, k);
+
+        // SAFETY: The generator is in the prime-order subgroup and `k` is in `[1, ORDER)`.
+        let mut point = generator;
+        unsafe { point.scalar_mul_assign_unchecked(k) };
+
+        assert_eq!(point.limbs_ref(), expected.limbs_ref());
+    }
+
+    // [k1]G + [k2]G through the multi-scalar multiplication API, which skips the terms with a zero
+    // scalar or the point at infinity.
+    // SAFETY: The points are in the prime-order subgroup and the scalars are less than `ORDER`.
+    let msm = unsafe {
+        Bn254Point::multi_scalar_mul_unchecked(
+            &[scalars[2], scalars[3], [0u32; 8], scalars[2]],
+            &[generator, generator, generator, Bn254Point::infinity()],
+        )
+    };
+    let mut expected = double_and_add(&generator, &scalars[2]);
+    expected.weierstrass_add_assign(&double_and_add(&generator, &scalars[3]));
+    assert_eq!(msm.limbs_ref(), expected.limbs_ref());
+}
@@ -0,0 +1,24 @@
+#![no_main]
+zkm_zkvm::entrypoint!(main);
+
+use hex_literal::hex;
+use zkm_lib::sha512::sha512;
+
+pub fn main() {
+    assert_eq!(
+        sha512(b"abc"),
+        hex!(
+            "ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a"
+            "2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f"
+        )
+    );
+
+    // Two full blocks, followed by a padding block.
+    assert_eq!(
+        sha512(&[b'a'; 200]),
+        hex!(
+            "4b11459c33f52a22ee8236782714c150a3b2c60994e9acee17fe68947a3e6789"
+            "f31e7668394592da7bef827cddca88c4e6f86e4df7ed1ae6cba71f3e98faee9f"
+        )
+    );
+}
@@ -11,6 +11,9 @@ pub const POSEIDON2_PERMUTE_ELF: &[u8] = include_elf!("poseidon2-permute-test");
 pub const SHA2_ELF: &[u8] = include_elf!("sha2-test");
 pub const SHA_EXTEND_ELF: &[u8] = include_elf!("sha-extend-test");
 pub const SHA_COMPRESS_ELF: &[u8] = include_elf!("sha-compress-test");
+pub const SHA512_ELF: &[u8] = include_elf!("sha512-test");
+pub const BLAKE2B_ELF: &[u8] = include_elf!("blake2b-test");
+pub const BLAKE3_ELF: &[u8] = include_elf!("blake3-test");
 
 pub const SHA3_CHAIN_ELF: &[u8] = include_elf!("sha3-chain");
 pub const KECCAK_SPONGE_ELF: &[u8] = include_elf!("keccak-sponge-test");
@@ -30,12 +33,16 @@ pub const SECP256K1_DECOMPRESS_ELF: &[u8] = include_elf!("secp256k1-decompress-t
 
 pub const SECP256K1_DOUBLE_ELF: &[u8] = include_elf!("secp256k1-double-test");
 
+pub const SECP256K1_SCALAR_MUL_ELF: &[u8] = include_elf!("secp256k1-scalar-mul-test");
+
 pub const SECP256R1_ADD_ELF: &[u8] = include_elf!("secp256r1-add-test");
 
 pub const SECP256R1_DECOMPRESS_ELF: &[u8] = include_elf!("secp256r1-decompress-test");
 
 pub const SECP256R1_DOUBLE_ELF: &[u8] = include_elf!("secp256r1-double-test");
 
+pub const SECP256R1_SCALAR_MUL_ELF: &[u8] = include_elf!("secp256r1-scalar-mul-test");
+
 pub const BN254_ADD_ELF: &[u8] = include_elf!("bn254-add-test");
 
 pub const BN254_DOUBLE_ELF: &[u8] = include_elf!("bn254-double-test");
@@ -62,12 +69,24 @@ pub const BLS12381_FP2_MUL_ELF: &[u8] = include_elf!("bls12381-fp2-mul-test");
 
 pub const BLS12381_FP2_ADDSUB_ELF: &[u8] = include_elf!("bls12381-fp2-addsub-test");
 
+pub const BLS12381_FP6_MUL_ELF: &[u8] = include_elf!("bls12381-fp6-mul-test");
+
+pub const BLS12381_PAIRING_ELF: &[u8] = include_elf!("bls12381-pairing-test");
+
+pub const BLS12381_SCALAR_MUL_ELF: &[u8] = include_elf!("bls12381-scalar-mul-test");
+
 pub const BN254_FP_ELF: &[u8] = include_elf!("bn254-fp-test");
 
 pub const BN254_FP2_ADDSUB_ELF: &[u8] = include_elf!("bn254-fp2-addsub-test");
 
 pub const BN254_FP2_MUL_ELF: &[u8] = include_elf!("bn254-fp2-mul-test");
 
+pub const BN254_FP6_MUL_ELF: &[u8] = include_elf!("bn254-fp6-mul-test");
+
+pub const BN254_PAIRING_ELF: &[u8] = include_elf!("bn254-pairing-test");
+
+pub const BN254_SCALAR_MUL_ELF: &[u8] = include_elf!("bn254-scalar-mul-test");
+
 //pub const TENDERMINT_BENCHMARK_ELF: &[u8] = include_elf!("tendermint-benchmark-program");
 
 pub const U256XU2048_MUL_ELF: &[u8] = include_elf!("u256x2048-mul");
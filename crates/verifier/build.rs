@@ -1,9 +1,18 @@
+use std::collections::BTreeMap;
 use std::env;
 use std::fs;
 use std::path::{Path, PathBuf};
 
+use p3_field::{FieldAlgebra, PrimeField32};
+use p3_koala_bear::KoalaBear;
+use p3_symmetric::Permutation;
+use p3_util::reverse_slice_index_bits;
+use zkm_stark::{koala_bear_poseidon2::my_perm, DIGEST_SIZE};
+
 const HISTORY_DIR: &str = "bn254-vk/history";
 const PART_STARK_VK_SUFFIX: &str = "_part_stark_vk.bin";
+const VK_MAP: &str = "../prover/vk_map.bin";
+const DUMMY_VK_MAP: &str = "../prover/dummy_vk_map.bin";
 
 fn main() {
     println!("cargo:rerun-if-changed={HISTORY_DIR}");
@@ -28,6 +37,54 @@ fn main() {
     fs::write(&registry, source).unwrap_or_else(|err| {
         panic!("failed to write registry {}: {err}", registry.display());
     });
+
+    write_vk_root(&manifest_dir, &out_dir);
+}
+
+/// Writes the Merkle root of the allowed recursion vks, so that verifying a proof inside the
+/// zkVM doesn't hash the whole map.
+fn write_vk_root(manifest_dir: &Path, out_dir: &Path) {
+    let vk_map =
+        if env::var_os("CARGO_FEATURE_DUMMY_VK_MAP").is_some() { DUMMY_VK_MAP } else { VK_MAP };
+    println!("cargo:rerun-if-changed={vk_map}");
+
+    let path = manifest_dir.join(vk_map);
+    let bytes = fs::read(&path).unwrap_or_else(|err| {
+        panic!("failed to read vk map {}: {err}", path.display());
+    });
+    let allowed_vk_map: BTreeMap<[KoalaBear; DIGEST_SIZE], usize> = bincode::deserialize(&bytes)
+        .unwrap_or_else(|err| panic!("failed to deserialize vk map {}: {err}", path.display()));
+    let root = merkle_root(allowed_vk_map.into_keys().collect()).map(|x| x.as_canonical_u32());
+
+    let vk_root = out_dir.join("vk_root.rs");
+    let source = format!(
+        "/// The Merkle root of the allowed recursion vks, in canonical form.\n\
+         pub(crate) const RECURSION_VK_ROOT: [u32; {DIGEST_SIZE}] = {root:?};\n"
+    );
+    fs::write(&vk_root, source).unwrap_or_else(|err| {
+        panic!("failed to write vk root {}: {err}", vk_root.display());
+    });
+}
+
+/// The root of the Merkle tree the prover commits the allowed vks to, with the leaves padded to a
+/// power of two and stored in bit-reversed order.
+fn merkle_root(mut layer: Vec<[KoalaBear; DIGEST_SIZE]>) -> [KoalaBear; DIGEST_SIZE] {
+    assert!(layer.len() > 1, "the vk map must hold at least two vks");
+    layer.resize(layer.len().next_power_of_two(), [KoalaBear::ZERO; DIGEST_SIZE]);
+    reverse_slice_index_bits(&mut layer);
+    let perm = my_perm();
+    while layer.len() > 1 {
+        layer = layer
+            .chunks_exact(2)
+            .map(|pair| {
+                let mut state: [KoalaBear; 2 * DIGEST_SIZE] =
+                    core::array::from_fn(|i| pair[i / DIGEST_SIZE][i % DIGEST_SIZE]);
+                perm.permute_mut(&mut state);
+                state[..DIGEST_SIZE].try_into().unwrap()
+            })
+            .collect();
+    }
+    layer[0]
 }
 
 fn collect_history_entries(history_dir: &Path) -> Vec<(String, String)> {
@@ -8,7 +8,7 @@ use p3_bn254_fr::Bn254Fr;
 use p3_field::{FieldAlgebra, PrimeField};
 use substrate_bn::Fr;
 
-use alloc::vec::Vec;
+use alloc::{format, vec::Vec};
 use sha2::{Digest, Sha256};
 use zkm_recursion_core::stark::KoalaBearPoseidon2Outer;
 use zkm_stark::PartStarkVerifyingKey;
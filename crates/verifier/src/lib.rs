@@ -1,4 +1,5 @@
-//! This crate provides verifiers for Ziren Groth16 and Plonk BN254 proofs in a no-std environment.
+//! This crate provides verifiers for Ziren Groth16 and Plonk BN254 proofs and for Ziren compressed
+//! STARK proofs in a no-std environment.
 //! It is patched for efficient verification within the Ziren zkVM context.
 
 #![cfg_attr(not(feature = "std"), no_std)]
@@ -17,7 +17,7 @@ pub(crate) use converter::{load_plonk_proof_from_bytes, load_plonk_verifying_key
 pub(crate) use proof::PlonkProof;
 pub(crate) use verify::verify_plonk_algebraic;
 
-use alloc::vec::Vec;
+use alloc::{format, vec::Vec};
 use error::PlonkError;
 use sha2::{Digest, Sha256};
 use substrate_bn::Fr;
@@ -1,3 +1,4 @@
+use alloc::string::String;
 use thiserror::Error;
 // use zkm_prover::{CoreSC, InnerSC};
 use zkm_stark::MachineVerificationError;
@@ -1,5 +1,6 @@
 extern crate alloc;
 
+use alloc::{boxed::Box, string::String, vec::Vec};
 use core::borrow::Borrow;
 use core::convert::AsRef;
 use itertools::Itertools;
@@ -9,18 +10,22 @@ use p3_field::FieldAlgebra;
 use p3_field::PrimeField32;
 use p3_field::TwoAdicField;
 use p3_koala_bear::KoalaBear;
+use p3_symmetric::CryptographicHasher;
 use serde::{Deserialize, Serialize};
 use strum_macros::{EnumDiscriminants, EnumTryAs};
 use zkm_core_executor::ZKMReduceProof;
-use zkm_primitives::{io::ZKMPublicValues, poseidon2_hash};
+use zkm_primitives::io::ZKMPublicValues;
 use zkm_stark::ShardProof;
 use zkm_stark::{
-    air::PublicValues, koala_bear_poseidon2::KoalaBearPoseidon2, StarkGenericConfig,
-    StarkVerifyingKey, Word, DIGEST_SIZE,
+    air::PublicValues,
+    koala_bear_poseidon2::{my_perm, KoalaBearPoseidon2, MyHash},
+    StarkGenericConfig, StarkVerifyingKey, Word, DIGEST_SIZE,
 };
 
 use error::StarkError;
 use verify::verify_stark_compressed_proof;
+#[cfg(test)]
+pub(crate) use verify::{recursion_vk_root, VK_MAP};
 
 pub mod error;
 mod verify;
@@ -74,6 +79,9 @@ pub type CoreSC = KoalaBearPoseidon2;
 pub type InnerSC = KoalaBearPoseidon2;
 
 /// The information necessary to verify a proof for a given MIPS program.
+///
+/// Only the STARK verifying key is read: the version and the fields following it in the encoding
+/// of the SDK verifying key are ignored.
 #[derive(Clone, Serialize, Deserialize)]
 pub struct ZKMVerifyingKey {
     pub vk: StarkVerifyingKey<CoreSC>,
@@ -178,6 +186,8 @@ where
             inputs.push(g);
         }
 
-        poseidon2_hash(inputs)
+        // The same sponge as `poseidon2_hash`, over the permutation of the config, which runs on
+        // the precompile inside the zkVM.
+        MyHash::new(my_perm()).hash_iter(inputs)
     }
 }
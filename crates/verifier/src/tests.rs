@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::fs::File;
 use std::io::Read;
 use test_artifacts::HELLO_WORLD_ELF;
@@ -5,8 +6,23 @@ use zkm_prover::build::groth16_bn254_artifacts_dev_dir;
 use zkm_sdk::install::try_install_circuit_artifacts;
 use zkm_sdk::{HashableKey, ProverClient, ZKMStdin, ZKM_CIRCUIT_VERSION};
 
+use p3_koala_bear::KoalaBear;
+use zkm_prover::InnerSC;
+use zkm_recursion_circuit::merkle_tree::MerkleTree;
+use zkm_stark::DIGEST_SIZE;
+
+use crate::stark::{recursion_vk_root, VK_MAP};
 use crate::{Groth16Verifier, PART_STARK_VK_BYTES};
 
+#[test]
+fn test_recursion_vk_root() {
+    // The root committed to by the build script is the one the prover commits the map to.
+    let allowed_vk_map: BTreeMap<[KoalaBear; DIGEST_SIZE], usize> =
+        bincode::deserialize(&VK_MAP).unwrap();
+    let (root, _) = MerkleTree::<KoalaBear, InnerSC>::commit(allowed_vk_map.into_keys().collect());
+    assert_eq!(recursion_vk_root(), root);
+}
+
 // RUST_LOG=debug cargo test -r test_verify_groth16 --features ark
 #[test]
 fn test_verify_groth16() {
@@ -22,6 +22,9 @@ extern crate alloc;
 #[cfg(target_os = "zkvm")]
 pub mod allocators;
 
+#[cfg(target_os = "zkvm")]
+mod panic;
+
 /// Size of the reserved region for input values with the embedded allocator.
 #[cfg(all(target_os = "zkvm", feature = "embedded"))]
 pub(crate) const EMBEDDED_RESERVED_INPUT_REGION_SIZE: usize = 1024 * 1024 * 1024;
@@ -77,7 +80,7 @@ pub extern "C" fn read_vec_raw() -> ReadVecResult {
                 // Get the existing pointer in the reserved region which is the start of the vec.
                 // Increment the pointer by the capacity to set the new pointer to the end of the vec.
                 let ptr = unsafe { EMBEDDED_RESERVED_INPUT_PTR };
-                if ptr + capacity > MAX_MEMORY {
+                if ptr + capacity > syscalls::VFS_BASE {
                     panic!("Input region overflowed.")
                 }
 
@@ -167,6 +170,8 @@ mod zkvm {
         #[cfg(all(target_os = "zkvm", feature = "embedded"))]
         crate::allocators::init();
 
+        crate::panic::install_hook();
+
         unsafe {
             PUBLIC_VALUES_HASHER = Some(Sha256::new());
             #[cfg(feature = "verify")]
@@ -0,0 +1,46 @@
+//! Reports guest panics to the host.
+
+use zkm_primitives::{consts::fd::FD_PANIC, types::PanicReport};
+
+use crate::syscalls::{sys_write, MAX_MEMORY};
+
+/// The maximum number of stack words sent to the host with a panic.
+const MAX_STACK_WORDS: usize = 4096;
+
+/// Install a panic hook that writes a [`PanicReport`] to [`FD_PANIC`] before running the default
+/// hook, which prints the panic to stderr.
+pub(crate) fn install_hook() {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let payload = info.payload();
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|message| message.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_default();
+        let (file, line, column) = info
+            .location()
+            .map(|location| (location.file().to_string(), location.line(), location.column()))
+            .unwrap_or_default();
+
+        let report = PanicReport { message, file, line, column, stack: stack_words() };
+        let bytes = bincode::serialize(&report).expect("failed to serialize the panic report");
+        sys_write(FD_PANIC, bytes.as_ptr(), bytes.len());
+
+        default_hook(info);
+    }));
+}
+
+/// The words between the stack pointer and the top of the stack, innermost first.
+///
+/// The return addresses the host rebuilds the backtrace from are among them.
+fn stack_words() -> Vec<u32> {
+    let sp: usize;
+    // SAFETY: Reading the stack pointer has no side effects.
+    unsafe { core::arch::asm!("move {}, $sp", out(reg) sp) };
+
+    let len = (MAX_MEMORY.saturating_sub(sp) / 4).min(MAX_STACK_WORDS);
+    // SAFETY: The words between the stack pointer and the top of the stack are initialized stack
+    // memory.
+    unsafe { core::slice::from_raw_parts(sp as *const u32, len) }.to_vec()
+}
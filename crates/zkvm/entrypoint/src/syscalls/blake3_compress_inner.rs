@@ -0,0 +1,23 @@
+#[cfg(target_os = "zkvm")]
+use core::arch::asm;
+
+/// Executes the seven rounds of the BLAKE3 compression function on the given working state and
+/// message block. The state is updated in place.
+///
+/// ### Safety
+///
+/// The caller must ensure that `state` and `msg` are valid pointers to data that is aligned along a
+/// four byte boundary.
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_blake3_compress_inner(state: *mut [u32; 16], msg: *const [u32; 16]) {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        asm!(
+            "syscall",
+            in("$2") crate::syscalls::BLAKE3_COMPRESS_INNER,
+            in("$4") state,
+            in("$5") msg,
+        );
+    }
+}
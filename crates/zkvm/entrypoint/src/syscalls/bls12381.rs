@@ -48,6 +48,32 @@ pub extern "C" fn syscall_bls12381_double(p: *mut [u32; 24]) {
     }
 }
 
+/// Multiplies a Bls12381 point by a scalar.
+///
+/// The scalar is given as 8 little-endian words. The result is stored in the point.
+///
+/// ### Safety
+///
+/// The caller must ensure that `p` and `k` are valid pointers to data that is aligned along a four
+/// byte boundary. Additionally, the caller must ensure that `p` is a valid point of the prime-order
+/// subgroup of the bls12381 curve, and that the scalar is non-zero and less than the subgroup order.
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_bls12381_scalar_mul(p: *mut [u32; 24], k: *const [u32; 8]) {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        asm!(
+            "syscall",
+            in("$2") crate::syscalls::BLS12381_SCALAR_MUL,
+            in("$4") p,
+            in("$5") k
+        );
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
+}
+
 /// Decompresses a compressed BLS12-381 point.
 ///
 /// The first half of the input array should contain the X coordinate. The second half of the input
@@ -121,6 +121,27 @@ pub extern "C" fn syscall_bls12381_fp2_mulmod(x: *mut u32, y: *const u32) {
     unreachable!()
 }
 
+/// BLS12-381 Fp6 multiplication operation.
+///
+/// Both inputs are Fp6 elements laid out as three consecutive Fp2 elements. The result is written
+/// over the first input.
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_bls12381_fp6_mulmod(x: *mut u32, y: *const u32) {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        asm!(
+            "syscall",
+            in("$2") crate::syscalls::BLS12381_FP6_MUL,
+            in("$4") x,
+            in("$5") y,
+        );
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
+}
+
 /// Fp addition operation.
 ///
 /// The result is written over the first input.
@@ -240,3 +261,24 @@ pub extern "C" fn syscall_bn254_fp2_mulmod(x: *mut u32, y: *const u32) {
     #[cfg(not(target_os = "zkvm"))]
     unreachable!()
 }
+
+/// BN254 Fp6 multiplication operation.
+///
+/// Both inputs are Fp6 elements laid out as three consecutive Fp2 elements. The result is written
+/// over the first input.
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_bn254_fp6_mulmod(x: *mut u32, y: *const u32) {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        asm!(
+            "syscall",
+            in("$2") crate::syscalls::BN254_FP6_MUL,
+            in("$4") x,
+            in("$5") y,
+        );
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
+}
@@ -15,6 +15,9 @@
 // Memory addresses must be lower than KoalaBear prime.
 pub const MAX_MEMORY: usize = 0x7f000000;
 
+/// The start of the virtual files attached by the host, which the heap must stay below.
+pub const VFS_BASE: usize = 0x70000000;
+
 #[allow(clippy::missing_safety_doc)]
 #[no_mangle]
 pub unsafe extern "C" fn sys_alloc_aligned(bytes: usize, align: usize) -> *mut u8 {
@@ -42,8 +45,8 @@ pub unsafe extern "C" fn sys_alloc_aligned(bytes: usize, align: usize) -> *mut u
     let ptr = heap_pos as *mut u8;
     let (heap_pos, overflowed) = heap_pos.overflowing_add(bytes);
 
-    if overflowed || MAX_MEMORY < heap_pos {
-        panic!("Memory limit exceeded ({MAX_MEMORY:#x})");
+    if overflowed || VFS_BASE < heap_pos {
+        panic!("Memory limit exceeded ({VFS_BASE:#x})");
     }
 
     unsafe { HEAP_POS = heap_pos };
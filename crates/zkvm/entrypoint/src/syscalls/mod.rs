@@ -1,4 +1,6 @@
 mod bigint;
+mod blake2b_compress_inner;
+mod blake3_compress_inner;
 mod bls12381;
 mod bn254;
 mod boolean_circuit_garble;
@@ -12,6 +14,8 @@ mod memory;
 mod poseidon2_permute;
 mod secp256k1;
 mod secp256r1;
+mod sha512_compress;
+mod sha512_extend;
 mod sha_compress;
 mod sha_extend;
 mod sys;
@@ -21,6 +25,8 @@ mod unconstrained;
 #[cfg(feature = "verify")]
 mod verify;
 pub use bigint::*;
+pub use blake2b_compress_inner::*;
+pub use blake3_compress_inner::*;
 pub use bls12381::*;
 pub use bn254::*;
 pub use boolean_circuit_garble::*;
@@ -33,6 +39,8 @@ pub use memory::*;
 pub use poseidon2_permute::*;
 pub use secp256k1::*;
 pub use secp256r1::*;
+pub use sha512_compress::*;
+pub use sha512_extend::*;
 pub use sha_compress::*;
 pub use sha_extend::*;
 pub use sys::*;
@@ -167,3 +175,33 @@ pub const POSEIDON2_PERMUTE: u32 = 0x00_01_00_30;
 
 /// Executes the `BOOLEAN_CIRCUIT_GARBLE` precompile.
 pub const BOOLEAN_CIRCUIT_GARBLE: u32 = 0x00_01_00_31;
+
+/// Executes `SHA512_EXTEND`.
+pub const SHA512_EXTEND: u32 = 0x40_01_00_32;
+
+/// Executes `SHA512_COMPRESS`.
+pub const SHA512_COMPRESS: u32 = 0x01_01_00_33;
+
+/// Executes `BLAKE3_COMPRESS_INNER`.
+pub const BLAKE3_COMPRESS_INNER: u32 = 0x38_01_00_34;
+
+/// Executes `BLAKE2B_COMPRESS_INNER`.
+pub const BLAKE2B_COMPRESS_INNER: u32 = 0x60_01_00_35;
+
+/// Executes the `BN254_FP6_MUL` precompile.
+pub const BN254_FP6_MUL: u32 = 0x01_01_00_36;
+
+/// Executes the `BLS12381_FP6_MUL` precompile.
+pub const BLS12381_FP6_MUL: u32 = 0x01_01_00_37;
+
+/// Executes the `SECP256K1_SCALAR_MUL` precompile.
+pub const SECP256K1_SCALAR_MUL: u32 = 0x01_01_00_38;
+
+/// Executes the `SECP256R1_SCALAR_MUL` precompile.
+pub const SECP256R1_SCALAR_MUL: u32 = 0x01_01_00_39;
+
+/// Executes the `BN254_SCALAR_MUL` precompile.
+pub const BN254_SCALAR_MUL: u32 = 0x01_01_00_3A;
+
+/// Executes the `BLS12381_SCALAR_MUL` precompile.
+pub const BLS12381_SCALAR_MUL: u32 = 0x01_01_00_3B;
@@ -52,6 +52,32 @@ pub extern "C" fn syscall_secp256r1_double(p: *mut [u32; 16]) {
     unreachable!()
 }
 
+/// Multiplies a Secp256r1 point by a scalar.
+///
+/// The scalar is given as 8 little-endian words. The result is stored in the point.
+///
+/// ### Safety
+///
+/// The caller must ensure that `p` and `k` are valid pointers to data that is aligned along a four
+/// byte boundary. Additionally, the caller must ensure that `p` is a valid point of the prime-order
+/// subgroup of the secp256r1 curve, and that the scalar is non-zero and less than the subgroup order.
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_secp256r1_scalar_mul(p: *mut [u32; 16], k: *const [u32; 8]) {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        asm!(
+            "syscall",
+            in("$2") crate::syscalls::SECP256R1_SCALAR_MUL,
+            in("$4") p,
+            in("$5") k
+        );
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
+}
+
 /// Decompresses a compressed Secp256r1 point.
 ///
 /// The input array should be 64 bytes long, with the first 32 bytes containing the X coordinate in
@@ -0,0 +1,70 @@
+use crate::syscall_blake2b_compress_inner;
+
+/// The BLAKE2b block size in bytes.
+pub const BLOCK_LEN: usize = 128;
+
+/// The BLAKE2b initialization vector.
+pub const BLAKE2B_IV: [u64; 8] = [
+    0x6a09e667f3bcc908,
+    0xbb67ae8584caa73b,
+    0x3c6ef372fe94f82b,
+    0xa54ff53a5f1d36f1,
+    0x510e527fade682d1,
+    0x9b05688c2b3e6c1f,
+    0x1f83d9abfb41bd6b,
+    0x5be0cd19137e2179,
+];
+
+/// Run the BLAKE2b compression function `F` on the given state.
+///
+/// `t` is the number of message bytes processed so far, including this block, and `last` marks
+/// the final block.
+pub fn blake2b_compress(h: &mut [u64; 8], m: &[u64; 16], t: u128, last: bool) {
+    let mut v = [0u64; 16];
+    v[..8].copy_from_slice(h);
+    v[8..].copy_from_slice(&BLAKE2B_IV);
+    v[12] ^= t as u64;
+    v[13] ^= (t >> 64) as u64;
+    if last {
+        v[14] = !v[14];
+    }
+    unsafe {
+        syscall_blake2b_compress_inner(&mut v, m);
+    }
+    for (i, word) in h.iter_mut().enumerate() {
+        *word ^= v[i] ^ v[i + 8];
+    }
+}
+
+/// Convert up to 128 bytes into a block of little-endian words, padding with zeros.
+fn block_words(bytes: &[u8]) -> [u64; 16] {
+    let mut padded = [0u8; BLOCK_LEN];
+    padded[..bytes.len()].copy_from_slice(bytes);
+    let mut block = [0u64; 16];
+    for (word, chunk) in block.iter_mut().zip(padded.chunks_exact(8)) {
+        *word = u64::from_le_bytes(chunk.try_into().unwrap());
+    }
+    block
+}
+
+/// Hash the given data with unkeyed BLAKE2b-512.
+pub fn blake2b(data: &[u8]) -> [u8; 64] {
+    let mut h = BLAKE2B_IV;
+    // The parameter block: a 64-byte digest, no key, fanout and depth of one.
+    h[0] ^= 0x0101_0000 ^ 64;
+
+    let num_blocks = data.len().div_ceil(BLOCK_LEN).max(1);
+    let mut blocks = data.chunks(BLOCK_LEN);
+    for i in 0..num_blocks - 1 {
+        let t = ((i + 1) * BLOCK_LEN) as u128;
+        blake2b_compress(&mut h, &block_words(blocks.next().unwrap()), t, false);
+    }
+    let last = blocks.next().unwrap_or(&[]);
+    blake2b_compress(&mut h, &block_words(last), data.len() as u128, true);
+
+    let mut digest = [0u8; 64];
+    for (chunk, word) in digest.chunks_exact_mut(8).zip(h) {
+        chunk.copy_from_slice(&word.to_le_bytes());
+    }
+    digest
+}
@@ -0,0 +1,141 @@
+use crate::syscall_blake3_compress_inner;
+
+/// The BLAKE3 block size in bytes.
+pub const BLOCK_LEN: usize = 64;
+
+/// The BLAKE3 chunk size in bytes.
+pub const CHUNK_LEN: usize = 1024;
+
+/// The BLAKE3 initialization vector.
+pub const BLAKE3_IV: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+/// The domain separation flags of the compression function.
+pub const CHUNK_START: u32 = 1 << 0;
+pub const CHUNK_END: u32 = 1 << 1;
+pub const PARENT: u32 = 1 << 2;
+pub const ROOT: u32 = 1 << 3;
+
+/// Run the BLAKE3 compression function, returning the full 16-word output.
+///
+/// The first eight words of the output are the new chaining value.
+pub fn blake3_compress(
+    cv: &[u32; 8],
+    block: &[u32; 16],
+    counter: u64,
+    block_len: u32,
+    flags: u32,
+) -> [u32; 16] {
+    let mut state = [
+        cv[0],
+        cv[1],
+        cv[2],
+        cv[3],
+        cv[4],
+        cv[5],
+        cv[6],
+        cv[7],
+        BLAKE3_IV[0],
+        BLAKE3_IV[1],
+        BLAKE3_IV[2],
+        BLAKE3_IV[3],
+        counter as u32,
+        (counter >> 32) as u32,
+        block_len,
+        flags,
+    ];
+    unsafe {
+        syscall_blake3_compress_inner(&mut state, block);
+    }
+    for (i, word) in cv.iter().enumerate() {
+        state[i] ^= state[i + 8];
+        state[i + 8] ^= word;
+    }
+    state
+}
+
+/// The input of a pending compression, which becomes either a chaining value or the root hash.
+struct Output {
+    cv: [u32; 8],
+    block: [u32; 16],
+    counter: u64,
+    block_len: u32,
+    flags: u32,
+}
+
+impl Output {
+    fn chaining_value(&self) -> [u32; 8] {
+        let out = blake3_compress(&self.cv, &self.block, self.counter, self.block_len, self.flags);
+        out[..8].try_into().unwrap()
+    }
+
+    fn root_hash(&self) -> [u8; 32] {
+        let out = blake3_compress(&self.cv, &self.block, 0, self.block_len, self.flags | ROOT);
+        let mut hash = [0u8; 32];
+        for (chunk, word) in hash.chunks_exact_mut(4).zip(out) {
+            chunk.copy_from_slice(&word.to_le_bytes());
+        }
+        hash
+    }
+}
+
+/// Convert up to 64 bytes into a block of little-endian words, padding with zeros.
+fn block_words(bytes: &[u8]) -> [u32; 16] {
+    let mut padded = [0u8; BLOCK_LEN];
+    padded[..bytes.len()].copy_from_slice(bytes);
+    let mut block = [0u32; 16];
+    for (word, chunk) in block.iter_mut().zip(padded.chunks_exact(4)) {
+        *word = u32::from_le_bytes(chunk.try_into().unwrap());
+    }
+    block
+}
+
+/// Compress all blocks of a chunk but the last, and return the output of the last block.
+fn chunk_output(chunk: &[u8], counter: u64) -> Output {
+    let mut cv = BLAKE3_IV;
+    let num_blocks = chunk.len().div_ceil(BLOCK_LEN).max(1);
+    let mut blocks = chunk.chunks(BLOCK_LEN);
+    for i in 0..num_blocks - 1 {
+        let block = block_words(blocks.next().unwrap());
+        let flags = if i == 0 { CHUNK_START } else { 0 };
+        cv =
+            blake3_compress(&cv, &block, counter, BLOCK_LEN as u32, flags)[..8].try_into().unwrap();
+    }
+    let last = blocks.next().unwrap_or(&[]);
+    let flags = if num_blocks == 1 { CHUNK_START | CHUNK_END } else { CHUNK_END };
+    Output { cv, block: block_words(last), counter, block_len: last.len() as u32, flags }
+}
+
+/// The output of a parent node with the given children.
+fn parent_output(left: &[u32; 8], right: &[u32; 8]) -> Output {
+    let mut block = [0u32; 16];
+    block[..8].copy_from_slice(left);
+    block[8..].copy_from_slice(right);
+    Output { cv: BLAKE3_IV, block, counter: 0, block_len: BLOCK_LEN as u32, flags: PARENT }
+}
+
+/// Hash the given data with BLAKE3.
+pub fn blake3(data: &[u8]) -> [u8; 32] {
+    let num_chunks = data.len().div_ceil(CHUNK_LEN).max(1);
+    let mut chunks = data.chunks(CHUNK_LEN);
+
+    // The chaining values of the complete subtrees, merged as soon as a subtree is complete.
+    let mut stack: Vec<[u32; 8]> = Vec::new();
+    for i in 0..num_chunks - 1 {
+        let mut cv = chunk_output(chunks.next().unwrap(), i as u64).chaining_value();
+        let mut total_chunks = i + 1;
+        while total_chunks & 1 == 0 {
+            cv = parent_output(&stack.pop().unwrap(), &cv).chaining_value();
+            total_chunks >>= 1;
+        }
+        stack.push(cv);
+    }
+
+    // Merge the last chunk with the remaining subtrees, from right to left.
+    let mut output = chunk_output(chunks.next().unwrap_or(&[]), num_chunks as u64 - 1);
+    while let Some(left) = stack.pop() {
+        output = parent_output(&left, &output.chaining_value());
+    }
+    output.root_hash()
+}
@@ -1,8 +1,12 @@
 use std::io::ErrorKind;
 
 use crate::{
+    pairing::{self, PairingCurve, TwistType},
     syscall_bls12381_add, syscall_bls12381_decompress, syscall_bls12381_double,
-    utils::{AffinePoint, WeierstrassAffinePoint, WeierstrassPoint},
+    syscall_bls12381_fp2_addmod, syscall_bls12381_fp2_mulmod, syscall_bls12381_fp2_submod,
+    syscall_bls12381_fp6_mulmod, syscall_bls12381_fp_addmod, syscall_bls12381_fp_mulmod,
+    syscall_bls12381_fp_submod, syscall_bls12381_scalar_mul,
+    utils::{AffinePoint, WeierstrassAffinePoint, WeierstrassPoint, WeierstrassScalarMul},
 };
 
 /// The number of limbs in [Bls12381AffinePoint].
@@ -23,6 +27,17 @@ impl WeierstrassAffinePoint<N> for Bls12381Point {
     }
 }
 
+impl WeierstrassScalarMul<N> for Bls12381Point {
+    const ORDER: [u32; 8] = [
+        0x00000001, 0xFFFFFFFF, 0xFFFE5BFE, 0x53BDA402, 0x09A1D805, 0x3339D808, 0x299D7D48,
+        0x73EDA753,
+    ];
+
+    unsafe fn scalar_mul_syscall(limbs: &mut [u32; N], scalar: &[u32; 8]) {
+        syscall_bls12381_scalar_mul(limbs, scalar);
+    }
+}
+
 impl AffinePoint<N> for Bls12381Point {
     /// The generator was taken from "py_ecc" python library by the Ethereum Foundation:
     ///
@@ -96,3 +111,160 @@ pub fn decompress_pubkey(compressed_key: &[u8; 48]) -> Result<[u8; 96], ErrorKin
 
     Ok(decompressed_key)
 }
+
+/// The BLS12-381 curve, used to instantiate the pairing.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Bls12381;
+
+/// An element of the BLS12-381 base field.
+pub type Fp = [u32; N / 2];
+/// An element of the BLS12-381 quadratic extension.
+pub type Fp2 = pairing::Fp2<Bls12381>;
+/// An element of the BLS12-381 sextic extension.
+pub type Fp6 = pairing::Fp6<Bls12381>;
+/// An element of the BLS12-381 degree twelve extension, which contains the pairing target group.
+pub type Fp12 = pairing::Fp12<Bls12381>;
+/// A point of the BLS12-381 G1 group.
+pub type G1Affine = pairing::G1Affine<Bls12381>;
+/// A point of the BLS12-381 G2 group.
+pub type G2Affine = pairing::G2Affine<Bls12381>;
+
+impl PairingCurve for Bls12381 {
+    type Fp = Fp;
+
+    const FP_ZERO: Fp = [0; N / 2];
+
+    const FP_ONE: Fp = [1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+
+    const MODULUS: Fp = [
+        4294945451, 3120496639, 2975072255, 514588670, 4138792484, 1731252896, 4085584575,
+        1685539716, 1129032919, 1260103606, 964683418, 436277738,
+    ];
+
+    const MODULUS_MINUS_TWO: Fp = [
+        4294945449, 3120496639, 2975072255, 514588670, 4138792484, 1731252896, 4085584575,
+        1685539716, 1129032919, 1260103606, 964683418, 436277738,
+    ];
+
+    const TWO_INV: Fp = [
+        4294956374, 3707731967, 1487536127, 257294335, 2069396242, 3013110096, 2042792287,
+        2990253506, 564516459, 630051803, 482341709, 218138869,
+    ];
+
+    const FP6_NON_RESIDUE: Fp2 =
+        Fp2::new([1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0], [1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+
+    const FROBENIUS_COEFF_FP6_C1: Fp2 = Fp2::new(
+        [0; N / 2],
+        [
+            43692, 2348613632, 1330249725, 1083451371, 263741339, 2306681189, 2306185940,
+            2853012861, 1674894981, 3959570566, 964683417, 436277738,
+        ],
+    );
+
+    const FROBENIUS_COEFF_FP6_C2: Fp2 = Fp2::new(
+        [
+            43693, 2348613632, 1330249725, 1083451371, 263741339, 2306681189, 2306185940,
+            2853012861, 1674894981, 3959570566, 964683417, 436277738,
+        ],
+        [0; N / 2],
+    );
+
+    const FROBENIUS_COEFF_FP12_C1: Fp2 = Fp2::new(
+        [
+            2451791800, 2366076397, 1676116285, 4135494973, 2226829764, 2065974231, 1019043663,
+            265683965, 539757855, 3258039988, 45811303, 419746751,
+        ],
+        [
+            1843153651, 754420242, 1298955970, 674060993, 1911962719, 3960245961, 3066540911,
+            1419855751, 589275064, 2297030914, 918872114, 16530987,
+        ],
+    );
+
+    const B: Fp = [4, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+
+    const G1_IS_PRIME_ORDER: bool = false;
+
+    /// `4 * ξ`.
+    const TWIST_B: Fp2 =
+        Fp2::new([4, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0], [4, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+
+    const SCALAR_ORDER: [u32; 8] = <Bls12381Point as WeierstrassScalarMul<N>>::ORDER;
+
+    const TWIST_TYPE: TwistType = TwistType::M;
+
+    /// `|x|` for `x = -0xd201000000010000`.
+    const ATE_LOOP_COUNT: u128 = 0xd201000000010000;
+
+    const ATE_LOOP_COUNT_IS_NEGATIVE: bool = true;
+
+    const FINAL_EXPONENT_HARD: &'static [u32] = &[
+        954448505, 3843474420, 3792227569, 4204335786, 861756264, 2422008119, 2967380534,
+        3340379689, 2573685960, 2205632832, 3064063897, 567369902, 3978526132, 785429123,
+        1819220001, 2438764531, 3732420453, 1743880139, 2425803191, 595114243, 467423708,
+        2039019638, 66877345, 544643408, 2787193179, 2189347339, 2372127647, 3852938190,
+        3263917962, 4136597348, 3473413399, 790823011, 1384999578, 2488297010, 3952700579,
+        3553486990, 425494942, 3233626307, 1031830785, 1009771,
+    ];
+
+    fn fp_add(x: &mut Fp, y: &Fp) {
+        unsafe { syscall_bls12381_fp_addmod(x.as_mut_ptr(), y.as_ptr()) }
+    }
+
+    fn fp_sub(x: &mut Fp, y: &Fp) {
+        unsafe { syscall_bls12381_fp_submod(x.as_mut_ptr(), y.as_ptr()) }
+    }
+
+    fn fp_mul(x: &mut Fp, y: &Fp) {
+        unsafe { syscall_bls12381_fp_mulmod(x.as_mut_ptr(), y.as_ptr()) }
+    }
+
+    fn fp2_add(x: &mut Fp2, y: &Fp2) {
+        unsafe {
+            syscall_bls12381_fp2_addmod(x as *mut Fp2 as *mut u32, y as *const Fp2 as *const u32)
+        }
+    }
+
+    fn fp2_sub(x: &mut Fp2, y: &Fp2) {
+        unsafe {
+            syscall_bls12381_fp2_submod(x as *mut Fp2 as *mut u32, y as *const Fp2 as *const u32)
+        }
+    }
+
+    fn fp2_mul(x: &mut Fp2, y: &Fp2) {
+        unsafe {
+            syscall_bls12381_fp2_mulmod(x as *mut Fp2 as *mut u32, y as *const Fp2 as *const u32)
+        }
+    }
+
+    fn fp6_mul(x: &mut Fp6, y: &Fp6) {
+        unsafe {
+            syscall_bls12381_fp6_mulmod(x as *mut Fp6 as *mut u32, y as *const Fp6 as *const u32)
+        }
+    }
+}
+
+impl From<Bls12381Point> for G1Affine {
+    fn from(point: Bls12381Point) -> Self {
+        match point.0 {
+            WeierstrassPoint::Infinity => Self::identity(),
+            WeierstrassPoint::Affine(limbs) => Self::new_unchecked(
+                limbs[..N / 2].try_into().unwrap(),
+                limbs[N / 2..].try_into().unwrap(),
+            ),
+        }
+    }
+}
+
+/// Computes the optimal ate pairing `e(p, q)`.
+pub fn pairing(p: &G1Affine, q: &G2Affine) -> Fp12 {
+    pairing::pairing(p, q)
+}
+
+/// Checks whether `e(p_1, q_1) * ... * e(p_n, q_n) == 1`, sharing a single final exponentiation
+/// between all pairs. This is the check performed when verifying BLS signatures.
+///
+/// The points must be in G1 and G2, see [`pairing::pairing_check`].
+pub fn pairing_check(pairs: &[(G1Affine, G2Affine)]) -> bool {
+    pairing::pairing_check(pairs)
+}
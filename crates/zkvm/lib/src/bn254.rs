@@ -1,6 +1,9 @@
 use crate::{
-    syscall_bn254_add, syscall_bn254_double,
-    utils::{AffinePoint, WeierstrassAffinePoint, WeierstrassPoint},
+    pairing::{self, PairingCurve, TwistType},
+    syscall_bn254_add, syscall_bn254_double, syscall_bn254_fp2_addmod, syscall_bn254_fp2_mulmod,
+    syscall_bn254_fp2_submod, syscall_bn254_fp6_mulmod, syscall_bn254_fp_addmod,
+    syscall_bn254_fp_mulmod, syscall_bn254_fp_submod, syscall_bn254_scalar_mul,
+    utils::{AffinePoint, WeierstrassAffinePoint, WeierstrassPoint, WeierstrassScalarMul},
 };
 
 /// The number of limbs in [Bn254AffinePoint].
@@ -21,6 +24,17 @@ impl WeierstrassAffinePoint<N> for Bn254Point {
     }
 }
 
+impl WeierstrassScalarMul<N> for Bn254Point {
+    const ORDER: [u32; 8] = [
+        0xF0000001, 0x43E1F593, 0x79B97091, 0x2833E848, 0x8181585D, 0xB85045B6, 0xE131A029,
+        0x30644E72,
+    ];
+
+    unsafe fn scalar_mul_syscall(limbs: &mut [u32; N], scalar: &[u32; 8]) {
+        syscall_bn254_scalar_mul(limbs, scalar);
+    }
+}
+
 impl AffinePoint<N> for Bn254Point {
     /// The generator has been taken from py_pairing python library by the Ethereum Foundation:
     ///
@@ -78,3 +92,198 @@ impl AffinePoint<N> for Bn254Point {
         }
     }
 }
+
+/// The BN254 curve, used to instantiate the pairing.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Bn254;
+
+/// An element of the BN254 base field.
+pub type Fp = [u32; N / 2];
+/// An element of the BN254 quadratic extension.
+pub type Fp2 = pairing::Fp2<Bn254>;
+/// An element of the BN254 sextic extension.
+pub type Fp6 = pairing::Fp6<Bn254>;
+/// An element of the BN254 degree twelve extension, which contains the pairing target group.
+pub type Fp12 = pairing::Fp12<Bn254>;
+/// A point of the BN254 G1 group.
+pub type G1Affine = pairing::G1Affine<Bn254>;
+/// A point of the BN254 G2 group.
+pub type G2Affine = pairing::G2Affine<Bn254>;
+
+/// `ξ^((p - 1) / 3)`, which is both the Frobenius coefficient of `v` and the twist coefficient of
+/// the `x` coordinate of `π(Q)`.
+const FROBENIUS_COEFF_FP6_C1: Fp2 = Fp2::new(
+    [393172285, 2581828951, 3267572492, 3079455504, 4116296003, 1275849788, 1333334519, 800278424],
+    [1678756770, 375772444, 192716238, 850274845, 3613001620, 1269091467, 1642835488, 382330192],
+);
+
+/// `ξ^((p - 1) / 2)`, the twist coefficient of the `y` coordinate of `π(Q)`.
+const TWIST_MUL_BY_Q_Y: Fp2 = Fp2::new(
+    [1906316122, 3696492870, 2848545176, 3685409005, 3068328377, 3697198744, 1218115036, 104657669],
+    [639873251, 2194898787, 2409782226, 562068937, 3967381291, 117749159, 2889942170, 130038972],
+);
+
+impl PairingCurve for Bn254 {
+    type Fp = Fp;
+
+    const FP_ZERO: Fp = [0; N / 2];
+
+    const FP_ONE: Fp = [1, 0, 0, 0, 0, 0, 0, 0];
+
+    const MODULUS: Fp = [
+        3632069959, 1008765974, 1752287885, 2541841041, 2172737629, 3092268470, 3778125865,
+        811880050,
+    ];
+
+    const MODULUS_MINUS_TWO: Fp = [
+        3632069957, 1008765974, 1752287885, 2541841041, 2172737629, 3092268470, 3778125865,
+        811880050,
+    ];
+
+    const TWO_INV: Fp = [
+        1816034980, 2651866635, 3023627590, 3418404168, 1086368814, 3693617883, 1889062932,
+        405940025,
+    ];
+
+    const FP6_NON_RESIDUE: Fp2 = Fp2::new([9, 0, 0, 0, 0, 0, 0, 0], [1, 0, 0, 0, 0, 0, 0, 0]);
+
+    const FROBENIUS_COEFF_FP6_C1: Fp2 = FROBENIUS_COEFF_FP6_C1;
+
+    const FROBENIUS_COEFF_FP6_C2: Fp2 = Fp2::new(
+        [
+            2451482466, 2223644501, 3197430898, 3543361015, 1511530116, 2163458231, 1693362177,
+            95768414,
+        ],
+        [
+            3442180390, 3241887505, 467584867, 914739946, 990583954, 2671411376, 3892153070,
+            739532507,
+        ],
+    );
+
+    const FROBENIUS_COEFF_FP12_C1: Fp2 = Fp2::new(
+        [
+            3704218736, 3591058906, 690954614, 1548885512, 1994820448, 3904479197, 677750751,
+            310687516,
+        ],
+        [
+            2163434156, 3395088479, 2398013413, 1954124407, 303369102, 2788326654, 3036342246,
+            610899699,
+        ],
+    );
+
+    const B: Fp = [3, 0, 0, 0, 0, 0, 0, 0];
+
+    const G1_IS_PRIME_ORDER: bool = true;
+
+    /// `3 / ξ`.
+    const TWIST_B: Fp2 = Fp2::new(
+        [
+            614545637, 845670108, 1507585955, 3048523237, 467692227, 2176719001, 3468208814,
+            722771264,
+        ],
+        [
+            2244154834, 3835870470, 3844937810, 2807013508, 4007198196, 3442257837, 988872404,
+            9900976,
+        ],
+    );
+
+    const SCALAR_ORDER: [u32; 8] = <Bn254Point as WeierstrassScalarMul<N>>::ORDER;
+
+    const TWIST_TYPE: TwistType = TwistType::D;
+
+    /// `6x + 2` for `x = 4965661367192848881`.
+    const ATE_LOOP_COUNT: u128 = 0x19d797039be763ba8;
+
+    const ATE_LOOP_COUNT_IS_NEGATIVE: bool = false;
+
+    const FINAL_EXPONENT_HARD: &'static [u32] = &[
+        3437183665, 3894129794, 4103878356, 1522490564, 497030397, 4044705406, 2278406863,
+        3704075340, 2504773514, 2862891491, 3574161187, 1804107094, 4093481158, 2035818006,
+        3515425405, 991630165, 1636265835, 1314036312, 3585155704, 1812903202, 2199355311,
+        857653585, 185031085, 29010545,
+    ];
+
+    fn fp_add(x: &mut Fp, y: &Fp) {
+        unsafe { syscall_bn254_fp_addmod(x.as_mut_ptr(), y.as_ptr()) }
+    }
+
+    fn fp_sub(x: &mut Fp, y: &Fp) {
+        unsafe { syscall_bn254_fp_submod(x.as_mut_ptr(), y.as_ptr()) }
+    }
+
+    fn fp_mul(x: &mut Fp, y: &Fp) {
+        unsafe { syscall_bn254_fp_mulmod(x.as_mut_ptr(), y.as_ptr()) }
+    }
+
+    fn fp2_add(x: &mut Fp2, y: &Fp2) {
+        unsafe {
+            syscall_bn254_fp2_addmod(x as *mut Fp2 as *mut u32, y as *const Fp2 as *const u32)
+        }
+    }
+
+    fn fp2_sub(x: &mut Fp2, y: &Fp2) {
+        unsafe {
+            syscall_bn254_fp2_submod(x as *mut Fp2 as *mut u32, y as *const Fp2 as *const u32)
+        }
+    }
+
+    fn fp2_mul(x: &mut Fp2, y: &Fp2) {
+        unsafe {
+            syscall_bn254_fp2_mulmod(x as *mut Fp2 as *mut u32, y as *const Fp2 as *const u32)
+        }
+    }
+
+    fn fp6_mul(x: &mut Fp6, y: &Fp6) {
+        unsafe {
+            syscall_bn254_fp6_mulmod(x as *mut Fp6 as *mut u32, y as *const Fp6 as *const u32)
+        }
+    }
+
+    /// Adds `π(Q)` and `-π^2(Q)` to the accumulator, as required by the optimal ate pairing on BN
+    /// curves.
+    fn miller_loop_tail(
+        f: &mut Fp12,
+        r: &mut pairing::G2Projective<Self>,
+        q: &G2Affine,
+        p: &G1Affine,
+    ) {
+        let q1 = G2Affine::new_unchecked(
+            q.x.conjugate().mul(&FROBENIUS_COEFF_FP6_C1),
+            q.y.conjugate().mul(&TWIST_MUL_BY_Q_Y),
+        );
+        let q2 = G2Affine::new_unchecked(
+            q1.x.conjugate().mul(&FROBENIUS_COEFF_FP6_C1),
+            q1.y.conjugate().mul(&TWIST_MUL_BY_Q_Y).neg(),
+        );
+
+        let coeffs = r.add_in_place(&q1);
+        *f = pairing::ell(f, &coeffs, p);
+        let coeffs = r.add_in_place(&q2);
+        *f = pairing::ell(f, &coeffs, p);
+    }
+}
+
+impl From<Bn254Point> for G1Affine {
+    fn from(point: Bn254Point) -> Self {
+        match point.0 {
+            WeierstrassPoint::Infinity => Self::identity(),
+            WeierstrassPoint::Affine(limbs) => Self::new_unchecked(
+                limbs[..N / 2].try_into().unwrap(),
+                limbs[N / 2..].try_into().unwrap(),
+            ),
+        }
+    }
+}
+
+/// Computes the optimal ate pairing `e(p, q)`.
+pub fn pairing(p: &G1Affine, q: &G2Affine) -> Fp12 {
+    pairing::pairing(p, q)
+}
+
+/// Checks whether `e(p_1, q_1) * ... * e(p_n, q_n) == 1`, sharing a single final exponentiation
+/// between all pairs.
+///
+/// The points must be in G1 and G2, see [`pairing::pairing_check`].
+pub fn pairing_check(pairs: &[(G1Affine, G2Affine)]) -> bool {
+    pairing::pairing_check(pairs)
+}
@@ -11,7 +11,7 @@
 //! In summary, ZKM overrides curve arithmetic entirely, and patches upstream field operations
 //! to be more efficient in the VM, such as `sqrt` or `inverse`.
 
-use crate::utils::AffinePoint as ZKMAffinePointTrait;
+use crate::utils::{AffinePoint as ZKMAffinePointTrait, WeierstrassScalarMul};
 
 use elliptic_curve::{
     ff, generic_array::typenum::consts::U32, subtle::CtOption, CurveArithmetic, FieldBytes,
@@ -42,7 +42,8 @@ type FIELD_BYTES_SIZE = U32;
 /// Patched crates implement this trait to take advantage of ZKM-specific acceleration in the zkVM
 /// context.
 ///
-/// Note: This trait only supports 32 byte base field curves.
+/// Note: This trait only supports 32 byte base field curves with a cofactor of one, so that every
+/// point on the curve is in the prime-order subgroup the scalar multiplication precompile expects.
 pub trait ECDSACurve
 where
     Self: CurveArithmetic<
@@ -83,7 +84,13 @@ pub type FieldElement<C> = <C as ECDSACurve>::FieldElement;
 
 /// Alias trait for the [`ZKMAffinePointTrait`] with 32 byte field elements.
 pub trait ECDSAPoint:
-    ZKMAffinePointTrait<POINT_LIMBS> + Clone + Copy + Debug + Send + Sync
+    ZKMAffinePointTrait<POINT_LIMBS>
+    + WeierstrassScalarMul<POINT_LIMBS>
+    + Clone
+    + Copy
+    + Debug
+    + Send
+    + Sync
 {
     #[inline]
     fn from(x: &[u8], y: &[u8]) -> Self {
@@ -92,7 +99,13 @@ pub trait ECDSAPoint:
 }
 
 impl<P> ECDSAPoint for P where
-    P: ZKMAffinePointTrait<POINT_LIMBS> + Clone + Copy + Debug + Send + Sync
+    P: ZKMAffinePointTrait<POINT_LIMBS>
+        + WeierstrassScalarMul<POINT_LIMBS>
+        + Clone
+        + Copy
+        + Debug
+        + Send
+        + Sync
 {
 }
 
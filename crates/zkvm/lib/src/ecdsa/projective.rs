@@ -5,6 +5,7 @@
 //! Note: When performing curve operations, accelerated crates for Ziren use affine arithmetic instead of projective arithmetic for performance.
 
 use super::{AffinePoint, ECDSACurve, ZKMAffinePointTrait};
+use crate::utils::WeierstrassScalarMul;
 
 use elliptic_curve::{
     group::{cofactor::CofactorGroup, prime::PrimeGroup},
@@ -131,11 +132,12 @@ impl<C: ECDSACurve> LinearCombination for ProjectivePoint<C> {
         let x = x.to_zkvm_point();
         let y = y.to_zkvm_point();
 
-        let a_bits_le = be_bytes_to_le_bits(k.to_repr().as_ref());
-        let b_bits_le = be_bytes_to_le_bits(l.to_repr().as_ref());
+        let k = be_bytes_to_le_words(k.to_repr());
+        let l = be_bytes_to_le_words(l.to_repr());
 
-        let zkm_point =
-            C::ZKMAffinePoint::multi_scalar_multiplication(&a_bits_le, x, &b_bits_le, y);
+        // SAFETY: The points are on a curve with a cofactor of one, so they are in the
+        // prime-order subgroup, and the scalars are reduced modulo its order.
+        let zkm_point = unsafe { C::ZKMAffinePoint::multi_scalar_mul_unchecked(&[k, l], &[x, y]) };
 
         Self::from_zkvm_point(zkm_point)
     }
@@ -147,16 +149,22 @@ impl<C: ECDSACurve, T: Borrow<C::Scalar>> Mul<T> for ProjectivePoint<C> {
     type Output = ProjectivePoint<C>;
 
     fn mul(mut self, rhs: T) -> Self::Output {
-        let zkm_point = self.as_mut_zkvm_point();
-        zkm_point.mul_assign(&be_bytes_to_le_words(rhs.borrow().to_repr()));
-
+        self.mul_assign(rhs);
         self
     }
 }
 
 impl<C: ECDSACurve, T: Borrow<C::Scalar>> MulAssign<T> for ProjectivePoint<C> {
     fn mul_assign(&mut self, rhs: T) {
-        self.as_mut_zkvm_point().mul_assign(&be_bytes_to_le_words(rhs.borrow().to_repr()));
+        let k = be_bytes_to_le_words(rhs.borrow().to_repr());
+        if self.is_identity().into() || k == [0u32; 8] {
+            *self = Self::identity();
+            return;
+        }
+
+        // SAFETY: The point is on a curve with a cofactor of one and is not the identity, so it is
+        // in the prime-order subgroup, and the scalar is reduced modulo its order and not zero.
+        unsafe { self.as_mut_zkvm_point().scalar_mul_assign_unchecked(&k) };
     }
 }
 
@@ -401,18 +409,3 @@ fn be_bytes_to_le_words<T: AsMut<[u8]>>(mut bytes: T) -> [u32; 8] {
     let mut iter = bytes.chunks(4).map(|b| u32::from_le_bytes(b.try_into().unwrap()));
     core::array::from_fn(|_| iter.next().unwrap())
 }
-
-/// Convert big-endian bytes with the most significant bit first to little-endian bytes with the least significant bit first.
-/// Panics: If the bytes have len > 32.
-#[inline]
-fn be_bytes_to_le_bits(be_bytes: &[u8]) -> [bool; 256] {
-    let mut bits = [false; 256];
-    // Reverse the byte order to little-endian.
-    for (i, &byte) in be_bytes.iter().rev().enumerate() {
-        for j in 0..8 {
-            // Flip the bit order so the least significant bit is now the first bit of the chunk.
-            bits[i * 8 + j] = ((byte >> j) & 1) == 1;
-        }
-    }
-    bits
-}
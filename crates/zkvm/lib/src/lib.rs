@@ -2,6 +2,8 @@
 //!
 //! Documentation for these syscalls can be found in the zkVM entrypoint
 //! `zkm_zkvm::syscalls` module.
+pub mod blake2b;
+pub mod blake3;
 pub mod bls12381;
 pub mod bn254;
 pub mod boolean_circuit_garble;
@@ -11,10 +13,12 @@ pub mod ecdsa;
 pub mod ed25519;
 pub mod io;
 pub mod keccak256;
+pub mod pairing;
 pub mod poseidon2;
 pub mod secp256k1;
 pub mod secp256r1;
 pub mod sha3;
+pub mod sha512;
 pub mod unconstrained;
 pub mod utils;
 #[cfg(feature = "verify")]
@@ -36,6 +40,18 @@ extern "C" {
     /// Executes the SHA-256 compress operation on the given word array and a given state.
     pub fn syscall_sha256_compress(w: *mut [u32; 64], state: *mut [u32; 8]);
 
+    /// Executes the SHA-512 extend operation on the given message schedule.
+    pub fn syscall_sha512_extend(w: *mut [u64; 80]);
+
+    /// Executes the SHA-512 compress operation on the given message schedule and a given state.
+    pub fn syscall_sha512_compress(w: *mut [u64; 80], state: *mut [u64; 8]);
+
+    /// Executes the rounds of the BLAKE3 compression function on the given working state.
+    pub fn syscall_blake3_compress_inner(state: *mut [u32; 16], msg: *const [u32; 16]);
+
+    /// Executes the rounds of the BLAKE2b compression function on the given working state.
+    pub fn syscall_blake2b_compress_inner(state: *mut [u64; 16], msg: *const [u64; 16]);
+
     /// Executes an Ed25519 curve addition on the given points.
     pub fn syscall_ed_add(p: *mut [u32; 16], q: *const [u32; 16]);
 
@@ -48,6 +64,9 @@ extern "C" {
     /// Executes an Secp256k1 curve doubling on the given point.
     pub fn syscall_secp256k1_double(p: *mut [u32; 16]);
 
+    /// Executes a Secp256k1 scalar multiplication on the given point.
+    pub fn syscall_secp256k1_scalar_mul(p: *mut [u32; 16], k: *const [u32; 8]);
+
     /// Executes an Secp256k1 curve decompression on the given point.
     pub fn syscall_secp256k1_decompress(point: &mut [u8; 64], is_odd: bool);
 
@@ -57,6 +76,9 @@ extern "C" {
     /// Executes an Secp256r1 curve doubling on the given point.
     pub fn syscall_secp256r1_double(p: *mut [u32; 16]);
 
+    /// Executes a Secp256r1 scalar multiplication on the given point.
+    pub fn syscall_secp256r1_scalar_mul(p: *mut [u32; 16], k: *const [u32; 8]);
+
     /// Executes an Secp256r1 curve decompression on the given point.
     pub fn syscall_secp256r1_decompress(point: &mut [u8; 64], is_odd: bool);
 
@@ -66,12 +88,18 @@ extern "C" {
     /// Executes a Bn254 curve doubling on the given point.
     pub fn syscall_bn254_double(p: *mut [u32; 16]);
 
+    /// Executes a Bn254 scalar multiplication on the given point.
+    pub fn syscall_bn254_scalar_mul(p: *mut [u32; 16], k: *const [u32; 8]);
+
     /// Executes a BLS12-381 curve addition on the given points.
     pub fn syscall_bls12381_add(p: *mut [u32; 24], q: *const [u32; 24]);
 
     /// Executes a BLS12-381 curve doubling on the given point.
     pub fn syscall_bls12381_double(p: *mut [u32; 24]);
 
+    /// Executes a BLS12-381 scalar multiplication on the given point.
+    pub fn syscall_bls12381_scalar_mul(p: *mut [u32; 24], k: *const [u32; 8]);
+
     /// Executes the Keccak Sponge
     pub fn syscall_keccak_sponge(input: *const u32, result: *mut [u32; 17]);
 
@@ -139,6 +167,9 @@ extern "C" {
     /// Executes a BLS12-381 Fp2 multiplication on the given inputs.
     pub fn syscall_bls12381_fp2_mulmod(p: *mut u32, q: *const u32);
 
+    /// Executes a BLS12-381 Fp6 multiplication on the given inputs.
+    pub fn syscall_bls12381_fp6_mulmod(p: *mut u32, q: *const u32);
+
     /// Executes a BN254 field addition on the given inputs.
     pub fn syscall_bn254_fp_addmod(p: *mut u32, q: *const u32);
 
@@ -157,6 +188,9 @@ extern "C" {
     /// Executes a BN254 Fp2 multiplication on the given inputs.
     pub fn syscall_bn254_fp2_mulmod(p: *mut u32, q: *const u32);
 
+    /// Executes a BN254 Fp6 multiplication on the given inputs.
+    pub fn syscall_bn254_fp6_mulmod(p: *mut u32, q: *const u32);
+
     /// Reads a buffer from the input stream.
     pub fn read_vec_raw() -> ReadVecResult;
 }
@@ -0,0 +1,651 @@
+//! Optimal ate pairing over the `Fp2 -> Fp6 -> Fp12` tower, built on top of the base field,
+//! quadratic extension and sextic extension precompiles.
+//!
+//! The tower is `Fp2 = Fp[u] / (u^2 + 1)`, `Fp6 = Fp2[v] / (v^3 - ξ)` and
+//! `Fp12 = Fp6[w] / (w^2 - v)`, where `ξ` is the curve specific non-residue. All field elements are
+//! expected to be in canonical form, i.e. strictly smaller than the modulus.
+
+/// The type of the sextic twist used to represent G2.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TwistType {
+    /// The twist `y^2 = x^3 + b * ξ`.
+    M,
+    /// The twist `y^2 = x^3 + b / ξ`.
+    D,
+}
+
+/// A pairing-friendly curve whose tower arithmetic is accelerated by precompiles.
+pub trait PairingCurve: Copy + Clone + core::fmt::Debug + PartialEq + Eq + Sized {
+    /// A base field element as little-endian `u32` limbs.
+    type Fp: Copy + Clone + core::fmt::Debug + PartialEq + Eq + AsRef<[u32]>;
+
+    /// The zero element of the base field.
+    const FP_ZERO: Self::Fp;
+
+    /// The one element of the base field.
+    const FP_ONE: Self::Fp;
+
+    /// The modulus of the base field.
+    const MODULUS: Self::Fp;
+
+    /// The modulus minus two, used to compute inverses via Fermat's little theorem.
+    const MODULUS_MINUS_TWO: Self::Fp;
+
+    /// The inverse of two in the base field.
+    const TWO_INV: Self::Fp;
+
+    /// The non-residue `ξ` defining `Fp6 = Fp2[v] / (v^3 - ξ)`.
+    const FP6_NON_RESIDUE: Fp2<Self>;
+
+    /// `ξ^((p - 1) / 3)`, the Frobenius coefficient of `v`.
+    const FROBENIUS_COEFF_FP6_C1: Fp2<Self>;
+
+    /// `ξ^(2 (p - 1) / 3)`, the Frobenius coefficient of `v^2`.
+    const FROBENIUS_COEFF_FP6_C2: Fp2<Self>;
+
+    /// `ξ^((p - 1) / 6)`, the Frobenius coefficient of `w`.
+    const FROBENIUS_COEFF_FP12_C1: Fp2<Self>;
+
+    /// The `b` coefficient of the curve `y^2 = x^3 + b`.
+    const B: Self::Fp;
+
+    /// Whether the curve has prime order, so that every point of it is in G1.
+    const G1_IS_PRIME_ORDER: bool;
+
+    /// The `b` coefficient of the twisted curve.
+    const TWIST_B: Fp2<Self>;
+
+    /// The order `r` of G1 and G2, as little-endian `u32` limbs.
+    const SCALAR_ORDER: [u32; 8];
+
+    /// The type of the twist.
+    const TWIST_TYPE: TwistType;
+
+    /// The absolute value of the Miller loop count.
+    const ATE_LOOP_COUNT: u128;
+
+    /// Whether the Miller loop count is negative.
+    const ATE_LOOP_COUNT_IS_NEGATIVE: bool;
+
+    /// The hard part of the final exponent, `(p^4 - p^2 + 1) / r`, as little-endian `u32` limbs.
+    const FINAL_EXPONENT_HARD: &'static [u32];
+
+    /// Computes `x = x + y` in the base field.
+    fn fp_add(x: &mut Self::Fp, y: &Self::Fp);
+
+    /// Computes `x = x - y` in the base field.
+    fn fp_sub(x: &mut Self::Fp, y: &Self::Fp);
+
+    /// Computes `x = x * y` in the base field.
+    fn fp_mul(x: &mut Self::Fp, y: &Self::Fp);
+
+    /// Computes `x = x + y` in the quadratic extension.
+    fn fp2_add(x: &mut Fp2<Self>, y: &Fp2<Self>);
+
+    /// Computes `x = x - y` in the quadratic extension.
+    fn fp2_sub(x: &mut Fp2<Self>, y: &Fp2<Self>);
+
+    /// Computes `x = x * y` in the quadratic extension.
+    fn fp2_mul(x: &mut Fp2<Self>, y: &Fp2<Self>);
+
+    /// Computes `x = x * y` in the sextic extension.
+    fn fp6_mul(x: &mut Fp6<Self>, y: &Fp6<Self>);
+
+    /// Runs the curve specific steps after the main Miller loop, e.g. the additions of the
+    /// Frobenius images of `Q` for BN curves.
+    fn miller_loop_tail(
+        _f: &mut Fp12<Self>,
+        _r: &mut G2Projective<Self>,
+        _q: &G2Affine<Self>,
+        _p: &G1Affine<Self>,
+    ) {
+    }
+}
+
+/// An element `c0 + c1 * u` of the quadratic extension.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[repr(C)]
+pub struct Fp2<C: PairingCurve> {
+    pub c0: C::Fp,
+    pub c1: C::Fp,
+}
+
+/// An element `c0 + c1 * v + c2 * v^2` of the sextic extension.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[repr(C)]
+pub struct Fp6<C: PairingCurve> {
+    pub c0: Fp2<C>,
+    pub c1: Fp2<C>,
+    pub c2: Fp2<C>,
+}
+
+/// An element `c0 + c1 * w` of the degree twelve extension.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[repr(C)]
+pub struct Fp12<C: PairingCurve> {
+    pub c0: Fp6<C>,
+    pub c1: Fp6<C>,
+}
+
+/// Raises `x` to the power given by the little-endian `u32` limbs of `exp`.
+fn fp_pow<C: PairingCurve>(x: &C::Fp, exp: &[u32]) -> C::Fp {
+    let mut res = C::FP_ONE;
+    for limb in exp.iter().rev() {
+        for i in (0..32).rev() {
+            let tmp = res;
+            C::fp_mul(&mut res, &tmp);
+            if (limb >> i) & 1 == 1 {
+                C::fp_mul(&mut res, x);
+            }
+        }
+    }
+    res
+}
+
+fn fp_neg<C: PairingCurve>(x: &C::Fp) -> C::Fp {
+    let mut res = C::FP_ZERO;
+    C::fp_sub(&mut res, x);
+    res
+}
+
+fn fp_mul<C: PairingCurve>(x: &C::Fp, y: &C::Fp) -> C::Fp {
+    let mut res = *x;
+    C::fp_mul(&mut res, y);
+    res
+}
+
+/// Whether `x` is strictly smaller than the modulus.
+fn fp_is_canonical<C: PairingCurve>(x: &C::Fp) -> bool {
+    x.as_ref().iter().rev().cmp(C::MODULUS.as_ref().iter().rev()) == core::cmp::Ordering::Less
+}
+
+/// A point in homogeneous projective coordinates `(X, Y, Z)`.
+type Projective<C> = (Fp2<C>, Fp2<C>, Fp2<C>);
+
+/// Adds two points of the curve `y^2 = x^3 + b`, where `b3 = 3 * b`, with the complete formulas
+/// of <https://eprint.iacr.org/2015/1060.pdf> (Algorithm 7), which also double and add the identity.
+fn complete_add<C: PairingCurve>(
+    (x1, y1, z1): &Projective<C>,
+    (x2, y2, z2): &Projective<C>,
+    b3: &Fp2<C>,
+) -> Projective<C> {
+    let t0 = x1.mul(x2);
+    let t1 = y1.mul(y2);
+    let t2 = z1.mul(z2);
+    let t3 = x1.add(y1).mul(&x2.add(y2)).sub(&t0.add(&t1));
+    let t4 = y1.add(z1).mul(&y2.add(z2)).sub(&t1.add(&t2));
+    let y3 = x1.add(z1).mul(&x2.add(z2)).sub(&t0.add(&t2));
+    let t0 = t0.double().add(&t0);
+    let t2 = b3.mul(&t2);
+    let z3 = t1.add(&t2);
+    let t1 = t1.sub(&t2);
+    let y3 = b3.mul(&y3);
+    let x3 = t3.mul(&t1).sub(&t4.mul(&y3));
+    let y3 = t1.mul(&z3).add(&y3.mul(&t0));
+    let z3 = z3.mul(&t4).add(&t0.mul(&t3));
+    (x3, y3, z3)
+}
+
+/// Whether `(x, y)` is on the curve `y^2 = x^3 + b` and, unless `prime_order`, whether it is in
+/// the subgroup of order `r`.
+///
+/// Points of G1 are given with zero `c1` coefficients, which keeps their arithmetic in the base
+/// field.
+fn is_on_curve_and_in_subgroup<C: PairingCurve>(
+    x: &Fp2<C>,
+    y: &Fp2<C>,
+    b: &Fp2<C>,
+    prime_order: bool,
+) -> bool {
+    if y.square() != x.square().mul(x).add(b) {
+        return false;
+    }
+    if prime_order {
+        return true;
+    }
+
+    // Check that [r](x, y) is the identity.
+    let b3 = b.double().add(b);
+    let point = (*x, *y, Fp2::ONE);
+    let mut acc = (Fp2::ZERO, Fp2::ONE, Fp2::ZERO);
+    for limb in C::SCALAR_ORDER.iter().rev() {
+        for i in (0..32).rev() {
+            acc = complete_add(&acc, &acc, &b3);
+            if (limb >> i) & 1 == 1 {
+                acc = complete_add(&acc, &point, &b3);
+            }
+        }
+    }
+    acc.2 == Fp2::ZERO
+}
+
+impl<C: PairingCurve> Fp2<C> {
+    /// The zero element.
+    pub const ZERO: Self = Self { c0: C::FP_ZERO, c1: C::FP_ZERO };
+
+    /// The one element.
+    pub const ONE: Self = Self { c0: C::FP_ONE, c1: C::FP_ZERO };
+
+    /// Creates a new element from its coefficients.
+    pub const fn new(c0: C::Fp, c1: C::Fp) -> Self {
+        Self { c0, c1 }
+    }
+
+    pub fn add(&self, other: &Self) -> Self {
+        let mut res = *self;
+        C::fp2_add(&mut res, other);
+        res
+    }
+
+    pub fn sub(&self, other: &Self) -> Self {
+        let mut res = *self;
+        C::fp2_sub(&mut res, other);
+        res
+    }
+
+    pub fn mul(&self, other: &Self) -> Self {
+        let mut res = *self;
+        C::fp2_mul(&mut res, other);
+        res
+    }
+
+    pub fn square(&self) -> Self {
+        self.mul(self)
+    }
+
+    pub fn double(&self) -> Self {
+        self.add(self)
+    }
+
+    pub fn neg(&self) -> Self {
+        Self::ZERO.sub(self)
+    }
+
+    /// Multiplies both coefficients by a base field element.
+    pub fn mul_by_fp(&self, other: &C::Fp) -> Self {
+        Self { c0: fp_mul::<C>(&self.c0, other), c1: fp_mul::<C>(&self.c1, other) }
+    }
+
+    /// Multiplies by the non-residue `ξ`.
+    pub fn mul_by_non_residue(&self) -> Self {
+        self.mul(&C::FP6_NON_RESIDUE)
+    }
+
+    /// Returns `c0 - c1 * u`, which is also the Frobenius map of this element.
+    pub fn conjugate(&self) -> Self {
+        Self { c0: self.c0, c1: fp_neg::<C>(&self.c1) }
+    }
+
+    /// Returns the multiplicative inverse, or `None` for zero.
+    pub fn inverse(&self) -> Option<Self> {
+        if *self == Self::ZERO {
+            return None;
+        }
+        let mut norm = fp_mul::<C>(&self.c0, &self.c0);
+        C::fp_add(&mut norm, &fp_mul::<C>(&self.c1, &self.c1));
+        let norm_inv = fp_pow::<C>(&norm, C::MODULUS_MINUS_TWO.as_ref());
+        Some(self.conjugate().mul_by_fp(&norm_inv))
+    }
+}
+
+impl<C: PairingCurve> Fp6<C> {
+    /// The zero element.
+    pub const ZERO: Self = Self { c0: Fp2::ZERO, c1: Fp2::ZERO, c2: Fp2::ZERO };
+
+    /// The one element.
+    pub const ONE: Self = Self { c0: Fp2::ONE, c1: Fp2::ZERO, c2: Fp2::ZERO };
+
+    /// Creates a new element from its coefficients.
+    pub const fn new(c0: Fp2<C>, c1: Fp2<C>, c2: Fp2<C>) -> Self {
+        Self { c0, c1, c2 }
+    }
+
+    pub fn add(&self, other: &Self) -> Self {
+        Self { c0: self.c0.add(&other.c0), c1: self.c1.add(&other.c1), c2: self.c2.add(&other.c2) }
+    }
+
+    pub fn sub(&self, other: &Self) -> Self {
+        Self { c0: self.c0.sub(&other.c0), c1: self.c1.sub(&other.c1), c2: self.c2.sub(&other.c2) }
+    }
+
+    pub fn mul(&self, other: &Self) -> Self {
+        let mut res = *self;
+        C::fp6_mul(&mut res, other);
+        res
+    }
+
+    pub fn square(&self) -> Self {
+        self.mul(self)
+    }
+
+    pub fn neg(&self) -> Self {
+        Self::ZERO.sub(self)
+    }
+
+    /// Multiplies by `v`.
+    pub fn mul_by_v(&self) -> Self {
+        Self { c0: self.c2.mul_by_non_residue(), c1: self.c0, c2: self.c1 }
+    }
+
+    /// Applies the Frobenius map `x -> x^p`.
+    pub fn frobenius_map(&self) -> Self {
+        Self {
+            c0: self.c0.conjugate(),
+            c1: self.c1.conjugate().mul(&C::FROBENIUS_COEFF_FP6_C1),
+            c2: self.c2.conjugate().mul(&C::FROBENIUS_COEFF_FP6_C2),
+        }
+    }
+
+    /// Returns the multiplicative inverse, or `None` for zero.
+    pub fn inverse(&self) -> Option<Self> {
+        let t0 = self.c0.square().sub(&self.c1.mul(&self.c2).mul_by_non_residue());
+        let t1 = self.c2.square().mul_by_non_residue().sub(&self.c0.mul(&self.c1));
+        let t2 = self.c1.square().sub(&self.c0.mul(&self.c2));
+        let norm =
+            self.c0.mul(&t0).add(&self.c2.mul(&t1).add(&self.c1.mul(&t2)).mul_by_non_residue());
+        let norm_inv = norm.inverse()?;
+        Some(Self { c0: t0.mul(&norm_inv), c1: t1.mul(&norm_inv), c2: t2.mul(&norm_inv) })
+    }
+}
+
+impl<C: PairingCurve> Fp12<C> {
+    /// The one element.
+    pub const ONE: Self = Self { c0: Fp6::ONE, c1: Fp6::ZERO };
+
+    /// Creates a new element from its coefficients.
+    pub const fn new(c0: Fp6<C>, c1: Fp6<C>) -> Self {
+        Self { c0, c1 }
+    }
+
+    pub fn is_one(&self) -> bool {
+        *self == Self::ONE
+    }
+
+    pub fn mul(&self, other: &Self) -> Self {
+        let aa = self.c0.mul(&other.c0);
+        let bb = self.c1.mul(&other.c1);
+        let c1 = self.c0.add(&self.c1).mul(&other.c0.add(&other.c1)).sub(&aa).sub(&bb);
+        Self { c0: aa.add(&bb.mul_by_v()), c1 }
+    }
+
+    pub fn square(&self) -> Self {
+        let ab = self.c0.mul(&self.c1);
+        let c0 = self
+            .c0
+            .add(&self.c1)
+            .mul(&self.c0.add(&self.c1.mul_by_v()))
+            .sub(&ab)
+            .sub(&ab.mul_by_v());
+        Self { c0, c1: ab.add(&ab) }
+    }
+
+    /// Returns `c0 - c1 * w`, which is the inverse of elements in the cyclotomic subgroup.
+    pub fn conjugate(&self) -> Self {
+        Self { c0: self.c0, c1: self.c1.neg() }
+    }
+
+    /// Returns the multiplicative inverse, or `None` for zero.
+    pub fn inverse(&self) -> Option<Self> {
+        let norm = self.c0.square().sub(&self.c1.square().mul_by_v());
+        let norm_inv = norm.inverse()?;
+        Some(Self { c0: self.c0.mul(&norm_inv), c1: self.c1.mul(&norm_inv).neg() })
+    }
+
+    /// Applies the Frobenius map `x -> x^p`.
+    pub fn frobenius_map(&self) -> Self {
+        let c1 = self.c1.frobenius_map();
+        Self {
+            c0: self.c0.frobenius_map(),
+            c1: Fp6 {
+                c0: c1.c0.mul(&C::FROBENIUS_COEFF_FP12_C1),
+                c1: c1.c1.mul(&C::FROBENIUS_COEFF_FP12_C1),
+                c2: c1.c2.mul(&C::FROBENIUS_COEFF_FP12_C1),
+            },
+        }
+    }
+
+    /// Raises this element to the power given by the little-endian `u32` limbs of `exp`.
+    pub fn pow(&self, exp: &[u32]) -> Self {
+        let mut res = Self::ONE;
+        for limb in exp.iter().rev() {
+            for i in (0..32).rev() {
+                res = res.square();
+                if (limb >> i) & 1 == 1 {
+                    res = res.mul(self);
+                }
+            }
+        }
+        res
+    }
+}
+
+/// A point of G1 in affine coordinates.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct G1Affine<C: PairingCurve> {
+    pub x: C::Fp,
+    pub y: C::Fp,
+    pub infinity: bool,
+}
+
+impl<C: PairingCurve> G1Affine<C> {
+    /// Creates a new point from its coordinates, or returns `None` unless it is a point of G1.
+    pub fn new(x: C::Fp, y: C::Fp) -> Option<Self> {
+        let point = Self::new_unchecked(x, y);
+        point.is_valid().then_some(point)
+    }
+
+    /// Creates a new point from its coordinates without checking that it is a point of G1.
+    ///
+    /// The pairings of a point that is not in G1 are meaningless, so the coordinates must come
+    /// from a trusted source, e.g. be constants.
+    pub const fn new_unchecked(x: C::Fp, y: C::Fp) -> Self {
+        Self { x, y, infinity: false }
+    }
+
+    /// Whether the point is the identity, or a point of G1 with canonical coordinates.
+    pub fn is_valid(&self) -> bool {
+        self.infinity
+            || (fp_is_canonical::<C>(&self.x)
+                && fp_is_canonical::<C>(&self.y)
+                && is_on_curve_and_in_subgroup(
+                    &Fp2::new(self.x, C::FP_ZERO),
+                    &Fp2::new(self.y, C::FP_ZERO),
+                    &Fp2::new(C::B, C::FP_ZERO),
+                    C::G1_IS_PRIME_ORDER,
+                ))
+    }
+
+    /// The point at infinity.
+    pub const fn identity() -> Self {
+        Self { x: C::FP_ZERO, y: C::FP_ZERO, infinity: true }
+    }
+
+    /// Returns the negation of this point.
+    pub fn neg(&self) -> Self {
+        Self { x: self.x, y: fp_neg::<C>(&self.y), infinity: self.infinity }
+    }
+}
+
+/// A point of G2 on the sextic twist in affine coordinates.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct G2Affine<C: PairingCurve> {
+    pub x: Fp2<C>,
+    pub y: Fp2<C>,
+    pub infinity: bool,
+}
+
+impl<C: PairingCurve> G2Affine<C> {
+    /// Creates a new point from its coordinates, or returns `None` unless it is a point of G2.
+    pub fn new(x: Fp2<C>, y: Fp2<C>) -> Option<Self> {
+        let point = Self::new_unchecked(x, y);
+        point.is_valid().then_some(point)
+    }
+
+    /// Creates a new point from its coordinates without checking that it is a point of G2.
+    ///
+    /// The pairings of a point that is not in G2 are meaningless, so the coordinates must come
+    /// from a trusted source, e.g. be constants.
+    pub const fn new_unchecked(x: Fp2<C>, y: Fp2<C>) -> Self {
+        Self { x, y, infinity: false }
+    }
+
+    /// Whether the point is the identity, or a point of G2 with canonical coordinates.
+    ///
+    /// The twist has a large cofactor, so this checks that the point is in the subgroup of order
+    /// `r` too.
+    pub fn is_valid(&self) -> bool {
+        self.infinity
+            || ([self.x.c0, self.x.c1, self.y.c0, self.y.c1].iter().all(fp_is_canonical::<C>)
+                && is_on_curve_and_in_subgroup(&self.x, &self.y, &C::TWIST_B, false))
+    }
+
+    /// The point at infinity.
+    pub const fn identity() -> Self {
+        Self { x: Fp2::ZERO, y: Fp2::ZERO, infinity: true }
+    }
+
+    /// Returns the negation of this point.
+    pub fn neg(&self) -> Self {
+        Self { x: self.x, y: self.y.neg(), infinity: self.infinity }
+    }
+}
+
+/// A point of G2 in homogeneous projective coordinates, used as the Miller loop accumulator.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct G2Projective<C: PairingCurve> {
+    x: Fp2<C>,
+    y: Fp2<C>,
+    z: Fp2<C>,
+}
+
+/// The coefficients of a line function, in the order used by the twist type.
+pub type LineCoeffs<C> = (Fp2<C>, Fp2<C>, Fp2<C>);
+
+impl<C: PairingCurve> G2Projective<C> {
+    fn from_affine(q: &G2Affine<C>) -> Self {
+        Self { x: q.x, y: q.y, z: Fp2::ONE }
+    }
+
+    /// Doubles the point and returns the tangent line, following
+    /// <https://eprint.iacr.org/2013/722.pdf> (Algorithm 26).
+    fn double_in_place(&mut self) -> LineCoeffs<C> {
+        let a = self.x.mul(&self.y).mul_by_fp(&C::TWO_INV);
+        let b = self.y.square();
+        let c = self.z.square();
+        let e = C::TWIST_B.mul(&c.double().add(&c));
+        let f = e.double().add(&e);
+        let g = b.add(&f).mul_by_fp(&C::TWO_INV);
+        let h = self.y.add(&self.z).square().sub(&b.add(&c));
+        let i = e.sub(&b);
+        let j = self.x.square();
+        let e_square = e.square();
+
+        self.x = a.mul(&b.sub(&f));
+        self.y = g.square().sub(&e_square.double().add(&e_square));
+        self.z = b.mul(&h);
+
+        let j3 = j.double().add(&j);
+        match C::TWIST_TYPE {
+            TwistType::M => (i, j3, h.neg()),
+            TwistType::D => (h.neg(), j3, i),
+        }
+    }
+
+    /// Adds an affine point and returns the line through both points, following
+    /// <https://eprint.iacr.org/2013/722.pdf> (Algorithm 27).
+    pub fn add_in_place(&mut self, q: &G2Affine<C>) -> LineCoeffs<C> {
+        let theta = self.y.sub(&q.y.mul(&self.z));
+        let lambda = self.x.sub(&q.x.mul(&self.z));
+        let c = theta.square();
+        let d = lambda.square();
+        let e = lambda.mul(&d);
+        let f = self.z.mul(&c);
+        let g = self.x.mul(&d);
+        let h = e.add(&f).sub(&g.double());
+
+        self.x = lambda.mul(&h);
+        self.y = theta.mul(&g.sub(&h)).sub(&e.mul(&self.y));
+        self.z = self.z.mul(&e);
+
+        let j = theta.mul(&q.x).sub(&lambda.mul(&q.y));
+        match C::TWIST_TYPE {
+            TwistType::M => (j, theta.neg(), lambda),
+            TwistType::D => (lambda, theta.neg(), j),
+        }
+    }
+}
+
+/// Multiplies `f` by the line with the given coefficients evaluated at `p`.
+pub fn ell<C: PairingCurve>(f: &Fp12<C>, coeffs: &LineCoeffs<C>, p: &G1Affine<C>) -> Fp12<C> {
+    let (c0, c1, c2) = coeffs;
+    let line = match C::TWIST_TYPE {
+        TwistType::M => Fp12::new(
+            Fp6::new(*c0, c1.mul_by_fp(&p.x), Fp2::ZERO),
+            Fp6::new(Fp2::ZERO, c2.mul_by_fp(&p.y), Fp2::ZERO),
+        ),
+        TwistType::D => Fp12::new(
+            Fp6::new(c0.mul_by_fp(&p.y), Fp2::ZERO, Fp2::ZERO),
+            Fp6::new(c1.mul_by_fp(&p.x), *c2, Fp2::ZERO),
+        ),
+    };
+    f.mul(&line)
+}
+
+/// Computes the product of the Miller loops of the given pairs.
+///
+/// Pairs containing a point at infinity contribute nothing to the product and are skipped.
+pub fn multi_miller_loop<C: PairingCurve>(pairs: &[(G1Affine<C>, G2Affine<C>)]) -> Fp12<C> {
+    let pairs = pairs.iter().filter(|(p, q)| !p.infinity && !q.infinity).collect::<Vec<_>>();
+    let mut rs = pairs.iter().map(|(_, q)| G2Projective::from_affine(q)).collect::<Vec<_>>();
+
+    let mut f = Fp12::ONE;
+    let num_bits = 128 - C::ATE_LOOP_COUNT.leading_zeros();
+    for i in (0..num_bits - 1).rev() {
+        f = f.square();
+        for ((p, _), r) in pairs.iter().zip(rs.iter_mut()) {
+            let coeffs = r.double_in_place();
+            f = ell(&f, &coeffs, p);
+        }
+        if (C::ATE_LOOP_COUNT >> i) & 1 == 1 {
+            for ((p, q), r) in pairs.iter().zip(rs.iter_mut()) {
+                let coeffs = r.add_in_place(q);
+                f = ell(&f, &coeffs, p);
+            }
+        }
+    }
+
+    for ((p, q), r) in pairs.iter().zip(rs.iter_mut()) {
+        C::miller_loop_tail(&mut f, r, q, p);
+    }
+
+    if C::ATE_LOOP_COUNT_IS_NEGATIVE {
+        f = f.conjugate();
+    }
+    f
+}
+
+/// Raises the output of the Miller loop to the power `(p^12 - 1) / r`.
+pub fn final_exponentiation<C: PairingCurve>(f: &Fp12<C>) -> Option<Fp12<C>> {
+    // Easy part: f^((p^6 - 1) (p^2 + 1)).
+    let f = f.conjugate().mul(&f.inverse()?);
+    let f = f.frobenius_map().frobenius_map().mul(&f);
+    // Hard part: f^((p^4 - p^2 + 1) / r).
+    Some(f.pow(C::FINAL_EXPONENT_HARD))
+}
+
+/// Computes the pairing `e(p, q)`.
+///
+/// The points must be in G1 and G2, see [`G1Affine::new`] and [`G2Affine::new`].
+pub fn pairing<C: PairingCurve>(p: &G1Affine<C>, q: &G2Affine<C>) -> Fp12<C> {
+    final_exponentiation(&multi_miller_loop(&[(*p, *q)])).unwrap_or(Fp12::ONE)
+}
+
+/// Checks whether the product of the pairings of the given pairs is one.
+///
+/// The points are not validated: they must be in G1 and G2, as checked by [`G1Affine::new`] and
+/// [`G2Affine::new`]. A point off the curve or outside the subgroup of order `r` can make the
+/// check pass for pairs whose pairings don't multiply to one, so verifiers must validate the
+/// points they are given before calling this.
+pub fn pairing_check<C: PairingCurve>(pairs: &[(G1Affine<C>, G2Affine<C>)]) -> bool {
+    final_exponentiation(&multi_miller_loop(pairs)).is_some_and(|f| f.is_one())
+}
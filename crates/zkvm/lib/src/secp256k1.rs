@@ -1,6 +1,6 @@
 use crate::{
-    syscall_secp256k1_add, syscall_secp256k1_double,
-    utils::{AffinePoint, WeierstrassAffinePoint, WeierstrassPoint},
+    syscall_secp256k1_add, syscall_secp256k1_double, syscall_secp256k1_scalar_mul,
+    utils::{AffinePoint, WeierstrassAffinePoint, WeierstrassPoint, WeierstrassScalarMul},
 };
 
 /// The number of limbs in [Secp256k1Point].
@@ -21,6 +21,17 @@ impl WeierstrassAffinePoint<N> for Secp256k1Point {
     }
 }
 
+impl WeierstrassScalarMul<N> for Secp256k1Point {
+    const ORDER: [u32; 8] = [
+        0xD0364141, 0xBFD25E8C, 0xAF48A03B, 0xBAAEDCE6, 0xFFFFFFFE, 0xFFFFFFFF, 0xFFFFFFFF,
+        0xFFFFFFFF,
+    ];
+
+    unsafe fn scalar_mul_syscall(limbs: &mut [u32; N], scalar: &[u32; 8]) {
+        syscall_secp256k1_scalar_mul(limbs, scalar);
+    }
+}
+
 impl AffinePoint<N> for Secp256k1Point {
     /// The values are taken from https://en.bitcoin.it/wiki/Secp256k1.
     const GENERATOR: [u32; N] = [
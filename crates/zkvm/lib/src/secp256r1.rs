@@ -1,6 +1,6 @@
 use crate::{
-    syscall_secp256r1_add, syscall_secp256r1_double,
-    utils::{AffinePoint, WeierstrassAffinePoint, WeierstrassPoint},
+    syscall_secp256r1_add, syscall_secp256r1_double, syscall_secp256r1_scalar_mul,
+    utils::{AffinePoint, WeierstrassAffinePoint, WeierstrassPoint, WeierstrassScalarMul},
 };
 
 /// The number of limbs in [Secp256r1Point].
@@ -21,6 +21,17 @@ impl WeierstrassAffinePoint<N> for Secp256r1Point {
     }
 }
 
+impl WeierstrassScalarMul<N> for Secp256r1Point {
+    const ORDER: [u32; 8] = [
+        0xFC632551, 0xF3B9CAC2, 0xA7179E84, 0xBCE6FAAD, 0xFFFFFFFF, 0xFFFFFFFF, 0x00000000,
+        0xFFFFFFFF,
+    ];
+
+    unsafe fn scalar_mul_syscall(limbs: &mut [u32; N], scalar: &[u32; 8]) {
+        syscall_secp256r1_scalar_mul(limbs, scalar);
+    }
+}
+
 impl AffinePoint<N> for Secp256r1Point {
     /// The values are taken from
     const GENERATOR: [u32; N] = [
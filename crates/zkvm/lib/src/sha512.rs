@@ -0,0 +1,61 @@
+use crate::{syscall_sha512_compress, syscall_sha512_extend};
+
+/// The SHA-512 block size in bytes.
+pub const BLOCK_LEN: usize = 128;
+
+/// The initial SHA-512 hash state.
+pub const SHA512_IV: [u64; 8] = [
+    0x6a09e667f3bcc908,
+    0xbb67ae8584caa73b,
+    0x3c6ef372fe94f82b,
+    0xa54ff53a5f1d36f1,
+    0x510e527fade682d1,
+    0x9b05688c2b3e6c1f,
+    0x1f83d9abfb41bd6b,
+    0x5be0cd19137e2179,
+];
+
+/// Compress the given blocks into the SHA-512 state.
+///
+/// This has the same signature as `sha2::compress512`, so a patched `sha2` crate can forward its
+/// SHA-512 backend to it.
+pub fn sha512_compress(state: &mut [u64; 8], blocks: &[[u8; BLOCK_LEN]]) {
+    let mut w = [0u64; 80];
+    for block in blocks {
+        for (word, chunk) in w.iter_mut().zip(block.chunks_exact(8)) {
+            *word = u64::from_be_bytes(chunk.try_into().unwrap());
+        }
+        unsafe {
+            syscall_sha512_extend(&mut w);
+            syscall_sha512_compress(&mut w, state);
+        }
+    }
+}
+
+/// Hash the given data with SHA-512.
+pub fn sha512(data: &[u8]) -> [u8; 64] {
+    let mut state = SHA512_IV;
+
+    let mut blocks = data.chunks_exact(BLOCK_LEN);
+    for block in &mut blocks {
+        sha512_compress(&mut state, core::slice::from_ref(block.try_into().unwrap()));
+    }
+    let remainder = blocks.remainder();
+
+    // Pad with a one bit, zeros and the 128-bit big-endian message length in bits.
+    let mut tail = [[0u8; BLOCK_LEN]; 2];
+    let tail_bytes = tail.as_flattened_mut();
+    tail_bytes[..remainder.len()].copy_from_slice(remainder);
+    tail_bytes[remainder.len()] = 0x80;
+    let num_tail_blocks = if remainder.len() < BLOCK_LEN - 16 { 1 } else { 2 };
+    let bit_len = (data.len() as u128) * 8;
+    tail_bytes[num_tail_blocks * BLOCK_LEN - 16..num_tail_blocks * BLOCK_LEN]
+        .copy_from_slice(&bit_len.to_be_bytes());
+    sha512_compress(&mut state, &tail[..num_tail_blocks]);
+
+    let mut digest = [0u8; 64];
+    for (chunk, word) in digest.chunks_exact_mut(8).zip(state) {
+        chunk.copy_from_slice(&word.to_be_bytes());
+    }
+    digest
+}
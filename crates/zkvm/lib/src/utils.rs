@@ -188,3 +188,74 @@ pub trait WeierstrassAffinePoint<const N: usize>: AffinePoint<N> {
         self.add_assign(other);
     }
 }
+
+/// A trait for Weierstrass curve points backed by a scalar multiplication precompile.
+///
+/// The precompile computes `[k]P` with a double-and-add loop of incomplete additions, which is only
+/// sound for points `P` of the prime-order subgroup and scalars `k` with `0 < k < ORDER`. Neither
+/// the executor nor the AIR can check that `P` is in the subgroup, so the precompile is only
+/// exposed through `unsafe` functions, and [`AffinePoint::mul_assign`] keeps using the add and
+/// double precompiles.
+pub trait WeierstrassScalarMul<const N: usize>: WeierstrassAffinePoint<N> {
+    /// The order of the prime-order subgroup, as little-endian words.
+    const ORDER: [u32; 8];
+
+    /// Executes the scalar multiplication precompile on the given limbs.
+    ///
+    /// # Safety
+    ///
+    /// See [`WeierstrassScalarMul::scalar_mul_assign_unchecked`].
+    unsafe fn scalar_mul_syscall(limbs: &mut [u32; N], scalar: &[u32; 8]);
+
+    /// Multiplies `self` by the given little-endian scalar with a single precompile call.
+    ///
+    /// # Safety
+    ///
+    /// `self` must be a point of the prime-order subgroup other than the point at infinity, and
+    /// `scalar` must be in `[1, ORDER)`. Otherwise the double-and-add loop of the precompile can
+    /// hit an exceptional case, for which the result is not constrained by the proof.
+    ///
+    /// Every point of the secp256k1, secp256r1 and BN254 curves is in the prime-order subgroup,
+    /// but the cofactor of BLS12-381 G1 is not one, so its points must be checked by the caller.
+    unsafe fn scalar_mul_assign_unchecked(&mut self, scalar: &[u32; 8]) {
+        debug_assert!(!self.is_infinity(), "the point must not be the point at infinity");
+        debug_assert!(
+            *scalar != [0u32; 8] && lt_words_le(scalar, &Self::ORDER),
+            "the scalar must be in [1, ORDER)"
+        );
+        Self::scalar_mul_syscall(self.limbs_mut(), scalar);
+    }
+
+    /// Computes the multi-scalar multiplication `sum_i [scalars[i]] points[i]`, with one scalar
+    /// multiplication precompile call per term. Terms with a zero scalar or the point at infinity
+    /// are skipped.
+    ///
+    /// # Safety
+    ///
+    /// The points must be in the prime-order subgroup and the scalars must be less than `ORDER`,
+    /// see [`WeierstrassScalarMul::scalar_mul_assign_unchecked`].
+    unsafe fn multi_scalar_mul_unchecked(scalars: &[[u32; 8]], points: &[Self]) -> Self {
+        assert_eq!(scalars.len(), points.len(), "mismatched number of scalars and points");
+
+        let mut res = Self::infinity();
+        for (scalar, point) in scalars.iter().zip(points.iter()) {
+            if point.is_infinity() || *scalar == [0u32; 8] {
+                continue;
+            }
+            let mut term = point.clone();
+            term.scalar_mul_assign_unchecked(scalar);
+            res.weierstrass_add_assign(&term);
+        }
+        res
+    }
+}
+
+/// Returns true if `a < b`, where both are little-endian words of the same length.
+fn lt_words_le(a: &[u32], b: &[u32]) -> bool {
+    for (x, y) in a.iter().rev().zip(b.iter().rev()) {
+        if x != y {
+            return x < y;
+        }
+    }
+    false
+}
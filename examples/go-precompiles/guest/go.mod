@@ -0,0 +1,7 @@
+module go-precompiles
+
+go 1.24.0
+
+replace github.com/ProjectZKM/Ziren/crates/go-runtime/zkvm_runtime => ../../../crates/go-runtime/zkvm_runtime
+
+require github.com/ProjectZKM/Ziren/crates/go-runtime/zkvm_runtime v0.0.0-20240817102429-2faba0888c02
@@ -0,0 +1,176 @@
+package main
+
+import (
+	"bytes"
+	"encoding/binary"
+	"encoding/hex"
+	"log"
+
+	"github.com/ProjectZKM/Ziren/crates/go-runtime/zkvm_runtime"
+)
+
+func mustDecode(s string) []byte {
+	b, err := hex.DecodeString(s)
+	if err != nil {
+		log.Fatal(err)
+	}
+	return b
+}
+
+// testEd25519 exercises ED_DECOMPRESS and ED_ADD through RFC 8032 test vectors.
+func testEd25519() {
+	vectors := []struct {
+		publicKey string
+		message   string
+		signature string
+	}{
+		{
+			"d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a",
+			"",
+			"e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e065224901555fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b",
+		},
+		{
+			"3d4017c3e843895a92b70aa74d1b7ebc9c982ccf2ec4968cc0cd55f12af4660c",
+			"72",
+			"92a009a9f0d4cab8720e820b5f642540a2b27b5416503f8fb3762223ebdb69da085ac1e43e15996e458f3613d0f11d8c387b2eaeb4302aeeb00d291612bb0c00",
+		},
+	}
+
+	for i, v := range vectors {
+		var publicKey [32]byte
+		var signature [64]byte
+		copy(publicKey[:], mustDecode(v.publicKey))
+		copy(signature[:], mustDecode(v.signature))
+		message := mustDecode(v.message)
+
+		if !zkvm_runtime.Ed25519Verify(publicKey, message, signature) {
+			log.Fatalf("ed25519 vector %d failed to verify", i)
+		}
+
+		tampered := append(message, 0x01)
+		if zkvm_runtime.Ed25519Verify(publicKey, tampered, signature) {
+			log.Fatalf("ed25519 vector %d verified a tampered message", i)
+		}
+	}
+}
+
+// testEd25519InvalidEncodings checks that malformed encodings of A and R are rejected instead of
+// aborting in ED_DECOMPRESS.
+func testEd25519InvalidEncodings() {
+	invalid := []string{
+		// y = p is not reduced.
+		"edffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f",
+		// y = 2 has no x on the curve.
+		"0200000000000000000000000000000000000000000000000000000000000000",
+		// y = 1 gives x = 0, which cannot have its sign bit set.
+		"0100000000000000000000000000000000000000000000000000000000000080",
+	}
+
+	var validPublicKey [32]byte
+	var validSignature [64]byte
+	copy(validPublicKey[:], mustDecode("d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a"))
+	copy(validSignature[:], mustDecode("e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e065224901555fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b"))
+
+	for i, enc := range invalid {
+		var publicKey [32]byte
+		copy(publicKey[:], mustDecode(enc))
+		if zkvm_runtime.Ed25519Verify(publicKey, nil, validSignature) {
+			log.Fatalf("ed25519 invalid public key %d accepted", i)
+		}
+
+		signature := validSignature
+		copy(signature[:32], publicKey[:])
+		if zkvm_runtime.Ed25519Verify(validPublicKey, nil, signature) {
+			log.Fatalf("ed25519 invalid signature R %d accepted", i)
+		}
+	}
+}
+
+// testU256xU2048Mul exercises U256XU2048_MUL with 2 * (2^2048 - 1) = 2^2049 - 2.
+func testU256xU2048Mul() {
+	a := [8]uint32{2}
+	var b [64]uint32
+	for i := range b {
+		b[i] = 0xffffffff
+	}
+
+	lo, hi := zkvm_runtime.U256xU2048Mul(&a, &b)
+	if lo[0] != 0xfffffffe || hi[0] != 1 {
+		log.Fatalf("u256xu2048 mul: unexpected low limb %x or high limb %x", lo[0], hi[0])
+	}
+	for i := 1; i < 64; i++ {
+		if lo[i] != 0xffffffff {
+			log.Fatalf("u256xu2048 mul: unexpected low limb %d: %x", i, lo[i])
+		}
+	}
+	for i := 1; i < 8; i++ {
+		if hi[i] != 0 {
+			log.Fatalf("u256xu2048 mul: unexpected high limb %d: %x", i, hi[i])
+		}
+	}
+}
+
+// testBooleanCircuitGarble exercises BOOLEAN_CIRCUIT_GARBLE with one AND and one OR gate.
+func testBooleanCircuitGarble() {
+	delta := [4]uint32{0x01234567, 0x89abcdef, 0xfedcba98, 0x76543210}
+
+	gatesInfo := make([]byte, 16+2*zkvm_runtime.GarbleGateInfoBytes)
+	for i, d := range delta {
+		binary.LittleEndian.PutUint32(gatesInfo[i*4:], d)
+	}
+	for gate := uint32(0); gate < 2; gate++ {
+		words := make([]uint32, 17)
+		words[0] = gate // 0 is an AND gate, 1 is an OR gate.
+		for i := uint32(0); i < 4; i++ {
+			h0, h1, labelB := 0x1000*gate+i, 0x2000*gate+3*i, 0x3000*gate+7*i
+			words[1+i], words[5+i], words[9+i] = h0, h1, labelB
+			words[13+i] = h0 ^ h1 ^ labelB
+			if gate == 1 {
+				words[13+i] ^= delta[i]
+			}
+		}
+		offset := 16 + int(gate)*zkvm_runtime.GarbleGateInfoBytes
+		for i, w := range words {
+			binary.LittleEndian.PutUint32(gatesInfo[offset+i*4:], w)
+		}
+	}
+
+	if !zkvm_runtime.BooleanCircuitGarble(gatesInfo) {
+		log.Fatal("boolean circuit garble: valid gates rejected")
+	}
+
+	// Flip a bit of the last ciphertext.
+	gatesInfo[len(gatesInfo)-1] ^= 0x80
+	if zkvm_runtime.BooleanCircuitGarble(gatesInfo) {
+		log.Fatal("boolean circuit garble: invalid gates accepted")
+	}
+}
+
+func main() {
+	testEd25519()
+	testEd25519InvalidEncodings()
+	testU256xU2048Mul()
+	testBooleanCircuitGarble()
+
+	// POSEIDON2_PERMUTE: hash the same input as the Rust poseidon2 example.
+	poseidon2 := zkvm_runtime.Poseidon2Hash(bytes.Repeat([]byte{1}, 1000))
+
+	// BLS12381_DECOMPRESS: the compressed BLS12-381 G1 generator.
+	var compressed [48]byte
+	copy(compressed[:], mustDecode("97f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb"))
+	g1 := zkvm_runtime.Bls12381Decompress(compressed)
+
+	// FD_ECRECOVER_HOOK: recover the signer of a secp256k1 signature.
+	var hash [32]byte
+	var signature [65]byte
+	copy(hash[:], mustDecode("08e63953018d614829147466ab08ebe60f18d2fd5ac0a47c34753c00ca0bb168"))
+	copy(signature[:], mustDecode("a81a8ce1e067155ecb6abf649698204dbd3b668bb08e880ff0d120ee356bfd2d455586eea32ad1b741ecbf9224943342a770bb8e8e0308a8cafdb8881ff1ae8f00"))
+	publicKey, ok := zkvm_runtime.Ecrecover(hash, signature)
+	if !ok {
+		log.Fatal("ecrecover failed")
+	}
+
+	zkvm_runtime.Commit[[32]byte](poseidon2)
+	zkvm_runtime.Commit[[96]byte](g1)
+	zkvm_runtime.Commit[[65]byte](publicKey)
+}
@@ -0,0 +1,39 @@
+use zkm_sdk::{utils, ProverClient, ZKMStdin};
+
+/// The ELF we want to execute inside the zkVM.
+const ELF: &[u8] = include_bytes!("../../guest/go-precompiles");
+
+/// The Poseidon2 hash of 1000 bytes of `1`, as computed by the Rust poseidon2 example.
+const POSEIDON2_HASH: &str = "ae45b14fe23b9f584c76c67d4d9ef6635a27b553a7114427584cc87ba8919866";
+
+/// The uncompressed BLS12-381 G1 generator.
+const BLS12381_G1_GENERATOR: &str = "17f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb08b3f481e3aaa0f1a09e30ed741d8ae4fcf5e095d5d00af600db18cb2c04b3edd03cc744a2888ae40caa232946c5e7e1";
+
+/// The uncompressed public key that signed the guest's secp256k1 test vector.
+const SECP256K1_PUBLIC_KEY: &str = "04a16bbddc8a19c2e1bd1002de8b705c8824273993d0cd4d23196c5a34b00890b8b966ab3f229eeaa8e8914b58ac091e11b85f84ba6d9e4e4c3ade59501575692b";
+
+fn main() {
+    utils::setup_logger();
+
+    let stdin = ZKMStdin::new();
+    let client = ProverClient::new();
+
+    // Execute the guest, which checks every precompile binding of the Go runtime and commits the
+    // outputs that depend on the executor.
+    let (mut public_values, report) = client.execute(ELF, &stdin).run().unwrap();
+    println!("executed program with {} cycles", report.total_instruction_count());
+
+    let mut poseidon2 = [0u8; 32];
+    public_values.read_slice(&mut poseidon2);
+    assert_eq!(hex::encode(poseidon2), POSEIDON2_HASH);
+
+    let mut g1 = [0u8; 96];
+    public_values.read_slice(&mut g1);
+    assert_eq!(hex::encode(g1), BLS12381_G1_GENERATOR);
+
+    let mut public_key = [0u8; 65];
+    public_values.read_slice(&mut public_key);
+    assert_eq!(hex::encode(public_key), SECP256K1_PUBLIC_KEY);
+
+    println!("successfully executed every Go runtime precompile!")
+}
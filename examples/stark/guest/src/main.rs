@@ -0,0 +1,25 @@
+//! A program that verifies a compressed STARK proof in ZKM.
+
+#![no_main]
+zkm_zkvm::entrypoint!(main);
+
+use zkm_verifier::StarkVerifier;
+
+pub fn main() {
+    // Read the proof, public values, and vkey from the input stream.
+    let proof = zkm_zkvm::io::read_vec();
+    let zkm_public_values = zkm_zkvm::io::read_vec();
+    let zkm_vk = zkm_zkvm::io::read_vec();
+
+    // Verify the compressed proof.
+    println!("cycle-tracker-start: verify");
+    let result = StarkVerifier::verify(&proof, &zkm_public_values, &zkm_vk);
+    println!("cycle-tracker-end: verify");
+
+    if let Err(e) = result {
+        panic!("Error verifying proof: {:?}", e);
+    }
+
+    // Commit the verified public values, so that the host can check which proof was verified.
+    zkm_zkvm::io::commit_slice(&zkm_public_values);
+}
@@ -0,0 +1,4 @@
+fn main() {
+    zkm_build::build_program("../guest");
+    zkm_build::build_program("../../fibonacci/guest");
+}
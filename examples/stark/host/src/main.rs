@@ -0,0 +1,72 @@
+//! A script that generates a compressed proof for the Fibonacci program, and verifies the
+//! compressed proof in ZKM.
+
+use zkm_sdk::{include_elf, utils, ProverClient, ZKMStdin};
+
+/// The ELF for the STARK verifier program.
+const STARK_ELF: &[u8] = include_elf!("stark-verifier");
+
+/// The ELF for the Fibonacci program.
+const FIBONACCI_ELF: &[u8] = include_elf!("fibonacci");
+
+/// Generates the proof, public values, and vkey for the Fibonacci program in a format that can be
+/// read by `zkm-verifier`.
+///
+/// Returns the proof bytes, public values, and vkey bytes.
+fn generate_fibonacci_proof() -> (Vec<u8>, Vec<u8>, Vec<u8>) {
+    // Create an input stream and write '20' to it.
+    let n = 20u32;
+
+    // The input stream that the program will read from using `zkm_zkvm::io::read`. Note that the
+    // types of the elements in the input stream must match the types being read in the program.
+    let mut stdin = ZKMStdin::new();
+    stdin.write(&n);
+
+    // Create a `ProverClient`.
+    let client = ProverClient::new();
+
+    // Generate the compressed proof for the Fibonacci program.
+    let (pk, vk) = client.setup(FIBONACCI_ELF);
+    let proof = client.prove(&pk, stdin).compressed().run().unwrap();
+    let vk = bincode::serialize(&vk).unwrap();
+    (proof.bytes(), proof.public_values.to_vec(), vk)
+}
+
+fn main() {
+    // Setup logging.
+    utils::setup_logger();
+
+    // Generate the Fibonacci proof, public values, and vkey.
+    let (fibonacci_proof, fibonacci_public_values, vk) = generate_fibonacci_proof();
+
+    // Write the proof, public values, and vkey to the input stream.
+    let mut stdin = ZKMStdin::new();
+    stdin.write_vec(fibonacci_proof.clone());
+    stdin.write_vec(fibonacci_public_values.clone());
+    stdin.write_vec(vk.clone());
+
+    // Create a `ProverClient`.
+    let client = ProverClient::new();
+
+    // Execute the program using the `ProverClient.execute` method, without generating a proof.
+    let (public_values, report) = client.execute(STARK_ELF, &stdin).run().unwrap();
+    println!("executed stark program with {} cycles", report.total_instruction_count());
+    println!("{}", report);
+
+    // The guest commits the public values of the proof it verified.
+    assert_eq!(public_values.as_slice(), fibonacci_public_values.as_slice());
+
+    // The guest must reject the proof for other public values.
+    let mut tampered_public_values = fibonacci_public_values;
+    tampered_public_values[0] ^= 1;
+    let mut stdin = ZKMStdin::new();
+    stdin.write_vec(fibonacci_proof);
+    stdin.write_vec(tampered_public_values);
+    stdin.write_vec(vk);
+    assert!(
+        client.execute(STARK_ELF, &stdin).run().is_err(),
+        "verified the proof with tampered public values"
+    );
+
+    println!("successfully verified the compressed proof in the zkVM!");
+}